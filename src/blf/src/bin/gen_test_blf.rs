@@ -1,6 +1,6 @@
 use byteorder::{LittleEndian, WriteBytesExt};
 use std::fs::File;
-use std::io::{Cursor, Write};
+use std::io::Write;
 
 // --- Minimal structures copied from blf crate ---
 
@@ -26,8 +26,8 @@ pub struct FileStatistics {
     pub application_minor: u8,
     pub file_size: u64,
     pub uncompressed_file_size: u64,
-    pub object_count: u64,
-    pub application_build: u8,
+    pub object_count: u32,
+    pub application_build: u32,
     pub measurement_start_time: SystemTime,
     pub last_object_time: SystemTime,
 }
@@ -99,33 +99,36 @@ pub fn serialize_file_statistics(stats: &FileStatistics) -> Vec<u8> {
         .write_u64::<LittleEndian>(stats.uncompressed_file_size)
         .unwrap();
     writer
-        .write_u64::<LittleEndian>(stats.object_count)
+        .write_u32::<LittleEndian>(stats.object_count)
+        .unwrap();
+    writer
+        .write_u32::<LittleEndian>(stats.application_build)
         .unwrap();
-    writer.write_u8(stats.application_build).unwrap();
     write_system_time(&stats.measurement_start_time, &mut writer);
     write_system_time(&stats.last_object_time, &mut writer);
-    writer.write_u32::<LittleEndian>(0).unwrap(); // apiNumber
-    writer.write_all(&[0; 32]).unwrap(); // reserved
-    writer.write_all(&[0; 96]).unwrap(); // restOfHeader
+    // The rest of the header is reserved
+    writer.resize(stats.statistics_size as usize, 0);
     writer
 }
 
-pub fn serialize_object_header(header: &ObjectHeader, writer: &mut impl Write) {
-    writer
-        .write_u32::<LittleEndian>(header.base.signature)
-        .unwrap();
+pub fn serialize_object_header_base(header: &ObjectHeaderBase, writer: &mut impl Write) {
+    writer.write_u32::<LittleEndian>(header.signature).unwrap();
     writer
-        .write_u16::<LittleEndian>(header.base.header_size)
+        .write_u16::<LittleEndian>(header.header_size)
         .unwrap();
     writer
-        .write_u16::<LittleEndian>(header.base.header_version)
+        .write_u16::<LittleEndian>(header.header_version)
         .unwrap();
     writer
-        .write_u32::<LittleEndian>(header.base.object_size)
+        .write_u32::<LittleEndian>(header.object_size)
         .unwrap();
     writer
-        .write_u32::<LittleEndian>(header.base.object_type as u32)
+        .write_u32::<LittleEndian>(header.object_type as u32)
         .unwrap();
+}
+
+pub fn serialize_object_header(header: &ObjectHeader, writer: &mut impl Write) {
+    serialize_object_header_base(&header.base, writer);
     writer
         .write_u32::<LittleEndian>(header.object_flags)
         .unwrap();
@@ -150,7 +153,7 @@ pub fn serialize_can_message(msg: &CanMessage) -> Vec<u8> {
 pub fn add_padding(data: &mut Vec<u8>) {
     let padding_len = (4 - (data.len() % 4)) % 4;
     if padding_len > 0 {
-        data.extend_from_slice(&vec![0; padding_len]);
+        data.extend_from_slice(&[0; 3][..padding_len]);
     }
 }
 
@@ -272,26 +275,17 @@ fn main() {
         inner_data.extend(m_bytes);
     }
 
-    // 2. Create LogContainer
+    // 2. Create LogContainer (containers only have the base header)
     let mut container_writer = Vec::new();
-    let container_size = (32 + 16 + inner_data.len()) as u32;
-    let container_header = ObjectHeader {
-        base: ObjectHeaderBase {
-            signature: 0x4A424F4C,
-            header_size: 32,
-            header_version: 1,
-            object_size: container_size,
-            object_type: ObjectType::LogContainer,
-        },
-        object_flags: 0,
-        client_index: 0,
-        object_version: 0,
-        object_time_stamp: 0,
-        original_time_stamp: None,
-        time_stamp_status: None,
-        reserved: 0,
+    let container_size = (16 + 16 + inner_data.len()) as u32;
+    let container_header = ObjectHeaderBase {
+        signature: 0x4A424F4C,
+        header_size: 16,
+        header_version: 1,
+        object_size: container_size,
+        object_type: ObjectType::LogContainer,
     };
-    serialize_object_header(&container_header, &mut container_writer);
+    serialize_object_header_base(&container_header, &mut container_writer);
     container_writer.write_u16::<LittleEndian>(0).unwrap(); // compression_method: 0
     container_writer.write_u16::<LittleEndian>(0).unwrap(); // res1
     container_writer.write_u32::<LittleEndian>(0).unwrap(); // res2
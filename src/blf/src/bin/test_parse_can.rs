@@ -7,7 +7,8 @@ use blf::{BlfParser, read_blf_from_file};
 fn main() {
     println!("=== Testing can.blf File Parsing ===\n");
 
-    // Enable debug mode by creating parser with_debug    let parser = BlfParser::with_debug();
+    // Enable debug mode by creating parser with_debug
+    let parser = BlfParser::with_debug();
 
     // Read the file
     println!("Reading can.blf...\n");
@@ -47,7 +48,7 @@ fn main() {
                 println!("\nLet's try parsing manually...\n");
 
                 // Try to read the file again and parse manually
-                use blf::{ObjectHeader, ObjectType};
+                use blf::{ObjectHeaderBase, ObjectType};
                 use std::fs;
                 use std::io::Cursor;
 
@@ -60,12 +61,11 @@ fn main() {
 
                         // Try to read first object
                         println!("Attempting to read first object at offset 144...");
-                        match ObjectHeader::read(&mut cursor) {
+                        match ObjectHeaderBase::read(&mut cursor) {
                             Ok(header) => {
                                 println!("✓ Read object header:");
                                 println!("  Type: {:?}", header.object_type);
-                                println!("  Size: {}", header.object_size);
-                                println!("  Timestamp: {}\n", header.object_time_stamp);
+                                println!("  Size: {}\n", header.object_size);
 
                                 if header.object_type == ObjectType::LogContainer {
                                     println!("First object is a LogContainer");
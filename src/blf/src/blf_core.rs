@@ -1,14 +1,18 @@
 //! Core BLF structures and error handling.
 
-// byteorder imports removed - not used in blf_core.rs
-use std::error::Error;
-use std::fmt;
-use std::io;
+#[cfg(feature = "std")]
+use crate::ByteReader;
+use crate::CodecError;
+use core::error::Error;
+use core::fmt;
+#[cfg(feature = "std")]
+use std::io::{self, Cursor};
 
 /// Represents a parsing error that can occur while processing a BLF file.
 #[derive(Debug)]
 pub enum BlfParseError {
     /// An I/O error occurred while reading the data.
+    #[cfg(feature = "std")]
     IoError(io::Error),
     /// The file does not start with the expected "LOGG" magic string.
     InvalidFileMagic,
@@ -27,6 +31,7 @@ pub enum BlfParseError {
 impl fmt::Display for BlfParseError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
+            #[cfg(feature = "std")]
             BlfParseError::IoError(e) => write!(f, "I/O error: {}", e),
             BlfParseError::InvalidFileMagic => {
                 write!(
@@ -65,12 +70,14 @@ impl fmt::Display for BlfParseError {
 impl Error for BlfParseError {
     fn source(&self) -> Option<&(dyn Error + 'static)> {
         match self {
+            #[cfg(feature = "std")]
             BlfParseError::IoError(e) => Some(e),
             _ => None,
         }
     }
 }
 
+#[cfg(feature = "std")]
 impl From<io::Error> for BlfParseError {
     fn from(err: io::Error) -> Self {
         if err.kind() == io::ErrorKind::UnexpectedEof {
@@ -81,6 +88,30 @@ impl From<io::Error> for BlfParseError {
     }
 }
 
+impl From<CodecError> for BlfParseError {
+    fn from(err: CodecError) -> Self {
+        match err {
+            CodecError::UnexpectedEof => BlfParseError::UnexpectedEof,
+            CodecError::InvalidSignature => BlfParseError::InvalidContainerMagic,
+            CodecError::UnknownHeaderVersion(v) => BlfParseError::UnknownHeaderVersion(v),
+            CodecError::InvalidFileSignature => BlfParseError::InvalidFileMagic,
+            CodecError::UnsupportedCompression(c) => BlfParseError::UnsupportedCompression(c),
+        }
+    }
+}
+
+/// Runs a [`ByteReader`] decoder at the cursor and moves the cursor past what it read.
+#[cfg(feature = "std")]
+pub(crate) fn decode_at<'a, T>(
+    cursor: &mut Cursor<&'a [u8]>,
+    decode: impl FnOnce(&mut ByteReader<'a>) -> Result<T, CodecError>,
+) -> BlfParseResult<T> {
+    let mut reader = ByteReader::at(cursor.get_ref(), cursor.position() as usize);
+    let value = decode(&mut reader)?;
+    cursor.set_position(reader.position() as u64);
+    Ok(value)
+}
+
 /// A specialized `Result` type for BLF parsing operations.
 pub type BlfParseResult<T> = Result<T, BlfParseError>;
 
@@ -91,57 +122,110 @@ pub enum ObjectType {
     /// Unknown object
     #[default]
     Unknown = 0,
+    /// CAN frame (`CAN_MESSAGE`)
     CanMessage = 1,
+    /// CAN error frame (`CAN_ERROR`)
     CanError = 2,
+    /// CAN overload frame (`CAN_OVERLOAD`)
     CanOverload = 3,
+    /// CAN driver statistics (`CAN_STATISTIC`)
     CanStatistic = 4,
+    /// Application trigger (`APP_TRIGGER`)
     AppTrigger = 5,
+    /// Integer environment variable (`ENV_INTEGER`)
     EnvInteger = 6,
+    /// Floating-point environment variable (`ENV_DOUBLE`)
     EnvDouble = 7,
+    /// String environment variable (`ENV_STRING`)
     EnvString = 8,
+    /// Container holding other objects, possibly compressed (`LOG_CONTAINER`)
     LogContainer = 10,
+    /// LIN frame (`LIN_MESSAGE`)
     LinMessage = 11,
+    /// LIN checksum error (`LIN_CRC_ERROR`)
     LinCrcError = 12,
+    /// LIN data length information (`LIN_DLC_INFO`)
     LinDlcInfo = 13,
+    /// LIN receive error (`LIN_RCV_ERROR`)
     LinReceiveError = 14,
+    /// LIN frame without response (`LIN_SND_ERROR`)
     LinSendError = 15,
+    /// LIN slave timeout (`LIN_SLV_TIMEOUT`)
     LinSlaveTimeout = 16,
+    /// LIN scheduler mode change (`LIN_SCHED_MODCH`)
     LinSchedulerModeChange = 17,
+    /// LIN synchronisation error (`LIN_SYN_ERROR`)
     LinSyncError = 18,
+    /// LIN baud rate detected (`LIN_BAUDRATE`)
     LinBaudrate = 19,
+    /// LIN sleep mode event (`LIN_SLEEP`)
     LinSleep = 20,
+    /// LIN wake-up event (`LIN_WAKEUP`)
     LinWakeup = 21,
+    /// MOST spy message (`MOST_SPY`)
     MostSpy = 22,
+    /// MOST control message (`MOST_CTRL`)
     MostCtrl = 23,
+    /// MOST light lock state (`MOST_LIGHTLOCK`)
     MostLightLock = 24,
+    /// MOST statistics (`MOST_STATISTIC`)
     MostStatistic = 25,
+    /// FlexRay data frame, old format (`FLEXRAY_DATA`)
     FlexRayData = 29,
+    /// FlexRay sync frame, old format (`FLEXRAY_SYNC`)
     FlexRaySync = 30,
+    /// CAN driver error (`CAN_DRIVER_ERROR`)
     CanDriverError = 31,
+    /// MOST packet (`MOST_PKT`)
     MostPkt = 32,
+    /// MOST packet, second version (`MOST_PKT2`)
     MostPkt2 = 33,
+    /// MOST hardware mode (`MOST_HWMODE`)
     MostHwMode = 34,
+    /// MOST register data (`MOST_REG`)
     MostReg = 35,
+    /// MOST general register data (`MOST_GENREG`)
     MostGenReg = 36,
+    /// MOST network state (`MOST_NETSTATE`)
     MostNetState = 37,
+    /// MOST data lost (`MOST_DATALOST`)
     MostDataLost = 38,
+    /// MOST trigger (`MOST_TRIGGER`)
     MostTrigger = 39,
+    /// FlexRay V6 message (`FLEXRAY_MESSAGE`)
     FlexRayMessage = 41,
+    /// LIN frame, second version (`LIN_MESSAGE2`)
     LinMessage2 = 57,
+    /// Ethernet frame (`ETHERNET_FRAME`)
     EthernetFrame = 71,
+    /// System variable (`SYS_VARIABLE`)
     SystemVariable = 72,
+    /// CAN frame, second version (`CAN_MESSAGE2`)
     CanMessage2 = 86,
+    /// Comment attached to the log (`EVENT_COMMENT`)
     EventComment = 92,
+    /// Marker set in the measurement (`GLOBAL_MARKER`)
     GlobalMarker = 96,
+    /// CAN FD frame (`CAN_FD_MESSAGE`)
     CanFdMessage = 100,
+    /// CAN FD frame, 64-byte version (`CAN_FD_MESSAGE_64`)
     CanFdMessage64 = 101,
-    FlexRayV6StartCycleEvent = 40, // Added
-    FlexRayStatusEvent = 45,       // Added
-    FlexRayVFrError = 47,          // Added
-    FlexRayVFrStatus = 48,         // Added
-    FlexRayVFrStartCycle = 49,     // Added
-    FlexRayVFrReceiveMsg = 50,     // Added
-    FlexRayVFrReceiveMsgEx = 66,   // Added
+    /// FlexRay V6 start of cycle (`FLEXRAY_CYCLE`)
+    FlexRayV6StartCycleEvent = 40,
+    /// FlexRay status (`FLEXRAY_STATUS`)
+    FlexRayStatusEvent = 45,
+    /// GPS position (`GPS_EVENT`)
+    GpsEvent = 46,
+    /// FlexRay error (`FR_ERROR`)
+    FlexRayVFrError = 47,
+    /// FlexRay controller status (`FR_STATUS`)
+    FlexRayVFrStatus = 48,
+    /// FlexRay start of cycle (`FR_STARTCYCLE`)
+    FlexRayVFrStartCycle = 49,
+    /// FlexRay received frame (`FR_RCVMESSAGE`)
+    FlexRayVFrReceiveMsg = 50,
+    /// FlexRay received frame, extended (`FR_RCVMESSAGE_EX`)
+    FlexRayVFrReceiveMsgEx = 66,
 }
 
 impl From<u32> for ObjectType {
@@ -185,6 +269,7 @@ impl From<u32> for ObjectType {
             40 => ObjectType::FlexRayV6StartCycleEvent,
             41 => ObjectType::FlexRayMessage,
             45 => ObjectType::FlexRayStatusEvent,
+            46 => ObjectType::GpsEvent,
             47 => ObjectType::FlexRayVFrError,
             48 => ObjectType::FlexRayVFrStatus,
             49 => ObjectType::FlexRayVFrStartCycle,
@@ -0,0 +1,82 @@
+//! Application triggers, comments and markers.
+
+use super::{ByteReader, CodecError};
+use crate::{AppTrigger, EventComment, GlobalMarker, ObjectHeader};
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+/// Reads `len` bytes of text; invalid UTF-8 is replaced.
+fn read_text(reader: &mut ByteReader, len: usize) -> Result<String, CodecError> {
+    Ok(String::from_utf8_lossy(reader.read_bytes(len)?).to_string())
+}
+
+impl AppTrigger {
+    /// Decodes the body following `header`.
+    pub fn decode(reader: &mut ByteReader, header: &ObjectHeader) -> Result<Self, CodecError> {
+        Ok(Self {
+            pre_trigger_time: reader.read_u64()?,
+            post_trigger_time: reader.read_u64()?,
+            channel: reader.read_u16()?,
+            flags: reader.read_u16()?,
+            timestamp: header.object_time_stamp,
+        })
+    }
+}
+
+impl EventComment {
+    /// Decodes the body following `header`.
+    pub fn decode(reader: &mut ByteReader, header: &ObjectHeader) -> Result<Self, CodecError> {
+        let commented_event_type = reader.read_u32()?;
+        let text_length = reader.read_u32()? as usize;
+        reader.skip(8)?; // reserved
+        Ok(Self {
+            commented_event_type,
+            text: read_text(reader, text_length)?,
+            timestamp: header.object_time_stamp,
+        })
+    }
+}
+
+impl GlobalMarker {
+    /// Decodes the body following `header`.
+    pub fn decode(reader: &mut ByteReader, header: &ObjectHeader) -> Result<Self, CodecError> {
+        let commented_event_type = reader.read_u32()?;
+        let foreground_color = reader.read_u32()?;
+        let background_color = reader.read_u32()?;
+        let is_relocatable = reader.read_u8()?;
+        reader.skip(1 + 2)?; // reserved
+        let group_name_length = reader.read_u32()? as usize;
+        let marker_name_length = reader.read_u32()? as usize;
+        let description_length = reader.read_u32()? as usize;
+        reader.skip(4 + 8)?; // reserved
+        Ok(Self {
+            commented_event_type,
+            foreground_color,
+            background_color,
+            is_relocatable,
+            group_name: read_text(reader, group_name_length)?,
+            marker_name: read_text(reader, marker_name_length)?,
+            description: read_text(reader, description_length)?,
+            timestamp: header.object_time_stamp,
+        })
+    }
+
+    /// Appends the body (everything after the header) to `out`.
+    pub fn encode_body(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.commented_event_type.to_le_bytes());
+        out.extend_from_slice(&self.foreground_color.to_le_bytes());
+        out.extend_from_slice(&self.background_color.to_le_bytes());
+        out.push(self.is_relocatable);
+        out.push(0); // reserved
+        out.extend_from_slice(&0u16.to_le_bytes()); // reserved
+        let texts = [&self.group_name, &self.marker_name, &self.description];
+        for text in texts {
+            out.extend_from_slice(&(text.len() as u32).to_le_bytes());
+        }
+        out.extend_from_slice(&0u32.to_le_bytes()); // reserved
+        out.extend_from_slice(&0u64.to_le_bytes()); // reserved
+        for text in texts {
+            out.extend_from_slice(text.as_bytes());
+        }
+    }
+}
@@ -0,0 +1,305 @@
+//! CAN frames, errors and driver statistics.
+
+use super::{ByteReader, CodecError};
+use crate::{
+    CanDriverError, CanDriverStatistic, CanErrorFrame, CanFdExtFrameData, CanFdMessage,
+    CanFdMessage64, CanMessage, CanMessage2, CanOverloadFrame, ObjectHeader,
+};
+use alloc::vec::Vec;
+
+/// Size of the fields of a `CanFdMessage64` between the header and the data.
+pub(crate) const CAN_FD_64_FIELDS_SIZE: usize = 40;
+
+/// Some BLF variants put 16 bytes of metadata (cycle time and reserved
+/// fields) before the frame; true if the frame looks like it starts there.
+///
+/// `dlc_offset` is where the DLC sits in the frame and `max_dlc` its largest
+/// valid value; the channel is read with `channel_width` bytes at offset 0.
+fn has_leading_metadata(data: &[u8], channel_width: usize, dlc_offset: usize, max_dlc: u8) -> bool {
+    let fields = |offset: usize| {
+        let mut channel = [0; 2];
+        channel[..channel_width].copy_from_slice(&data[offset..offset + channel_width]);
+        let id = [
+            data[offset + 4],
+            data[offset + 5],
+            data[offset + 6],
+            data[offset + 7],
+        ];
+        (
+            u16::from_le_bytes(channel),
+            data[offset + dlc_offset],
+            u32::from_le_bytes(id),
+        )
+    };
+    let (channel_at_0, dlc_at_0, id_at_0) = fields(0);
+    let (channel_at_16, dlc_at_16, id_at_16) = fields(16);
+
+    // Offset 0 looks invalid (all zeros or suspicious) AND offset 16 looks valid
+    let offset_0_invalid = dlc_at_0 == 0 && id_at_0 == 0 && channel_at_0 <= 1;
+    let offset_16_valid =
+        (channel_at_16 > 0 || dlc_at_16 > 0 || id_at_16 > 0) && dlc_at_16 <= max_dlc;
+    offset_0_invalid && offset_16_valid
+}
+
+impl CanMessage {
+    /// Decodes the body following `header`.
+    pub fn decode(reader: &mut ByteReader, header: &ObjectHeader) -> Result<Self, CodecError> {
+        let remaining = reader.remaining();
+        if remaining.len() >= 24 && has_leading_metadata(remaining, 2, 3, 8) {
+            reader.skip(16)?;
+        }
+        Ok(Self {
+            header: header.clone(),
+            channel: reader.read_u16()?,
+            flags: reader.read_u8()?,
+            dlc: reader.read_u8()?,
+            id: reader.read_u32()?,
+            data: reader.read_array()?,
+        })
+    }
+
+    /// Appends the body (everything after the header) to `out`.
+    pub fn encode_body(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.channel.to_le_bytes());
+        out.push(self.flags);
+        out.push(self.dlc);
+        out.extend_from_slice(&self.id.to_le_bytes());
+        out.extend_from_slice(&self.data);
+    }
+}
+
+impl CanMessage2 {
+    /// Decodes the body following `header`, carrying `data_size` payload bytes.
+    pub fn decode(
+        reader: &mut ByteReader,
+        header: &ObjectHeader,
+        data_size: usize,
+    ) -> Result<Self, CodecError> {
+        Ok(Self {
+            header: header.clone(),
+            channel: reader.read_u16()?,
+            flags: reader.read_u8()?,
+            dlc: reader.read_u8()?,
+            id: reader.read_u32()?,
+            data: reader.read_bytes(data_size)?.to_vec(),
+            frame_length: reader.read_u32()?,
+            bit_count: reader.read_u8()?,
+            reserved1: reader.read_u8()?,
+            reserved2: reader.read_u16()?,
+        })
+    }
+
+    /// Appends the body (everything after the header) to `out`.
+    pub fn encode_body(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.channel.to_le_bytes());
+        out.push(self.flags);
+        out.push(self.dlc);
+        out.extend_from_slice(&self.id.to_le_bytes());
+        out.extend_from_slice(&self.data);
+        out.extend_from_slice(&self.frame_length.to_le_bytes());
+        out.push(self.bit_count);
+        out.push(self.reserved1);
+        out.extend_from_slice(&self.reserved2.to_le_bytes());
+    }
+}
+
+impl CanFdMessage {
+    /// Decodes the body following `header`.
+    pub fn decode(reader: &mut ByteReader, header: &ObjectHeader) -> Result<Self, CodecError> {
+        Ok(Self {
+            header: header.clone(),
+            channel: reader.read_u16()?,
+            flags: reader.read_u8()?,
+            dlc: reader.read_u8()?,
+            id: reader.read_u32()?,
+            frame_length: reader.read_u32()?,
+            arb_bit_count: reader.read_u8()?,
+            can_fd_flags: reader.read_u8()?,
+            valid_data_bytes: reader.read_u8()?,
+            reserved1: reader.read_u8()?,
+            reserved2: reader.read_u32()?,
+            data: reader.read_array()?,
+            reserved3: reader.read_u32()?,
+        })
+    }
+
+    /// Appends the body (everything after the header) to `out`.
+    pub fn encode_body(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.channel.to_le_bytes());
+        out.push(self.flags);
+        out.push(self.dlc);
+        out.extend_from_slice(&self.id.to_le_bytes());
+        out.extend_from_slice(&self.frame_length.to_le_bytes());
+        out.push(self.arb_bit_count);
+        out.push(self.can_fd_flags);
+        out.push(self.valid_data_bytes);
+        out.push(self.reserved1);
+        out.extend_from_slice(&self.reserved2.to_le_bytes());
+        out.extend_from_slice(&self.data);
+        out.extend_from_slice(&self.reserved3.to_le_bytes());
+    }
+}
+
+impl CanFdExtFrameData {
+    /// Decodes `size` bytes of extended frame data.
+    pub fn decode(reader: &mut ByteReader, size: usize) -> Result<Self, CodecError> {
+        Ok(Self {
+            btr_ext_arb: reader.read_u32()?,
+            btr_ext_data: reader.read_u32()?,
+            reserved: reader.read_bytes(size.saturating_sub(8))?.to_vec(),
+        })
+    }
+}
+
+impl CanFdMessage64 {
+    /// Decodes the body following `header`.
+    pub fn decode(reader: &mut ByteReader, header: &ObjectHeader) -> Result<Self, CodecError> {
+        let remaining = reader.remaining();
+        if remaining.len() >= 32 && has_leading_metadata(remaining, 1, 1, 15) {
+            reader.skip(16)?;
+        }
+
+        let mut msg = Self {
+            header: header.clone(),
+            channel: reader.read_u8()?,
+            dlc: reader.read_u8()?,
+            valid_data_bytes: reader.read_u8()?,
+            tx_count: reader.read_u8()?,
+            id: reader.read_u32()?,
+            frame_length: reader.read_u32()?,
+            flags: reader.read_u32()?,
+            btr_cfg_arb: reader.read_u32()?,
+            btr_cfg_data: reader.read_u32()?,
+            time_offset_brs_ns: reader.read_u32()?,
+            time_offset_crc_del_ns: reader.read_u32()?,
+            bit_count: reader.read_u16()?,
+            dir: reader.read_u8()?,
+            ext_data_offset: reader.read_u8()?,
+            crc: reader.read_u32()?,
+            data: Vec::new(),
+            ext_data: None,
+        };
+        msg.data = reader.read_bytes(msg.valid_data_bytes as usize)?.to_vec();
+
+        // extDataOffset counts from the start of the object
+        let ext_data_offset = msg.ext_data_offset as usize;
+        let object_size = header.object_size as usize;
+        if ext_data_offset != 0 && object_size >= ext_data_offset + 8 {
+            let ext_data_size = object_size - ext_data_offset;
+            msg.ext_data = Some(CanFdExtFrameData::decode(reader, ext_data_size)?);
+        }
+        Ok(msg)
+    }
+
+    /// Appends the body (everything after the header) to `out`.
+    ///
+    /// The extended data is written only if `ext_data_offset` points past the
+    /// payload; `valid_data_bytes` is taken from the payload length.
+    pub fn encode_body(&self, out: &mut Vec<u8>) {
+        let data_end =
+            self.header.calculate_header_size() as usize + CAN_FD_64_FIELDS_SIZE + self.data.len();
+        let ext_data = self
+            .ext_data
+            .as_ref()
+            .filter(|_| self.ext_data_offset as usize >= data_end);
+
+        out.push(self.channel);
+        out.push(self.dlc);
+        out.push(self.data.len() as u8);
+        out.push(self.tx_count);
+        for field in [
+            self.id,
+            self.frame_length,
+            self.flags,
+            self.btr_cfg_arb,
+            self.btr_cfg_data,
+            self.time_offset_brs_ns,
+            self.time_offset_crc_del_ns,
+        ] {
+            out.extend_from_slice(&field.to_le_bytes());
+        }
+        out.extend_from_slice(&self.bit_count.to_le_bytes());
+        out.push(self.dir);
+        out.push(ext_data.map_or(0, |_| self.ext_data_offset));
+        out.extend_from_slice(&self.crc.to_le_bytes());
+        out.extend_from_slice(&self.data);
+        if let Some(ext) = ext_data {
+            out.resize(out.len() + self.ext_data_offset as usize - data_end, 0);
+            out.extend_from_slice(&ext.btr_ext_arb.to_le_bytes());
+            out.extend_from_slice(&ext.btr_ext_data.to_le_bytes());
+            out.extend_from_slice(&ext.reserved);
+        }
+    }
+}
+
+impl CanErrorFrame {
+    /// Decodes the body following `header`.
+    pub fn decode(reader: &mut ByteReader, header: &ObjectHeader) -> Result<Self, CodecError> {
+        let channel = reader.read_u16()?;
+        let length = reader.read_u16()?;
+        // Based on C++ CanErrorFrame.cpp, a reserved u32 follows if length > 0
+        if length > 0 {
+            reader.advance(4);
+        }
+        Ok(Self {
+            header: header.clone(),
+            channel,
+            length,
+        })
+    }
+
+    /// Appends the body (everything after the header) to `out`.
+    pub fn encode_body(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.channel.to_le_bytes());
+        out.extend_from_slice(&self.length.to_le_bytes());
+        if self.length > 0 {
+            out.extend_from_slice(&0u32.to_le_bytes()); // reserved
+        }
+    }
+}
+
+impl CanOverloadFrame {
+    /// Decodes the body following `header`.
+    pub fn decode(reader: &mut ByteReader, header: &ObjectHeader) -> Result<Self, CodecError> {
+        let channel = reader.read_u16()?;
+        // Based on C++ CanOverloadFrame.cpp, reserved u16 and u32
+        reader.advance(2 + 4);
+        Ok(Self {
+            header: header.clone(),
+            channel,
+        })
+    }
+}
+
+impl CanDriverStatistic {
+    /// Decodes the body following `header`.
+    pub fn decode(reader: &mut ByteReader, header: &ObjectHeader) -> Result<Self, CodecError> {
+        let statistic = Self {
+            header: header.clone(),
+            channel: reader.read_u16()?,
+            bus_load: reader.read_u16()?,
+            standard_data_frames: reader.read_u32()?,
+            extended_data_frames: reader.read_u32()?,
+            standard_remote_frames: reader.read_u32()?,
+            extended_remote_frames: reader.read_u32()?,
+            error_frames: reader.read_u32()?,
+            overload_frames: reader.read_u32()?,
+        };
+        // Based on C++ CanDriverStatistic.cpp, reserved u32
+        reader.advance(4);
+        Ok(statistic)
+    }
+}
+
+impl CanDriverError {
+    /// Decodes the body following `header`.
+    pub fn decode(reader: &mut ByteReader, header: &ObjectHeader) -> Result<Self, CodecError> {
+        Ok(Self {
+            header: header.clone(),
+            channel: reader.read_u16()?,
+            tx_errors: reader.read_u8()?,
+            rx_errors: reader.read_u8()?,
+            error_code: reader.read_u32()?,
+        })
+    }
+}
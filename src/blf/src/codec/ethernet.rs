@@ -0,0 +1,31 @@
+//! Ethernet frames.
+
+use super::{ByteReader, CodecError};
+use crate::{EthernetFrame, ObjectHeader};
+
+impl EthernetFrame {
+    /// Decodes the body following `header`.
+    pub fn decode(reader: &mut ByteReader, header: &ObjectHeader) -> Result<Self, CodecError> {
+        let source_address = reader.read_array()?;
+        let channel = reader.read_u16()?;
+        let destination_address = reader.read_array()?;
+        let dir = reader.read_u16()?;
+        let frame_type = reader.read_u16()?;
+        let tpid = reader.read_u16()?;
+        let tci = reader.read_u16()?;
+        let payload_length = reader.read_u16()?;
+        reader.skip(8)?; // reserved
+        Ok(Self {
+            source_address,
+            channel,
+            destination_address,
+            dir,
+            frame_type,
+            tpid,
+            tci,
+            payload_length,
+            payload: reader.read_bytes(payload_length as usize)?.to_vec(),
+            timestamp: header.object_time_stamp,
+        })
+    }
+}
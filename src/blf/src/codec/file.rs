@@ -0,0 +1,129 @@
+//! The file statistics header and log containers.
+
+use super::{ByteReader, CodecError};
+use crate::file_statistics::FILE_SIGNATURE;
+use crate::objects::object_header::ObjectHeaderBase;
+use crate::{FileStatistics, LogContainer, SystemTime};
+use alloc::vec::Vec;
+
+/// Size of the fields of a `LogContainer` between the header and the data.
+pub(crate) const LOG_CONTAINER_FIELDS_SIZE: usize = 16;
+
+impl SystemTime {
+    /// Decodes the 16 byte `SYSTEMTIME`.
+    pub fn decode(reader: &mut ByteReader) -> Result<Self, CodecError> {
+        Ok(Self {
+            year: reader.read_u16()?,
+            month: reader.read_u16()?,
+            day_of_week: reader.read_u16()?,
+            day: reader.read_u16()?,
+            hour: reader.read_u16()?,
+            minute: reader.read_u16()?,
+            second: reader.read_u16()?,
+            milliseconds: reader.read_u16()?,
+        })
+    }
+
+    /// Appends the 16 byte `SYSTEMTIME` to `out`.
+    pub fn encode(&self, out: &mut Vec<u8>) {
+        for field in [
+            self.year,
+            self.month,
+            self.day_of_week,
+            self.day,
+            self.hour,
+            self.minute,
+            self.second,
+            self.milliseconds,
+        ] {
+            out.extend_from_slice(&field.to_le_bytes());
+        }
+    }
+}
+
+impl FileStatistics {
+    /// Decodes the header at the start of a file, including its reserved tail.
+    pub fn decode(reader: &mut ByteReader) -> Result<Self, CodecError> {
+        let start = reader.position();
+        if reader.read_u32()? != FILE_SIGNATURE {
+            return Err(CodecError::InvalidFileSignature);
+        }
+        let stats = Self {
+            statistics_size: reader.read_u32()?,
+            api_number: reader.read_u32()?,
+            application_id: reader.read_u8()?,
+            compression_level: reader.read_u8()?,
+            application_major: reader.read_u8()?,
+            application_minor: reader.read_u8()?,
+            file_size: reader.read_u64()?,
+            uncompressed_file_size: reader.read_u64()?,
+            object_count: reader.read_u32()?,
+            application_build: reader.read_u32()?,
+            measurement_start_time: SystemTime::decode(reader)?,
+            last_object_time: SystemTime::decode(reader)?,
+        };
+        // The rest up to statistics_size is reserved (restore points and so on)
+        let read = reader.position() - start;
+        reader.skip((stats.statistics_size as usize).saturating_sub(read))?;
+        Ok(stats)
+    }
+
+    /// Appends the header to `out`, padded with zeros to `statistics_size`.
+    pub fn encode(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&FILE_SIGNATURE.to_le_bytes());
+        out.extend_from_slice(&self.statistics_size.to_le_bytes());
+        out.extend_from_slice(&self.api_number.to_le_bytes());
+        out.push(self.application_id);
+        out.push(self.compression_level);
+        out.push(self.application_major);
+        out.push(self.application_minor);
+        out.extend_from_slice(&self.file_size.to_le_bytes());
+        out.extend_from_slice(&self.uncompressed_file_size.to_le_bytes());
+        out.extend_from_slice(&self.object_count.to_le_bytes());
+        out.extend_from_slice(&self.application_build.to_le_bytes());
+        self.measurement_start_time.encode(out);
+        self.last_object_time.encode(out);
+
+        // signature .. lastObjectTime is 72 bytes; the rest is reserved
+        let reserved = (self.statistics_size as usize).saturating_sub(72);
+        out.resize(out.len() + reserved, 0);
+    }
+}
+
+impl LogContainer {
+    /// Decodes a container following `header` whose data is stored uncompressed.
+    ///
+    /// Compressed containers fail with [`CodecError::UnsupportedCompression`];
+    /// [`LogContainer::read`] inflates zlib containers.
+    pub fn decode(reader: &mut ByteReader, header: ObjectHeaderBase) -> Result<Self, CodecError> {
+        let (compression_method, _, data) = Self::decode_stored(reader, &header)?;
+        if compression_method != 0 {
+            return Err(CodecError::UnsupportedCompression(compression_method));
+        }
+        Ok(Self {
+            header,
+            compression_method,
+            uncompressed_data: data.to_vec(),
+        })
+    }
+
+    /// The compression method, uncompressed size and stored data of the
+    /// container following `header`.
+    pub(crate) fn decode_stored<'a>(
+        reader: &mut ByteReader<'a>,
+        header: &ObjectHeaderBase,
+    ) -> Result<(u16, usize, &'a [u8]), CodecError> {
+        let compression_method = reader.read_u16()?;
+        reader.skip(2 + 4)?; // reserved
+        let uncompressed_size = reader.read_u32()? as usize;
+        reader.skip(4)?; // reserved
+        let data_size = (header.object_size as usize)
+            .saturating_sub(header.header_size as usize)
+            .saturating_sub(LOG_CONTAINER_FIELDS_SIZE);
+        Ok((
+            compression_method,
+            uncompressed_size,
+            reader.read_bytes(data_size)?,
+        ))
+    }
+}
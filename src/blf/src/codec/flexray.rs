@@ -0,0 +1,291 @@
+//! FlexRay frames, cycles and status events.
+
+use super::{ByteReader, CodecError};
+use crate::{
+    FlexRayData, FlexRayStatusEvent, FlexRaySync, FlexRayV6Message, FlexRayV6StartCycleEvent,
+    FlexRayVFrError, FlexRayVFrReceiveMsg, FlexRayVFrReceiveMsgEx, FlexRayVFrStartCycle,
+    FlexRayVFrStatus, ObjectHeader,
+};
+use alloc::vec::Vec;
+
+/// Size of the fields of a `FlexRayVFrReceiveMsgEx` before the payload.
+const RECEIVE_MSG_EX_FIELDS_SIZE: usize = 4 * 2 + 2 * 4 + 6 * 2 + 6 * 4 + 3 * 2 + 13 * 2;
+
+impl FlexRayData {
+    /// Decodes the body following `header`.
+    pub fn decode(reader: &mut ByteReader, header: &ObjectHeader) -> Result<Self, CodecError> {
+        let channel = reader.read_u16()?;
+        let mux = reader.read_u8()?;
+        let len = reader.read_u8()?;
+        let message_id = reader.read_u16()?;
+        let crc = reader.read_u16()?;
+        let dir = reader.read_u8()?;
+        reader.skip(1 + 2)?; // reserved
+        Ok(Self {
+            channel,
+            mux,
+            len,
+            message_id,
+            crc,
+            dir,
+            data_bytes: reader.read_array()?,
+            timestamp: header.object_time_stamp,
+        })
+    }
+}
+
+impl FlexRaySync {
+    /// Decodes the body following `header`.
+    pub fn decode(reader: &mut ByteReader, header: &ObjectHeader) -> Result<Self, CodecError> {
+        let channel = reader.read_u16()?;
+        let mux = reader.read_u8()?;
+        let len = reader.read_u8()?;
+        let message_id = reader.read_u16()?;
+        let crc = reader.read_u16()?;
+        let dir = reader.read_u8()?;
+        reader.skip(1 + 2)?; // reserved
+        Ok(Self {
+            channel,
+            mux,
+            len,
+            message_id,
+            crc,
+            dir,
+            data_bytes: reader.read_array()?,
+            cycle: reader.read_u8()?,
+            timestamp: header.object_time_stamp,
+        })
+    }
+}
+
+impl FlexRayV6Message {
+    /// Decodes the body following `header`.
+    pub fn decode(reader: &mut ByteReader, header: &ObjectHeader) -> Result<Self, CodecError> {
+        let channel = reader.read_u16()?;
+        let dir = reader.read_u8()?;
+        // lowTime, fpgaTick, fpgaTickOverflow, clientIndexFlexRayV6Message, clusterTime
+        reader.skip(1 + 4 + 4 + 4 + 4)?;
+        let frame_id = reader.read_u16()?;
+        reader.skip(2 + 2)?; // headerCrc, frameState
+        let length = reader.read_u8()?;
+        let cycle = reader.read_u8()?;
+        reader.skip(1 + 1 + 2)?; // headerBitMask, reserved
+        Ok(Self {
+            channel,
+            dir,
+            frame_id,
+            length,
+            cycle,
+            data_bytes: reader.read_array()?,
+            timestamp: header.object_time_stamp,
+        })
+    }
+}
+
+impl FlexRayV6StartCycleEvent {
+    /// Decodes the body following `header`.
+    pub fn decode(reader: &mut ByteReader, header: &ObjectHeader) -> Result<Self, CodecError> {
+        let channel = reader.read_u16()?;
+        let dir = reader.read_u8()?;
+        // lowTime, fpgaTick, fpgaTickOverflow, clientIndexFlexRayV6StartCycleEvent
+        reader.skip(1 + 4 + 4 + 4)?;
+        let event = Self {
+            channel,
+            dir,
+            cluster_time: reader.read_u32()?,
+            data_bytes: reader.read_array()?,
+            timestamp: header.object_time_stamp,
+        };
+        reader.skip(2)?; // reserved
+        Ok(event)
+    }
+}
+
+impl FlexRayStatusEvent {
+    /// Decodes the body following `header`.
+    pub fn decode(reader: &mut ByteReader, header: &ObjectHeader) -> Result<Self, CodecError> {
+        let event = Self {
+            channel: reader.read_u16()?,
+            version: reader.read_u16()?,
+            status_type: reader.read_u16()?,
+            info_mask1: reader.read_u16()?,
+            info_mask2: reader.read_u16()?,
+            info_mask3: reader.read_u16()?,
+            timestamp: header.object_time_stamp,
+        };
+        reader.advance(18 * 2); // reservedFlexRayStatusEvent[18]
+        Ok(event)
+    }
+}
+
+impl FlexRayVFrError {
+    /// Decodes the body following `header`.
+    pub fn decode(reader: &mut ByteReader, header: &ObjectHeader) -> Result<Self, CodecError> {
+        let channel = reader.read_u16()?;
+        let version = reader.read_u16()?;
+        let channel_mask = reader.read_u16()?;
+        let cycle = reader.read_u8()?;
+        reader.skip(1)?; // reserved
+        let error = Self {
+            channel,
+            version,
+            channel_mask,
+            cycle,
+            client_index: reader.read_u32()?,
+            cluster_no: reader.read_u32()?,
+            tag: reader.read_u32()?,
+            data: read_u32_array(reader)?,
+            timestamp: header.object_time_stamp,
+        };
+        reader.skip(4)?; // reserved
+        Ok(error)
+    }
+}
+
+impl FlexRayVFrStatus {
+    /// Decodes the body following `header`.
+    pub fn decode(reader: &mut ByteReader, header: &ObjectHeader) -> Result<Self, CodecError> {
+        let channel = reader.read_u16()?;
+        let version = reader.read_u16()?;
+        let channel_mask = reader.read_u16()?;
+        let cycle = reader.read_u8()?;
+        reader.skip(1)?; // reserved
+        let status = Self {
+            channel,
+            version,
+            channel_mask,
+            cycle,
+            client_index: reader.read_u32()?,
+            cluster_no: reader.read_u32()?,
+            wus: reader.read_u32()?,
+            cc_sync_state: reader.read_u32()?,
+            tag: reader.read_u32()?,
+            data: read_u32_array(reader)?,
+            timestamp: header.object_time_stamp,
+        };
+        reader.advance(18 * 2); // reservedFlexRayVFrStatus2[18]
+        Ok(status)
+    }
+}
+
+impl FlexRayVFrStartCycle {
+    /// Decodes the body following `header`.
+    pub fn decode(reader: &mut ByteReader, header: &ObjectHeader) -> Result<Self, CodecError> {
+        let channel = reader.read_u16()?;
+        let version = reader.read_u16()?;
+        let channel_mask = reader.read_u16()?;
+        let dir = reader.read_u8()?;
+        let cycle = reader.read_u8()?;
+        let client_index = reader.read_u32()?;
+        let cluster_no = reader.read_u32()?;
+        let nm_size = reader.read_u16()?;
+        let data_bytes = reader.read_array()?;
+        reader.skip(2)?; // reserved
+        let event = Self {
+            channel,
+            version,
+            channel_mask,
+            dir,
+            cycle,
+            client_index,
+            cluster_no,
+            nm_size,
+            data_bytes,
+            tag: reader.read_u32()?,
+            data: read_u32_array(reader)?,
+            timestamp: header.object_time_stamp,
+        };
+        reader.skip(8)?; // reserved
+        Ok(event)
+    }
+}
+
+impl FlexRayVFrReceiveMsg {
+    /// Decodes the body following `header`.
+    pub fn decode(reader: &mut ByteReader, header: &ObjectHeader) -> Result<Self, CodecError> {
+        let channel = reader.read_u16()?;
+        let version = reader.read_u16()?;
+        let channel_mask = reader.read_u8()?;
+        let dir = reader.read_u8()?;
+        reader.skip(2)?; // reservedFlexRayVFrReceiveMsg1
+        let client_index = reader.read_u32()?;
+        let cluster_no = reader.read_u32()?;
+        let frame_id = reader.read_u16()?;
+        let header_crc1 = reader.read_u16()?;
+        let header_crc2 = reader.read_u16()?;
+        let byte_count = reader.read_u16()?;
+        let data_count = reader.read_u16()?;
+        let cycle = reader.read_u8()?;
+        reader.skip(1)?; // reservedFlexRayVFrReceiveMsg2
+        let msg = Self {
+            channel,
+            version,
+            channel_mask,
+            dir,
+            client_index,
+            cluster_no,
+            frame_id,
+            header_crc1,
+            header_crc2,
+            byte_count,
+            data_count,
+            cycle,
+            tag: reader.read_u32()?,
+            data: reader.read_u32()?,
+            frame_flags: reader.read_u32()?,
+            app_parameter: reader.read_u32()?,
+            data_bytes: reader.read_array()?,
+            timestamp: header.object_time_stamp,
+        };
+        reader.skip(2 + 4)?; // reservedFlexRayVFrReceiveMsg3, reservedFlexRayVFrReceiveMsg4
+        Ok(msg)
+    }
+}
+
+impl FlexRayVFrReceiveMsgEx {
+    /// Decodes the body following `header`.
+    pub fn decode(reader: &mut ByteReader, header: &ObjectHeader) -> Result<Self, CodecError> {
+        let mut msg = Self {
+            channel: reader.read_u16()?,
+            version: reader.read_u16()?,
+            channel_mask: reader.read_u16()?,
+            dir: reader.read_u16()?,
+            client_index: reader.read_u32()?,
+            cluster_no: reader.read_u32()?,
+            frame_id: reader.read_u16()?,
+            header_crc1: reader.read_u16()?,
+            header_crc2: reader.read_u16()?,
+            byte_count: reader.read_u16()?,
+            data_count: reader.read_u16()?,
+            cycle: reader.read_u16()?,
+            tag: reader.read_u32()?,
+            data: reader.read_u32()?,
+            frame_flags: reader.read_u32()?,
+            app_parameter: reader.read_u32()?,
+            frame_crc: reader.read_u32()?,
+            frame_length_ns: reader.read_u32()?,
+            frame_id1: reader.read_u16()?,
+            pdu_offset: reader.read_u16()?,
+            blf_log_mask: reader.read_u16()?,
+            data_bytes: Vec::new(),
+            timestamp: header.object_time_stamp,
+        };
+        reader.skip(13 * 2)?; // reservedFlexRayVFrReceiveMsgEx1
+        msg.data_bytes = reader.read_bytes(msg.data_count as usize)?.to_vec();
+
+        // The C++ code reads a dynamically sized reserved block at the end
+        let body_size =
+            (header.object_size as usize).saturating_sub(header.calculate_header_size() as usize);
+        reader.advance(body_size.saturating_sub(RECEIVE_MSG_EX_FIELDS_SIZE + msg.data_bytes.len()));
+        Ok(msg)
+    }
+}
+
+/// Reads `N` consecutive little-endian `u32`s.
+fn read_u32_array<const N: usize>(reader: &mut ByteReader) -> Result<[u32; N], CodecError> {
+    let mut values = [0; N];
+    for value in &mut values {
+        *value = reader.read_u32()?;
+    }
+    Ok(values)
+}
@@ -0,0 +1,40 @@
+//! GPS fixes.
+
+use super::{ByteReader, CodecError};
+use crate::{GpsEvent, ObjectHeader};
+use alloc::vec::Vec;
+
+impl GpsEvent {
+    /// Decodes the body following `header`.
+    pub fn decode(reader: &mut ByteReader, header: &ObjectHeader) -> Result<Self, CodecError> {
+        let flags = reader.read_u32()?;
+        let channel = reader.read_u16()?;
+        reader.skip(2)?; // reserved
+        Ok(Self {
+            flags,
+            channel,
+            latitude: reader.read_f64()?,
+            longitude: reader.read_f64()?,
+            altitude: reader.read_f64()?,
+            speed: reader.read_f64()?,
+            course: reader.read_f64()?,
+            timestamp: header.object_time_stamp,
+        })
+    }
+
+    /// Appends the body (everything after the header) to `out`.
+    pub fn encode_body(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.flags.to_le_bytes());
+        out.extend_from_slice(&self.channel.to_le_bytes());
+        out.extend_from_slice(&0u16.to_le_bytes()); // reserved
+        for value in [
+            self.latitude,
+            self.longitude,
+            self.altitude,
+            self.speed,
+            self.course,
+        ] {
+            out.extend_from_slice(&value.to_le_bytes());
+        }
+    }
+}
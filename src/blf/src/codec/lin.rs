@@ -0,0 +1,198 @@
+//! LIN frames and events.
+
+use super::{ByteReader, CodecError};
+use crate::{
+    LinBaudrateEvent, LinCrcError, LinDlcInfo, LinMessage, LinMessage2, LinReceiveError,
+    LinSchedulerModeChange, LinSendError, LinSlaveTimeout, LinSleepModeEvent, LinSyncError,
+    LinWakeupEvent, ObjectHeader,
+};
+use alloc::vec::Vec;
+
+/// Size of a version 1 `LIN_MESSAGE2` body: the 112-byte databyte timestamp
+/// event followed by the frame fields.
+const LIN_MESSAGE2_BASE_SIZE: usize = 112 + 8 + 2 + 1 + 1 + 1 + 1 + 1 + 1 + 1 + 1 + 2;
+
+impl LinMessage {
+    /// Decodes the body following `header`.
+    pub fn decode(reader: &mut ByteReader, header: &ObjectHeader) -> Result<Self, CodecError> {
+        // Based on C++ LinMessage.cpp
+        let msg = Self {
+            header: header.clone(),
+            channel: reader.read_u16()?,
+            id: reader.read_u8()?,
+            dlc: reader.read_u8()?,
+            data: reader.read_array()?,
+            fsm_id: reader.read_u16()?,
+            fsm_state: reader.read_u16()?,
+            header_time: reader.read_u32()?,
+            full_time: reader.read_u32()?,
+            crc: reader.read_u8()?,
+            dir: reader.read_u8()?,
+        };
+        reader.advance(2 + 4); // reservedLinMessage1 + reservedLinMessage2
+        Ok(msg)
+    }
+
+    /// Appends the body (everything after the header) to `out`.
+    pub fn encode_body(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.channel.to_le_bytes());
+        out.push(self.id);
+        out.push(self.dlc);
+        out.extend_from_slice(&self.data);
+        out.extend_from_slice(&self.fsm_id.to_le_bytes());
+        out.extend_from_slice(&self.fsm_state.to_le_bytes());
+        out.extend_from_slice(&self.header_time.to_le_bytes());
+        out.extend_from_slice(&self.full_time.to_le_bytes());
+        out.push(self.crc);
+        out.push(self.dir);
+        out.extend_from_slice(&[0; 2 + 4]); // reservedLinMessage1 + reservedLinMessage2
+    }
+}
+
+impl LinMessage2 {
+    /// Decodes the body following `header`, `data_size` bytes long.
+    pub fn decode(
+        reader: &mut ByteReader,
+        header: &ObjectHeader,
+        data_size: usize,
+    ) -> Result<Self, CodecError> {
+        // Based on C++ LinMessage2.cpp, which starts with a LinDatabyteTimestampEvent:
+        // LinBusEvent: sof, eventBaudrate, channel, reserved
+        reader.skip(8 + 4)?;
+        let channel = reader.read_u16()?;
+        // reserved, then LinSynchFieldEvent: synchBreakLength, synchDelLength
+        // LinMessageDescriptor: supplierId, messageId, nad
+        reader.skip(2 + 8 + 8 + 2 + 2 + 1)?;
+        let id = reader.read_u8()?;
+        let dlc = reader.read_u8()?;
+        // checksumModel, then databyteTimestamps[9]
+        reader.skip(1 + 9 * 8)?;
+
+        let data = reader.read_array()?;
+        let crc = reader.read_u16()?;
+        let dir = reader.read_u8()?;
+        let simulated = reader.read_u8()?;
+        let is_etf = reader.read_u8()?;
+        // etfAssocIndex, etfAssocEtfId, fsmId, fsmState, reserved1, reserved2
+        reader.skip(1 + 1 + 1 + 1 + 1 + 2)?;
+
+        let remaining_size = data_size.saturating_sub(LIN_MESSAGE2_BASE_SIZE);
+        let resp_baudrate = if remaining_size >= 4 {
+            Some(reader.read_u32()?)
+        } else {
+            None
+        };
+        // 4 for resp_baudrate + 8 for this f64
+        let exact_header_baudrate = if remaining_size >= 12 {
+            Some(reader.read_f64()?)
+        } else {
+            None
+        };
+
+        Ok(Self {
+            header: header.clone(),
+            channel,
+            id,
+            dlc,
+            data,
+            crc,
+            dir,
+            simulated,
+            is_etf,
+            resp_baudrate,
+            exact_header_baudrate,
+        })
+    }
+}
+
+// The LIN events below keep only their object header; nothing of the body is read.
+
+impl LinCrcError {
+    /// Decodes the object following `header`.
+    pub fn decode(_reader: &mut ByteReader, header: &ObjectHeader) -> Result<Self, CodecError> {
+        Ok(Self {
+            header: header.clone(),
+        })
+    }
+}
+
+impl LinReceiveError {
+    /// Decodes the object following `header`.
+    pub fn decode(_reader: &mut ByteReader, header: &ObjectHeader) -> Result<Self, CodecError> {
+        Ok(Self {
+            header: header.clone(),
+        })
+    }
+}
+
+impl LinSendError {
+    /// Decodes the object following `header`.
+    pub fn decode(_reader: &mut ByteReader, header: &ObjectHeader) -> Result<Self, CodecError> {
+        Ok(Self {
+            header: header.clone(),
+        })
+    }
+}
+
+impl LinSlaveTimeout {
+    /// Decodes the object following `header`.
+    pub fn decode(_reader: &mut ByteReader, header: &ObjectHeader) -> Result<Self, CodecError> {
+        Ok(Self {
+            header: header.clone(),
+        })
+    }
+}
+
+impl LinSchedulerModeChange {
+    /// Decodes the object following `header`.
+    pub fn decode(_reader: &mut ByteReader, header: &ObjectHeader) -> Result<Self, CodecError> {
+        Ok(Self {
+            header: header.clone(),
+        })
+    }
+}
+
+impl LinSyncError {
+    /// Decodes the object following `header`.
+    pub fn decode(_reader: &mut ByteReader, header: &ObjectHeader) -> Result<Self, CodecError> {
+        Ok(Self {
+            header: header.clone(),
+        })
+    }
+}
+
+impl LinBaudrateEvent {
+    /// Decodes the object following `header`.
+    pub fn decode(_reader: &mut ByteReader, header: &ObjectHeader) -> Result<Self, CodecError> {
+        Ok(Self {
+            header: header.clone(),
+        })
+    }
+}
+
+impl LinSleepModeEvent {
+    /// Decodes the object following `header`.
+    pub fn decode(_reader: &mut ByteReader, header: &ObjectHeader) -> Result<Self, CodecError> {
+        Ok(Self {
+            header: header.clone(),
+        })
+    }
+}
+
+impl LinWakeupEvent {
+    /// Decodes the object following `header`.
+    pub fn decode(_reader: &mut ByteReader, header: &ObjectHeader) -> Result<Self, CodecError> {
+        Ok(Self {
+            header: header.clone(),
+        })
+    }
+}
+
+impl LinDlcInfo {
+    /// Decodes the object following `header`.
+    pub fn decode(_reader: &mut ByteReader, header: &ObjectHeader) -> Result<Self, CodecError> {
+        Ok(Self {
+            header: header.clone(),
+        })
+    }
+}
@@ -0,0 +1,544 @@
+//! Byte-slice encoding of BLF objects.
+//!
+//! Everything in this module uses `core` and `alloc` only, no `std::io`, so
+//! firmware that logs BLF itself can share the object model and wire layout
+//! with the host tools. Objects are decoded from a [`ByteReader`] over a
+//! slice and encoded by appending to a `Vec<u8>`; [`LogObject::decode`]
+//! decodes an object of any type from its header.
+//!
+//! The `read`/`write` methods of the file header, object headers and objects
+//! are thin wrappers over these for the `Cursor` based parser and
+//! [`BlfWriter`](crate::BlfWriter).
+//!
+//! # Example
+//! ```
+//! use blf::{ByteReader, CanMessage, LogObject, ObjectHeader, ObjectType};
+//!
+//! let mut header = ObjectHeader::new_v1(ObjectType::CanMessage, 0);
+//! header.object_time_stamp = 1_000;
+//! header.prepare_for_write();
+//! let frame = CanMessage { header, channel: 1, dlc: 2, id: 0x123, ..Default::default() };
+//!
+//! let mut bytes = Vec::new();
+//! frame.header.encode(&mut bytes)?;
+//! frame.encode_body(&mut bytes);
+//!
+//! let mut reader = ByteReader::new(&bytes);
+//! let header = ObjectHeader::decode(&mut reader)?;
+//! assert_eq!(LogObject::decode(&mut reader, &header)?, LogObject::CanMessage(frame));
+//! # Ok::<(), blf::CodecError>(())
+//! ```
+
+mod app_events;
+mod can;
+mod ethernet;
+mod file;
+mod flexray;
+mod gps;
+mod lin;
+mod most;
+
+pub(crate) use file::LOG_CONTAINER_FIELDS_SIZE;
+
+use crate::objects::object_header::{OBJECT_SIGNATURE, ObjectHeaderBase};
+use crate::objects::*;
+use crate::{LogObject, ObjectType};
+use alloc::vec::Vec;
+use core::fmt;
+
+/// Error of the `std`-free codec.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CodecError {
+    /// The slice ended inside an object.
+    UnexpectedEof,
+    /// An object header does not start with "LOBJ".
+    InvalidSignature,
+    /// An object header has a version other than 1 or 2.
+    UnknownHeaderVersion(u16),
+    /// The file does not start with "LOGG".
+    InvalidFileSignature,
+    /// A log container is compressed, which the codec does not inflate.
+    UnsupportedCompression(u16),
+}
+
+impl fmt::Display for CodecError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CodecError::UnexpectedEof => write!(f, "Unexpected end of data"),
+            CodecError::InvalidSignature => write!(f, "Invalid LOBJ object signature"),
+            CodecError::UnknownHeaderVersion(v) => {
+                write!(f, "Unknown object header version: {}", v)
+            }
+            CodecError::InvalidFileSignature => write!(f, "Invalid LOGG file signature"),
+            CodecError::UnsupportedCompression(c) => {
+                write!(f, "Unsupported compression method: {}", c)
+            }
+        }
+    }
+}
+
+impl core::error::Error for CodecError {}
+
+/// Little-endian reader over a byte slice.
+#[derive(Debug, Clone)]
+pub struct ByteReader<'a> {
+    data: &'a [u8],
+    position: usize,
+}
+
+impl<'a> ByteReader<'a> {
+    /// Reads `data` from its start.
+    pub fn new(data: &'a [u8]) -> Self {
+        Self::at(data, 0)
+    }
+
+    /// Reads `data` from `position` (clamped to its end).
+    pub fn at(data: &'a [u8], position: usize) -> Self {
+        Self {
+            data,
+            position: position.min(data.len()),
+        }
+    }
+
+    /// Bytes consumed from the start of the slice.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// The bytes not read yet.
+    pub fn remaining(&self) -> &'a [u8] {
+        &self.data[self.position..]
+    }
+
+    /// The next `len` bytes.
+    pub fn read_bytes(&mut self, len: usize) -> Result<&'a [u8], CodecError> {
+        let bytes = self
+            .remaining()
+            .get(..len)
+            .ok_or(CodecError::UnexpectedEof)?;
+        self.position += len;
+        Ok(bytes)
+    }
+
+    /// The next `N` bytes as an array.
+    pub fn read_array<const N: usize>(&mut self) -> Result<[u8; N], CodecError> {
+        let mut array = [0; N];
+        array.copy_from_slice(self.read_bytes(N)?);
+        Ok(array)
+    }
+
+    /// Skips `len` bytes.
+    pub fn skip(&mut self, len: usize) -> Result<(), CodecError> {
+        self.read_bytes(len).map(|_| ())
+    }
+
+    /// Skips up to `len` bytes, stopping at the end of the slice.
+    ///
+    /// For reserved fields at the end of an object, which some writers leave out.
+    pub fn advance(&mut self, len: usize) {
+        self.position = self.position.saturating_add(len).min(self.data.len());
+    }
+
+    /// Reads a `u8`.
+    pub fn read_u8(&mut self) -> Result<u8, CodecError> {
+        Ok(self.read_array::<1>()?[0])
+    }
+
+    /// Reads a little-endian `u16`.
+    pub fn read_u16(&mut self) -> Result<u16, CodecError> {
+        self.read_array().map(u16::from_le_bytes)
+    }
+
+    /// Reads a little-endian `u32`.
+    pub fn read_u32(&mut self) -> Result<u32, CodecError> {
+        self.read_array().map(u32::from_le_bytes)
+    }
+
+    /// Reads a little-endian `u64`.
+    pub fn read_u64(&mut self) -> Result<u64, CodecError> {
+        self.read_array().map(u64::from_le_bytes)
+    }
+
+    /// Reads a little-endian `i16`.
+    pub fn read_i16(&mut self) -> Result<i16, CodecError> {
+        self.read_array().map(i16::from_le_bytes)
+    }
+
+    /// Reads a little-endian `i32`.
+    pub fn read_i32(&mut self) -> Result<i32, CodecError> {
+        self.read_array().map(i32::from_le_bytes)
+    }
+
+    /// Reads a little-endian `f64`.
+    pub fn read_f64(&mut self) -> Result<f64, CodecError> {
+        self.read_array().map(f64::from_le_bytes)
+    }
+}
+
+impl ObjectHeaderBase {
+    /// Decodes the 16 base header bytes.
+    pub fn decode(reader: &mut ByteReader) -> Result<Self, CodecError> {
+        let signature = reader.read_u32()?;
+        if signature != OBJECT_SIGNATURE {
+            return Err(CodecError::InvalidSignature);
+        }
+        Ok(Self {
+            signature,
+            header_size: reader.read_u16()?,
+            header_version: reader.read_u16()?,
+            object_size: reader.read_u32()?,
+            object_type: ObjectType::from(reader.read_u32()?),
+        })
+    }
+
+    /// Appends the 16 base header bytes to `out`.
+    pub fn encode(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.signature.to_le_bytes());
+        out.extend_from_slice(&self.header_size.to_le_bytes());
+        out.extend_from_slice(&self.header_version.to_le_bytes());
+        out.extend_from_slice(&self.object_size.to_le_bytes());
+        out.extend_from_slice(&(self.object_type as u32).to_le_bytes());
+    }
+}
+
+impl ObjectHeader {
+    /// Decodes a V1 or V2 header; timestamps are normalized to nanoseconds.
+    pub fn decode(reader: &mut ByteReader) -> Result<Self, CodecError> {
+        let base = ObjectHeaderBase::decode(reader)?;
+        let mut header = ObjectHeader {
+            base,
+            object_flags: 0,
+            client_index: 0,
+            object_version: 0,
+            object_time_stamp: 0,
+            original_time_stamp: None,
+            time_stamp_status: None,
+            reserved: 0,
+        };
+
+        match header.base.header_version {
+            // 很多 BLF 文件的 header_size 不准确：声称 16 字节的 V1 header
+            // 后面往往仍跟着完整的 32 字节字段，数据足够时照常读取
+            1 if header.base.header_size >= 32
+                || (header.base.header_size == 16 && reader.remaining().len() >= 16) =>
+            {
+                header.object_flags = reader.read_u32()?;
+                header.client_index = reader.read_u16()?;
+                header.object_version = reader.read_u16()?;
+                header.object_time_stamp = reader.read_u64()?;
+            }
+            // 数据不足，这才是真正的紧凑型 header
+            1 if header.base.header_size == 16 => {}
+            2 => {
+                header.object_flags = reader.read_u32()?;
+                header.time_stamp_status = Some(reader.read_u8()?);
+                header.reserved = reader.read_u8()?;
+                header.object_version = reader.read_u16()?;
+                header.object_time_stamp = reader.read_u64()?;
+                header.original_time_stamp = Some(reader.read_u64()?);
+            }
+            version => return Err(CodecError::UnknownHeaderVersion(version)),
+        }
+
+        // Normalize 10 µs ticks to nanoseconds so objects of both resolutions compare
+        let nanos_per_tick = header.timestamp_resolution().nanos_per_tick();
+        header.object_time_stamp = header.object_time_stamp.saturating_mul(nanos_per_tick);
+        header.original_time_stamp = header
+            .original_time_stamp
+            .map(|timestamp| timestamp.saturating_mul(nanos_per_tick));
+        Ok(header)
+    }
+
+    /// Appends the header to `out`, timestamps in the flagged resolution.
+    ///
+    /// Sizes are written as they are; see [`prepare_for_write`](Self::prepare_for_write).
+    pub fn encode(&self, out: &mut Vec<u8>) -> Result<(), CodecError> {
+        let nanos_per_tick = self.timestamp_resolution().nanos_per_tick();
+        let object_time_stamp = self.object_time_stamp / nanos_per_tick;
+        let original_time_stamp = self.original_time_stamp.unwrap_or(0) / nanos_per_tick;
+
+        match self.base.header_version {
+            1 => {
+                self.base.encode(out);
+                // A compact 16 byte V1 header has no further fields
+                if self.base.header_size >= 32 {
+                    out.extend_from_slice(&self.object_flags.to_le_bytes());
+                    out.extend_from_slice(&self.client_index.to_le_bytes());
+                    out.extend_from_slice(&self.object_version.to_le_bytes());
+                    out.extend_from_slice(&object_time_stamp.to_le_bytes());
+                }
+            }
+            2 => {
+                self.base.encode(out);
+                out.extend_from_slice(&self.object_flags.to_le_bytes());
+                out.push(self.time_stamp_status.unwrap_or(0));
+                out.push(self.reserved);
+                out.extend_from_slice(&self.object_version.to_le_bytes());
+                out.extend_from_slice(&object_time_stamp.to_le_bytes());
+                out.extend_from_slice(&original_time_stamp.to_le_bytes());
+            }
+            version => return Err(CodecError::UnknownHeaderVersion(version)),
+        }
+        Ok(())
+    }
+}
+
+impl LogObject {
+    /// Decodes the body of the object `header` describes.
+    ///
+    /// Types this crate does not decode come back as [`LogObject::Unhandled`]
+    /// with their raw body.
+    pub fn decode(reader: &mut ByteReader, header: &ObjectHeader) -> Result<Self, CodecError> {
+        let body_size =
+            (header.object_size as usize).saturating_sub(header.calculate_header_size() as usize);
+        Ok(match header.object_type {
+            ObjectType::CanMessage => LogObject::CanMessage(CanMessage::decode(reader, header)?),
+            ObjectType::CanMessage2 => {
+                LogObject::CanMessage2(CanMessage2::decode(reader, header, body_size)?)
+            }
+            ObjectType::CanError => {
+                LogObject::CanErrorFrame(CanErrorFrame::decode(reader, header)?)
+            }
+            ObjectType::CanFdMessage => {
+                LogObject::CanFdMessage(CanFdMessage::decode(reader, header)?)
+            }
+            ObjectType::CanFdMessage64 => {
+                LogObject::CanFdMessage64(CanFdMessage64::decode(reader, header)?)
+            }
+            ObjectType::CanOverload => {
+                LogObject::CanOverloadFrame(CanOverloadFrame::decode(reader, header)?)
+            }
+            ObjectType::CanStatistic => {
+                LogObject::CanDriverStatistic(CanDriverStatistic::decode(reader, header)?)
+            }
+            ObjectType::CanDriverError => {
+                LogObject::CanDriverError(CanDriverError::decode(reader, header)?)
+            }
+            ObjectType::LinMessage => LogObject::LinMessage(LinMessage::decode(reader, header)?),
+            ObjectType::LinCrcError => LogObject::LinCrcError(LinCrcError::decode(reader, header)?),
+            ObjectType::LinDlcInfo => LogObject::LinDlcInfo(LinDlcInfo::decode(reader, header)?),
+            ObjectType::LinReceiveError => {
+                LogObject::LinReceiveError(LinReceiveError::decode(reader, header)?)
+            }
+            ObjectType::LinSendError => {
+                LogObject::LinSendError(LinSendError::decode(reader, header)?)
+            }
+            ObjectType::LinSlaveTimeout => {
+                LogObject::LinSlaveTimeout(LinSlaveTimeout::decode(reader, header)?)
+            }
+            ObjectType::LinSchedulerModeChange => {
+                LogObject::LinSchedulerModeChange(LinSchedulerModeChange::decode(reader, header)?)
+            }
+            ObjectType::LinSyncError => {
+                LogObject::LinSyncError(LinSyncError::decode(reader, header)?)
+            }
+            ObjectType::LinBaudrate => {
+                LogObject::LinBaudrateEvent(LinBaudrateEvent::decode(reader, header)?)
+            }
+            ObjectType::LinSleep => {
+                LogObject::LinSleepModeEvent(LinSleepModeEvent::decode(reader, header)?)
+            }
+            ObjectType::LinWakeup => {
+                LogObject::LinWakeupEvent(LinWakeupEvent::decode(reader, header)?)
+            }
+            ObjectType::LinMessage2 => {
+                LogObject::LinMessage2(LinMessage2::decode(reader, header, body_size)?)
+            }
+            ObjectType::FlexRayData => LogObject::FlexRayData(FlexRayData::decode(reader, header)?),
+            ObjectType::FlexRaySync => LogObject::FlexRaySync(FlexRaySync::decode(reader, header)?),
+            ObjectType::FlexRayMessage => {
+                LogObject::FlexRayV6Message(FlexRayV6Message::decode(reader, header)?)
+            }
+            ObjectType::FlexRayV6StartCycleEvent => LogObject::FlexRayV6StartCycleEvent(
+                FlexRayV6StartCycleEvent::decode(reader, header)?,
+            ),
+            ObjectType::FlexRayStatusEvent => {
+                LogObject::FlexRayStatusEvent(FlexRayStatusEvent::decode(reader, header)?)
+            }
+            ObjectType::FlexRayVFrError => {
+                LogObject::FlexRayVFrError(FlexRayVFrError::decode(reader, header)?)
+            }
+            ObjectType::FlexRayVFrStatus => {
+                LogObject::FlexRayVFrStatus(FlexRayVFrStatus::decode(reader, header)?)
+            }
+            ObjectType::FlexRayVFrStartCycle => {
+                LogObject::FlexRayVFrStartCycle(FlexRayVFrStartCycle::decode(reader, header)?)
+            }
+            ObjectType::FlexRayVFrReceiveMsg => {
+                LogObject::FlexRayVFrReceiveMsg(FlexRayVFrReceiveMsg::decode(reader, header)?)
+            }
+            ObjectType::FlexRayVFrReceiveMsgEx => {
+                LogObject::FlexRayVFrReceiveMsgEx(FlexRayVFrReceiveMsgEx::decode(reader, header)?)
+            }
+            ObjectType::EthernetFrame => {
+                LogObject::EthernetFrame(EthernetFrame::decode(reader, header)?)
+            }
+            ObjectType::AppTrigger => LogObject::AppTrigger(AppTrigger::decode(reader, header)?),
+            ObjectType::EventComment => {
+                LogObject::EventComment(EventComment::decode(reader, header)?)
+            }
+            ObjectType::GlobalMarker => {
+                LogObject::GlobalMarker(GlobalMarker::decode(reader, header)?)
+            }
+            ObjectType::GpsEvent => LogObject::GpsEvent(GpsEvent::decode(reader, header)?),
+            ObjectType::MostSpy => LogObject::MostSpy(MostSpy::decode(reader, header)?),
+            ObjectType::MostCtrl => LogObject::MostCtrl(MostCtrl::decode(reader, header)?),
+            ObjectType::MostPkt2 => LogObject::MostPkt2(MostPkt2::decode(reader, header)?),
+            ObjectType::MostLightLock => {
+                LogObject::MostLightLock(MostLightLock::decode(reader, header)?)
+            }
+            ObjectType::MostStatistic => {
+                LogObject::MostStatistic(MostStatistic::decode(reader, header)?)
+            }
+            ObjectType::MostHwMode => LogObject::MostHwMode(MostHwMode::decode(reader, header)?),
+            ObjectType::MostReg => LogObject::MostReg(MostReg::decode(reader, header)?),
+            ObjectType::MostGenReg => LogObject::MostGenReg(MostGenReg::decode(reader, header)?),
+            ObjectType::MostNetState => {
+                LogObject::MostNetState(MostNetState::decode(reader, header)?)
+            }
+            ObjectType::MostDataLost => {
+                LogObject::MostDataLost(MostDataLost::decode(reader, header)?)
+            }
+            ObjectType::MostTrigger => LogObject::MostTrigger(MostTrigger::decode(reader, header)?),
+            _ => LogObject::Unhandled {
+                object_type: header.object_type as u32,
+                timestamp: header.object_time_stamp,
+                data: reader.read_bytes(body_size)?.to_vec(),
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::can::CAN_FD_64_FIELDS_SIZE;
+    use super::*;
+    use crate::objects::object_header::ObjectFlags;
+    use crate::{FileStatistics, SystemTime};
+    use alloc::vec;
+
+    #[test]
+    fn test_objects_round_trip_through_slices() {
+        let mut header = ObjectHeader::new_v2(ObjectType::CanMessage2);
+        header.object_flags = ObjectFlags::TimeTenMics as u32;
+        header.object_time_stamp = 250_000;
+        header.original_time_stamp = Some(120_000);
+        header.prepare_for_write();
+        let frame = CanMessage2 {
+            header,
+            channel: 2,
+            dlc: 3,
+            id: 0x18FF_0001,
+            data: vec![1, 2, 3],
+            frame_length: 2_000,
+            bit_count: 60,
+            ..Default::default()
+        };
+
+        let mut bytes = Vec::new();
+        frame.header.encode(&mut bytes).unwrap();
+        assert_eq!(bytes.len(), 40);
+        // Stored in 10 µs ticks, read back in nanoseconds
+        assert_eq!(u64::from_le_bytes(bytes[24..32].try_into().unwrap()), 25);
+        frame.encode_body(&mut bytes);
+
+        let mut reader = ByteReader::new(&bytes);
+        let header = ObjectHeader::decode(&mut reader).unwrap();
+        assert_eq!(header, frame.header);
+        assert_eq!(CanMessage2::decode(&mut reader, &header, 3).unwrap(), frame);
+        assert!(reader.remaining().is_empty());
+
+        let fd = CanFdMessage64 {
+            header: ObjectHeader::new_v1(ObjectType::CanFdMessage64, 0),
+            channel: 1,
+            dlc: 9,
+            valid_data_bytes: 12,
+            id: 0x321,
+            data: (0..12).collect(),
+            ..Default::default()
+        };
+        let mut body = Vec::new();
+        fd.encode_body(&mut body);
+        assert_eq!(body.len(), CAN_FD_64_FIELDS_SIZE + 12);
+        let decoded = CanFdMessage64::decode(&mut ByteReader::new(&body), &fd.header).unwrap();
+        assert_eq!(decoded, fd);
+
+        // Truncated data and foreign bytes are reported, not panicked on
+        let result = CanFdMessage64::decode(&mut ByteReader::new(&body[..20]), &fd.header);
+        assert_eq!(result, Err(CodecError::UnexpectedEof));
+        let result = ObjectHeader::decode(&mut ByteReader::new(b"LOGG and more bytes"));
+        assert_eq!(result, Err(CodecError::InvalidSignature));
+    }
+
+    #[test]
+    fn test_log_objects_decode_by_type() {
+        let marker = GlobalMarker {
+            commented_event_type: 1,
+            foreground_color: 0x00ff_0000,
+            background_color: 0x0000_00ff,
+            is_relocatable: 1,
+            group_name: "group".into(),
+            marker_name: "marker".into(),
+            description: "description".into(),
+            timestamp: 7_000,
+        };
+        let mut body = Vec::new();
+        marker.encode_body(&mut body);
+
+        let mut header = ObjectHeader::new_v1(ObjectType::GlobalMarker, 0);
+        header.object_time_stamp = marker.timestamp;
+        header.prepare_for_write();
+        header.object_size += body.len() as u32;
+        let decoded = LogObject::decode(&mut ByteReader::new(&body), &header).unwrap();
+        assert_eq!(decoded, LogObject::GlobalMarker(marker));
+
+        // Types without a decoder keep their raw body
+        let mut header = ObjectHeader::new_v1(ObjectType::EnvInteger, 0);
+        header.prepare_for_write();
+        header.object_size += 4;
+        let mut reader = ByteReader::new(&[1, 2, 3, 4]);
+        match LogObject::decode(&mut reader, &header).unwrap() {
+            LogObject::Unhandled { data, .. } => assert_eq!(data, [1, 2, 3, 4]),
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_file_statistics_round_trip_through_slices() {
+        let time = SystemTime {
+            year: 2025,
+            month: 1,
+            day_of_week: 4,
+            day: 2,
+            hour: 3,
+            minute: 4,
+            second: 5,
+            milliseconds: 6,
+        };
+        let stats = FileStatistics {
+            statistics_size: 144,
+            api_number: 4_080_500,
+            application_id: 1,
+            compression_level: 6,
+            application_major: 1,
+            application_minor: 2,
+            file_size: 4_096,
+            uncompressed_file_size: 8_192,
+            object_count: 10,
+            application_build: 3,
+            measurement_start_time: time.clone(),
+            last_object_time: time,
+        };
+        let mut bytes = Vec::new();
+        stats.encode(&mut bytes);
+        assert_eq!(bytes.len(), 144);
+
+        let mut reader = ByteReader::new(&bytes);
+        assert_eq!(FileStatistics::decode(&mut reader).unwrap(), stats);
+        assert!(reader.remaining().is_empty());
+
+        bytes[0] = b'X';
+        let result = FileStatistics::decode(&mut ByteReader::new(&bytes));
+        assert_eq!(result, Err(CodecError::InvalidFileSignature));
+    }
+}
@@ -0,0 +1,236 @@
+//! MOST (Media Oriented Systems Transport) messages and events.
+
+use super::{ByteReader, CodecError};
+use crate::{
+    MostCtrl, MostDataLost, MostGenReg, MostHwMode, MostLightLock, MostNetState, MostPkt2, MostReg,
+    MostSpy, MostStatistic, MostTrigger, ObjectHeader,
+};
+
+impl MostSpy {
+    /// Decodes the body following `header`.
+    pub fn decode(reader: &mut ByteReader, header: &ObjectHeader) -> Result<Self, CodecError> {
+        let channel = reader.read_u16()?;
+        let dir = reader.read_u8()?;
+        reader.skip(1)?; // reserved
+        let source_adr = reader.read_u32()?;
+        let dest_adr = reader.read_u32()?;
+        let msg = reader.read_array()?;
+        reader.skip(1)?; // reserved
+        let r_typ = reader.read_u16()?;
+        let r_typ_adr = reader.read_u8()?;
+        let state = reader.read_u8()?;
+        reader.skip(1)?; // reserved
+        Ok(Self {
+            channel,
+            dir,
+            source_adr,
+            dest_adr,
+            msg,
+            r_typ,
+            r_typ_adr,
+            state,
+            ack_nack: reader.read_u8()?,
+            crc: reader.read_u32()?,
+            timestamp: header.object_time_stamp,
+        })
+    }
+}
+
+impl MostCtrl {
+    /// Decodes the body following `header`.
+    pub fn decode(reader: &mut ByteReader, header: &ObjectHeader) -> Result<Self, CodecError> {
+        let channel = reader.read_u16()?;
+        let dir = reader.read_u8()?;
+        reader.skip(1)?; // reserved
+        let source_adr = reader.read_u32()?;
+        let dest_adr = reader.read_u32()?;
+        let msg = reader.read_array()?;
+        reader.skip(1)?; // reserved
+        let r_typ = reader.read_u16()?;
+        let r_typ_adr = reader.read_u8()?;
+        let state = reader.read_u8()?;
+        reader.skip(1)?; // reserved
+        Ok(Self {
+            channel,
+            dir,
+            source_adr,
+            dest_adr,
+            msg,
+            r_typ,
+            r_typ_adr,
+            state,
+            ack_nack: reader.read_u8()?,
+            timestamp: header.object_time_stamp,
+        })
+    }
+}
+
+impl MostPkt2 {
+    /// Decodes the body following `header`.
+    pub fn decode(reader: &mut ByteReader, header: &ObjectHeader) -> Result<Self, CodecError> {
+        let channel = reader.read_u16()?;
+        let dir = reader.read_u8()?;
+        reader.skip(1)?; // reserved
+        let source_adr = reader.read_u32()?;
+        let dest_adr = reader.read_u32()?;
+        let arbitration = reader.read_u8()?;
+        reader.skip(1)?; // timeRes
+        let quads_to_follow = reader.read_u8()?;
+        reader.skip(1)?; // reserved
+        let crc = reader.read_u16()?;
+        let priority = reader.read_u8()?;
+        let transfer_type = reader.read_u8()?;
+        let state = reader.read_u8()?;
+        reader.skip(1 + 2)?; // reserved
+        let pkt_data_length = reader.read_u32()? as usize;
+        reader.skip(4)?; // reserved
+        Ok(Self {
+            channel,
+            dir,
+            source_adr,
+            dest_adr,
+            arbitration,
+            quads_to_follow,
+            crc,
+            priority,
+            transfer_type,
+            state,
+            pkt_data: reader.read_bytes(pkt_data_length)?.to_vec(),
+            timestamp: header.object_time_stamp,
+            original_timestamp: header.original_time_stamp,
+        })
+    }
+}
+
+impl MostLightLock {
+    /// Decodes the body following `header`.
+    pub fn decode(reader: &mut ByteReader, header: &ObjectHeader) -> Result<Self, CodecError> {
+        let light_lock = Self {
+            channel: reader.read_u16()?,
+            state: reader.read_i16()?,
+            timestamp: header.object_time_stamp,
+        };
+        reader.skip(4)?; // reserved
+        Ok(light_lock)
+    }
+}
+
+impl MostStatistic {
+    /// Decodes the body following `header`.
+    pub fn decode(reader: &mut ByteReader, header: &ObjectHeader) -> Result<Self, CodecError> {
+        Ok(Self {
+            channel: reader.read_u16()?,
+            pkt_cnt: reader.read_u16()?,
+            frm_cnt: reader.read_i32()?,
+            light_cnt: reader.read_i32()?,
+            buffer_level: reader.read_i32()?,
+            timestamp: header.object_time_stamp,
+        })
+    }
+}
+
+impl MostHwMode {
+    /// Decodes the body following `header`.
+    pub fn decode(reader: &mut ByteReader, header: &ObjectHeader) -> Result<Self, CodecError> {
+        let channel = reader.read_u16()?;
+        reader.skip(2)?; // reserved
+        Ok(Self {
+            channel,
+            hw_mode: reader.read_u16()?,
+            hw_mode_mask: reader.read_u16()?,
+            timestamp: header.object_time_stamp,
+            original_timestamp: header.original_time_stamp,
+        })
+    }
+}
+
+impl MostReg {
+    /// Decodes the body following `header`.
+    pub fn decode(reader: &mut ByteReader, header: &ObjectHeader) -> Result<Self, CodecError> {
+        let channel = reader.read_u16()?;
+        let sub_type = reader.read_u8()?;
+        reader.skip(1)?; // reserved
+        Ok(Self {
+            channel,
+            sub_type,
+            handle: reader.read_u32()?,
+            offset: reader.read_u32()?,
+            chip: reader.read_u16()?,
+            reg_data_len: reader.read_u16()?,
+            reg_data: reader.read_array()?,
+            timestamp: header.object_time_stamp,
+            original_timestamp: header.original_time_stamp,
+        })
+    }
+}
+
+impl MostGenReg {
+    /// Decodes the body following `header`.
+    pub fn decode(reader: &mut ByteReader, header: &ObjectHeader) -> Result<Self, CodecError> {
+        let channel = reader.read_u16()?;
+        let sub_type = reader.read_u8()?;
+        reader.skip(1)?; // reserved
+        let handle = reader.read_u32()?;
+        let reg_id = reader.read_u16()?;
+        reader.skip(2 + 4)?; // reserved
+        Ok(Self {
+            channel,
+            sub_type,
+            handle,
+            reg_id,
+            reg_value: reader.read_u64()?,
+            timestamp: header.object_time_stamp,
+            original_timestamp: header.original_time_stamp,
+        })
+    }
+}
+
+impl MostNetState {
+    /// Decodes the body following `header`.
+    pub fn decode(reader: &mut ByteReader, header: &ObjectHeader) -> Result<Self, CodecError> {
+        let net_state = Self {
+            channel: reader.read_u16()?,
+            state_new: reader.read_u16()?,
+            state_old: reader.read_u16()?,
+            timestamp: header.object_time_stamp,
+            original_timestamp: header.original_time_stamp,
+        };
+        reader.skip(2)?; // reserved
+        Ok(net_state)
+    }
+}
+
+impl MostDataLost {
+    /// Decodes the body following `header`.
+    pub fn decode(reader: &mut ByteReader, header: &ObjectHeader) -> Result<Self, CodecError> {
+        let channel = reader.read_u16()?;
+        reader.skip(2)?; // reserved
+        Ok(Self {
+            channel,
+            info: reader.read_u32()?,
+            lost_msgs_ctrl: reader.read_u32()?,
+            lost_msgs_async: reader.read_u32()?,
+            last_good_time_stamp_ns: reader.read_u64()?,
+            next_good_time_stamp_ns: reader.read_u64()?,
+            timestamp: header.object_time_stamp,
+            original_timestamp: header.original_time_stamp,
+        })
+    }
+}
+
+impl MostTrigger {
+    /// Decodes the body following `header`.
+    pub fn decode(reader: &mut ByteReader, header: &ObjectHeader) -> Result<Self, CodecError> {
+        let channel = reader.read_u16()?;
+        reader.skip(2)?; // reserved
+        Ok(Self {
+            channel,
+            mode: reader.read_u16()?,
+            hw: reader.read_u16()?,
+            previous_trigger_value: reader.read_u32()?,
+            current_trigger_value: reader.read_u32()?,
+            timestamp: header.object_time_stamp,
+            original_timestamp: header.original_time_stamp,
+        })
+    }
+}
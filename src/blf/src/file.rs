@@ -1,8 +1,14 @@
 //! Handles the top-level reading and parsing of BLF files.
 
-use crate::{BlfParseError, BlfParseResult, BlfParser, FileStatistics, LogObject};
+#[cfg(feature = "fs")]
+use crate::{BlfParseError, BlfReader};
+use crate::{BlfParseResult, BlfParser, FileStatistics, IdIndex, LogObject, LogSummary};
+#[cfg(feature = "fs")]
 use std::fs::{self, File};
-use std::io::{BufReader, Cursor, Read, Seek, SeekFrom};
+use std::io::Cursor;
+#[cfg(feature = "fs")]
+use std::io::{BufReader, Read, Seek, SeekFrom};
+#[cfg(feature = "fs")]
 use std::path::Path;
 
 /// Represents the complete result of parsing a BLF file.
@@ -12,6 +18,10 @@ pub struct BlfResult {
     pub file_stats: FileStatistics,
     /// A vector of all parsed log objects.
     pub objects: Vec<LogObject>,
+    /// Object counts, time span and channels of `objects`, gathered while parsing.
+    pub summary: LogSummary,
+    /// Positions of each frame ID in `objects`.
+    pub id_index: IdIndex,
 }
 
 impl BlfResult {
@@ -47,6 +57,7 @@ impl BlfResult {
     }
 }
 
+#[cfg(feature = "fs")]
 /// Reads a BLF file from the given path and parses its content.
 ///
 /// This function orchestrates the entire parsing process:
@@ -62,25 +73,156 @@ impl BlfResult {
 ///
 /// A `BlfParseResult` containing a `BlfResult` struct on success, which holds both the
 /// file statistics and the list of parsed log objects.
+///
+/// # Example
+///
+/// ```no_run
+/// let log = blf::read_blf_from_file("measurement.blf")?;
+/// println!("{} objects, started {}", log.objects.len(), log.measurement_start_time_str());
+/// # Ok::<(), blf::BlfParseError>(())
+/// ```
 pub fn read_blf_from_file<P: AsRef<Path>>(path: P) -> BlfParseResult<BlfResult> {
     let data = fs::read(path).map_err(BlfParseError::IoError)?;
-    let mut cursor = Cursor::new(&data[..]);
+    read_blf_from_bytes(&data)
+}
+
+#[cfg(feature = "fs")]
+/// Reads the objects of a BLF file stamped between `from_ns` and `to_ns`.
+///
+/// Times are nanoseconds from the measurement start, like
+/// [`LogObject::timestamp`]. Containers before the window are not decoded
+/// and reading stops at the first one after it, so cutting a short window
+/// out of a long log takes a fraction of reading it whole.
+///
+/// # Example
+///
+/// ```no_run
+/// // The 30 seconds around an incident 2 hours into the log
+/// let (incident_ns, margin_ns) = (7_200_000_000_000, 15_000_000_000);
+/// let log = blf::read_blf_range("measurement.blf", incident_ns - margin_ns, incident_ns + margin_ns)?;
+/// println!("{} objects around the incident", log.objects.len());
+/// # Ok::<(), blf::BlfParseError>(())
+/// ```
+pub fn read_blf_range<P: AsRef<Path>>(
+    path: P,
+    from_ns: u64,
+    to_ns: u64,
+) -> BlfParseResult<BlfResult> {
+    let mut reader = BlfReader::open(path)?.with_time_range(from_ns, to_ns);
+    let mut objects = Vec::new();
+    while let Some(batch) = reader.read_container()? {
+        objects.extend(batch);
+    }
+    Ok(BlfResult {
+        file_stats: reader.file_stats().clone(),
+        id_index: IdIndex::from_objects(&objects),
+        objects,
+        summary: reader.summary().clone(),
+    })
+}
+
+/// Parses a whole BLF file already held in memory.
+///
+/// Used for logs that did not come straight from disk, e.g. ones unpacked
+/// from a compressed archive or received over the network.
+///
+/// # Example
+///
+/// ```
+/// use blf::{BlfParseError, read_blf_from_bytes};
+///
+/// assert!(matches!(read_blf_from_bytes(b"not a log"), Err(BlfParseError::InvalidFileMagic)));
+/// ```
+pub fn read_blf_from_bytes(data: &[u8]) -> BlfParseResult<BlfResult> {
+    read_blf_from_bytes_with(data, &BlfParser::new())
+}
+
+/// Parses a whole BLF file held in memory with `parser`.
+///
+/// A parser made with [`BlfParser::with_object_types`] keeps only the objects
+/// the caller wants and skips decoding the rest.
+///
+/// # Example
+///
+/// ```
+/// use blf::{BlfParser, BlfWriter, CanMessage, GpsEvent, LogObject, ObjectType, SystemTime};
+/// use std::io::Cursor;
+///
+/// let mut writer = BlfWriter::new(Cursor::new(Vec::new()), SystemTime::from_timestamp_nanos(0))?;
+/// writer.write_object(&LogObject::CanMessage(CanMessage::default()))?;
+/// writer.write_object(&LogObject::GpsEvent(GpsEvent::default()))?;
+/// writer.finish()?;
+///
+/// let parser = BlfParser::new().with_object_types([ObjectType::CanMessage]);
+/// let log = blf::read_blf_from_bytes_with(&writer.into_inner().into_inner(), &parser)?;
+/// assert!(matches!(log.objects[..], [LogObject::CanMessage(_)]));
+/// # Ok::<(), blf::BlfParseError>(())
+/// ```
+pub fn read_blf_from_bytes_with(data: &[u8], parser: &BlfParser) -> BlfParseResult<BlfResult> {
+    let mut cursor = Cursor::new(data);
 
     // 1. Parse the file statistics header. This will advance the cursor.
     let file_stats = FileStatistics::read(&mut cursor)?;
 
     // 2. Parse the log objects from the rest of the data slice.
-    let parser = BlfParser::new();
     let remaining_data = &data[cursor.position() as usize..];
     let objects = parser.parse(remaining_data)?;
+    let summary = LogSummary::from_objects(&objects);
+    let id_index = IdIndex::from_objects(&objects);
 
     Ok(BlfResult {
         file_stats,
         objects,
+        summary,
+        id_index,
     })
 }
 
+/// Parses a BLF file held in memory without collecting its objects.
+///
+/// The objects of each log container are handed to `on_objects` as soon as
+/// they are decoded, so a caller that keeps only some of them (or only a
+/// summary) never holds the whole log. Returns the file statistics header.
+///
+/// # Example
+///
+/// ```
+/// use blf::{BlfWriter, CanMessage, LogObject, SystemTime, visit_blf_from_bytes};
+/// use std::io::Cursor;
+///
+/// let mut writer = BlfWriter::new(Cursor::new(Vec::new()), SystemTime::from_timestamp_nanos(0))?;
+/// for id in [0x100, 0x200, 0x100] {
+///     writer.write_object(&LogObject::CanMessage(CanMessage { id, ..Default::default() }))?;
+/// }
+/// writer.finish()?;
+/// let data = writer.into_inner().into_inner();
+///
+/// // Count the frames of one ID without keeping any of them
+/// let mut count = 0;
+/// visit_blf_from_bytes(&data, |objects| {
+///     count += objects
+///         .iter()
+///         .filter(|object| matches!(object, LogObject::CanMessage(msg) if msg.id == 0x100))
+///         .count();
+/// })?;
+/// assert_eq!(count, 2);
+/// # Ok::<(), blf::BlfParseError>(())
+/// ```
+pub fn visit_blf_from_bytes(
+    data: &[u8],
+    on_objects: impl FnMut(Vec<LogObject>),
+) -> BlfParseResult<FileStatistics> {
+    let mut cursor = Cursor::new(data);
+    let file_stats = FileStatistics::read(&mut cursor)?;
+    BlfParser::new().parse_each(&data[cursor.position() as usize..], on_objects)?;
+    Ok(file_stats)
+}
+
+#[cfg(feature = "fs")]
 /// Streaming BLF reader for handling large files efficiently
+///
+/// Reads the file in fixed 1 MB chunks, so containers crossing a chunk boundary
+/// are lost; [`BlfReader`](crate::BlfReader) reads whole containers instead.
 pub struct StreamingBlfReader {
     reader: BufReader<File>,
     file_stats: FileStatistics,
@@ -91,6 +233,7 @@ pub struct StreamingBlfReader {
     current_position: u64,
 }
 
+#[cfg(feature = "fs")]
 impl StreamingBlfReader {
     /// Creates a new streaming BLF reader
     pub fn new<P: AsRef<Path>>(path: P) -> BlfParseResult<Self> {
@@ -178,6 +321,7 @@ impl StreamingBlfReader {
     }
 }
 
+#[cfg(feature = "fs")]
 /// Iterator implementation for streaming BLF reader
 pub struct BlfIterator {
     reader: StreamingBlfReader,
@@ -186,7 +330,9 @@ pub struct BlfIterator {
     batch_index: usize,
 }
 
+#[cfg(feature = "fs")]
 impl BlfIterator {
+    /// Iterate over the objects of `reader`, reading `batch_size` objects at a time
     pub fn new(reader: StreamingBlfReader, batch_size: usize) -> Self {
         Self {
             reader,
@@ -197,6 +343,7 @@ impl BlfIterator {
     }
 }
 
+#[cfg(feature = "fs")]
 impl Iterator for BlfIterator {
     type Item = BlfParseResult<LogObject>;
 
@@ -226,6 +373,7 @@ impl Iterator for BlfIterator {
     }
 }
 
+#[cfg(feature = "fs")]
 /// Convenience function to create a streaming BLF iterator
 pub fn stream_blf_from_file<P: AsRef<Path>>(
     path: P,
@@ -235,34 +383,49 @@ pub fn stream_blf_from_file<P: AsRef<Path>>(
     Ok(BlfIterator::new(reader, batch_size))
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "fs"))]
 mod tests {
     use super::*;
     use crate::test_utils::*;
-    use crate::{CanMessage, LogContainer, ObjectHeader, ObjectType, SystemTime};
+    use crate::{
+        CanMessage, LogContainer, ObjectFlags, ObjectHeader, ObjectHeaderBase, ObjectType,
+        SystemTime,
+    };
     use std::io::Write;
 
-    #[test]
-    fn test_read_blf_from_file_successfully() {
-        // 1. --- Define the objects we want to serialize ---
-        let can_msg_header = ObjectHeader {
-            base: crate::objects::object_header::ObjectHeaderBase {
-                signature: 0x4A424F4C, // "LOBJ"
-                header_size: 32,
-                header_version: 1,
-                object_size: 48, // header + can_msg_fields + data
-                object_type: ObjectType::CanMessage,
-            },
-            object_flags: 0,
-            client_index: 0,
-            object_version: 0,
-            object_time_stamp: 1000,
-            original_time_stamp: None,
-            time_stamp_status: None,
-            reserved: 0,
-        };
+    fn system_time(second: u16) -> SystemTime {
+        SystemTime {
+            year: 2025,
+            month: 11,
+            day: 22,
+            day_of_week: 0,
+            hour: 8,
+            minute: 30,
+            second,
+            milliseconds: 0,
+        }
+    }
+
+    /// A file holding one uncompressed container with one CAN message
+    fn single_can_file() -> (tempfile::NamedTempFile, FileStatistics, CanMessage) {
+        // 1. --- Define the object we want to serialize ---
         let can_message = CanMessage {
-            header: can_msg_header,
+            header: ObjectHeader {
+                base: ObjectHeaderBase {
+                    signature: 0x4A424F4C, // "LOBJ"
+                    header_size: 32,
+                    header_version: 1,
+                    object_size: 48, // header + can_msg_fields + data
+                    object_type: ObjectType::CanMessage,
+                },
+                object_flags: ObjectFlags::TimeOneNans as u32,
+                client_index: 0,
+                object_version: 0,
+                object_time_stamp: 1000,
+                original_time_stamp: None,
+                time_stamp_status: None,
+                reserved: 0,
+            },
             channel: 1,
             flags: 0,
             dlc: 8,
@@ -275,32 +438,12 @@ mod tests {
         add_padding(&mut inner_object_bytes);
 
         // 3. --- Create and serialize the LogContainer ---
-        let container_header = ObjectHeader {
-            base: crate::objects::object_header::ObjectHeaderBase {
-                signature: 0x4A424F4C, // "LOBJ"
-                header_size: 32,       // 修正header_size为实际大小
-                header_version: 1,
-                object_size: 0, // Will be calculated later
-                object_type: ObjectType::LogContainer,
-            },
-            object_flags: 0,
-            client_index: 0,
-            object_version: 0,
-            object_time_stamp: 0,
-            original_time_stamp: None,
-            time_stamp_status: None,
-        };
         let mut log_container = LogContainer {
-            header: container_header.clone(),
+            header: ObjectHeaderBase::new(1, ObjectType::LogContainer),
             compression_method: 0, // No compression
             uncompressed_data: inner_object_bytes.clone(),
         };
-        // Correctly calculate the object size
-        let calculated_size = log_container.calculate_object_size();
-
-        // 更新LogContainer的header大小
-        log_container.header.object_size = calculated_size;
-
+        log_container.header.object_size = log_container.calculate_object_size();
         let mut container_bytes = serialize_log_container(&log_container);
         add_padding(&mut container_bytes);
 
@@ -316,44 +459,28 @@ mod tests {
             uncompressed_file_size: (208 + inner_object_bytes.len()) as u64,
             object_count: 1,
             application_build: 0,
-            measurement_start_time: SystemTime {
-                year: 2025,
-                month: 11,
-                day: 22,
-                day_of_week: 0,
-                hour: 8,
-                minute: 30,
-                second: 0,
-                milliseconds: 0,
-            },
-            last_object_time: SystemTime {
-                year: 2025,
-                month: 11,
-                day: 22,
-                day_of_week: 0,
-                hour: 8,
-                minute: 30,
-                second: 1,
-                milliseconds: 0,
-            },
+            measurement_start_time: system_time(0),
+            last_object_time: system_time(1),
         };
-        let file_stats_bytes = serialize_file_statistics(&file_stats);
-
-        // 5. --- Combine all parts into a single byte array ---
-        let mut blf_data = Vec::new();
-        blf_data.extend(file_stats_bytes.clone());
-        blf_data.extend(container_bytes.clone());
 
-        // 6. --- Write to a temporary file and parse it ---
+        // 5. --- Write both parts to a temporary file ---
         let mut temp_file = tempfile::NamedTempFile::new().unwrap();
-        temp_file.write_all(&blf_data).unwrap();
+        temp_file
+            .write_all(&serialize_file_statistics(&file_stats))
+            .unwrap();
+        temp_file.write_all(&container_bytes).unwrap();
+        temp_file.flush().unwrap();
+        (temp_file, file_stats, can_message)
+    }
+
+    #[test]
+    fn test_read_blf_from_file_successfully() {
+        let (temp_file, file_stats, can_message) = single_can_file();
 
         let result = read_blf_from_file(temp_file.path()).unwrap();
 
-        // 7. --- Assert the results ---
         assert_eq!(result.file_stats, file_stats);
         assert_eq!(result.objects.len(), 1);
-
         if let Some(LogObject::CanMessage(parsed_can_message)) = result.objects.first() {
             assert_eq!(parsed_can_message, &can_message);
         } else {
@@ -363,100 +490,7 @@ mod tests {
 
     #[test]
     fn test_streaming_blf_reader() {
-        // Create a simple BLF file for testing
-        let can_msg_header = ObjectHeader {
-            signature: 0x4A424F4C,
-            header_size: 32,
-            header_version: 1,
-            object_size: 48,
-            object_type: ObjectType::CanMessage,
-            object_flags: 0,
-            client_index: 0,
-            object_version: 0,
-            object_time_stamp: 1000,
-            original_time_stamp: None,
-            time_stamp_status: None,
-        };
-
-        let can_message = CanMessage {
-            header: can_msg_header,
-            channel: 1,
-            flags: 0,
-            dlc: 8,
-            id: 0x123,
-            data: [1, 2, 3, 4, 5, 6, 7, 8],
-        };
-
-        let mut inner_object_bytes = serialize_can_message(&can_message);
-        add_padding(&mut inner_object_bytes);
-
-        let container_header = ObjectHeader {
-            signature: 0x4A424F4C,
-            header_size: 32,
-            header_version: 1,
-            object_size: 0,
-            object_type: ObjectType::LogContainer,
-            object_flags: 0,
-            client_index: 0,
-            object_version: 0,
-            object_time_stamp: 0,
-            original_time_stamp: None,
-            time_stamp_status: None,
-        };
-
-        let mut log_container = LogContainer {
-            header: container_header.clone(),
-            compression_method: 0,
-            uncompressed_data: inner_object_bytes.clone(),
-        };
-
-        log_container.header.object_size = log_container.calculate_object_size();
-
-        let mut container_bytes = serialize_log_container(&log_container);
-        add_padding(&mut container_bytes);
-
-        let file_stats = FileStatistics {
-            statistics_size: 208,
-            api_number: 0,
-            application_id: 1,
-            compression_level: 0,
-            application_major: 1,
-            application_minor: 0,
-            file_size: (208 + container_bytes.len()) as u64,
-            uncompressed_file_size: (208 + inner_object_bytes.len()) as u64,
-            object_count: 1,
-            application_build: 0,
-            measurement_start_time: SystemTime {
-                year: 2025,
-                month: 11,
-                day: 22,
-                day_of_week: 0,
-                hour: 8,
-                minute: 30,
-                second: 0,
-                milliseconds: 0,
-            },
-            last_object_time: SystemTime {
-                year: 2025,
-                month: 11,
-                day: 22,
-                day_of_week: 0,
-                hour: 8,
-                minute: 30,
-                second: 1,
-                milliseconds: 0,
-            },
-        };
-
-        let file_stats_bytes = serialize_file_statistics(&file_stats);
-
-        let mut blf_data = Vec::new();
-        blf_data.extend(file_stats_bytes);
-        blf_data.extend(container_bytes);
-
-        let mut temp_file = tempfile::NamedTempFile::new().unwrap();
-        temp_file.write_all(&blf_data).unwrap();
-        temp_file.flush().unwrap();
+        let (temp_file, file_stats, can_message) = single_can_file();
 
         // Test streaming reader
         let mut reader = StreamingBlfReader::new(temp_file.path()).unwrap();
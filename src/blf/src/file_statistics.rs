@@ -1,10 +1,15 @@
 //! File statistics header definition.
 
-use crate::{BlfParseError, BlfParseResult};
-use byteorder::{LittleEndian, ReadBytesExt};
-use std::io::{Cursor, Read};
+#[cfg(feature = "std")]
+use crate::BlfParseResult;
+#[cfg(feature = "std")]
+use crate::blf_core::decode_at;
+use alloc::format;
+use alloc::string::String;
+#[cfg(feature = "std")]
+use std::io::{Cursor, Write};
 
-const FILE_SIGNATURE: u32 = 0x47474f4c; // "LOGG" (注意字节序)
+pub(crate) const FILE_SIGNATURE: u32 = 0x47474f4c; // "LOGG" (注意字节序)
 
 /// Represents the Windows SYSTEMTIME structure.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -29,40 +34,28 @@ pub struct SystemTime {
 
 impl SystemTime {
     /// Reads a `SystemTime` from a byte cursor.
+    #[cfg(feature = "std")]
     pub fn read(cursor: &mut Cursor<&[u8]>) -> BlfParseResult<Self> {
-        Ok(Self {
-            year: cursor.read_u16::<LittleEndian>()?,
-            month: cursor.read_u16::<LittleEndian>()?,
-            day_of_week: cursor.read_u16::<LittleEndian>()?,
-            day: cursor.read_u16::<LittleEndian>()?,
-            hour: cursor.read_u16::<LittleEndian>()?,
-            minute: cursor.read_u16::<LittleEndian>()?,
-            second: cursor.read_u16::<LittleEndian>()?,
-            milliseconds: cursor.read_u16::<LittleEndian>()?,
-        })
+        decode_at(cursor, Self::decode)
     }
 
     /// 转换为 Unix 时间戳（纳秒）
     ///
     /// 返回自 1970-01-01 00:00:00 UTC 以来的纳秒数
     pub fn to_timestamp_nanos(&self) -> i64 {
-        use chrono::{TimeZone, Utc};
-
-        if let Some(dt) = Utc
-            .with_ymd_and_hms(
-                self.year as i32,
-                self.month as u32,
-                self.day as u32,
-                self.hour as u32,
-                self.minute as u32,
-                self.second as u32,
-            )
-            .single()
-        {
-            dt.timestamp_nanos_opt().unwrap_or(0) + (self.milliseconds as i64) * 1_000_000
-        } else {
-            0
+        let valid = (1..=12).contains(&self.month)
+            && self.day >= 1
+            && self.day <= days_in_month(self.year as i64, self.month as u32)
+            && self.hour < 24
+            && self.minute < 60
+            && self.second < 60;
+        if !valid {
+            return 0;
         }
+        let days = days_from_civil(self.year as i64, self.month as u32, self.day as u32);
+        let seconds =
+            days * 86_400 + self.hour as i64 * 3_600 + self.minute as i64 * 60 + self.second as i64;
+        seconds * NANOS_PER_SECOND + (self.milliseconds as i64) * 1_000_000
     }
 
     /// 添加纳秒偏移，返回新的时间戳（纳秒）
@@ -95,20 +88,86 @@ impl SystemTime {
     /// # 返回
     /// 格式化的时间字符串
     pub fn format_with_offset(&self, offset_ns: u64) -> String {
-        use chrono::DateTime;
-
         let absolute_ns = self.add_nanoseconds(offset_ns);
+        let time = Self::from_timestamp_nanos(absolute_ns);
+        format!(
+            "{:04}-{:02}-{:02} {:02}:{:02}:{:02}.{:06}",
+            time.year,
+            time.month,
+            time.day,
+            time.hour,
+            time.minute,
+            time.second,
+            absolute_ns.rem_euclid(NANOS_PER_SECOND) / 1_000
+        )
+    }
 
-        // 转换为 DateTime
-        if let Some(dt) = DateTime::from_timestamp(
-            absolute_ns / 1_000_000_000,
-            (absolute_ns % 1_000_000_000) as u32,
-        ) {
-            dt.format("%Y-%m-%d %H:%M:%S%.6f").to_string()
-        } else {
-            format!("Invalid time (offset: {} ns)", offset_ns)
+    /// Builds a `SystemTime` from a Unix timestamp in nanoseconds (UTC).
+    pub fn from_timestamp_nanos(timestamp_ns: i64) -> Self {
+        let seconds = timestamp_ns.div_euclid(NANOS_PER_SECOND);
+        let nanos = timestamp_ns.rem_euclid(NANOS_PER_SECOND);
+        let days = seconds.div_euclid(86_400);
+        let second_of_day = seconds.rem_euclid(86_400);
+        let (year, month, day) = civil_from_days(days);
+        Self {
+            year: year as u16,
+            month: month as u16,
+            // 1970-01-01 是星期四
+            day_of_week: (days + 4).rem_euclid(7) as u16,
+            day: day as u16,
+            hour: (second_of_day / 3_600) as u16,
+            minute: (second_of_day / 60 % 60) as u16,
+            second: (second_of_day % 60) as u16,
+            milliseconds: (nanos / 1_000_000) as u16,
         }
     }
+
+    /// Writes a `SystemTime` to a byte stream.
+    #[cfg(feature = "std")]
+    pub fn write<W: Write>(&self, writer: &mut W) -> BlfParseResult<()> {
+        let mut bytes = Vec::with_capacity(16);
+        self.encode(&mut bytes);
+        writer.write_all(&bytes)?;
+        Ok(())
+    }
+}
+
+const NANOS_PER_SECOND: i64 = 1_000_000_000;
+
+/// 公历日期到 1970-01-01 起的天数（Howard Hinnant 的 days_from_civil 算法）
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let month = month as i64;
+    let day_of_year =
+        (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day as i64 - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// 1970-01-01 起的天数到公历 (年, 月, 日)，`days_from_civil` 的逆运算
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+fn days_in_month(year: i64, month: u32) -> u16 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
 }
 
 /// Represents the file statistics header at the beginning of a BLF file.
@@ -158,65 +217,25 @@ impl FileStatistics {
     /// 12. measurementStartTime (16 bytes - SYSTEMTIME)
     /// 13. lastObjectTime (16 bytes - SYSTEMTIME)
     /// 14. reserved/restorePointsOffset (variable)
+    #[cfg(feature = "std")]
     pub fn read(cursor: &mut Cursor<&[u8]>) -> BlfParseResult<Self> {
-        // 读取文件签名 (字节序: 0x47474f4c = "LOGG")
-        let signature = cursor.read_u32::<LittleEndian>()?;
-        if signature != FILE_SIGNATURE {
-            return Err(BlfParseError::InvalidFileMagic);
-        }
-
-        // 读取统计信息大小
-        let statistics_size = cursor.read_u32::<LittleEndian>()?;
-
-        // 读取 API number (在144字节格式中可能为0)
-        let api_number = cursor.read_u32::<LittleEndian>()?;
-
-        // 读取应用程序信息
-        let application_id = cursor.read_u8()?;
-        let compression_level = cursor.read_u8()?;
-        let application_major = cursor.read_u8()?;
-        let application_minor = cursor.read_u8()?;
-
-        // 读取文件统计信息
-        let file_size = cursor.read_u64::<LittleEndian>()?;
-        let uncompressed_file_size = cursor.read_u64::<LittleEndian>()?;
-        let object_count = cursor.read_u32::<LittleEndian>()?;
-
-        // 读取 application build
-        let application_build = cursor.read_u32::<LittleEndian>()?;
-
-        // 读取时间戳信息
-        let measurement_start_time = SystemTime::read(cursor)?;
-        let last_object_time = SystemTime::read(cursor)?;
-
-        // 读取剩余的保留字段
-        let current_pos = cursor.position();
-        let remaining_bytes = statistics_size as u64 - current_pos;
-        if remaining_bytes > 0 {
-            let mut _rest = vec![0u8; remaining_bytes as usize];
-            cursor.read_exact(&mut _rest)?;
-        }
+        decode_at(cursor, Self::decode)
+    }
 
-        Ok(FileStatistics {
-            statistics_size,
-            api_number,
-            application_id,
-            compression_level,
-            application_major,
-            application_minor,
-            file_size,
-            uncompressed_file_size,
-            object_count,
-            application_build,
-            measurement_start_time,
-            last_object_time,
-        })
+    /// Writes the `FileStatistics` header, padded with zeros to `statistics_size`.
+    #[cfg(feature = "std")]
+    pub fn write<W: Write>(&self, writer: &mut W) -> BlfParseResult<()> {
+        let mut bytes = Vec::with_capacity(self.statistics_size as usize);
+        self.encode(&mut bytes);
+        writer.write_all(&bytes)?;
+        Ok(())
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "std"))]
 mod tests {
     use super::*;
+    use crate::BlfParseError;
     use crate::test_utils::*;
 
     #[test]
@@ -312,7 +331,7 @@ mod tests {
         // 0x0F: 0x67 (103 - application_minor)
         // Note: application_build comes later in standard format
 
-        let mut data = vec![0u8; 144];
+        let mut data = [0u8; 144];
         let mut cursor = Cursor::new(&mut data[..]);
 
         // Write the header
@@ -378,7 +397,7 @@ mod tests {
 
     #[test]
     fn test_read_file_statistics_invalid_signature() {
-        let mut data = vec![0; 104];
+        let mut data = [0u8; 104];
         use byteorder::{LittleEndian, WriteBytesExt};
         // Write an invalid signature
         (&mut data[0..4])
@@ -389,4 +408,38 @@ mod tests {
 
         assert!(matches!(result, Err(BlfParseError::InvalidFileMagic)));
     }
+
+    #[test]
+    fn test_system_time_unix_conversion() {
+        let time = SystemTime {
+            year: 2025,
+            month: 1,
+            day_of_week: 4,
+            day: 2,
+            hour: 3,
+            minute: 4,
+            second: 5,
+            milliseconds: 6,
+        };
+        assert_eq!(time.to_timestamp_nanos(), 1_735_787_045_006_000_000);
+        assert_eq!(
+            SystemTime::from_timestamp_nanos(time.to_timestamp_nanos()),
+            time
+        );
+
+        // Leap day, and the offset carries into the next month
+        let leap = SystemTime::from_timestamp_nanos(1_709_251_199_000_000_000);
+        assert_eq!((leap.month, leap.day, leap.day_of_week), (2, 29, 4));
+        assert_eq!(
+            leap.format_with_offset(1_001_500_000),
+            "2024-03-01 00:00:00.001500"
+        );
+
+        let invalid = SystemTime { month: 13, ..time };
+        assert_eq!(invalid.to_timestamp_nanos(), 0);
+        assert_eq!(
+            SystemTime::from_timestamp_nanos(0).format(),
+            "1970-01-01 00:00:00.000"
+        );
+    }
 }
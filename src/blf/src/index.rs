@@ -0,0 +1,150 @@
+//! Positions of every frame ID in a log.
+
+use crate::LogObject;
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+
+/// Positions of the objects of each (channel, ID) pair, in log order.
+///
+/// Built once after a log is read, it answers "every frame of this ID"
+/// without scanning the log again, e.g. for an ID filter or the cycle time
+/// of a message. Objects without an ID (errors, markers, …) are not listed.
+///
+/// # Example
+/// ```
+/// use blf::{CanMessage, IdIndex, LogObject};
+///
+/// let objects: Vec<LogObject> = [0x100, 0x200, 0x100]
+///     .into_iter()
+///     .map(|id| LogObject::CanMessage(CanMessage { channel: 1, id, ..Default::default() }))
+///     .collect();
+/// let index = IdIndex::from_objects(&objects);
+/// assert_eq!(index.positions(1, 0x100), [0, 2]);
+/// assert_eq!(index.matching(None, Some(0x200)), [1]);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IdIndex {
+    by_id: BTreeMap<(u16, u32), Vec<usize>>,
+    /// Number of objects indexed, IDs or not; the position of the next one.
+    object_count: usize,
+}
+
+impl IdIndex {
+    /// Indexes `objects`.
+    pub fn from_objects(objects: &[LogObject]) -> Self {
+        let mut index = Self::default();
+        index.extend(objects);
+        index
+    }
+
+    /// Adds `objects`, which follow the objects indexed so far in the log.
+    pub fn extend(&mut self, objects: &[LogObject]) {
+        for object in objects {
+            if let (Some(channel), Some(id)) = (object.channel(), object.id()) {
+                self.by_id
+                    .entry((channel, id))
+                    .or_default()
+                    .push(self.object_count);
+            }
+            self.object_count += 1;
+        }
+    }
+
+    /// Number of objects indexed.
+    pub fn object_count(&self) -> usize {
+        self.object_count
+    }
+
+    /// Positions of the frames of `id` on `channel`, ascending.
+    pub fn positions(&self, channel: u16, id: u32) -> &[usize] {
+        self.by_id
+            .get(&(channel, id))
+            .map_or(&[][..], Vec::as_slice)
+    }
+
+    /// Every (channel, ID) pair in the log, ordered by channel then ID.
+    pub fn ids(&self) -> impl Iterator<Item = (u16, u32)> + '_ {
+        self.by_id.keys().copied()
+    }
+
+    /// Positions of the frames on `channel` with `id`, ascending; `None` matches any.
+    pub fn matching(&self, channel: Option<u16>, id: Option<u32>) -> Vec<usize> {
+        let mut positions: Vec<usize> = match channel {
+            Some(channel) => self.by_id.range((channel, 0)..=(channel, u32::MAX)),
+            None => self.by_id.range(..),
+        }
+        .filter(|((_, frame_id), _)| id.is_none_or(|id| *frame_id == id))
+        .flat_map(|(_, positions)| positions.iter().copied())
+        .collect();
+        positions.sort_unstable();
+        positions
+    }
+
+    /// Time between consecutive frames of `id` on `channel`, in nanoseconds.
+    ///
+    /// `objects` are the objects this index was built from.
+    pub fn cycle_times<'a>(
+        &'a self,
+        objects: &'a [LogObject],
+        channel: u16,
+        id: u32,
+    ) -> impl Iterator<Item = u64> + 'a {
+        self.positions(channel, id).windows(2).filter_map(|pair| {
+            let (earlier, later) = (objects.get(pair[0])?, objects.get(pair[1])?);
+            Some(later.timestamp().saturating_sub(earlier.timestamp()))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CanMessage;
+    use alloc::vec;
+
+    fn can(channel: u16, id: u32, timestamp: u64) -> LogObject {
+        let mut msg = CanMessage {
+            channel,
+            id,
+            ..Default::default()
+        };
+        msg.header.object_time_stamp = timestamp;
+        LogObject::CanMessage(msg)
+    }
+
+    #[test]
+    fn test_index_positions_matching_and_cycle_times() {
+        let objects = vec![
+            can(1, 0x100, 0),
+            can(2, 0x100, 5),
+            can(1, 0x200, 7),
+            can(1, 0x100, 10),
+        ];
+        let mut index = IdIndex::from_objects(&objects[..2]);
+        index.extend(&objects[2..]);
+        assert_eq!(index, IdIndex::from_objects(&objects));
+        assert_eq!(index.object_count(), 4);
+        assert_eq!(index.positions(1, 0x100), [0, 3]);
+        assert!(index.positions(3, 0x100).is_empty());
+        assert_eq!(
+            index.ids().collect::<Vec<_>>(),
+            [(1, 0x100), (1, 0x200), (2, 0x100)]
+        );
+        assert_eq!(index.matching(None, Some(0x100)), [0, 1, 3]);
+        assert_eq!(index.matching(Some(1), None), [0, 2, 3]);
+        assert_eq!(index.matching(None, None), [0, 1, 2, 3]);
+        assert_eq!(
+            index.cycle_times(&objects, 1, 0x100).collect::<Vec<_>>(),
+            [10]
+        );
+
+        // Objects without an ID still take up a position
+        let unhandled = LogObject::Unhandled {
+            object_type: 999,
+            timestamp: 15,
+            data: Vec::new(),
+        };
+        index.extend(&[unhandled, can(1, 0x100, 20)]);
+        assert_eq!(index.positions(1, 0x100), [0, 3, 5]);
+    }
+}
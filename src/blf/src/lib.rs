@@ -1,23 +1,119 @@
-//! lib.rs
-//
-// A production-ready BLF (Binary Logging Format) parser library,
-// translated from the C++ implementation.
-
-//#![deny(missing_docs)]
+//! Reading and writing Vector BLF (Binary Logging Format) files.
+//!
+//! A BLF file is a statistics header ([`FileStatistics`]) followed by log
+//! containers, each holding (usually zlib-compressed) objects: CAN, CAN FD,
+//! LIN, FlexRay, Ethernet and MOST frames, GPS fixes, comments and so on.
+//! The crate decodes them into [`LogObject`]s and writes them back.
+//! It is a translation of the C++ Vector BLF library and uses no `unsafe` code.
+//!
+//! # Quickstart
+//!
+//! ```
+//! # #[cfg(feature = "fs")]
+//! use blf::{CanMessage, LogObject, SystemTime, read_blf_from_file, write_blf_to_file};
+//!
+//! # #[cfg(feature = "fs")]
+//! # fn main() -> Result<(), blf::BlfParseError> {
+//! let path = std::env::temp_dir().join(format!("blf_quickstart_{}.blf", std::process::id()));
+//!
+//! // Write two CAN frames, 10 ms apart
+//! let frames: Vec<LogObject> = (0..2u64)
+//!     .map(|i| {
+//!         let mut msg = CanMessage { channel: 1, id: 0x123, dlc: 8, ..Default::default() };
+//!         msg.data[0] = i as u8;
+//!         msg.header.object_time_stamp = i * 10_000_000;
+//!         LogObject::CanMessage(msg)
+//!     })
+//!     .collect();
+//! let start = SystemTime::from_timestamp_nanos(1_700_000_000_000_000_000);
+//! let stats = write_blf_to_file(&path, start, &frames)?;
+//! assert_eq!(stats.object_count, 2);
+//!
+//! // Read them back
+//! let log = read_blf_from_file(&path)?;
+//! for object in &log.objects {
+//!     if let LogObject::CanMessage(msg) = object {
+//!         println!("{} ns  CH{}  {:#x}  {:02X?}", object.timestamp(), msg.channel, msg.id, msg.data);
+//!     }
+//! }
+//! assert_eq!(log.objects.len(), 2);
+//! assert_eq!(log.objects[1].timestamp(), 10_000_000);
+//! # std::fs::remove_file(&path).ok();
+//! # Ok(())
+//! # }
+//! # #[cfg(not(feature = "fs"))]
+//! # fn main() {}
+//! ```
+//!
+//! Logs too large to hold at once are read lazily with [`BlfReader`], or
+//! visited container by container with [`visit_blf_from_bytes`];
+//! [`BlfWriter`] writes objects as they arrive.
+//!
+//! Every object also encodes to and decodes from plain byte slices
+//! ([`ByteReader`], `encode`/`decode`, [`LogObject::decode`]) using only
+//! `core` and `alloc`, for firmware that writes BLF objects itself.
+//!
+//! # Features
+//!
+//! All are on by default; gateways that only iterate CAN objects out of a
+//! buffer can turn them off with `default-features = false`.
+//!
+//! - `std`: the parser, readers, writers and everything else built on
+//!   `std::io`. Without it the crate is `no_std` and keeps the object model,
+//!   the slice codec, [`IdIndex`] and [`LogSummary`], which need only `alloc`.
+//! - `compression`: zlib log containers, through `flate2`. Without it the
+//!   parser fails on compressed containers with
+//!   [`BlfParseError::UnsupportedCompression`] and [`BlfWriter`] stores
+//!   containers uncompressed.
+//! - `fs`: everything that takes a path, such as `read_blf_from_file`,
+//!   `BlfReader::open`, `StreamingBlfReader`, `BlfWriter::create` and `RotatingBlfWriter`.
+//!   Without it the crate only works on byte slices and `Write + Seek` sinks.
 
+#![cfg_attr(not(feature = "std"), no_std)]
+#![deny(missing_docs)]
 #![allow(dead_code)] // Allow unused methods (e.g., write methods for future functionality)
 
+extern crate alloc;
+
 mod blf_core;
+mod codec;
+#[cfg(feature = "std")]
 mod file;
 mod file_statistics;
+mod index;
+mod log_object;
 mod objects;
+#[cfg(feature = "std")]
 mod parser;
+#[cfg(feature = "std")]
+mod reader;
+#[cfg(feature = "std")]
+mod stream;
+mod summary;
+#[cfg(feature = "std")]
+mod timestamps;
+#[cfg(feature = "std")]
+mod writer;
 
-#[cfg(test)]
+#[cfg(all(test, feature = "std"))]
 mod test_utils;
 
 pub use blf_core::*;
+pub use codec::*;
+#[cfg(feature = "std")]
 pub use file::*;
 pub use file_statistics::*;
+pub use index::*;
+pub use log_object::*;
 pub use objects::*;
+#[cfg(feature = "std")]
 pub use parser::*;
+#[cfg(feature = "std")]
+pub use reader::*;
+#[cfg(feature = "std")]
+pub use stream::*;
+pub use summary::*;
+#[cfg(feature = "std")]
+pub use timestamps::*;
+#[cfg(feature = "std")]
+pub use writer::*;
@@ -0,0 +1,398 @@
+//! The objects a BLF log is made of.
+
+use crate::ObjectType;
+use crate::objects::*;
+use alloc::vec::Vec;
+
+/// One object read from a BLF file
+///
+/// ```
+/// use blf::{CanMessage, LogObject};
+///
+/// let object = LogObject::CanMessage(CanMessage { channel: 1, id: 0x123, ..Default::default() });
+/// if let LogObject::CanMessage(msg) = &object {
+///     assert_eq!(msg.id, 0x123);
+/// }
+/// assert_eq!(object.timestamp(), 0);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub enum LogObject {
+    /// CAN frame
+    CanMessage(CanMessage),
+    /// CAN frame, second version
+    CanMessage2(CanMessage2),
+    /// CAN error frame
+    CanErrorFrame(CanErrorFrame),
+    /// CAN FD frame
+    CanFdMessage(CanFdMessage),
+    /// CAN FD frame, 64-byte version
+    CanFdMessage64(CanFdMessage64),
+    /// CAN overload frame
+    CanOverloadFrame(CanOverloadFrame),
+    /// CAN driver statistics
+    CanDriverStatistic(CanDriverStatistic),
+    /// CAN driver error
+    CanDriverError(CanDriverError),
+    /// LIN frame
+    LinMessage(LinMessage),
+    /// LIN frame, second version
+    LinMessage2(LinMessage2),
+    /// LIN checksum error
+    LinCrcError(LinCrcError),
+    /// LIN data length information
+    LinDlcInfo(LinDlcInfo),
+    /// LIN receive error
+    LinReceiveError(LinReceiveError),
+    /// LIN frame without response
+    LinSendError(LinSendError),
+    /// LIN slave timeout
+    LinSlaveTimeout(LinSlaveTimeout),
+    /// LIN scheduler mode change
+    LinSchedulerModeChange(LinSchedulerModeChange),
+    /// LIN synchronisation error
+    LinSyncError(LinSyncError),
+    /// LIN baud rate detected
+    LinBaudrateEvent(LinBaudrateEvent),
+    /// LIN sleep mode event
+    LinSleepModeEvent(LinSleepModeEvent),
+    /// LIN wake-up event
+    LinWakeupEvent(LinWakeupEvent),
+    /// FlexRay data frame, old format
+    FlexRayData(FlexRayData),
+    /// FlexRay sync frame, old format
+    FlexRaySync(FlexRaySync),
+    /// FlexRay V6 message
+    FlexRayV6Message(FlexRayV6Message),
+    /// FlexRay V6 start of cycle
+    FlexRayV6StartCycleEvent(FlexRayV6StartCycleEvent),
+    /// FlexRay status
+    FlexRayStatusEvent(FlexRayStatusEvent),
+    /// FlexRay error
+    FlexRayVFrError(FlexRayVFrError),
+    /// FlexRay controller status
+    FlexRayVFrStatus(FlexRayVFrStatus),
+    /// FlexRay start of cycle
+    FlexRayVFrStartCycle(FlexRayVFrStartCycle),
+    /// FlexRay received frame
+    FlexRayVFrReceiveMsg(FlexRayVFrReceiveMsg),
+    /// FlexRay received frame, extended
+    FlexRayVFrReceiveMsgEx(FlexRayVFrReceiveMsgEx),
+    /// Ethernet frame
+    EthernetFrame(EthernetFrame),
+    // Environment variables
+    // EnvInteger(EnvInteger),
+    // EnvDouble(EnvDouble),
+    // EnvString(EnvString),
+    /// Application trigger
+    AppTrigger(AppTrigger),
+    /// Comment attached to the log
+    EventComment(EventComment),
+    /// Marker set in the measurement
+    GlobalMarker(GlobalMarker),
+    /// GPS position
+    GpsEvent(GpsEvent),
+    /// MOST spy message
+    MostSpy(MostSpy),
+    /// MOST control message
+    MostCtrl(MostCtrl),
+    /// MOST packet
+    MostPkt2(MostPkt2),
+    /// MOST light lock state
+    MostLightLock(MostLightLock),
+    /// MOST statistics
+    MostStatistic(MostStatistic),
+    /// MOST hardware mode
+    MostHwMode(MostHwMode),
+    /// MOST register data
+    MostReg(MostReg),
+    /// MOST general register data
+    MostGenReg(MostGenReg),
+    /// MOST network state
+    MostNetState(MostNetState),
+    /// MOST data lost
+    MostDataLost(MostDataLost),
+    /// MOST trigger
+    MostTrigger(MostTrigger),
+    /// Object of a type this crate does not decode, kept as raw bytes
+    Unhandled {
+        /// Raw [`ObjectType`] number
+        object_type: u32,
+        /// Timestamp from the object header
+        timestamp: u64,
+        /// Object body after the header
+        data: Vec<u8>,
+    },
+}
+
+impl LogObject {
+    /// Returns the timestamp of the log object
+    pub fn timestamp(&self) -> u64 {
+        match self {
+            LogObject::CanMessage(msg) => msg.header.object_time_stamp,
+            LogObject::CanMessage2(msg) => msg.header.object_time_stamp,
+            LogObject::CanErrorFrame(msg) => msg.header.object_time_stamp,
+            LogObject::CanFdMessage(msg) => msg.header.object_time_stamp,
+            LogObject::CanFdMessage64(msg) => msg.header.object_time_stamp,
+            LogObject::CanOverloadFrame(msg) => msg.header.object_time_stamp,
+            LogObject::CanDriverStatistic(msg) => msg.header.object_time_stamp,
+            LogObject::CanDriverError(msg) => msg.header.object_time_stamp,
+            LogObject::LinMessage(msg) => msg.header.object_time_stamp,
+            LogObject::LinMessage2(msg) => msg.header.object_time_stamp,
+            LogObject::LinCrcError(msg) => msg.header.object_time_stamp,
+            LogObject::LinDlcInfo(msg) => msg.header.object_time_stamp,
+            LogObject::LinReceiveError(msg) => msg.header.object_time_stamp,
+            LogObject::LinSendError(msg) => msg.header.object_time_stamp,
+            LogObject::LinSlaveTimeout(msg) => msg.header.object_time_stamp,
+            LogObject::LinSchedulerModeChange(msg) => msg.header.object_time_stamp,
+            LogObject::LinSyncError(msg) => msg.header.object_time_stamp,
+            LogObject::LinBaudrateEvent(msg) => msg.header.object_time_stamp,
+            LogObject::LinSleepModeEvent(msg) => msg.header.object_time_stamp,
+            LogObject::LinWakeupEvent(msg) => msg.header.object_time_stamp,
+            LogObject::FlexRayData(msg) => msg.timestamp,
+            LogObject::FlexRaySync(msg) => msg.timestamp,
+            LogObject::FlexRayV6Message(msg) => msg.timestamp,
+            LogObject::FlexRayV6StartCycleEvent(msg) => msg.timestamp,
+            LogObject::FlexRayStatusEvent(msg) => msg.timestamp,
+            LogObject::FlexRayVFrError(msg) => msg.timestamp,
+            LogObject::FlexRayVFrStatus(msg) => msg.timestamp,
+            LogObject::FlexRayVFrStartCycle(msg) => msg.timestamp,
+            LogObject::FlexRayVFrReceiveMsg(msg) => msg.timestamp,
+            LogObject::FlexRayVFrReceiveMsgEx(msg) => msg.timestamp,
+            LogObject::EthernetFrame(msg) => msg.timestamp,
+            LogObject::AppTrigger(msg) => msg.timestamp,
+            LogObject::EventComment(msg) => msg.timestamp,
+            LogObject::GlobalMarker(msg) => msg.timestamp,
+            LogObject::GpsEvent(msg) => msg.timestamp,
+            LogObject::MostSpy(msg) => msg.timestamp,
+            LogObject::MostCtrl(msg) => msg.timestamp,
+            LogObject::MostPkt2(msg) => msg.timestamp,
+            LogObject::MostLightLock(msg) => msg.timestamp,
+            LogObject::MostStatistic(msg) => msg.timestamp,
+            LogObject::MostHwMode(msg) => msg.timestamp,
+            LogObject::MostReg(msg) => msg.timestamp,
+            LogObject::MostGenReg(msg) => msg.timestamp,
+            LogObject::MostNetState(msg) => msg.timestamp,
+            LogObject::MostDataLost(msg) => msg.timestamp,
+            LogObject::MostTrigger(msg) => msg.timestamp,
+            LogObject::Unhandled { timestamp, .. } => *timestamp,
+        }
+    }
+
+    /// Overwrites the timestamp of the log object
+    pub fn set_timestamp(&mut self, timestamp_ns: u64) {
+        match self {
+            LogObject::CanMessage(msg) => msg.header.object_time_stamp = timestamp_ns,
+            LogObject::CanMessage2(msg) => msg.header.object_time_stamp = timestamp_ns,
+            LogObject::CanErrorFrame(msg) => msg.header.object_time_stamp = timestamp_ns,
+            LogObject::CanFdMessage(msg) => msg.header.object_time_stamp = timestamp_ns,
+            LogObject::CanFdMessage64(msg) => msg.header.object_time_stamp = timestamp_ns,
+            LogObject::CanOverloadFrame(msg) => msg.header.object_time_stamp = timestamp_ns,
+            LogObject::CanDriverStatistic(msg) => msg.header.object_time_stamp = timestamp_ns,
+            LogObject::CanDriverError(msg) => msg.header.object_time_stamp = timestamp_ns,
+            LogObject::LinMessage(msg) => msg.header.object_time_stamp = timestamp_ns,
+            LogObject::LinMessage2(msg) => msg.header.object_time_stamp = timestamp_ns,
+            LogObject::LinCrcError(msg) => msg.header.object_time_stamp = timestamp_ns,
+            LogObject::LinDlcInfo(msg) => msg.header.object_time_stamp = timestamp_ns,
+            LogObject::LinReceiveError(msg) => msg.header.object_time_stamp = timestamp_ns,
+            LogObject::LinSendError(msg) => msg.header.object_time_stamp = timestamp_ns,
+            LogObject::LinSlaveTimeout(msg) => msg.header.object_time_stamp = timestamp_ns,
+            LogObject::LinSchedulerModeChange(msg) => msg.header.object_time_stamp = timestamp_ns,
+            LogObject::LinSyncError(msg) => msg.header.object_time_stamp = timestamp_ns,
+            LogObject::LinBaudrateEvent(msg) => msg.header.object_time_stamp = timestamp_ns,
+            LogObject::LinSleepModeEvent(msg) => msg.header.object_time_stamp = timestamp_ns,
+            LogObject::LinWakeupEvent(msg) => msg.header.object_time_stamp = timestamp_ns,
+            LogObject::FlexRayData(msg) => msg.timestamp = timestamp_ns,
+            LogObject::FlexRaySync(msg) => msg.timestamp = timestamp_ns,
+            LogObject::FlexRayV6Message(msg) => msg.timestamp = timestamp_ns,
+            LogObject::FlexRayV6StartCycleEvent(msg) => msg.timestamp = timestamp_ns,
+            LogObject::FlexRayStatusEvent(msg) => msg.timestamp = timestamp_ns,
+            LogObject::FlexRayVFrError(msg) => msg.timestamp = timestamp_ns,
+            LogObject::FlexRayVFrStatus(msg) => msg.timestamp = timestamp_ns,
+            LogObject::FlexRayVFrStartCycle(msg) => msg.timestamp = timestamp_ns,
+            LogObject::FlexRayVFrReceiveMsg(msg) => msg.timestamp = timestamp_ns,
+            LogObject::FlexRayVFrReceiveMsgEx(msg) => msg.timestamp = timestamp_ns,
+            LogObject::EthernetFrame(msg) => msg.timestamp = timestamp_ns,
+            LogObject::AppTrigger(msg) => msg.timestamp = timestamp_ns,
+            LogObject::EventComment(msg) => msg.timestamp = timestamp_ns,
+            LogObject::GlobalMarker(msg) => msg.timestamp = timestamp_ns,
+            LogObject::GpsEvent(msg) => msg.timestamp = timestamp_ns,
+            LogObject::MostSpy(msg) => msg.timestamp = timestamp_ns,
+            LogObject::MostCtrl(msg) => msg.timestamp = timestamp_ns,
+            LogObject::MostPkt2(msg) => msg.timestamp = timestamp_ns,
+            LogObject::MostLightLock(msg) => msg.timestamp = timestamp_ns,
+            LogObject::MostStatistic(msg) => msg.timestamp = timestamp_ns,
+            LogObject::MostHwMode(msg) => msg.timestamp = timestamp_ns,
+            LogObject::MostReg(msg) => msg.timestamp = timestamp_ns,
+            LogObject::MostGenReg(msg) => msg.timestamp = timestamp_ns,
+            LogObject::MostNetState(msg) => msg.timestamp = timestamp_ns,
+            LogObject::MostDataLost(msg) => msg.timestamp = timestamp_ns,
+            LogObject::MostTrigger(msg) => msg.timestamp = timestamp_ns,
+            LogObject::Unhandled { timestamp, .. } => *timestamp = timestamp_ns,
+        }
+    }
+
+    /// Returns the raw [`ObjectType`] number the object was stored under
+    ///
+    /// Objects this crate does not decode keep the number from their header.
+    pub fn object_type(&self) -> u32 {
+        let object_type = match self {
+            LogObject::CanMessage(_) => ObjectType::CanMessage,
+            LogObject::CanMessage2(_) => ObjectType::CanMessage2,
+            LogObject::CanErrorFrame(_) => ObjectType::CanError,
+            LogObject::CanFdMessage(_) => ObjectType::CanFdMessage,
+            LogObject::CanFdMessage64(_) => ObjectType::CanFdMessage64,
+            LogObject::CanOverloadFrame(_) => ObjectType::CanOverload,
+            LogObject::CanDriverStatistic(_) => ObjectType::CanStatistic,
+            LogObject::CanDriverError(_) => ObjectType::CanDriverError,
+            LogObject::LinMessage(_) => ObjectType::LinMessage,
+            LogObject::LinMessage2(_) => ObjectType::LinMessage2,
+            LogObject::LinCrcError(_) => ObjectType::LinCrcError,
+            LogObject::LinDlcInfo(_) => ObjectType::LinDlcInfo,
+            LogObject::LinReceiveError(_) => ObjectType::LinReceiveError,
+            LogObject::LinSendError(_) => ObjectType::LinSendError,
+            LogObject::LinSlaveTimeout(_) => ObjectType::LinSlaveTimeout,
+            LogObject::LinSchedulerModeChange(_) => ObjectType::LinSchedulerModeChange,
+            LogObject::LinSyncError(_) => ObjectType::LinSyncError,
+            LogObject::LinBaudrateEvent(_) => ObjectType::LinBaudrate,
+            LogObject::LinSleepModeEvent(_) => ObjectType::LinSleep,
+            LogObject::LinWakeupEvent(_) => ObjectType::LinWakeup,
+            LogObject::FlexRayData(_) => ObjectType::FlexRayData,
+            LogObject::FlexRaySync(_) => ObjectType::FlexRaySync,
+            LogObject::FlexRayV6Message(_) => ObjectType::FlexRayMessage,
+            LogObject::FlexRayV6StartCycleEvent(_) => ObjectType::FlexRayV6StartCycleEvent,
+            LogObject::FlexRayStatusEvent(_) => ObjectType::FlexRayStatusEvent,
+            LogObject::FlexRayVFrError(_) => ObjectType::FlexRayVFrError,
+            LogObject::FlexRayVFrStatus(_) => ObjectType::FlexRayVFrStatus,
+            LogObject::FlexRayVFrStartCycle(_) => ObjectType::FlexRayVFrStartCycle,
+            LogObject::FlexRayVFrReceiveMsg(_) => ObjectType::FlexRayVFrReceiveMsg,
+            LogObject::FlexRayVFrReceiveMsgEx(_) => ObjectType::FlexRayVFrReceiveMsgEx,
+            LogObject::EthernetFrame(_) => ObjectType::EthernetFrame,
+            LogObject::AppTrigger(_) => ObjectType::AppTrigger,
+            LogObject::EventComment(_) => ObjectType::EventComment,
+            LogObject::GlobalMarker(_) => ObjectType::GlobalMarker,
+            LogObject::GpsEvent(_) => ObjectType::GpsEvent,
+            LogObject::MostSpy(_) => ObjectType::MostSpy,
+            LogObject::MostCtrl(_) => ObjectType::MostCtrl,
+            LogObject::MostPkt2(_) => ObjectType::MostPkt2,
+            LogObject::MostLightLock(_) => ObjectType::MostLightLock,
+            LogObject::MostStatistic(_) => ObjectType::MostStatistic,
+            LogObject::MostHwMode(_) => ObjectType::MostHwMode,
+            LogObject::MostReg(_) => ObjectType::MostReg,
+            LogObject::MostGenReg(_) => ObjectType::MostGenReg,
+            LogObject::MostNetState(_) => ObjectType::MostNetState,
+            LogObject::MostDataLost(_) => ObjectType::MostDataLost,
+            LogObject::MostTrigger(_) => ObjectType::MostTrigger,
+            LogObject::Unhandled { object_type, .. } => return *object_type,
+        };
+        object_type as u32
+    }
+
+    /// Returns the channel ID of the log object (if applicable)
+    pub fn channel(&self) -> Option<u16> {
+        match self {
+            LogObject::CanMessage(msg) => Some(msg.channel),
+            LogObject::CanMessage2(msg) => Some(msg.channel),
+            LogObject::CanErrorFrame(msg) => Some(msg.channel),
+            LogObject::CanFdMessage(msg) => Some(msg.channel),
+            LogObject::CanFdMessage64(msg) => Some(msg.channel as u16),
+            LogObject::CanOverloadFrame(msg) => Some(msg.channel),
+            LogObject::CanDriverStatistic(msg) => Some(msg.channel),
+            LogObject::CanDriverError(msg) => Some(msg.channel),
+            LogObject::LinMessage(msg) => Some(msg.channel),
+            LogObject::LinMessage2(msg) => Some(msg.channel),
+            // LIN events are parsed as header-only stubs
+            LogObject::LinCrcError(_)
+            | LogObject::LinDlcInfo(_)
+            | LogObject::LinReceiveError(_)
+            | LogObject::LinSendError(_)
+            | LogObject::LinSlaveTimeout(_)
+            | LogObject::LinSchedulerModeChange(_)
+            | LogObject::LinSyncError(_)
+            | LogObject::LinBaudrateEvent(_)
+            | LogObject::LinSleepModeEvent(_)
+            | LogObject::LinWakeupEvent(_) => None,
+            LogObject::FlexRayData(msg) => Some(msg.channel),
+            LogObject::FlexRaySync(msg) => Some(msg.channel),
+            LogObject::FlexRayV6Message(msg) => Some(msg.channel),
+            LogObject::FlexRayV6StartCycleEvent(msg) => Some(msg.channel),
+            LogObject::FlexRayStatusEvent(msg) => Some(msg.channel),
+            LogObject::FlexRayVFrError(msg) => Some(msg.channel),
+            LogObject::FlexRayVFrStatus(msg) => Some(msg.channel),
+            LogObject::FlexRayVFrStartCycle(msg) => Some(msg.channel),
+            LogObject::FlexRayVFrReceiveMsg(msg) => Some(msg.channel),
+            LogObject::FlexRayVFrReceiveMsgEx(msg) => Some(msg.channel),
+            LogObject::EthernetFrame(msg) => Some(msg.channel),
+            LogObject::AppTrigger(msg) => Some(msg.channel),
+            LogObject::EventComment(_) | LogObject::GlobalMarker(_) => None,
+            LogObject::GpsEvent(msg) => Some(msg.channel),
+            LogObject::MostSpy(msg) => Some(msg.channel),
+            LogObject::MostCtrl(msg) => Some(msg.channel),
+            LogObject::MostPkt2(msg) => Some(msg.channel),
+            LogObject::MostLightLock(msg) => Some(msg.channel),
+            LogObject::MostStatistic(msg) => Some(msg.channel),
+            LogObject::MostHwMode(msg) => Some(msg.channel),
+            LogObject::MostReg(msg) => Some(msg.channel),
+            LogObject::MostGenReg(msg) => Some(msg.channel),
+            LogObject::MostNetState(msg) => Some(msg.channel),
+            LogObject::MostDataLost(msg) => Some(msg.channel),
+            LogObject::MostTrigger(msg) => Some(msg.channel),
+            LogObject::Unhandled { .. } => None,
+        }
+    }
+
+    /// Returns the frame identifier of the log object (if applicable)
+    ///
+    /// CAN and LIN frames report their arbitration ID, FlexRay frames their
+    /// slot ID; status and error events carry none.
+    pub fn id(&self) -> Option<u32> {
+        match self {
+            LogObject::CanMessage(msg) => Some(msg.id),
+            LogObject::CanMessage2(msg) => Some(msg.id),
+            LogObject::CanFdMessage(msg) => Some(msg.id),
+            LogObject::CanFdMessage64(msg) => Some(msg.id),
+            LogObject::LinMessage(msg) => Some(msg.id as u32),
+            LogObject::LinMessage2(msg) => Some(msg.id as u32),
+            LogObject::FlexRayData(msg) => Some(msg.message_id as u32),
+            LogObject::FlexRaySync(msg) => Some(msg.message_id as u32),
+            LogObject::FlexRayV6Message(msg) => Some(msg.frame_id as u32),
+            LogObject::FlexRayVFrReceiveMsg(msg) => Some(msg.frame_id as u32),
+            LogObject::FlexRayVFrReceiveMsgEx(msg) => Some(msg.frame_id as u32),
+            LogObject::CanErrorFrame(_)
+            | LogObject::CanOverloadFrame(_)
+            | LogObject::CanDriverStatistic(_)
+            | LogObject::CanDriverError(_)
+            | LogObject::LinCrcError(_)
+            | LogObject::LinDlcInfo(_)
+            | LogObject::LinReceiveError(_)
+            | LogObject::LinSendError(_)
+            | LogObject::LinSlaveTimeout(_)
+            | LogObject::LinSchedulerModeChange(_)
+            | LogObject::LinSyncError(_)
+            | LogObject::LinBaudrateEvent(_)
+            | LogObject::LinSleepModeEvent(_)
+            | LogObject::LinWakeupEvent(_)
+            | LogObject::FlexRayV6StartCycleEvent(_)
+            | LogObject::FlexRayStatusEvent(_)
+            | LogObject::FlexRayVFrError(_)
+            | LogObject::FlexRayVFrStatus(_)
+            | LogObject::FlexRayVFrStartCycle(_)
+            | LogObject::EthernetFrame(_)
+            | LogObject::AppTrigger(_)
+            | LogObject::EventComment(_)
+            | LogObject::GlobalMarker(_)
+            | LogObject::GpsEvent(_)
+            | LogObject::MostSpy(_)
+            | LogObject::MostCtrl(_)
+            | LogObject::MostPkt2(_)
+            | LogObject::MostLightLock(_)
+            | LogObject::MostStatistic(_)
+            | LogObject::MostHwMode(_)
+            | LogObject::MostReg(_)
+            | LogObject::MostGenReg(_)
+            | LogObject::MostNetState(_)
+            | LogObject::MostDataLost(_)
+            | LogObject::MostTrigger(_)
+            | LogObject::Unhandled { .. } => None,
+        }
+    }
+}
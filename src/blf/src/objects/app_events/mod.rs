@@ -1,3 +1,5 @@
+//! Application event objects: triggers, comments and markers.
+
 pub mod comment_marker;
 pub mod trigger;
 
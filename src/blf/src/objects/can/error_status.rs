@@ -1,9 +1,6 @@
 //! CAN error and status object definitions.
 
-use crate::BlfParseResult;
 use crate::objects::object_header::ObjectHeader;
-use byteorder::{LittleEndian, ReadBytesExt};
-use std::io::Cursor;
 
 /// Represents a CAN error frame (`CAN_ERROR`).
 #[derive(Debug, Clone, PartialEq, Eq, Default)]
@@ -16,24 +13,6 @@ pub struct CanErrorFrame {
     pub length: u16,
 }
 
-impl CanErrorFrame {
-    pub(crate) fn read(cursor: &mut Cursor<&[u8]>, header: &ObjectHeader) -> BlfParseResult<Self> {
-        let channel = cursor.read_u16::<LittleEndian>()?;
-        let length = cursor.read_u16::<LittleEndian>()?;
-
-        // Based on C++ CanErrorFrame.cpp, skip reserved bytes if length > 0
-        if length > 0 {
-            cursor.set_position(cursor.position() + 4); // Skip reserved u32
-        }
-
-        Ok(Self {
-            header: header.clone(),
-            channel,
-            length,
-        })
-    }
-}
-
 /// Represents a CAN overload frame (`CAN_OVERLOAD`).
 #[derive(Debug, Clone, PartialEq, Eq, Default)]
 pub struct CanOverloadFrame {
@@ -43,20 +22,6 @@ pub struct CanOverloadFrame {
     pub channel: u16,
 }
 
-impl CanOverloadFrame {
-    pub(crate) fn read(cursor: &mut Cursor<&[u8]>, header: &ObjectHeader) -> BlfParseResult<Self> {
-        let channel = cursor.read_u16::<LittleEndian>()?;
-
-        // Based on C++ CanOverloadFrame.cpp, skip reserved bytes
-        cursor.set_position(cursor.position() + 2 + 4); // Skip reserved u16 and u32
-
-        Ok(Self {
-            header: header.clone(),
-            channel,
-        })
-    }
-}
-
 /// Represents CAN driver statistics (`CAN_STATISTIC`).
 #[derive(Debug, Clone, PartialEq)]
 pub struct CanDriverStatistic {
@@ -80,34 +45,6 @@ pub struct CanDriverStatistic {
     pub overload_frames: u32,
 }
 
-impl CanDriverStatistic {
-    pub(crate) fn read(cursor: &mut Cursor<&[u8]>, header: &ObjectHeader) -> BlfParseResult<Self> {
-        let channel = cursor.read_u16::<LittleEndian>()?;
-        let bus_load = cursor.read_u16::<LittleEndian>()?;
-        let standard_data_frames = cursor.read_u32::<LittleEndian>()?;
-        let extended_data_frames = cursor.read_u32::<LittleEndian>()?;
-        let standard_remote_frames = cursor.read_u32::<LittleEndian>()?;
-        let extended_remote_frames = cursor.read_u32::<LittleEndian>()?;
-        let error_frames = cursor.read_u32::<LittleEndian>()?;
-        let overload_frames = cursor.read_u32::<LittleEndian>()?;
-
-        // Based on C++ CanDriverStatistic.cpp, skip reserved bytes
-        cursor.set_position(cursor.position() + 4); // Skip reserved u32
-
-        Ok(Self {
-            header: header.clone(),
-            channel,
-            bus_load,
-            standard_data_frames,
-            extended_data_frames,
-            standard_remote_frames,
-            extended_remote_frames,
-            error_frames,
-            overload_frames,
-        })
-    }
-}
-
 /// Represents a CAN driver error (`CAN_DRIVER_ERROR`).
 #[derive(Debug, Clone, PartialEq, Eq, Default)]
 pub struct CanDriverError {
@@ -122,19 +59,3 @@ pub struct CanDriverError {
     /// Error code.
     pub error_code: u32,
 }
-
-impl CanDriverError {
-    pub(crate) fn read(cursor: &mut Cursor<&[u8]>, header: &ObjectHeader) -> BlfParseResult<Self> {
-        let channel = cursor.read_u16::<LittleEndian>()?;
-        let tx_errors = cursor.read_u8()?;
-        let rx_errors = cursor.read_u8()?;
-        let error_code = cursor.read_u32::<LittleEndian>()?;
-        Ok(Self {
-            header: header.clone(),
-            channel,
-            tx_errors,
-            rx_errors,
-            error_code,
-        })
-    }
-}
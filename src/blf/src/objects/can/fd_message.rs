@@ -1,9 +1,12 @@
 //! CAN FD message object definitions.
 
+#[cfg(feature = "std")]
 use crate::BlfParseResult;
+#[cfg(feature = "std")]
+use crate::blf_core::decode_at;
 use crate::objects::object_header::ObjectHeader;
-use byteorder::{LittleEndian, ReadBytesExt};
-use std::io::{Cursor, Read};
+#[cfg(feature = "std")]
+use std::io::Cursor;
 
 /// Represents a CAN FD message (`CAN_FD_MESSAGE`).
 #[derive(Debug, Clone, PartialEq)]
@@ -58,37 +61,9 @@ impl Default for CanFdMessage {
 
 impl CanFdMessage {
     /// Reads a `CanFdMessage` from a byte cursor.
+    #[cfg(feature = "std")]
     pub fn read(cursor: &mut Cursor<&[u8]>, header: &ObjectHeader) -> BlfParseResult<Self> {
-        let channel = cursor.read_u16::<LittleEndian>()?;
-        println!("-=--channel---{}", channel);
-        let flags = cursor.read_u8()?;
-        let dlc = cursor.read_u8()?;
-        let id = cursor.read_u32::<LittleEndian>()?;
-        let frame_length = cursor.read_u32::<LittleEndian>()?;
-        let arb_bit_count = cursor.read_u8()?;
-        let can_fd_flags = cursor.read_u8()?;
-        let valid_data_bytes = cursor.read_u8()?;
-        let reserved1 = cursor.read_u8()?;
-        let reserved2 = cursor.read_u32::<LittleEndian>()?;
-        let mut data = [0u8; 64];
-        cursor.read_exact(&mut data)?;
-        let reserved3 = cursor.read_u32::<LittleEndian>()?;
-
-        Ok(Self {
-            header: header.clone(),
-            channel,
-            flags,
-            dlc,
-            id,
-            frame_length,
-            arb_bit_count,
-            can_fd_flags,
-            valid_data_bytes,
-            reserved1,
-            reserved2,
-            data,
-            reserved3,
-        })
+        decode_at(cursor, |reader| Self::decode(reader, header))
     }
 }
 
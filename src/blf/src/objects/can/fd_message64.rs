@@ -1,9 +1,13 @@
 //! CAN FD Message 64 object definitions.
 
+#[cfg(feature = "std")]
 use crate::BlfParseResult;
+#[cfg(feature = "std")]
+use crate::blf_core::decode_at;
 use crate::objects::object_header::ObjectHeader;
-use byteorder::{LittleEndian, ReadBytesExt};
-use std::io::{Cursor, Read};
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use std::io::Cursor;
 
 /// Optional extended data for CanFdMessage64
 #[derive(Debug, Clone, PartialEq, Default)]
@@ -28,24 +32,9 @@ pub struct CanFdExtFrameData {
 
 impl CanFdExtFrameData {
     /// Reads CanFdExtFrameData from a byte cursor
+    #[cfg(feature = "std")]
     pub fn read(cursor: &mut Cursor<&[u8]>, size: usize) -> BlfParseResult<Self> {
-        let btr_ext_arb = cursor.read_u32::<LittleEndian>()?;
-        let btr_ext_data = cursor.read_u32::<LittleEndian>()?;
-
-        // Calculate remaining size for reserved data
-        let header_size = 8; // 2 * u32
-        let reserved_size = size.saturating_sub(header_size);
-
-        let mut reserved = vec![0u8; reserved_size];
-        if reserved_size > 0 {
-            cursor.read_exact(&mut reserved)?;
-        }
-
-        Ok(Self {
-            btr_ext_arb,
-            btr_ext_data,
-            reserved,
-        })
+        decode_at(cursor, |reader| Self::decode(reader, size))
     }
 
     /// Calculates the size of this structure
@@ -147,104 +136,9 @@ pub struct CanFdMessage64 {
 
 impl CanFdMessage64 {
     /// Reads a `CanFdMessage64` from a byte cursor.
+    #[cfg(feature = "std")]
     pub fn read(cursor: &mut Cursor<&[u8]>, header: &ObjectHeader) -> BlfParseResult<Self> {
-        // Check if we need to skip bytes (similar to CanMessage)
-        // Some BLF variants have extra metadata before the actual CAN FD data
-        let remaining = &cursor.get_ref()[cursor.position() as usize..];
-        let skip_bytes = if remaining.len() >= 32 {
-            // Check both offset 0 and offset 16
-            let channel_at_0 = remaining[0];
-            let dlc_at_1 = remaining[1];
-            let id_at_4 =
-                u32::from_le_bytes([remaining[4], remaining[5], remaining[6], remaining[7]]);
-
-            let channel_at_16 = remaining[16];
-            let dlc_at_17 = remaining[17];
-            let id_at_20 =
-                u32::from_le_bytes([remaining[20], remaining[21], remaining[22], remaining[23]]);
-
-            // Offset 0 looks invalid (all zeros or suspicious) AND offset 16 looks valid
-            let offset_0_invalid = (channel_at_0 == 0 && dlc_at_1 == 0 && id_at_4 == 0)
-                || (dlc_at_1 == 0 && id_at_4 == 0 && channel_at_0 <= 1);
-
-            let offset_16_valid =
-                (channel_at_16 > 0 || dlc_at_17 > 0 || id_at_20 > 0) && dlc_at_17 <= 15;
-
-            if offset_0_invalid && offset_16_valid {
-                16
-            } else {
-                0
-            }
-        } else {
-            0
-        };
-
-        // Skip extra bytes if detected
-        if skip_bytes > 0 {
-            let mut temp = [0u8; 16];
-            cursor.read_exact(&mut temp)?;
-        }
-
-        let channel = { cursor.read_u8()? };
-
-        let dlc = cursor.read_u8()?;
-        let valid_data_bytes = cursor.read_u8()?;
-        let tx_count = cursor.read_u8()?;
-        let id = cursor.read_u32::<LittleEndian>()?;
-        let frame_length = cursor.read_u32::<LittleEndian>()?;
-        let flags = cursor.read_u32::<LittleEndian>()?;
-        let btr_cfg_arb = cursor.read_u32::<LittleEndian>()?;
-        let btr_cfg_data = cursor.read_u32::<LittleEndian>()?;
-        let time_offset_brs_ns = cursor.read_u32::<LittleEndian>()?;
-        let time_offset_crc_del_ns = cursor.read_u32::<LittleEndian>()?;
-        let bit_count = cursor.read_u16::<LittleEndian>()?;
-        let dir = cursor.read_u8()?;
-        let ext_data_offset = cursor.read_u8()?;
-        let crc = cursor.read_u32::<LittleEndian>()?;
-
-        // Read data - actual length is valid_data_bytes
-        let mut data = vec![0u8; valid_data_bytes as usize];
-        if valid_data_bytes > 0 {
-            cursor.read_exact(&mut data)?;
-        }
-
-        // Check if we have extended data
-        let ext_data = if ext_data_offset != 0
-            && (header.object_size as usize) >= (ext_data_offset as usize + 8)
-        {
-            // Calculate the size of ext data based on remaining object size
-            let total_object_size = header.object_size as usize;
-            let ext_data_size = total_object_size.saturating_sub(ext_data_offset as usize);
-
-            if ext_data_size > 0 {
-                Some(CanFdExtFrameData::read(cursor, ext_data_size)?)
-            } else {
-                None
-            }
-        } else {
-            None
-        };
-
-        Ok(Self {
-            header: header.clone(),
-            channel,
-            dlc,
-            valid_data_bytes,
-            tx_count,
-            id,
-            frame_length,
-            flags,
-            btr_cfg_arb,
-            btr_cfg_data,
-            time_offset_brs_ns,
-            time_offset_crc_del_ns,
-            bit_count,
-            dir,
-            ext_data_offset,
-            crc,
-            data,
-            ext_data,
-        })
+        decode_at(cursor, |reader| Self::decode(reader, header))
     }
 
     /// Check if this is a CAN FD frame (EDL bit set)
@@ -297,7 +191,7 @@ impl CanFdMessage64 {
     pub const FLAG_BURST: u32 = 0x20000;
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "std"))]
 mod tests {
     use super::*;
     use crate::ObjectHeader;
@@ -324,7 +218,7 @@ mod tests {
 
         // Create test data: channel=1, dlc=8, valid_bytes=8, tx_count=0, id=0x123
         // rest zeros
-        let mut test_data = vec![0u8; 88];
+        let mut test_data = [0u8; 88];
         test_data[0] = 1; // channel
         test_data[1] = 8; // dlc
         test_data[2] = 8; // valid_data_bytes
@@ -367,7 +261,7 @@ mod tests {
             reserved: 0,
         };
 
-        let mut test_data = vec![0u8; 88];
+        let mut test_data = [0u8; 88];
         test_data[0] = 1; // channel
         // flags at offset 12 (after channel(1) + dlc(1) + valid_bytes(1) + tx_count(1) + id(4) + frame_length(4))
         test_data[12..16].copy_from_slice(&0x1000u32.to_le_bytes()); // EDL bit set
@@ -399,7 +293,7 @@ mod tests {
             reserved: 0,
         };
 
-        let mut test_data = vec![0u8; 88];
+        let mut test_data = [0u8; 88];
         test_data[0] = 1; // channel
         // EDL + BRS + ESI bits set (offset 12)
         test_data[12..16].copy_from_slice(&0x7000u32.to_le_bytes());
@@ -1,9 +1,13 @@
 //! CAN message object definitions (non-FD).
 
+#[cfg(feature = "std")]
 use crate::BlfParseResult;
+#[cfg(feature = "std")]
+use crate::blf_core::decode_at;
 use crate::objects::object_header::ObjectHeader;
-use byteorder::{LittleEndian, ReadBytesExt};
-use std::io::{Cursor, Read};
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use std::io::Cursor;
 
 /// Represents a standard CAN message (`CAN_MESSAGE`).
 #[derive(Debug, Clone, PartialEq, Default)]
@@ -24,62 +28,9 @@ pub struct CanMessage {
 
 impl CanMessage {
     /// Reads a `CanMessage` from a byte cursor.
+    #[cfg(feature = "std")]
     pub fn read(cursor: &mut Cursor<&[u8]>, header: &ObjectHeader) -> BlfParseResult<Self> {
-        // Check if we need to skip 16 bytes (eckzeit and other metadata)
-        // This happens in some BLF file variants where the format includes:
-        // - 8 bytes: eckzeit (cycle time in ns)
-        // - 8 bytes: reserved/unknown fields
-        // before the actual CAN message data
-        let remaining = &cursor.get_ref()[cursor.position() as usize..];
-        let skip_bytes = if remaining.len() >= 24 {
-            // Check both offset 0 and offset 16
-            let channel_at_0 = u16::from_le_bytes([remaining[0], remaining[1]]);
-            let dlc_at_3 = remaining[3];
-            let id_at_4 =
-                u32::from_le_bytes([remaining[4], remaining[5], remaining[6], remaining[7]]);
-
-            let channel_at_16 = u16::from_le_bytes([remaining[16], remaining[17]]);
-            let dlc_at_19 = remaining[19];
-            let id_at_20 =
-                u32::from_le_bytes([remaining[20], remaining[21], remaining[22], remaining[23]]);
-
-            // Offset 0 looks invalid (all zeros or suspicious) AND offset 16 looks valid
-            let offset_0_invalid = (channel_at_0 == 0 && dlc_at_3 == 0 && id_at_4 == 0)
-                || (dlc_at_3 == 0 && id_at_4 == 0 && channel_at_0 <= 1);
-
-            let offset_16_valid =
-                (channel_at_16 > 0 || dlc_at_19 > 0 || id_at_20 > 0) && dlc_at_19 <= 8;
-
-            if offset_0_invalid && offset_16_valid {
-                16
-            } else {
-                0
-            }
-        } else {
-            0
-        };
-
-        // Skip the extra bytes if detected
-        if skip_bytes > 0 {
-            let mut temp = [0u8; 16];
-            cursor.read_exact(&mut temp)?;
-        }
-
-        let channel = cursor.read_u16::<LittleEndian>()?;
-        let flags = cursor.read_u8()?;
-        let dlc = cursor.read_u8()?;
-        let id = cursor.read_u32::<LittleEndian>()?;
-        let mut data = [0u8; 8];
-        cursor.read_exact(&mut data)?;
-
-        Ok(Self {
-            header: header.clone(),
-            channel,
-            flags,
-            dlc,
-            id,
-            data,
-        })
+        decode_at(cursor, |reader| Self::decode(reader, header))
     }
 }
 
@@ -110,37 +61,13 @@ pub struct CanMessage2 {
 
 impl CanMessage2 {
     /// Reads a `CanMessage2` from a byte cursor.
+    #[cfg(feature = "std")]
     pub fn read(
         cursor: &mut Cursor<&[u8]>,
         header: &ObjectHeader,
         data_size: usize,
     ) -> BlfParseResult<Self> {
-        let channel = cursor.read_u16::<LittleEndian>()?;
-        let flags = cursor.read_u8()?;
-        let dlc = cursor.read_u8()?;
-        let id = cursor.read_u32::<LittleEndian>()?;
-
-        // Read data based on the provided data_size
-        let mut data = vec![0u8; data_size];
-        cursor.read_exact(&mut data)?;
-
-        let frame_length = cursor.read_u32::<LittleEndian>()?;
-        let bit_count = cursor.read_u8()?;
-        let reserved1 = cursor.read_u8()?;
-        let reserved2 = cursor.read_u16::<LittleEndian>()?;
-
-        Ok(Self {
-            header: header.clone(),
-            channel,
-            flags,
-            dlc,
-            id,
-            data,
-            frame_length,
-            bit_count,
-            reserved1,
-            reserved2,
-        })
+        decode_at(cursor, |reader| Self::decode(reader, header, data_size))
     }
 }
 
@@ -156,7 +83,7 @@ impl CanMessage2 {
     pub const FLAG_RTR: u8 = 1 << 7;
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "std"))]
 mod tests {
     use super::*;
     use crate::ObjectHeader;
@@ -1,3 +1,5 @@
+//! CAN object definitions: classic and FD frames, errors and driver statistics.
+
 pub mod error_status;
 pub mod fd_message;
 pub mod fd_message64;
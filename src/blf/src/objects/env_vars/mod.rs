@@ -1,3 +1,5 @@
+//! Environment and system variable objects (not decoded yet).
+
 // Temporarily comment out the missing modules
 // pub mod system_variable;
 // pub mod environment_variable;
@@ -1,9 +1,6 @@
 //! Ethernet frame object definitions.
 
-use crate::BlfParseResult;
-use crate::objects::object_header::ObjectHeader;
-use byteorder::{LittleEndian, ReadBytesExt};
-use std::io::{Cursor, Read, Write};
+use alloc::vec::Vec;
 
 /// Represents an Ethernet frame (`ETHERNET_FRAME`).
 #[derive(Debug, Clone, PartialEq)]
@@ -29,39 +26,3 @@ pub struct EthernetFrame {
     /// Timestamp of the message.
     pub timestamp: u64,
 }
-
-impl EthernetFrame {
-    pub(crate) fn read(cursor: &mut Cursor<&[u8]>, header: &ObjectHeader) -> BlfParseResult<Self> {
-        let mut source_address = [0u8; 6];
-        cursor.read_exact(&mut source_address)?;
-        let channel = cursor.read_u16::<LittleEndian>()?;
-        let mut destination_address = [0u8; 6];
-        cursor.read_exact(&mut destination_address)?;
-        let dir = cursor.read_u16::<LittleEndian>()?;
-        let frame_type = cursor.read_u16::<LittleEndian>()?;
-        let tpid = cursor.read_u16::<LittleEndian>()?;
-        let tci = cursor.read_u16::<LittleEndian>()?;
-        let payload_length = cursor.read_u16::<LittleEndian>()?;
-        let _reserved = cursor.read_u64::<LittleEndian>()?;
-
-        let mut payload = vec![0; payload_length as usize];
-        cursor.read_exact(&mut payload)?;
-
-        Ok(Self {
-            source_address,
-            channel,
-            destination_address,
-            dir,
-            frame_type,
-            tpid,
-            tci,
-            payload_length,
-            payload,
-            timestamp: header.object_time_stamp,
-        })
-    }
-
-    pub(crate) fn write<W: Write>(&self, _writer: &mut W) -> BlfParseResult<()> {
-        unimplemented!("Writing EthernetFrame is not yet implemented.")
-    }
-}
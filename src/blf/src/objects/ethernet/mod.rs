@@ -1,3 +1,5 @@
+//! Ethernet object definitions.
+
 pub mod frame;
 
 pub use frame::*;
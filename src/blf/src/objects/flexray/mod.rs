@@ -1,3 +1,5 @@
+//! FlexRay object definitions: frames, cycles and status events.
+
 pub mod message;
 pub mod status_events;
 
@@ -1,10 +1,5 @@
 //! FlexRay status event object definitions.
 
-use crate::BlfParseResult;
-use crate::objects::object_header::ObjectHeader;
-use byteorder::{LittleEndian, ReadBytesExt};
-use std::io::{Cursor, Read, Write};
-
 /// Represents a FlexRay sync frame (`FLEXRAY_SYNC`, deprecated).
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct FlexRaySync {
@@ -28,38 +23,6 @@ pub struct FlexRaySync {
     pub timestamp: u64,
 }
 
-impl FlexRaySync {
-    pub(crate) fn read(cursor: &mut Cursor<&[u8]>, header: &ObjectHeader) -> BlfParseResult<Self> {
-        let channel = cursor.read_u16::<LittleEndian>()?;
-        let mux = cursor.read_u8()?;
-        let len = cursor.read_u8()?;
-        let message_id = cursor.read_u16::<LittleEndian>()?;
-        let crc = cursor.read_u16::<LittleEndian>()?;
-        let dir = cursor.read_u8()?;
-        let _reserved1 = cursor.read_u8()?;
-        let _reserved2 = cursor.read_u16::<LittleEndian>()?;
-        let mut data_bytes = [0u8; 11];
-        cursor.read_exact(&mut data_bytes)?;
-        let cycle = cursor.read_u8()?;
-
-        Ok(Self {
-            channel,
-            mux,
-            len,
-            message_id,
-            crc,
-            dir,
-            data_bytes,
-            cycle,
-            timestamp: header.object_time_stamp,
-        })
-    }
-
-    pub(crate) fn write<W: Write>(&self, _writer: &mut W) -> BlfParseResult<()> {
-        unimplemented!("Writing FlexRaySync is not yet implemented.")
-    }
-}
-
 /// Represents a FlexRay V6 Start Cycle event (`FLEXRAY_CYCLE`).
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct FlexRayV6StartCycleEvent {
@@ -75,33 +38,6 @@ pub struct FlexRayV6StartCycleEvent {
     pub timestamp: u64,
 }
 
-impl FlexRayV6StartCycleEvent {
-    pub(crate) fn read(cursor: &mut Cursor<&[u8]>, header: &ObjectHeader) -> BlfParseResult<Self> {
-        let channel = cursor.read_u16::<LittleEndian>()?;
-        let dir = cursor.read_u8()?;
-        let _low_time = cursor.read_u8()?;
-        let _fpga_tick = cursor.read_u32::<LittleEndian>()?;
-        let _fpga_tick_overflow = cursor.read_u32::<LittleEndian>()?;
-        let _client_index = cursor.read_u32::<LittleEndian>()?;
-        let cluster_time = cursor.read_u32::<LittleEndian>()?;
-        let mut data_bytes = [0u8; 2];
-        cursor.read_exact(&mut data_bytes)?;
-        let _reserved = cursor.read_u16::<LittleEndian>()?;
-
-        Ok(Self {
-            channel,
-            dir,
-            cluster_time,
-            data_bytes,
-            timestamp: header.object_time_stamp,
-        })
-    }
-
-    pub(crate) fn write<W: Write>(&self, _writer: &mut W) -> BlfParseResult<()> {
-        unimplemented!("Writing FlexRayV6StartCycleEvent is not yet implemented.")
-    }
-}
-
 /// Represents a FlexRay Status event (`FLEXRAY_STATUS`, deprecated).
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct FlexRayStatusEvent {
@@ -121,33 +57,6 @@ pub struct FlexRayStatusEvent {
     pub timestamp: u64,
 }
 
-impl FlexRayStatusEvent {
-    pub(crate) fn read(cursor: &mut Cursor<&[u8]>, header: &ObjectHeader) -> BlfParseResult<Self> {
-        let channel = cursor.read_u16::<LittleEndian>()?;
-        let version = cursor.read_u16::<LittleEndian>()?;
-        let status_type = cursor.read_u16::<LittleEndian>()?;
-        let info_mask1 = cursor.read_u16::<LittleEndian>()?;
-        let info_mask2 = cursor.read_u16::<LittleEndian>()?;
-        let info_mask3 = cursor.read_u16::<LittleEndian>()?;
-        let _reserved = [0u16; 18]; // Skip reserved array
-        cursor.set_position(cursor.position() + (18 * 2) as u64); // Advance cursor by 18 * sizeof(u16)
-
-        Ok(Self {
-            channel,
-            version,
-            status_type,
-            info_mask1,
-            info_mask2,
-            info_mask3,
-            timestamp: header.object_time_stamp,
-        })
-    }
-
-    pub(crate) fn write<W: Write>(&self, _writer: &mut W) -> BlfParseResult<()> {
-        unimplemented!("Writing FlexRayStatusEvent is not yet implemented.")
-    }
-}
-
 /// Represents a FlexRay Error event (`FR_ERROR`).
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct FlexRayVFrError {
@@ -171,40 +80,6 @@ pub struct FlexRayVFrError {
     pub timestamp: u64,
 }
 
-impl FlexRayVFrError {
-    pub(crate) fn read(cursor: &mut Cursor<&[u8]>, header: &ObjectHeader) -> BlfParseResult<Self> {
-        let channel = cursor.read_u16::<LittleEndian>()?;
-        let version = cursor.read_u16::<LittleEndian>()?;
-        let channel_mask = cursor.read_u16::<LittleEndian>()?;
-        let cycle = cursor.read_u8()?;
-        let _reserved1 = cursor.read_u8()?;
-        let client_index = cursor.read_u32::<LittleEndian>()?;
-        let cluster_no = cursor.read_u32::<LittleEndian>()?;
-        let tag = cursor.read_u32::<LittleEndian>()?;
-        let mut data = [0u32; 4];
-        for item in &mut data {
-            *item = cursor.read_u32::<LittleEndian>()?;
-        }
-        let _reserved2 = cursor.read_u32::<LittleEndian>()?;
-
-        Ok(Self {
-            channel,
-            version,
-            channel_mask,
-            cycle,
-            client_index,
-            cluster_no,
-            tag,
-            data,
-            timestamp: header.object_time_stamp,
-        })
-    }
-
-    pub(crate) fn write<W: Write>(&self, _writer: &mut W) -> BlfParseResult<()> {
-        unimplemented!("Writing FlexRayVFrError is not yet implemented.")
-    }
-}
-
 /// Represents a FlexRay Status event (`FR_STATUS`).
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct FlexRayVFrStatus {
@@ -232,45 +107,6 @@ pub struct FlexRayVFrStatus {
     pub timestamp: u64,
 }
 
-impl FlexRayVFrStatus {
-    pub(crate) fn read(cursor: &mut Cursor<&[u8]>, header: &ObjectHeader) -> BlfParseResult<Self> {
-        let channel = cursor.read_u16::<LittleEndian>()?;
-        let version = cursor.read_u16::<LittleEndian>()?;
-        let channel_mask = cursor.read_u16::<LittleEndian>()?;
-        let cycle = cursor.read_u8()?;
-        let _reserved1 = cursor.read_u8()?;
-        let client_index = cursor.read_u32::<LittleEndian>()?;
-        let cluster_no = cursor.read_u32::<LittleEndian>()?;
-        let wus = cursor.read_u32::<LittleEndian>()?;
-        let cc_sync_state = cursor.read_u32::<LittleEndian>()?;
-        let tag = cursor.read_u32::<LittleEndian>()?;
-        let mut data = [0u32; 2];
-        for item in &mut data {
-            *item = cursor.read_u32::<LittleEndian>()?;
-        }
-        let _reserved2 = [0u16; 18]; // Skip reserved array
-        cursor.set_position(cursor.position() + (18 * 2) as u64); // Advance cursor by 18 * sizeof(u16)
-
-        Ok(Self {
-            channel,
-            version,
-            channel_mask,
-            cycle,
-            client_index,
-            cluster_no,
-            wus,
-            cc_sync_state,
-            tag,
-            data,
-            timestamp: header.object_time_stamp,
-        })
-    }
-
-    pub(crate) fn write<W: Write>(&self, _writer: &mut W) -> BlfParseResult<()> {
-        unimplemented!("Writing FlexRayVFrStatus is not yet implemented.")
-    }
-}
-
 /// Represents a FlexRay Start Cycle event (`FR_STARTCYCLE`).
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct FlexRayVFrStartCycle {
@@ -299,44 +135,3 @@ pub struct FlexRayVFrStartCycle {
     /// Timestamp of the message.
     pub timestamp: u64,
 }
-
-impl FlexRayVFrStartCycle {
-    pub(crate) fn read(cursor: &mut Cursor<&[u8]>, header: &ObjectHeader) -> BlfParseResult<Self> {
-        let channel = cursor.read_u16::<LittleEndian>()?;
-        let version = cursor.read_u16::<LittleEndian>()?;
-        let channel_mask = cursor.read_u16::<LittleEndian>()?;
-        let dir = cursor.read_u8()?;
-        let cycle = cursor.read_u8()?;
-        let client_index = cursor.read_u32::<LittleEndian>()?;
-        let cluster_no = cursor.read_u32::<LittleEndian>()?;
-        let nm_size = cursor.read_u16::<LittleEndian>()?;
-        let mut data_bytes = [0u8; 12];
-        cursor.read_exact(&mut data_bytes)?;
-        let _reserved1 = cursor.read_u16::<LittleEndian>()?;
-        let tag = cursor.read_u32::<LittleEndian>()?;
-        let mut data = [0u32; 5];
-        for item in &mut data {
-            *item = cursor.read_u32::<LittleEndian>()?;
-        }
-        let _reserved2 = cursor.read_u64::<LittleEndian>()?;
-
-        Ok(Self {
-            channel,
-            version,
-            channel_mask,
-            dir,
-            cycle,
-            client_index,
-            cluster_no,
-            nm_size,
-            data_bytes,
-            tag,
-            data,
-            timestamp: header.object_time_stamp,
-        })
-    }
-
-    pub(crate) fn write<W: Write>(&self, _writer: &mut W) -> BlfParseResult<()> {
-        unimplemented!("Writing FlexRayVFrStartCycle is not yet implemented.")
-    }
-}
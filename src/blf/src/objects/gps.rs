@@ -0,0 +1,54 @@
+//! GPS event object definition.
+
+/// Represents a GPS position fix (`GPS_EVENT`).
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct GpsEvent {
+    /// Validity flags of the fix.
+    pub flags: u32,
+    /// Application channel.
+    pub channel: u16,
+    /// Latitude in degrees, positive north.
+    pub latitude: f64,
+    /// Longitude in degrees, positive east.
+    pub longitude: f64,
+    /// Altitude in meters.
+    pub altitude: f64,
+    /// Ground speed in km/h.
+    pub speed: f64,
+    /// Course over ground in degrees.
+    pub course: f64,
+    /// Timestamp of the message.
+    pub timestamp: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ByteReader, ObjectHeader};
+    use alloc::vec::Vec;
+
+    #[test]
+    fn test_gps_event_decode() {
+        let mut body = Vec::new();
+        body.extend_from_slice(&1u32.to_le_bytes());
+        body.extend_from_slice(&2u16.to_le_bytes());
+        body.extend_from_slice(&0u16.to_le_bytes());
+        for value in [48.137f64, 11.575, 519.0, 42.5, 270.0] {
+            body.extend_from_slice(&value.to_le_bytes());
+        }
+
+        let header = ObjectHeader {
+            object_time_stamp: 5_000,
+            ..Default::default()
+        };
+        let mut reader = ByteReader::new(&body);
+        let event = GpsEvent::decode(&mut reader, &header).unwrap();
+
+        assert_eq!(event.channel, 2);
+        assert_eq!(event.latitude, 48.137);
+        assert_eq!(event.longitude, 11.575);
+        assert_eq!(event.speed, 42.5);
+        assert_eq!(event.course, 270.0);
+        assert_eq!(event.timestamp, 5_000);
+    }
+}
@@ -1,118 +1,142 @@
 //! LIN event object definitions.
 
+#[cfg(feature = "std")]
 use crate::BlfParseResult;
+#[cfg(feature = "std")]
+use crate::blf_core::decode_at;
 use crate::objects::object_header::ObjectHeader;
+#[cfg(feature = "std")]
 use std::io::Cursor;
 
 // --- Stubs for LIN event objects ---
 
+/// LIN checksum error; only the object header is decoded
 #[derive(Debug, Clone, PartialEq, Default)]
 pub struct LinCrcError {
+    /// Header of the object
     pub header: ObjectHeader,
 }
 impl LinCrcError {
-    pub fn read(_cursor: &mut Cursor<&[u8]>, header: &ObjectHeader) -> BlfParseResult<Self> {
-        Ok(Self {
-            header: header.clone(),
-        })
+    /// Read the object body following `header`
+    #[cfg(feature = "std")]
+    pub fn read(cursor: &mut Cursor<&[u8]>, header: &ObjectHeader) -> BlfParseResult<Self> {
+        decode_at(cursor, |reader| Self::decode(reader, header))
     }
 }
+/// LIN receive error; only the object header is decoded
 #[derive(Debug, Clone, PartialEq, Default)]
 pub struct LinReceiveError {
+    /// Header of the object
     pub header: ObjectHeader,
 }
 impl LinReceiveError {
-    pub fn read(_cursor: &mut Cursor<&[u8]>, header: &ObjectHeader) -> BlfParseResult<Self> {
-        Ok(Self {
-            header: header.clone(),
-        })
+    /// Read the object body following `header`
+    #[cfg(feature = "std")]
+    pub fn read(cursor: &mut Cursor<&[u8]>, header: &ObjectHeader) -> BlfParseResult<Self> {
+        decode_at(cursor, |reader| Self::decode(reader, header))
     }
 }
+/// LIN frame header sent without a response; only the object header is decoded
 #[derive(Debug, Clone, PartialEq, Default)]
 pub struct LinSendError {
+    /// Header of the object
     pub header: ObjectHeader,
 }
 impl LinSendError {
-    pub fn read(_cursor: &mut Cursor<&[u8]>, header: &ObjectHeader) -> BlfParseResult<Self> {
-        Ok(Self {
-            header: header.clone(),
-        })
+    /// Read the object body following `header`
+    #[cfg(feature = "std")]
+    pub fn read(cursor: &mut Cursor<&[u8]>, header: &ObjectHeader) -> BlfParseResult<Self> {
+        decode_at(cursor, |reader| Self::decode(reader, header))
     }
 }
+/// LIN slave that did not answer in time; only the object header is decoded
 #[derive(Debug, Clone, PartialEq, Default)]
 pub struct LinSlaveTimeout {
+    /// Header of the object
     pub header: ObjectHeader,
 }
 impl LinSlaveTimeout {
-    pub fn read(_cursor: &mut Cursor<&[u8]>, header: &ObjectHeader) -> BlfParseResult<Self> {
-        Ok(Self {
-            header: header.clone(),
-        })
+    /// Read the object body following `header`
+    #[cfg(feature = "std")]
+    pub fn read(cursor: &mut Cursor<&[u8]>, header: &ObjectHeader) -> BlfParseResult<Self> {
+        decode_at(cursor, |reader| Self::decode(reader, header))
     }
 }
+/// LIN scheduler switched tables; only the object header is decoded
 #[derive(Debug, Clone, PartialEq, Default)]
 pub struct LinSchedulerModeChange {
+    /// Header of the object
     pub header: ObjectHeader,
 }
 impl LinSchedulerModeChange {
-    pub fn read(_cursor: &mut Cursor<&[u8]>, header: &ObjectHeader) -> BlfParseResult<Self> {
-        Ok(Self {
-            header: header.clone(),
-        })
+    /// Read the object body following `header`
+    #[cfg(feature = "std")]
+    pub fn read(cursor: &mut Cursor<&[u8]>, header: &ObjectHeader) -> BlfParseResult<Self> {
+        decode_at(cursor, |reader| Self::decode(reader, header))
     }
 }
+/// LIN synchronisation field error; only the object header is decoded
 #[derive(Debug, Clone, PartialEq, Default)]
 pub struct LinSyncError {
+    /// Header of the object
     pub header: ObjectHeader,
 }
 impl LinSyncError {
-    pub fn read(_cursor: &mut Cursor<&[u8]>, header: &ObjectHeader) -> BlfParseResult<Self> {
-        Ok(Self {
-            header: header.clone(),
-        })
+    /// Read the object body following `header`
+    #[cfg(feature = "std")]
+    pub fn read(cursor: &mut Cursor<&[u8]>, header: &ObjectHeader) -> BlfParseResult<Self> {
+        decode_at(cursor, |reader| Self::decode(reader, header))
     }
 }
+/// LIN baud rate detected on the bus; only the object header is decoded
 #[derive(Debug, Clone, PartialEq, Default)]
 pub struct LinBaudrateEvent {
+    /// Header of the object
     pub header: ObjectHeader,
 }
 impl LinBaudrateEvent {
-    pub fn read(_cursor: &mut Cursor<&[u8]>, header: &ObjectHeader) -> BlfParseResult<Self> {
-        Ok(Self {
-            header: header.clone(),
-        })
+    /// Read the object body following `header`
+    #[cfg(feature = "std")]
+    pub fn read(cursor: &mut Cursor<&[u8]>, header: &ObjectHeader) -> BlfParseResult<Self> {
+        decode_at(cursor, |reader| Self::decode(reader, header))
     }
 }
+/// LIN bus went to or left sleep mode; only the object header is decoded
 #[derive(Debug, Clone, PartialEq, Default)]
 pub struct LinSleepModeEvent {
+    /// Header of the object
     pub header: ObjectHeader,
 }
 impl LinSleepModeEvent {
-    pub fn read(_cursor: &mut Cursor<&[u8]>, header: &ObjectHeader) -> BlfParseResult<Self> {
-        Ok(Self {
-            header: header.clone(),
-        })
+    /// Read the object body following `header`
+    #[cfg(feature = "std")]
+    pub fn read(cursor: &mut Cursor<&[u8]>, header: &ObjectHeader) -> BlfParseResult<Self> {
+        decode_at(cursor, |reader| Self::decode(reader, header))
     }
 }
+/// LIN wake-up frame; only the object header is decoded
 #[derive(Debug, Clone, PartialEq, Default)]
 pub struct LinWakeupEvent {
+    /// Header of the object
     pub header: ObjectHeader,
 }
 impl LinWakeupEvent {
-    pub fn read(_cursor: &mut Cursor<&[u8]>, header: &ObjectHeader) -> BlfParseResult<Self> {
-        Ok(Self {
-            header: header.clone(),
-        })
+    /// Read the object body following `header`
+    #[cfg(feature = "std")]
+    pub fn read(cursor: &mut Cursor<&[u8]>, header: &ObjectHeader) -> BlfParseResult<Self> {
+        decode_at(cursor, |reader| Self::decode(reader, header))
     }
 }
+/// LIN data length learned for a frame ID; only the object header is decoded
 #[derive(Debug, Clone, PartialEq, Default)]
 pub struct LinDlcInfo {
+    /// Header of the object
     pub header: ObjectHeader,
 }
 impl LinDlcInfo {
-    pub fn read(_cursor: &mut Cursor<&[u8]>, header: &ObjectHeader) -> BlfParseResult<Self> {
-        Ok(Self {
-            header: header.clone(),
-        })
+    /// Read the object body following `header`
+    #[cfg(feature = "std")]
+    pub fn read(cursor: &mut Cursor<&[u8]>, header: &ObjectHeader) -> BlfParseResult<Self> {
+        decode_at(cursor, |reader| Self::decode(reader, header))
     }
 }
@@ -1,10 +1,18 @@
 //! Log container object definition.
 
+#[cfg(feature = "std")]
+use crate::blf_core::decode_at;
+use crate::codec::LOG_CONTAINER_FIELDS_SIZE;
 use crate::objects::object_header::ObjectHeaderBase;
+#[cfg(feature = "std")]
 use crate::{BlfParseError, BlfParseResult};
-use byteorder::{LittleEndian, ReadBytesExt};
+use alloc::vec::Vec;
+#[cfg(feature = "compression")]
 use flate2::read::ZlibDecoder;
-use std::io::{Cursor, Read};
+#[cfg(feature = "std")]
+use std::io::Cursor;
+#[cfg(feature = "compression")]
+use std::io::Read;
 
 /// Represents a container for other log objects, which may be compressed (`LOG_CONTAINER`).
 #[derive(Debug, Clone)]
@@ -12,6 +20,9 @@ pub struct LogContainer {
     /// The header of this log container.
     pub header: ObjectHeaderBase,
     /// The compression method used (0 = None, 2 = zlib).
+    ///
+    /// zlib containers need the `compression` feature; without it they fail
+    /// with [`BlfParseError::UnsupportedCompression`].
     pub compression_method: u16,
     /// The uncompressed data.
     pub uncompressed_data: Vec<u8>,
@@ -19,25 +30,17 @@ pub struct LogContainer {
 
 impl LogContainer {
     /// Reads and uncompresses a `LogContainer` from a byte stream.
+    #[cfg(feature = "std")]
     pub fn read(cursor: &mut Cursor<&[u8]>, header: ObjectHeaderBase) -> BlfParseResult<Self> {
-        let compression_method = cursor.read_u16::<LittleEndian>()?;
-        let _reserved1 = cursor.read_u16::<LittleEndian>()?;
-        let _reserved2 = cursor.read_u32::<LittleEndian>()?;
-        let uncompressed_size = cursor.read_u32::<LittleEndian>()? as usize;
-        let _reserved3 = cursor.read_u32::<LittleEndian>()?;
-
-        let log_container_specific_fields_size = 16;
-        let data_size = (header.object_size as usize)
-            .saturating_sub(header.header_size as usize)
-            .saturating_sub(log_container_specific_fields_size);
-
-        let mut compressed_data = vec![0; data_size];
-        cursor.read_exact(&mut compressed_data)?;
+        #[cfg_attr(not(feature = "compression"), allow(unused_variables))]
+        let (compression_method, uncompressed_size, stored) =
+            decode_at(cursor, |reader| Self::decode_stored(reader, &header))?;
 
         let uncompressed_data = match compression_method {
-            0 => compressed_data,
+            0 => stored.to_vec(),
+            #[cfg(feature = "compression")]
             2 => {
-                let mut decoder = ZlibDecoder::new(&compressed_data[..]);
+                let mut decoder = ZlibDecoder::new(stored);
                 let mut uncompressed = Vec::with_capacity(uncompressed_size);
                 decoder.read_to_end(&mut uncompressed)?;
 
@@ -75,6 +78,8 @@ impl LogContainer {
         // Object size should be header_size + compressed data size
         // Header size + log container specific fields (compression_method + reserved1 + reserved2 + uncompressed_size + reserved3)
         // + actual data size
-        self.header.header_size as u32 + 16 + self.uncompressed_data.len() as u32
+        self.header.header_size as u32
+            + LOG_CONTAINER_FIELDS_SIZE as u32
+            + self.uncompressed_data.len() as u32
     }
 }
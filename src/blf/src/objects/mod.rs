@@ -7,6 +7,7 @@ pub mod can;
 pub mod env_vars;
 pub mod ethernet;
 pub mod flexray;
+pub mod gps;
 pub mod lin;
 pub mod log_container; // New
 pub mod most;
@@ -17,6 +18,7 @@ pub use app_events::*;
 pub use can::*;
 pub use ethernet::*;
 pub use flexray::*;
+pub use gps::*;
 pub use lin::*;
 // pub use env_vars::*; // Not used - commented out to avoid warning
 pub use log_container::*; // New
@@ -1,10 +1,7 @@
 //! MOST (Media Oriented Systems Transport) object definitions.
 //! Most object definitions.
 
-use crate::BlfParseResult;
-use crate::objects::object_header::ObjectHeader;
-use byteorder::{LittleEndian, ReadBytesExt};
-use std::io::{Cursor, Read};
+use alloc::vec::Vec;
 
 /// Represents a message from the MOST Control Channel in spy mode (`MOST_SPY`).
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -33,38 +30,6 @@ pub struct MostSpy {
     pub timestamp: u64,
 }
 
-impl MostSpy {
-    pub(crate) fn read(cursor: &mut Cursor<&[u8]>, header: &ObjectHeader) -> BlfParseResult<Self> {
-        let channel = cursor.read_u16::<LittleEndian>()?;
-        let dir = cursor.read_u8()?;
-        let _reserved1 = cursor.read_u8()?;
-        let source_adr = cursor.read_u32::<LittleEndian>()?;
-        let dest_adr = cursor.read_u32::<LittleEndian>()?;
-        let mut msg = [0u8; 17];
-        cursor.read_exact(&mut msg)?;
-        let _reserved2 = cursor.read_u8()?;
-        let r_typ = cursor.read_u16::<LittleEndian>()?;
-        let r_typ_adr = cursor.read_u8()?;
-        let state = cursor.read_u8()?;
-        let _reserved3 = cursor.read_u8()?;
-        let ack_nack = cursor.read_u8()?;
-        let crc = cursor.read_u32::<LittleEndian>()?;
-        Ok(Self {
-            channel,
-            dir,
-            source_adr,
-            dest_adr,
-            msg,
-            r_typ,
-            r_typ_adr,
-            state,
-            ack_nack,
-            crc,
-            timestamp: header.object_time_stamp,
-        })
-    }
-}
-
 /// Represents a message from the MOST Control Channel in node mode (`MOST_CTRL`).
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct MostCtrl {
@@ -90,36 +55,6 @@ pub struct MostCtrl {
     pub timestamp: u64,
 }
 
-impl MostCtrl {
-    pub(crate) fn read(cursor: &mut Cursor<&[u8]>, header: &ObjectHeader) -> BlfParseResult<Self> {
-        let channel = cursor.read_u16::<LittleEndian>()?;
-        let dir = cursor.read_u8()?;
-        let _reserved1 = cursor.read_u8()?;
-        let source_adr = cursor.read_u32::<LittleEndian>()?;
-        let dest_adr = cursor.read_u32::<LittleEndian>()?;
-        let mut msg = [0u8; 17];
-        cursor.read_exact(&mut msg)?;
-        let _reserved2 = cursor.read_u8()?;
-        let r_typ = cursor.read_u16::<LittleEndian>()?;
-        let r_typ_adr = cursor.read_u8()?;
-        let state = cursor.read_u8()?;
-        let _reserved3 = cursor.read_u8()?;
-        let ack_nack = cursor.read_u8()?;
-        Ok(Self {
-            channel,
-            dir,
-            source_adr,
-            dest_adr,
-            msg,
-            r_typ,
-            r_typ_adr,
-            state,
-            ack_nack,
-            timestamp: header.object_time_stamp,
-        })
-    }
-}
-
 /// Represents a message on the MOST Packet Data Channel (`MOST_PKT2`).
 #[derive(Debug, Clone, PartialEq)]
 pub struct MostPkt2 {
@@ -151,46 +86,6 @@ pub struct MostPkt2 {
     pub original_timestamp: Option<u64>,
 }
 
-impl MostPkt2 {
-    pub(crate) fn read(cursor: &mut Cursor<&[u8]>, header: &ObjectHeader) -> BlfParseResult<Self> {
-        let channel = cursor.read_u16::<LittleEndian>()?;
-        let dir = cursor.read_u8()?;
-        let _reserved1 = cursor.read_u8()?;
-        let source_adr = cursor.read_u32::<LittleEndian>()?;
-        let dest_adr = cursor.read_u32::<LittleEndian>()?;
-        let arbitration = cursor.read_u8()?;
-        let _time_res = cursor.read_u8()?;
-        let quads_to_follow = cursor.read_u8()?;
-        let _reserved2 = cursor.read_u8()?;
-        let crc = cursor.read_u16::<LittleEndian>()?;
-        let priority = cursor.read_u8()?;
-        let transfer_type = cursor.read_u8()?;
-        let state = cursor.read_u8()?;
-        let _reserved3 = cursor.read_u8()?;
-        let _reserved4 = cursor.read_u16::<LittleEndian>()?;
-        let pkt_data_length = cursor.read_u32::<LittleEndian>()? as usize;
-        let _reserved5 = cursor.read_u32::<LittleEndian>()?;
-        let mut pkt_data = vec![0; pkt_data_length];
-        cursor.read_exact(&mut pkt_data)?;
-
-        Ok(Self {
-            channel,
-            dir,
-            source_adr,
-            dest_adr,
-            arbitration,
-            quads_to_follow,
-            crc,
-            priority,
-            transfer_type,
-            state,
-            pkt_data,
-            timestamp: header.object_time_stamp,
-            original_timestamp: header.original_time_stamp,
-        })
-    }
-}
-
 /// Represents a MOST light lock event (`MOST_LIGHTLOCK`).
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct MostLightLock {
@@ -202,19 +97,6 @@ pub struct MostLightLock {
     pub timestamp: u64,
 }
 
-impl MostLightLock {
-    pub(crate) fn read(cursor: &mut Cursor<&[u8]>, header: &ObjectHeader) -> BlfParseResult<Self> {
-        let channel = cursor.read_u16::<LittleEndian>()?;
-        let state = cursor.read_i16::<LittleEndian>()?;
-        let _reserved = cursor.read_u32::<LittleEndian>()?;
-        Ok(Self {
-            channel,
-            state,
-            timestamp: header.object_time_stamp,
-        })
-    }
-}
-
 /// Represents MOST network statistics (`MOST_STATISTIC`).
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct MostStatistic {
@@ -232,24 +114,6 @@ pub struct MostStatistic {
     pub timestamp: u64,
 }
 
-impl MostStatistic {
-    pub(crate) fn read(cursor: &mut Cursor<&[u8]>, header: &ObjectHeader) -> BlfParseResult<Self> {
-        let channel = cursor.read_u16::<LittleEndian>()?;
-        let pkt_cnt = cursor.read_u16::<LittleEndian>()?;
-        let frm_cnt = cursor.read_i32::<LittleEndian>()?;
-        let light_cnt = cursor.read_i32::<LittleEndian>()?;
-        let buffer_level = cursor.read_i32::<LittleEndian>()?;
-        Ok(Self {
-            channel,
-            pkt_cnt,
-            frm_cnt,
-            light_cnt,
-            buffer_level,
-            timestamp: header.object_time_stamp,
-        })
-    }
-}
-
 /// Represents a MOST hardware mode event (`MOST_HWMODE`).
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct MostHwMode {
@@ -265,22 +129,6 @@ pub struct MostHwMode {
     pub original_timestamp: Option<u64>,
 }
 
-impl MostHwMode {
-    pub(crate) fn read(cursor: &mut Cursor<&[u8]>, header: &ObjectHeader) -> BlfParseResult<Self> {
-        let channel = cursor.read_u16::<LittleEndian>()?;
-        let _reserved = cursor.read_u16::<LittleEndian>()?;
-        let hw_mode = cursor.read_u16::<LittleEndian>()?;
-        let hw_mode_mask = cursor.read_u16::<LittleEndian>()?;
-        Ok(Self {
-            channel,
-            hw_mode,
-            hw_mode_mask,
-            timestamp: header.object_time_stamp,
-            original_timestamp: header.original_time_stamp,
-        })
-    }
-}
-
 /// Represents MOST register data (`MOST_REG`).
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct MostReg {
@@ -304,31 +152,6 @@ pub struct MostReg {
     pub original_timestamp: Option<u64>,
 }
 
-impl MostReg {
-    pub(crate) fn read(cursor: &mut Cursor<&[u8]>, header: &ObjectHeader) -> BlfParseResult<Self> {
-        let channel = cursor.read_u16::<LittleEndian>()?;
-        let sub_type = cursor.read_u8()?;
-        let _reserved1 = cursor.read_u8()?;
-        let handle = cursor.read_u32::<LittleEndian>()?;
-        let offset = cursor.read_u32::<LittleEndian>()?;
-        let chip = cursor.read_u16::<LittleEndian>()?;
-        let reg_data_len = cursor.read_u16::<LittleEndian>()?;
-        let mut reg_data = [0u8; 16];
-        cursor.read_exact(&mut reg_data)?;
-        Ok(Self {
-            channel,
-            sub_type,
-            handle,
-            offset,
-            chip,
-            reg_data_len,
-            reg_data,
-            timestamp: header.object_time_stamp,
-            original_timestamp: header.original_time_stamp,
-        })
-    }
-}
-
 /// Represents MOST general register data (`MOST_GENREG`).
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct MostGenReg {
@@ -348,28 +171,6 @@ pub struct MostGenReg {
     pub original_timestamp: Option<u64>,
 }
 
-impl MostGenReg {
-    pub(crate) fn read(cursor: &mut Cursor<&[u8]>, header: &ObjectHeader) -> BlfParseResult<Self> {
-        let channel = cursor.read_u16::<LittleEndian>()?;
-        let sub_type = cursor.read_u8()?;
-        let _reserved1 = cursor.read_u8()?;
-        let handle = cursor.read_u32::<LittleEndian>()?;
-        let reg_id = cursor.read_u16::<LittleEndian>()?;
-        let _reserved2 = cursor.read_u16::<LittleEndian>()?;
-        let _reserved3 = cursor.read_u32::<LittleEndian>()?;
-        let reg_value = cursor.read_u64::<LittleEndian>()?;
-        Ok(Self {
-            channel,
-            sub_type,
-            handle,
-            reg_id,
-            reg_value,
-            timestamp: header.object_time_stamp,
-            original_timestamp: header.original_time_stamp,
-        })
-    }
-}
-
 /// Represents a MOST network state event (`MOST_NETSTATE`).
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct MostNetState {
@@ -385,22 +186,6 @@ pub struct MostNetState {
     pub original_timestamp: Option<u64>,
 }
 
-impl MostNetState {
-    pub(crate) fn read(cursor: &mut Cursor<&[u8]>, header: &ObjectHeader) -> BlfParseResult<Self> {
-        let channel = cursor.read_u16::<LittleEndian>()?;
-        let state_new = cursor.read_u16::<LittleEndian>()?;
-        let state_old = cursor.read_u16::<LittleEndian>()?;
-        let _reserved = cursor.read_u16::<LittleEndian>()?;
-        Ok(Self {
-            channel,
-            state_new,
-            state_old,
-            timestamp: header.object_time_stamp,
-            original_timestamp: header.original_time_stamp,
-        })
-    }
-}
-
 /// Represents a MOST data lost event (`MOST_DATALOST`).
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct MostDataLost {
@@ -422,28 +207,6 @@ pub struct MostDataLost {
     pub original_timestamp: Option<u64>,
 }
 
-impl MostDataLost {
-    pub(crate) fn read(cursor: &mut Cursor<&[u8]>, header: &ObjectHeader) -> BlfParseResult<Self> {
-        let channel = cursor.read_u16::<LittleEndian>()?;
-        let _reserved1 = cursor.read_u16::<LittleEndian>()?;
-        let info = cursor.read_u32::<LittleEndian>()?;
-        let lost_msgs_ctrl = cursor.read_u32::<LittleEndian>()?;
-        let lost_msgs_async = cursor.read_u32::<LittleEndian>()?;
-        let last_good_time_stamp_ns = cursor.read_u64::<LittleEndian>()?;
-        let next_good_time_stamp_ns = cursor.read_u64::<LittleEndian>()?;
-        Ok(Self {
-            channel,
-            info,
-            lost_msgs_ctrl,
-            lost_msgs_async,
-            last_good_time_stamp_ns,
-            next_good_time_stamp_ns,
-            timestamp: header.object_time_stamp,
-            original_timestamp: header.original_time_stamp,
-        })
-    }
-}
-
 /// Represents a MOST trigger event (`MOST_TRIGGER`).
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct MostTrigger {
@@ -462,23 +225,3 @@ pub struct MostTrigger {
     /// Original timestamp, if available.
     pub original_timestamp: Option<u64>,
 }
-
-impl MostTrigger {
-    pub(crate) fn read(cursor: &mut Cursor<&[u8]>, header: &ObjectHeader) -> BlfParseResult<Self> {
-        let channel = cursor.read_u16::<LittleEndian>()?;
-        let _reserved1 = cursor.read_u16::<LittleEndian>()?;
-        let mode = cursor.read_u16::<LittleEndian>()?;
-        let hw = cursor.read_u16::<LittleEndian>()?;
-        let previous_trigger_value = cursor.read_u32::<LittleEndian>()?;
-        let current_trigger_value = cursor.read_u32::<LittleEndian>()?;
-        Ok(Self {
-            channel,
-            mode,
-            hw,
-            previous_trigger_value,
-            current_trigger_value,
-            timestamp: header.object_time_stamp,
-            original_timestamp: header.original_time_stamp,
-        })
-    }
-}
@@ -8,8 +8,13 @@
 //! In Rust, we use composition instead of inheritance.
 
 use crate::ObjectType;
+#[cfg(feature = "std")]
+use crate::blf_core::decode_at;
+#[cfg(feature = "std")]
 use crate::{BlfParseError, BlfParseResult};
-use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+use alloc::format;
+use alloc::string::String;
+#[cfg(feature = "std")]
 use std::io::{Cursor, Write};
 
 /// Object signature constant ("LOBJ" = 0x4A424F4C)
@@ -59,32 +64,17 @@ impl ObjectHeaderBase {
     }
 
     /// Reads the base header fields from a byte stream.
+    #[cfg(feature = "std")]
     pub fn read(cursor: &mut Cursor<&[u8]>) -> BlfParseResult<Self> {
-        let signature = cursor.read_u32::<LittleEndian>()?;
-        if signature != OBJECT_SIGNATURE {
-            return Err(BlfParseError::InvalidContainerMagic);
-        }
-        let header_size = cursor.read_u16::<LittleEndian>()?;
-        let header_version = cursor.read_u16::<LittleEndian>()?;
-        let object_size = cursor.read_u32::<LittleEndian>()?;
-        let object_type = ObjectType::from(cursor.read_u32::<LittleEndian>()?);
-
-        Ok(ObjectHeaderBase {
-            signature,
-            header_size,
-            header_version,
-            object_size,
-            object_type,
-        })
+        decode_at(cursor, Self::decode)
     }
 
     /// Writes the base header fields to a byte stream.
+    #[cfg(feature = "std")]
     pub fn write<W: Write>(&self, writer: &mut W) -> BlfParseResult<()> {
-        writer.write_u32::<LittleEndian>(self.signature)?;
-        writer.write_u16::<LittleEndian>(self.header_size)?;
-        writer.write_u16::<LittleEndian>(self.header_version)?;
-        writer.write_u32::<LittleEndian>(self.object_size)?;
-        writer.write_u32::<LittleEndian>(self.object_type as u32)?;
+        let mut bytes = Vec::with_capacity(16);
+        self.encode(&mut bytes);
+        writer.write_all(&bytes)?;
         Ok(())
     }
 
@@ -93,11 +83,11 @@ impl ObjectHeaderBase {
     /// For the base class, this is always 16 bytes:
     /// - signature (4) + headerSize (2) + headerVersion (2) + objectSize (4) + objectType (4)
     pub fn calculate_header_size(&self) -> u16 {
-        std::mem::size_of::<u32>() as u16 + // signature
-        std::mem::size_of::<u16>() as u16 + // headerSize
-        std::mem::size_of::<u16>() as u16 + // headerVersion
-        std::mem::size_of::<u32>() as u16 + // objectSize
-        std::mem::size_of::<u32>() as u16 // objectType
+        core::mem::size_of::<u32>() as u16 + // signature
+        core::mem::size_of::<u16>() as u16 + // headerSize
+        core::mem::size_of::<u16>() as u16 + // headerVersion
+        core::mem::size_of::<u32>() as u16 + // objectSize
+        core::mem::size_of::<u32>() as u16 // objectType
     }
 
     /// Calculates the object size in bytes (corresponds to C++ ObjectHeaderBase::calculateObjectSize).
@@ -118,6 +108,28 @@ pub enum ObjectFlags {
     TimeOneNans = 0x00000002,
 }
 
+/// Resolution of the timestamps stored in a header, selected by its object flags.
+///
+/// Timestamps are normalized to nanoseconds when a header is read and
+/// converted back to this resolution when it is written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimestampResolution {
+    /// Ticks of 10 microseconds (`ObjectFlags::TimeTenMics`)
+    TenMicroseconds,
+    /// Ticks of 1 nanosecond (`ObjectFlags::TimeOneNans`)
+    Nanoseconds,
+}
+
+impl TimestampResolution {
+    /// Nanoseconds per stored timestamp tick.
+    pub fn nanos_per_tick(&self) -> u64 {
+        match self {
+            TimestampResolution::TenMicroseconds => 10_000,
+            TimestampResolution::Nanoseconds => 1,
+        }
+    }
+}
+
 /// Timestamp status flags (corresponds to C++ ObjectHeader2::TimeStampStatus).
 #[derive(Debug, Clone, Copy)]
 pub enum TimeStampStatus {
@@ -181,11 +193,11 @@ pub struct ObjectHeader {
     pub object_version: u16,
 
     // V1 & V2 common fields
-    /// Timestamp of the object.
+    /// Timestamp of the object in nanoseconds, whatever resolution the file stores.
     pub object_time_stamp: u64,
 
     // V2-specific fields
-    /// Original timestamp (only in V2 headers).
+    /// Original timestamp in nanoseconds (only in V2 headers).
     pub original_time_stamp: Option<u64>,
     /// Timestamp status (only in V2 headers).
     pub time_stamp_status: Option<u8>,
@@ -202,7 +214,11 @@ impl ObjectHeader {
     ///
     /// # Example
     /// ```
+    /// use blf::{ObjectHeader, ObjectType};
+    ///
     /// let header = ObjectHeader::new_v1(ObjectType::CanMessage, 0);
+    /// assert_eq!(header.version(), 1);
+    /// assert_eq!(header.object_type(), ObjectType::CanMessage);
     /// ```
     ///
     /// # C++ Correspondence
@@ -235,7 +251,11 @@ impl ObjectHeader {
     ///
     /// # Example
     /// ```
+    /// use blf::{ObjectHeader, ObjectType};
+    ///
     /// let header = ObjectHeader::new_v2(ObjectType::CanMessage2);
+    /// assert_eq!(header.version(), 2);
+    /// assert_eq!(header.original_time_stamp, Some(0));
     /// ```
     ///
     /// # C++ Correspondence
@@ -273,73 +293,20 @@ impl ObjectHeader {
     }
 
     /// Reads an `ObjectHeader` (V1 or V2) from a byte stream.
+    #[cfg(feature = "std")]
     pub fn read(cursor: &mut Cursor<&[u8]>) -> BlfParseResult<Self> {
-        // Read base header first
-        let base = ObjectHeaderBase::read(cursor)?;
-
-        let mut object_flags = 0;
-        let mut client_index = 0;
-        let mut object_version = 0;
-        let mut object_time_stamp = 0;
-        let mut original_time_stamp = None;
-        let mut time_stamp_status = None;
-        let mut reserved = 0;
-
-        if base.header_version == 1 {
-            // V1 header 解析
-            // 注意：有些 BLF 文件的 header_size 字段不准确
-            // 即使 header_size=16，实际数据中也可能包含完整的 32 字节 header
-            // 我们需要根据实际可用数据来判断
-
-            if base.header_size >= 32 {
-                // 标准的完整 V1 header (32 字节)
-                object_flags = cursor.read_u32::<LittleEndian>()?;
-                client_index = cursor.read_u16::<LittleEndian>()?;
-                object_version = cursor.read_u16::<LittleEndian>()?;
-                object_time_stamp = cursor.read_u64::<LittleEndian>()?;
-            } else if base.header_size == 16 {
-                // header_size 声称是 16 字节，但我们尝试读取完整数据
-                // 因为很多 BLF 文件的 header_size 字段不准确
-
-                // 检查是否还有足够的数据（至少 16 字节）
-                let remaining = cursor.get_ref().len() - cursor.position() as usize;
-
-                if remaining >= 16 {
-                    // 有足够数据，尝试读取完整 header
-                    object_flags = cursor.read_u32::<LittleEndian>()?;
-                    client_index = cursor.read_u16::<LittleEndian>()?;
-                    object_version = cursor.read_u16::<LittleEndian>()?;
-                    object_time_stamp = cursor.read_u64::<LittleEndian>()?;
-                } else {
-                    // 数据不足，这才是真正的紧凑型 header
-                    // 保持默认值（全零）
-                }
-            } else {
-                return Err(BlfParseError::UnknownHeaderVersion(base.header_version));
-            }
-        } else if base.header_version == 2 {
-            // V2 header: flags + timeStampStatus + reserved + objectVersion + timestamp + originalTimestamp
-            object_flags = cursor.read_u32::<LittleEndian>()?;
-            time_stamp_status = Some(cursor.read_u8()?);
-            reserved = cursor.read_u8()?;
-            object_version = cursor.read_u16::<LittleEndian>()?;
-            object_time_stamp = cursor.read_u64::<LittleEndian>()?;
-            original_time_stamp = Some(cursor.read_u64::<LittleEndian>()?);
-            client_index = 0; // Not used in V2
+        decode_at(cursor, Self::decode)
+    }
+
+    /// Resolution the timestamps of this object are stored with in the file.
+    ///
+    /// Headers without a resolution flag are treated as nanoseconds.
+    pub fn timestamp_resolution(&self) -> TimestampResolution {
+        if self.object_flags & ObjectFlags::TimeTenMics as u32 != 0 {
+            TimestampResolution::TenMicroseconds
         } else {
-            return Err(BlfParseError::UnknownHeaderVersion(base.header_version));
+            TimestampResolution::Nanoseconds
         }
-
-        Ok(ObjectHeader {
-            base,
-            object_flags,
-            client_index,
-            object_version,
-            object_time_stamp,
-            original_time_stamp,
-            time_stamp_status,
-            reserved,
-        })
     }
 
     /// Writes an `ObjectHeader` to a byte stream (corresponds to C++ ObjectHeader::write).
@@ -349,38 +316,21 @@ impl ObjectHeader {
     ///
     /// # Example
     /// ```
-    /// header.prepare_for_write();  // Calculate sizes first
+    /// use blf::{ObjectHeader, ObjectType};
+    ///
+    /// let mut header = ObjectHeader::new_v1(ObjectType::CanMessage, 0);
+    /// header.prepare_for_write(); // Calculate sizes first
+    /// let mut writer = Vec::new();
     /// header.write(&mut writer)?;
+    /// assert_eq!(writer.len(), 32);
+    /// assert_eq!(&writer[..4], b"LOBJ");
+    /// # Ok::<(), blf::BlfParseError>(())
     /// ```
+    #[cfg(feature = "std")]
     pub fn write<W: Write>(&self, writer: &mut W) -> BlfParseResult<()> {
-        // Write base header first
-        self.base.write(writer)?;
-
-        if self.base.header_version == 1 {
-            // V1 has two variants:
-            // - 16 bytes: compact header (no additional fields)
-            // - 32 bytes: full header with flags, clientIndex, objectVersion, timestamp
-            if self.base.header_size >= 32 {
-                // Full V1 header
-                writer.write_u32::<LittleEndian>(self.object_flags)?;
-                writer.write_u16::<LittleEndian>(self.client_index)?;
-                writer.write_u16::<LittleEndian>(self.object_version)?;
-                writer.write_u64::<LittleEndian>(self.object_time_stamp)?;
-            }
-            // If header_size == 16, don't write anything else (compact header)
-        } else if self.base.header_version == 2 {
-            // V2 header: flags + timeStampStatus + reserved + objectVersion + timestamp + originalTimestamp
-            writer.write_u32::<LittleEndian>(self.object_flags)?;
-            writer.write_u8(self.time_stamp_status.unwrap_or(0))?;
-            writer.write_u8(self.reserved)?;
-            writer.write_u16::<LittleEndian>(self.object_version)?;
-            writer.write_u64::<LittleEndian>(self.object_time_stamp)?;
-            writer.write_u64::<LittleEndian>(self.original_time_stamp.unwrap_or(0))?;
-        } else {
-            return Err(BlfParseError::UnknownHeaderVersion(
-                self.base.header_version,
-            ));
-        }
+        let mut bytes = Vec::with_capacity(40);
+        self.encode(&mut bytes)?;
+        writer.write_all(&bytes)?;
         Ok(())
     }
 
@@ -392,19 +342,19 @@ impl ObjectHeader {
         if self.base.header_version == 1 {
             // V1: ObjectHeader
             self.base.calculate_header_size() + // 16
-            std::mem::size_of::<u32>() as u16 + // objectFlags
-            std::mem::size_of::<u16>() as u16 + // clientIndex
-            std::mem::size_of::<u16>() as u16 + // objectVersion
-            std::mem::size_of::<u64>() as u16 // objectTimeStamp
+            core::mem::size_of::<u32>() as u16 + // objectFlags
+            core::mem::size_of::<u16>() as u16 + // clientIndex
+            core::mem::size_of::<u16>() as u16 + // objectVersion
+            core::mem::size_of::<u64>() as u16 // objectTimeStamp
         } else if self.base.header_version == 2 {
             // V2: ObjectHeader2 = 40 bytes
             self.base.calculate_header_size() + // 16
-            std::mem::size_of::<u32>() as u16 + // objectFlags (4)
-            std::mem::size_of::<u8>() as u16 +  // timeStampStatus (1)
-            std::mem::size_of::<u8>() as u16 +  // reserved (1)
-            std::mem::size_of::<u16>() as u16 + // objectVersion (2)
-            std::mem::size_of::<u64>() as u16 + // objectTimeStamp (8)
-            std::mem::size_of::<u64>() as u16 // originalTimeStamp (8)
+            core::mem::size_of::<u32>() as u16 + // objectFlags (4)
+            core::mem::size_of::<u8>() as u16 +  // timeStampStatus (1)
+            core::mem::size_of::<u8>() as u16 +  // reserved (1)
+            core::mem::size_of::<u16>() as u16 + // objectVersion (2)
+            core::mem::size_of::<u64>() as u16 + // objectTimeStamp (8)
+            core::mem::size_of::<u64>() as u16 // originalTimeStamp (8)
         // Total: 16 + 4 + 1 + 1 + 2 + 8 + 8 = 40 bytes
         } else {
             self.base.header_size
@@ -425,8 +375,11 @@ impl ObjectHeader {
     ///
     /// # Example
     /// ```
+    /// use blf::{ObjectHeader, ObjectType};
+    ///
+    /// let mut header = ObjectHeader::new_v2(ObjectType::CanMessage2);
     /// header.prepare_for_write();
-    /// header.write(&mut writer)?;
+    /// assert_eq!(header.calculate_header_size(), 40);
     /// ```
     pub fn prepare_for_write(&mut self) {
         self.base.header_size = self.calculate_header_size();
@@ -450,7 +403,7 @@ impl ObjectHeader {
 }
 
 // Provide convenience methods for accessing common fields
-impl std::ops::Deref for ObjectHeader {
+impl core::ops::Deref for ObjectHeader {
     type Target = ObjectHeaderBase;
 
     fn deref(&self) -> &Self::Target {
@@ -458,7 +411,7 @@ impl std::ops::Deref for ObjectHeader {
     }
 }
 
-impl std::ops::DerefMut for ObjectHeader {
+impl core::ops::DerefMut for ObjectHeader {
     fn deref_mut(&mut self) -> &mut Self::Target {
         &mut self.base
     }
@@ -503,6 +456,7 @@ impl Default for ObjectHeader {
 
 impl ObjectHeader {
     /// Validates the header consistency with detailed logging.
+    #[cfg(feature = "std")]
     pub fn validate(&self) -> BlfParseResult<()> {
         if self.signature != OBJECT_SIGNATURE {
             println!("ERROR: Invalid object signature: 0x{:08X}", self.signature);
@@ -557,7 +511,7 @@ impl ObjectHeader {
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "std"))]
 mod tests {
     use super::*;
     use std::io::Cursor;
@@ -645,6 +599,29 @@ mod tests {
         assert_eq!(header2.time_stamp_status, Some(0x01));
     }
 
+    #[test]
+    fn test_ten_microsecond_timestamps_are_normalized() {
+        let mut header = ObjectHeader::new_v2(ObjectType::CanMessage2);
+        header.object_flags = ObjectFlags::TimeTenMics as u32;
+        header.object_time_stamp = 1_230_000;
+        header.original_time_stamp = Some(40_000);
+        header.prepare_for_write();
+
+        let mut buffer = Vec::new();
+        header.write(&mut buffer).unwrap();
+        // Stored as 10 µs ticks
+        assert_eq!(&buffer[24..32], &123u64.to_le_bytes());
+
+        let mut cursor = Cursor::new(buffer.as_slice());
+        let header2 = ObjectHeader::read(&mut cursor).unwrap();
+        assert_eq!(
+            header2.timestamp_resolution(),
+            TimestampResolution::TenMicroseconds
+        );
+        assert_eq!(header2.object_time_stamp, 1_230_000);
+        assert_eq!(header2.original_time_stamp, Some(40_000));
+    }
+
     #[test]
     fn test_object_header_v1_compact_header() {
         // Create a compact V1 header (16 bytes)
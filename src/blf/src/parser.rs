@@ -1,143 +1,19 @@
 //! BLF parser implementation.
 //! BLF parser module for parsing log objects from BLF files.
 
+use crate::blf_core::decode_at;
 use crate::objects::*;
-use crate::{BlfParseError, BlfParseResult, LogContainer, ObjectType};
-
-use std::io::{Cursor, Read};
-
-// Log object enum for all supported BLF objects
-#[derive(Debug, Clone, PartialEq)]
-pub enum LogObject {
-    CanMessage(CanMessage),
-    CanMessage2(CanMessage2),
-    CanErrorFrame(CanErrorFrame),
-    CanFdMessage(CanFdMessage),
-    CanFdMessage64(CanFdMessage64),
-    CanOverloadFrame(CanOverloadFrame),
-    CanDriverStatistic(CanDriverStatistic),
-    CanDriverError(CanDriverError),
-    LinMessage(LinMessage),
-    LinMessage2(LinMessage2),
-    LinCrcError(LinCrcError),
-    LinDlcInfo(LinDlcInfo),
-    LinReceiveError(LinReceiveError),
-    LinSendError(LinSendError),
-    LinSlaveTimeout(LinSlaveTimeout),
-    LinSchedulerModeChange(LinSchedulerModeChange),
-    LinSyncError(LinSyncError),
-    LinBaudrateEvent(LinBaudrateEvent),
-    LinSleepModeEvent(LinSleepModeEvent),
-    LinWakeupEvent(LinWakeupEvent),
-    FlexRayData(FlexRayData),
-    FlexRaySync(FlexRaySync),
-    FlexRayV6Message(FlexRayV6Message),
-    FlexRayV6StartCycleEvent(FlexRayV6StartCycleEvent),
-    FlexRayStatusEvent(FlexRayStatusEvent),
-    FlexRayVFrError(FlexRayVFrError),
-    FlexRayVFrStatus(FlexRayVFrStatus),
-    FlexRayVFrStartCycle(FlexRayVFrStartCycle),
-    FlexRayVFrReceiveMsg(FlexRayVFrReceiveMsg),
-    FlexRayVFrReceiveMsgEx(FlexRayVFrReceiveMsgEx),
-    EthernetFrame(EthernetFrame),
-    // Environment variables
-    // EnvInteger(EnvInteger),
-    // EnvDouble(EnvDouble),
-    // EnvString(EnvString),
-    AppTrigger(AppTrigger),
-    EventComment(EventComment),
-    GlobalMarker(GlobalMarker),
-    MostSpy(MostSpy),
-    MostCtrl(MostCtrl),
-    MostPkt2(MostPkt2),
-    MostLightLock(MostLightLock),
-    MostStatistic(MostStatistic),
-    MostHwMode(MostHwMode),
-    MostReg(MostReg),
-    MostGenReg(MostGenReg),
-    MostNetState(MostNetState),
-    MostDataLost(MostDataLost),
-    MostTrigger(MostTrigger),
-    // Placeholder for unhandled objects
-    Unhandled {
-        object_type: u32,
-        timestamp: u64,
-        data: Vec<u8>,
-    },
-}
-
-impl LogObject {
-    /// Returns the timestamp of the log object
-    pub fn timestamp(&self) -> u64 {
-        match self {
-            LogObject::CanMessage(msg) => msg.header.object_time_stamp,
-            LogObject::CanMessage2(msg) => msg.header.object_time_stamp,
-            LogObject::CanErrorFrame(msg) => msg.header.object_time_stamp,
-            LogObject::CanFdMessage(msg) => msg.header.object_time_stamp,
-            LogObject::CanFdMessage64(msg) => msg.header.object_time_stamp,
-            LogObject::CanOverloadFrame(msg) => msg.header.object_time_stamp,
-            LogObject::CanDriverStatistic(msg) => msg.header.object_time_stamp,
-            LogObject::CanDriverError(msg) => msg.header.object_time_stamp,
-            LogObject::LinMessage(msg) => msg.header.object_time_stamp,
-            LogObject::LinMessage2(msg) => msg.header.object_time_stamp,
-            LogObject::LinCrcError(msg) => msg.header.object_time_stamp,
-            LogObject::LinDlcInfo(msg) => msg.header.object_time_stamp,
-            LogObject::LinReceiveError(msg) => msg.header.object_time_stamp,
-            LogObject::LinSendError(msg) => msg.header.object_time_stamp,
-            LogObject::LinSlaveTimeout(msg) => msg.header.object_time_stamp,
-            LogObject::LinSchedulerModeChange(msg) => msg.header.object_time_stamp,
-            LogObject::LinSyncError(msg) => msg.header.object_time_stamp,
-            LogObject::LinBaudrateEvent(msg) => msg.header.object_time_stamp,
-            LogObject::LinSleepModeEvent(msg) => msg.header.object_time_stamp,
-            LogObject::LinWakeupEvent(msg) => msg.header.object_time_stamp,
-            LogObject::FlexRayData(msg) => msg.timestamp,
-            LogObject::FlexRaySync(msg) => msg.timestamp,
-            LogObject::FlexRayV6Message(msg) => msg.timestamp,
-            LogObject::FlexRayV6StartCycleEvent(msg) => msg.timestamp,
-            LogObject::FlexRayStatusEvent(msg) => msg.timestamp,
-            LogObject::FlexRayVFrError(msg) => msg.timestamp,
-            LogObject::FlexRayVFrStatus(msg) => msg.timestamp,
-            LogObject::FlexRayVFrStartCycle(msg) => msg.timestamp,
-            LogObject::FlexRayVFrReceiveMsg(msg) => msg.timestamp,
-            LogObject::FlexRayVFrReceiveMsgEx(msg) => msg.timestamp,
-            LogObject::EthernetFrame(msg) => msg.timestamp,
-            LogObject::AppTrigger(msg) => msg.timestamp,
-            LogObject::EventComment(msg) => msg.timestamp,
-            LogObject::GlobalMarker(msg) => msg.timestamp,
-            LogObject::MostSpy(msg) => msg.timestamp,
-            LogObject::MostCtrl(msg) => msg.timestamp,
-            LogObject::MostPkt2(msg) => msg.timestamp,
-            LogObject::MostLightLock(msg) => msg.timestamp,
-            LogObject::MostStatistic(msg) => msg.timestamp,
-            LogObject::MostHwMode(msg) => msg.timestamp,
-            LogObject::MostReg(msg) => msg.timestamp,
-            LogObject::MostGenReg(msg) => msg.timestamp,
-            LogObject::MostNetState(msg) => msg.timestamp,
-            LogObject::MostDataLost(msg) => msg.timestamp,
-            LogObject::MostTrigger(msg) => msg.timestamp,
-            LogObject::Unhandled { timestamp, .. } => *timestamp,
-        }
-    }
+use crate::{BlfParseError, BlfParseResult, LogContainer, LogObject, ObjectType};
 
-    /// Returns the channel ID of the log object (if applicable)
-    pub fn channel(&self) -> Option<u16> {
-        match self {
-            LogObject::CanMessage(msg) => Some(msg.channel),
-            LogObject::CanMessage2(msg) => Some(msg.channel),
-            LogObject::CanFdMessage(msg) => Some(msg.channel),
-            LogObject::CanFdMessage64(msg) => Some(msg.channel as u16),
-            LogObject::LinMessage(msg) => Some(msg.channel),
-            LogObject::LinMessage2(_msg) => None, // LinMessage2 doesn't have a direct channel field
-            _ => None,
-        }
-    }
-}
+use std::io::Cursor;
 
 /// BLF parser for handling log objects
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone)]
 pub struct BlfParser {
     /// Enable debug logging
     pub debug: bool,
+    /// Object types to decode; `None` decodes all of them.
+    object_types: Option<Vec<ObjectType>>,
 }
 
 impl BlfParser {
@@ -148,13 +24,57 @@ impl BlfParser {
 
     /// Creates a new BlfParser with debug logging enabled.
     pub fn with_debug() -> Self {
-        Self { debug: true }
+        Self {
+            debug: true,
+            ..Self::default()
+        }
+    }
+
+    /// Decodes only objects of `object_types` and skips the others unread.
+    ///
+    /// Skipped objects cost neither decoding time nor memory, which helps when
+    /// e.g. only the CAN traffic of a log that also holds Ethernet or MOST
+    /// matters. Unknown types are kept when [`ObjectType::Unknown`] is listed.
+    ///
+    /// # Example
+    /// ```
+    /// use blf::{BlfParser, ObjectType};
+    ///
+    /// let parser = BlfParser::new()
+    ///     .with_object_types([ObjectType::CanMessage, ObjectType::CanFdMessage64]);
+    /// assert!(parser.wants(ObjectType::CanFdMessage64));
+    /// assert!(!parser.wants(ObjectType::EthernetFrame));
+    /// ```
+    pub fn with_object_types(mut self, object_types: impl IntoIterator<Item = ObjectType>) -> Self {
+        self.object_types = Some(object_types.into_iter().collect());
+        self
+    }
+
+    /// Whether objects of `object_type` are decoded.
+    pub fn wants(&self, object_type: ObjectType) -> bool {
+        self.object_types
+            .as_ref()
+            .is_none_or(|types| types.contains(&object_type))
     }
 
     /// Parses the data slice and returns a vector of log objects.
     pub fn parse(&self, data: &[u8]) -> BlfParseResult<Vec<LogObject>> {
-        let mut cursor = Cursor::new(data);
         let mut all_objects = Vec::new();
+        self.parse_each(data, |objects| all_objects.extend(objects))?;
+        Ok(all_objects)
+    }
+
+    /// Parses the data slice and hands the objects of each container to `on_objects`.
+    ///
+    /// Unlike [`parse`](Self::parse), the objects never have to be held all at
+    /// once, so the caller can summarise or drop them as they come.
+    pub fn parse_each(
+        &self,
+        data: &[u8],
+        mut on_objects: impl FnMut(Vec<LogObject>),
+    ) -> BlfParseResult<()> {
+        let mut cursor = Cursor::new(data);
+        let mut total_objects = 0;
         let data_len = cursor.get_ref().len();
 
         if self.debug {
@@ -232,7 +152,8 @@ impl BlfParser {
                                         objects.len()
                                     );
                                 }
-                                all_objects.extend(objects);
+                                total_objects += objects.len();
+                                on_objects(objects);
                             }
                             Err(e) => {
                                 if self.debug {
@@ -254,215 +175,17 @@ impl BlfParser {
         }
 
         if self.debug {
-            println!(
-                "Parsing complete, found {} objects total",
-                all_objects.len()
-            );
+            println!("Parsing complete, found {} objects total", total_objects);
         }
 
-        Ok(all_objects)
+        Ok(())
     }
 
-    fn parse_can_object(
-        &self,
-        cursor: &mut Cursor<&[u8]>,
-        header: &ObjectHeader,
-        object_data_size: usize,
-    ) -> BlfParseResult<Option<LogObject>> {
-        match header.object_type {
-            ObjectType::CanMessage => Ok(Some(LogObject::CanMessage(CanMessage::read(
-                cursor, header,
-            )?))),
-            ObjectType::CanMessage2 => Ok(Some(LogObject::CanMessage2(CanMessage2::read(
-                cursor,
-                header,
-                object_data_size,
-            )?))),
-            ObjectType::CanError => Ok(Some(LogObject::CanErrorFrame(CanErrorFrame::read(
-                cursor, header,
-            )?))),
-            ObjectType::CanFdMessage => Ok(Some(LogObject::CanFdMessage(CanFdMessage::read(
-                cursor, header,
-            )?))),
-            ObjectType::CanFdMessage64 => Ok(Some(LogObject::CanFdMessage64(
-                CanFdMessage64::read(cursor, header)?,
-            ))),
-            ObjectType::CanOverload => Ok(Some(LogObject::CanOverloadFrame(
-                CanOverloadFrame::read(cursor, header)?,
-            ))),
-            ObjectType::CanStatistic => Ok(Some(LogObject::CanDriverStatistic(
-                CanDriverStatistic::read(cursor, header)?,
-            ))),
-            ObjectType::CanDriverError => Ok(Some(LogObject::CanDriverError(
-                CanDriverError::read(cursor, header)?,
-            ))),
-            _ => self.parse_lin_object(cursor, header, object_data_size),
-        }
-    }
-
-    fn parse_lin_object(
-        &self,
-        cursor: &mut Cursor<&[u8]>,
-        header: &ObjectHeader,
-        object_data_size: usize,
-    ) -> BlfParseResult<Option<LogObject>> {
-        match header.object_type {
-            ObjectType::LinMessage => Ok(Some(LogObject::LinMessage(LinMessage::read(
-                cursor, header,
-            )?))),
-            ObjectType::LinCrcError => Ok(Some(LogObject::LinCrcError(LinCrcError::read(
-                cursor, header,
-            )?))),
-            ObjectType::LinDlcInfo => Ok(Some(LogObject::LinDlcInfo(LinDlcInfo::read(
-                cursor, header,
-            )?))),
-            ObjectType::LinReceiveError => Ok(Some(LogObject::LinReceiveError(
-                LinReceiveError::read(cursor, header)?,
-            ))),
-            ObjectType::LinSendError => Ok(Some(LogObject::LinSendError(LinSendError::read(
-                cursor, header,
-            )?))),
-            ObjectType::LinSlaveTimeout => Ok(Some(LogObject::LinSlaveTimeout(
-                LinSlaveTimeout::read(cursor, header)?,
-            ))),
-            ObjectType::LinSchedulerModeChange => Ok(Some(LogObject::LinSchedulerModeChange(
-                LinSchedulerModeChange::read(cursor, header)?,
-            ))),
-            ObjectType::LinSyncError => Ok(Some(LogObject::LinSyncError(LinSyncError::read(
-                cursor, header,
-            )?))),
-            ObjectType::LinBaudrate => Ok(Some(LogObject::LinBaudrateEvent(
-                LinBaudrateEvent::read(cursor, header)?,
-            ))),
-            ObjectType::LinSleep => Ok(Some(LogObject::LinSleepModeEvent(
-                LinSleepModeEvent::read(cursor, header)?,
-            ))),
-            ObjectType::LinWakeup => Ok(Some(LogObject::LinWakeupEvent(LinWakeupEvent::read(
-                cursor, header,
-            )?))),
-            ObjectType::LinMessage2 => Ok(Some(LogObject::LinMessage2(LinMessage2::read(
-                cursor,
-                header,
-                object_data_size,
-            )?))),
-            _ => self.parse_flexray_object(cursor, &header.clone(), object_data_size),
-        }
-    }
-    fn parse_flexray_object(
-        &self,
-        cursor: &mut Cursor<&[u8]>,
-        header: &ObjectHeader,
-        object_data_size: usize,
-    ) -> BlfParseResult<Option<LogObject>> {
-        match header.object_type {
-            ObjectType::FlexRayData => Ok(Some(LogObject::FlexRayData(FlexRayData::read(
-                cursor, header,
-            )?))),
-            ObjectType::FlexRaySync => Ok(Some(LogObject::FlexRaySync(FlexRaySync::read(
-                cursor, header,
-            )?))),
-            ObjectType::FlexRayMessage => Ok(Some(LogObject::FlexRayV6Message(
-                FlexRayV6Message::read(cursor, header)?,
-            ))),
-            ObjectType::FlexRayV6StartCycleEvent => Ok(Some(LogObject::FlexRayV6StartCycleEvent(
-                FlexRayV6StartCycleEvent::read(cursor, header)?,
-            ))),
-            ObjectType::FlexRayStatusEvent => Ok(Some(LogObject::FlexRayStatusEvent(
-                FlexRayStatusEvent::read(cursor, header)?,
-            ))),
-            ObjectType::FlexRayVFrError => Ok(Some(LogObject::FlexRayVFrError(
-                FlexRayVFrError::read(cursor, header)?,
-            ))),
-            ObjectType::FlexRayVFrStatus => Ok(Some(LogObject::FlexRayVFrStatus(
-                FlexRayVFrStatus::read(cursor, header)?,
-            ))),
-            ObjectType::FlexRayVFrStartCycle => Ok(Some(LogObject::FlexRayVFrStartCycle(
-                FlexRayVFrStartCycle::read(cursor, header)?,
-            ))),
-            ObjectType::FlexRayVFrReceiveMsg => Ok(Some(LogObject::FlexRayVFrReceiveMsg(
-                FlexRayVFrReceiveMsg::read(cursor, header)?,
-            ))),
-            ObjectType::FlexRayVFrReceiveMsgEx => Ok(Some(LogObject::FlexRayVFrReceiveMsgEx(
-                FlexRayVFrReceiveMsgEx::read(cursor, header)?,
-            ))),
-            _ => self.parse_other_object(cursor, &header.clone(), object_data_size),
-        }
-    }
-    fn parse_other_object(
-        &self,
-        cursor: &mut Cursor<&[u8]>,
-        header: &ObjectHeader,
-        object_data_size: usize,
-    ) -> BlfParseResult<Option<LogObject>> {
-        match header.object_type {
-            ObjectType::EthernetFrame => Ok(Some(LogObject::EthernetFrame(EthernetFrame::read(
-                cursor, header,
-            )?))),
-            ObjectType::AppTrigger => Ok(Some(LogObject::AppTrigger(AppTrigger::read(
-                cursor, header,
-            )?))),
-            ObjectType::EventComment => Ok(Some(LogObject::EventComment(EventComment::read(
-                cursor, header,
-            )?))),
-            ObjectType::GlobalMarker => Ok(Some(LogObject::GlobalMarker(GlobalMarker::read(
-                cursor, header,
-            )?))),
-            // Temporarily comment out missing types
-            // ObjectType::SystemVariable => Ok(Some(LogObject::SystemVariable(SystemVariable::read(cursor, header)?))),
-            // ObjectType::EnvInteger => Ok(Some(LogObject::EnvInteger(EnvInteger::read(cursor, header)?))),
-            // ObjectType::EnvDouble => Ok(Some(LogObject::EnvDouble(EnvDouble::read(cursor, header)?))),
-            _ => self.parse_most_object(cursor, header.clone(), object_data_size),
-        }
-    }
-    fn parse_most_object(
+    /// Parses the actual log objects contained within a (decompressed) LogContainer.
+    pub(crate) fn parse_inner_objects(
         &self,
         cursor: &mut Cursor<&[u8]>,
-        header: ObjectHeader,
-        object_data_size: usize,
-    ) -> BlfParseResult<Option<LogObject>> {
-        match header.object_type {
-            // Temporarily comment out EnvString since it's not yet implemented
-            // ObjectType::EnvString => Ok(Some(LogObject::EnvString(EnvString::read(cursor, &header, object_data_size)?))),
-            ObjectType::MostSpy => Ok(Some(LogObject::MostSpy(MostSpy::read(cursor, &header)?))),
-            ObjectType::MostCtrl => Ok(Some(LogObject::MostCtrl(MostCtrl::read(cursor, &header)?))),
-            ObjectType::MostPkt2 => Ok(Some(LogObject::MostPkt2(MostPkt2::read(cursor, &header)?))),
-            ObjectType::MostLightLock => Ok(Some(LogObject::MostLightLock(MostLightLock::read(
-                cursor, &header,
-            )?))),
-            ObjectType::MostStatistic => Ok(Some(LogObject::MostStatistic(MostStatistic::read(
-                cursor, &header,
-            )?))),
-            ObjectType::MostHwMode => Ok(Some(LogObject::MostHwMode(MostHwMode::read(
-                cursor, &header,
-            )?))),
-            ObjectType::MostReg => Ok(Some(LogObject::MostReg(MostReg::read(cursor, &header)?))),
-            ObjectType::MostGenReg => Ok(Some(LogObject::MostGenReg(MostGenReg::read(
-                cursor, &header,
-            )?))),
-            ObjectType::MostNetState => Ok(Some(LogObject::MostNetState(MostNetState::read(
-                cursor, &header,
-            )?))),
-            ObjectType::MostDataLost => Ok(Some(LogObject::MostDataLost(MostDataLost::read(
-                cursor, &header,
-            )?))),
-            ObjectType::MostTrigger => Ok(Some(LogObject::MostTrigger(MostTrigger::read(
-                cursor, &header,
-            )?))),
-            _ => {
-                // Create an Unhandled object for unknown types
-                let mut data = vec![0u8; object_data_size];
-                cursor.read_exact(&mut data)?;
-                Ok(Some(LogObject::Unhandled {
-                    object_type: header.object_type as u32,
-                    timestamp: header.object_time_stamp,
-                    data,
-                }))
-            }
-        }
-    }
-
-    /// Parses the actual log objects contained within a (decompressed) LogContainer.
-    fn parse_inner_objects(&self, cursor: &mut Cursor<&[u8]>) -> BlfParseResult<Vec<LogObject>> {
+    ) -> BlfParseResult<Vec<LogObject>> {
         let mut all_objects = Vec::new();
         let data_len = cursor.get_ref().len();
 
@@ -487,10 +210,11 @@ impl BlfParser {
 
             // LogContainers should not be nested. If they are, we skip them to avoid infinite recursion.
             if header.object_type != ObjectType::LogContainer {
-                let object_body_size = (header.object_size as usize)
-                    .saturating_sub(header.calculate_header_size() as usize);
-                if let Some(object) = self.parse_can_object(cursor, &header, object_body_size)? {
-                    all_objects.push(object);
+                // Unwanted types are left undecoded; the cursor moves past them below
+                if self.wants(header.object_type) {
+                    all_objects.push(decode_at(cursor, |reader| {
+                        LogObject::decode(reader, &header)
+                    })?);
                 }
             } else {
                 // For LogContainer objects, we skip them but still need to advance the cursor
@@ -703,10 +427,22 @@ mod tests {
             .unwrap();
         add_padding(&mut unknown_object_bytes);
 
+        // Unknown objects are kept undecoded, with their body
+        let mut cursor = Cursor::new(&unknown_object_bytes[..]);
+        let result = parser.parse_inner_objects(&mut cursor).unwrap();
+        assert_eq!(
+            result,
+            vec![LogObject::Unhandled {
+                object_type: ObjectType::Unknown as u32,
+                timestamp: 1000,
+                data: vec![0; 6],
+            }]
+        );
+
+        // A parser limited to other types skips them
+        let parser = BlfParser::new().with_object_types([ObjectType::CanMessage]);
         let mut cursor = Cursor::new(&unknown_object_bytes[..]);
         let result = parser.parse_inner_objects(&mut cursor).unwrap();
-
-        // The parser should gracefully skip the unknown object and return an empty list.
         assert!(result.is_empty());
     }
 }
@@ -0,0 +1,378 @@
+//! Reads a BLF file object by object.
+//!
+//! [`read_blf_from_file`](crate::read_blf_from_file) holds the whole file and
+//! every object at once, which does not fit multi-GB logs. [`BlfReader`]
+//! reads one top-level object at a time from any [`Read`] source and only
+//! decompresses a `LogContainer` once the objects before it are consumed, so
+//! memory stays at about one container regardless of the file size.
+
+use crate::objects::object_header::{OBJECT_SIGNATURE, ObjectHeader, ObjectHeaderBase};
+use crate::{
+    BlfParseResult, BlfParser, FileStatistics, LogContainer, LogObject, LogSummary, ObjectType,
+};
+use byteorder::{ByteOrder, LittleEndian};
+use std::collections::VecDeque;
+use std::io::{self, Cursor, ErrorKind, Read};
+use std::ops::RangeInclusive;
+#[cfg(feature = "fs")]
+use std::{fs::File, io::BufReader, path::Path};
+
+/// Size of the base header every top-level object starts with.
+const BASE_HEADER_SIZE: usize = 16;
+
+/// Lazily parses the objects of a BLF stream.
+///
+/// Iterating yields the objects one by one; [`read_container`](Self::read_container)
+/// hands them out a container at a time instead. Like
+/// [`read_blf_from_bytes`](crate::read_blf_from_bytes), unreadable containers are
+/// skipped and a log that ends mid-object (e.g. a logger that lost power) ends
+/// at its last complete container.
+///
+/// # Example
+/// ```
+/// use blf::{BlfReader, BlfWriter, CanMessage, LogObject, SystemTime};
+/// use std::io::Cursor;
+///
+/// let mut writer = BlfWriter::new(Cursor::new(Vec::new()), SystemTime::from_timestamp_nanos(0))?;
+/// for id in [0x100, 0x200] {
+///     writer.write_object(&LogObject::CanMessage(CanMessage { id, ..Default::default() }))?;
+/// }
+/// writer.finish()?;
+///
+/// let data = writer.into_inner().into_inner();
+/// let reader = BlfReader::new(Cursor::new(data))?;
+/// assert_eq!(reader.file_stats().object_count, 2);
+/// let ids: Vec<_> = reader.map(|object| object.map(|object| object.id())).collect::<Result<_, _>>()?;
+/// assert_eq!(ids, [Some(0x100), Some(0x200)]);
+/// # Ok::<(), blf::BlfParseError>(())
+/// ```
+pub struct BlfReader<R: Read> {
+    inner: R,
+    file_stats: FileStatistics,
+    parser: BlfParser,
+    /// Objects of the current container not yet handed out.
+    pending: VecDeque<LogObject>,
+    /// Overview of every object read so far.
+    summary: LogSummary,
+    /// Only objects with a timestamp in this range are read.
+    time_range: Option<RangeInclusive<u64>>,
+    done: bool,
+}
+
+#[cfg(feature = "fs")]
+impl BlfReader<BufReader<File>> {
+    /// Opens the file at `path` and reads its statistics header.
+    pub fn open<P: AsRef<Path>>(path: P) -> BlfParseResult<Self> {
+        Self::new(BufReader::new(File::open(path)?))
+    }
+}
+
+impl<R: Read> BlfReader<R> {
+    /// Reads the statistics header from `inner`; objects are read as they are consumed.
+    pub fn new(mut inner: R) -> BlfParseResult<Self> {
+        // Signature and statistics size come first; the size covers the whole header
+        let mut header = vec![0; 8];
+        inner.read_exact(&mut header)?;
+        let statistics_size = LittleEndian::read_u32(&header[4..]) as u64;
+        (&mut inner)
+            .take(statistics_size.saturating_sub(8))
+            .read_to_end(&mut header)?;
+        let file_stats = FileStatistics::read(&mut Cursor::new(&header[..]))?;
+
+        Ok(Self {
+            inner,
+            file_stats,
+            parser: BlfParser::new(),
+            pending: VecDeque::new(),
+            summary: LogSummary::default(),
+            time_range: None,
+            done: false,
+        })
+    }
+
+    /// Decodes only objects of `object_types`, see [`BlfParser::with_object_types`].
+    ///
+    /// The [`summary`](Self::summary) then covers the decoded objects only.
+    pub fn with_object_types(mut self, object_types: impl IntoIterator<Item = ObjectType>) -> Self {
+        self.parser = self.parser.with_object_types(object_types);
+        self
+    }
+
+    /// Reads only objects stamped between `from_ns` and `to_ns` (inclusive).
+    ///
+    /// A container is decompressed, but its objects are decoded only when
+    /// their timestamps reach into the window; reading ends at the first
+    /// container that starts after it, since a log is written in time order.
+    pub fn with_time_range(mut self, from_ns: u64, to_ns: u64) -> Self {
+        self.time_range = Some(from_ns..=to_ns);
+        self
+    }
+
+    /// The statistics header of the file.
+    pub fn file_stats(&self) -> &FileStatistics {
+        &self.file_stats
+    }
+
+    /// Overview of the objects read so far; of the whole log once it is read.
+    pub fn summary(&self) -> &LogSummary {
+        &self.summary
+    }
+
+    /// Objects of the next container (or the rest of the current one after
+    /// iterating), `None` at the end of the log.
+    pub fn read_container(&mut self) -> BlfParseResult<Option<Vec<LogObject>>> {
+        if !self.pending.is_empty() {
+            return Ok(Some(self.pending.drain(..).collect()));
+        }
+        while !self.done {
+            if let Some(objects) = self.next_top_level_object()? {
+                self.summary.extend(&objects);
+                return Ok(Some(objects));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Returns the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    /// Reads one top-level object; returns the objects if it was a readable container.
+    fn next_top_level_object(&mut self) -> BlfParseResult<Option<Vec<LogObject>>> {
+        let mut object = vec![0; BASE_HEADER_SIZE];
+        if !self.fill(&mut object)? {
+            return Ok(None);
+        }
+        // Skip whatever is not an object in steps of 4, as `BlfParser::parse` does
+        while LittleEndian::read_u32(&object) != OBJECT_SIGNATURE {
+            object.drain(..4);
+            object.resize(BASE_HEADER_SIZE, 0);
+            if !self.fill(&mut object[BASE_HEADER_SIZE - 4..])? {
+                return Ok(None);
+            }
+        }
+        let header = ObjectHeaderBase::read(&mut Cursor::new(&object[..]))?;
+        let object_size = header.object_size as u64;
+        if object_size < header.header_size as u64 || object_size < BASE_HEADER_SIZE as u64 {
+            self.skip(BASE_HEADER_SIZE as u64)?;
+            return Ok(None);
+        }
+
+        let body_size = object_size - BASE_HEADER_SIZE as u64;
+        let padding = object_size.next_multiple_of(4) - object_size;
+        if header.object_type != ObjectType::LogContainer {
+            self.skip(body_size + padding)?;
+            return Ok(None);
+        }
+        // Grows with the data actually read, so a corrupt size cannot exhaust memory
+        (&mut self.inner).take(body_size).read_to_end(&mut object)?;
+        if object.len() as u64 != object_size {
+            self.done = true;
+            return Ok(None);
+        }
+        self.skip(padding)?;
+        match self.time_range.clone() {
+            Some(range) => Ok(self.read_in_range(&object, range)),
+            None => self.parser.parse(&object).map(Some),
+        }
+    }
+
+    /// Objects of `container` inside `range`; `None` for a container outside it.
+    fn read_in_range(
+        &mut self,
+        container: &[u8],
+        range: RangeInclusive<u64>,
+    ) -> Option<Vec<LogObject>> {
+        let mut cursor = Cursor::new(container);
+        let header = ObjectHeaderBase::read(&mut cursor).ok()?;
+        let container = LogContainer::read(&mut cursor, header).ok()?;
+        let data = &container.uncompressed_data[..];
+        match time_span(data) {
+            Some((first, _)) if first > *range.end() => {
+                self.done = true;
+                return None;
+            }
+            Some((_, last)) if last < *range.start() => return None,
+            _ => {}
+        }
+        let mut objects = self
+            .parser
+            .parse_inner_objects(&mut Cursor::new(data))
+            .ok()?;
+        objects.retain(|object| range.contains(&object.timestamp()));
+        Some(objects)
+    }
+
+    /// Reads `buffer` full; returns false (and ends the log) at the end of the data.
+    fn fill(&mut self, buffer: &mut [u8]) -> BlfParseResult<bool> {
+        match self.inner.read_exact(buffer) {
+            Ok(()) => Ok(true),
+            Err(e) if e.kind() == ErrorKind::UnexpectedEof => {
+                self.done = true;
+                Ok(false)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn skip(&mut self, len: u64) -> BlfParseResult<()> {
+        if io::copy(&mut (&mut self.inner).take(len), &mut io::sink())? < len {
+            self.done = true;
+        }
+        Ok(())
+    }
+}
+
+/// Earliest and latest timestamp of a decompressed container, read from the
+/// object headers alone; `None` if a header cannot be read.
+fn time_span(data: &[u8]) -> Option<(u64, u64)> {
+    let mut cursor = Cursor::new(data);
+    let mut span: Option<(u64, u64)> = None;
+    while (cursor.position() as usize) < data.len() {
+        let start = cursor.position();
+        let header = ObjectHeader::read(&mut cursor).ok()?;
+        if (header.object_size as usize) < BASE_HEADER_SIZE {
+            return None;
+        }
+        let timestamp = header.object_time_stamp;
+        span = Some(span.map_or((timestamp, timestamp), |(first, last)| {
+            (first.min(timestamp), last.max(timestamp))
+        }));
+        cursor.set_position((start + header.object_size as u64).next_multiple_of(4));
+    }
+    span
+}
+
+impl<R: Read> Iterator for BlfReader<R> {
+    type Item = BlfParseResult<LogObject>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.pending.is_empty() {
+            match self.read_container() {
+                Ok(Some(objects)) => self.pending.extend(objects),
+                Ok(None) => return None,
+                Err(e) => {
+                    self.done = true;
+                    return Some(Err(e));
+                }
+            }
+        }
+        self.pending.pop_front().map(Ok)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BlfWriter, CanMessage, SystemTime, read_blf_from_bytes};
+
+    fn log_bytes(frames: u32) -> Vec<u8> {
+        let start = SystemTime::from_timestamp_nanos(0);
+        let mut writer = BlfWriter::new(Cursor::new(Vec::new()), start).unwrap();
+        for id in 0..frames {
+            let mut msg = CanMessage {
+                id,
+                dlc: 8,
+                ..Default::default()
+            };
+            msg.header.object_time_stamp = id as u64 * 1_000;
+            writer.write_object(&LogObject::CanMessage(msg)).unwrap();
+        }
+        writer.finish().unwrap();
+        writer.into_inner().into_inner()
+    }
+
+    fn ids(reader: BlfReader<Cursor<Vec<u8>>>) -> Vec<u32> {
+        reader.map(|object| object.unwrap().id().unwrap()).collect()
+    }
+
+    #[test]
+    fn test_reader_matches_whole_file_parse() {
+        // Enough frames for several containers
+        let data = log_bytes(10_000);
+        let expected: Vec<u32> = read_blf_from_bytes(&data)
+            .unwrap()
+            .objects
+            .iter()
+            .map(|object| object.id().unwrap())
+            .collect();
+        assert_eq!(expected.len(), 10_000);
+        assert_eq!(
+            ids(BlfReader::new(Cursor::new(data.clone())).unwrap()),
+            expected
+        );
+
+        let mut reader = BlfReader::new(Cursor::new(data.clone())).unwrap();
+        let first = reader.next().unwrap().unwrap();
+        assert_eq!(first.id(), Some(0));
+        let rest = reader.read_container().unwrap().unwrap();
+        assert_eq!(rest.first().and_then(LogObject::id), Some(1));
+        let mut containers = 1;
+        while reader.read_container().unwrap().is_some() {
+            containers += 1;
+        }
+        assert!(containers > 1);
+
+        // Bytes between containers are skipped
+        assert_eq!(reader.summary().total(), 10_000);
+        let header_size = reader.file_stats().statistics_size as usize;
+        let mut padded = data.clone();
+        padded.splice(header_size..header_size, [0xAB; 8]);
+        assert_eq!(ids(BlfReader::new(Cursor::new(padded)).unwrap()), expected);
+
+        // A cut-off log ends at its last complete container
+        let truncated = data[..data.len() - 10].to_vec();
+        let read = ids(BlfReader::new(Cursor::new(truncated)).unwrap());
+        assert!(!read.is_empty() && read.len() < expected.len());
+        assert_eq!(read, expected[..read.len()]);
+    }
+
+    #[test]
+    fn test_reader_reads_only_time_range() {
+        // Frames 1 µs apart, over several containers
+        let data = log_bytes(10_000);
+        let reader = BlfReader::new(Cursor::new(data.clone()))
+            .unwrap()
+            .with_time_range(4_000_000, 4_010_000);
+        assert_eq!(ids(reader), (4_000..=4_010).collect::<Vec<_>>());
+
+        let mut reader = BlfReader::new(Cursor::new(data))
+            .unwrap()
+            .with_time_range(20_000_000, 30_000_000);
+        assert!(reader.read_container().unwrap().is_none());
+        assert_eq!(reader.summary().total(), 0);
+    }
+
+    #[test]
+    fn test_reader_decodes_only_wanted_types() {
+        let start = SystemTime::from_timestamp_nanos(0);
+        let mut writer = BlfWriter::new(Cursor::new(Vec::new()), start).unwrap();
+        for object in [
+            LogObject::CanMessage(CanMessage::default()),
+            LogObject::GpsEvent(Default::default()),
+            LogObject::CanMessage(CanMessage {
+                id: 0x200,
+                ..Default::default()
+            }),
+        ] {
+            writer.write_object(&object).unwrap();
+        }
+        writer.finish().unwrap();
+        let data = writer.into_inner().into_inner();
+
+        let mut reader = BlfReader::new(Cursor::new(data.clone()))
+            .unwrap()
+            .with_object_types([ObjectType::CanMessage]);
+        let objects = reader.read_container().unwrap().unwrap();
+        assert_eq!(objects.len(), 2);
+        assert_eq!(objects[1].id(), Some(0x200));
+        assert_eq!(reader.summary().count(ObjectType::GpsEvent), 0);
+
+        let reader = BlfReader::new(Cursor::new(data))
+            .unwrap()
+            .with_object_types([ObjectType::GpsEvent]);
+        let objects: Vec<LogObject> = reader.map(Result::unwrap).collect();
+        assert!(matches!(objects[..], [LogObject::GpsEvent(_)]));
+    }
+}
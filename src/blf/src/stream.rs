@@ -0,0 +1,200 @@
+//! Hands objects from a producer thread to a consumer through a bounded queue.
+//!
+//! A file loader, a live capture or a network gateway produces objects faster
+//! than a UI can take them. [`blf_channel`] connects such a producer to a
+//! [`BlfStream`] through a queue of at most `capacity` batches: once it is
+//! full, [`BlfSender::send`] blocks until the consumer catches up, so memory
+//! stays bounded however far the producer runs ahead.
+
+use crate::{BlfParseError, BlfParseResult, BlfReader, LogObject};
+use std::collections::VecDeque;
+use std::io::Read;
+use std::sync::mpsc::{Receiver, RecvError, SyncSender, TryRecvError, sync_channel};
+
+/// Creates a stream fed by the returned sender, queueing at most `capacity` batches.
+pub fn blf_channel(capacity: usize) -> (BlfSender, BlfStream) {
+    let (sender, receiver) = sync_channel(capacity);
+    (
+        BlfSender { sender },
+        BlfStream {
+            receiver,
+            pending: VecDeque::new(),
+            finished: false,
+        },
+    )
+}
+
+/// Producer side of a [`BlfStream`]; the stream ends once every sender is dropped.
+#[derive(Debug, Clone)]
+pub struct BlfSender {
+    sender: SyncSender<BlfParseResult<Vec<LogObject>>>,
+}
+
+impl BlfSender {
+    /// Queues a batch, waiting while the queue is full.
+    ///
+    /// Returns `false` once the stream has been dropped, which tells the
+    /// producer to stop. An empty batch still finds that out, e.g. for a
+    /// producer that polls a source with nothing to report.
+    pub fn send(&self, batch: Vec<LogObject>) -> bool {
+        self.sender.send(Ok(batch)).is_ok()
+    }
+
+    /// Passes an error to the consumer; returns `false` once the stream has been dropped.
+    pub fn fail(&self, error: BlfParseError) -> bool {
+        self.sender.send(Err(error)).is_ok()
+    }
+}
+
+/// Consumer side of a bounded producer/consumer queue of objects.
+///
+/// [`recv_batch`](Self::recv_batch) and [`try_recv_batch`](Self::try_recv_batch)
+/// take the objects a batch at a time, e.g. once per UI frame; iterating yields
+/// them one by one and ends when the producer is done.
+///
+/// # Example
+/// ```
+/// use blf::{BlfReader, BlfStream, BlfWriter, CanMessage, LogObject, SystemTime};
+/// use std::io::Cursor;
+///
+/// let mut writer = BlfWriter::new(Cursor::new(Vec::new()), SystemTime::from_timestamp_nanos(0))?;
+/// for id in [0x100, 0x200] {
+///     writer.write_object(&LogObject::CanMessage(CanMessage { id, ..Default::default() }))?;
+/// }
+/// writer.finish()?;
+///
+/// let reader = BlfReader::new(Cursor::new(writer.into_inner().into_inner()))?;
+/// let stream = BlfStream::spawn(reader, 4);
+/// let ids: Vec<_> = stream.map(|object| object.map(|object| object.id())).collect::<Result<_, _>>()?;
+/// assert_eq!(ids, [Some(0x100), Some(0x200)]);
+/// # Ok::<(), blf::BlfParseError>(())
+/// ```
+#[derive(Debug)]
+pub struct BlfStream {
+    receiver: Receiver<BlfParseResult<Vec<LogObject>>>,
+    /// Objects of the current batch not yet handed out by the iterator.
+    pending: VecDeque<LogObject>,
+    finished: bool,
+}
+
+impl BlfStream {
+    /// Parses `reader` on a new thread, at most `capacity` containers ahead of the consumer.
+    pub fn spawn<R: Read + Send + 'static>(mut reader: BlfReader<R>, capacity: usize) -> Self {
+        let (sender, stream) = blf_channel(capacity);
+        std::thread::spawn(move || {
+            loop {
+                match reader.read_container() {
+                    Ok(Some(objects)) => {
+                        if !sender.send(objects) {
+                            break;
+                        }
+                    }
+                    Ok(None) => break,
+                    Err(e) => {
+                        sender.fail(e);
+                        break;
+                    }
+                }
+            }
+        });
+        stream
+    }
+
+    /// Waits for the next batch; `None` once the producer is done.
+    pub fn recv_batch(&mut self) -> Option<BlfParseResult<Vec<LogObject>>> {
+        if !self.pending.is_empty() {
+            return Some(Ok(self.pending.drain(..).collect()));
+        }
+        if self.finished {
+            return None;
+        }
+        match self.receiver.recv() {
+            Ok(batch) => Some(batch),
+            Err(RecvError) => {
+                self.finished = true;
+                None
+            }
+        }
+    }
+
+    /// The next batch if one is queued, without waiting.
+    pub fn try_recv_batch(&mut self) -> Option<BlfParseResult<Vec<LogObject>>> {
+        if !self.pending.is_empty() {
+            return Some(Ok(self.pending.drain(..).collect()));
+        }
+        if self.finished {
+            return None;
+        }
+        match self.receiver.try_recv() {
+            Ok(batch) => Some(batch),
+            Err(TryRecvError::Empty) => None,
+            Err(TryRecvError::Disconnected) => {
+                self.finished = true;
+                None
+            }
+        }
+    }
+
+    /// Whether the producer is done and every batch has been taken.
+    pub fn is_finished(&self) -> bool {
+        self.finished && self.pending.is_empty()
+    }
+}
+
+impl Iterator for BlfStream {
+    type Item = BlfParseResult<LogObject>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.pending.is_empty() {
+            match self.recv_batch()? {
+                Ok(batch) => self.pending.extend(batch),
+                Err(e) => return Some(Err(e)),
+            }
+        }
+        self.pending.pop_front().map(Ok)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CanMessage;
+
+    fn batch(ids: std::ops::Range<u32>) -> Vec<LogObject> {
+        ids.map(|id| {
+            LogObject::CanMessage(CanMessage {
+                id,
+                ..Default::default()
+            })
+        })
+        .collect()
+    }
+
+    #[test]
+    fn test_channel_applies_backpressure() {
+        let (sender, mut stream) = blf_channel(1);
+        assert!(stream.try_recv_batch().is_none());
+        assert!(sender.send(batch(0..2)));
+        // The queue is full: a second send waits for the consumer
+        let producer = std::thread::spawn(move || {
+            let sent = sender.send(batch(2..4));
+            sender.fail(BlfParseError::UnexpectedEof);
+            sent
+        });
+        assert_eq!(stream.next().unwrap().unwrap().id(), Some(0));
+        assert_eq!(stream.recv_batch().unwrap().unwrap().len(), 1);
+        assert_eq!(stream.recv_batch().unwrap().unwrap().len(), 2);
+        assert!(producer.join().unwrap());
+        assert!(matches!(
+            stream.next(),
+            Some(Err(BlfParseError::UnexpectedEof))
+        ));
+        assert!(stream.next().is_none());
+        assert!(stream.is_finished());
+
+        // A dropped stream stops the producer
+        let (sender, stream) = blf_channel(1);
+        drop(stream);
+        assert!(!sender.send(Vec::new()));
+    }
+}
@@ -0,0 +1,116 @@
+//! Overview of a log gathered while its objects are read.
+
+use crate::{LogObject, ObjectType};
+use alloc::collections::{BTreeMap, BTreeSet};
+
+/// Object counts, time span and channels of a log.
+///
+/// Readers extend it container by container, so callers that only want an
+/// overview of the log do not need another pass over its objects.
+///
+/// # Example
+/// ```
+/// use blf::{CanMessage, LogObject, LogSummary, ObjectType};
+///
+/// let mut msg = CanMessage { channel: 2, ..Default::default() };
+/// msg.header.object_time_stamp = 5_000;
+/// let mut summary = LogSummary::default();
+/// summary.extend(&[LogObject::CanMessage(msg)]);
+/// assert_eq!(summary.count(ObjectType::CanMessage), 1);
+/// assert_eq!(summary.first_timestamp, Some(5_000));
+/// assert!(summary.channels.contains(&2));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LogSummary {
+    /// Number of objects per raw [`ObjectType`] number.
+    pub object_counts: BTreeMap<u32, usize>,
+    /// Earliest object timestamp in nanoseconds, `None` for an empty log.
+    pub first_timestamp: Option<u64>,
+    /// Latest object timestamp in nanoseconds, `None` for an empty log.
+    pub last_timestamp: Option<u64>,
+    /// Channels carrying at least one object.
+    pub channels: BTreeSet<u16>,
+}
+
+impl LogSummary {
+    /// Summarizes `objects`.
+    pub fn from_objects(objects: &[LogObject]) -> Self {
+        let mut summary = Self::default();
+        summary.extend(objects);
+        summary
+    }
+
+    /// Adds `objects` to the summary.
+    pub fn extend(&mut self, objects: &[LogObject]) {
+        for object in objects {
+            *self.object_counts.entry(object.object_type()).or_default() += 1;
+            let timestamp = object.timestamp();
+            self.first_timestamp =
+                Some(self.first_timestamp.map_or(timestamp, |t| t.min(timestamp)));
+            self.last_timestamp = Some(self.last_timestamp.map_or(timestamp, |t| t.max(timestamp)));
+            if let Some(channel) = object.channel() {
+                self.channels.insert(channel);
+            }
+        }
+    }
+
+    /// Number of objects of `object_type`.
+    pub fn count(&self, object_type: ObjectType) -> usize {
+        self.object_counts
+            .get(&(object_type as u32))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Number of objects of all types.
+    pub fn total(&self) -> usize {
+        self.object_counts.values().sum()
+    }
+
+    /// Time between the earliest and the latest object in nanoseconds.
+    pub fn duration_ns(&self) -> u64 {
+        match (self.first_timestamp, self.last_timestamp) {
+            (Some(first), Some(last)) => last - first,
+            _ => 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CanMessage, CanMessage2};
+    use alloc::vec::Vec;
+
+    #[test]
+    fn test_summary_counts_types_span_and_channels() {
+        let can = |channel, timestamp| {
+            let mut msg = CanMessage {
+                channel,
+                ..Default::default()
+            };
+            msg.header.object_time_stamp = timestamp;
+            LogObject::CanMessage(msg)
+        };
+        let mut summary = LogSummary::from_objects(&[can(1, 300), can(2, 100)]);
+        summary.extend(&[
+            LogObject::CanMessage2(CanMessage2::default()),
+            LogObject::Unhandled {
+                object_type: 999,
+                timestamp: 400,
+                data: Vec::new(),
+            },
+        ]);
+
+        assert_eq!(summary.count(ObjectType::CanMessage), 2);
+        assert_eq!(summary.count(ObjectType::CanMessage2), 1);
+        assert_eq!(summary.object_counts.get(&999), Some(&1));
+        assert_eq!(summary.total(), 4);
+        // The default CAN_MESSAGE2 sits at time 0 on channel 0
+        assert_eq!(summary.first_timestamp, Some(0));
+        assert_eq!(summary.last_timestamp, Some(400));
+        assert_eq!(summary.duration_ns(), 400);
+        assert_eq!(summary.channels, BTreeSet::from([0, 1, 2]));
+        assert_eq!(LogSummary::default().duration_ns(), 0);
+    }
+}
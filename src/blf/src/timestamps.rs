@@ -0,0 +1,162 @@
+//! Timestamp sanity checks for parsed log objects.
+//!
+//! Some loggers write objects out of time order or repeat timestamps, e.g.
+//! after a clock resync or when containers are flushed per channel. The first
+//! pass ([`check_timestamps`]) only counts the anomalies; the second pass
+//! ([`repair_timestamps`]) optionally fixes them by re-sorting or by shifting
+//! the objects after each backward jump.
+
+use crate::LogObject;
+use std::collections::HashMap;
+
+/// How [`repair_timestamps`] treats objects that go back in time
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TimestampRepair {
+    /// Only report the anomalies
+    #[default]
+    Keep,
+    /// Stable-sort all objects by timestamp
+    Sort,
+    /// Keep the file order and shift every object after a backward jump so
+    /// the log continues from the last timestamp seen
+    Offset,
+}
+
+/// Anomalies found by [`check_timestamps`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct TimestampReport {
+    /// Objects with a timestamp earlier than an object before them
+    pub out_of_order: usize,
+    /// Objects with the same timestamp as the previous object on their channel
+    pub duplicates: usize,
+    /// Largest step back in time, in nanoseconds
+    pub max_backstep_ns: u64,
+}
+
+impl TimestampReport {
+    /// Total number of anomalies
+    pub fn anomalies(&self) -> usize {
+        self.out_of_order + self.duplicates
+    }
+
+    /// No object is out of order or duplicated
+    pub fn is_clean(&self) -> bool {
+        self.anomalies() == 0
+    }
+}
+
+/// First pass: counts out-of-order and duplicate timestamps without changing anything.
+pub fn check_timestamps(objects: &[LogObject]) -> TimestampReport {
+    let mut report = TimestampReport::default();
+    let mut latest: Option<u64> = None;
+    let mut last_by_channel: HashMap<u16, u64> = HashMap::new();
+
+    for object in objects {
+        let timestamp = object.timestamp();
+        if let Some(latest) = latest.filter(|&latest| timestamp < latest) {
+            report.out_of_order += 1;
+            report.max_backstep_ns = report.max_backstep_ns.max(latest - timestamp);
+        }
+        latest = latest.max(Some(timestamp));
+
+        if let Some(channel) = object.channel()
+            && last_by_channel.insert(channel, timestamp) == Some(timestamp)
+        {
+            report.duplicates += 1;
+        }
+    }
+    report
+}
+
+/// Second pass: applies `repair` to `objects` and returns the anomalies found
+/// before the repair.
+///
+/// Duplicate timestamps are only reported; there is no way to tell which of
+/// the objects carries the right one.
+pub fn repair_timestamps(objects: &mut [LogObject], repair: TimestampRepair) -> TimestampReport {
+    let report = check_timestamps(objects);
+    if report.out_of_order == 0 {
+        return report;
+    }
+
+    match repair {
+        TimestampRepair::Keep => {}
+        TimestampRepair::Sort => objects.sort_by_key(LogObject::timestamp),
+        TimestampRepair::Offset => {
+            let mut offset = 0u64;
+            let mut latest = 0u64;
+            for object in objects.iter_mut() {
+                let mut timestamp = object.timestamp().saturating_add(offset);
+                if timestamp < latest {
+                    offset += latest - timestamp;
+                    timestamp = latest;
+                }
+                latest = timestamp;
+                object.set_timestamp(timestamp);
+            }
+        }
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CanMessage;
+
+    fn can(channel: u16, timestamp: u64) -> LogObject {
+        let mut msg = CanMessage {
+            channel,
+            ..Default::default()
+        };
+        msg.header.object_time_stamp = timestamp;
+        LogObject::CanMessage(msg)
+    }
+
+    fn timestamps(objects: &[LogObject]) -> Vec<u64> {
+        objects.iter().map(LogObject::timestamp).collect()
+    }
+
+    #[test]
+    fn test_check_counts_backsteps_and_duplicates() {
+        let objects = vec![
+            can(1, 100),
+            can(2, 100),
+            can(1, 300),
+            can(1, 150),
+            can(2, 100),
+            can(1, 400),
+        ];
+        let report = check_timestamps(&objects);
+        assert_eq!(report.out_of_order, 2);
+        assert_eq!(report.duplicates, 1);
+        assert_eq!(report.max_backstep_ns, 200);
+        assert!(check_timestamps(&objects[..3]).is_clean());
+        assert!(!check_timestamps(&objects[..4]).is_clean());
+    }
+
+    #[test]
+    fn test_repair_sort_and_offset() {
+        let objects = vec![
+            can(1, 100),
+            can(1, 300),
+            can(1, 50),
+            can(1, 80),
+            can(1, 120),
+        ];
+
+        let mut sorted = objects.clone();
+        let report = repair_timestamps(&mut sorted, TimestampRepair::Sort);
+        assert_eq!(report.out_of_order, 3);
+        assert_eq!(timestamps(&sorted), vec![50, 80, 100, 120, 300]);
+
+        let mut shifted = objects.clone();
+        repair_timestamps(&mut shifted, TimestampRepair::Offset);
+        assert_eq!(timestamps(&shifted), vec![100, 300, 300, 330, 370]);
+        assert_eq!(check_timestamps(&shifted).out_of_order, 0);
+
+        let mut kept = objects.clone();
+        repair_timestamps(&mut kept, TimestampRepair::Keep);
+        assert_eq!(kept, objects);
+    }
+}
@@ -0,0 +1,649 @@
+//! Writes log objects to a BLF file.
+//!
+//! Objects are serialized into `LogContainer`s behind a `FileStatistics`
+//! header, zlib-compressed with the `compression` feature and stored as-is
+//! without it. The header is written as a placeholder first and
+//! rewritten with the final object count, sizes and last object time by
+//! [`BlfWriter::finish`].
+//!
+//! Only objects that carry everything needed to reproduce them are written
+//! (CAN, CAN FD, GPS events and global markers); [`BlfWriter::write_object`]
+//! reports the others as skipped and [`BlfWriter::skipped`] counts them by
+//! type.
+//!
+//! A writer dropped with objects written since its last `finish` finishes
+//! the file itself, so a recording cut short still gets a valid header.
+//!
+//! [`RotatingBlfWriter`] splits long recordings into numbered files by size
+//! or duration, each with its own complete header.
+
+use crate::objects::object_header::{OBJECT_SIGNATURE, ObjectHeaderBase};
+use crate::{BlfParseResult, FileStatistics, LogObject, ObjectHeader, ObjectType, SystemTime};
+use byteorder::{LittleEndian, WriteBytesExt};
+#[cfg(feature = "compression")]
+use flate2::{Compression, write::ZlibEncoder};
+use std::collections::BTreeMap;
+#[cfg(feature = "fs")]
+use std::fs::File;
+#[cfg(feature = "fs")]
+use std::io::BufWriter;
+use std::io::{Seek, SeekFrom, Write};
+#[cfg(feature = "fs")]
+use std::path::{Path, PathBuf};
+
+/// Size of the `FileStatistics` header written by [`BlfWriter`].
+const STATISTICS_SIZE: u32 = 144;
+
+/// Uncompressed bytes collected before a `LogContainer` is flushed.
+const CONTAINER_SIZE: usize = 128 * 1024;
+
+/// Size of the `LogContainer` fields following the base header.
+const CONTAINER_FIELDS_SIZE: u32 = 16;
+
+/// Compression method id of zlib in `LogContainer`.
+#[cfg(feature = "compression")]
+const COMPRESSION_ZLIB: u16 = 2;
+
+/// Compression method id of uncompressed data in `LogContainer`.
+#[cfg(not(feature = "compression"))]
+const COMPRESSION_NONE: u16 = 0;
+
+/// `compression_level` recorded in the header.
+#[cfg(feature = "compression")]
+const COMPRESSION_LEVEL: u8 = 6;
+#[cfg(not(feature = "compression"))]
+const COMPRESSION_LEVEL: u8 = 0;
+
+/// Compresses a container's payload; returns the method id and the bytes to store.
+#[cfg(feature = "compression")]
+fn compress(data: &[u8]) -> BlfParseResult<(u16, Vec<u8>)> {
+    let mut encoder = ZlibEncoder::new(Vec::new(), Compression::new(COMPRESSION_LEVEL as u32));
+    encoder.write_all(data)?;
+    Ok((COMPRESSION_ZLIB, encoder.finish()?))
+}
+
+/// Stores a container's payload as-is when built without the `compression` feature.
+#[cfg(not(feature = "compression"))]
+fn compress(data: &[u8]) -> BlfParseResult<(u16, Vec<u8>)> {
+    Ok((COMPRESSION_NONE, data.to_vec()))
+}
+
+/// Streaming BLF writer.
+///
+/// # Example
+/// ```
+/// use blf::{BlfWriter, CanMessage, LogObject, SystemTime, read_blf_from_bytes};
+/// use std::io::Cursor;
+///
+/// let frame = CanMessage { id: 0x7DF, dlc: 8, ..Default::default() };
+/// let objects = vec![LogObject::CanMessage(frame)];
+/// let mut writer = BlfWriter::new(Cursor::new(Vec::new()), SystemTime::from_timestamp_nanos(0))?;
+/// for object in &objects {
+///     writer.write_object(object)?;
+/// }
+/// let stats = writer.finish()?;
+/// assert_eq!(stats.object_count, 1);
+///
+/// let data = writer.into_inner().into_inner();
+/// let log = read_blf_from_bytes(&data)?;
+/// assert!(matches!(&log.objects[..], [LogObject::CanMessage(msg)] if msg.id == 0x7DF));
+/// # Ok::<(), blf::BlfParseError>(())
+/// ```
+///
+/// With the `fs` feature, `BlfWriter::create` writes straight to a file.
+///
+/// Dropping the writer finishes the file like [`finish`](Self::finish) but
+/// ignores errors; call `finish` to see them.
+pub struct BlfWriter<W: Write + Seek> {
+    /// Taken by [`into_inner`](Self::into_inner)
+    inner: Option<W>,
+    stats: FileStatistics,
+    /// Serialized objects waiting for the next container.
+    container: Vec<u8>,
+    /// Bytes written to `inner` so far, including the header.
+    written: u64,
+    last_timestamp: u64,
+    /// The header was not rewritten since the last object
+    unfinished: bool,
+    /// Objects not written, by raw [`ObjectType`] number
+    skipped: BTreeMap<u32, u64>,
+}
+
+#[cfg(feature = "fs")]
+impl BlfWriter<BufWriter<File>> {
+    /// Creates (or truncates) the file at `path` and writes a placeholder header.
+    pub fn create<P: AsRef<Path>>(
+        path: P,
+        measurement_start_time: SystemTime,
+    ) -> BlfParseResult<Self> {
+        let file = File::create(path)?;
+        Self::new(BufWriter::new(file), measurement_start_time)
+    }
+}
+
+impl<W: Write + Seek> BlfWriter<W> {
+    /// Wraps `inner` and writes a placeholder header.
+    pub fn new(mut inner: W, measurement_start_time: SystemTime) -> BlfParseResult<Self> {
+        let stats = FileStatistics {
+            statistics_size: STATISTICS_SIZE,
+            api_number: 0,
+            application_id: 0,
+            compression_level: COMPRESSION_LEVEL,
+            application_major: 0,
+            application_minor: 0,
+            file_size: 0,
+            uncompressed_file_size: 0,
+            object_count: 0,
+            application_build: 0,
+            last_object_time: measurement_start_time.clone(),
+            measurement_start_time,
+        };
+        stats.write(&mut inner)?;
+
+        Ok(Self {
+            inner: Some(inner),
+            stats,
+            container: Vec::with_capacity(CONTAINER_SIZE),
+            written: STATISTICS_SIZE as u64,
+            last_timestamp: 0,
+            unfinished: true,
+            skipped: BTreeMap::new(),
+        })
+    }
+
+    fn inner(&mut self) -> &mut W {
+        self.inner
+            .as_mut()
+            .expect("the writer is only taken by into_inner")
+    }
+
+    /// Number of objects written so far.
+    pub fn object_count(&self) -> u32 {
+        self.stats.object_count
+    }
+
+    /// Approximate file size so far; pending objects are counted uncompressed.
+    pub fn file_size(&self) -> u64 {
+        self.written + self.container.len() as u64
+    }
+
+    /// Timestamp of the latest object written, relative to the measurement start.
+    pub fn last_timestamp(&self) -> u64 {
+        self.last_timestamp
+    }
+
+    /// Objects left out because their type cannot be written, by raw
+    /// [`ObjectType`] number.
+    pub fn skipped(&self) -> &BTreeMap<u32, u64> {
+        &self.skipped
+    }
+
+    /// Writes one object; returns `false` if its type cannot be written.
+    pub fn write_object(&mut self, object: &LogObject) -> BlfParseResult<bool> {
+        let Some((mut header, body)) = encode_object(object) else {
+            *self.skipped.entry(object.object_type()).or_default() += 1;
+            return Ok(false);
+        };
+        if header.base.header_version != 2 {
+            header.base.header_version = 1;
+        }
+        header.base.signature = OBJECT_SIGNATURE;
+        header.base.header_size = header.calculate_header_size();
+        header.base.object_size = header.base.header_size as u32 + body.len() as u32;
+
+        let start = self.container.len();
+        header.write(&mut self.container)?;
+        self.container.extend_from_slice(&body);
+        pad_to_4(&mut self.container);
+
+        self.stats.object_count += 1;
+        self.stats.uncompressed_file_size += (self.container.len() - start) as u64;
+        self.last_timestamp = self.last_timestamp.max(object.timestamp());
+        self.unfinished = true;
+
+        if self.container.len() >= CONTAINER_SIZE {
+            self.flush_container()?;
+        }
+        Ok(true)
+    }
+
+    /// Packs the pending objects into one `LogContainer`.
+    fn flush_container(&mut self) -> BlfParseResult<()> {
+        if self.container.is_empty() {
+            return Ok(());
+        }
+
+        let (method, compressed) = compress(&self.container)?;
+
+        let mut header = ObjectHeaderBase::new(1, ObjectType::LogContainer);
+        header.object_size =
+            header.header_size as u32 + CONTAINER_FIELDS_SIZE + compressed.len() as u32;
+
+        let mut object = Vec::with_capacity(header.object_size as usize + 3);
+        header.write(&mut object)?;
+        object.write_u16::<LittleEndian>(method)?;
+        object.write_u16::<LittleEndian>(0)?; // reserved
+        object.write_u32::<LittleEndian>(0)?; // reserved
+        object.write_u32::<LittleEndian>(self.container.len() as u32)?;
+        object.write_u32::<LittleEndian>(0)?; // reserved
+        object.extend_from_slice(&compressed);
+        pad_to_4(&mut object);
+
+        self.inner().write_all(&object)?;
+        self.written += object.len() as u64;
+        self.container.clear();
+        Ok(())
+    }
+
+    /// Flushes pending objects and rewrites the header with the final statistics.
+    ///
+    /// The writer can keep writing afterwards; call `finish` again to update
+    /// the header.
+    pub fn finish(&mut self) -> BlfParseResult<FileStatistics> {
+        self.flush_container()?;
+
+        self.stats.file_size = self.written;
+        self.stats.last_object_time = SystemTime::from_timestamp_nanos(
+            self.stats
+                .measurement_start_time
+                .add_nanoseconds(self.last_timestamp),
+        );
+
+        let stats = self.stats.clone();
+        let inner = self.inner();
+        inner.seek(SeekFrom::Start(0))?;
+        stats.write(inner)?;
+        inner.seek(SeekFrom::End(0))?;
+        inner.flush()?;
+        self.unfinished = false;
+        Ok(stats)
+    }
+
+    /// Returns the underlying writer without finishing the file.
+    pub fn into_inner(mut self) -> W {
+        self.inner
+            .take()
+            .expect("the writer is only taken by into_inner")
+    }
+}
+
+impl<W: Write + Seek> Drop for BlfWriter<W> {
+    fn drop(&mut self) {
+        if self.unfinished && self.inner.is_some() {
+            let _ = self.finish();
+        }
+    }
+}
+
+#[cfg(feature = "fs")]
+/// When [`RotatingBlfWriter`] starts a new file; `None` disables a limit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct RotationPolicy {
+    /// Maximum file size in bytes (checked against the uncompressed pending data,
+    /// so files stay slightly below the limit).
+    pub max_bytes: Option<u64>,
+    /// Maximum time between the first and the last object of a file, in nanoseconds.
+    pub max_duration_ns: Option<u64>,
+}
+
+#[cfg(feature = "fs")]
+/// Writes objects to `<stem>_001.blf`, `<stem>_002.blf`, … next to `base_path`,
+/// starting a new file whenever the [`RotationPolicy`] limit is reached.
+///
+/// Every file keeps the measurement start time of the recording, so object
+/// timestamps stay comparable across files. Dropping the writer finishes the
+/// open file.
+pub struct RotatingBlfWriter {
+    base_path: PathBuf,
+    measurement_start_time: SystemTime,
+    policy: RotationPolicy,
+    current: Option<BlfWriter<BufWriter<File>>>,
+    current_path: PathBuf,
+    first_timestamp: u64,
+    finished: Vec<(PathBuf, FileStatistics)>,
+    /// Objects not written, by raw [`ObjectType`] number
+    skipped: BTreeMap<u32, u64>,
+}
+
+#[cfg(feature = "fs")]
+impl RotatingBlfWriter {
+    /// Prepares the writer; the first file is created with the first object.
+    pub fn new<P: AsRef<Path>>(
+        base_path: P,
+        measurement_start_time: SystemTime,
+        policy: RotationPolicy,
+    ) -> Self {
+        Self {
+            base_path: base_path.as_ref().to_path_buf(),
+            measurement_start_time,
+            policy,
+            current: None,
+            current_path: PathBuf::new(),
+            first_timestamp: 0,
+            finished: Vec::new(),
+            skipped: BTreeMap::new(),
+        }
+    }
+
+    /// Path of file number `index` (1-based).
+    pub fn file_path(&self, index: usize) -> PathBuf {
+        let stem = self
+            .base_path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_else(|| "capture".to_string());
+        self.base_path
+            .with_file_name(format!("{}_{:03}.blf", stem, index))
+    }
+
+    /// Number of files started so far.
+    pub fn file_count(&self) -> usize {
+        self.finished.len() + usize::from(self.current.is_some())
+    }
+
+    /// Objects left out of every file because their type cannot be written,
+    /// by raw [`ObjectType`] number.
+    pub fn skipped(&self) -> &BTreeMap<u32, u64> {
+        &self.skipped
+    }
+
+    /// Writes one object, rotating first if it would exceed a limit.
+    ///
+    /// Returns `false` if its type cannot be written.
+    pub fn write_object(&mut self, object: &LogObject) -> BlfParseResult<bool> {
+        let timestamp = object.timestamp();
+        let rotate = self.current.as_ref().is_some_and(|writer| {
+            let too_big = self
+                .policy
+                .max_bytes
+                .is_some_and(|max| writer.file_size() >= max);
+            let too_long = self.policy.max_duration_ns.is_some_and(|max| {
+                writer.object_count() > 0 && timestamp.saturating_sub(self.first_timestamp) >= max
+            });
+            too_big || too_long
+        });
+        if rotate {
+            self.close_current()?;
+        }
+
+        if self.current.is_none() {
+            let path = self.file_path(self.finished.len() + 1);
+            self.current = Some(BlfWriter::create(
+                &path,
+                self.measurement_start_time.clone(),
+            )?);
+            self.current_path = path;
+            self.first_timestamp = timestamp;
+        }
+        let written = match &mut self.current {
+            Some(writer) => writer.write_object(object)?,
+            None => false,
+        };
+        if !written {
+            *self.skipped.entry(object.object_type()).or_default() += 1;
+        }
+        Ok(written)
+    }
+
+    fn close_current(&mut self) -> BlfParseResult<()> {
+        if let Some(mut writer) = self.current.take() {
+            let stats = writer.finish()?;
+            self.finished
+                .push((std::mem::take(&mut self.current_path), stats));
+        }
+        Ok(())
+    }
+
+    /// Finishes the open file and returns every file written with its statistics.
+    pub fn finish(mut self) -> BlfParseResult<Vec<(PathBuf, FileStatistics)>> {
+        self.close_current()?;
+        Ok(self.finished)
+    }
+}
+
+#[cfg(feature = "fs")]
+/// Convenience function writing `objects` to a new BLF file at `path`.
+///
+/// Returns the final statistics; objects that cannot be written are skipped.
+pub fn write_blf_to_file<'a, P: AsRef<Path>>(
+    path: P,
+    measurement_start_time: SystemTime,
+    objects: impl IntoIterator<Item = &'a LogObject>,
+) -> BlfParseResult<FileStatistics> {
+    let mut writer = BlfWriter::create(path, measurement_start_time)?;
+    for object in objects {
+        writer.write_object(object)?;
+    }
+    writer.finish()
+}
+
+fn pad_to_4(data: &mut Vec<u8>) {
+    data.resize(data.len().next_multiple_of(4), 0);
+}
+
+/// Header (with the object type set) and serialized body of `object`.
+fn encode_object(object: &LogObject) -> Option<(ObjectHeader, Vec<u8>)> {
+    let mut body = Vec::new();
+    let (header, object_type) = match object {
+        LogObject::CanMessage(msg) => {
+            msg.encode_body(&mut body);
+            (msg.header.clone(), ObjectType::CanMessage)
+        }
+        LogObject::CanMessage2(msg) => {
+            msg.encode_body(&mut body);
+            (msg.header.clone(), ObjectType::CanMessage2)
+        }
+        LogObject::CanErrorFrame(msg) => {
+            msg.encode_body(&mut body);
+            (msg.header.clone(), ObjectType::CanError)
+        }
+        LogObject::CanFdMessage(msg) => {
+            msg.encode_body(&mut body);
+            (msg.header.clone(), ObjectType::CanFdMessage)
+        }
+        LogObject::CanFdMessage64(msg) => {
+            msg.encode_body(&mut body);
+            (msg.header.clone(), ObjectType::CanFdMessage64)
+        }
+        LogObject::LinMessage(msg) => {
+            msg.encode_body(&mut body);
+            (msg.header.clone(), ObjectType::LinMessage)
+        }
+        LogObject::GpsEvent(event) => {
+            event.encode_body(&mut body);
+            let mut header = ObjectHeader::new_v1(ObjectType::GpsEvent, 0);
+            header.object_time_stamp = event.timestamp;
+            (header, ObjectType::GpsEvent)
+        }
+        LogObject::GlobalMarker(marker) => {
+            marker.encode_body(&mut body);
+            let mut header = ObjectHeader::new_v1(ObjectType::GlobalMarker, 0);
+            header.object_time_stamp = marker.timestamp;
+            (header, ObjectType::GlobalMarker)
+        }
+        _ => return None,
+    };
+
+    let mut header = header;
+    header.base.object_type = object_type;
+    Some((header, body))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BlfParser, CanFdMessage64, CanMessage, GlobalMarker, GpsEvent, LinMessage};
+    use std::io::{Cursor, Read};
+
+    fn can_at(timestamp_ns: u64) -> LogObject {
+        let mut msg = CanMessage {
+            channel: 1,
+            dlc: 8,
+            id: 0x100,
+            ..Default::default()
+        };
+        msg.header.object_time_stamp = timestamp_ns;
+        LogObject::CanMessage(msg)
+    }
+
+    fn start_time() -> SystemTime {
+        SystemTime {
+            year: 2025,
+            month: 3,
+            day_of_week: 2,
+            day: 4,
+            hour: 10,
+            minute: 30,
+            second: 0,
+            milliseconds: 0,
+        }
+    }
+
+    #[test]
+    fn test_written_file_parses_back() {
+        let mut can = CanMessage {
+            channel: 1,
+            dlc: 8,
+            id: 0x123,
+            data: [1, 2, 3, 4, 5, 6, 7, 8],
+            ..Default::default()
+        };
+        can.header.object_time_stamp = 1_000_000;
+        let mut fd = CanFdMessage64 {
+            channel: 2,
+            dlc: 9,
+            id: 0x456,
+            data: (0..12).collect(),
+            ..Default::default()
+        };
+        fd.header.object_time_stamp = 2_000_000_000;
+        let gps = GpsEvent {
+            channel: 1,
+            latitude: 48.1,
+            longitude: 11.5,
+            timestamp: 3_000_000,
+            ..Default::default()
+        };
+        let mut lin = LinMessage {
+            channel: 3,
+            id: 0x21,
+            dlc: 2,
+            data: [0xAA, 0x55, 0, 0, 0, 0, 0, 0],
+            crc: 0x8F,
+            dir: 1,
+            ..Default::default()
+        };
+        lin.header.object_time_stamp = 2_200_000_000;
+        let marker = GlobalMarker {
+            commented_event_type: 0,
+            foreground_color: 0,
+            background_color: 0x00FF_FF00,
+            is_relocatable: 1,
+            group_name: "CanView".to_string(),
+            marker_name: "Brake test".to_string(),
+            description: "Pedal fully pressed".to_string(),
+            timestamp: 2_500_000_000,
+        };
+        let objects = vec![
+            LogObject::CanMessage(can),
+            LogObject::CanFdMessage64(fd),
+            LogObject::GpsEvent(gps),
+            LogObject::LinMessage(lin.clone()),
+            LogObject::GlobalMarker(marker.clone()),
+        ];
+
+        let mut writer = BlfWriter::new(Cursor::new(Vec::new()), start_time()).unwrap();
+        for object in &objects {
+            assert!(writer.write_object(object).unwrap());
+        }
+        let skipped = LogObject::Unhandled {
+            object_type: 0,
+            timestamp: 0,
+            data: Vec::new(),
+        };
+        assert!(!writer.write_object(&skipped).unwrap());
+        assert_eq!(writer.skipped(), &BTreeMap::from([(0, 1)]));
+        let stats = writer.finish().unwrap();
+        let data = writer.into_inner().into_inner();
+
+        assert_eq!(stats.object_count, 5);
+        assert_eq!(stats.file_size, data.len() as u64);
+        assert_eq!(stats.last_object_time.second, 2);
+
+        let mut cursor = Cursor::new(&data[..]);
+        let read_stats = FileStatistics::read(&mut cursor).unwrap();
+        assert_eq!(read_stats, stats);
+
+        let mut rest = Vec::new();
+        cursor.read_to_end(&mut rest).unwrap();
+        let parsed = BlfParser::new().parse(&rest).unwrap();
+        assert_eq!(parsed.len(), 5);
+        assert_eq!(parsed[4], LogObject::GlobalMarker(marker));
+        for (parsed, original) in parsed.iter().zip(&objects) {
+            assert_eq!(parsed.timestamp(), original.timestamp());
+            assert_eq!(parsed.channel(), original.channel());
+        }
+        match &parsed[1] {
+            LogObject::CanFdMessage64(msg) => {
+                assert_eq!(msg.id, 0x456);
+                assert_eq!(msg.data, (0..12).collect::<Vec<u8>>());
+            }
+            other => panic!("unexpected object {:?}", other),
+        }
+        match &parsed[3] {
+            LogObject::LinMessage(msg) => {
+                assert_eq!((msg.id, msg.dlc, msg.data), (lin.id, lin.dlc, lin.data));
+                assert_eq!((msg.crc, msg.dir), (lin.crc, lin.dir));
+            }
+            other => panic!("unexpected object {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_dropped_writer_finishes_the_file() {
+        let mut data = Vec::new();
+        {
+            let mut writer = BlfWriter::new(Cursor::new(&mut data), start_time()).unwrap();
+            writer.write_object(&can_at(1_000)).unwrap();
+        }
+        let stats = FileStatistics::read(&mut Cursor::new(&data[..])).unwrap();
+        assert_eq!(stats.object_count, 1);
+        assert_eq!(stats.file_size, data.len() as u64);
+        assert_eq!(crate::read_blf_from_bytes(&data).unwrap().objects.len(), 1);
+    }
+
+    #[cfg(feature = "fs")]
+    #[test]
+    fn test_rotation_by_duration() {
+        let dir = tempfile::tempdir().unwrap();
+        let policy = RotationPolicy {
+            max_bytes: None,
+            max_duration_ns: Some(10),
+        };
+        let mut writer = RotatingBlfWriter::new(dir.path().join("run.blf"), start_time(), policy);
+        for timestamp in [0, 5, 10, 15, 25] {
+            assert!(writer.write_object(&can_at(timestamp)).unwrap());
+        }
+        let lin = LogObject::Unhandled {
+            object_type: ObjectType::LinMessage as u32,
+            timestamp: 26,
+            data: Vec::new(),
+        };
+        assert!(!writer.write_object(&lin).unwrap());
+        assert_eq!(
+            writer.skipped(),
+            &BTreeMap::from([(ObjectType::LinMessage as u32, 1)])
+        );
+        assert_eq!(writer.file_count(), 3);
+        let files = writer.finish().unwrap();
+
+        let counts: Vec<_> = files.iter().map(|(_, stats)| stats.object_count).collect();
+        assert_eq!(counts, vec![2, 2, 1]);
+        assert_eq!(files[1].0, dir.path().join("run_002.blf"));
+        for (path, stats) in &files {
+            let result = crate::read_blf_from_file(path).unwrap();
+            assert_eq!(result.objects.len() as u32, stats.object_count);
+            assert_eq!(std::fs::metadata(path).unwrap().len(), stats.file_size);
+        }
+    }
+}
@@ -1,4 +1,83 @@
 use std::collections::HashMap;
+use std::fmt;
+use std::ops::RangeInclusive;
+
+/// Multiplexors selecting multiplexors are followed this deep
+const MAX_MUX_DEPTH: usize = 8;
+
+/// Role of a signal in a multiplexed message
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Multiplexing {
+    /// Always present
+    #[default]
+    None,
+    /// Selects which multiplexed signals are present (`M`)
+    Multiplexor,
+    /// Present only while the multiplexor has this raw value (`m3`)
+    Multiplexed(u64),
+    /// Multiplexed signal that selects further signals in turn (`m3M`)
+    MultiplexedMultiplexor(u64),
+}
+
+impl Multiplexing {
+    /// Parse the indicator between the signal name and the colon
+    fn parse(indicator: &str) -> Self {
+        if indicator == "M" {
+            return Multiplexing::Multiplexor;
+        }
+        let Some(value) = indicator.strip_prefix('m') else {
+            return Multiplexing::None;
+        };
+        match value.strip_suffix('M') {
+            Some(value) => value
+                .parse()
+                .map_or(Multiplexing::None, Multiplexing::MultiplexedMultiplexor),
+            None => value
+                .parse()
+                .map_or(Multiplexing::None, Multiplexing::Multiplexed),
+        }
+    }
+
+    /// Raw multiplexor value selecting a multiplexed signal
+    pub fn selector(self) -> Option<u64> {
+        match self {
+            Multiplexing::Multiplexed(value) | Multiplexing::MultiplexedMultiplexor(value) => {
+                Some(value)
+            }
+            Multiplexing::None | Multiplexing::Multiplexor => None,
+        }
+    }
+}
+
+/// Extended multiplexing of a signal, from `SG_MUL_VAL_`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExtendedMultiplexing {
+    /// Multiplexor selecting the signal
+    pub switch: String,
+    /// Raw multiplexor values selecting the signal
+    pub ranges: Vec<RangeInclusive<u64>>,
+}
+
+/// Parse `2024 S2 Mux1 3-3, 5-10;` of a `SG_MUL_VAL_` line into message ID,
+/// signal name and multiplexing
+fn parse_extended_multiplexing(text: &str) -> Option<(u32, &str, ExtendedMultiplexing)> {
+    let mut parts = text
+        .trim()
+        .trim_end_matches(';')
+        .splitn(4, char::is_whitespace);
+    let id = parts.next()?.parse().ok()?;
+    let signal = parts.next()?;
+    let switch = parts.next()?.to_string();
+    let ranges = parts
+        .next()?
+        .split(',')
+        .map(|range| {
+            let (low, high) = range.trim().split_once('-')?;
+            Some(low.trim().parse().ok()?..=high.trim().parse().ok()?)
+        })
+        .collect::<Option<Vec<_>>>()?;
+    Some((id, signal, ExtendedMultiplexing { switch, ranges }))
+}
 
 #[derive(Debug, Clone)]
 pub struct Signal {
@@ -14,66 +93,174 @@ pub struct Signal {
     pub unit: String,
     pub receivers: Vec<String>,
     pub comment: Option<String>,
+    pub multiplexing: Multiplexing,
+    /// Multiplexor and values selecting the signal, when given by `SG_MUL_VAL_`
+    pub extended_multiplexing: Option<ExtendedMultiplexing>,
+    /// Value table from `VAL_`, keyed by raw value (sign-extended for signed signals)
+    pub value_descriptions: HashMap<i64, String>,
+}
+
+/// A decoded signal: raw bits, scaled value, unit and value table entry
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DecodedValue<'a> {
+    /// Raw bits as transmitted, exact even for 64-bit counters
+    pub raw: u64,
+    /// Raw value scaled by the signal's factor and offset
+    pub physical: f64,
+    /// Unit from the signal definition, `None` when it is empty
+    pub unit: Option<&'a str>,
+    /// Description of the raw value from the signal's value table
+    pub text: Option<&'a str>,
+}
+
+impl fmt::Display for DecodedValue<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.text, self.unit) {
+            (Some(text), _) => write!(f, "{}", text),
+            (None, Some(unit)) => write!(f, "{} {}", self.physical, unit),
+            (None, None) => write!(f, "{}", self.physical),
+        }
+    }
 }
 
 impl Signal {
-    pub fn decode(&self, data: &[u8]) -> f64 {
-        let mut raw_value: u64 = 0;
-
-        if self.byte_order == 1 {
-            // Intel / Little Endian
-            for i in 0..self.signal_size {
-                let bit_pos = self.start_bit + i;
-                let byte_idx = (bit_pos / 8) as usize;
-                let bit_in_byte = bit_pos % 8;
+    /// Width in bits, capped at the 64 bits a raw value can hold
+    fn bit_len(&self) -> u32 {
+        self.signal_size.min(64)
+    }
 
-                if byte_idx < data.len() {
-                    let bit = (data[byte_idx] >> bit_in_byte) & 1;
-                    raw_value |= (bit as u64) << i;
-                }
-            }
-        } else {
-            // Motorola / Big Endian
-            // In Vector DBC, Motorola start_bit is the MSB of the signal.
-            // We need to decode backwards.
-            let mut current_bit = self.start_bit as i32;
-            for i in 0..self.signal_size {
-                let byte_idx = (current_bit / 8) as usize;
-                let bit_in_byte = current_bit % 8;
-
-                if byte_idx < data.len() {
-                    let bit = (data[byte_idx] >> bit_in_byte) & 1;
-                    raw_value |= (bit as u64) << (self.signal_size - 1 - i);
-                }
+    fn mask(&self) -> u64 {
+        match self.bit_len() {
+            64 => u64::MAX,
+            len => (1u64 << len) - 1,
+        }
+    }
 
-                // Move to next bit in Motorola order
-                if current_bit % 8 == 0 {
-                    current_bit += 15;
+    /// Bit positions in DBC numbering (byte * 8 + bit), each with its shift in the raw value
+    fn bit_positions(&self) -> impl Iterator<Item = (u32, u32)> + '_ {
+        let len = self.bit_len();
+        let mut motorola_bit = self.start_bit;
+        (0..len).map(move |i| {
+            if self.byte_order == 1 {
+                // Intel / Little Endian: start_bit is the LSB, counting up
+                (self.start_bit + i, i)
+            } else {
+                // Motorola / Big Endian: start_bit is the MSB; walk down to bit 0
+                // of the byte, then continue at bit 7 of the next byte
+                let bit = motorola_bit;
+                motorola_bit = if bit.is_multiple_of(8) {
+                    bit + 15
                 } else {
-                    current_bit -= 1;
-                }
+                    bit - 1
+                };
+                (bit, len - 1 - i)
             }
-        }
+        })
+    }
 
-        // Handle signed
-        let value = if self.value_type == '-' {
-            let sign_bit = 1u64 << (self.signal_size - 1);
-            if (raw_value & sign_bit) != 0 {
-                // Sign extend
-                let mask = (1u64 << self.signal_size) - 1;
-                let extended = raw_value | !mask;
-                extended as i64 as f64
-            } else {
-                raw_value as f64
+    /// Raw bits of the signal, before sign extension and scaling
+    ///
+    /// Bits beyond the end of `data` read as zero.
+    pub fn decode_raw(&self, data: &[u8]) -> u64 {
+        self.bit_positions().fold(0u64, |raw, (bit_pos, shift)| {
+            match data.get((bit_pos / 8) as usize) {
+                Some(byte) => raw | (((byte >> (bit_pos % 8)) & 1) as u64) << shift,
+                None => raw,
             }
+        })
+    }
+
+    /// Raw value as an integer, sign-extended for signed signals
+    pub fn raw_to_i64(&self, raw: u64) -> i64 {
+        let len = self.bit_len();
+        if self.value_type == '-' && len > 0 && raw & (1u64 << (len - 1)) != 0 {
+            (raw | !self.mask()) as i64
         } else {
-            raw_value as f64
+            raw as i64
+        }
+    }
+
+    /// Raw value as a number, sign-extended for signed signals
+    pub fn raw_to_f64(&self, raw: u64) -> f64 {
+        if self.value_type == '-' {
+            self.raw_to_i64(raw) as f64
+        } else {
+            raw as f64
+        }
+    }
+
+    /// Fractional digits needed to show every step of the physical value
+    ///
+    /// DBC files carry no decimal places of their own, so they follow from the
+    /// scaling: a factor of 0.1 gives one digit, 0.25 two, and an offset of
+    /// 0.5 at least one. Factors that are no decimal fraction, such as 1/3,
+    /// get `MAX_DECIMALS`.
+    pub fn decimals(&self) -> usize {
+        fraction_digits(self.factor).max(fraction_digits(self.offset))
+    }
+
+    pub fn decode(&self, data: &[u8]) -> DecodedValue<'_> {
+        let raw = self.decode_raw(data);
+        DecodedValue {
+            raw,
+            physical: self.raw_to_f64(raw) * self.factor + self.offset,
+            unit: (!self.unit.is_empty()).then_some(self.unit.as_str()),
+            text: self
+                .value_descriptions
+                .get(&self.raw_to_i64(raw))
+                .map(String::as_str),
+        }
+    }
+
+    /// Raw bits for the physical `value`, rounded and clamped to the signal size
+    pub fn raw_from_physical(&self, value: f64) -> u64 {
+        let mask = self.mask();
+        let raw = if self.factor == 0.0 {
+            0.0
+        } else {
+            ((value - self.offset) / self.factor).round()
         };
+        if self.value_type == '-' {
+            let max = (mask >> 1) as f64;
+            (raw.clamp(-max - 1.0, max) as i64 as u64) & mask
+        } else {
+            raw.clamp(0.0, mask as f64) as u64
+        }
+    }
+
+    /// Write the physical `value` into `data`, the inverse of `decode`
+    ///
+    /// The raw value is rounded and clamped to what fits in the signal;
+    /// bits beyond the end of `data` are skipped.
+    pub fn encode(&self, value: f64, data: &mut [u8]) {
+        self.encode_raw(self.raw_from_physical(value), data);
+    }
 
-        value * self.factor + self.offset
+    /// Write raw bits into `data`; bits beyond the end of `data` are skipped
+    pub fn encode_raw(&self, raw: u64, data: &mut [u8]) {
+        for (bit_pos, shift) in self.bit_positions() {
+            if let Some(byte) = data.get_mut((bit_pos / 8) as usize) {
+                let bit_in_byte = bit_pos % 8;
+                let bit = ((raw >> shift) & 1) as u8;
+                *byte = (*byte & !(1 << bit_in_byte)) | (bit << bit_in_byte);
+            }
+        }
     }
 }
 
+/// Most fractional digits [`Signal::decimals`] asks for
+pub const MAX_DECIMALS: usize = 6;
+
+/// Digits after the decimal point of `x`, up to `MAX_DECIMALS`
+fn fraction_digits(x: f64) -> usize {
+    (0..MAX_DECIMALS)
+        .find(|&digits| {
+            let scaled = x * 10f64.powi(digits as i32);
+            (scaled - scaled.round()).abs() < 1e-9 * scaled.abs().max(1.0)
+        })
+        .unwrap_or(MAX_DECIMALS)
+}
+
 #[derive(Debug, Clone)]
 pub struct Message {
     pub id: u32,
@@ -85,6 +272,97 @@ pub struct Message {
     pub comment: Option<String>,
 }
 
+impl Message {
+    /// The signal selecting the multiplexed signals, if the message has one
+    pub fn multiplexor(&self) -> Option<&Signal> {
+        self.signals
+            .values()
+            .find(|signal| signal.multiplexing == Multiplexing::Multiplexor)
+    }
+
+    /// The multiplexor a multiplexed signal depends on
+    ///
+    /// That is the multiplexor named by `SG_MUL_VAL_` if there is one, the
+    /// message's `M` signal otherwise.
+    pub fn switch_of(&self, signal: &Signal) -> Option<&Signal> {
+        signal.multiplexing.selector()?;
+        match &signal.extended_multiplexing {
+            Some(extended) => self.signals.get(&extended.switch),
+            None => self.multiplexor(),
+        }
+    }
+
+    /// Whether `signal` is present in a frame carrying `data`
+    ///
+    /// A multiplexed signal is present while its multiplexor is present and
+    /// holds a raw value selecting it.
+    pub fn is_active(&self, signal: &Signal, data: &[u8]) -> bool {
+        let mut signal = signal;
+        for _ in 0..MAX_MUX_DEPTH {
+            let Some(selector) = signal.multiplexing.selector() else {
+                return true;
+            };
+            let Some(switch) = self.switch_of(signal) else {
+                return false;
+            };
+            let raw = switch.decode_raw(data);
+            let selected = match &signal.extended_multiplexing {
+                Some(extended) => extended.ranges.iter().any(|range| range.contains(&raw)),
+                None => raw == selector,
+            };
+            if !selected {
+                return false;
+            }
+            signal = switch;
+        }
+        // Multiplexors selecting each other in a circle
+        false
+    }
+
+    /// Signals present in a frame carrying `data`, in no particular order
+    pub fn active_signals<'a: 'd, 'd>(
+        &'a self,
+        data: &'d [u8],
+    ) -> impl Iterator<Item = &'a Signal> + 'd {
+        self.signals
+            .values()
+            .filter(move |signal| self.is_active(signal, data))
+    }
+
+    /// Multiplexors between `signal` and a signal that is always present
+    fn mux_depth(&self, signal: &Signal) -> usize {
+        let mut signal = signal;
+        let mut depth = 0;
+        while let Some(switch) = self.switch_of(signal).filter(|_| depth < MAX_MUX_DEPTH) {
+            signal = switch;
+            depth += 1;
+        }
+        depth
+    }
+
+    /// Build a `dlc`-byte payload from physical signal values, the inverse of decoding
+    ///
+    /// Signals missing from `values` are left at raw 0. Multiplexors are written
+    /// before the signals they select, and multiplexed signals only when the
+    /// multiplexor value (raw 0 if not given) selects them.
+    pub fn encode(&self, values: &HashMap<String, f64>) -> Vec<u8> {
+        let mut data = vec![0u8; self.dlc as usize];
+        let mut signals: Vec<(&Signal, f64)> = self
+            .signals
+            .values()
+            .filter_map(|signal| Some((signal, *values.get(&signal.name)?)))
+            .collect();
+        signals.sort_by_key(|(signal, _)| self.mux_depth(signal));
+
+        for (signal, value) in signals {
+            if self.is_active(signal, &data) {
+                signal.encode(value, &mut data);
+            }
+        }
+        data
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct DbcDatabase {
     pub messages: HashMap<u32, Message>,
@@ -93,6 +371,25 @@ pub struct DbcDatabase {
     pub description: Option<String>,
 }
 
+/// Parse `0 "Off" 1 "On" ;` pairs of a `VAL_` line
+fn parse_value_descriptions(mut text: &str) -> HashMap<i64, String> {
+    let mut descriptions = HashMap::new();
+    loop {
+        text = text.trim_start();
+        let Some((value, rest)) = text.split_once('"') else {
+            break;
+        };
+        let Some((description, rest)) = rest.split_once('"') else {
+            break;
+        };
+        if let Ok(value) = value.trim().parse::<i64>() {
+            descriptions.insert(value, description.to_string());
+        }
+        text = rest;
+    }
+    descriptions
+}
+
 pub struct DbcParser;
 
 impl Default for DbcParser {
@@ -141,6 +438,17 @@ impl DbcParser {
                         current_message_id = Some(id);
                     }
                 }
+            } else if let Some(rest) = line.strip_prefix("SG_MUL_VAL_ ") {
+                // SG_MUL_VAL_ 2024 S2 Mux1 3-3, 5-10;
+                if let Some((id, name, extended)) = parse_extended_multiplexing(rest) {
+                    if let Some(signal) = database
+                        .messages
+                        .get_mut(&id)
+                        .and_then(|msg| msg.signals.get_mut(name))
+                    {
+                        signal.extended_multiplexing = Some(extended);
+                    }
+                }
             } else if line.starts_with("SG_") {
                 if let Some(msg_id) = current_message_id {
                     // Format: SG_ Name : StartBit|Size@ByteOrderValueType (Factor,Offset) [Min|Max] "Unit" Receivers
@@ -154,6 +462,12 @@ impl DbcParser {
                     } // Basic check
 
                     let name = parts[1].to_string();
+                    // SG_ Name M : ... / SG_ Name m3 : ...
+                    let multiplexing = if parts[2] == ":" {
+                        Multiplexing::None
+                    } else {
+                        Multiplexing::parse(parts[2])
+                    };
 
                     // Remainder string for complex parsing
                     if let Some(rest_idx) = line.find(':') {
@@ -235,6 +549,9 @@ impl DbcParser {
                                         unit,
                                         receivers,
                                         comment: None,
+                                        multiplexing,
+                                        extended_multiplexing: None,
+                                        value_descriptions: HashMap::new(),
                                     };
 
                                     if let Some(msg) = database.messages.get_mut(&msg_id) {
@@ -245,6 +562,24 @@ impl DbcParser {
                         }
                     }
                 }
+            } else if let Some(rest) = line.strip_prefix("VAL_ ") {
+                // VAL_ 123 SigName 0 "Off" 1 "On" ;
+                let rest = rest.trim_start();
+                let Some((id, rest)) = rest.split_once(char::is_whitespace) else {
+                    continue;
+                };
+                let rest = rest.trim_start();
+                let Some((sig_name, rest)) = rest.split_once(char::is_whitespace) else {
+                    continue;
+                };
+                if let Some(sig) = id
+                    .parse::<u32>()
+                    .ok()
+                    .and_then(|id| database.messages.get_mut(&id))
+                    .and_then(|msg| msg.signals.get_mut(sig_name))
+                {
+                    sig.value_descriptions = parse_value_descriptions(rest);
+                }
             } else if line.starts_with("CM_") {
                 // CM_ "Global Comment";
                 // CM_ BO_ 123 "Message Comment";
@@ -383,4 +718,332 @@ CM_ SG_ 12345 TestSignal1 "Signal Comment";
         assert_eq!(sig2.max, 100.0);
         assert_eq!(sig2.unit, "unit2");
     }
+
+    fn signal(start_bit: u32, signal_size: u32, byte_order: u8, value_type: char) -> Signal {
+        Signal {
+            name: "Test".to_string(),
+            start_bit,
+            signal_size,
+            byte_order,
+            value_type,
+            factor: 0.5,
+            offset: -10.0,
+            min: 0.0,
+            max: 0.0,
+            unit: String::new(),
+            receivers: Vec::new(),
+            comment: None,
+            multiplexing: Multiplexing::None,
+            extended_multiplexing: None,
+            value_descriptions: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_encode_round_trips_decode() {
+        for sig in [
+            signal(4, 12, 1, '+'),
+            signal(4, 12, 1, '-'),
+            signal(7, 16, 0, '+'),
+            signal(13, 10, 0, '-'),
+        ] {
+            let mut data = [0xA5u8; 8];
+            sig.encode(-20.5, &mut data);
+            assert_eq!(
+                sig.decode(&data).physical,
+                if sig.value_type == '-' { -20.5 } else { -10.0 }
+            );
+            sig.encode(90.0, &mut data);
+            assert_eq!(sig.decode(&data).physical, 90.0);
+        }
+    }
+
+    #[test]
+    fn test_encode_leaves_other_bits_and_clamps() {
+        let sig = signal(8, 8, 1, '+');
+        let mut data = [0xFFu8; 3];
+        sig.encode(1000.0, &mut data);
+        assert_eq!(data, [0xFF, 0xFF, 0xFF]);
+        sig.encode(-10.0, &mut data);
+        assert_eq!(data, [0xFF, 0x00, 0xFF]);
+    }
+
+    #[test]
+    fn test_message_encode_honors_multiplexing() {
+        let dbc_content = r#"
+BO_ 512 Muxed: 8 ECU
+ SG_ Mode M : 0|8@1+ (1,0) [0|255] "" Vector__XXX
+ SG_ Speed m1 : 8|16@1+ (0.1,0) [0|6553.5] "km/h" Vector__XXX
+ SG_ Temp m2 : 8|8@1- (1,-40) [-40|215] "degC" Vector__XXX
+ SG_ Counter : 63|4@0+ (1,0) [0|15] "" Vector__XXX
+"#;
+        let db = DbcParser::new().parse(dbc_content).unwrap();
+        let msg = db.messages.get(&512).unwrap();
+        assert_eq!(msg.signals["Mode"].multiplexing, Multiplexing::Multiplexor);
+        assert_eq!(
+            msg.signals["Speed"].multiplexing,
+            Multiplexing::Multiplexed(1)
+        );
+        assert_eq!(msg.signals["Counter"].multiplexing, Multiplexing::None);
+        assert_eq!(msg.multiplexor().map(|s| s.name.as_str()), Some("Mode"));
+
+        let values = HashMap::from([
+            ("Mode".to_string(), 1.0),
+            ("Speed".to_string(), 123.4),
+            ("Temp".to_string(), 10.0),
+            ("Counter".to_string(), 9.0),
+        ]);
+        let data = msg.encode(&values);
+        assert_eq!(data.len(), 8);
+        assert_eq!(msg.signals["Mode"].decode(&data).physical, 1.0);
+        assert!((msg.signals["Speed"].decode(&data).physical - 123.4).abs() < 1e-9);
+        assert_eq!(msg.signals["Counter"].decode(&data).physical, 9.0);
+
+        // Temp shares its bits with Speed and is only written when selected
+        let values = HashMap::from([("Mode".to_string(), 2.0), ("Temp".to_string(), 10.0)]);
+        let data = msg.encode(&values);
+        assert_eq!(msg.signals["Temp"].decode(&data).physical, 10.0);
+        assert_eq!(data[2], 0);
+    }
+
+    #[test]
+    fn test_only_active_multiplexed_signals_decode() {
+        let dbc_content = r#"
+BO_ 1024 Bms: 8 BMS
+ SG_ Page M : 0|8@1+ (1,0) [0|255] "" Vector__XXX
+ SG_ Cell m0M : 8|8@1+ (1,0) [0|255] "" Vector__XXX
+ SG_ Voltage m1 : 16|16@1+ (0.001,0) [0|65.535] "V" Vector__XXX
+ SG_ Temp m4 : 16|8@1+ (1,-40) [-40|215] "degC" Vector__XXX
+ SG_ Soc m1 : 8|8@1+ (0.5,0) [0|100] "%" Vector__XXX
+
+SG_MUL_VAL_ 1024 Cell Page 0-0;
+SG_MUL_VAL_ 1024 Voltage Cell 1-3, 8-9;
+SG_MUL_VAL_ 1024 Temp Cell 4-4;
+"#;
+        let db = DbcParser::new().parse(dbc_content).unwrap();
+        let msg = db.messages.get(&1024).unwrap();
+        assert_eq!(
+            msg.signals["Cell"].multiplexing,
+            Multiplexing::MultiplexedMultiplexor(0)
+        );
+        assert_eq!(
+            msg.signals["Voltage"].extended_multiplexing,
+            Some(ExtendedMultiplexing {
+                switch: "Cell".to_string(),
+                ranges: vec![1..=3, 8..=9],
+            })
+        );
+        let active = |data: &[u8]| {
+            let mut names: Vec<&str> = msg
+                .active_signals(data)
+                .map(|signal| signal.name.as_str())
+                .collect();
+            names.sort();
+            names
+        };
+
+        let data = [0, 2, 0x10, 0x27, 0, 0, 0, 0];
+        assert_eq!(active(&data), ["Cell", "Page", "Voltage"]);
+        assert_eq!(msg.signals["Voltage"].decode(&data).physical, 10.0);
+        assert_eq!(active(&[0, 4, 65, 0, 0, 0, 0, 0]), ["Cell", "Page", "Temp"]);
+        assert_eq!(active(&[0, 5, 65, 0, 0, 0, 0, 0]), ["Cell", "Page"]);
+        // Page 1 drops Cell and, with it, everything Cell selects
+        assert_eq!(active(&[1, 2, 0x10, 0x27, 0, 0, 0, 0]), ["Page", "Soc"]);
+
+        // Encoding writes the multiplexors first, whatever order the signals come in
+        let values = HashMap::from([
+            ("Voltage".to_string(), 3.3),
+            ("Temp".to_string(), 25.0),
+            ("Cell".to_string(), 9.0),
+            ("Page".to_string(), 0.0),
+        ]);
+        let data = msg.encode(&values);
+        assert_eq!(active(&data), ["Cell", "Page", "Voltage"]);
+        assert!((msg.signals["Voltage"].decode(&data).physical - 3.3).abs() < 1e-9);
+    }
+
+    /// Raw value read with whole-payload integer arithmetic instead of the bit walk
+    fn reference_raw(sig: &Signal, data: &[u8; 8]) -> u64 {
+        let mask = if sig.signal_size == 64 {
+            u64::MAX
+        } else {
+            (1u64 << sig.signal_size) - 1
+        };
+        if sig.byte_order == 1 {
+            (u64::from_le_bytes(*data) >> sig.start_bit) & mask
+        } else {
+            // Big-endian bit index of the MSB: 0 is bit 7 of byte 0
+            let msb = (sig.start_bit / 8) * 8 + (7 - sig.start_bit % 8);
+            let lsb = msb + sig.signal_size - 1;
+            (u64::from_be_bytes(*data) >> (63 - lsb)) & mask
+        }
+    }
+
+    #[test]
+    fn test_codec_matches_reference_for_all_layouts() {
+        let mut state = 0x2545_F491_4F6C_DD1Du64;
+        let mut next = move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        };
+
+        for byte_order in [0u8, 1] {
+            for value_type in ['+', '-'] {
+                for size in 1..=64u32 {
+                    for start_bit in 0..64u32 {
+                        let mut sig = signal(start_bit, size, byte_order, value_type);
+                        sig.factor = 1.0;
+                        sig.offset = 0.0;
+                        let fits = if byte_order == 1 {
+                            start_bit + size <= 64
+                        } else {
+                            (start_bit / 8) * 8 + (7 - start_bit % 8) + size <= 64
+                        };
+                        if !fits {
+                            continue;
+                        }
+
+                        let data = next().to_le_bytes();
+                        let raw = reference_raw(&sig, &data);
+                        assert_eq!(
+                            sig.decode_raw(&data),
+                            raw,
+                            "{:?}",
+                            (start_bit, size, byte_order)
+                        );
+
+                        // Writing into another payload changes only the signal's own bits
+                        let mut signal_bits = [0u8; 8];
+                        sig.encode_raw(u64::MAX, &mut signal_bits);
+                        let signal_bits = u64::from_le_bytes(signal_bits);
+                        assert_eq!(signal_bits.count_ones(), size);
+
+                        let before = next().to_le_bytes();
+                        let mut after = before;
+                        sig.encode_raw(raw, &mut after);
+                        assert_eq!(sig.decode_raw(&after), raw);
+                        let changed = u64::from_le_bytes(before) ^ u64::from_le_bytes(after);
+                        assert_eq!(changed & !signal_bits, 0);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_motorola_signals_crossing_bytes() {
+        // 12 bits with MSB at bit 3: low nibble of byte 0, then all of byte 1
+        let sig = signal(3, 12, 0, '+');
+        assert_eq!(sig.decode_raw(&[0x0A, 0xBC]), 0xABC);
+
+        // 16 bits with MSB at bit 39: bytes 4 and 5, big-endian
+        let sig = signal(39, 16, 0, '+');
+        assert_eq!(sig.decode_raw(&[0, 0, 0, 0, 0x12, 0x34, 0, 0]), 0x1234);
+
+        // 20 bits spanning three bytes, MSB at bit 5
+        let sig = signal(5, 20, 0, '+');
+        assert_eq!(sig.decode_raw(&[0x2A, 0xBC, 0xDE]), 0xAAF37);
+        let mut data = [0u8; 3];
+        sig.encode_raw(0xFFFFF, &mut data);
+        assert_eq!(data, [0x3F, 0xFF, 0xFC]);
+    }
+
+    #[test]
+    fn test_signed_and_wide_signals() {
+        // Sign extension at the top bit of a 12-bit Intel signal
+        let mut sig = signal(0, 12, 1, '-');
+        sig.factor = 1.0;
+        sig.offset = 0.0;
+        assert_eq!(sig.decode(&[0xFF, 0x0F]).physical, -1.0);
+        assert_eq!(sig.decode(&[0x00, 0x08]).physical, -2048.0);
+        assert_eq!(sig.decode(&[0xFF, 0x07]).physical, 2047.0);
+
+        // Signed Motorola signal crossing a byte boundary
+        let mut sig = signal(3, 12, 0, '-');
+        sig.factor = 1.0;
+        sig.offset = 0.0;
+        assert_eq!(sig.decode(&[0x08, 0x00]).physical, -2048.0);
+        let mut data = [0u8; 2];
+        sig.encode(-2.0, &mut data);
+        assert_eq!(data, [0x0F, 0xFE]);
+
+        // 64-bit signals no longer overflow the sign mask
+        let mut sig = signal(0, 64, 1, '-');
+        sig.factor = 1.0;
+        sig.offset = 0.0;
+        assert_eq!(sig.decode(&[0xFF; 8]).physical, -1.0);
+        let sig = signal(7, 64, 0, '+');
+        assert_eq!(
+            sig.decode_raw(&[0x80, 0, 0, 0, 0, 0, 0, 1]),
+            0x8000_0000_0000_0001
+        );
+
+        // 40-bit counter above the 32-bit range
+        let mut sig = signal(0, 40, 1, '+');
+        sig.factor = 1.0;
+        sig.offset = 0.0;
+        let mut data = [0u8; 8];
+        sig.encode(0xAB_CDEF_0123u64 as f64, &mut data);
+        assert_eq!(sig.decode_raw(&data), 0xAB_CDEF_0123);
+
+        // Zero-width and short payloads decode without panicking
+        assert_eq!(signal(0, 0, 1, '-').decode(&[0xFF]).physical, -10.0);
+        assert_eq!(signal(0, 16, 1, '+').decode_raw(&[0xFF]), 0xFF);
+    }
+
+    #[test]
+    fn test_decimals_follow_scaling() {
+        let mut sig = signal(0, 8, 1, '+');
+        for (factor, offset, decimals) in [
+            (1.0, 0.0, 0),
+            (0.1, 0.0, 1),
+            (0.25, -40.0, 2),
+            (0.001, 0.0, 3),
+            (2.0, -0.5, 1),
+            (1.0 / 3.0, 0.0, MAX_DECIMALS),
+        ] {
+            sig.factor = factor;
+            sig.offset = offset;
+            assert_eq!(
+                sig.decimals(),
+                decimals,
+                "factor {} offset {}",
+                factor,
+                offset
+            );
+        }
+    }
+
+    #[test]
+    fn test_decode_returns_raw_unit_and_text() {
+        let dbc_content = r#"
+BO_ 768 Status: 8 ECU
+ SG_ Gear : 0|4@1- (1,0) [-1|6] "" Vector__XXX
+ SG_ Odometer : 8|56@1+ (0.001,0) [0|0] "km" Vector__XXX
+
+VAL_ 768 Gear -1 "Reverse" 0 "Neutral" 1 "First gear" ;
+"#;
+        let db = DbcParser::new().parse(dbc_content).unwrap();
+        let msg = db.messages.get(&768).unwrap();
+
+        let data = [0x0F, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF];
+        let gear = msg.signals["Gear"].decode(&data);
+        assert_eq!(gear.raw, 0xF);
+        assert_eq!(gear.physical, -1.0);
+        assert_eq!(gear.unit, None);
+        assert_eq!(gear.text, Some("Reverse"));
+        assert_eq!(gear.to_string(), "Reverse");
+
+        let data = [0x01, 0, 0, 0, 0, 0, 0, 0];
+        assert_eq!(msg.signals["Gear"].decode(&data).text, Some("First gear"));
+
+        // 56-bit counter: the raw value stays exact where f64 would round
+        let odometer =
+            msg.signals["Odometer"].decode(&[0, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF]);
+        assert_eq!(odometer.raw, (1u64 << 56) - 1);
+        assert_eq!(odometer.unit, Some("km"));
+        assert_eq!(odometer.text, None);
+    }
 }
@@ -0,0 +1,163 @@
+//! Text encoding detection for database files
+//!
+//! DBC and LDF files are often saved by Windows tools in a legacy code page:
+//! Latin-1 for European comments, GBK for Chinese ones. `read_to_string`
+//! rejects both, so files are read as bytes and transcoded to UTF-8 here.
+
+use encoding_rs::{Encoding, GBK, WINDOWS_1252};
+use std::path::Path;
+
+/// Encoding a file was decoded with
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextEncoding {
+    Utf8,
+    Utf16,
+    Gbk,
+    Latin1,
+}
+
+/// Whether every non-ASCII byte starts a valid GBK two-byte character
+fn is_strict_gbk(bytes: &[u8]) -> bool {
+    let mut rest = bytes;
+    while let Some((&byte, tail)) = rest.split_first() {
+        rest = tail;
+        if byte.is_ascii() {
+            continue;
+        }
+        match rest.split_first() {
+            Some((&trail, tail))
+                if (0x81..=0xFE).contains(&byte) && matches!(trail, 0x40..=0x7E | 0x80..=0xFE) =>
+            {
+                rest = tail;
+            }
+            _ => return false,
+        }
+    }
+    true
+}
+
+/// Whether the non-ASCII bytes read as accented letters or unit symbols
+///
+/// Latin-1 text has them one or two at a time inside words ("Größe", "°C"),
+/// while Chinese characters stand between quotes and spaces as well.
+fn looks_like_latin1(bytes: &[u8]) -> bool {
+    let is_latin1_char = |byte: &u8| {
+        matches!(byte, 0xC0..=0xFF | 0xB0..=0xB3 | 0xB5 | 0xA7) && !matches!(byte, 0xD7 | 0xF7)
+    };
+    let mut start = 0;
+    while start < bytes.len() {
+        if bytes[start].is_ascii() {
+            start += 1;
+            continue;
+        }
+        let end = bytes[start..]
+            .iter()
+            .position(u8::is_ascii)
+            .map_or(bytes.len(), |len| start + len);
+        let run = &bytes[start..end];
+        let in_word = (start > 0 && bytes[start - 1].is_ascii_alphanumeric())
+            || bytes.get(end).is_some_and(u8::is_ascii_alphanumeric);
+        if run.len() > 2 || !run.iter().all(is_latin1_char) || !in_word {
+            return false;
+        }
+        start = end;
+    }
+    true
+}
+
+/// Decode file contents to UTF-8, detecting the encoding
+///
+/// A byte order mark wins; otherwise valid UTF-8 is taken as is. Text made
+/// of valid GBK characters throughout is GBK unless it also reads as Latin-1
+/// letters, and Latin-1 (as Windows-1252, which never fails) is the fallback.
+pub fn decode_text(bytes: &[u8]) -> (String, TextEncoding) {
+    if let Some((encoding, bom_len)) = Encoding::for_bom(bytes) {
+        let (text, _) = encoding.decode_without_bom_handling(&bytes[bom_len..]);
+        let detected = if encoding == encoding_rs::UTF_8 {
+            TextEncoding::Utf8
+        } else {
+            TextEncoding::Utf16
+        };
+        return (text.into_owned(), detected);
+    }
+    if let Ok(text) = std::str::from_utf8(bytes) {
+        return (text.to_string(), TextEncoding::Utf8);
+    }
+    if is_strict_gbk(bytes) && !looks_like_latin1(bytes) {
+        if let Some(text) = GBK.decode_without_bom_handling_and_without_replacement(bytes) {
+            return (text.into_owned(), TextEncoding::Gbk);
+        }
+    }
+    let (text, _) = WINDOWS_1252.decode_without_bom_handling(bytes);
+    (text.into_owned(), TextEncoding::Latin1)
+}
+
+/// Read a text file in any supported encoding
+pub fn read_text_file(path: impl AsRef<Path>) -> std::io::Result<(String, TextEncoding)> {
+    std::fs::read(path).map(|bytes| decode_text(&bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_utf8_with_and_without_bom() {
+        assert_eq!(
+            decode_text("CM_ \"速度\";".as_bytes()),
+            ("CM_ \"速度\";".to_string(), TextEncoding::Utf8)
+        );
+        let mut bytes = vec![0xEF, 0xBB, 0xBF];
+        bytes.extend_from_slice(b"VERSION \"\"");
+        assert_eq!(
+            decode_text(&bytes),
+            ("VERSION \"\"".to_string(), TextEncoding::Utf8)
+        );
+    }
+
+    #[test]
+    fn test_gbk_comment() {
+        let (bytes, _, _) = GBK.encode("CM_ SG_ 256 Speed \"车速信号\";");
+        assert_eq!(
+            decode_text(&bytes),
+            (
+                "CM_ SG_ 256 Speed \"车速信号\";".to_string(),
+                TextEncoding::Gbk
+            )
+        );
+    }
+
+    #[test]
+    fn test_gbk_single_character_units() {
+        let text = "BO_ 256 Body: 8 ECU\n \
+                    SG_ Angle : 0|16@1+ (0.1,0) [0|360] \"度\" ECU\n \
+                    SG_ Height : 16|8@1+ (1,0) [0|255] \"米\" ECU\n\
+                    CM_ SG_ 256 Angle \"角\";";
+        let (bytes, _, _) = GBK.encode(text);
+        assert_eq!(decode_text(&bytes), (text.to_string(), TextEncoding::Gbk));
+    }
+
+    #[test]
+    fn test_latin1_comment() {
+        // "Größe" and "Zähler": valid GBK byte pairs, but isolated letters
+        let bytes = b"CM_ SG_ 256 Size \"Gr\xF6\xDFe\";\nCM_ SG_ 256 Count \"Z\xE4hler\";";
+        assert_eq!(
+            decode_text(bytes),
+            (
+                "CM_ SG_ 256 Size \"Größe\";\nCM_ SG_ 256 Count \"Zähler\";".to_string(),
+                TextEncoding::Latin1
+            )
+        );
+
+        // "°C" is a valid GBK pair too
+        assert_eq!(
+            decode_text(b"SG_ Temp : 0|8@1+ (1,-40) [-40|215] \"\xB0C\" ECU"),
+            (
+                "SG_ Temp : 0|8@1+ (1,-40) [-40|215] \"°C\" ECU".to_string(),
+                TextEncoding::Latin1
+            )
+        );
+        // Not GBK at all: a lead byte without a trail byte
+        assert_eq!(decode_text(b"\"\xB0\"").1, TextEncoding::Latin1);
+    }
+}
@@ -45,11 +45,69 @@ pub struct LdfFrame {
     pub comment: Option<String>,
 }
 
+/// Sporadic frame: a slot shared by unconditional frames, sent when one of
+/// their signals changed
+#[derive(Debug, Clone)]
+pub struct LdfSporadicFrame {
+    pub name: String,
+    pub frames: Vec<String>,
+}
+
+/// Event-triggered frame: a header answered by whichever of its associated
+/// frames has new data; collisions are resolved by the schedule table
+#[derive(Debug, Clone)]
+pub struct LdfEventTriggeredFrame {
+    pub name: String,
+    pub id: u32,
+    /// Collision resolving schedule table (LIN 2.1+)
+    pub schedule_table: Option<String>,
+    pub frames: Vec<String>,
+}
+
 #[derive(Debug, Clone)]
 pub struct LdfDatabase {
     pub version: String,
     pub signals: HashMap<String, LdfSignal>,
     pub frames: HashMap<String, LdfFrame>,
+    pub sporadic_frames: HashMap<String, LdfSporadicFrame>,
+    pub event_triggered_frames: HashMap<String, LdfEventTriggeredFrame>,
+    /// `response_error` signal per node, from `Node_attributes`
+    pub response_errors: HashMap<String, String>,
+}
+
+fn parse_int(s: &str) -> Option<u32> {
+    let s = s.trim();
+    if let Some(hex) = s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+        u32::from_str_radix(hex, 16).ok()
+    } else {
+        s.parse::<u32>().ok()
+    }
+}
+
+/// Initial value of a signal: a scalar, or a `{b0, b1, ...}` byte array
+/// folded little-endian into the first four bytes
+fn parse_initial_value(s: &str) -> u32 {
+    match s.trim().strip_prefix('{') {
+        Some(array) => array
+            .trim_end_matches('}')
+            .split(',')
+            .filter_map(parse_int)
+            .take(4)
+            .enumerate()
+            .fold(0, |acc, (i, byte)| acc | ((byte & 0xFF) << (i * 8))),
+        None => parse_int(s).unwrap_or(0),
+    }
+}
+
+/// Split a `Name: a, b, c` definition into its name and trimmed fields
+fn split_definition(line: &str) -> Option<(String, Vec<&str>)> {
+    let (name, rest) = line.split_once(':')?;
+    let fields = rest
+        .split(',')
+        .map(|s| s.trim())
+        .filter(|s| !s.is_empty())
+        .collect();
+    Some((name.trim().to_string(), fields))
 }
 
 pub struct LdfParser;
@@ -70,9 +128,15 @@ impl LdfParser {
             version: "".to_string(),
             signals: HashMap::new(),
             frames: HashMap::new(),
+            sporadic_frames: HashMap::new(),
+            event_triggered_frames: HashMap::new(),
+            response_errors: HashMap::new(),
         };
 
         let mut section = "";
+        // Node_attributes nests a block per node, so track depth and owner
+        let mut node_depth = 0usize;
+        let mut current_node = String::new();
 
         // Simple line parser. Real LDF parsing is token-based and sensitive to braces.
         // We will approximate by looking for "Block {" lines.
@@ -112,6 +176,31 @@ impl LdfParser {
                 section = "Signals";
             } else if line.starts_with("Frames {") {
                 section = "Frames";
+            } else if line.starts_with("Sporadic_frames {") {
+                section = "Sporadic_frames";
+            } else if line.starts_with("Event_triggered_frames {") {
+                section = "Event_triggered_frames";
+            } else if line.starts_with("Node_attributes {") {
+                section = "Node_attributes";
+                node_depth = 1;
+            } else if section == "Node_attributes" {
+                if node_depth == 1 && line.ends_with('{') {
+                    current_node = line.trim_end_matches('{').trim().to_string();
+                } else if node_depth == 2 {
+                    // response_error = SigName;
+                    if let Some((key, value)) = line.trim_end_matches(';').split_once('=') {
+                        if key.trim() == "response_error" {
+                            database
+                                .response_errors
+                                .insert(current_node.clone(), value.trim().to_string());
+                        }
+                    }
+                }
+                node_depth += line.matches('{').count();
+                node_depth = node_depth.saturating_sub(line.matches('}').count());
+                if node_depth == 0 {
+                    section = "";
+                }
             } else if line == "}" {
                 section = "";
             } else {
@@ -119,14 +208,24 @@ impl LdfParser {
                     "Signals" => {
                         // SigName: Size, InitValue, Publisher, Subscriber1, Subscriber2;
                         // Example: SysSt: 8, 0, BCM, IPC;
+                        // Byte arrays: Data: 16, {0, 0}, BCM, IPC;
                         let clean_line = line.trim_end_matches(';');
                         if let Some(colon_idx) = clean_line.find(':') {
                             let name = clean_line[..colon_idx].trim().to_string();
                             let rest = &clean_line[colon_idx + 1..];
+                            // Pull out a braced initial value before splitting on commas
+                            let (initial, rest) = match (rest.find('{'), rest.find('}')) {
+                                (Some(open), Some(close)) if open < close => (
+                                    Some(&rest[open..=close]),
+                                    format!("{}0{}", &rest[..open], &rest[close + 1..]),
+                                ),
+                                _ => (None, rest.to_string()),
+                            };
                             let parts: Vec<&str> = rest.split(',').map(|s| s.trim()).collect();
                             if parts.len() >= 3 {
                                 let size = parts[0].parse::<u32>().unwrap_or(0);
-                                let initial_value = parts[1].parse::<u32>().unwrap_or(0);
+                                let initial_value =
+                                    parse_initial_value(initial.unwrap_or(parts[1]));
                                 let published_by = parts[2].to_string();
                                 let mut subscribed_by = Vec::new();
                                 for sub in parts.iter().skip(3) {
@@ -145,7 +244,41 @@ impl LdfParser {
                             }
                         }
                     }
-                    "Frames" => {
+                    "Sporadic_frames" => {
+                        // SporadicName: Frame1, Frame2;
+                        if let Some((name, frames)) = split_definition(line.trim_end_matches(';')) {
+                            let frames = frames.iter().map(|f| f.to_string()).collect();
+                            database
+                                .sporadic_frames
+                                .insert(name.clone(), LdfSporadicFrame { name, frames });
+                        }
+                    }
+                    "Event_triggered_frames" => {
+                        // LIN 2.0: EventName: Id, Frame1, Frame2;
+                        // LIN 2.1+: EventName: ScheduleTable, Id, Frame1, Frame2;
+                        if let Some((name, fields)) = split_definition(line.trim_end_matches(';')) {
+                            // The schedule table is a name, the frame id a number
+                            let (schedule_table, rest) = match fields.first() {
+                                Some(first) if parse_int(first).is_none() => {
+                                    (Some(first.to_string()), &fields[1..])
+                                }
+                                _ => (None, &fields[..]),
+                            };
+                            if let Some(id) = rest.first().and_then(|f| parse_int(f)) {
+                                let frames = rest[1..].iter().map(|f| f.to_string()).collect();
+                                database.event_triggered_frames.insert(
+                                    name.clone(),
+                                    LdfEventTriggeredFrame {
+                                        name,
+                                        id,
+                                        schedule_table,
+                                        frames,
+                                    },
+                                );
+                            }
+                        }
+                    }
+                    "Frames" if line.ends_with('{') => {
                         // FrameName: Id, Publisher, Size {
                         //    SigName, Offset;
                         // }
@@ -157,73 +290,65 @@ impl LdfParser {
                         //    Mapping...
                         // }
 
-                        if line.ends_with("{") {
-                            // Start of frame definition
-                            // Example: IPC_Frame: 0x10, IPC, 4 {
-                            let clean_line = line.trim_end_matches('{').trim();
-                            if let Some(colon_idx) = clean_line.find(':') {
-                                let name = clean_line[..colon_idx].trim().to_string();
-                                let rest = &clean_line[colon_idx + 1..];
-                                let parts: Vec<&str> = rest.split(',').map(|s| s.trim()).collect();
-                                if parts.len() >= 3 {
-                                    let id_str = parts[0];
-                                    let id = if let Some(stripped) = id_str.strip_prefix("0x") {
-                                        u32::from_str_radix(stripped, 16).unwrap_or(0)
+                        // Start of frame definition
+                        // Example: IPC_Frame: 0x10, IPC, 4 {
+                        let clean_line = line.trim_end_matches('{').trim();
+                        if let Some(colon_idx) = clean_line.find(':') {
+                            let name = clean_line[..colon_idx].trim().to_string();
+                            let rest = &clean_line[colon_idx + 1..];
+                            let parts: Vec<&str> = rest.split(',').map(|s| s.trim()).collect();
+                            if parts.len() >= 3 {
+                                let id = parse_int(parts[0]).unwrap_or(0);
+                                let published_by = parts[1].to_string();
+                                let size = parts[2].parse::<u32>().unwrap_or(0);
+
+                                let mut signals_map = Vec::new();
+
+                                // Read lines until "}"
+                                i += 1;
+                                while i < lines.len() {
+                                    let raw_inner = lines[i];
+                                    let (inner_line, _) = if let Some(idx) = raw_inner.find("//") {
+                                        (
+                                            raw_inner[..idx].trim(),
+                                            Some(raw_inner[idx + 2..].trim().to_string()),
+                                        )
                                     } else {
-                                        id_str.parse::<u32>().unwrap_or(0)
+                                        (raw_inner, None)
                                     };
-                                    let published_by = parts[1].to_string();
-                                    let size = parts[2].parse::<u32>().unwrap_or(0);
-
-                                    let mut signals_map = Vec::new();
 
-                                    // Read lines until "}"
-                                    i += 1;
-                                    while i < lines.len() {
-                                        let raw_inner = lines[i];
-                                        let (inner_line, _) =
-                                            if let Some(idx) = raw_inner.find("//") {
-                                                (
-                                                    raw_inner[..idx].trim(),
-                                                    Some(raw_inner[idx + 2..].trim().to_string()),
-                                                )
-                                            } else {
-                                                (raw_inner, None)
-                                            };
-
-                                        if inner_line == "}" {
-                                            break;
-                                        }
-                                        if inner_line.is_empty() {
-                                            i += 1;
-                                            continue;
-                                        }
-                                        // SigName, Offset;
-                                        let inner_clean = inner_line.trim_end_matches(';');
-                                        let inner_parts: Vec<&str> =
-                                            inner_clean.split(',').map(|s| s.trim()).collect();
-                                        if inner_parts.len() == 2 {
-                                            let sig_name = inner_parts[0].to_string();
-                                            let offset = inner_parts[1].parse::<u32>().unwrap_or(0);
-                                            signals_map.push(LdfSignalMapping {
-                                                offset,
-                                                signal_name: sig_name,
-                                            });
-                                        }
+                                    if inner_line == "}" {
+                                        break;
+                                    }
+                                    if inner_line.is_empty() {
                                         i += 1;
+                                        continue;
                                     }
+                                    // SigName, Offset;
+                                    let inner_clean = inner_line.trim_end_matches(';');
+                                    let inner_parts: Vec<&str> =
+                                        inner_clean.split(',').map(|s| s.trim()).collect();
+                                    if inner_parts.len() == 2 {
+                                        let sig_name = inner_parts[0].to_string();
+                                        let offset = inner_parts[1].parse::<u32>().unwrap_or(0);
+                                        signals_map.push(LdfSignalMapping {
+                                            offset,
+                                            signal_name: sig_name,
+                                        });
+                                    }
+                                    i += 1;
+                                }
 
-                                    let frame = LdfFrame {
-                                        name: name.clone(),
-                                        id,
-                                        published_by,
-                                        size,
+                                let frame = LdfFrame {
+                                    name: name.clone(),
+                                    id,
+                                    published_by,
+                                    size,
 
-                                        signals: signals_map,
-                                        comment: comment.clone(),
-                                    };
-                                    database.frames.insert(name, frame);
-                                }
+                                    signals: signals_map,
+                                    comment: comment.clone(),
+                                };
+                                database.frames.insert(name, frame);
                             }
                         }
                     }
@@ -285,4 +410,75 @@ Frames {
         assert_eq!(frame1.signals[0].offset, 0);
         assert_eq!(frame1.comment, Some("Frame Comment".to_string()));
     }
+
+    #[test]
+    fn test_parse_lin22_frames() {
+        let ldf_content = r#"
+LIN_description_file;
+LIN_protocol_version = "2.2";
+LIN_language_version = "2.2";
+
+Signals {
+    RSMErr: 1, 0, RSM, BCM;
+    Pos: 8, 0x7F, RSM, BCM;
+    Serial: 16, {0x34, 0x12}, RSM, BCM;
+}
+
+Frames {
+    RSM_St: 0x20, RSM, 4 {
+        RSMErr, 0;
+        Pos, 8;
+        Serial, 16;
+    }
+    LSM_St: 0x21, LSM, 2 {
+    }
+}
+
+Sporadic_frames {
+    Spor1: RSM_St, LSM_St;
+}
+
+Event_triggered_frames {
+    Evt1: Collision_Table, 0x3A, RSM_St, LSM_St;
+    Evt2: 0x3B, LSM_St;
+}
+
+Node_attributes {
+    RSM {
+        LIN_protocol = "2.2";
+        configured_NAD = 0x20;
+        response_error = RSMErr;
+        configurable_frames {
+            RSM_St;
+        }
+    }
+    LSM {
+        LIN_protocol = "2.2";
+    }
+}
+"#;
+
+        let db = LdfParser::new().parse(ldf_content).unwrap();
+
+        assert_eq!(db.signals.len(), 3);
+        assert_eq!(db.signals["Pos"].initial_value, 0x7F);
+        assert_eq!(db.signals["Serial"].initial_value, 0x1234);
+        assert_eq!(db.signals["Serial"].published_by, "RSM");
+        assert_eq!(db.signals["Serial"].subscribed_by, vec!["BCM"]);
+        assert_eq!(db.frames.len(), 2);
+        assert_eq!(db.frames["RSM_St"].signals.len(), 3);
+
+        assert_eq!(db.sporadic_frames["Spor1"].frames, vec!["RSM_St", "LSM_St"]);
+
+        let evt1 = &db.event_triggered_frames["Evt1"];
+        assert_eq!(evt1.id, 0x3A);
+        assert_eq!(evt1.schedule_table.as_deref(), Some("Collision_Table"));
+        assert_eq!(evt1.frames, vec!["RSM_St", "LSM_St"]);
+        let evt2 = &db.event_triggered_frames["Evt2"];
+        assert_eq!(evt2.id, 0x3B);
+        assert_eq!(evt2.schedule_table, None);
+
+        assert_eq!(db.response_errors.len(), 1);
+        assert_eq!(db.response_errors["RSM"], "RSMErr");
+    }
 }
@@ -1,2 +1,3 @@
 pub mod dbc;
+pub mod encoding;
 pub mod ldf;
@@ -1,5 +1,13 @@
+/// Generate the gRPC service from proto/canview.proto
+fn compile_protos() {
+    #[cfg(feature = "grpc")]
+    tonic_build::compile_protos("proto/canview.proto").expect("Failed to compile canview.proto");
+}
+
 #[cfg(target_os = "windows")]
 fn main() {
+    compile_protos();
+
     let mut res = winres::WindowsResource::new();
 
     // Set icon
@@ -20,5 +28,5 @@ fn main() {
 
 #[cfg(not(target_os = "windows"))]
 fn main() {
-    // Do nothing on non-Windows platforms
+    compile_protos();
 }
@@ -0,0 +1,382 @@
+//! The Batch view: exporting many logs at once
+
+use super::state::CanViewApp;
+use crate::models::preferences::ExportFormat;
+use gpui::{prelude::*, *};
+use gpui_component::input::Input;
+
+impl CanViewApp {
+    /// Add logs to the batch list; files already listed are skipped
+    fn add_batch_files(&mut self, cx: &mut Context<Self>) {
+        cx.spawn(async move |this, cx| {
+            let Some(files) = rfd::AsyncFileDialog::new()
+                .add_filter("Logs", &crate::handlers::LOG_FILE_EXTENSIONS)
+                .set_title("Logs to process")
+                .pick_files()
+                .await
+            else {
+                return;
+            };
+            let _ = this.update(cx, |app, cx| {
+                for file in files {
+                    let path = file.path().to_path_buf();
+                    if !app.batch_jobs.iter().any(|job| job.input == path) {
+                        app.batch_jobs.push(crate::handlers::BatchJob::new(path));
+                    }
+                }
+                cx.notify();
+            });
+        })
+        .detach();
+    }
+
+    fn pick_batch_destination(&mut self, cx: &mut Context<Self>) {
+        cx.spawn(async move |this, cx| {
+            let Some(folder) = rfd::AsyncFileDialog::new()
+                .set_title("Folder for batch outputs")
+                .pick_folder()
+                .await
+            else {
+                return;
+            };
+            let _ = this.update(cx, |app, cx| {
+                app.batch_destination = Some(folder.path().to_path_buf());
+                cx.notify();
+            });
+        })
+        .detach();
+    }
+
+    /// Run the pipeline over every listed file, one after the other
+    fn run_batch(&mut self, cx: &mut Context<Self>) {
+        if self.batch_running {
+            return;
+        }
+        if self.batch_jobs.is_empty() {
+            self.status_msg = "❌ Add logs to the batch first".into();
+            cx.notify();
+            return;
+        }
+        let Some(destination) = self.batch_destination.clone() else {
+            self.status_msg = "❌ Choose the folder for batch outputs".into();
+            cx.notify();
+            return;
+        };
+        let filter = self
+            .batch_filter_input
+            .as_ref()
+            .map(|input| input.read(cx).value().trim().to_string())
+            .unwrap_or_default();
+        let condition = if filter.is_empty() {
+            None
+        } else {
+            match crate::handlers::parse_condition(&filter) {
+                Ok(condition) => Some(condition),
+                Err(e) => {
+                    self.status_msg = format!("❌ Filter: {}", e).into();
+                    cx.notify();
+                    return;
+                }
+            }
+        };
+        let pipeline = std::sync::Arc::new(crate::handlers::BatchPipeline {
+            condition,
+            format: self.batch_format,
+            decoded: self.batch_decoded,
+            csv: self.app_config.preferences.csv_export,
+            destination,
+            manifest: self.app_config.preferences.export_manifest,
+        });
+        let dbc_channels = std::sync::Arc::new(self.dbc_channels.clone());
+        let ldf_channels = std::sync::Arc::new(self.ldf_channels.clone());
+        for job in &mut self.batch_jobs {
+            job.status = crate::handlers::BatchStatus::Pending;
+        }
+        self.batch_running = true;
+        cx.notify();
+
+        cx.spawn(async move |this, cx| {
+            let mut index = 0;
+            loop {
+                let input = this.update(cx, |app, cx| {
+                    let total = app.batch_jobs.len();
+                    let job = app.batch_jobs.get_mut(index)?;
+                    job.status = crate::handlers::BatchStatus::Running;
+                    let input = job.input.clone();
+                    app.status_msg =
+                        format!("⚙ Batch {}/{}: {}", index + 1, total, input.display()).into();
+                    cx.notify();
+                    Some(input)
+                });
+                let Ok(Some(input)) = input else {
+                    break;
+                };
+                let result = cx
+                    .background_executor()
+                    .spawn({
+                        let pipeline = pipeline.clone();
+                        let dbc_channels = dbc_channels.clone();
+                        let ldf_channels = ldf_channels.clone();
+                        let input = input.clone();
+                        async move { pipeline.run(&input, &dbc_channels, &ldf_channels) }
+                    })
+                    .await;
+                let _ = this.update(cx, |app, cx| {
+                    // The list may have been cleared while the file ran
+                    let job = app.batch_jobs.get_mut(index);
+                    if let Some(job) = job.filter(|job| job.input == input) {
+                        job.status = match result {
+                            Ok((output, rows)) => {
+                                crate::handlers::BatchStatus::Done { output, rows }
+                            }
+                            Err(e) => crate::handlers::BatchStatus::Failed(e),
+                        };
+                    }
+                    cx.notify();
+                });
+                index += 1;
+            }
+            let _ = this.update(cx, |app, cx| {
+                app.batch_running = false;
+                let failed = app
+                    .batch_jobs
+                    .iter()
+                    .any(|job| matches!(job.status, crate::handlers::BatchStatus::Failed(_)));
+                app.status_msg = format!(
+                    "{} Batch: {}",
+                    if failed { "⚠" } else { "✅" },
+                    crate::handlers::batch_summary(&app.batch_jobs)
+                )
+                .into();
+                cx.notify();
+            });
+        })
+        .detach();
+    }
+
+    pub(super) fn render_batch_view(&self, view: Entity<CanViewApp>) -> impl IntoElement {
+        let columns: [(&str, f32); 4] = [
+            ("FILE", 280.),
+            ("STATUS", 90.),
+            ("ROWS", 90.),
+            ("OUTPUT / ERROR", 480.),
+        ];
+        let file_name = |path: &std::path::Path| {
+            path.file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_else(|| path.display().to_string())
+        };
+        let toolbar_button =
+            |id: &'static str,
+             label: &'static str,
+             on_click: fn(&mut CanViewApp, &mut Context<CanViewApp>)| {
+                let view = view.clone();
+                div()
+                    .id(id)
+                    .px_3()
+                    .py_1()
+                    .text_xs()
+                    .rounded(px(3.))
+                    .cursor_pointer()
+                    .text_color(rgb(0x9ca3af))
+                    .bg(rgb(0x1a1a1a))
+                    .hover(|style| style.bg(rgb(0x252f3a)).text_color(rgb(0xcdd6f4)))
+                    .on_mouse_down(MouseButton::Left, move |_event, _window, cx| {
+                        view.update(cx, |app, cx| on_click(app, cx));
+                    })
+                    .child(label)
+            };
+
+        let rows = self.batch_jobs.iter().map(|job| {
+            let (rows, detail, color) = match &job.status {
+                crate::handlers::BatchStatus::Pending => (String::new(), String::new(), 0x6b7280),
+                crate::handlers::BatchStatus::Running => (String::new(), String::new(), 0xf59e0b),
+                crate::handlers::BatchStatus::Done { output, rows } => {
+                    (rows.to_string(), output.display().to_string(), 0x22c55e)
+                }
+                crate::handlers::BatchStatus::Failed(e) => (String::new(), e.clone(), 0xef4444),
+            };
+            let cells = [
+                file_name(&job.input),
+                job.status.label().to_string(),
+                rows,
+                detail,
+            ];
+            div()
+                .flex()
+                .h(px(22.))
+                .items_center()
+                .border_b_1()
+                .border_color(rgb(0x2a2a2a))
+                .text_xs()
+                .text_color(rgb(0xd1d5db))
+                .children(cells.into_iter().zip(columns).enumerate().map(
+                    |(index, (text, (_, width)))| {
+                        div()
+                            .w(px(width))
+                            .px_3()
+                            .flex_shrink_0()
+                            .overflow_hidden()
+                            .whitespace_nowrap()
+                            .when(index == 1 || index == 3, |el| el.text_color(rgb(color)))
+                            .child(text)
+                    },
+                ))
+        });
+
+        div()
+            .size_full()
+            .flex()
+            .flex_col()
+            .child(
+                // Toolbar
+                div()
+                    .h(px(36.))
+                    .px_3()
+                    .flex()
+                    .items_center()
+                    .justify_between()
+                    .border_b_1()
+                    .border_color(rgb(0x2a2a2a))
+                    .child(
+                        div()
+                            .text_xs()
+                            .text_color(rgb(0x9ca3af))
+                            .child(crate::handlers::batch_summary(&self.batch_jobs)),
+                    )
+                    .child(
+                        div()
+                            .flex()
+                            .gap_2()
+                            .child(toolbar_button("batch-add", "Add files…", |app, cx| {
+                                app.add_batch_files(cx)
+                            }))
+                            .child(toolbar_button("batch-clear", "Clear", |app, cx| {
+                                if !app.batch_running {
+                                    app.batch_jobs.clear();
+                                    cx.notify();
+                                }
+                            }))
+                            .child(toolbar_button("batch-run", "▶ Run", |app, cx| {
+                                app.run_batch(cx)
+                            })),
+                    ),
+            )
+            .child(
+                // Pipeline
+                div()
+                    .px_3()
+                    .py_2()
+                    .flex()
+                    .flex_col()
+                    .gap_2()
+                    .border_b_1()
+                    .border_color(rgb(0x2a2a2a))
+                    .child(
+                        div()
+                            .flex()
+                            .items_center()
+                            .gap_3()
+                            .child(
+                                div()
+                                    .w(px(90.))
+                                    .text_xs()
+                                    .text_color(rgb(0x9ca3af))
+                                    .child("Filter"),
+                            )
+                            .child(
+                                div()
+                                    .flex_1()
+                                    .when_some(self.batch_filter_input.as_ref(), |el, input| {
+                                        el.child(Input::new(input))
+                                    }),
+                            ),
+                    )
+                    .child(Self::render_choice_row(
+                        view.clone(),
+                        "Format",
+                        ExportFormat::ALL.map(|format| (format, format.label().into())),
+                        self.batch_format,
+                        crate::handlers::is_format_supported,
+                        |app, format, cx| {
+                            app.batch_format = format;
+                            cx.notify();
+                        },
+                    ))
+                    .child(Self::render_choice_row(
+                        view.clone(),
+                        "Signals",
+                        [(false, "Raw frames".into()), (true, "Decoded".into())],
+                        self.batch_decoded,
+                        |_| true,
+                        |app, decoded, cx| {
+                            app.batch_decoded = decoded;
+                            cx.notify();
+                        },
+                    ))
+                    .child(
+                        div()
+                            .flex()
+                            .items_center()
+                            .gap_3()
+                            .child(
+                                div()
+                                    .w(px(90.))
+                                    .text_xs()
+                                    .text_color(rgb(0x9ca3af))
+                                    .child("Destination"),
+                            )
+                            .child(
+                                div().flex_1().text_xs().text_color(rgb(0xd1d5db)).child(
+                                    self.batch_destination
+                                        .as_ref()
+                                        .map(|folder| folder.display().to_string())
+                                        .unwrap_or_else(|| "Not chosen".to_string()),
+                                ),
+                            )
+                            .child(toolbar_button(
+                                "batch-destination",
+                                "Choose…",
+                                |app, cx| app.pick_batch_destination(cx),
+                            )),
+                    ),
+            )
+            .child(
+                div()
+                    .h(px(24.))
+                    .flex()
+                    .items_center()
+                    .bg(rgb(0x141414))
+                    .text_xs()
+                    .font_weight(FontWeight::MEDIUM)
+                    .text_color(rgb(0x646473))
+                    .children(columns.iter().map(|(title, width)| {
+                        div().w(px(*width)).px_3().flex_shrink_0().child(*title)
+                    })),
+            )
+            .child(
+                div()
+                    .id("batch-jobs")
+                    .flex_1()
+                    .flex()
+                    .flex_col()
+                    .overflow_y_scroll()
+                    .when(self.batch_jobs.is_empty(), |parent| {
+                        parent.child(
+                            div()
+                                .flex_1()
+                                .flex()
+                                .items_center()
+                                .justify_center()
+                                .text_sm()
+                                .text_color(rgb(0x6b7280))
+                                .child(
+                                    "No files yet. Add logs, choose a filter, format and \
+                                     destination, then Run.",
+                                ),
+                        )
+                    })
+                    .children(rows),
+            )
+    }
+}
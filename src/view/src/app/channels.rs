@@ -0,0 +1,571 @@
+//! The Config view: the channels of the configuration and their databases
+
+use super::events::AppEvent;
+use super::state::CanViewApp;
+use crate::ChannelType;
+use crate::ui::components::CHANNEL_ID_VALIDATION;
+use gpui::{prelude::*, *};
+
+impl CanViewApp {
+    pub(super) fn render_config_view(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        div()
+            .size_full()
+            .p_6()
+            .flex()
+            .flex_col()
+            .gap_4()
+            .text_color(rgb(0xd1d5db))
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .justify_between()
+                    .child(
+                        div()
+                            .text_lg()
+                            .font_weight(FontWeight::MEDIUM)
+                            .text_color(rgb(0xffffff))
+                            .child("Configuration"),
+                    )
+                    .child(
+                        div()
+                            .flex()
+                            .gap_2()
+                            .child(
+                                div()
+                                    .px_3()
+                                    .py_1()
+                                    .bg(rgb(0x3b82f6))
+                                    .rounded(px(4.))
+                                    .cursor_pointer()
+                                    .hover(|style| style.bg(rgb(0x2563eb)))
+                                    .text_color(rgb(0xffffff))
+                                    .text_sm()
+                                    .child("Import Database")
+                                    .on_mouse_down(gpui::MouseButton::Left, {
+                                        let view = cx.entity().clone();
+                                        move |_event, _window, cx| {
+                                            view.update(cx, |this, cx| {
+                                                this.import_database_file(cx);
+                                            });
+                                        }
+                                    }),
+                            )
+                            .child(
+                                div()
+                                    .px_3()
+                                    .py_1()
+                                    .bg(rgb(0x374151))
+                                    .rounded(px(4.))
+                                    .cursor_pointer()
+                                    .hover(|style| style.bg(rgb(0x4b5563)))
+                                    .text_color(rgb(0xffffff))
+                                    .text_sm()
+                                    .child("Load Config")
+                                    .on_mouse_down(gpui::MouseButton::Left, {
+                                        let view = cx.entity().clone();
+                                        move |_event, _window, cx| {
+                                            view.update(cx, |this, cx| {
+                                                this.load_config(cx);
+                                            });
+                                        }
+                                    }),
+                            )
+                            .child(
+                                div()
+                                    .px_3()
+                                    .py_1()
+                                    .bg(rgb(0x10b981))
+                                    .rounded(px(4.))
+                                    .cursor_pointer()
+                                    .hover(|style| style.bg(rgb(0x059669)))
+                                    .text_color(rgb(0xffffff))
+                                    .text_sm()
+                                    .child("Save Config")
+                                    .on_mouse_down(gpui::MouseButton::Left, {
+                                        let view = cx.entity().clone();
+                                        move |_event, _window, cx| {
+                                            view.update(cx, |this, cx| {
+                                                this.save_config(cx);
+                                            });
+                                        }
+                                    }),
+                            )
+                            .child(
+                                div()
+                                    .px_3()
+                                    .py_1()
+                                    .bg(rgb(0xd97706))
+                                    .rounded(px(4.))
+                                    .cursor_pointer()
+                                    .hover(|style| style.bg(rgb(0xb45309)))
+                                    .text_color(rgb(0xffffff))
+                                    .text_sm()
+                                    .child("Validate")
+                                    .on_mouse_down(gpui::MouseButton::Left, {
+                                        let view = cx.entity().clone();
+                                        move |_event, _window, cx| {
+                                            view.update(cx, |this, cx| {
+                                                this.validate_mappings(cx);
+                                            });
+                                        }
+                                    }),
+                            ),
+                    ),
+            )
+            .child(
+                div()
+                    .flex_1()
+                    .bg(rgb(0x1f1f1f))
+                    .border_1()
+                    .border_color(rgb(0x2a2a2a))
+                    .rounded(px(8.))
+                    .flex()
+                    .flex_col()
+                    .gap_2()
+                    .p_4()
+                    .child(
+                        div()
+                            .text_sm()
+                            .font_weight(FontWeight::MEDIUM)
+                            .text_color(rgb(0xffffff))
+                            .child("Channel Mappings"),
+                    )
+                    .child(
+                        div().flex_1().flex().flex_col().gap_2().children(
+                            self.app_config
+                                .mappings
+                                .iter()
+                                .enumerate()
+                                .map(|(index, mapping)| {
+                                    let view = cx.entity().clone();
+                                    let menu_open = self.capture_device_menu == Some(index);
+                                    let row = div()
+                                        .p_3()
+                                        .bg(rgb(0x374151))
+                                        .rounded(px(4.))
+                                        .flex()
+                                        .items_center()
+                                        .justify_between()
+                                        .child(
+                                            div()
+                                                .flex()
+                                                .flex_col()
+                                                .gap_1()
+                                                .child(
+                                                    div()
+                                                        .text_sm()
+                                                        .font_weight(FontWeight::MEDIUM)
+                                                        .text_color(rgb(0xffffff))
+                                                        .child(format!(
+                                                            "Channel {} ({})",
+                                                            mapping.channel_id,
+                                                            if mapping.channel_type
+                                                                == ChannelType::CAN
+                                                            {
+                                                                "CAN"
+                                                            } else {
+                                                                "LIN"
+                                                            }
+                                                        )),
+                                                )
+                                                .child(
+                                                    div()
+                                                        .text_xs()
+                                                        .text_color(rgb(0x9ca3af))
+                                                        .child(mapping.path.clone()),
+                                                ),
+                                        )
+                                        // Hardware captured into this channel in live mode
+                                        .child(
+                                            div()
+                                                .id(("capture-device", index))
+                                                .px_2()
+                                                .py_1()
+                                                .rounded(px(4.))
+                                                .cursor_pointer()
+                                                .hover(|style| style.bg(rgb(0x4b5563)))
+                                                .text_xs()
+                                                .text_color(if mapping.capture.is_some() {
+                                                    rgb(0x22c55e)
+                                                } else {
+                                                    rgb(0x9ca3af)
+                                                })
+                                                .child(match &mapping.capture {
+                                                    Some(device) => {
+                                                        format!("🔌 {}", device.label())
+                                                    }
+                                                    None => "🔌 No capture device".to_string(),
+                                                })
+                                                .on_mouse_down(MouseButton::Left, {
+                                                    let view = view.clone();
+                                                    move |_event, _window, cx| {
+                                                        view.update(cx, |app, cx| {
+                                                            app.toggle_capture_device_menu(
+                                                                index, cx,
+                                                            )
+                                                        });
+                                                    }
+                                                }),
+                                        );
+                                    div().flex().flex_col().gap_1().child(row).when(
+                                        menu_open,
+                                        |el| {
+                                            el.child(
+                                                self.render_capture_device_picker(index, view, cx),
+                                            )
+                                        },
+                                    )
+                                }),
+                        ),
+                    ),
+            )
+            .child(
+                // Status bar
+                div()
+                    .p_4()
+                    .bg(rgb(0x1f1f1f))
+                    .border_1()
+                    .border_color(rgb(0x2a2a2a))
+                    .rounded(px(8.))
+                    .flex()
+                    .flex_col()
+                    .gap_2()
+                    .child(
+                        div()
+                            .text_sm()
+                            .font_weight(FontWeight::MEDIUM)
+                            .text_color(rgb(0xffffff))
+                            .child("System Status"),
+                    )
+                    .child(
+                        div()
+                            .flex()
+                            .gap_4()
+                            .child(div().text_xs().text_color(rgb(0x9ca3af)).child(format!(
+                                "Messages: {}",
+                                self.log_view.read(cx).messages.len()
+                            )))
+                            .child(
+                                div()
+                                    .text_xs()
+                                    .text_color(rgb(0x9ca3af))
+                                    .child(format!("DBC: {}", self.dbc_channels.len())),
+                            )
+                            .child(
+                                div()
+                                    .text_xs()
+                                    .text_color(rgb(0x9ca3af))
+                                    .child(format!("LIN: {}", self.ldf_channels.len())),
+                            ),
+                    ),
+            )
+    }
+
+    /// Show channel input for adding a new channel (inline)
+    pub fn show_add_channel_dialog(&mut self, cx: &mut Context<Self>) {
+        self.show_add_channel_input = true;
+        self.new_channel_id.clear();
+        self.new_channel_name.clear();
+        self.new_channel_db_path.clear();
+        self.editing_channel_index = None;
+        cx.notify();
+    }
+
+    /// Hide channel input and clear values
+    pub fn hide_add_channel_input(&mut self, cx: &mut Context<Self>) {
+        self.show_add_channel_input = false;
+        self.new_channel_id.clear();
+        self.new_channel_name.clear();
+        self.new_channel_db_path.clear();
+        cx.notify();
+    }
+
+    /// Save channel configuration
+    pub fn save_channel_config(&mut self, cx: &mut Context<Self>) {
+        // Debug: print current state
+        eprintln!("DEBUG: Saving channel config");
+        eprintln!("DEBUG: new_channel_id before: '{}'", self.new_channel_id);
+        eprintln!(
+            "DEBUG: new_channel_name before: '{}'",
+            self.new_channel_name
+        );
+        eprintln!(
+            "DEBUG: new_channel_db_path before: '{}'",
+            self.new_channel_db_path
+        );
+
+        // Read values from input fields (Manual read as primary)
+        // Note: Validation on input creation is currently removed to avoid issues.
+        if let Some(id_input) = &self.channel_id_input {
+            let id_text = id_input.read(cx).text().to_string();
+            eprintln!(
+                "DEBUG: Manual Read ID: '{}', Listener ID: '{}'",
+                id_text, self.new_channel_id
+            );
+            // If listener failed, fallback to manual read
+            if self.new_channel_id.is_empty() && !id_text.is_empty() {
+                self.new_channel_id = id_text;
+            } else if !id_text.is_empty() {
+                self.new_channel_id = id_text;
+            }
+        } else {
+            self.status_msg = "Error: Input lost. Try reopening.".into();
+            cx.notify();
+            return;
+        }
+
+        if let Some(name_input) = &self.channel_name_input {
+            let name_text = name_input.read(cx).text().to_string();
+            self.new_channel_name = name_text;
+        }
+
+        if self.new_channel_id.is_empty() {
+            self.status_msg = "Please enter channel ID".into();
+            cx.notify();
+            return;
+        }
+
+        if self.new_channel_name.is_empty() {
+            self.status_msg = "Please enter channel name".into();
+            cx.notify();
+            return;
+        }
+
+        if self.new_channel_db_path.is_empty() {
+            self.status_msg = "Please select a database file".into();
+            cx.notify();
+            return;
+        }
+
+        // Path is set automatically when file is selected via "Select File..." button
+        // No need to read from input since path display is read-only
+        eprintln!(
+            "DEBUG: Database path from file selector: '{}'",
+            self.new_channel_db_path
+        );
+
+        eprintln!(
+            "DEBUG: Final values - ID: '{}', Name: '{}', Path: '{}'",
+            self.new_channel_id, self.new_channel_name, self.new_channel_db_path
+        );
+
+        // Validate inputs
+        let channel_id = match CHANNEL_ID_VALIDATION.parse(&self.new_channel_id) {
+            Ok(id) => id as u16,
+            Err(e) => {
+                self.status_msg = format!("Invalid channel ID: {}", e).into();
+                cx.notify();
+                return;
+            }
+        };
+
+        if self.new_channel_name.trim().is_empty() {
+            self.status_msg = "Channel name cannot be empty".into();
+            cx.notify();
+            return;
+        }
+
+        if self.new_channel_db_path.trim().is_empty() {
+            self.status_msg = "Please select a database file or enter a path".into();
+            cx.notify();
+            return;
+        }
+
+        // Get the selected library and version
+        let state = self.library.read(cx);
+        let library_id = match &state.selected_library_id {
+            Some(id) => id.clone(),
+            None => {
+                self.status_msg = "No library selected".into();
+                cx.notify();
+                return;
+            }
+        };
+
+        // Find version name first to avoid borrow issues
+        let (library_name, version_name) = {
+            let library = match state.library_manager.find_library(&library_id) {
+                Some(lib) => lib,
+                None => {
+                    self.status_msg = "Library not found".into();
+                    cx.notify();
+                    return;
+                }
+            };
+
+            let version = match library.latest_version() {
+                Some(v) => v,
+                None => {
+                    self.status_msg = "No version found. Please add a version first.".into();
+                    cx.notify();
+                    return;
+                }
+            };
+
+            (library.name.clone(), version.name.clone())
+        };
+
+        // Set selected_version_id if not already set
+        self.library.update(cx, |library, _| {
+            if library.selected_version_id.is_none() {
+                library.selected_version_id = Some(version_name.clone());
+            }
+        });
+
+        // Create channel database config
+        let mut channel_db = crate::models::library::ChannelDatabase::new(
+            self.new_channel_type,
+            channel_id,
+            self.new_channel_name.trim().to_string(),
+            self.new_channel_db_path.trim().to_string(),
+        );
+
+        // 🔧 自动复制文件到本地存储
+        if let Some(ref storage) = self.signal_storage {
+            // 复制文件到本地存储
+            let source_path = std::path::Path::new(&self.new_channel_db_path);
+            match storage.copy_database(&library_name, &version_name, source_path) {
+                Ok(local_path) => {
+                    // 使用本地路径更新 channel_db
+                    channel_db.database_path = local_path.to_string_lossy().to_string();
+                    eprintln!("✅ Database file copied to local storage: {:?}", local_path);
+                }
+                Err(e) => {
+                    self.status_msg = format!("Failed to copy database file: {}", e).into();
+                    cx.notify();
+                    return;
+                }
+            }
+        } else {
+            eprintln!("⚠️  Signal storage not available, using original path");
+        }
+
+        // Validate the channel config
+        if let Err(e) = channel_db.validate() {
+            let msg = format!("Validation error: {}", e);
+            eprintln!("❌ {}", msg);
+            self.status_msg = msg.into();
+            cx.notify();
+            return;
+        }
+
+        // Add to the version (we need mutable access)
+        let added = self.library.update(cx, |library, _| {
+            let library = library.library_manager.find_library_mut(&library_id)?;
+            let version = library
+                .versions
+                .iter_mut()
+                .find(|v| v.name == version_name)?;
+            let added = version.add_channel_database(channel_db);
+            if added.is_ok()
+                && let Err(e) = version.update_checksum()
+            {
+                eprintln!("⚠️  Checksum not updated: {}", e);
+            }
+            Some(added)
+        });
+        if let Some(added) = added {
+            match added {
+                Ok(_) => {
+                    self.status_msg = format!("Channel {} added successfully", channel_id).into();
+                    // Keep input row open for continuous adding
+                    self.show_add_channel_input = true;
+
+                    // Clear input fields
+                    self.new_channel_id.clear();
+                    self.new_channel_name.clear();
+                    self.new_channel_db_path.clear();
+
+                    // Reset input entities so they can be recreated next time
+                    self.channel_id_input = None;
+                    self.channel_name_input = None;
+                    self.channel_db_path_input = None;
+
+                    // Reset type to CAN
+                    self.new_channel_type = crate::models::ChannelType::CAN;
+
+                    // 🔄 同步到 app_config
+                    self.app_config.libraries =
+                        self.library.read(cx).library_manager.libraries().to_vec();
+
+                    // 💾 自动保存配置
+                    self.save_config(cx);
+                    eprintln!("✅ Configuration saved automatically");
+
+                    cx.notify();
+                }
+                Err(e) => {
+                    self.status_msg = format!("Error adding channel: {}", e).into();
+                    cx.notify();
+                }
+            }
+        }
+    }
+
+    /// Delete the database of one type from a channel of the version
+    pub fn delete_channel(
+        &mut self,
+        channel_type: ChannelType,
+        channel_id: u16,
+        cx: &mut Context<Self>,
+    ) {
+        let removed = self.library.update(cx, |library, _| {
+            let library_id = library.selected_library_id.clone()?;
+            let version_name = library.selected_version_id.clone()?;
+            let library = library.library_manager.find_library_mut(&library_id)?;
+            let version = library
+                .versions
+                .iter_mut()
+                .find(|v| v.name == version_name)?;
+            // Remove from configuration
+            version
+                .channel_databases
+                .retain(|db| db.channel_type != channel_type || db.channel_id != channel_id);
+            if let Err(e) = version.update_checksum() {
+                eprintln!("⚠️  Checksum not updated: {}", e);
+            }
+            Some(())
+        });
+
+        if removed.is_some() {
+            // Remove from runtime cache
+            match channel_type {
+                ChannelType::CAN => {
+                    self.dbc_channels.remove(&channel_id);
+                }
+                ChannelType::LIN => {
+                    self.ldf_channels.remove(&channel_id);
+                }
+            }
+            cx.emit(AppEvent::DatabaseChanged);
+
+            // Sync to app config
+            self.app_config.libraries = self.library.read(cx).library_manager.libraries().to_vec();
+
+            // Save to disk
+            self.save_config(cx);
+
+            self.status_msg = format!("{:?} channel {} deleted", channel_type, channel_id).into();
+            cx.notify();
+        }
+    }
+
+    pub fn cancel_channel_config(&mut self, cx: &mut Context<Self>) {
+        self.show_add_channel_input = false;
+        self.new_channel_id.clear();
+        self.new_channel_name.clear();
+        self.new_channel_db_path.clear();
+
+        // Reset input entities
+        self.channel_id_input = None;
+        self.channel_name_input = None;
+        self.channel_db_path_input = None;
+
+        // Reset type to CAN
+        self.new_channel_type = crate::models::ChannelType::CAN;
+
+        self.editing_channel_index = None;
+        cx.notify();
+    }
+}
@@ -0,0 +1,777 @@
+//! The Charts view: decoded signals plotted over the log
+//!
+//! Signals are picked from the loaded DBC and LDF files in the list on the
+//! left and decoded over the whole log through the ID index, one chart each.
+//! The charts share the time span of [`ChartState`]: the wheel zooms around
+//! the pointer, dragging pans, a left click places cursor A and a right
+//! click cursor B. Either cursor is dragged by pressing on it. Every chart
+//! reads out its values at the cursors and the change between them, and the
+//! toolbar the time between them, to measure rise times and event spacing.
+//!
+//! Stacked, every signal gets a plot of its own. Overlaid on two axes or
+//! normalized, the signals share one taller plot, each line in its color.
+//!
+//! [`ChartState`]: crate::handlers::ChartState
+
+use super::state::CanViewApp;
+use crate::handlers::{
+    CHART_COLORS, ChartAxis, ChartDrag, ChartLayout, ChartSeries, ChartSignal, ChartViewport,
+    chart_signals, signal_series,
+};
+use crate::models::preferences::NumberFormat;
+use gpui::{prelude::*, *};
+
+/// Height of one chart's plot, in pixels
+const PLOT_HEIGHT: f32 = 140.0;
+
+/// Height of the plot shared by all signals, in pixels
+const SHARED_PLOT_HEIGHT: f32 = 320.0;
+
+/// Width of the signal list, in pixels
+const PICKER_WIDTH: f32 = 280.0;
+
+/// Span kept by one wheel step or zoom button press
+const ZOOM_STEP: f64 = 0.8;
+
+/// Pointer travel below which a press counts as a click, in pixels
+const CLICK_SLOP: f32 = 3.0;
+
+/// Distance from a cursor within which a press drags it, in pixels
+const CURSOR_GRAB: f32 = 4.0;
+
+/// Colors of cursors A and B
+const CURSOR_COLORS: [u32; 2] = [0xfacc15, 0xf87171];
+
+/// "12.345678 s"
+fn format_chart_time(time_ns: u64) -> String {
+    format!("{:.6} s", time_ns as f64 / 1_000_000_000.0)
+}
+
+/// "6000 rpm"
+pub(super) fn format_chart_value(
+    numbers: NumberFormat,
+    value: f64,
+    decimals: usize,
+    unit: &str,
+) -> String {
+    let text = numbers.format(value, decimals);
+    if unit.is_empty() {
+        text
+    } else {
+        format!("{} {}", text, unit)
+    }
+}
+
+/// Scale labels along one side of a plot
+struct AxisLabels {
+    axis: ChartAxis,
+    low: String,
+    high: String,
+    color: u32,
+}
+
+/// Color of the series at `index`
+pub(super) fn chart_color(index: usize) -> u32 {
+    CHART_COLORS[index % CHART_COLORS.len()]
+}
+
+impl CanViewApp {
+    /// Decode `signal` over the whole log, scaled to `axis` when overlaid
+    fn decode_chart_series(&self, signal: ChartSignal, axis: ChartAxis, cx: &App) -> ChartSeries {
+        let log = self.log_view.read(cx);
+        let frames = log.id_index.positions(signal.channel, signal.id).iter();
+        let points = signal_series(
+            frames.filter_map(|&index| log.messages.get(index)),
+            signal.channel,
+            signal.id,
+            &signal.name,
+            &self.dbc_channels,
+            &self.ldf_channels,
+            &self.time_offsets,
+        );
+        ChartSeries {
+            signal,
+            points,
+            axis,
+        }
+    }
+
+    /// Chart `signal`, or remove its chart
+    fn toggle_chart_signal(&mut self, signal: ChartSignal, cx: &mut Context<Self>) {
+        if self.charts.is_charted(&signal) {
+            self.charts.remove(&signal);
+        } else {
+            let series = self.decode_chart_series(signal, ChartAxis::Left, cx);
+            if series.points.is_empty() {
+                self.status_msg =
+                    format!("📈 {} has no values in this log", series.signal.name).into();
+            }
+            self.charts.series.push(series);
+        }
+        cx.notify();
+    }
+
+    /// Decode the charted signals again after the log or the databases changed
+    ///
+    /// Signals the databases no longer hold are dropped.
+    pub(super) fn refresh_charts(&mut self, cx: &App) {
+        let available = chart_signals(&self.dbc_channels, &self.ldf_channels);
+        let signals: Vec<(ChartSignal, ChartAxis)> = self
+            .charts
+            .series
+            .drain(..)
+            .map(|series| (series.signal, series.axis))
+            .filter(|(signal, _)| available.contains(signal))
+            .collect();
+        self.charts.series = signals
+            .into_iter()
+            .map(|(signal, axis)| self.decode_chart_series(signal, axis, cx))
+            .collect();
+        if self.charts.series.is_empty() {
+            self.charts.reset_view();
+        }
+    }
+
+    /// Fraction of the plot width at window x `x`
+    fn chart_fraction(&self, x: Pixels) -> f64 {
+        let (left, width) = self.chart_plot.get();
+        if width <= 0.0 {
+            return 0.5;
+        }
+        ((f32::from(x) - left) / width).clamp(0.0, 1.0) as f64
+    }
+
+    fn on_chart_wheel(
+        &mut self,
+        event: &ScrollWheelEvent,
+        window: &Window,
+        cx: &mut Context<Self>,
+    ) {
+        let delta = event.delta.pixel_delta(window.line_height());
+        let (dx, dy) = (f32::from(delta.x), f32::from(delta.y));
+        if dy != 0.0 {
+            let factor = if dy > 0.0 { ZOOM_STEP } else { 1.0 / ZOOM_STEP };
+            self.charts
+                .zoom(factor, self.chart_fraction(event.position.x));
+        } else if dx != 0.0 {
+            let width = self.chart_plot.get().1.max(1.0);
+            self.charts.pan(-(dx / width) as f64);
+        }
+        cx.notify();
+    }
+
+    fn on_chart_mouse_down(&mut self, event: &MouseDownEvent, cx: &mut Context<Self>) {
+        let Some(viewport) = self.charts.viewport() else {
+            return;
+        };
+        match event.button {
+            MouseButton::Left => {
+                let fraction = self.chart_fraction(event.position.x);
+                let tolerance = (CURSOR_GRAB / self.chart_plot.get().1.max(1.0)) as f64;
+                // A press on a cursor drags it, anywhere else the view
+                let drag = match self.charts.cursor_near(fraction, viewport, tolerance) {
+                    Some(cursor) => ChartDrag::Cursor(cursor),
+                    None => ChartDrag::Pan {
+                        start_x: f32::from(event.position.x),
+                        from: viewport,
+                    },
+                };
+                self.charts.drag = Some(drag);
+            }
+            MouseButton::Right => {
+                let time = viewport.time_at(self.chart_fraction(event.position.x));
+                self.charts.cursors[1] = Some(time);
+                cx.notify();
+            }
+            _ => {}
+        }
+    }
+
+    fn on_chart_mouse_move(&mut self, event: &MouseMoveEvent, cx: &mut Context<Self>) {
+        let Some(drag) = self.charts.drag else {
+            return;
+        };
+        if event.pressed_button != Some(MouseButton::Left) {
+            self.charts.drag = None;
+            return;
+        }
+        match drag {
+            ChartDrag::Pan { start_x, .. } => {
+                let dx = f32::from(event.position.x) - start_x;
+                if dx.abs() >= CLICK_SLOP {
+                    self.charts.drag_to(dx, self.chart_plot.get().1);
+                    cx.notify();
+                }
+            }
+            ChartDrag::Cursor(cursor) => {
+                if let Some(viewport) = self.charts.viewport() {
+                    let time = viewport.time_at(self.chart_fraction(event.position.x));
+                    self.charts.cursors[cursor] = Some(time);
+                    cx.notify();
+                }
+            }
+        }
+    }
+
+    fn on_chart_mouse_up(&mut self, event: &MouseUpEvent, cx: &mut Context<Self>) {
+        let Some(ChartDrag::Pan { start_x, from }) = self.charts.drag.take() else {
+            return;
+        };
+        // A press that did not pan places cursor A
+        if (f32::from(event.position.x) - start_x).abs() < CLICK_SLOP {
+            self.charts.cursors[0] = Some(from.time_at(self.chart_fraction(event.position.x)));
+            cx.notify();
+        }
+    }
+
+    /// Signals of the loaded databases; clicking one charts it or removes its chart
+    fn render_chart_picker(&self, view: Entity<CanViewApp>) -> impl IntoElement {
+        let signals = chart_signals(&self.dbc_channels, &self.ldf_channels);
+        let colors: Vec<Option<u32>> = signals
+            .iter()
+            .map(|signal| {
+                self.charts
+                    .series
+                    .iter()
+                    .position(|series| &series.signal == signal)
+                    .map(chart_color)
+            })
+            .collect();
+
+        div()
+            .w(px(PICKER_WIDTH))
+            .h_full()
+            .flex_shrink_0()
+            .flex()
+            .flex_col()
+            .border_r_1()
+            .border_color(rgb(0x2a2a2a))
+            .child(
+                div()
+                    .h(px(28.))
+                    .px_3()
+                    .flex()
+                    .items_center()
+                    .bg(rgb(0x141414))
+                    .border_b_1()
+                    .border_color(rgb(0x2a2a2a))
+                    .text_xs()
+                    .font_weight(FontWeight::MEDIUM)
+                    .text_color(rgb(0x646473))
+                    .child(format!("SIGNALS ({})", signals.len())),
+            )
+            .when(signals.is_empty(), |el| {
+                el.child(div().p_3().text_xs().text_color(rgb(0x646473)).child(
+                    "Assign a DBC or LDF to a channel in the Config view to chart \
+                                its signals.",
+                ))
+            })
+            .child(
+                div()
+                    .id("chart-signals")
+                    .flex_1()
+                    .overflow_y_scroll()
+                    .children(signals.into_iter().zip(colors).enumerate().map(
+                        |(i, (signal, color))| {
+                            let view = view.clone();
+                            let label = signal.label();
+                            div()
+                                .id(("chart-signal", i))
+                                .px_3()
+                                .py_1()
+                                .flex()
+                                .items_center()
+                                .gap_2()
+                                .text_xs()
+                                .cursor_pointer()
+                                .text_color(rgb(if color.is_some() { 0xcdd6f4 } else { 0x9ca3af }))
+                                .hover(|style| style.bg(rgb(0x1a1a1a)))
+                                .child(
+                                    div()
+                                        .size(px(8.))
+                                        .flex_shrink_0()
+                                        .rounded(px(4.))
+                                        .bg(rgb(color.unwrap_or(0x2a2a2a))),
+                                )
+                                .child(div().overflow_hidden().child(label))
+                                .on_mouse_down(MouseButton::Left, move |_event, _window, cx| {
+                                    let signal = signal.clone();
+                                    view.update(cx, |app, cx| app.toggle_chart_signal(signal, cx));
+                                })
+                        },
+                    )),
+            )
+    }
+
+    /// Plot of `lines`, each `(points, color)` as made by [`ChartSeries::polyline`],
+    /// with scale labels and the cursors
+    fn render_chart_plot(
+        &self,
+        lines: Vec<(Vec<(f32, f32)>, u32)>,
+        axes: Vec<AxisLabels>,
+        viewport: ChartViewport,
+        height: f32,
+        view: Entity<CanViewApp>,
+    ) -> impl IntoElement {
+        let plot = self.chart_plot.clone();
+        let cursors: Vec<(f32, u32)> = self
+            .charts
+            .cursors
+            .iter()
+            .zip(CURSOR_COLORS)
+            .filter_map(|(time, color)| Some((viewport.fraction_of((*time)?) as f32, color)))
+            .filter(|(fraction, _)| (0.0..=1.0).contains(fraction))
+            .collect();
+        let empty = lines.iter().all(|(points, _)| points.is_empty());
+
+        let on_mouse_down = {
+            let view = view.clone();
+            move |event: &MouseDownEvent, _window: &mut Window, cx: &mut App| {
+                view.update(cx, |app, cx| app.on_chart_mouse_down(event, cx))
+            }
+        };
+
+        div()
+            .h(px(height))
+            .relative()
+            .bg(rgb(0x101014))
+            .on_scroll_wheel({
+                let view = view.clone();
+                move |event, window, cx| {
+                    // Zooming takes the wheel from the list of charts
+                    cx.stop_propagation();
+                    view.update(cx, |app, cx| app.on_chart_wheel(event, window, cx));
+                }
+            })
+            .on_mouse_down(MouseButton::Left, on_mouse_down.clone())
+            .on_mouse_down(MouseButton::Right, on_mouse_down)
+            .child(
+                canvas(
+                    move |bounds, _window, _cx| {
+                        plot.set((f32::from(bounds.origin.x), f32::from(bounds.size.width)));
+                    },
+                    move |bounds, (), window, _cx| {
+                        let x = |fraction: f32| bounds.origin.x + bounds.size.width * fraction;
+                        let y = |fraction: f32| bounds.origin.y + bounds.size.height * fraction;
+                        window.with_content_mask(Some(ContentMask { bounds }), |window| {
+                            for (points, color) in &lines {
+                                let [(first_x, first_y), rest @ ..] = points.as_slice() else {
+                                    continue;
+                                };
+                                let mut builder = PathBuilder::stroke(px(1.5));
+                                builder.move_to(point(x(*first_x), y(*first_y)));
+                                for &(fx, fy) in rest {
+                                    builder.line_to(point(x(fx), y(fy)));
+                                }
+                                if rest.is_empty() {
+                                    // A lone sample still shows as a short tick
+                                    builder.line_to(point(x(*first_x) + px(2.), y(*first_y)));
+                                }
+                                if let Ok(path) = builder.build() {
+                                    window.paint_path(path, rgb(*color));
+                                }
+                            }
+                            for &(fraction, cursor_color) in &cursors {
+                                window.paint_quad(fill(
+                                    Bounds::new(
+                                        point(x(fraction), bounds.origin.y),
+                                        size(px(1.), bounds.size.height),
+                                    ),
+                                    rgb(cursor_color),
+                                ));
+                            }
+                        });
+                    },
+                )
+                .absolute()
+                .size_full(),
+            )
+            .children(axes.into_iter().flat_map(|labels| {
+                let label = |text: String| {
+                    let el = div()
+                        .absolute()
+                        .px_1()
+                        .text_xs()
+                        .text_color(rgb(labels.color));
+                    let el = match labels.axis {
+                        ChartAxis::Left => el.left_0(),
+                        ChartAxis::Right => el.right_0(),
+                    };
+                    el.child(text)
+                };
+                [label(labels.high).top_0(), label(labels.low).bottom_0()]
+            }))
+            .when(empty, |el| {
+                el.flex().items_center().justify_center().child(
+                    div()
+                        .text_xs()
+                        .text_color(rgb(0x646473))
+                        .child("No values in this span"),
+                )
+            })
+    }
+
+    /// Plot of the series at `index` alone, scaled to its own values
+    fn render_series_plot(
+        &self,
+        index: usize,
+        viewport: ChartViewport,
+        view: Entity<CanViewApp>,
+    ) -> impl IntoElement {
+        let series = &self.charts.series[index];
+        let numbers = self.app_config.preferences.number_format;
+        let range = series.value_range(viewport);
+        let line = range
+            .map(|range| series.polyline(viewport, range, self.chart_columns()))
+            .unwrap_or_default();
+        let axes = range
+            .map(|(low, high)| {
+                let (decimals, unit) = (series.signal.decimals, series.signal.unit.as_str());
+                AxisLabels {
+                    axis: ChartAxis::Left,
+                    low: format_chart_value(numbers, low, decimals, unit),
+                    high: format_chart_value(numbers, high, decimals, unit),
+                    color: 0x646473,
+                }
+            })
+            .into_iter()
+            .collect();
+        self.render_chart_plot(
+            vec![(line, chart_color(index))],
+            axes,
+            viewport,
+            PLOT_HEIGHT,
+            view,
+        )
+    }
+
+    /// One plot of every series, scaled per axis or each to its own values
+    fn render_shared_plot(
+        &self,
+        viewport: ChartViewport,
+        view: Entity<CanViewApp>,
+    ) -> impl IntoElement {
+        let columns = self.chart_columns();
+        let lines = (0..self.charts.series.len())
+            .map(|index| {
+                let series = &self.charts.series[index];
+                let line = self
+                    .charts
+                    .plot_range(index, viewport)
+                    .map(|range| series.polyline(viewport, range, columns))
+                    .unwrap_or_default();
+                (line, chart_color(index))
+            })
+            .collect();
+        // Normalized signals have no common scale; their headers give the ranges
+        let axes = match self.charts.layout {
+            ChartLayout::Overlay => [ChartAxis::Left, ChartAxis::Right]
+                .into_iter()
+                .filter_map(|axis| self.axis_labels(axis, viewport))
+                .collect(),
+            ChartLayout::Stacked | ChartLayout::Normalized => Vec::new(),
+        };
+        self.render_chart_plot(lines, axes, viewport, SHARED_PLOT_HEIGHT, view)
+    }
+
+    /// Scale labels of `axis`, in the color of its signal when it has just one
+    fn axis_labels(&self, axis: ChartAxis, viewport: ChartViewport) -> Option<AxisLabels> {
+        let (low, high) = self.charts.axis_range(axis, viewport)?;
+        let numbers = self.app_config.preferences.number_format;
+        let decimals = self.charts.on_axis(axis).map(|s| s.signal.decimals).max()?;
+        let unit = self.charts.axis_unit(axis);
+        let mut indices =
+            (0..self.charts.series.len()).filter(|&index| self.charts.series[index].axis == axis);
+        let color = match (indices.next(), indices.next()) {
+            (Some(index), None) => chart_color(index),
+            _ => 0x9ca3af,
+        };
+        Some(AxisLabels {
+            axis,
+            low: format_chart_value(numbers, low, decimals, &unit),
+            high: format_chart_value(numbers, high, decimals, &unit),
+            color,
+        })
+    }
+
+    /// Pixel columns of the plots; the width measured last frame sets the detail of the lines
+    fn chart_columns(&self) -> usize {
+        match self.chart_plot.get().1 {
+            width if width >= 1.0 => width as usize,
+            _ => 1_000,
+        }
+    }
+
+    /// Name, cursor readouts and remove button of the series at `index`
+    ///
+    /// Overlaid, a button moves the series to the other axis; normalized,
+    /// the header gives the range the series is stretched over.
+    fn render_chart_header(
+        &self,
+        index: usize,
+        viewport: ChartViewport,
+        view: Entity<CanViewApp>,
+    ) -> impl IntoElement {
+        let numbers = self.app_config.preferences.number_format;
+        let series = &self.charts.series[index];
+        let signal = series.signal.clone();
+        let value_text =
+            |value: f64| format_chart_value(numbers, value, signal.decimals, &signal.unit);
+        let range = match self.charts.layout {
+            ChartLayout::Normalized => series.value_range(viewport),
+            ChartLayout::Stacked | ChartLayout::Overlay => None,
+        };
+        let range = range.map(|(low, high)| format!("{} – {}", value_text(low), value_text(high)));
+        let mut readouts: Vec<(&str, u32, String)> = ["A", "B"]
+            .into_iter()
+            .zip(CURSOR_COLORS)
+            .zip(self.charts.cursors)
+            .filter_map(|((name, cursor_color), time)| {
+                let value = series.value_at(time?)?;
+                Some((name, cursor_color, value_text(value)))
+            })
+            .collect();
+        if let Some(delta) = self.charts.cursor_delta(series) {
+            let sign = if delta > 0.0 { "+" } else { "" };
+            readouts.push(("Δ", 0x9ca3af, format!("{}{}", sign, value_text(delta))));
+        }
+        let axis = (self.charts.layout == ChartLayout::Overlay).then_some(series.axis);
+
+        div()
+            .h(px(26.))
+            .px_3()
+            .flex()
+            .items_center()
+            .gap_3()
+            .text_xs()
+            .when_some(axis, |el, axis| {
+                let view = view.clone();
+                el.child(
+                    div()
+                        .id(("chart-axis", index))
+                        .px_1()
+                        .rounded(px(3.))
+                        .cursor_pointer()
+                        .text_color(rgb(0x9ca3af))
+                        .bg(rgb(0x1a1a1a))
+                        .hover(|style| style.bg(rgb(0x252f3a)))
+                        .child(axis.label())
+                        .on_mouse_down(MouseButton::Left, move |_event, _window, cx| {
+                            view.update(cx, |app, cx| {
+                                app.charts.toggle_axis(index);
+                                cx.notify();
+                            });
+                        }),
+                )
+            })
+            .child(
+                div()
+                    .size(px(8.))
+                    .rounded(px(4.))
+                    .bg(rgb(chart_color(index))),
+            )
+            .child(div().text_color(rgb(0xcdd6f4)).child(signal.label()))
+            .when_some(range, |el, range| {
+                el.child(div().text_color(rgb(0x646473)).child(range))
+            })
+            .children(readouts.into_iter().map(|(name, cursor_color, text)| {
+                div()
+                    .flex()
+                    .gap_1()
+                    .child(div().text_color(rgb(cursor_color)).child(name))
+                    .child(div().text_color(rgb(0xd1d5db)).child(text))
+            }))
+            .child(div().flex_1())
+            .child(
+                div()
+                    .id(("chart-remove", index))
+                    .px_1()
+                    .cursor_pointer()
+                    .text_color(rgb(0x646473))
+                    .hover(|style| style.text_color(rgb(0xcdd6f4)))
+                    .child("✕")
+                    .on_mouse_down(MouseButton::Left, move |_event, _window, cx| {
+                        let signal = signal.clone();
+                        view.update(cx, |app, cx| app.toggle_chart_signal(signal, cx));
+                    }),
+            )
+    }
+
+    /// Toolbar with the span on screen, the cursor times and the view buttons
+    fn render_chart_toolbar(
+        &self,
+        viewport: Option<ChartViewport>,
+        view: Entity<CanViewApp>,
+    ) -> impl IntoElement {
+        let button =
+            |id: &'static str,
+             label: &'static str,
+             on_click: fn(&mut CanViewApp, &mut Context<CanViewApp>)| {
+                let view = view.clone();
+                div()
+                    .id(id)
+                    .px_3()
+                    .py_1()
+                    .text_xs()
+                    .rounded(px(3.))
+                    .cursor_pointer()
+                    .text_color(rgb(0x9ca3af))
+                    .bg(rgb(0x1a1a1a))
+                    .hover(|style| style.bg(rgb(0x252f3a)).text_color(rgb(0xcdd6f4)))
+                    .on_mouse_down(MouseButton::Left, move |_event, _window, cx| {
+                        view.update(cx, |app, cx| {
+                            on_click(app, cx);
+                            cx.notify();
+                        });
+                    })
+                    .child(label)
+            };
+        let span = match viewport {
+            Some(viewport) => format!(
+                "{} – {}",
+                format_chart_time(viewport.start_ns),
+                format_chart_time(viewport.end_ns)
+            ),
+            None => "No charts".to_string(),
+        };
+        let cursors = match self.charts.cursors {
+            [Some(a), Some(b)] => {
+                let span_s = self.charts.cursor_span_ns().unwrap_or(0) as f64 / 1_000_000_000.0;
+                // The rate of an event repeating every Δt
+                let rate = if span_s == 0.0 {
+                    String::new()
+                } else {
+                    format!(" ({:.3} Hz)", 1.0 / span_s.abs())
+                };
+                format!(
+                    "A {} · B {} · Δt {:+.6} s{}",
+                    format_chart_time(a),
+                    format_chart_time(b),
+                    span_s,
+                    rate
+                )
+            }
+            [Some(a), None] => format!("A {}", format_chart_time(a)),
+            [None, Some(b)] => format!("B {}", format_chart_time(b)),
+            [None, None] => {
+                "Click to place cursor A, right-click for B, drag either to move it".to_string()
+            }
+        };
+
+        div()
+            .h(px(36.))
+            .px_3()
+            .flex()
+            .items_center()
+            .gap_3()
+            .border_b_1()
+            .border_color(rgb(0x2a2a2a))
+            .text_xs()
+            .child(div().text_color(rgb(0x9ca3af)).child(span))
+            .child(div().text_color(rgb(0x646473)).child(cursors))
+            .child(div().flex_1())
+            .children(ChartLayout::ALL.into_iter().enumerate().map(|(i, layout)| {
+                let view = view.clone();
+                let selected = self.charts.layout == layout;
+                div()
+                    .id(("chart-layout", i))
+                    .px_2()
+                    .py_1()
+                    .rounded(px(3.))
+                    .cursor_pointer()
+                    .text_color(rgb(if selected { 0xcdd6f4 } else { 0x9ca3af }))
+                    .bg(rgb(if selected { 0x252f3a } else { 0x1a1a1a }))
+                    .hover(|style| style.bg(rgb(0x252f3a)))
+                    .child(layout.label())
+                    .on_mouse_down(MouseButton::Left, move |_event, _window, cx| {
+                        view.update(cx, |app, cx| {
+                            app.charts.layout = layout;
+                            cx.notify();
+                        });
+                    })
+            }))
+            .child(div().w(px(8.)))
+            .child(button("chart-zoom-in", "Zoom in", |app, _cx| {
+                app.charts.zoom(ZOOM_STEP, 0.5)
+            }))
+            .child(button("chart-zoom-out", "Zoom out", |app, _cx| {
+                app.charts.zoom(1.0 / ZOOM_STEP, 0.5)
+            }))
+            .child(button("chart-fit", "Fit", |app, _cx| {
+                app.charts.viewport = None
+            }))
+            .child(button(
+                "chart-clear-cursors",
+                "Clear cursors",
+                |app, _cx| app.charts.cursors = [None; 2],
+            ))
+            .child(button("chart-remove-all", "Remove all", |app, _cx| {
+                app.charts.series.clear();
+                app.charts.reset_view();
+            }))
+    }
+
+    /// Signal picker on the left, the charts stacked on the right
+    pub(super) fn render_chart_view(&self, view: Entity<CanViewApp>) -> impl IntoElement {
+        let viewport = self.charts.viewport();
+        // A drag that started on a plot continues over the rest of the view
+        let charts = div()
+            .id("charts")
+            .flex_1()
+            .overflow_y_scroll()
+            .on_mouse_move({
+                let view = view.clone();
+                move |event, _window, cx| {
+                    view.update(cx, |app, cx| app.on_chart_mouse_move(event, cx))
+                }
+            })
+            .on_mouse_up(MouseButton::Left, {
+                let view = view.clone();
+                move |event, _window, cx| {
+                    view.update(cx, |app, cx| app.on_chart_mouse_up(event, cx))
+                }
+            });
+        let charts = match (viewport, self.charts.layout) {
+            (Some(viewport), ChartLayout::Stacked) => {
+                charts.children((0..self.charts.series.len()).map(|i| {
+                    div()
+                        .border_b_1()
+                        .border_color(rgb(0x2a2a2a))
+                        .child(self.render_chart_header(i, viewport, view.clone()))
+                        .child(self.render_series_plot(i, viewport, view.clone()))
+                }))
+            }
+            (Some(viewport), ChartLayout::Overlay | ChartLayout::Normalized) => charts
+                .children(
+                    (0..self.charts.series.len())
+                        .map(|i| self.render_chart_header(i, viewport, view.clone())),
+                )
+                .child(self.render_shared_plot(viewport, view.clone())),
+            (None, _) => charts.flex().items_center().justify_center().child(
+                div()
+                    .text_sm()
+                    .text_color(rgb(0x646473))
+                    .child(if self.charts.series.is_empty() {
+                        "Pick signals on the left to chart them over the log"
+                    } else {
+                        "The charted signals have no values in this log"
+                    }),
+            ),
+        };
+
+        div()
+            .size_full()
+            .flex()
+            .child(self.render_chart_picker(view.clone()))
+            .child(
+                div()
+                    .flex_1()
+                    .h_full()
+                    .flex()
+                    .flex_col()
+                    .overflow_hidden()
+                    .child(self.render_chart_toolbar(viewport, view))
+                    .child(charts),
+            )
+    }
+}
@@ -0,0 +1,244 @@
+//! The compare dialog: the loaded log against a second run
+
+use super::state::{AppModal, CanViewApp};
+use crate::handlers::AnalysisKind;
+use crate::ui::components::Modal;
+use gpui::{prelude::*, *};
+use gpui_component::input::Input;
+
+impl CanViewApp {
+    pub fn open_compare_dialog(&mut self, cx: &mut Context<Self>) {
+        self.compare_signals_input = None;
+        self.open_modal(AppModal::Compare, cx);
+    }
+
+    /// Pick run B and compare it against the loaded log on the background executor
+    fn compare_with_file(&mut self, cx: &mut Context<Self>) {
+        if self.compare_running {
+            return;
+        }
+        if self.log_view.read(cx).messages.is_empty() {
+            self.status_msg = "❌ Load run A first".into();
+            cx.notify();
+            return;
+        }
+        let text = self
+            .compare_signals_input
+            .as_ref()
+            .map(|input| input.read(cx).value().to_string())
+            .unwrap_or_default();
+        let signals = match crate::handlers::parse_signal_refs(&text) {
+            Ok(signals) => signals,
+            Err(e) => {
+                self.status_msg = format!("❌ {}", e).into();
+                cx.notify();
+                return;
+            }
+        };
+        self.compare_signals = signals.clone();
+
+        let messages = std::sync::Arc::new(self.log_view.read(cx).messages.clone());
+        let dbc_channels = self.dbc_channels.clone();
+        let ldf_channels = self.ldf_channels.clone();
+        let pool = self.analysis_pool.clone();
+        cx.spawn(async move |this, cx| {
+            let Some(file) = rfd::AsyncFileDialog::new()
+                .add_filter("Logs", &crate::handlers::LOG_FILE_EXTENSIONS)
+                .pick_file()
+                .await
+            else {
+                return;
+            };
+            let path = file.path().to_path_buf();
+            let _ = this.update(cx, |app, cx| {
+                app.compare_running = true;
+                app.status_msg = format!("⇄ Comparing with {}...", path.display()).into();
+                cx.notify();
+            });
+
+            let run_b = cx
+                .background_executor()
+                .spawn({
+                    let path = path.clone();
+                    async move { crate::handlers::read_log_file(&path) }
+                })
+                .await;
+            let result = match run_b {
+                Ok(run_b) => {
+                    let job = pool.submit(AnalysisKind::Compare, move |_| {
+                        crate::handlers::compare_runs(
+                            &messages,
+                            &run_b.objects,
+                            &signals,
+                            crate::handlers::DEFAULT_DRIFT_PCT,
+                            &dbc_channels,
+                            &ldf_channels,
+                        )
+                    });
+                    let _ = this.update(cx, |app, cx| {
+                        app.track_analysis_job(job.progress().clone(), cx);
+                    });
+                    job.await
+                }
+                Err(e) => Err(e),
+            };
+
+            let _ = this.update(cx, |app, cx| {
+                app.compare_running = false;
+                match result {
+                    Ok(comparison) => {
+                        app.status_msg = if comparison.is_identical() {
+                            "⇄ No differences between the runs".into()
+                        } else {
+                            format!(
+                                "⇄ {} new, {} missing IDs, {} cycle drifts",
+                                comparison.new_ids.len(),
+                                comparison.missing_ids.len(),
+                                comparison.drifted.len()
+                            )
+                            .into()
+                        };
+                        app.comparison = Some((path, comparison));
+                    }
+                    Err(e) => {
+                        app.status_msg = format!("❌ Compare: {}", e).into();
+                    }
+                }
+                cx.notify();
+            });
+        })
+        .detach();
+    }
+
+    /// Text of the last comparison report
+    fn comparison_report(&self) -> Option<String> {
+        self.comparison.as_ref().map(|(path, comparison)| {
+            crate::handlers::format_report(comparison, "loaded log", &path.display().to_string())
+        })
+    }
+
+    fn save_comparison_report(&mut self, cx: &mut Context<Self>) {
+        let Some(report) = self.comparison_report() else {
+            return;
+        };
+        // The report covers both logs
+        let manifest_sources = self.manifest_sources().map(|mut sources| {
+            sources.extend(self.comparison.as_ref().map(|(path, _)| path.clone()));
+            sources
+        });
+        cx.spawn(async move |this, cx| {
+            let Some(file) = rfd::AsyncFileDialog::new()
+                .add_filter("Text", &["txt"])
+                .set_file_name("comparison.txt")
+                .save_file()
+                .await
+            else {
+                return;
+            };
+            let path = file.path().to_path_buf();
+            let result = cx
+                .background_executor()
+                .spawn({
+                    let path = path.clone();
+                    async move {
+                        std::fs::write(&path, report).map_err(|e| e.to_string())?;
+                        match manifest_sources {
+                            Some(sources) => {
+                                crate::handlers::write_export_manifest(&path, &sources).map(|_| ())
+                            }
+                            None => Ok(()),
+                        }
+                    }
+                })
+                .await;
+
+            let _ = this.update(cx, |app, cx| {
+                app.status_msg = match result {
+                    Ok(()) => format!("💾 Saved report to {}", path.display()).into(),
+                    Err(e) => format!("❌ Save failed: {}", e).into(),
+                };
+                cx.notify();
+            });
+        })
+        .detach();
+    }
+
+    pub(super) fn render_compare_modal(
+        &self,
+        view: Entity<CanViewApp>,
+        on_close: impl Fn(&mut Window, &mut App) + Clone + 'static,
+    ) -> Modal {
+        let muted = |text: &'static str| div().text_xs().text_color(rgb(0x646473)).child(text);
+        let report = self.comparison_report();
+
+        Modal::new("compare-modal")
+            .title("Compare runs")
+            .width(px(600.))
+            .child(muted(
+                "The loaded log is run A; run B is picked next. New and missing IDs, \
+                 cycle times drifting by more than 5%, error frames and the signals \
+                 below are compared.",
+            ))
+            .child(muted(
+                "Signals separated by ';': 0x123 EngineSpeed, 2:0x123 EngineSpeed (channel 2 only).",
+            ))
+            .when_some(self.compare_signals_input.as_ref(), |modal, input| {
+                modal.child(Input::new(input))
+            })
+            .when(self.compare_running, |modal| modal.child(muted("Comparing...")))
+            .when_some(report.clone(), |modal, report| {
+                modal.child(
+                    div()
+                        .id("compare-report")
+                        .max_h(px(320.))
+                        .overflow_y_scroll()
+                        .flex()
+                        .flex_col()
+                        .text_xs()
+                        .text_color(rgb(0x9ca3af))
+                        .children(
+                            report
+                                .lines()
+                                .map(|line| div().whitespace_nowrap().child(line.to_string()))
+                                .collect::<Vec<_>>(),
+                        ),
+                )
+            })
+            .footer(
+                div()
+                    .flex()
+                    .gap_2()
+                    .when_some(report, |el, report| {
+                        el.child(Self::render_modal_button("compare-copy", "Copy report", false, {
+                            let view = view.clone();
+                            move |_window, cx| {
+                                cx.write_to_clipboard(ClipboardItem::new_string(report.clone()));
+                                view.update(cx, |app, cx| {
+                                    app.status_msg = "📋 Copied comparison report".into();
+                                    cx.notify();
+                                });
+                            }
+                        }))
+                        .child(Self::render_modal_button("compare-save", "Save report…", false, {
+                            let view = view.clone();
+                            move |_window, cx| {
+                                view.update(cx, |app, cx| app.save_comparison_report(cx));
+                            }
+                        }))
+                    })
+                    .child(Self::render_modal_button(
+                        "compare-close",
+                        "Close",
+                        false,
+                        on_close.clone(),
+                    ))
+                    .child(Self::render_modal_button("compare-run", "Compare with…", true, {
+                        let view = view.clone();
+                        move |_window, cx| {
+                            view.update(cx, |app, cx| app.compare_with_file(cx));
+                        }
+                    })),
+            )
+            .on_close(on_close)
+    }
+}
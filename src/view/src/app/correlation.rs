@@ -0,0 +1,245 @@
+//! The correlation dialog: a heatmap of the pairwise correlation of signals
+
+use super::state::{AppModal, CanViewApp};
+use crate::handlers::AnalysisKind;
+use crate::ui::components::Modal;
+use gpui::{prelude::*, *};
+use gpui_component::input::{Input, InputState};
+
+impl CanViewApp {
+    /// Open the correlation dialog; the time range follows the current selection
+    pub fn open_correlation_dialog(&mut self, cx: &mut Context<Self>) {
+        self.correlation_signals_input = None;
+        self.correlation_start_input = None;
+        self.correlation_end_input = None;
+        self.correlation_step_input = None;
+        self.open_modal(AppModal::Correlation, cx);
+    }
+
+    /// Correlate the signals of the dialog over its time range
+    fn compute_correlation(&mut self, cx: &mut Context<Self>) {
+        let read = |input: &Option<Entity<InputState>>| {
+            input
+                .as_ref()
+                .map(|input| input.read(cx).value().to_string())
+                .unwrap_or_default()
+        };
+        let parsed = crate::handlers::parse_signal_refs(&read(&self.correlation_signals_input))
+            .and_then(|signals| {
+                if signals.len() < 2 {
+                    return Err("Enter at least two signals".to_string());
+                }
+                let (start_s, end_s) = crate::handlers::parse_time_range(
+                    &read(&self.correlation_start_input),
+                    &read(&self.correlation_end_input),
+                )?;
+                let step = read(&self.correlation_step_input);
+                let step_ms = step
+                    .trim()
+                    .parse::<f64>()
+                    .ok()
+                    .filter(|ms| ms.is_finite() && *ms > 0.0)
+                    .ok_or_else(|| format!("Invalid step: '{}'", step.trim()))?;
+                Ok((signals, start_s.max(0.0), end_s.max(0.0), step_ms))
+            });
+        let (signals, start_s, end_s, step_ms) = match parsed {
+            Ok(parsed) => parsed,
+            Err(e) => {
+                self.status_msg = format!("❌ {}", e).into();
+                cx.notify();
+                return;
+            }
+        };
+
+        self.correlation_signals = signals.clone();
+        self.correlation_step_ms = step_ms;
+        self.cancel_analysis(AnalysisKind::Correlation);
+        let messages = std::sync::Arc::new(self.log_view.read(cx).messages.clone());
+        let dbc_channels = self.dbc_channels.clone();
+        let ldf_channels = self.ldf_channels.clone();
+        let time_offsets = self.time_offsets.clone();
+        let job = self
+            .analysis_pool
+            .submit(AnalysisKind::Correlation, move |_| {
+                crate::handlers::correlation_matrix(
+                    &messages,
+                    &signals,
+                    (start_s * 1_000_000_000.0) as u64..=(end_s * 1_000_000_000.0) as u64,
+                    (step_ms * 1_000_000.0) as u64,
+                    &dbc_channels,
+                    &ldf_channels,
+                    &time_offsets,
+                )
+            });
+        let progress = job.progress().clone();
+        self.status_msg = "≈ Correlating...".into();
+        self.track_analysis_job(progress.clone(), cx);
+
+        cx.spawn(async move |this, cx| {
+            let result = job.await;
+            let _ = this.update(cx, |app, cx| {
+                if progress.is_cancelled() {
+                    return;
+                }
+                match result {
+                    Ok(matrix) => {
+                        app.status_msg = format!(
+                            "≈ {} signals correlated over {:.3}–{:.3} s",
+                            matrix.signals.len(),
+                            start_s,
+                            end_s
+                        )
+                        .into();
+                        app.correlation = Some(matrix);
+                    }
+                    Err(e) => app.status_msg = format!("❌ {}", e).into(),
+                }
+                cx.notify();
+            });
+        })
+        .detach();
+    }
+
+    /// Heatmap cell color: red for positive, blue for negative correlation
+    fn correlation_color(r: Option<f64>) -> Rgba {
+        let Some(r) = r else {
+            return rgb(0x141414);
+        };
+        let (base, target): (u32, u32) = (0x1a1a1a, if r >= 0.0 { 0xef4444 } else { 0x3b82f6 });
+        let mix = |shift: u32| {
+            let from = ((base >> shift) & 0xff) as f64;
+            let to = ((target >> shift) & 0xff) as f64;
+            ((from + (to - from) * r.abs()).round() as u32) << shift
+        };
+        rgb(mix(16) | mix(8) | mix(0))
+    }
+
+    fn render_correlation_heatmap(matrix: &crate::handlers::CorrelationMatrix) -> Div {
+        const LABEL_WIDTH: f32 = 180.;
+        const CELL_SIZE: f32 = 48.;
+        let n = matrix.signals.len();
+        let header = div()
+            .flex()
+            .h(px(20.))
+            .items_center()
+            .text_xs()
+            .text_color(rgb(0x646473))
+            .child(div().w(px(LABEL_WIDTH)).flex_shrink_0())
+            .children((0..n).map(|column| {
+                div()
+                    .w(px(CELL_SIZE))
+                    .flex_shrink_0()
+                    .flex()
+                    .justify_center()
+                    .child((column + 1).to_string())
+            }));
+        let rows = matrix.signals.iter().enumerate().map(|(row, signal)| {
+            div()
+                .flex()
+                .h(px(CELL_SIZE * 0.6))
+                .items_center()
+                .text_xs()
+                .child(
+                    div()
+                        .w(px(LABEL_WIDTH))
+                        .flex_shrink_0()
+                        .pr_2()
+                        .overflow_hidden()
+                        .whitespace_nowrap()
+                        .text_color(rgb(0x9ca3af))
+                        .child(format!("{} {}", row + 1, signal)),
+                )
+                .children((0..n).map(|column| {
+                    let r = matrix.get(row, column);
+                    div()
+                        .w(px(CELL_SIZE))
+                        .h_full()
+                        .flex_shrink_0()
+                        .flex()
+                        .items_center()
+                        .justify_center()
+                        .border_1()
+                        .border_color(rgb(0x0c0c0e))
+                        .bg(Self::correlation_color(r))
+                        .text_color(rgb(0xf5f5f5))
+                        .child(r.map_or_else(|| "–".to_string(), |r| format!("{:+.2}", r)))
+                }))
+        });
+
+        div().flex().flex_col().child(header).children(rows).child(
+            div()
+                .pt_2()
+                .text_xs()
+                .text_color(rgb(0x646473))
+                .child(format!(
+                    "Resampled every {:.3} ms; – marks a signal missing or constant in the range.",
+                    matrix.step_ns as f64 / 1_000_000.0
+                )),
+        )
+    }
+
+    pub(super) fn render_correlation_modal(
+        &self,
+        view: Entity<CanViewApp>,
+        on_close: impl Fn(&mut Window, &mut App) + Clone + 'static,
+    ) -> Modal {
+        let muted = |text: &'static str| div().text_xs().text_color(rgb(0x646473)).child(text);
+        let signal_count = self
+            .correlation
+            .as_ref()
+            .map_or(0, |matrix| matrix.signals.len());
+        let field =
+            |label: &'static str, input: Option<&Entity<InputState>>, unit: &'static str| {
+                div()
+                    .flex()
+                    .items_center()
+                    .justify_between()
+                    .child(div().text_sm().text_color(rgb(0xcdd6f4)).child(label))
+                    .child(
+                        div()
+                            .flex()
+                            .items_center()
+                            .gap_2()
+                            .when_some(input, |el, input| {
+                                el.child(div().w(px(120.)).child(Input::new(input)))
+                            })
+                            .child(muted(unit)),
+                    )
+            };
+
+        Modal::new("correlation-modal")
+            .title("Signal correlation")
+            .width(px((200. + 48. * signal_count as f32).max(560.)))
+            .child(muted(
+                "Signals separated by ';': 0x123 EngineSpeed, 2:0x123 EngineSpeed (channel 2 only). \
+                 Each is resampled by holding its last value, then every pair is correlated.",
+            ))
+            .when_some(self.correlation_signals_input.as_ref(), |modal, input| {
+                modal.child(Input::new(input))
+            })
+            .child(field("From", self.correlation_start_input.as_ref(), "s"))
+            .child(field("To", self.correlation_end_input.as_ref(), "s"))
+            .child(field("Step", self.correlation_step_input.as_ref(), "ms"))
+            .when_some(self.correlation.as_ref(), |modal, matrix| {
+                modal.child(Self::render_correlation_heatmap(matrix))
+            })
+            .footer(
+                div()
+                    .flex()
+                    .gap_2()
+                    .child(Self::render_modal_button(
+                        "correlation-close",
+                        "Close",
+                        false,
+                        on_close.clone(),
+                    ))
+                    .child(Self::render_modal_button("correlation-run", "Compute", true, {
+                        let view = view.clone();
+                        move |_window, cx| {
+                            view.update(cx, |app, cx| app.compute_correlation(cx));
+                        }
+                    })),
+            )
+            .on_close(on_close)
+    }
+}
@@ -0,0 +1,109 @@
+//! Events CanViewApp emits about its own state
+//!
+//! Code that changes the log, the filters, the databases or the selected row
+//! emits an [`AppEvent`] instead of also fixing up everything that depends on
+//! it. Reactions live in [`CanViewApp::on_app_event`], and other entities
+//! (panels moved into entities of their own, for instance) can subscribe to
+//! the app with `cx.subscribe` and follow the same events.
+
+use super::state::CanViewApp;
+use gpui::{Context, EventEmitter};
+use std::collections::BTreeSet;
+
+/// A change to the state shared by the views
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AppEvent {
+    /// A log was loaded, or a live session started with an empty one
+    FileLoaded,
+    /// The ID / channel / type / file / tree filters changed
+    FiltersChanged,
+    /// A DBC or LDF was loaded, replaced or removed
+    DatabaseChanged,
+    /// The selected row changed; the message index of the time cursor, if any
+    CursorMoved(Option<usize>),
+}
+
+impl EventEmitter<AppEvent> for CanViewApp {}
+
+impl CanViewApp {
+    /// Handle the app's own events; called once when the app entity is created
+    pub(super) fn subscribe_app_events(&mut self, cx: &mut Context<Self>) {
+        cx.subscribe(&cx.entity(), |app, _, event, cx| {
+            app.on_app_event(*event, cx)
+        })
+        .detach();
+        // Quitting may not drop the app, so a running recording is closed here
+        cx.on_app_quit(|app, cx| {
+            if let Some(recorder) = app.capture.update(cx, |capture, _| capture.recorder.take()) {
+                if let Err(e) = recorder.finish() {
+                    eprintln!("❌ Recording failed: {}", e);
+                }
+            }
+            async {}
+        })
+        .detach();
+        // Floating panels come back with the layout and go away with the app
+        self.sync_panel_windows(cx);
+        cx.on_release(|app, cx| app.close_panel_windows(cx))
+            .detach();
+    }
+
+    fn on_app_event(&mut self, event: AppEvent, cx: &mut Context<Self>) {
+        match event {
+            AppEvent::FileLoaded => {
+                self.log_view.update(cx, |log, _| {
+                    log.selection.clear();
+                    log.row_details.borrow_mut().clear();
+                });
+                self.filters.update(cx, |filters, _| {
+                    filters.tree.clear();
+                    filters.files.clear();
+                });
+                self.charts.reset_view();
+                self.refresh_charts(cx);
+                self.video_playback = None;
+            }
+            AppEvent::FiltersChanged => {
+                // Keep the selection on visible rows
+                let visible: BTreeSet<usize> = self.filtered_indices(cx).iter().copied().collect();
+                self.log_view.update(cx, |log, _| {
+                    if log
+                        .selection
+                        .indices()
+                        .any(|index| !visible.contains(&index))
+                    {
+                        log.selection.clear();
+                    }
+                });
+            }
+            AppEvent::DatabaseChanged => {
+                // Decoded texts were made with the previous databases
+                self.log_view.read(cx).row_details.borrow_mut().clear();
+                self.refresh_charts(cx);
+            }
+            AppEvent::CursorMoved(index) => {
+                // Cursor A of the charts follows the selected row
+                let messages = &self.log_view.read(cx).messages;
+                if let Some(msg) = index.and_then(|index| messages.get(index)) {
+                    self.charts.cursors[0] = Some(self.time_offsets.timestamp(msg));
+                }
+                // The video pane follows the cursor, unless the video moves it
+                if self.video_playback.is_none() {
+                    self.seek_video_to_cursor(cx);
+                }
+            }
+        }
+        cx.notify();
+    }
+
+    /// Emit [`AppEvent::FiltersChanged`]
+    pub(super) fn filters_changed(&mut self, cx: &mut Context<Self>) {
+        cx.emit(AppEvent::FiltersChanged);
+    }
+
+    /// Emit [`AppEvent::CursorMoved`] for the current selection
+    pub(super) fn cursor_moved(&mut self, cx: &mut Context<Self>) {
+        let index = self.log_view.read(cx).selection.indices().next();
+        cx.emit(AppEvent::CursorMoved(index));
+    }
+}
@@ -0,0 +1,615 @@
+//! The export dialog and the export it starts
+
+use super::state::{AppModal, CanViewApp};
+use crate::handlers::{EXPORT_CHUNK_SIZE, ExportChoice, ExportPlugin, ExportScope};
+use crate::models::preferences::{
+    CsvDelimiter, DecimalSeparator, ExportFormat, IdBase, SignalPrecision, TimestampFormat,
+};
+use crate::ui::components::Modal;
+use gpui::{prelude::*, *};
+use gpui_component::input::{Input, InputState};
+use std::path::PathBuf;
+
+impl CanViewApp {
+    /// Open the export dialog preset to the preferred format
+    pub fn open_export_dialog(&mut self, cx: &mut Context<Self>) {
+        self.export_format = self.app_config.preferences.default_export_format;
+        self.export_plugin = None;
+        self.open_modal(AppModal::Export, cx);
+    }
+
+    /// Logs to hash into an export manifest; `None` when manifests are turned off
+    pub(super) fn manifest_sources(&self) -> Option<Vec<PathBuf>> {
+        self.app_config
+            .preferences
+            .export_manifest
+            .then(|| self.log_path.iter().cloned().collect())
+    }
+
+    /// Messages covered by the chosen export scope
+    fn export_messages(&self, cx: &App) -> Result<Vec<crate::handlers::NumberedMessage>, String> {
+        let log = self.log_view.read(cx);
+        match self.export_scope {
+            ExportScope::All => Ok(crate::handlers::numbered_messages(
+                &log.messages,
+                0..log.messages.len(),
+            )),
+            ExportScope::Filtered => Ok(crate::handlers::numbered_messages(
+                &log.messages,
+                self.filtered_indices(cx).iter().copied(),
+            )),
+            ExportScope::Selected if log.selection.is_empty() => {
+                Err("No rows selected".to_string())
+            }
+            ExportScope::Selected => Ok(crate::handlers::numbered_messages(
+                &log.messages,
+                log.selection.indices(),
+            )),
+            ExportScope::TimeRange => {
+                let value = |input: &Option<Entity<InputState>>| {
+                    input
+                        .as_ref()
+                        .map(|input| input.read(cx).value().to_string())
+                        .unwrap_or_default()
+                };
+                let (start_s, end_s) = crate::handlers::parse_time_range(
+                    &value(&self.export_range_start_input),
+                    &value(&self.export_range_end_input),
+                )?;
+                Ok(crate::handlers::messages_in_time_range(
+                    &log.messages,
+                    &self.time_offsets,
+                    start_s,
+                    end_s,
+                ))
+            }
+        }
+    }
+
+    /// Ask for a destination and write the export on the background executor
+    fn start_export(&mut self, cx: &mut Context<Self>) {
+        if self.export_progress.is_some() {
+            return;
+        }
+
+        let messages = match self.export_messages(cx) {
+            Ok(messages) => std::sync::Arc::new(messages),
+            Err(e) => {
+                self.status_msg = format!("❌ Export: {}", e).into();
+                cx.notify();
+                return;
+            }
+        };
+        // A plugin gets a CSV or JSON export and converts it afterwards
+        let plugin = self
+            .export_plugin
+            .and_then(|index| self.app_config.export_plugins.get(index).cloned());
+        if let Some(Err(e)) = plugin.as_ref().map(ExportPlugin::validate) {
+            self.status_msg = format!("❌ Export: {}", e).into();
+            cx.notify();
+            return;
+        }
+        let format = plugin
+            .as_ref()
+            .map_or(self.export_format, |plugin| plugin.input);
+        let (label, extension) = match &plugin {
+            Some(plugin) => (plugin.name.clone(), plugin.extension.clone()),
+            None => (format.label().to_string(), format.extension().to_string()),
+        };
+        let decoded = self.export_decoded;
+        // Plugins expect the fixed CSV layout, so the CSV options apply to plain exports only
+        let csv_options = (format == ExportFormat::Csv && plugin.is_none())
+            .then_some(self.app_config.preferences.csv_export);
+        let dbc_channels = std::sync::Arc::new(self.dbc_channels.clone());
+        let ldf_channels = std::sync::Arc::new(self.ldf_channels.clone());
+        let time_offsets = self.time_offsets.clone();
+        let start_time = self.start_time;
+        let manifest_sources = self.manifest_sources();
+        let total = messages.len();
+
+        self.export_progress = Some((0, total));
+        cx.notify();
+
+        cx.spawn(async move |this, cx| {
+            let Some(file) = rfd::AsyncFileDialog::new()
+                .add_filter(label.as_str(), &[extension.as_str()])
+                .set_file_name(format!("export.{}", extension))
+                .save_file()
+                .await
+            else {
+                let _ = this.update(cx, |app, cx| {
+                    app.export_progress = None;
+                    cx.notify();
+                });
+                return;
+            };
+            let output = file.path().to_path_buf();
+            let path = plugin.as_ref().map_or_else(
+                || output.clone(),
+                |plugin| plugin.intermediate_path(&output),
+            );
+
+            // Binary formats get the same chunked pipeline, with records instead of text
+            let sqlite = format == ExportFormat::Sqlite;
+            let blf_export = (format == ExportFormat::Blf)
+                .then(|| std::sync::Arc::new(crate::handlers::BlfExport::new(start_time)));
+            let parquet_export = (format == ExportFormat::Parquet)
+                .then(|| std::sync::Arc::new(crate::handlers::ParquetExport::new(decoded)));
+            // MDF channel groups and CSV signal columns need a pass over the messages first
+            let mf4_export = match format {
+                ExportFormat::Mf4 => Some(std::sync::Arc::new(
+                    cx.background_executor()
+                        .spawn({
+                            let messages = messages.clone();
+                            let dbc_channels = dbc_channels.clone();
+                            let ldf_channels = ldf_channels.clone();
+                            async move {
+                                crate::handlers::Mf4Export::new(
+                                    &messages,
+                                    &dbc_channels,
+                                    &ldf_channels,
+                                    start_time,
+                                )
+                            }
+                        })
+                        .await,
+                )),
+                _ => None,
+            };
+            let csv_export = match csv_options {
+                Some(options) => Some(std::sync::Arc::new(
+                    cx.background_executor()
+                        .spawn({
+                            let messages = messages.clone();
+                            let dbc_channels = dbc_channels.clone();
+                            let ldf_channels = ldf_channels.clone();
+                            async move {
+                                crate::handlers::CsvExport::new(
+                                    options,
+                                    decoded,
+                                    &messages,
+                                    &dbc_channels,
+                                    &ldf_channels,
+                                    start_time,
+                                )
+                            }
+                        })
+                        .await,
+                )),
+                None => None,
+            };
+            let header = match &csv_export {
+                Some(export) => export.header(),
+                None => crate::handlers::format_header(format, decoded, start_time),
+            };
+            let mut result = cx
+                .background_executor()
+                .spawn({
+                    let path = path.clone();
+                    let mf4_export = mf4_export.clone();
+                    let blf_export = blf_export.clone();
+                    let parquet_export = parquet_export.clone();
+                    async move {
+                        if let Some(export) = mf4_export {
+                            export.create(&path).map_err(std::io::Error::other)
+                        } else if let Some(export) = blf_export {
+                            export.create(&path).map_err(std::io::Error::other)
+                        } else if let Some(export) = parquet_export {
+                            export.create(&path).map_err(std::io::Error::other)
+                        } else if sqlite {
+                            crate::handlers::create_sqlite_export(&path)
+                                .map_err(std::io::Error::other)
+                        } else {
+                            std::fs::write(&path, header)
+                        }
+                    }
+                })
+                .await;
+
+            let mut rows = 0;
+            let mut start = 0;
+            while result.is_ok() && start < total {
+                let end = (start + EXPORT_CHUNK_SIZE).min(total);
+                let (written, chunk_result) = cx
+                    .background_executor()
+                    .spawn({
+                        let path = path.clone();
+                        let messages = messages.clone();
+                        let dbc_channels = dbc_channels.clone();
+                        let ldf_channels = ldf_channels.clone();
+                        let time_offsets = time_offsets.clone();
+                        let csv_export = csv_export.clone();
+                        let mf4_export = mf4_export.clone();
+                        let blf_export = blf_export.clone();
+                        let parquet_export = parquet_export.clone();
+                        async move {
+                            if let Some(export) = blf_export {
+                                return match export.append(&messages[start..end], &time_offsets) {
+                                    Ok(written) => (written, Ok(())),
+                                    Err(e) => (0, Err(std::io::Error::other(e))),
+                                };
+                            }
+                            if let Some(export) = parquet_export {
+                                return match export.append(
+                                    &messages[start..end],
+                                    &dbc_channels,
+                                    &ldf_channels,
+                                    &time_offsets,
+                                ) {
+                                    Ok(written) => (written, Ok(())),
+                                    Err(e) => (0, Err(std::io::Error::other(e))),
+                                };
+                            }
+                            if let Some(export) = mf4_export {
+                                return match export.append(
+                                    &path,
+                                    &messages[start..end],
+                                    &dbc_channels,
+                                    &ldf_channels,
+                                    &time_offsets,
+                                ) {
+                                    Ok(written) => (written, Ok(())),
+                                    Err(e) => (0, Err(std::io::Error::other(e))),
+                                };
+                            }
+                            if sqlite {
+                                return match crate::handlers::append_sqlite_rows(
+                                    &path,
+                                    &messages[start..end],
+                                    &dbc_channels,
+                                    &ldf_channels,
+                                    &time_offsets,
+                                ) {
+                                    Ok(written) => (written, Ok(())),
+                                    Err(e) => (0, Err(std::io::Error::other(e))),
+                                };
+                            }
+                            let (text, written) = match &csv_export {
+                                Some(export) => export.rows(
+                                    &messages[start..end],
+                                    &dbc_channels,
+                                    &ldf_channels,
+                                    &time_offsets,
+                                ),
+                                None if format == ExportFormat::Candump => {
+                                    crate::handlers::format_candump_rows(
+                                        &messages[start..end],
+                                        &time_offsets,
+                                        start_time,
+                                    )
+                                }
+                                None => crate::handlers::format_rows(
+                                    format,
+                                    decoded,
+                                    &messages[start..end],
+                                    &dbc_channels,
+                                    &ldf_channels,
+                                    &time_offsets,
+                                    rows == 0,
+                                ),
+                            };
+                            let result = std::fs::OpenOptions::new()
+                                .append(true)
+                                .open(&path)
+                                .and_then(|mut file| {
+                                    std::io::Write::write_all(&mut file, text.as_bytes())
+                                });
+                            (written, result)
+                        }
+                    })
+                    .await;
+                rows += written;
+                result = chunk_result;
+                start = end;
+
+                let _ = this.update(cx, |app, cx| {
+                    app.export_progress = Some((start, total));
+                    cx.notify();
+                });
+            }
+
+            // Objects the BLF writer has no layout for
+            let mut left_out = None;
+            if let (Ok(()), Some(export)) = (&result, blf_export) {
+                result = match cx
+                    .background_executor()
+                    .spawn(async move { export.finish() })
+                    .await
+                {
+                    Ok(skipped) => {
+                        left_out = skipped;
+                        Ok(())
+                    }
+                    Err(e) => Err(std::io::Error::other(e)),
+                };
+            } else if let (Ok(()), Some(export)) = (&result, parquet_export) {
+                result = cx
+                    .background_executor()
+                    .spawn(async move { export.finish() })
+                    .await
+                    .map_err(std::io::Error::other);
+            } else if result.is_ok() {
+                let footer = crate::handlers::format_footer(format, rows > 0);
+                result = cx
+                    .background_executor()
+                    .spawn({
+                        let path = path.clone();
+                        let mf4_export = mf4_export.clone();
+                        async move {
+                            if let Some(export) = mf4_export {
+                                return export.finish(&path).map_err(std::io::Error::other);
+                            }
+                            if sqlite {
+                                return crate::handlers::finish_sqlite_export(&path)
+                                    .map_err(std::io::Error::other);
+                            }
+                            std::fs::OpenOptions::new()
+                                .append(true)
+                                .open(&path)
+                                .and_then(|mut file| {
+                                    std::io::Write::write_all(&mut file, footer.as_bytes())
+                                })
+                        }
+                    })
+                    .await;
+            }
+
+            if let Some(plugin) = plugin {
+                // The intermediate file goes away whether or not the export got this far
+                let convert = result.is_ok();
+                let converted = cx
+                    .background_executor()
+                    .spawn({
+                        let path = path.clone();
+                        let output = output.clone();
+                        async move {
+                            let converted = match convert {
+                                true => plugin.run(&path, &output),
+                                false => Ok(()),
+                            };
+                            let _ = std::fs::remove_file(&path);
+                            converted
+                        }
+                    })
+                    .await;
+                if let Err(e) = converted {
+                    result = Err(std::io::Error::other(e));
+                }
+            }
+
+            let with_manifest = manifest_sources.is_some();
+            if let (Ok(()), Some(sources)) = (&result, manifest_sources) {
+                result = cx
+                    .background_executor()
+                    .spawn({
+                        let output = output.clone();
+                        async move {
+                            crate::handlers::write_export_manifest(&output, &sources)
+                                .map(|_| ())
+                                .map_err(std::io::Error::other)
+                        }
+                    })
+                    .await;
+            }
+
+            let _ = this.update(cx, |app, cx| {
+                app.export_progress = None;
+                match result {
+                    Ok(()) => {
+                        app.status_msg = format!(
+                            "✅ Exported {} rows to {}{}{}",
+                            rows,
+                            output.display(),
+                            if with_manifest { " with manifest" } else { "" },
+                            left_out
+                                .map(|types| format!(", left out {}", types))
+                                .unwrap_or_default()
+                        )
+                        .into();
+                        app.close_modal(AppModal::Export, cx);
+                    }
+                    Err(e) => {
+                        app.status_msg = format!("❌ Export failed: {}", e).into();
+                    }
+                }
+                cx.notify();
+            });
+        })
+        .detach();
+    }
+
+    pub(super) fn render_export_modal(
+        &self,
+        view: Entity<CanViewApp>,
+        on_close: impl Fn(&mut Window, &mut App) + Clone + 'static,
+        cx: &App,
+    ) -> Modal {
+        let running = self.export_progress;
+        let has_content_choice = self.export_plugin.is_some()
+            || !matches!(
+                self.export_format,
+                ExportFormat::Blf | ExportFormat::Sqlite | ExportFormat::Mf4
+            );
+
+        Modal::new("export-modal")
+            .title("Export")
+            .width(px(520.))
+            .child(Self::render_choice_row(
+                view.clone(),
+                "Format",
+                ExportFormat::ALL
+                    .map(|format| (ExportChoice::Format(format), format.label().into()))
+                    .into_iter()
+                    .chain(self.app_config.export_plugins.iter().enumerate().map(
+                        |(index, plugin)| (ExportChoice::Plugin(index), plugin.name.clone().into()),
+                    )),
+                match self.export_plugin {
+                    Some(index) => ExportChoice::Plugin(index),
+                    None => ExportChoice::Format(self.export_format),
+                },
+                |choice| match choice {
+                    ExportChoice::Format(format) => crate::handlers::is_format_supported(format),
+                    ExportChoice::Plugin(_) => true,
+                },
+                |app, choice, cx| {
+                    match choice {
+                        ExportChoice::Format(format) => {
+                            app.export_format = format;
+                            app.export_plugin = None;
+                        }
+                        ExportChoice::Plugin(index) => app.export_plugin = Some(index),
+                    }
+                    cx.notify();
+                },
+            ))
+            .child(Self::render_choice_row(
+                view.clone(),
+                "Scope",
+                ExportScope::ALL.map(|scope| (scope, scope.label().into())),
+                self.export_scope,
+                {
+                    let has_selection = !self.log_view.read(cx).selection.is_empty();
+                    move |scope| scope != ExportScope::Selected || has_selection
+                },
+                |app, scope, cx| {
+                    app.export_scope = scope;
+                    cx.notify();
+                },
+            ))
+            .when(self.export_scope == ExportScope::TimeRange, |modal| {
+                modal.child(
+                    div()
+                        .flex()
+                        .items_center()
+                        .justify_end()
+                        .gap_2()
+                        .when_some(self.export_range_start_input.as_ref(), |el, input| {
+                            el.child(div().w(px(110.)).child(Input::new(input)))
+                        })
+                        .child(div().text_xs().text_color(rgb(0x646473)).child("–"))
+                        .when_some(self.export_range_end_input.as_ref(), |el, input| {
+                            el.child(div().w(px(110.)).child(Input::new(input)))
+                        })
+                        .child(div().text_xs().text_color(rgb(0x646473)).child("seconds")),
+                )
+            })
+            // SQLite always carries both the frames and a table of decoded signals, MDF the signals
+            .when(has_content_choice, |modal| {
+                modal.child(Self::render_choice_row(
+                    view.clone(),
+                    "Content",
+                    [(false, "Raw frames".into()), (true, "Decoded signals".into())],
+                    self.export_decoded,
+                    |_| true,
+                    |app, decoded, cx| {
+                        app.export_decoded = decoded;
+                        cx.notify();
+                    },
+                ))
+            })
+            // Plugins read the fixed CSV layout, so only a plain CSV export is configurable
+            .when(
+                self.export_plugin.is_none() && self.export_format == ExportFormat::Csv,
+                |modal| {
+                    let csv = self.app_config.preferences.csv_export;
+                    modal
+                        .child(Self::render_preference_choice(
+                            view.clone(),
+                            "Delimiter",
+                            CsvDelimiter::ALL.map(|value| (value, value.label().into())),
+                            csv.delimiter,
+                            |preferences, value| preferences.csv_export.delimiter = value,
+                        ))
+                        .child(Self::render_preference_choice(
+                            view.clone(),
+                            "Time",
+                            TimestampFormat::ALL.map(|format| (format, format.label().into())),
+                            csv.time_format,
+                            |preferences, value| preferences.csv_export.time_format = value,
+                        ))
+                        .child(Self::render_preference_choice(
+                            view.clone(),
+                            "IDs",
+                            IdBase::ALL.map(|base| (base, base.label().into())),
+                            csv.id_base,
+                            |preferences, value| preferences.csv_export.id_base = value,
+                        ))
+                        .child(Self::render_preference_choice(
+                            view.clone(),
+                            "Decimal mark",
+                            DecimalSeparator::ALL.map(|mark| (mark, mark.label().into())),
+                            csv.numbers.decimal_separator,
+                            |preferences, value| {
+                                preferences.csv_export.numbers.decimal_separator = value
+                            },
+                        ))
+                        .child(Self::render_preference_choice(
+                            view.clone(),
+                            "Decimals",
+                            SignalPrecision::ALL.map(|digits| (digits, digits.label().into())),
+                            csv.numbers.precision,
+                            |preferences, value| preferences.csv_export.numbers.precision = value,
+                        ))
+                },
+            )
+            .child(
+                div()
+                    .text_xs()
+                    .text_color(rgb(0x646473))
+                    .child(match running {
+                        Some((done, total)) => format!("Exporting… {} / {} messages", done, total),
+                        None => format!(
+                            "{} messages loaded, {} after filters, {} selected. The destination is chosen next.",
+                            self.log_view.read(cx).messages.len(),
+                            self.filtered_indices(cx).len(),
+                            self.log_view.read(cx).selection.len()
+                        ),
+                    }),
+            )
+            .footer(
+                div()
+                    .flex()
+                    .gap_2()
+                    .child(
+                        div()
+                            .id("export-cancel")
+                            .px_3()
+                            .py_1()
+                            .text_xs()
+                            .text_color(rgb(0x9ca3af))
+                            .rounded(px(3.))
+                            .cursor_pointer()
+                            .hover(|style| style.bg(rgb(0x252f3a)))
+                            .on_mouse_down(gpui::MouseButton::Left, {
+                                let on_close = on_close.clone();
+                                move |_event, window, cx| {
+                                    cx.stop_propagation();
+                                    on_close(window, cx);
+                                }
+                            })
+                            .child("Cancel"),
+                    )
+                    .child(
+                        div()
+                            .id("export-start")
+                            .px_3()
+                            .py_1()
+                            .text_xs()
+                            .rounded(px(3.))
+                            .when(running.is_some(), |el| el.bg(rgb(0x1a1a1a)).text_color(rgb(0x646473)))
+                            .when(running.is_none(), |el| {
+                                el.bg(rgb(0x1e3a8a))
+                                    .text_color(rgb(0xffffff))
+                                    .cursor_pointer()
+                                    .hover(|style| style.bg(rgb(0x1d4ed8)))
+                            })
+                            .on_mouse_down(gpui::MouseButton::Left, move |_event, _window, cx| {
+                                cx.stop_propagation();
+                                view.update(cx, |app, cx| app.start_export(cx));
+                            })
+                            .child("Export…"),
+                    ),
+            )
+            .on_close(on_close)
+    }
+}
@@ -0,0 +1,187 @@
+//! Filters of the log view and the filter files they are saved to
+
+use super::state::{CanViewApp, LogViewState};
+use crate::handlers::{ActiveFilter, FilterColumn, FilterState};
+use gpui::{prelude::*, *};
+use std::path::PathBuf;
+use std::rc::Rc;
+
+impl CanViewApp {
+    /// Narrow the log view by one filter, replacing any filter on the same column
+    fn apply_filter(&mut self, filter: ActiveFilter, cx: &mut Context<Self>) {
+        self.filters.update(cx, |filters, _| filters.apply(filter));
+        self.log_view.update(cx, |log, _| match filter {
+            ActiveFilter::Id(id) => {
+                log.id_filter_text = id.to_string().into();
+                log.show_id_filter_input = false;
+            }
+            ActiveFilter::Channel(channel) => {
+                log.channel_filter_text = channel.to_string().into();
+                log.show_channel_filter_input = false;
+            }
+            ActiveFilter::Type(_) => log.show_type_filter_input = false,
+            ActiveFilter::File(_) => {}
+        });
+        self.filters_changed(cx);
+    }
+
+    /// Drop one filter, as clicked in the breadcrumb bar
+    pub(super) fn remove_filter(&mut self, filter: ActiveFilter, cx: &mut Context<Self>) {
+        self.filters.update(cx, |filters, _| filters.remove(filter));
+        self.log_view.update(cx, |log, _| match filter {
+            ActiveFilter::Id(_) => log.id_filter_text = "".into(),
+            ActiveFilter::Channel(_) => log.channel_filter_text = "".into(),
+            ActiveFilter::Type(_) | ActiveFilter::File(_) => {}
+        });
+        self.filters_changed(cx);
+    }
+
+    /// Filter on the value shown in one cell of a log row (double-click)
+    pub(super) fn quick_filter(
+        &mut self,
+        message_index: usize,
+        column: FilterColumn,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(filter) = self
+            .log_view
+            .read(cx)
+            .messages
+            .get(message_index)
+            .and_then(|msg| ActiveFilter::from_cell(msg, column))
+        {
+            self.apply_filter(filter, cx);
+        }
+    }
+
+    /// Indices (into `messages`) of the rows passing the current filters
+    pub(super) fn filtered_indices(&self, cx: &App) -> Rc<Vec<usize>> {
+        self.log_view
+            .read(cx)
+            .filtered_indices(self.filters.read(cx))
+    }
+
+    /// Change the log view and the filters in one step, as the filter widgets do
+    pub(super) fn update_log_view_and_filters<R>(
+        &self,
+        cx: &mut Context<Self>,
+        update: impl FnOnce(&mut LogViewState, &mut FilterState) -> R,
+    ) -> R {
+        let filters = self.filters.clone();
+        self.log_view.update(cx, |log, cx| {
+            filters.update(cx, |filters, _| update(log, filters))
+        })
+    }
+
+    /// The loaded log as a filter file refers to it
+    fn log_reference(&self, cx: &App) -> crate::handlers::LogReference {
+        let log = self.log_view.read(cx);
+        crate::handlers::LogReference::new(&log.log_sources, log.messages.len(), self.start_time)
+    }
+
+    /// Save the filters and the rows on screen to a filter file a teammate can apply
+    pub(super) fn save_filter_file(&mut self, cx: &mut Context<Self>) {
+        let rows = self.filtered_indices(cx);
+        let log = self.log_view.read(cx);
+        let visible = log.message_list.metrics().visible_range();
+        let on_screen = rows
+            .get(visible.start..visible.end.min(rows.len()))
+            .unwrap_or_default();
+        // The fixed view shows no time span
+        let time_range_ns = on_screen
+            .first()
+            .zip(on_screen.last())
+            .filter(|_| !log.fixed_view)
+            .map(|(&first, &last)| {
+                (
+                    log.messages[first].timestamp(),
+                    log.messages[last].timestamp(),
+                )
+            });
+        let file = crate::handlers::FilterFile::new(
+            self.log_reference(cx),
+            self.filters.read(cx),
+            &log.log_sources,
+            time_range_ns,
+        );
+        let name = self
+            .log_path
+            .as_ref()
+            .and_then(|path| path.file_stem())
+            .map_or_else(
+                || "view".to_string(),
+                |stem| stem.to_string_lossy().into_owned(),
+            );
+        cx.spawn(async move |this, cx| {
+            let Some(picked) = rfd::AsyncFileDialog::new()
+                .add_filter("View filters", &[crate::handlers::FILTER_FILE_EXTENSION])
+                .set_file_name(format!(
+                    "{}.{}",
+                    name,
+                    crate::handlers::FILTER_FILE_EXTENSION
+                ))
+                .save_file()
+                .await
+            else {
+                return;
+            };
+            let path = picked.path().to_path_buf();
+            let result = crate::handlers::write_filter_file(&path, &file);
+            let _ = this.update(cx, |app, cx| {
+                app.status_msg = match result {
+                    Ok(()) => format!("🔗 Saved the view filters to {}", path.display()).into(),
+                    Err(e) => format!("❌ Save failed: {}", e).into(),
+                };
+                cx.notify();
+            });
+        })
+        .detach();
+    }
+
+    /// Apply a filter file onto the loaded log and scroll to the time it showed
+    pub(super) fn apply_filter_file(&mut self, path: PathBuf, cx: &mut Context<Self>) {
+        if self.log_view.read(cx).messages.is_empty() {
+            self.status_msg = "❌ Open the log the filters were made on first".into();
+            cx.notify();
+            return;
+        }
+        let file = match crate::handlers::read_filter_file(&path) {
+            Ok(file) => file,
+            Err(e) => {
+                self.status_msg = format!("❌ {}", e).into();
+                cx.notify();
+                return;
+            }
+        };
+        self.update_log_view_and_filters(cx, |log, filters| {
+            *filters = file.filter_state(&log.log_sources);
+            log.id_filter_text = filters
+                .id
+                .map_or_else(String::new, |id| id.to_string())
+                .into();
+            log.channel_filter_text = filters
+                .channel
+                .map_or_else(String::new, |channel| channel.to_string())
+                .into();
+        });
+        let log = self.log_view.read(cx);
+        if let Some((start, _)) = file.time_range_ns.filter(|_| !log.fixed_view) {
+            let rows = log.filtered_indices(self.filters.read(cx));
+            let position = rows.partition_point(|&row| log.messages[row].timestamp() < start);
+            log.message_list.scroll_to(position);
+        }
+        let differences = file.log.differences(&self.log_reference(cx));
+        self.status_msg = if differences.is_empty() {
+            format!("🔗 Applied the view filters of {}", path.display()).into()
+        } else {
+            format!(
+                "⚠ Applied the view filters of {}, made on another log ({})",
+                path.display(),
+                differences.join(", ")
+            )
+            .into()
+        };
+        self.filters_changed(cx);
+        cx.notify();
+    }
+}
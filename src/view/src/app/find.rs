@@ -0,0 +1,118 @@
+//! The find dialog: jumping to the next row that fires a trigger rule
+
+use super::state::{AppModal, AppView, CanViewApp};
+use crate::handlers::TimeDisplay;
+use crate::ui::components::Modal;
+use gpui::{prelude::*, *};
+use gpui_component::input::Input;
+
+impl CanViewApp {
+    /// Open the find dialog with the condition of the last search
+    pub fn open_find_dialog(&mut self, cx: &mut Context<Self>) {
+        self.find_input = None;
+        self.open_modal(AppModal::Find, cx);
+    }
+
+    /// Select and scroll to the first visible row after the selection where the
+    /// find condition becomes true
+    ///
+    /// Searching continues from the selection, so repeated presses step through
+    /// every onset; the dialog stays open for that.
+    fn find_next(&mut self, cx: &mut Context<Self>) {
+        let log = self.log_view.read(cx);
+        let text = self
+            .find_input
+            .as_ref()
+            .map(|input| input.read(cx).value().to_string())
+            .unwrap_or_default();
+        let rule = match crate::handlers::parse_trigger_rules(&text) {
+            Ok(rules) if rules.len() == 1 => rules.into_iter().next().unwrap(),
+            Ok(_) => {
+                self.status_msg = "❌ Enter exactly one condition".into();
+                cx.notify();
+                return;
+            }
+            Err(e) => {
+                self.status_msg = format!("❌ {}", e).into();
+                cx.notify();
+                return;
+            }
+        };
+
+        let after = log.selection.indices().next();
+        let rows = self.filtered_indices(cx);
+        let found = crate::handlers::trigger_onsets(
+            &log.messages,
+            rule.clone(),
+            &self.dbc_channels,
+            &self.ldf_channels,
+        )
+        .filter(|event| after.is_none_or(|after| event.index > after))
+        .find_map(|event| {
+            rows.binary_search(&event.index)
+                .ok()
+                .map(|position| (event, position))
+        });
+
+        self.status_msg = match found {
+            Some((event, position)) => {
+                self.select_row_at(&rows, position, cx);
+                self.current_view = AppView::LogView;
+                let time = TimeDisplay::new(self.start_time, &self.app_config.preferences);
+                format!(
+                    "🔍 '{}' at #{} ({})",
+                    rule,
+                    crate::handlers::sequence_number(event.index),
+                    time.format(event.timestamp_ns)
+                )
+                .into()
+            }
+            None => format!("🔍 '{}' not found after the selection", rule).into(),
+        };
+        self.find_rule = Some(rule);
+        cx.notify();
+    }
+
+    pub(super) fn render_find_modal(
+        &self,
+        view: Entity<CanViewApp>,
+        on_close: impl Fn(&mut Window, &mut App) + Clone + 'static,
+    ) -> Modal {
+        let muted = |text: &'static str| div().text_xs().text_color(rgb(0x646473)).child(text);
+
+        Modal::new("find-modal")
+            .title("Find")
+            .width(px(520.))
+            .child(muted(
+                "Finds the next row after the selection where a condition becomes true: \
+                 byte3 bit2 == 1, byte0 != 0x20, optionally for one ID as in \
+                 0x123 byte3 bit2 == 1 or 2:0x123 ... Trigger rules such as \
+                 signal 0x100 Speed > 50 work as well.",
+            ))
+            .when_some(self.find_input.as_ref(), |modal, input| {
+                modal.child(Input::new(input))
+            })
+            .child(muted(
+                "A condition that stays true is found once, on the frame where it starts \
+                 to hold; only rows passing the current filters are considered.",
+            ))
+            .footer(
+                div()
+                    .flex()
+                    .gap_2()
+                    .child(Self::render_modal_button(
+                        "find-close",
+                        "Close",
+                        false,
+                        on_close.clone(),
+                    ))
+                    .child(Self::render_modal_button("find-next", "Find next", true, {
+                        let view = view.clone();
+                        move |_window, cx| {
+                            view.update(cx, |app, cx| app.find_next(cx));
+                        }
+                    })),
+            )
+            .on_close(on_close)
+    }
+}
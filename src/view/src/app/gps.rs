@@ -0,0 +1,287 @@
+//! The GPS view: the track of the log's position fixes and its GPX export
+
+use super::state::CanViewApp;
+use crate::handlers::ExportFrame;
+use crate::ui::components::VirtualList;
+use gpui::{prelude::*, *};
+
+impl CanViewApp {
+    /// Re-read the GPS track from the log unless a GPS file was imported
+    pub(super) fn refresh_log_gps(&mut self, cx: &App) {
+        if self.gps_file.is_none() {
+            let messages = &self.log_view.read(cx).messages;
+            self.gps_fixes = crate::handlers::fixes_from_log(messages, &self.time_offsets);
+        }
+    }
+
+    /// Load the GPS track from an NMEA or CSV file recorded alongside the log
+    fn import_gps_file(&mut self, cx: &mut Context<Self>) {
+        let start_time = self.start_time;
+        cx.spawn(async move |this, cx| {
+            let Some(file) = rfd::AsyncFileDialog::new()
+                .add_filter("GPS Files", &["nmea", "txt", "log", "csv"])
+                .pick_file()
+                .await
+            else {
+                return;
+            };
+            let path = file.path().to_path_buf();
+
+            let result = cx
+                .background_executor()
+                .spawn({
+                    let path = path.clone();
+                    async move { crate::handlers::read_gps_file(&path, start_time) }
+                })
+                .await;
+
+            let _ = this.update(cx, |app, cx| {
+                match result {
+                    Ok(fixes) => {
+                        app.status_msg = format!("🛰 Imported {} GPS fixes", fixes.len()).into();
+                        app.gps_fixes = fixes;
+                        app.gps_file = Some(path);
+                    }
+                    Err(e) => {
+                        app.status_msg = format!("❌ GPS import: {}", e).into();
+                    }
+                }
+                cx.notify();
+            });
+        })
+        .detach();
+    }
+
+    /// Write the GPS track as GPX, with the selected rows as waypoints
+    fn export_gpx(&mut self, cx: &mut Context<Self>) {
+        if self.gps_fixes.is_empty() {
+            self.status_msg = "❌ No GPS track to export".into();
+            cx.notify();
+            return;
+        }
+        let log = self.log_view.read(cx);
+        let events: Vec<(u64, String)> = log
+            .selection
+            .indices()
+            .filter_map(|index| log.messages.get(index))
+            .filter_map(|msg| ExportFrame::with_offsets(msg, &self.time_offsets))
+            .map(|frame| {
+                let id = crate::handlers::hex_frame_id(frame.kind, frame.id);
+                let name = format!("{} CH{} {}", frame.kind, frame.channel, id);
+                (frame.timestamp_ns, name)
+            })
+            .collect();
+        let gpx = crate::handlers::format_gpx(&self.gps_fixes, &events, self.start_time);
+
+        cx.spawn(async move |this, cx| {
+            let Some(file) = rfd::AsyncFileDialog::new()
+                .add_filter("GPX", &["gpx"])
+                .set_file_name("track.gpx")
+                .save_file()
+                .await
+            else {
+                return;
+            };
+            let path = file.path().to_path_buf();
+            let result = cx
+                .background_executor()
+                .spawn({
+                    let path = path.clone();
+                    async move { std::fs::write(&path, gpx) }
+                })
+                .await;
+
+            let _ = this.update(cx, |app, cx| {
+                app.status_msg = match result {
+                    Ok(()) => format!("✅ Exported GPX track to {}", path.display()).into(),
+                    Err(e) => format!("❌ GPX export failed: {}", e).into(),
+                };
+                cx.notify();
+            });
+        })
+        .detach();
+    }
+
+    pub(super) fn render_gps_view(&self, view: Entity<CanViewApp>) -> impl IntoElement {
+        let fixes = self.gps_fixes.clone();
+        let source = match &self.gps_file {
+            Some(path) => path
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            None => "GPS events in the log".to_string(),
+        };
+        let columns: [(&str, f32); 7] = [
+            ("#", 60.),
+            ("TIME (s)", 120.),
+            ("LATITUDE", 130.),
+            ("LONGITUDE", 130.),
+            ("ALT (m)", 90.),
+            ("SPEED (km/h)", 110.),
+            ("COURSE", 90.),
+        ];
+        let toolbar_button =
+            |id: &'static str,
+             label: &'static str,
+             on_click: fn(&mut CanViewApp, &mut Context<CanViewApp>)| {
+                let view = view.clone();
+                div()
+                    .id(id)
+                    .px_3()
+                    .py_1()
+                    .text_xs()
+                    .rounded(px(3.))
+                    .cursor_pointer()
+                    .text_color(rgb(0x9ca3af))
+                    .bg(rgb(0x1a1a1a))
+                    .hover(|style| style.bg(rgb(0x252f3a)).text_color(rgb(0xcdd6f4)))
+                    .on_mouse_down(MouseButton::Left, move |_event, _window, cx| {
+                        view.update(cx, |app, cx| on_click(app, cx));
+                    })
+                    .child(label)
+            };
+
+        div()
+            .size_full()
+            .flex()
+            .flex_col()
+            .child(
+                // Toolbar
+                div()
+                    .h(px(36.))
+                    .px_3()
+                    .flex()
+                    .items_center()
+                    .justify_between()
+                    .border_b_1()
+                    .border_color(rgb(0x2a2a2a))
+                    .child(div().text_xs().text_color(rgb(0x9ca3af)).child(format!(
+                        "{} fixes · {}",
+                        fixes.len(),
+                        source
+                    )))
+                    .child(
+                        div()
+                            .flex()
+                            .gap_2()
+                            .when(self.gps_file.is_some(), |el| {
+                                el.child(toolbar_button("gps-use-log", "Use log GPS", |app, cx| {
+                                    app.gps_file = None;
+                                    app.refresh_log_gps(cx);
+                                    cx.notify();
+                                }))
+                            })
+                            .child(toolbar_button(
+                                "gps-import",
+                                "Import NMEA/CSV…",
+                                |app, cx| app.import_gps_file(cx),
+                            ))
+                            .child(toolbar_button("gps-export", "Export GPX…", |app, cx| {
+                                app.export_gpx(cx)
+                            })),
+                    ),
+            )
+            .child(
+                // Column headers
+                div()
+                    .h(px(28.))
+                    .flex()
+                    .items_center()
+                    .bg(rgb(0x141414))
+                    .border_b_1()
+                    .border_color(rgb(0x2a2a2a))
+                    .text_xs()
+                    .font_weight(FontWeight::MEDIUM)
+                    .text_color(rgb(0x646473))
+                    .children(columns.iter().map(|(title, width)| {
+                        div().w(px(*width)).px_3().flex_shrink_0().child(*title)
+                    })),
+            )
+            .child(
+                div()
+                    .flex_1()
+                    .flex()
+                    .flex_col()
+                    .when(fixes.is_empty(), |parent| {
+                        parent.child(
+                            div()
+                                .flex_1()
+                                .flex()
+                                .items_center()
+                                .justify_center()
+                                .text_sm()
+                                .text_color(rgb(0x6b7280))
+                                .child(
+                                    "No GPS data. The log has no GPS events; \
+                                     import an NMEA or CSV file.",
+                                ),
+                        )
+                    })
+                    .when(!fixes.is_empty(), |parent| {
+                        let count = fixes.len();
+                        let row_height = self.gps_list.row_height();
+                        parent.child(
+                            VirtualList::new(
+                                "gps-list",
+                                &self.gps_list,
+                                count,
+                                move |range: std::ops::Range<usize>,
+                                      _window: &mut gpui::Window,
+                                      _cx: &mut gpui::App| {
+                                    range
+                                        .filter_map(|index| {
+                                            fixes.get(index).map(|fix| (index, fix))
+                                        })
+                                        .map(|(index, fix)| {
+                                            let optional =
+                                                |value: Option<f64>, precision: usize| {
+                                                    value
+                                                        .map(|value| {
+                                                            format!("{:.*}", precision, value)
+                                                        })
+                                                        .unwrap_or_else(|| "–".to_string())
+                                                };
+                                            let cells = [
+                                                (index + 1).to_string(),
+                                                format!(
+                                                    "{:.6}",
+                                                    fix.timestamp_ns as f64 / 1_000_000_000.0
+                                                ),
+                                                format!("{:.7}", fix.latitude),
+                                                format!("{:.7}", fix.longitude),
+                                                optional(fix.altitude, 1),
+                                                optional(fix.speed_kmh, 1),
+                                                optional(fix.course, 1),
+                                            ];
+                                            div()
+                                                .flex()
+                                                .w_full()
+                                                .h(px(row_height))
+                                                .items_center()
+                                                .bg(rgb(0x181818))
+                                                .border_b_1()
+                                                .border_color(rgb(0x2a2a2a))
+                                                .text_xs()
+                                                .text_color(rgb(0xd1d5db))
+                                                .children(cells.into_iter().zip(columns).map(
+                                                    |(text, (_, width))| {
+                                                        div()
+                                                            .w(px(width))
+                                                            .px_3()
+                                                            .flex_shrink_0()
+                                                            .whitespace_nowrap()
+                                                            .overflow_hidden()
+                                                            .child(text)
+                                                    },
+                                                ))
+                                                .into_any_element()
+                                        })
+                                        .collect::<Vec<_>>()
+                                },
+                            )
+                            .build(),
+                        )
+                    }),
+            )
+    }
+}
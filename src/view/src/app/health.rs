@@ -0,0 +1,202 @@
+//! The health dialog: a quick triage of the loaded log
+
+use super::state::{AppModal, CanViewApp};
+use crate::handlers::{MAX_LISTED_GAPS, TimeDisplay};
+use crate::ui::components::Modal;
+use gpui::{prelude::*, *};
+
+impl CanViewApp {
+    /// Show the health summary of the loaded log again
+    pub fn open_health_dialog(&mut self, cx: &mut Context<Self>) {
+        if self.log_health.is_none() {
+            self.status_msg = "❌ Open a BLF file first".into();
+            cx.notify();
+            return;
+        }
+        self.open_modal(AppModal::Health, cx);
+    }
+
+    pub(super) fn render_health_modal(
+        &self,
+        view: Entity<CanViewApp>,
+        on_close: impl Fn(&mut Window, &mut App) + Clone + 'static,
+    ) -> Modal {
+        let muted = |text: String| div().text_xs().text_color(rgb(0x646473)).child(text);
+        let line = |label: &'static str, value: String, warn: bool| {
+            div()
+                .flex()
+                .justify_between()
+                .text_sm()
+                .child(div().text_color(rgb(0x9ca3af)).child(label))
+                .child(
+                    div()
+                        .text_color(if warn { rgb(0xf59e0b) } else { rgb(0xcdd6f4) })
+                        .child(value),
+                )
+        };
+        let seconds = |ns: u64| format!("{:.3} s", ns as f64 / 1_000_000_000.0);
+        let time = TimeDisplay::new(self.start_time, &self.app_config.preferences);
+        let gap_ms = self.app_config.preferences.health_gap_ms;
+        let Some(health) = self.log_health.as_ref() else {
+            return Modal::new("health-modal")
+                .title("Log health")
+                .on_close(on_close);
+        };
+
+        let channels = health.channels.iter().map(|(channel, counts)| {
+            let cells = [
+                channel.to_string(),
+                counts.frames.to_string(),
+                counts.errors.to_string(),
+            ];
+            div()
+                .flex()
+                .text_xs()
+                .text_color(if counts.errors > 0 {
+                    rgb(0xf59e0b)
+                } else {
+                    rgb(0xd1d5db)
+                })
+                .children(
+                    cells
+                        .into_iter()
+                        .zip([60., 120., 100.])
+                        .map(|(text, width)| div().w(px(width)).flex_shrink_0().child(text)),
+                )
+        });
+        let gaps = health
+            .gaps
+            .iter()
+            .take(MAX_LISTED_GAPS)
+            .enumerate()
+            .map(|(n, gap)| {
+                let index = gap.index;
+                div()
+                    .id(("health-gap", n))
+                    .flex()
+                    .gap_3()
+                    .px_1()
+                    .text_xs()
+                    .text_color(rgb(0xd1d5db))
+                    .rounded(px(3.))
+                    .cursor_pointer()
+                    .hover(|style| style.bg(rgb(0x252f3a)))
+                    .on_mouse_down(MouseButton::Left, {
+                        let view = view.clone();
+                        move |_event, _window, cx| {
+                            view.update(cx, |app, cx| app.jump_to_row(index, cx));
+                        }
+                    })
+                    .child(
+                        div()
+                            .w(px(80.))
+                            .text_color(rgb(0x6b7280))
+                            .child(format!("#{}", crate::handlers::sequence_number(index))),
+                    )
+                    .child(div().w(px(160.)).child(time.format(gap.start_ns)))
+                    .child(seconds(gap.duration_ns()))
+            });
+        let unknown = health
+            .unknown_types
+            .iter()
+            .map(|(object_type, count)| format!("type {} ×{}", object_type, count))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        Modal::new("health-modal")
+            .title("Log health")
+            .width(px(480.))
+            .child(line(
+                "Duration",
+                format!(
+                    "{} · {} objects",
+                    seconds(health.duration_ns()),
+                    health.objects
+                ),
+                false,
+            ))
+            .child(
+                div()
+                    .flex()
+                    .flex_col()
+                    .child(
+                        div()
+                            .flex()
+                            .text_xs()
+                            .font_weight(FontWeight::MEDIUM)
+                            .text_color(rgb(0x646473))
+                            .children([("CH", 60.), ("FRAMES", 120.), ("ERRORS", 100.)].map(
+                                |(title, width)| div().w(px(width)).flex_shrink_0().child(title),
+                            )),
+                    )
+                    .children(channels),
+            )
+            .child(line(
+                "Error frames",
+                health.error_frames.to_string(),
+                health.error_frames > 0,
+            ))
+            .child(line(
+                "Timestamps",
+                if health.timestamps.is_clean() {
+                    "in order".to_string()
+                } else {
+                    format!(
+                        "{} out of order, {} duplicates",
+                        health.timestamps.out_of_order, health.timestamps.duplicates
+                    )
+                },
+                !health.timestamps.is_clean(),
+            ))
+            .child(line(
+                "Unknown objects",
+                if unknown.is_empty() {
+                    "none".to_string()
+                } else {
+                    unknown
+                },
+                !health.unknown_types.is_empty(),
+            ))
+            .child(line(
+                "Gaps",
+                format!("{} over {} ms", health.gaps.len(), gap_ms),
+                !health.gaps.is_empty(),
+            ))
+            .child(div().flex().flex_col().children(gaps))
+            .when(health.gaps.len() > MAX_LISTED_GAPS, |modal| {
+                modal.child(muted(format!(
+                    "Showing the {} longest gaps; click one to jump to it.",
+                    MAX_LISTED_GAPS
+                )))
+            })
+            .footer(
+                div()
+                    .flex()
+                    .gap_2()
+                    .child(Self::render_modal_button(
+                        "health-mute",
+                        "Don't show on open",
+                        false,
+                        {
+                            let view = view.clone();
+                            let on_close = on_close.clone();
+                            move |window, cx| {
+                                view.update(cx, |app, cx| {
+                                    let mut preferences = app.app_config.preferences.clone();
+                                    preferences.show_health_summary = false;
+                                    app.set_preferences(preferences, cx);
+                                });
+                                on_close(window, cx);
+                            }
+                        },
+                    ))
+                    .child(Self::render_modal_button(
+                        "health-close",
+                        "Close",
+                        true,
+                        on_close.clone(),
+                    )),
+            )
+            .on_close(on_close)
+    }
+}
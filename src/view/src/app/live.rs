@@ -0,0 +1,1071 @@
+//! Live sources: simulation, replay, capture, MQTT and the watched folder
+//!
+//! A simulation, replay or capture pushes its frames into the log on a
+//! timer, [`LIVE_SOURCE_TICK`], while the MQTT publisher forwards them to a
+//! broker and the folder watcher queues the logs written to its folder.
+
+use super::events::AppEvent;
+use super::state::{AppModal, CanViewApp};
+use crate::handlers::{
+    AnalysisKind, CaptureDevice, CaptureSource, LiveSource, LiveSourceKind, LoadMode, Replayer,
+    Simulator,
+};
+use crate::ui::components::Modal;
+use blf::LogObject;
+use gpui::{prelude::*, *};
+use gpui_component::input::{Input, InputState};
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// Interval at which a simulation or replay pushes due frames into the log
+const LIVE_SOURCE_TICK: Duration = Duration::from_millis(50);
+
+impl CanViewApp {
+    /// Append messages received in live mode to the log and the capture history
+    pub fn push_live_messages(&mut self, batch: Vec<LogObject>, cx: &mut Context<Self>) {
+        self.capture.update(cx, |capture, _| {
+            let Some(recorder) = &mut capture.recorder else {
+                return;
+            };
+            let skipped_types = recorder.skipped().len();
+            if let Err(e) = batch
+                .iter()
+                .try_for_each(|msg| recorder.write_object(msg).map(|_| ()))
+            {
+                self.status_msg = format!("❌ Recording stopped: {}", e).into();
+                capture.recorder = None;
+            } else if recorder.skipped().len() > skipped_types {
+                let skipped = crate::handlers::skipped_objects(recorder.skipped());
+                self.status_msg = format!(
+                    "⚠️ BLF cannot hold every type; the recording leaves out {}",
+                    skipped.unwrap_or_default()
+                )
+                .into();
+            }
+        });
+        for msg in &batch {
+            self.sequence_checker
+                .feed(msg, &self.dbc_channels, &self.ldf_channels);
+        }
+        if let Some(publisher) = &mut self.mqtt_publisher {
+            publisher.publish(&batch, &self.dbc_channels, &self.ldf_channels);
+            if let Some(e) = publisher.error() {
+                self.status_msg = format!("❌ MQTT stopped: {}", e).into();
+                self.mqtt_publisher = None;
+            }
+        }
+        self.capture.update(cx, |capture, _| {
+            capture.buffer.extend(batch.iter().cloned())
+        });
+        self.log_view.update(cx, |log, _| log.extend(batch));
+        cx.notify();
+    }
+
+    /// Start recording live traffic to disk, or stop the running recording
+    pub(super) fn toggle_recording(&mut self, cx: &mut Context<Self>) {
+        if let Some(recorder) = self
+            .capture
+            .update(cx, |capture, _| capture.recorder.take())
+        {
+            self.status_msg = match crate::handlers::finish_recording(recorder) {
+                Ok(summary) => format!("⏺ Recorded {}", summary).into(),
+                Err(e) => format!("❌ Recording failed: {}", e).into(),
+            };
+            cx.notify();
+            return;
+        }
+
+        cx.spawn(async move |this, cx| {
+            let Some(file) = rfd::AsyncFileDialog::new()
+                .add_filter("BLF", &["blf"])
+                .set_file_name("recording.blf")
+                .save_file()
+                .await
+            else {
+                return;
+            };
+            let path = file.path().to_path_buf();
+            let _ = this.update(cx, |app, cx| {
+                let recorder = crate::handlers::start_recording(
+                    &path,
+                    app.start_time,
+                    &app.app_config.preferences,
+                );
+                app.status_msg =
+                    format!("⏺ Recording to {}", recorder.file_path(1).display()).into();
+                app.capture
+                    .update(cx, |capture, _| capture.recorder = Some(recorder));
+                cx.notify();
+            });
+        })
+        .detach();
+    }
+
+    /// Save the live history, including what arrived before the button was pressed
+    pub(super) fn save_capture_history(&mut self, cx: &mut Context<Self>) {
+        let buffer = &self.capture.read(cx).buffer;
+        if buffer.is_empty() {
+            self.status_msg = "❌ Nothing captured yet".into();
+            cx.notify();
+            return;
+        }
+        let messages = buffer.snapshot();
+        let start_time = self.start_time;
+        let manifest_sources = self.manifest_sources();
+
+        cx.spawn(async move |this, cx| {
+            let Some(file) = rfd::AsyncFileDialog::new()
+                .add_filter("BLF", &["blf"])
+                .set_file_name("capture.blf")
+                .save_file()
+                .await
+            else {
+                return;
+            };
+            let path = file.path().to_path_buf();
+            let result = cx
+                .background_executor()
+                .spawn({
+                    let path = path.clone();
+                    async move {
+                        let count = crate::handlers::save_capture(&path, &messages, start_time)?;
+                        if let Some(sources) = manifest_sources {
+                            crate::handlers::write_export_manifest(&path, &sources)?;
+                        }
+                        Ok::<_, String>(count)
+                    }
+                })
+                .await;
+            let _ = this.update(cx, |app, cx| {
+                app.status_msg = match result {
+                    Ok(count) => {
+                        format!("💾 Saved {} messages to {}", count, path.display()).into()
+                    }
+                    Err(e) => format!("❌ Save failed: {}", e).into(),
+                };
+                cx.notify();
+            });
+        })
+        .detach();
+    }
+
+    /// Open the simulation dialog with the frames of the last run
+    pub fn open_simulation_dialog(&mut self, cx: &mut Context<Self>) {
+        self.simulation_input = None;
+        self.open_modal(AppModal::Simulation, cx);
+    }
+
+    /// Start the simulated bus with the frames entered in the dialog
+    fn start_simulation(&mut self, cx: &mut Context<Self>) {
+        let text = self
+            .simulation_input
+            .as_ref()
+            .map(|input| input.read(cx).value().to_string())
+            .unwrap_or_default();
+        let frames = match crate::handlers::parse_simulation(&text) {
+            Ok(frames) if !frames.is_empty() => frames,
+            Ok(_) => {
+                self.status_msg = "❌ Enter at least one simulated frame".into();
+                cx.notify();
+                return;
+            }
+            Err(e) => {
+                self.status_msg = format!("❌ {}", e).into();
+                cx.notify();
+                return;
+            }
+        };
+
+        self.status_msg = format!("📡 Simulating {} frames", frames.len()).into();
+        self.start_live_source(LiveSource::Simulation(Simulator::new(frames.clone())), cx);
+        self.simulation_frames = frames;
+        self.close_modal(AppModal::Simulation, cx);
+    }
+
+    /// Open the replay dialog with the overrides currently applied
+    pub fn open_replay_dialog(&mut self, cx: &mut Context<Self>) {
+        self.signal_overrides_input = None;
+        self.open_modal(AppModal::Replay, cx);
+    }
+
+    /// Take the overrides from the dialog; a running replay uses them from the next batch
+    fn apply_signal_overrides(&mut self, cx: &mut Context<Self>) -> bool {
+        let text = self
+            .signal_overrides_input
+            .as_ref()
+            .map(|input| input.read(cx).value().to_string())
+            .unwrap_or_default();
+        match crate::handlers::parse_signal_overrides(&text) {
+            Ok(overrides) => {
+                self.status_msg = format!("🔁 {} signal overrides active", overrides.len()).into();
+                self.signal_overrides = overrides;
+                cx.notify();
+                true
+            }
+            Err(e) => {
+                self.status_msg = format!("❌ {}", e).into();
+                cx.notify();
+                false
+            }
+        }
+    }
+
+    /// Replay the CAN frames of the loaded log through live mode, with the overrides applied
+    fn start_replay(&mut self, cx: &mut Context<Self>) {
+        if !self.apply_signal_overrides(cx) {
+            return;
+        }
+        let replayer = Replayer::new(&self.log_view.read(cx).messages);
+        if replayer.is_empty() {
+            self.status_msg = "❌ No CAN frames to replay".into();
+            cx.notify();
+            return;
+        }
+
+        self.status_msg = format!(
+            "🔁 Replaying {} frames with {} overrides",
+            replayer.len(),
+            self.signal_overrides.len()
+        )
+        .into();
+        self.start_live_source(LiveSource::Replay(replayer), cx);
+        self.close_modal(AppModal::Replay, cx);
+    }
+
+    /// Open the capture dialog with the interface of the last capture
+    pub fn open_capture_dialog(&mut self, cx: &mut Context<Self>) {
+        self.capture_interface_input = None;
+        self.open_modal(AppModal::Capture, cx);
+    }
+
+    /// Capture the SocketCAN interface entered in the dialog; `canN` goes on channel N+1
+    fn start_capture(&mut self, cx: &mut Context<Self>) {
+        let interface = self
+            .capture_interface_input
+            .as_ref()
+            .map(|input| input.read(cx).value().trim().to_string())
+            .unwrap_or_default();
+        if interface.is_empty() {
+            self.status_msg = "❌ Enter a CAN interface, e.g. can0".into();
+            cx.notify();
+            return;
+        }
+        let channel = crate::handlers::interface_channel(&interface).unwrap_or(1);
+        let source = match crate::handlers::SocketCanSource::open(&interface, channel) {
+            Ok(source) => source,
+            Err(e) => {
+                self.status_msg = format!("❌ {}", e).into();
+                cx.notify();
+                return;
+            }
+        };
+
+        self.status_msg = format!("🔌 Capturing {} on channel {}", interface, channel).into();
+        self.start_live_source(LiveSource::Capture(vec![Box::new(source)]), cx);
+        self.capture
+            .update(cx, |capture, _| capture.interface = interface);
+        self.close_modal(AppModal::Capture, cx);
+    }
+
+    /// Capture every channel mapping that has a capture device, each on its own channel
+    fn start_configured_capture(&mut self, cx: &mut Context<Self>) {
+        let mut sources: Vec<Box<dyn CaptureSource>> = Vec::new();
+        for mapping in &self.app_config.mappings {
+            let Some(device) = &mapping.capture else {
+                continue;
+            };
+            match device.open(mapping.channel_id, mapping.channel_type) {
+                Ok(source) => sources.push(source),
+                Err(e) => {
+                    self.status_msg = format!("❌ {}", e).into();
+                    cx.notify();
+                    return;
+                }
+            }
+        }
+        if sources.is_empty() {
+            self.status_msg =
+                "❌ No channel has a capture device; pick one in Configuration".into();
+            cx.notify();
+            return;
+        }
+
+        let names: Vec<String> = sources.iter().map(|source| source.name()).collect();
+        self.status_msg = format!("🔌 Capturing {}", names.join(", ")).into();
+        self.start_live_source(LiveSource::Capture(sources), cx);
+        self.close_modal(AppModal::Capture, cx);
+    }
+
+    /// Open or close the device picker of the mapping at `index`, listing the connected devices
+    pub(super) fn toggle_capture_device_menu(&mut self, index: usize, cx: &mut Context<Self>) {
+        if self.capture_device_menu == Some(index) {
+            self.capture_device_menu = None;
+        } else {
+            let devices = crate::handlers::capture_devices();
+            self.capture
+                .update(cx, |capture, _| capture.devices = devices);
+            self.capture_device_menu = Some(index);
+        }
+        cx.notify();
+    }
+
+    /// Capture the mapping at `index` from `device`, or not at all
+    fn set_capture_device(
+        &mut self,
+        index: usize,
+        device: Option<CaptureDevice>,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(mapping) = self.app_config.mappings.get_mut(index) {
+            mapping.capture = device;
+            self.save_config(cx);
+        }
+        self.capture_device_menu = None;
+        cx.notify();
+    }
+
+    /// Clear the log, switch to streaming mode and drive `source` until it is stopped
+    fn start_live_source(&mut self, source: LiveSource, cx: &mut Context<Self>) {
+        self.log_view.update(cx, |log, _| log.clear());
+        self.sequence_checker.reset();
+        self.cancel_analysis(AnalysisKind::Correlation);
+        self.cancel_analysis(AnalysisKind::SequenceCheck);
+        self.markers.clear();
+        self.markers_unsaved = false;
+        self.log_health = None;
+        self.minimap = None;
+        self.state_timelines.clear();
+        self.trigger_events.clear();
+        self.start_time = Some(chrono::Local::now().naive_local());
+        self.log_path = None;
+        self.load_mode = LoadMode::Full;
+        self.capture.update(cx, |capture, _| {
+            capture.buffer.clear();
+            capture.feed = Some(source.spawn(LIVE_SOURCE_TICK));
+        });
+        self.is_streaming_mode = true;
+        cx.emit(AppEvent::FileLoaded);
+
+        cx.spawn(async move |this, cx| {
+            loop {
+                cx.background_executor().timer(LIVE_SOURCE_TICK).await;
+                let running = this
+                    .update(cx, |app, cx| app.tick_live_source(cx))
+                    .unwrap_or(false);
+                if !running {
+                    break;
+                }
+            }
+        })
+        .detach();
+    }
+
+    /// Push the frames the feed produced; false once the source is stopped or finished
+    fn tick_live_source(&mut self, cx: &mut Context<Self>) -> bool {
+        let polled = self.capture.update(cx, |capture, _| {
+            let feed = capture.feed.as_mut()?;
+            Some((feed.poll(), feed.is_finished()))
+        });
+        let Some((mut batch, finished)) = polled else {
+            return false;
+        };
+        if !self.signal_overrides.is_empty() {
+            for msg in &mut batch {
+                crate::handlers::apply_signal_overrides(
+                    msg,
+                    &self.signal_overrides,
+                    &self.dbc_channels,
+                );
+            }
+        }
+        self.push_live_messages(batch, cx);
+        if finished {
+            self.stop_live_source(cx);
+        }
+        !finished
+    }
+
+    pub(super) fn is_simulating(&self, cx: &App) -> bool {
+        self.capture.read(cx).source_kind() == Some(LiveSourceKind::Simulation)
+    }
+
+    pub(super) fn is_replaying(&self, cx: &App) -> bool {
+        self.capture.read(cx).source_kind() == Some(LiveSourceKind::Replay)
+    }
+
+    pub(super) fn is_capturing(&self, cx: &App) -> bool {
+        self.capture.read(cx).source_kind() == Some(LiveSourceKind::Capture)
+    }
+
+    /// Stop the simulation, replay or capture; a running recording is closed with it
+    pub(super) fn stop_live_source(&mut self, cx: &mut Context<Self>) {
+        let stopped = self.capture.update(cx, |capture, _| {
+            let feed = capture.feed.take()?;
+            Some((feed, capture.recorder.take()))
+        });
+        let Some((feed, recorder)) = stopped else {
+            return;
+        };
+        self.is_streaming_mode = false;
+        let recording = recorder.map(crate::handlers::finish_recording);
+        self.status_msg = match recording {
+            Some(Ok(summary)) => format!("{} stopped, recorded {}", feed.name(), summary).into(),
+            Some(Err(e)) => format!("❌ Recording failed: {}", e).into(),
+            None => format!("{} stopped", feed.name()).into(),
+        };
+    }
+
+    /// Open the MQTT dialog with the saved settings
+    pub fn open_mqtt_dialog(&mut self, cx: &mut Context<Self>) {
+        self.mqtt_broker_input = None;
+        self.mqtt_topic_input = None;
+        self.mqtt_signals_input = None;
+        self.open_modal(AppModal::Mqtt, cx);
+    }
+
+    /// Save the settings from the dialog and publish live signals with them
+    fn start_mqtt(&mut self, cx: &mut Context<Self>) {
+        let [broker, topic_prefix, signals] = [
+            &self.mqtt_broker_input,
+            &self.mqtt_topic_input,
+            &self.mqtt_signals_input,
+        ]
+        .map(|input| {
+            input
+                .as_ref()
+                .map(|input| input.read(cx).value().trim().to_string())
+                .unwrap_or_default()
+        });
+        let signals = match crate::handlers::parse_signal_filters(&signals) {
+            Ok(signals) if !signals.is_empty() => signals,
+            Ok(_) => {
+                self.status_msg = "❌ Select at least one signal to publish".into();
+                cx.notify();
+                return;
+            }
+            Err(e) => {
+                self.status_msg = format!("❌ {}", e).into();
+                cx.notify();
+                return;
+            }
+        };
+        let defaults = crate::handlers::MqttSettings::default();
+        let settings = crate::handlers::MqttSettings {
+            broker: if broker.is_empty() {
+                defaults.broker
+            } else {
+                broker
+            },
+            topic_prefix: if topic_prefix.is_empty() {
+                defaults.topic_prefix
+            } else {
+                topic_prefix
+            },
+            signals,
+        };
+
+        self.status_msg = format!("📤 Publishing to {}/…", settings.broker).into();
+        self.mqtt_publisher = Some(crate::handlers::MqttPublisher::start(settings.clone()));
+        self.app_config.mqtt = settings;
+        self.save_config(cx);
+        self.close_modal(AppModal::Mqtt, cx);
+    }
+
+    fn stop_mqtt(&mut self, cx: &mut Context<Self>) {
+        if let Some(publisher) = self.mqtt_publisher.take() {
+            self.status_msg = match publisher.dropped() {
+                0 => format!("📤 MQTT stopped after {} values", publisher.published()),
+                dropped => format!(
+                    "📤 MQTT stopped after {} values ({} dropped, broker too slow)",
+                    publisher.published(),
+                    dropped
+                ),
+            }
+            .into();
+        }
+        cx.notify();
+    }
+
+    /// Open the watch folder dialog seeded with the saved folder
+    pub fn open_watch_dialog(&mut self, cx: &mut Context<Self>) {
+        self.watch_folder_input = None;
+        self.open_modal(AppModal::WatchFolder, cx);
+    }
+
+    /// Watch the folder from the dialog and save it with the configuration
+    fn start_watch(&mut self, cx: &mut Context<Self>) {
+        let folder = self
+            .watch_folder_input
+            .as_ref()
+            .map(|input| PathBuf::from(input.read(cx).value().trim()))
+            .unwrap_or_default();
+        if folder.as_os_str().is_empty() {
+            self.status_msg = "❌ Enter the folder to watch".into();
+            cx.notify();
+            return;
+        }
+        let watcher = match crate::handlers::FolderWatcher::new(folder.clone()) {
+            Ok(watcher) => watcher,
+            Err(e) => {
+                self.status_msg = format!("❌ {}", e).into();
+                cx.notify();
+                return;
+            }
+        };
+
+        let was_watching = self.folder_watcher.is_some();
+        self.status_msg = format!("📥 Watching {} for new logs", folder.display()).into();
+        self.folder_watcher = Some(watcher);
+        self.app_config.watch.folder = Some(folder);
+        self.save_config(cx);
+        self.close_modal(AppModal::WatchFolder, cx);
+        // A running loop picks up the new watcher
+        if !was_watching {
+            self.spawn_watch_loop(cx);
+        }
+    }
+
+    fn stop_watch(&mut self, cx: &mut Context<Self>) {
+        if let Some(watcher) = self.folder_watcher.take() {
+            self.status_msg = format!("📥 Stopped watching {}", watcher.folder().display()).into();
+        }
+        cx.notify();
+    }
+
+    /// Scan the watched folder every `WATCH_INTERVAL` until watching stops
+    fn spawn_watch_loop(&mut self, cx: &mut Context<Self>) {
+        cx.spawn(async move |this, cx| {
+            loop {
+                cx.background_executor()
+                    .timer(crate::handlers::WATCH_INTERVAL)
+                    .await;
+                let folder = this.update(cx, |app, _| {
+                    app.folder_watcher
+                        .as_ref()
+                        .map(|watcher| watcher.folder().to_path_buf())
+                });
+                let Ok(Some(folder)) = folder else {
+                    break;
+                };
+                let scan = cx
+                    .background_executor()
+                    .spawn({
+                        let folder = folder.clone();
+                        async move { crate::handlers::scan_folder(&folder) }
+                    })
+                    .await;
+                let _ = this.update(cx, |app, cx| app.apply_watch_scan(&folder, scan, cx));
+            }
+        })
+        .detach();
+    }
+
+    /// Feed a scan to the watcher and open or queue the logs that became complete
+    fn apply_watch_scan(
+        &mut self,
+        folder: &std::path::Path,
+        scan: Result<Vec<(PathBuf, crate::handlers::FileStamp)>, String>,
+        cx: &mut Context<Self>,
+    ) {
+        // Watching stopped or moved to another folder during the scan
+        let Some(watcher) = self
+            .folder_watcher
+            .as_mut()
+            .filter(|watcher| watcher.folder() == folder)
+        else {
+            return;
+        };
+        // An unreachable share keeps being watched, it may come back
+        let mut ready = match scan {
+            Ok(scan) => watcher.update(scan),
+            Err(e) => {
+                self.status_msg = format!("❌ Watch folder: {}", e).into();
+                cx.notify();
+                return;
+            }
+        };
+        if ready.is_empty() {
+            return;
+        }
+
+        // Only the newest log is opened, and never over a live session
+        let load = self.app_config.watch.action == crate::handlers::WatchAction::Load
+            && self.capture.read(cx).feed.is_none();
+        let newest = if load { ready.pop() } else { None };
+        self.watch_queue.extend(ready);
+        match newest {
+            Some(path) => self.load_log_file(path, cx),
+            None => {
+                self.status_msg = format!(
+                    "📥 {} new logs waiting in the queue",
+                    self.watch_queue.len()
+                )
+                .into();
+                cx.notify();
+            }
+        }
+    }
+
+    /// Open the queued log at `index`, taking it off the queue
+    fn open_queued_log(&mut self, index: usize, cx: &mut Context<Self>) {
+        if index < self.watch_queue.len() {
+            let path = self.watch_queue.remove(index);
+            self.ask_to_open_log(path, cx);
+        }
+    }
+
+    pub(super) fn render_simulation_modal(
+        &self,
+        view: Entity<CanViewApp>,
+        on_close: impl Fn(&mut Window, &mut App) + Clone + 'static,
+        cx: &App,
+    ) -> Modal {
+        let muted = |text: &'static str| div().text_xs().text_color(rgb(0x646473)).child(text);
+        let running = self.is_simulating(cx);
+
+        Modal::new("simulation-modal")
+            .title("Simulated bus")
+            .width(px(520.))
+            .child(muted(
+                "Frames separated by ';': [channel:]id period [ramp duration], \
+                 e.g. 0x100 10ms ramp 5s; 2:0x200 100ms.",
+            ))
+            .when_some(self.simulation_input.as_ref(), |modal, input| {
+                modal.child(Input::new(input))
+            })
+            .child(muted(
+                "Bytes 0-1 carry a 16-bit ramp and byte 7 a rolling counter. \
+                 Starting clears the log and switches to streaming mode.",
+            ))
+            .footer(
+                div()
+                    .flex()
+                    .gap_2()
+                    .child(Self::render_modal_button(
+                        "simulation-cancel",
+                        "Cancel",
+                        false,
+                        on_close.clone(),
+                    ))
+                    .when(running, |el| {
+                        el.child(Self::render_modal_button(
+                            "simulation-stop",
+                            "Stop",
+                            false,
+                            {
+                                let view = view.clone();
+                                move |_window, cx| {
+                                    view.update(cx, |app, cx| {
+                                        app.stop_live_source(cx);
+                                        app.close_modal(AppModal::Simulation, cx);
+                                    });
+                                }
+                            },
+                        ))
+                    })
+                    .child(Self::render_modal_button(
+                        "simulation-start",
+                        if running { "Restart" } else { "Start" },
+                        true,
+                        {
+                            let view = view.clone();
+                            move |_window, cx| {
+                                view.update(cx, |app, cx| app.start_simulation(cx));
+                            }
+                        },
+                    )),
+            )
+            .on_close(on_close)
+    }
+
+    pub(super) fn render_replay_modal(
+        &self,
+        view: Entity<CanViewApp>,
+        on_close: impl Fn(&mut Window, &mut App) + Clone + 'static,
+        cx: &App,
+    ) -> Modal {
+        let muted = |text: &'static str| div().text_xs().text_color(rgb(0x646473)).child(text);
+        let running = self.is_replaying(cx);
+
+        Modal::new("replay-modal")
+            .title("Replay")
+            .width(px(520.))
+            .child(muted(
+                "Sends the CAN frames of the loaded log again at their original pace. \
+                 Signal overrides separated by ';': 0x100 Speed = 50, \
+                 0x100 Rpm ramp 800..3000 10s.",
+            ))
+            .when_some(self.signal_overrides_input.as_ref(), |modal, input| {
+                modal.child(Input::new(input))
+            })
+            .child(muted(
+                "Overridden signals are re-encoded through the DBC of the frame's channel. \
+                 While the replay runs, Apply changes them for the frames still to come.",
+            ))
+            .footer(
+                div()
+                    .flex()
+                    .gap_2()
+                    .child(Self::render_modal_button(
+                        "replay-cancel",
+                        "Cancel",
+                        false,
+                        on_close.clone(),
+                    ))
+                    .when(running, |el| {
+                        el.child(Self::render_modal_button("replay-stop", "Stop", false, {
+                            let view = view.clone();
+                            move |_window, cx| {
+                                view.update(cx, |app, cx| {
+                                    app.stop_live_source(cx);
+                                    app.close_modal(AppModal::Replay, cx);
+                                });
+                            }
+                        }))
+                        .child(Self::render_modal_button(
+                            "replay-apply",
+                            "Apply",
+                            true,
+                            {
+                                let view = view.clone();
+                                move |_window, cx| {
+                                    view.update(cx, |app, cx| {
+                                        if app.apply_signal_overrides(cx) {
+                                            app.close_modal(AppModal::Replay, cx);
+                                        }
+                                    });
+                                }
+                            },
+                        ))
+                    })
+                    .when(!running, |el| {
+                        el.child(Self::render_modal_button("replay-start", "Start", true, {
+                            let view = view.clone();
+                            move |_window, cx| {
+                                view.update(cx, |app, cx| app.start_replay(cx));
+                            }
+                        }))
+                    }),
+            )
+            .on_close(on_close)
+    }
+
+    /// Devices the mapping at `index` can be captured from, plus "None"
+    pub(super) fn render_capture_device_picker(
+        &self,
+        index: usize,
+        view: Entity<CanViewApp>,
+        cx: &App,
+    ) -> Div {
+        let current = self
+            .app_config
+            .mappings
+            .get(index)
+            .and_then(|mapping| mapping.capture.clone());
+        let devices = self.capture.read(cx).devices.iter().cloned().map(Some);
+        let choices = std::iter::once(None).chain(devices);
+
+        div()
+            .flex()
+            .flex_wrap()
+            .gap_1()
+            .children(choices.enumerate().map(|(choice, device)| {
+                let selected = device == current;
+                div()
+                    .id(("capture-device-choice", choice))
+                    .px_2()
+                    .py_1()
+                    .rounded(px(4.))
+                    .cursor_pointer()
+                    .bg(rgb(0x2a2a2a))
+                    .hover(|style| style.bg(rgb(0x4b5563)))
+                    .text_xs()
+                    .text_color(if selected {
+                        rgb(0x22c55e)
+                    } else {
+                        rgb(0xd1d5db)
+                    })
+                    .child(
+                        device
+                            .as_ref()
+                            .map_or("None".to_string(), CaptureDevice::label),
+                    )
+                    .on_mouse_down(MouseButton::Left, {
+                        let view = view.clone();
+                        move |_event, _window, cx| {
+                            let device = device.clone();
+                            view.update(cx, |app, cx| app.set_capture_device(index, device, cx));
+                        }
+                    })
+            }))
+            .when(self.capture.read(cx).devices.is_empty(), |el| {
+                el.child(
+                    div()
+                        .text_xs()
+                        .text_color(rgb(0x646473))
+                        .child("No SocketCAN interface or Vector channel found"),
+                )
+            })
+    }
+
+    pub(super) fn render_capture_modal(
+        &self,
+        view: Entity<CanViewApp>,
+        on_close: impl Fn(&mut Window, &mut App) + Clone + 'static,
+    ) -> Modal {
+        let muted = |text: String| div().text_xs().text_color(rgb(0x646473)).child(text);
+        let interfaces = crate::handlers::socketcan_interfaces();
+
+        let configured: Vec<String> = self
+            .app_config
+            .mappings
+            .iter()
+            .filter_map(|mapping| {
+                let device = mapping.capture.as_ref()?;
+                Some(format!("CH{} ← {}", mapping.channel_id, device.label()))
+            })
+            .collect();
+
+        Modal::new("capture-modal")
+            .title("Capture")
+            .width(px(520.))
+            .child(muted(
+                "Receives the frames of a SocketCAN interface (Linux). canN goes on channel \
+                 N+1 and is decoded with that channel's DBC."
+                    .to_string(),
+            ))
+            .when_some(self.capture_interface_input.as_ref(), |modal, input| {
+                modal.child(Input::new(input))
+            })
+            .child(muted(if interfaces.is_empty() {
+                "No CAN interface found".to_string()
+            } else {
+                format!("Interfaces: {}", interfaces.join(", "))
+            }))
+            .child(muted(if configured.is_empty() {
+                "Configured channels: none yet, pick a SocketCAN interface or Vector channel \
+                 per mapping in Configuration."
+                    .to_string()
+            } else {
+                format!("Configured channels: {}", configured.join(", "))
+            }))
+            .child(muted(
+                "Starting clears the log and switches to streaming mode.".to_string(),
+            ))
+            .footer(
+                div()
+                    .flex()
+                    .gap_2()
+                    .child(Self::render_modal_button(
+                        "capture-cancel",
+                        "Cancel",
+                        false,
+                        on_close.clone(),
+                    ))
+                    .child(Self::render_modal_button(
+                        "capture-configured",
+                        "Configured channels",
+                        false,
+                        {
+                            let view = view.clone();
+                            move |_window, cx| {
+                                view.update(cx, |app, cx| app.start_configured_capture(cx));
+                            }
+                        },
+                    ))
+                    .child(Self::render_modal_button("capture-start", "Start", true, {
+                        let view = view.clone();
+                        move |_window, cx| {
+                            view.update(cx, |app, cx| app.start_capture(cx));
+                        }
+                    })),
+            )
+            .on_close(on_close)
+    }
+
+    pub(super) fn render_mqtt_modal(
+        &self,
+        view: Entity<CanViewApp>,
+        on_close: impl Fn(&mut Window, &mut App) + Clone + 'static,
+    ) -> Modal {
+        let muted = |text: &'static str| div().text_xs().text_color(rgb(0x646473)).child(text);
+        let field = |label: &'static str, input: Option<&Entity<InputState>>| {
+            div()
+                .flex()
+                .flex_col()
+                .gap_1()
+                .child(muted(label))
+                .when_some(input, |el, input| el.child(Input::new(input)))
+        };
+        let running = self.mqtt_publisher.is_some();
+
+        Modal::new("mqtt-modal")
+            .title("MQTT")
+            .width(px(520.))
+            .child(muted(
+                "Publishes the selected signals while live mode runs, one topic per signal: \
+                 <prefix>/<channel>/<id>/<signal>, with a JSON payload holding the value, \
+                 raw value, unit and time.",
+            ))
+            .child(field("Broker (host:port)", self.mqtt_broker_input.as_ref()))
+            .child(field("Topic prefix", self.mqtt_topic_input.as_ref()))
+            .child(field(
+                "Signals separated by ';': 0x100 EngineSpeed, 2:0x200 (every signal of the frame)",
+                self.mqtt_signals_input.as_ref(),
+            ))
+            .when_some(self.mqtt_publisher.as_ref(), |modal, publisher| {
+                modal.child(
+                    div()
+                        .text_xs()
+                        .text_color(rgb(0x22c55e))
+                        .child(format!("📤 {} values published", publisher.published())),
+                )
+            })
+            .footer(
+                div()
+                    .flex()
+                    .gap_2()
+                    .child(Self::render_modal_button(
+                        "mqtt-cancel",
+                        "Cancel",
+                        false,
+                        on_close.clone(),
+                    ))
+                    .when(running, |el| {
+                        el.child(Self::render_modal_button("mqtt-stop", "Stop", false, {
+                            let view = view.clone();
+                            move |_window, cx| {
+                                view.update(cx, |app, cx| {
+                                    app.stop_mqtt(cx);
+                                    app.close_modal(AppModal::Mqtt, cx);
+                                });
+                            }
+                        }))
+                    })
+                    .child(Self::render_modal_button(
+                        "mqtt-start",
+                        if running { "Restart" } else { "Start" },
+                        true,
+                        {
+                            let view = view.clone();
+                            move |_window, cx| {
+                                view.update(cx, |app, cx| app.start_mqtt(cx));
+                            }
+                        },
+                    )),
+            )
+            .on_close(on_close)
+    }
+
+    pub(super) fn render_watch_modal(
+        &self,
+        view: Entity<CanViewApp>,
+        on_close: impl Fn(&mut Window, &mut App) + Clone + 'static,
+    ) -> Modal {
+        let muted = |text: &'static str| div().text_xs().text_color(rgb(0x646473)).child(text);
+        let watching = self.folder_watcher.is_some();
+
+        Modal::new("watch-modal")
+            .title("Watch folder")
+            .width(px(520.))
+            .child(muted(
+                "New BLF, .blf.gz and .zip logs appearing in the folder are picked up once \
+                 the logger has finished writing them. Logs already there are left alone.",
+            ))
+            .when_some(self.watch_folder_input.as_ref(), |modal, input| {
+                modal.child(Input::new(input))
+            })
+            .child(Self::render_choice_row(
+                view.clone(),
+                "New logs",
+                crate::handlers::WatchAction::ALL.map(|action| (action, action.label().into())),
+                self.app_config.watch.action,
+                |_| true,
+                |app, action, cx| {
+                    app.app_config.watch.action = action;
+                    app.save_config(cx);
+                    cx.notify();
+                },
+            ))
+            .when(!self.watch_queue.is_empty(), |modal| {
+                modal.child(muted("Queued logs, click to open:")).child(
+                    div().flex().flex_col().gap_1().children(
+                        self.watch_queue.iter().enumerate().map(|(index, path)| {
+                            let name = path
+                                .file_name()
+                                .map(|name| name.to_string_lossy().into_owned())
+                                .unwrap_or_default();
+                            div()
+                                .id(("watch-queued", index))
+                                .px_1()
+                                .text_xs()
+                                .text_color(rgb(0xd1d5db))
+                                .rounded(px(3.))
+                                .cursor_pointer()
+                                .hover(|style| style.bg(rgb(0x252f3a)))
+                                .on_mouse_down(MouseButton::Left, {
+                                    let view = view.clone();
+                                    move |_event, _window, cx| {
+                                        view.update(cx, |app, cx| {
+                                            app.open_queued_log(index, cx);
+                                            app.close_modal(AppModal::WatchFolder, cx);
+                                        });
+                                    }
+                                })
+                                .child(name)
+                        }),
+                    ),
+                )
+            })
+            .footer(
+                div()
+                    .flex()
+                    .gap_2()
+                    .child(Self::render_modal_button(
+                        "watch-cancel",
+                        "Cancel",
+                        false,
+                        on_close.clone(),
+                    ))
+                    .when(!self.watch_queue.is_empty(), |el| {
+                        el.child(Self::render_modal_button(
+                            "watch-clear",
+                            "Clear queue",
+                            false,
+                            {
+                                let view = view.clone();
+                                move |_window, cx| {
+                                    view.update(cx, |app, cx| {
+                                        app.watch_queue.clear();
+                                        cx.notify();
+                                    });
+                                }
+                            },
+                        ))
+                    })
+                    .when(watching, |el| {
+                        el.child(Self::render_modal_button("watch-stop", "Stop", false, {
+                            let view = view.clone();
+                            move |_window, cx| {
+                                view.update(cx, |app, cx| {
+                                    app.stop_watch(cx);
+                                    app.close_modal(AppModal::WatchFolder, cx);
+                                });
+                            }
+                        }))
+                    })
+                    .child(Self::render_modal_button(
+                        "watch-start",
+                        if watching { "Restart" } else { "Watch" },
+                        true,
+                        {
+                            let view = view.clone();
+                            move |_window, cx| {
+                                view.update(cx, |app, cx| app.start_watch(cx));
+                            }
+                        },
+                    )),
+            )
+            .on_close(on_close)
+    }
+}
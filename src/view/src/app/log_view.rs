@@ -0,0 +1,2222 @@
+//! The Log view: the message table with its minimap, channel tree and filters
+//!
+//! Rows are rendered by a virtual list from the displayed indices, the
+//! filtered ones or the fixed view's one row per ID, and decoded lazily.
+
+use super::state::{AppView, CanViewApp};
+use crate::handlers::{
+    ActiveFilter, CheckState, DecodedSignal, ExportFrame, ExportScope, FilterColumn, IdStats,
+    LogSources, MessageKind, Minimap, TimeDisplay, TreeNode, message_strings,
+};
+use crate::models::preferences::{NumberFormat, PanelPlacement};
+use crate::rendering::calculate_column_widths;
+use crate::ui::components::{Dropdown, NumberValidation, TextTooltip, VirtualList};
+use blf::LogObject;
+use gpui::{prelude::*, *};
+use parser::dbc::DbcDatabase;
+use parser::ldf::LdfDatabase;
+use std::collections::{BTreeSet, HashMap};
+use std::rc::Rc;
+
+/// Height of the active-filter breadcrumb bar above the log header
+const FILTER_BREADCRUMB_HEIGHT: f32 = 24.0;
+
+/// Width of the CYCLE and COUNT columns of the fixed view
+const FIXED_COLUMN_WIDTH: f32 = 80.0;
+
+impl CanViewApp {
+    fn get_timestamp_string(&self, timestamp: u64) -> String {
+        TimeDisplay::new(self.start_time, &self.app_config.preferences).format(timestamp)
+    }
+
+    #[allow(dead_code)]
+    fn render_message_row(&self, msg: &LogObject, index: usize) -> impl IntoElement {
+        let (time_str, channel_id, msg_type, id_str, dlc_str, data_str, signals_str) = match msg {
+            LogObject::CanMessage(can_msg) => {
+                let timestamp = can_msg.header.object_time_stamp;
+                let time_str = self.get_timestamp_string(timestamp);
+                let actual_data_len = can_msg.data.len().min(can_msg.dlc as usize);
+                let data_hex = can_msg
+                    .data
+                    .iter()
+                    .take(actual_data_len)
+                    .map(|b| format!("{:02X}", b))
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                let numbers = self.app_config.preferences.number_format;
+                let signals = if let Some(db) = self.dbc_channels.get(&can_msg.channel) {
+                    if let Some(message) = db.messages.get(&can_msg.id) {
+                        message
+                            .active_signals(&can_msg.data)
+                            .map(|signal| {
+                                let name = &signal.name;
+                                let decoded = signal.decode(&can_msg.data);
+                                match decoded.text {
+                                    Some(text) => format!("{}={}", name, text),
+                                    None => {
+                                        let decimals = signal.decimals();
+                                        let value = numbers.format(decoded.physical, decimals);
+                                        format!("{}={}", name, value)
+                                    }
+                                }
+                            })
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    } else {
+                        String::new()
+                    }
+                } else {
+                    String::new()
+                };
+
+                (
+                    time_str,
+                    can_msg.channel,
+                    "CAN".to_string(),
+                    crate::handlers::hex_can_id(can_msg.id),
+                    actual_data_len.to_string(),
+                    data_hex,
+                    signals,
+                )
+            }
+            LogObject::LinMessage(lin_msg) => {
+                let timestamp = lin_msg.header.object_time_stamp;
+                let time_str = self.get_timestamp_string(timestamp);
+                let actual_data_len = lin_msg.data.len().min(lin_msg.dlc as usize);
+                let data_hex = lin_msg
+                    .data
+                    .iter()
+                    .take(actual_data_len)
+                    .map(|b| format!("{:02X}", b))
+                    .collect::<Vec<_>>()
+                    .join(" ");
+
+                let signals = if let Some(db) = self.ldf_channels.get(&lin_msg.channel) {
+                    // Search for the frame with the matching ID
+                    if let Some(frame) = db.frames.values().find(|f| f.id == lin_msg.id as u32) {
+                        frame
+                            .signals
+                            .iter()
+                            .filter_map(|mapping| {
+                                db.signals
+                                    .get(&mapping.signal_name)
+                                    .map(|sig| (mapping, sig))
+                            })
+                            .map(|(mapping, signal)| {
+                                let val = signal.decode(&lin_msg.data, mapping.offset);
+                                format!("{}={}", signal.name, val)
+                            })
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    } else {
+                        String::new()
+                    }
+                } else {
+                    String::new()
+                };
+
+                (
+                    time_str,
+                    lin_msg.channel,
+                    "LIN".to_string(),
+                    crate::handlers::hex_lin_id(lin_msg.id as u32),
+                    actual_data_len.to_string(),
+                    data_hex,
+                    signals,
+                )
+            }
+            _ => (
+                "Unknown".to_string(),
+                0,
+                "Other".to_string(),
+                "-".to_string(),
+                "-".to_string(),
+                "-".to_string(),
+                String::new(),
+            ),
+        };
+
+        let bg_color = if index.is_multiple_of(2) {
+            rgb(0x09090b) // Zed's dark background (zebra)
+        } else {
+            rgb(0x0c0c0e) // Zed's dark background (base)
+        };
+
+        div()
+            .flex()
+            .w_full()
+            .min_h(px(24.)) // Slightly taller for better readability
+            .bg(bg_color)
+            .border_b_1()
+            .border_color(rgb(0x2a2a2a)) // Semi-transparent border like Zed
+            .items_center()
+            .text_sm() // Slightly larger text like Zed
+            .text_color(rgb(0xcdd6f4)) // Zed's default text color
+            .hover(|style| style.bg(rgb(0x1f1f1f))) // Subtle hover like Zed
+            .cursor_pointer()
+            .child(
+                div()
+                    .w(px(100.))
+                    .px_3()
+                    .py_1()
+                    .text_color(rgb(0x646473)) // Zed's muted color
+                    .child(time_str),
+            )
+            .child(
+                div()
+                    .w(px(40.))
+                    .px_2()
+                    .py_1()
+                    .text_color(rgb(0x7dcfff)) // Zed's blue
+                    .child(channel_id.to_string()),
+            )
+            .child(
+                div()
+                    .w(px(50.))
+                    .px_2()
+                    .py_1()
+                    .text_color(rgb(0xa6e3a1)) // Zed's green
+                    .child(msg_type),
+            )
+            .child(
+                div()
+                    .w(px(70.))
+                    .px_2()
+                    .py_1()
+                    .text_color(rgb(0xf9e2af)) // Zed's yellow
+                    .child(id_str),
+            )
+            .child(div().w(px(40.)).px_2().py_1().child(dlc_str))
+            .child(
+                div()
+                    .w(px(150.))
+                    .px_2()
+                    .py_1()
+                    .text_color(rgb(0xb4befe)) // Zed's purple
+                    .child(data_str),
+            )
+            .child(
+                div()
+                    .flex_1()
+                    .px_2()
+                    .py_1()
+                    .text_color(rgb(0x9399b2)) // Zed's comment color
+                    .child(signals_str),
+            )
+    }
+
+    /// Decode the rows that were shown raw while the list was busy
+    fn decode_queued_rows(&mut self, cx: &mut Context<Self>) {
+        let log = self.log_view.read(cx);
+        let Some((generation, rows)) = log.row_details.borrow_mut().take_queue() else {
+            return;
+        };
+        let frames: Vec<(usize, LogObject)> = rows
+            .into_iter()
+            .filter_map(|index| Some((index, log.messages.get(index)?.clone())))
+            .collect();
+        let dbc_channels = self.dbc_channels.clone();
+        let ldf_channels = self.ldf_channels.clone();
+        let numbers = log.row_details.borrow().number_format();
+        cx.spawn(async move |this, cx| {
+            let texts = cx
+                .background_executor()
+                .spawn(async move {
+                    frames
+                        .iter()
+                        .map(|(index, msg)| {
+                            let text = crate::handlers::signal_summary(
+                                msg,
+                                &dbc_channels,
+                                &ldf_channels,
+                                numbers,
+                            );
+                            (*index, text)
+                        })
+                        .collect::<Vec<_>>()
+                })
+                .await;
+            let _ = this.update(cx, |app, cx| {
+                if app
+                    .log_view
+                    .read(cx)
+                    .row_details
+                    .borrow_mut()
+                    .fill(generation, texts)
+                {
+                    cx.notify();
+                }
+                // Rows queued while this batch ran
+                app.decode_queued_rows(cx);
+            });
+        })
+        .detach();
+    }
+
+    /// IDs of the fixed view with their latest frames, in the order shown
+    pub(super) fn fixed_view_rows(&self, cx: &App) -> Vec<crate::handlers::FixedRow> {
+        self.log_view
+            .read(cx)
+            .fixed_view_rows(self.filters.read(cx))
+    }
+
+    /// Indices (into `messages`) of the log view's rows, in the order shown
+    pub(super) fn displayed_rows(&self, cx: &App) -> Rc<Vec<usize>> {
+        self.log_view.read(cx).displayed_rows(self.filters.read(cx))
+    }
+
+    /// Update the selection for a click on the row showing message `index`
+    fn click_message_row(&mut self, index: usize, modifiers: &Modifiers, cx: &mut Context<Self>) {
+        let rows = modifiers.shift.then(|| self.displayed_rows(cx));
+        self.log_view.update(cx, |log, _| {
+            if let Some(rows) = rows {
+                log.selection.extend_to(index, &rows);
+            } else if modifiers.secondary() {
+                log.selection.toggle(index);
+            } else {
+                log.selection.select_only(index);
+            }
+        });
+        self.cursor_moved(cx);
+    }
+
+    /// Open the export dialog with the scope set to the selected rows
+    pub fn export_selected(&mut self, cx: &mut Context<Self>) {
+        if self.log_view.read(cx).selection.is_empty() {
+            return;
+        }
+        self.open_export_dialog(cx);
+        self.export_scope = ExportScope::Selected;
+    }
+
+    /// Put the value history of one signal on the clipboard as CSV
+    fn copy_signal_series(
+        &mut self,
+        channel: u16,
+        id: u32,
+        signal: &DecodedSignal,
+        cx: &mut Context<Self>,
+    ) {
+        let log = self.log_view.read(cx);
+        let frames = log.id_index.positions(channel, id).iter();
+        let points = crate::handlers::signal_series(
+            frames.filter_map(|&index| log.messages.get(index)),
+            channel,
+            id,
+            &signal.name,
+            &self.dbc_channels,
+            &self.ldf_channels,
+            &self.time_offsets,
+        );
+        let text = crate::handlers::format_series_csv(&signal.name, &signal.unit, &points);
+        cx.write_to_clipboard(ClipboardItem::new_string(text));
+        self.status_msg = format!(
+            "📋 Copied {} values of {} as CSV",
+            points.len(),
+            signal.name
+        )
+        .into();
+        cx.notify();
+    }
+
+    /// Decoded signals of the selected row; clicking one copies its series
+    pub(super) fn render_signal_strip(
+        view: Entity<CanViewApp>,
+        frame: ExportFrame,
+        signals: Vec<DecodedSignal>,
+        numbers: NumberFormat,
+    ) -> impl IntoElement {
+        div()
+            .flex()
+            .flex_wrap()
+            .items_center()
+            .gap_2()
+            .px_3()
+            .py_1()
+            .bg(rgb(0x141414))
+            .border_t_1()
+            .border_color(rgb(0x2a2a2a))
+            .text_xs()
+            .child(div().text_color(rgb(0x646473)).child("Copy series as CSV:"))
+            .children(signals.into_iter().enumerate().map(move |(i, signal)| {
+                let view = view.clone();
+                let (channel, id) = (frame.channel, frame.id);
+                div()
+                    .id(("signal-series", i))
+                    .px_2()
+                    .rounded(px(3.))
+                    .bg(rgb(0x1f2937))
+                    .text_color(rgb(0xd1d5db))
+                    .cursor_pointer()
+                    .hover(|style| style.bg(rgb(0x374151)))
+                    .child(format!(
+                        "{} = {}",
+                        signal.name,
+                        signal.display_value(numbers)
+                    ))
+                    .on_mouse_down(MouseButton::Left, move |_event, _window, cx| {
+                        view.update(cx, |app, cx| {
+                            app.copy_signal_series(channel, id, &signal, cx)
+                        });
+                    })
+            }))
+    }
+
+    /// Breadcrumb bar listing the active filters; clicking a chip removes it
+    fn render_filter_breadcrumbs(
+        view: Entity<CanViewApp>,
+        filters: Vec<ActiveFilter>,
+        decimal: bool,
+        sources: &LogSources,
+    ) -> impl IntoElement {
+        div()
+            .h(px(FILTER_BREADCRUMB_HEIGHT))
+            .flex()
+            .items_center()
+            .gap_2()
+            .px_3()
+            .bg(rgb(0x141414))
+            .border_b_1()
+            .border_color(rgb(0x2a2a2a))
+            .text_xs()
+            .child(div().text_color(rgb(0x646473)).child("Filters:"))
+            .children(filters.into_iter().enumerate().map({
+                let view = view.clone();
+                move |(i, filter)| {
+                    let view = view.clone();
+                    let label = match filter {
+                        ActiveFilter::File(source) => format!("FILE: {}", sources.name(source)),
+                        _ => filter.label(decimal),
+                    };
+                    div()
+                        .id(("filter-chip", i))
+                        .px_2()
+                        .rounded(px(3.))
+                        .bg(rgb(0x1e3a8a))
+                        .text_color(rgb(0xd1d5db))
+                        .cursor_pointer()
+                        .hover(|style| style.bg(rgb(0x1e40af)))
+                        .child(format!("{}  ✕", label))
+                        .on_mouse_down(MouseButton::Left, move |_event, _window, cx| {
+                            view.update(cx, |app, cx| {
+                                app.remove_filter(filter, cx);
+                            });
+                        })
+                }
+            }))
+            .child(
+                div()
+                    .id("filter-share")
+                    .px_2()
+                    .text_color(rgb(0x9ca3af))
+                    .cursor_pointer()
+                    .hover(|style| style.text_color(rgb(0xffffff)))
+                    .child("Share…")
+                    .on_mouse_down(MouseButton::Left, {
+                        let view = view.clone();
+                        move |_event, _window, cx| {
+                            view.update(cx, |app, cx| app.save_filter_file(cx));
+                        }
+                    }),
+            )
+            .child(
+                div()
+                    .id("filter-clear-all")
+                    .px_2()
+                    .text_color(rgb(0x9ca3af))
+                    .cursor_pointer()
+                    .hover(|style| style.text_color(rgb(0xffffff)))
+                    .child("Clear all")
+                    .on_mouse_down(MouseButton::Left, move |_event, _window, cx| {
+                        view.update(cx, |app, cx| {
+                            for filter in app.filters.read(cx).active() {
+                                app.remove_filter(filter, cx);
+                            }
+                        });
+                    }),
+            )
+    }
+
+    /// Files of a merged log with their row colors; clicking one shows or hides its rows
+    fn render_source_legend(
+        view: Entity<CanViewApp>,
+        sources: &LogSources,
+        shown: &BTreeSet<u16>,
+    ) -> impl IntoElement {
+        div()
+            .h(px(FILTER_BREADCRUMB_HEIGHT))
+            .flex()
+            .items_center()
+            .gap_3()
+            .px_3()
+            .bg(rgb(0x141414))
+            .border_b_1()
+            .border_color(rgb(0x2a2a2a))
+            .text_xs()
+            .child(div().text_color(rgb(0x646473)).child("Files:"))
+            .children((0..sources.files.len() as u16).map(|source| {
+                let view = view.clone();
+                let hidden = !shown.is_empty() && !shown.contains(&source);
+                div()
+                    .id(("source-file", usize::from(source)))
+                    .flex()
+                    .items_center()
+                    .gap_1()
+                    .cursor_pointer()
+                    .text_color(rgb(if hidden { 0x646473 } else { 0xd1d5db }))
+                    .hover(|style| style.text_color(rgb(0xffffff)))
+                    .child(
+                        div()
+                            .w(px(3.))
+                            .h(px(12.))
+                            .bg(rgb(LogSources::color(source))),
+                    )
+                    .child(sources.name(source))
+                    .on_mouse_down(MouseButton::Left, move |_event, _window, cx| {
+                        view.update(cx, |app, cx| {
+                            app.filters
+                                .update(cx, |filters, _| filters.toggle_file(source));
+                            app.filters_changed(cx);
+                        });
+                    })
+            }))
+    }
+
+    /// Check or uncheck a tree node
+    fn toggle_tree_node(&mut self, node: TreeNode, cx: &mut Context<Self>) {
+        let log_view = self.log_view.clone();
+        self.filters.update(cx, |filters, cx| {
+            filters.tree.toggle(node, &log_view.read(cx).channel_tree)
+        });
+        self.filters_changed(cx);
+    }
+
+    /// Sidebar with buses, channels and IDs; checkboxes filter the log view
+    pub(super) fn render_channel_tree(
+        &self,
+        view: Entity<CanViewApp>,
+        cx: &App,
+    ) -> impl IntoElement {
+        let log = self.log_view.read(cx);
+        let filters = self.filters.read(cx);
+        let mut rows: Vec<(TreeNode, usize)> = Vec::new();
+        for bus in log.channel_tree.children(None) {
+            rows.push((bus, 0));
+            if !log.is_tree_expanded(bus) {
+                continue;
+            }
+            for channel in log.channel_tree.children(Some(bus)) {
+                rows.push((channel, 1));
+                if log.is_tree_expanded(channel) {
+                    let ids = log.channel_tree.children(Some(channel));
+                    rows.extend(ids.into_iter().map(|id| (id, 2)));
+                }
+            }
+        }
+        let decimal = log.id_display_decimal;
+
+        div()
+            .id("channel_tree")
+            .size_full()
+            .flex()
+            .flex_col()
+            .overflow_y_scroll()
+            .bg(rgb(0x0f0f11))
+            .text_xs()
+            .child(
+                div()
+                    .h(px(32.))
+                    .px_2()
+                    .flex()
+                    .items_center()
+                    .justify_between()
+                    .text_color(rgb(0x646473))
+                    .child("BUSES")
+                    .when(!filters.tree.is_empty(), |el| {
+                        el.child(
+                            div()
+                                .id("channel_tree_reset")
+                                .cursor_pointer()
+                                .hover(|style| style.text_color(rgb(0xcdd6f4)))
+                                .on_mouse_down(gpui::MouseButton::Left, {
+                                    let view = view.clone();
+                                    move |_event, _, cx| {
+                                        view.update(cx, |app, cx| {
+                                            app.filters
+                                                .update(cx, |filters, _| filters.tree.clear());
+                                            app.filters_changed(cx);
+                                        });
+                                    }
+                                })
+                                .child("Show all"),
+                        )
+                    }),
+            )
+            .when(rows.is_empty(), |el| {
+                el.child(div().px_2().text_color(rgb(0x646473)).child("No messages"))
+            })
+            .children(rows.into_iter().enumerate().map(|(row, (node, depth))| {
+                let has_children = !matches!(node, TreeNode::Id(..));
+                let expanded = has_children && log.is_tree_expanded(node);
+                let check = match filters.tree.state(node) {
+                    CheckState::Checked => "☑",
+                    CheckState::Partial => "▣",
+                    CheckState::Unchecked => "☐",
+                };
+                let highlights = &self.app_config.highlights;
+                let highlight = match node {
+                    TreeNode::Bus(_) => None,
+                    TreeNode::Channel(_, channel) => {
+                        crate::handlers::highlight_color(highlights, channel, None)
+                    }
+                    TreeNode::Id(_, channel, id) => {
+                        crate::handlers::highlight_color(highlights, channel, Some(id))
+                    }
+                };
+                let label = match node {
+                    TreeNode::Bus(bus) => bus.label().to_string(),
+                    TreeNode::Channel(_, channel) => format!("CH {}", channel),
+                    TreeNode::Id(_, _, id) if decimal => id.to_string(),
+                    TreeNode::Id(_, _, id) => format!("0x{:X}", id),
+                };
+
+                div()
+                    .id(("channel_tree_row", row))
+                    .h(px(22.))
+                    .pl(px(8. + depth as f32 * 14.))
+                    .pr_2()
+                    .flex()
+                    .items_center()
+                    .gap_1()
+                    .cursor_pointer()
+                    .text_color(if filters.tree.state(node) == CheckState::Unchecked {
+                        rgb(0x646473)
+                    } else {
+                        rgb(0xcdd6f4)
+                    })
+                    .hover(|style| style.bg(rgb(0x1e1e2e)))
+                    .on_mouse_down(gpui::MouseButton::Left, {
+                        let view = view.clone();
+                        move |_event, _, cx| {
+                            view.update(cx, |app, cx| {
+                                app.toggle_tree_node(node, cx);
+                            });
+                        }
+                    })
+                    .child(div().id(("channel_tree_expand", row)).w(px(12.)).when(
+                        has_children,
+                        |el| {
+                            el.child(if expanded { "▾" } else { "▸" }).on_mouse_down(
+                                gpui::MouseButton::Left,
+                                {
+                                    let view = view.clone();
+                                    move |_event, _, cx| {
+                                        cx.stop_propagation();
+                                        view.update(cx, |app, cx| {
+                                            app.log_view.update(cx, |log, _| {
+                                                log.toggle_tree_expanded(node)
+                                            });
+                                            cx.notify();
+                                        });
+                                    }
+                                },
+                            )
+                        },
+                    ))
+                    .child(check)
+                    .when_some(highlight, |el, color| {
+                        el.child(div().size(px(8.)).rounded(px(2.)).bg(rgb(color)))
+                    })
+                    .child(div().flex_1().child(label))
+                    .child(
+                        div()
+                            .text_color(rgb(0x646473))
+                            .child(log.channel_tree.count(node).to_string()),
+                    )
+            }))
+    }
+
+    /// Density strip over the whole log; gaps are red, highlighted IDs mark the
+    /// top of their bins, the selection is a white line and clicking a bin
+    /// jumps the list to its start
+    pub(super) fn render_minimap(
+        &self,
+        minimap: &Minimap,
+        view: Entity<CanViewApp>,
+        cx: &App,
+    ) -> impl IntoElement {
+        let max_count = minimap.max_count().max(1) as f32;
+        let log = self.log_view.read(cx);
+        let selected = log
+            .selection
+            .indices()
+            .next()
+            .and_then(|index| log.messages.get(index))
+            .map(|msg| minimap.fraction_of(msg.timestamp()));
+        let bins = minimap.bins.iter().enumerate().map(|(bin, &count)| {
+            let start_ns = minimap.bin_start_ns(bin);
+            div()
+                .id(("minimap-bin", bin))
+                .flex_1()
+                .h_full()
+                .flex()
+                .items_end()
+                .cursor_pointer()
+                .hover(|style| style.bg(rgb(0x252f3a)))
+                .on_mouse_down(MouseButton::Left, {
+                    let view = view.clone();
+                    move |_event, _window, cx| {
+                        view.update(cx, |app, cx| {
+                            app.jump_to_time(start_ns, cx);
+                        });
+                    }
+                })
+                .when_some(minimap.highlights[bin], |el, color| {
+                    el.relative().child(
+                        div()
+                            .absolute()
+                            .top_0()
+                            .left_0()
+                            .w_full()
+                            .h(px(3.))
+                            .bg(rgb(color)),
+                    )
+                })
+                .when(count > 0, |el| {
+                    // Square root keeps sparse stretches visible next to busy ones
+                    let height = (count as f32 / max_count).sqrt().max(0.08);
+                    el.child(div().w_full().h(relative(height)).bg(rgb(0x3b82f6)))
+                })
+        });
+        let gaps = minimap.gaps.iter().map(|gap| {
+            let left = minimap.fraction_of(gap.start_ns);
+            let width = minimap.fraction_of(gap.end_ns) - left;
+            div()
+                .absolute()
+                .top_0()
+                .bottom_0()
+                .left(relative(left))
+                .w(relative(width))
+                .min_w(px(2.))
+                .bg(rgb(0xef4444))
+                .opacity(0.5)
+        });
+
+        div()
+            .h(px(28.))
+            .w_full()
+            .flex_none()
+            .relative()
+            .flex()
+            .bg(rgb(0x0f0f11))
+            .border_b_1()
+            .border_color(rgb(0x2a2a2a))
+            .children(bins)
+            .children(gaps)
+            .when_some(selected, |el, fraction| {
+                el.child(
+                    div()
+                        .absolute()
+                        .top_0()
+                        .bottom_0()
+                        .left(relative(fraction))
+                        .w(px(1.))
+                        .bg(rgb(0xf5f5f5)),
+                )
+            })
+    }
+
+    pub(super) fn render_log_view(&self, view: Entity<CanViewApp>, cx: &App) -> impl IntoElement {
+        let log = self.log_view.read(cx);
+        let filters = self.filters.read(cx);
+        // Clone view for use in multiple closures
+        let view_clone1 = view.clone();
+        let view_clone2 = view.clone();
+
+        // Decoded signals of a single selected row, unless the detail panel is elsewhere
+        let row_signals = (self.app_config.preferences.detail_panel == PanelPlacement::Bottom)
+            .then(|| self.selected_row_signals(cx))
+            .flatten();
+
+        // Rows passing the filters; the list reads their messages as they scroll into view
+        let filtered_rows = self.filtered_indices(cx);
+        // The fixed view lists each ID once, at its latest frame
+        let fixed_rows = log.fixed_view.then(|| self.fixed_view_rows(cx));
+        let fixed_view = log.fixed_view;
+
+        let dbc_channels = self.dbc_channels.clone();
+        let ldf_channels = self.ldf_channels.clone();
+        // Relative timestamps are shown as seconds since the measurement start
+        let time = TimeDisplay::new(self.start_time, &self.app_config.preferences);
+        let row_height = log.message_list.row_height();
+        let signal_lines = self.app_config.preferences.signal_lines();
+        let id_display_decimal = log.id_display_decimal;
+        let id_filter = filters.id;
+        let id_filter_text = log.id_filter_text.clone();
+
+        // Calculate column widths based on ALL messages (not filtered), to keep layout consistent
+        let (time_width, ch_width, type_width, id_width, dlc_width) =
+            calculate_column_widths(&log.messages, &dbc_channels, &ldf_channels, time);
+
+        // Clone view for use in event handlers
+        let view_for_mouse_up = view.clone();
+        let view_for_keyboard = view.clone();
+        let log_view = self.log_view.clone();
+
+        // Clone for dialog display
+        let _id_filter_text_for_dialog = id_filter_text.clone();
+
+        // Filter dropdowns hang below the header, which the file legend and the
+        // breadcrumb bar push down
+        let active_filters = filters.active();
+        let merged = log.log_sources.is_merged();
+        let dropdown_top =
+            32.0 + if merged {
+                FILTER_BREADCRUMB_HEIGHT
+            } else {
+                0.0
+            } + if active_filters.is_empty() {
+                0.0
+            } else {
+                FILTER_BREADCRUMB_HEIGHT
+            };
+
+        div()
+            .size_full()
+            .flex()
+            .flex_col()
+            .relative()  // Add relative positioning for absolute children
+            .font(self.data_font())
+            // Handle keyboard input for ID filter
+            .on_key_down(move |event, _window, cx| {
+                eprintln!("Global on_key_down: keystroke={}", event.keystroke);
+                // Check if filter box is active
+                let show_filter = log_view.read(cx).show_id_filter_input;
+                eprintln!("  show_filter={}", show_filter);
+
+                // If filter box is active, handle input for it
+                if show_filter {
+                    eprintln!("  Filter box active, handling input");
+                    let keystroke_str = format!("{}", event.keystroke);
+                    match keystroke_str.as_str() {
+                        "backspace" => {
+                            log_view.update(cx, |log, cx| {
+                                let mut text = log.id_filter_text.to_string();
+                                if !text.is_empty() {
+                                    text.pop();
+                                    log.id_filter_text = text.into();
+                                    eprintln!("  Filter text (backspace): {}", log.id_filter_text);
+                                    cx.notify();
+                                }
+                            });
+                            return;  // Don't continue to default handler
+                        }
+                        "escape" => {
+                            log_view.update(cx, |log, cx| {
+                                log.show_id_filter_input = false;
+                                eprintln!("  Filter box closed (escape)");
+                                cx.notify();
+                            });
+                            return;
+                        }
+                        "enter" => {
+                            view_for_keyboard.update(cx, |app, cx| {
+                                app.update_log_view_and_filters(cx, |log, filters| {
+                                    // Apply filter and close
+                                    if let Ok(parsed_id) = NumberValidation::FrameId.parse(&log.id_filter_text) {
+                                        filters.id = Some(parsed_id);
+                                    }
+                                    log.show_id_filter_input = false;
+                                    eprintln!("  Filter applied (enter): id={:?}", filters.id);
+                                });
+                                app.filters_changed(cx);
+                            });
+                            return;
+                        }
+                        _ => {
+                            // Handle digit input
+                            if keystroke_str.len() == 1 {
+                                if let Some(ch) = keystroke_str.chars().next() {
+                                    if NumberValidation::FrameId.accepts_char(ch) {
+                                        log_view.update(cx, |log, cx| {
+                                            let mut text = log.id_filter_text.to_string();
+                                            text.push(ch);
+                                            log.id_filter_text = text.into();
+                                            eprintln!("  Filter text: {}", log.id_filter_text);
+                                            cx.notify();
+                                        });
+                                        return;
+                                    }
+                                }
+                            }
+                        }
+                    }
+                    // For non-digit keys when filter is active, still don't pass through
+                    return;
+                }
+
+                // Type filter dropdown has no search line; escape closes it
+                if log_view.read(cx).show_type_filter_input
+                    && format!("{}", event.keystroke) == "escape"
+                {
+                    log_view.update(cx, |log, cx| {
+                        log.show_type_filter_input = false;
+                        cx.notify();
+                    });
+                    return;
+                }
+
+                // Channel filter dropdown search
+                if log_view.read(cx).show_channel_filter_input {
+                    let keystroke_str = format!("{}", event.keystroke);
+                    view_for_keyboard.update(cx, |app, cx| {
+                        let applied = app.update_log_view_and_filters(cx, |log, filters| {
+                            match keystroke_str.as_str() {
+                                "backspace" => {
+                                    let mut text = log.channel_filter_text.to_string();
+                                    text.pop();
+                                    log.channel_filter_text = text.into();
+                                }
+                                "escape" => {
+                                    log.show_channel_filter_input = false;
+                                }
+                                "enter" => {
+                                    let channel = NumberValidation::Integer { min: 0, max: u16::MAX as u32 };
+                                    if let Ok(channel) = channel.parse(&log.channel_filter_text) {
+                                        filters.channel = Some(channel as u16);
+                                    }
+                                    log.show_channel_filter_input = false;
+                                    return Some(true);
+                                }
+                                key if key.len() == 1 && key.chars().all(|ch| ch.is_ascii_digit()) => {
+                                    let mut text = log.channel_filter_text.to_string();
+                                    text.push_str(key);
+                                    log.channel_filter_text = text.into();
+                                }
+                                _ => return None,
+                            }
+                            Some(false)
+                        });
+                        match applied {
+                            Some(true) => app.filters_changed(cx),
+                            Some(false) => cx.notify(),
+                            None => {}
+                        }
+                    });
+                    return;
+                }
+
+                // Convert Keystroke to string for matching
+                let keystroke_str = format!("{}", event.keystroke);
+
+                // Navigation keys scroll the message list
+                if log_view.read(cx).message_list.handle_key(&keystroke_str) {
+                    cx.notify(view_for_keyboard.entity_id());
+                    return;
+                }
+
+                match keystroke_str.as_str() {
+                    // Select every displayed row
+                    "ctrl-a" | "cmd-a" => {
+                        view_for_keyboard.update(cx, |app, cx| {
+                            let rows = app.displayed_rows(cx);
+                            app.log_view.update(cx, |log, _| log.selection.select_all(&rows));
+                            app.cursor_moved(cx);
+                        });
+                    }
+                    // Export the selected rows
+                    "ctrl-e" | "cmd-e" => {
+                        view_for_keyboard.update(cx, |app, cx| app.export_selected(cx));
+                    }
+                    // Backspace to delete
+                    "backspace" => {
+                        view_for_keyboard.update(cx, |app, cx| {
+                            let changed = app.update_log_view_and_filters(cx, |log, filters| {
+                                let mut text = log.id_filter_text.to_string();
+                                if text.is_empty() {
+                                    return false;
+                                }
+                                text.pop();
+                                let new_text = text.clone();
+                                log.id_filter_text = text.into();
+
+                                if new_text.is_empty() {
+                                    filters.id = None;
+                                } else if let Ok(parsed_id) = NumberValidation::FrameId.parse(&new_text) {
+                                    filters.id = Some(parsed_id);
+                                } else {
+                                    filters.id = None;
+                                }
+                                true
+                            });
+                            if changed {
+                                app.filters_changed(cx);
+                            }
+                        });
+                    }
+                    // Escape clears the selection first, then the filter
+                    "escape" => {
+                        view_for_keyboard.update(cx, |app, cx| {
+                            let cleared = app.log_view.update(cx, |log, _| {
+                                let selected = !log.selection.is_empty();
+                                log.selection.clear();
+                                selected
+                            });
+                            if cleared {
+                                cx.notify();
+                                return;
+                            }
+                            app.update_log_view_and_filters(cx, |log, filters| {
+                                filters.id = None;
+                                log.id_filter_text = "".into();
+                            });
+                            app.filters_changed(cx);
+                        });
+                    }
+                    _ => {
+                        // Check if it's a single digit (0-9)
+                        if keystroke_str.len() == 1 {
+                            let ch = keystroke_str.chars().next().unwrap();
+                            if NumberValidation::FrameId.accepts_char(ch) {
+                                view_for_keyboard.update(cx, |app, cx| {
+                                    app.update_log_view_and_filters(cx, |log, filters| {
+                                        let mut text = log.id_filter_text.to_string();
+                                        text.push(ch);
+                                        let new_text = text.clone();
+                                        log.id_filter_text = text.into();
+
+                                        // Try to parse the ID
+                                        if let Ok(parsed_id) = NumberValidation::FrameId.parse(&new_text) {
+                                            filters.id = Some(parsed_id);
+                                        }
+                                    });
+                                    app.filters_changed(cx);
+                                });
+                            }
+                        }
+                    }
+                }
+            })
+            // Keep scrollbar drags alive when the pointer leaves the list
+            .on_mouse_move({
+                let message_list = log.message_list.clone();
+                move |event, window, _cx| {
+                    if event.pressed_button != Some(MouseButton::Left) {
+                        message_list.end_drag();
+                    } else if message_list.update_drag(f32::from(event.position.y)) {
+                        window.refresh();
+                    }
+                }
+            })
+            // Global mouse up handler - this will catch mouse up anywhere
+            .on_mouse_up(MouseButton::Left, move |_event, _window, cx| {
+                // Always clear drag state on mouse up, anywhere in the window
+                view_for_mouse_up.read(cx).log_view.read(cx).message_list.end_drag();
+            })
+            .when(merged, |parent| {
+                parent.child(Self::render_source_legend(
+                    view.clone(),
+                    &log.log_sources,
+                    &filters.files,
+                ))
+            })
+            .when(!active_filters.is_empty(), |parent| {
+                parent.child(Self::render_filter_breadcrumbs(
+                    view.clone(),
+                    active_filters,
+                    id_display_decimal,
+                    &log.log_sources,
+                ))
+            })
+            .child(
+                // Zed-style header with calculated column widths and proper alignment
+                div()
+                    .w_full()
+                    .h(px(28.))
+                    .bg(rgb(0x1f1f1f))
+                    .border_b_1()
+                    .border_color(rgb(0x2a2a2a))
+                    .flex()
+                    .items_center()
+                    .text_xs()
+                    .font_weight(FontWeight::MEDIUM)
+                    .text_color(rgb(0x9ca3af))
+                    .child(
+                        div()
+                            .w(px(60.))
+                            .px_3()
+                            .py_1()
+                            .flex()
+                            .items_center()
+                            .flex_shrink_0()
+                            .whitespace_nowrap()
+                            .overflow_hidden()
+                            .child("#")
+                    )
+                    .child(
+                        div()
+                            .w(time_width)
+                            .px_3()
+                            .py_1()
+                            .flex()
+                            .items_center()
+                            .flex_shrink_0()
+                            .whitespace_nowrap()
+                            .overflow_hidden()
+                            .child("TIME")
+                    )
+                    .child(
+                        {
+                            let _view_for_ch_filter = view.clone();
+                            div()
+                                .w(ch_width)
+                                .px_2()
+                                .py_1()
+                                .flex()
+                                .items_center()
+                                .flex_shrink_0()
+                                .whitespace_nowrap()
+                                .overflow_hidden()
+                                .child("CH")
+                                .child(
+                                    div()
+                                        .text_xs()
+                                        .cursor_pointer()
+                                        .text_color(if filters.channel.is_some() {
+                                            rgb(0x60a5fa)
+                                        } else {
+                                            rgb(0x4b5563)
+                                        })
+                                        .hover(|style| style.bg(rgb(0x374151)))
+                                        .rounded(px(2.))
+                                        .ml_0p5()  // Small left margin to bring it closer to CH
+                                        .pl_0()  // No left padding
+                                        .pr_0()  // No right padding
+                                        .py_0p5()
+                                        .on_mouse_down(gpui::MouseButton::Left, {
+                                            let view = view.clone();
+                                            move |_event, _window, cx| {
+                                                view.update(cx, |app, cx| {
+                                                    app.update_log_view_and_filters(cx, |log, filters| {
+                                                    // If filter is active, clicking clears it
+                                                    // If filter is not active, clicking shows dropdown
+                                                    if filters.channel.is_some() {
+                                                        eprintln!("Clearing channel filter");
+                                                        filters.channel = None;
+                                                        log.channel_filter_text = "".into();
+                                                        log.show_channel_filter_input = false;
+                                                    } else {
+                                                        eprintln!("Before: show_channel_filter_input={}", log.show_channel_filter_input);
+                                                        log.show_channel_filter_input = !log.show_channel_filter_input;
+                                                        eprintln!("After: show_channel_filter_input={}", log.show_channel_filter_input);
+                                                    }
+                                                    });
+                                                    app.filters_changed(cx);
+                                                });
+                                            }
+                                        })
+                                        .child(if filters.channel.is_some() { "✓" } else { "⚙" })
+                                )
+                        }
+                    )
+                    .child(
+                        div()
+                            .w(type_width)
+                            .px_2()
+                            .py_1()
+                            .flex()
+                            .items_center()
+                            .flex_shrink_0()
+                            .whitespace_nowrap()
+                            .overflow_hidden()
+                            .child("TYPE")
+                            .child(
+                                div()
+                                    .text_xs()
+                                    .cursor_pointer()
+                                    .text_color(if filters.types.is_empty() {
+                                        rgb(0x4b5563)
+                                    } else {
+                                        rgb(0x60a5fa)
+                                    })
+                                    .hover(|style| style.bg(rgb(0x374151)))
+                                    .rounded(px(2.))
+                                    .ml_0p5()
+                                    .py_0p5()
+                                    .on_mouse_down(gpui::MouseButton::Left, {
+                                        let view = view.clone();
+                                        move |_event, _window, cx| {
+                                            // Multi-select: the dropdown stays open and is
+                                            // also where types are cleared
+                                            view.update(cx, |app, cx| {
+                                                app.log_view.update(cx, |log, cx| {
+                                                    log.show_type_filter_input = !log.show_type_filter_input;
+                                                    cx.notify();
+                                                });
+                                            });
+                                        }
+                                    })
+                                    .child(if filters.types.is_empty() { "⚙" } else { "✓" })
+                            )
+                    )
+                    .child(
+                        div()
+                            .w(id_width)
+                            .pl_2()  // Only left padding
+                            .pr_0()  // No right padding
+                            .py_1()
+                            .flex()
+                            .items_center()
+                            .flex_shrink_0()
+                            .child(
+                                div()
+                                    .flex()
+                                    .items_center()
+                                    .child(
+                                        div()
+                                            .cursor_pointer()
+                                            .rounded(px(2.))
+                                            .pl_1()  // Left padding only
+                                            .pr_0()  // No right padding
+                                            .py_0p5()
+                                            .hover(|style| style.bg(rgb(0x374151)))
+                                            .on_mouse_down(gpui::MouseButton::Left, {
+                                                let view = view.clone();
+                                                move |_, _, cx| {
+                                                    view.update(cx, |app, cx| {
+                                                        app.log_view.update(cx, |log, cx| {
+                                                            log.id_display_decimal = !log.id_display_decimal;
+                                                            cx.notify();
+                                                        });
+                                                    });
+                                                }
+                                            })
+                                            .child(
+                                                div()
+                                                    .flex()
+                                                    .items_center()
+                                                    .gap_0p5()
+                                                    .child("ID")
+                                                    .child(
+                                                        div()
+                                                            .text_xs()
+                                                            .text_color(rgb(0x6b7280))
+                                                            .child(if id_display_decimal { "10" } else { "16" })
+                                                    )
+                                            )
+                                    )
+                                    .child(
+                                        div()
+                                            .text_xs()
+                                            .cursor_pointer()
+                                            .text_color(if id_filter.is_some() {
+                                                rgb(0x60a5fa)
+                                            } else {
+                                                rgb(0x4b5563)
+                                            })
+                                            .hover(|style| style.bg(rgb(0x374151)))
+                                            .rounded(px(2.))
+                                            .pl_1()  // Left padding only
+                                            .pr_0()  // No right padding
+                                            .py_0p5()
+                                            .on_mouse_down(gpui::MouseButton::Left, {
+                                                let view = view.clone();
+                                                move |event, _, cx| {
+                                                    eprintln!("Gear clicked! Position: {:?}", event.position);
+                                                    view.update(cx, |app, cx| {
+                                                        app.update_log_view_and_filters(cx, |log, filters| {
+                                                        // If filter is active, clicking clears it
+                                                        // If filter is not active, clicking shows dropdown
+                                                        if filters.id.is_some() {
+                                                            eprintln!("Clearing filter");
+                                                            filters.id = None;
+                                                            log.id_filter_text = "".into();
+                                                            log.show_id_filter_input = false;
+                                                        } else {
+                                                            eprintln!("Before: show_id_filter_input={}", log.show_id_filter_input);
+                                                            log.show_id_filter_input = !log.show_id_filter_input;
+                                                            eprintln!("After: show_id_filter_input={}", log.show_id_filter_input);
+                                                        }
+                                                        });
+                                                        app.filters_changed(cx);
+                                                    });
+                                                }
+                                            })
+                                            .child(if id_filter.is_some() { "✓" } else { "⚙" })
+                                    )
+                            )
+                    )
+                    .child(
+                        div()
+                            .w(dlc_width)
+                            .px_2()
+                            .py_1()
+                            .flex()
+                            .items_center()
+                            .flex_shrink_0()
+                            .whitespace_nowrap()
+                            .overflow_hidden()
+                            .child("DLC")
+                    )
+                    .child(
+                        div()
+                            .flex_1()  // DATA列使用flex_1()占据剩余空间
+                            .px_2()
+                            .py_1()
+                            .flex()
+                            .items_center()
+                            .whitespace_nowrap()
+                            .justify_between()
+                            .child("DATA")
+                            .child(
+                                div()
+                                    .id("trace-mode")
+                                    .px_2()
+                                    .rounded(px(2.))
+                                    .cursor_pointer()
+                                    .text_color(rgb(0x9ca3af))
+                                    .hover(|style| style.bg(rgb(0x374151)))
+                                    .child(if fixed_view { "Fixed ⇄" } else { "Trace ⇄" })
+                                    .on_mouse_down(gpui::MouseButton::Left, {
+                                        let view = view.clone();
+                                        move |_, _, cx| {
+                                            view.update(cx, |app, cx| {
+                                                app.log_view.update(cx, |log, cx| {
+                                                    log.fixed_view = !log.fixed_view;
+                                                    log.message_list.scroll_to(0);
+                                                    cx.notify();
+                                                });
+                                            });
+                                        }
+                                    }),
+                            )
+                    )
+                    .when(fixed_view, |header| {
+                        let cell = |text: &'static str| {
+                            div()
+                                .w(px(FIXED_COLUMN_WIDTH))
+                                .px_2()
+                                .py_1()
+                                .flex_shrink_0()
+                                .whitespace_nowrap()
+                                .child(text)
+                        };
+                        header.child(cell("CYCLE")).child(cell("COUNT"))
+                    }),
+            )
+            .child(
+                // Content area with simple list
+                div()
+                    .flex_1()
+                    .flex()
+                    .flex_col()
+                    .relative()
+                    .when(log.messages.is_empty(), |parent| {
+                        // Show placeholder when no messages
+                        parent.child(
+                            div()
+                                .flex_1()
+                                .flex()
+                                .items_center()
+                                .justify_center()
+                                .child(
+                                    div()
+                                        .text_lg()
+                                        .text_color(rgb(0x6b7280))
+                                        .child("No messages loaded. Click '📂 Open log' to load a file.")
+                                )
+                        )
+                    })
+                    .when(!filtered_rows.is_empty(), |parent| {
+                        // Only the visible rows are built; the list owns its scrollbar
+                        let display_count =
+                            fixed_rows.as_ref().map_or(filtered_rows.len(), Vec::len);
+                        let view_entity = view.clone();
+                        let row_details = log.row_details.clone();
+
+                        parent.child(
+                            VirtualList::new(
+                                "message-list",
+                                &log.message_list,
+                                display_count,
+                                move |range: std::ops::Range<usize>, _window: &mut gpui::Window, cx: &mut gpui::App| {
+                                    // Disable hover when filter dropdown is open
+                                    let app = view_entity.read(cx);
+                                    let log = app.log_view.read(cx);
+                                    let disable_hover = log.show_id_filter_input;
+                                    let selection = &log.selection;
+                                    // Under load rows show the raw payload; their signals follow
+                                    let mut details = row_details.borrow_mut();
+                                    let decode_now = details.begin_frame(
+                                        range.start,
+                                        range.len(),
+                                        std::time::Instant::now(),
+                                    );
+                                    let rows = range
+                                        .map(|index| {
+                                            let entry = match &fixed_rows {
+                                                Some(fixed) => {
+                                                    fixed.get(index).map(|row| (row.index, Some(row)))
+                                                }
+                                                None => {
+                                                    filtered_rows.get(index).map(|&row| (row, None))
+                                                }
+                                            };
+                                            if let Some((message_index, msg, fixed_row)) =
+                                                entry.and_then(|(row, fixed)| {
+                                                    Some((row, log.messages.get(row)?, fixed))
+                                                })
+                                            {
+                                                let signals = details.text(
+                                                    message_index,
+                                                    msg,
+                                                    decode_now,
+                                                    &dbc_channels,
+                                                    &ldf_channels,
+                                                );
+                                                let row = Self::render_message_row_static_with_widths(
+                                                    msg,
+                                                    log.id_stats.lookup(msg),
+                                                    message_index,
+                                                    time_width,
+                                                    ch_width,
+                                                    type_width,
+                                                    id_width,
+                                                    dlc_width,
+                                                    signals,
+                                                    signal_lines,
+                                                    time,
+                                                    crate::handlers::message_channel(msg)
+                                                        .map_or(0, |channel| app.time_offsets.get(channel)),
+                                                    id_display_decimal,
+                                                    row_height,
+                                                    selection.is_selected(message_index),
+                                                    disable_hover,
+                                                    Rc::new({
+                                                        let view = view_entity.clone();
+                                                        move |column, cx| {
+                                                            view.update(cx, |app, cx| {
+                                                                app.quick_filter(message_index, column, cx);
+                                                            });
+                                                        }
+                                                    }),
+                                                );
+                                                let row = match fixed_row {
+                                                    Some(fixed) => Self::render_fixed_row(
+                                                        row,
+                                                        fixed,
+                                                        selection.is_selected(message_index),
+                                                    ),
+                                                    None => row,
+                                                };
+                                                let view = view_entity.clone();
+                                                let is_trigger = app
+                                                    .trigger_events
+                                                    .binary_search_by_key(&message_index, |event| event.index)
+                                                    .is_ok();
+                                                let is_gap = app
+                                                    .sequence_checker
+                                                    .gaps()
+                                                    .binary_search_by_key(&message_index, |gap| gap.index)
+                                                    .is_ok();
+                                                let source = log
+                                                    .log_sources
+                                                    .is_merged()
+                                                    .then(|| log.log_sources.tag(message_index));
+                                                let marker = crate::handlers::marker_at_row(
+                                                    &app.markers,
+                                                    &log.messages,
+                                                    message_index,
+                                                )
+                                                .map(|marker| marker.name.clone());
+                                                let highlight = crate::handlers::message_highlight(
+                                                    &app.app_config.highlights,
+                                                    msg,
+                                                );
+                                                div()
+                                                    .relative()
+                                                    .on_mouse_down(MouseButton::Left, move |event, _window, cx| {
+                                                        view.update(cx, |app, cx| {
+                                                            app.click_message_row(message_index, &event.modifiers, cx);
+                                                        });
+                                                    })
+                                                    .child(row)
+                                                    // Highlighted ID or channel: tint the row
+                                                    .when_some(highlight, |el, color| {
+                                                        el.child(
+                                                            div()
+                                                                .absolute()
+                                                                .top_0()
+                                                                .left_0()
+                                                                .size_full()
+                                                                .bg(rgb(color))
+                                                                .opacity(0.15),
+                                                        )
+                                                    })
+                                                    // Segment boundary: a trigger fired on this row
+                                                    .when(is_trigger, |el| {
+                                                        el.child(
+                                                            div()
+                                                                .absolute()
+                                                                .top_0()
+                                                                .left_0()
+                                                                .w_full()
+                                                                .h(px(2.))
+                                                                .bg(rgb(0xf59e0b)),
+                                                        )
+                                                    })
+                                                    // Frames were lost right before this row
+                                                    .when(is_gap, |el| {
+                                                        el.child(
+                                                            div()
+                                                                .absolute()
+                                                                .bottom_0()
+                                                                .left_0()
+                                                                .w_full()
+                                                                .h(px(2.))
+                                                                .bg(rgb(0xef4444)),
+                                                        )
+                                                    })
+                                                    // Merged log: the file this row came from
+                                                    .when_some(source, |el, source| {
+                                                        el.child(
+                                                            div()
+                                                                .absolute()
+                                                                .top_0()
+                                                                .right_0()
+                                                                .h_full()
+                                                                .w(px(3.))
+                                                                .bg(rgb(LogSources::color(source))),
+                                                        )
+                                                    })
+                                                    // A marker falls right before or on this row
+                                                    .when_some(marker, |el, name| {
+                                                        el.child(
+                                                            div()
+                                                                .id(("marker", message_index))
+                                                                .absolute()
+                                                                .top_0()
+                                                                .left_0()
+                                                                .h_full()
+                                                                .w(px(3.))
+                                                                .bg(rgb(0x3b82f6))
+                                                                .tooltip(move |_window, cx| {
+                                                                    cx.new(|_| TextTooltip::new(vec![format!("⚑ {}", name)]))
+                                                                        .into()
+                                                                }),
+                                                        )
+                                                    })
+                                                    .into_any_element()
+                                            } else {
+                                                div().into_any_element()
+                                            }
+                                        })
+                                        .collect::<Vec<_>>();
+                                    details.end_frame(std::time::Instant::now());
+                                    if details.has_queue() {
+                                        let view = view_entity.clone();
+                                        cx.defer(move |cx| {
+                                            view.update(cx, |app, cx| app.decode_queued_rows(cx));
+                                        });
+                                    }
+                                    rows
+                                },
+                            )
+                            // Wrapped signals make rows taller than the row height
+                            .variable_heights(signal_lines > 1)
+                            .build(),
+                        )
+                    }),
+            )
+            .when_some(row_signals, |parent, (frame, signals)| {
+                parent.child(Self::render_signal_strip(
+                    view.clone(),
+                    frame,
+                    signals,
+                    self.app_config.preferences.number_format,
+                ))
+            })
+            .when(self.show_video_bar, |parent| {
+                parent.child(self.render_video_bar(view.clone(), cx))
+            })
+            // ID filter dropdown - shows all IDs in a virtual list
+            .when(log.show_id_filter_input, |parent| {
+                // Calculate ALL unique IDs from messages
+                let id_list = crate::handlers::extract_unique_ids(&log.messages);
+
+                let filter_left = 60.0 + f32::from(time_width) + f32::from(ch_width) + f32::from(type_width) + f32::from(id_width) - 40.0;
+
+                parent.child(
+                    div().absolute().left(px(filter_left)).top(px(dropdown_top)).child(
+                        Dropdown::new("filter-dropdown", &log.id_filter_list)
+                            // Both bases in the label, so either can be searched
+                            .items(id_list.into_iter().map(|id| (id, format!("ID: {} (0x{:X})", id, id))))
+                            .selected(filters.id)
+                            .query(log.id_filter_text.clone())
+                            .error(NumberValidation::FrameId.error(&log.id_filter_text))
+                            .width(px(180.))
+                            .on_select({
+                                let view = view_clone1.clone();
+                                move |id, _window, cx| {
+                                    view.update(cx, |app, cx| {
+                                        app.update_log_view_and_filters(cx, |log, filters| {
+                                            filters.id = Some(*id);
+                                            log.id_filter_text = id.to_string().into();
+                                            log.show_id_filter_input = false;
+                                        });
+                                        app.filters_changed(cx);
+                                    });
+                                }
+                            })
+                            .on_dismiss({
+                                let view = view_clone1.clone();
+                                move |_window, cx| {
+                                    view.update(cx, |app, cx| {
+                                        app.log_view.update(cx, |log, cx| {
+                                            log.show_id_filter_input = false;
+                                            cx.notify();
+                                        });
+                                    });
+                                }
+                            })
+                            .build(),
+                    ),
+                )
+            })
+            // Channel filter dropdown
+            .when(log.show_channel_filter_input, |parent| {
+                // Calculate ALL unique channels from messages
+                let channel_list: Vec<u16> = log.log_summary.channels.iter().copied().collect();
+
+                let filter_left = 60.0 + f32::from(time_width) + 10.0; // Position after TIME column
+
+                parent.child(
+                    div().absolute().left(px(filter_left)).top(px(dropdown_top)).child(
+                        Dropdown::new("channel-filter-dropdown", &log.channel_filter_list)
+                            .items(channel_list.into_iter().map(|channel| (channel, format!("CH: {}", channel))))
+                            .selected(filters.channel)
+                            .query(log.channel_filter_text.clone())
+                            .width(px(120.))
+                            .on_select({
+                                let view = view_clone2.clone();
+                                move |channel, _window, cx| {
+                                    view.update(cx, |app, cx| {
+                                        app.update_log_view_and_filters(cx, |log, filters| {
+                                            filters.channel = Some(*channel);
+                                            log.channel_filter_text = channel.to_string().into();
+                                            log.show_channel_filter_input = false;
+                                        });
+                                        app.filters_changed(cx);
+                                    });
+                                }
+                            })
+                            .on_dismiss({
+                                let view = view_clone2.clone();
+                                move |_window, cx| {
+                                    view.update(cx, |app, cx| {
+                                        app.log_view.update(cx, |log, cx| {
+                                            log.show_channel_filter_input = false;
+                                            cx.notify();
+                                        });
+                                    });
+                                }
+                            })
+                            .build(),
+                    ),
+                )
+            })
+            // Message type filter dropdown (multi-select)
+            .when(log.show_type_filter_input, |parent| {
+                let filter_left = 60.0 + f32::from(time_width) + f32::from(ch_width) + 10.0; // Position after CH column
+
+                // `None` is the "All types" row, which clears the selection
+                let items = std::iter::once((None, "All types".to_string())).chain(
+                    MessageKind::ALL
+                        .into_iter()
+                        .map(|kind| (Some(kind), kind.label().to_string())),
+                );
+                let selected: Vec<Option<MessageKind>> = if filters.types.is_empty() {
+                    vec![None]
+                } else {
+                    filters.types.iter().copied().map(Some).collect()
+                };
+
+                parent.child(
+                    div().absolute().left(px(filter_left)).top(px(dropdown_top)).child(
+                        Dropdown::new("type-filter-dropdown", &log.type_filter_list)
+                            .items(items)
+                            .selected_many(selected)
+                            .width(px(140.))
+                            .on_select({
+                                let view = view.clone();
+                                move |kind, _window, cx| {
+                                    view.update(cx, |app, cx| {
+                                        app.filters.update(cx, |filters, _| filters.toggle_type(*kind));
+                                        app.filters_changed(cx);
+                                    });
+                                }
+                            })
+                            .on_dismiss({
+                                let view = view.clone();
+                                move |_window, cx| {
+                                    view.update(cx, |app, cx| {
+                                        app.log_view.update(cx, |log, cx| {
+                                            log.show_type_filter_input = false;
+                                            cx.notify();
+                                        });
+                                    });
+                                }
+                            })
+                            .build(),
+                    ),
+                )
+            })
+    }
+
+    /// A trace row followed by the CYCLE and COUNT cells of the fixed view
+    fn render_fixed_row(
+        row: gpui::AnyElement,
+        fixed: &crate::handlers::FixedRow,
+        selected: bool,
+    ) -> gpui::AnyElement {
+        // The cells take the height of the row, which the list relies on
+        let cell = |text: String| {
+            div()
+                .w(px(FIXED_COLUMN_WIDTH))
+                .h_full()
+                .px_2()
+                .flex()
+                .items_center()
+                .flex_shrink_0()
+                .whitespace_nowrap()
+                .overflow_hidden()
+                .border_b_1()
+                .border_color(rgb(0x2a2a2a))
+                .child(text)
+        };
+        let cycle = fixed.cycle_ns.map_or_else(
+            || "-".to_string(),
+            |ns| format!("{:.1} ms", ns as f64 / 1_000_000.0),
+        );
+        div()
+            .flex()
+            .w_full()
+            .bg(if selected {
+                rgb(0x1e3a5f)
+            } else {
+                rgb(0x181818)
+            })
+            .text_xs()
+            .text_color(rgb(0x9ca3af))
+            .child(div().flex_1().min_w_0().child(row))
+            .child(cell(cycle))
+            .child(cell(fixed.count.to_string()))
+            .into_any_element()
+    }
+
+    // Render message row with pre-calculated widths for perfect alignment
+    fn render_message_row_static_with_widths(
+        msg: &LogObject,
+        id_stats: Option<&IdStats>,
+        message_index: usize,
+        time_width: gpui::Pixels,
+        ch_width: gpui::Pixels,
+        type_width: gpui::Pixels,
+        id_width: gpui::Pixels,
+        dlc_width: gpui::Pixels,
+        signals: Option<String>,
+        signal_lines: usize,
+        time: TimeDisplay,
+        offset_ns: i64,
+        decimal: bool,
+        row_height: f32,
+        selected: bool,
+        disable_hover: bool, // New parameter to disable hover effect
+        on_quick_filter: Rc<dyn Fn(FilterColumn, &mut App)>,
+    ) -> gpui::AnyElement {
+        let (time_str, channel_id, msg_type, id_str, dlc_str, data_str) =
+            message_strings(msg, time, offset_ns, decimal);
+
+        // Double-clicking a CH / TYPE / ID cell filters on its value
+        let quick_filter = |column: FilterColumn| {
+            let on_quick_filter = on_quick_filter.clone();
+            move |event: &MouseDownEvent, _window: &mut Window, cx: &mut App| {
+                if event.click_count == 2 {
+                    on_quick_filter(column, cx);
+                }
+            }
+        };
+
+        let bg_color = if selected {
+            rgb(0x1e3a5f)
+        } else {
+            rgb(0x181818)
+        };
+        let type_color = match msg_type.as_str() {
+            "CAN" | "CAN2" => rgb(0x34d399),
+            "CAN_ERR" => rgb(0xef4444),
+            "CAN_FD" | "CAN_FD64" => rgb(0x8b5cf6),
+            "CAN_OV" => rgb(0xf59e0b),
+            "LIN" | "LIN2" => rgb(0x60a5fa),
+            _ => rgb(0x9ca3af),
+        };
+
+        let wrap = signal_lines > 1;
+
+        div()
+            .flex()
+            .w_full()
+            // Must match the virtual list row height; wrapped rows are measured by the list
+            .when(!wrap, |row| row.h(px(row_height)).items_center())
+            .when(wrap, |row| row.min_h(px(row_height)).items_start())
+            .bg(bg_color)
+            .border_b_1()
+            .border_color(rgb(0x2a2a2a))
+            .text_xs()
+            .text_color(rgb(0xd1d5db))
+            .when(!disable_hover && !selected, |div| {
+                div.hover(|style| style.bg(rgb(0x1f2937)))
+            })
+            .cursor_pointer()
+            .overflow_hidden() // Ensure row doesn't overflow
+            .child(
+                // Line number column
+                div()
+                    .w(px(60.))
+                    .px_3()
+                    .py_1()
+                    .flex()
+                    .items_center()
+                    .flex_shrink_0()
+                    .text_color(rgb(0x6b7280))
+                    .whitespace_nowrap()
+                    .overflow_hidden()
+                    .child(format!(
+                        "{}",
+                        crate::handlers::sequence_number(message_index)
+                    )),
+            )
+            .child(
+                div()
+                    .w(time_width)
+                    .px_3()
+                    .py_1()
+                    .flex()
+                    .items_center()
+                    .flex_shrink_0()
+                    .text_color(rgb(0x9ca3af))
+                    .whitespace_nowrap()
+                    .overflow_hidden()
+                    .child(time_str),
+            )
+            .child(
+                div()
+                    .w(ch_width)
+                    .px_2()
+                    .py_1()
+                    .flex()
+                    .items_center()
+                    .flex_shrink_0()
+                    .text_color(rgb(0x60a5fa))
+                    .whitespace_nowrap()
+                    .overflow_hidden()
+                    .on_mouse_down(MouseButton::Left, quick_filter(FilterColumn::Channel))
+                    .child(channel_id.to_string()),
+            )
+            .child(
+                div()
+                    .w(type_width)
+                    .px_2()
+                    .py_1()
+                    .flex()
+                    .items_center()
+                    .flex_shrink_0()
+                    .text_color(type_color)
+                    .whitespace_nowrap()
+                    .overflow_hidden()
+                    .on_mouse_down(MouseButton::Left, quick_filter(FilterColumn::Type))
+                    .child(msg_type),
+            )
+            .child(
+                div()
+                    .id(("message-id", message_index))
+                    .w(id_width)
+                    .px_2()
+                    .py_1()
+                    .flex()
+                    .items_center()
+                    .flex_shrink_0()
+                    .text_color(rgb(0xfbbf24))
+                    .whitespace_nowrap()
+                    .overflow_hidden()
+                    .on_mouse_down(MouseButton::Left, quick_filter(FilterColumn::Id))
+                    // Quick stats of this ID from the background index
+                    .when_some(id_stats.map(IdStats::tooltip_lines), |el, lines| {
+                        el.tooltip(move |_window, cx| {
+                            cx.new(|_| TextTooltip::new(lines.clone())).into()
+                        })
+                    })
+                    .child(id_str),
+            )
+            .child(
+                div()
+                    .w(dlc_width)
+                    .px_2()
+                    .py_1()
+                    .flex()
+                    .items_center()
+                    .flex_shrink_0()
+                    .whitespace_nowrap()
+                    .overflow_hidden()
+                    .child(dlc_str),
+            )
+            .child(
+                div()
+                    .flex_1() // DATA列使用flex_1()占据剩余空间
+                    .px_2()
+                    .py_1()
+                    .flex()
+                    .items_start()
+                    .gap_3()
+                    .text_color(rgb(0xa78bfa))
+                    .whitespace_nowrap()
+                    .overflow_hidden()
+                    .child(div().flex_shrink_0().child(data_str))
+                    // Decoded signals, filled in later while the list is busy
+                    .when_some(signals, |el, signals| {
+                        el.child(
+                            div()
+                                .flex_1()
+                                .min_w_0()
+                                .text_color(rgb(0x6b7280))
+                                .when(wrap, |el| el.whitespace_normal().line_clamp(signal_lines))
+                                .child(signals),
+                        )
+                    }),
+            )
+            .into_any_element()
+    }
+
+    #[allow(dead_code)]
+    // Static helper to format a timestamp for the TIME column
+    fn format_timestamp_static(timestamp: u64, time: TimeDisplay) -> String {
+        time.format(timestamp)
+    }
+
+    #[allow(dead_code)]
+    // Static helper to render a message row (needed for uniform_list closure)
+    fn render_message_row_static(
+        msg: &LogObject,
+        index: usize,
+        _dbc_channels: &HashMap<u16, DbcDatabase>,
+        _ldf_channels: &HashMap<u16, LdfDatabase>,
+        time: TimeDisplay,
+    ) -> gpui::AnyElement {
+        let (time_str, channel_id, msg_type, id_str, dlc_str, data_str): (
+            String,
+            u16,
+            String,
+            String,
+            String,
+            String,
+        ) = match msg {
+            // CAN Message Types
+            LogObject::CanMessage(can_msg) => {
+                let timestamp = can_msg.header.object_time_stamp;
+                let time_str = Self::format_timestamp_static(timestamp, time);
+
+                let actual_data_len = can_msg.data.len().min(can_msg.dlc as usize);
+                let data_hex = can_msg
+                    .data
+                    .iter()
+                    .take(actual_data_len)
+                    .map(|b| format!("{:02X}", b))
+                    .collect::<Vec<_>>()
+                    .join(" ");
+
+                (
+                    time_str,
+                    can_msg.channel,
+                    "CAN".to_string(),
+                    crate::handlers::hex_can_id(can_msg.id),
+                    actual_data_len.to_string(),
+                    data_hex,
+                )
+            }
+            LogObject::CanMessage2(can_msg) => {
+                let timestamp = can_msg.header.object_time_stamp;
+                let time_str = Self::format_timestamp_static(timestamp, time);
+
+                let actual_data_len = can_msg.data.len().min(can_msg.dlc as usize);
+                let data_hex = can_msg
+                    .data
+                    .iter()
+                    .take(actual_data_len)
+                    .map(|b| format!("{:02X}", b))
+                    .collect::<Vec<_>>()
+                    .join(" ");
+
+                (
+                    time_str,
+                    can_msg.channel,
+                    "CAN2".to_string(),
+                    crate::handlers::hex_can_id(can_msg.id),
+                    actual_data_len.to_string(),
+                    data_hex,
+                )
+            }
+            LogObject::CanErrorFrame(err) => {
+                let timestamp = err.header.object_time_stamp;
+                let time_str = Self::format_timestamp_static(timestamp, time);
+
+                (
+                    time_str,
+                    err.channel,
+                    "CAN_ERR".to_string(),
+                    "-".to_string(),
+                    err.length.to_string(),
+                    "-".to_string(),
+                )
+            }
+            LogObject::CanFdMessage(fd_msg) => {
+                let timestamp = fd_msg.header.object_time_stamp;
+                let time_str = Self::format_timestamp_static(timestamp, time);
+
+                let actual_data_len = fd_msg.data.len().min(fd_msg.dlc as usize);
+                let data_hex = fd_msg
+                    .data
+                    .iter()
+                    .take(actual_data_len)
+                    .map(|b| format!("{:02X}", b))
+                    .collect::<Vec<_>>()
+                    .join(" ");
+
+                (
+                    time_str,
+                    fd_msg.channel, // Convert u8 to u16
+                    "CAN_FD".to_string(),
+                    crate::handlers::hex_can_id(fd_msg.id),
+                    actual_data_len.to_string(),
+                    data_hex,
+                )
+            }
+            LogObject::CanFdMessage64(fd_msg) => {
+                let timestamp = fd_msg.header.object_time_stamp;
+                let time_str = Self::format_timestamp_static(timestamp, time);
+
+                let actual_data_len = fd_msg.data.len().min(fd_msg.valid_data_bytes as usize);
+                let data_hex = fd_msg
+                    .data
+                    .iter()
+                    .take(actual_data_len)
+                    .map(|b| format!("{:02X}", b))
+                    .collect::<Vec<_>>()
+                    .join(" ");
+
+                (
+                    time_str,
+                    fd_msg.channel as u16, // Convert u8 to u16
+                    "CAN_FD64".to_string(),
+                    crate::handlers::hex_can_id(fd_msg.id),
+                    actual_data_len.to_string(),
+                    data_hex,
+                )
+            }
+            LogObject::CanOverloadFrame(ov) => {
+                let timestamp = ov.header.object_time_stamp;
+                let time_str = Self::format_timestamp_static(timestamp, time);
+
+                (
+                    time_str,
+                    ov.channel,
+                    "CAN_OV".to_string(),
+                    "-".to_string(),
+                    "-".to_string(),
+                    "-".to_string(),
+                )
+            }
+
+            // LIN Message Types
+            LogObject::LinMessage(lin_msg) => {
+                let timestamp = lin_msg.header.object_time_stamp;
+                let time_str = time.format(timestamp);
+
+                let actual_data_len = lin_msg.data.len().min(lin_msg.dlc as usize);
+                let data_hex = lin_msg
+                    .data
+                    .iter()
+                    .take(actual_data_len)
+                    .map(|b| format!("{:02X}", b))
+                    .collect::<Vec<_>>()
+                    .join(" ");
+
+                (
+                    time_str,
+                    lin_msg.channel,
+                    "LIN".to_string(),
+                    crate::handlers::hex_lin_id(lin_msg.id as u32),
+                    actual_data_len.to_string(),
+                    data_hex,
+                )
+            }
+            LogObject::LinMessage2(lin_msg) => {
+                let timestamp = lin_msg.header.object_time_stamp;
+                let time_str = Self::format_timestamp_static(timestamp, time);
+
+                let actual_data_len = lin_msg.data.len().min(lin_msg.dlc as usize);
+                let data_hex = lin_msg
+                    .data
+                    .iter()
+                    .take(actual_data_len)
+                    .map(|b| format!("{:02X}", b))
+                    .collect::<Vec<_>>()
+                    .join(" ");
+
+                (
+                    time_str,
+                    lin_msg.channel,
+                    "LIN2".to_string(),
+                    crate::handlers::hex_lin_id(lin_msg.id as u32),
+                    actual_data_len.to_string(),
+                    data_hex,
+                )
+            }
+
+            // Default case for other types (LIN errors, FlexRay, etc.)
+            _ => {
+                let type_name = format!("{:?}", msg);
+                (
+                    "-".to_string(),
+                    0_u16,
+                    type_name.split('(').next().unwrap_or("UNKNOWN").to_string(),
+                    "-".to_string(),
+                    "-".to_string(),
+                    "-".to_string(),
+                )
+            }
+        };
+
+        let bg_color = if index.is_multiple_of(2) {
+            rgb(0x181818)
+        } else {
+            rgb(0x1a1a1a)
+        };
+
+        // Color code message types
+        let type_color = match msg_type.as_str() {
+            "CAN" | "CAN2" => rgb(0x34d399),        // Green for normal CAN
+            "CAN_ERR" => rgb(0xef4444),             // Red for errors
+            "CAN_FD" | "CAN_FD64" => rgb(0x8b5cf6), // Purple for CAN FD
+            "CAN_OV" => rgb(0xf59e0b),              // Orange for overload
+            "LIN" | "LIN2" => rgb(0x60a5fa),        // Blue for LIN
+            "LIN_CRC" | "LIN_RX_ERR" | "LIN_TX_ERR" => rgb(0xef4444), // Red for LIN errors
+            "LIN_WAKE" => rgb(0xfbbf24),            // Yellow for wakeup
+            "LIN_SLEEP" => rgb(0x6b7280),           // Gray for sleep
+            "FLEX" | "FLEX_SYNC" => rgb(0xec4899),  // Pink for FlexRay
+            _ => rgb(0x9ca3af),                     // Default gray
+        };
+
+        div()
+            .flex()
+            .w_full()
+            .min_h(px(22.))
+            .bg(bg_color)
+            .border_b_1()
+            .border_color(rgb(0x2a2a2a))
+            .items_center()
+            .text_xs()
+            .text_color(rgb(0xd1d5db))
+            .hover(|style| style.bg(rgb(0x1f2937)))
+            .cursor_pointer()
+            .child(
+                div()
+                    .px_3()
+                    .py_1()
+                    .text_color(rgb(0x9ca3af))
+                    .whitespace_nowrap()
+                    .child(time_str),
+            )
+            .child(
+                div()
+                    .px_2()
+                    .py_1()
+                    .text_color(rgb(0x60a5fa))
+                    .whitespace_nowrap()
+                    .child(channel_id.to_string()),
+            )
+            .child(
+                div()
+                    .px_2()
+                    .py_1()
+                    .text_color(type_color)
+                    .whitespace_nowrap()
+                    .child(msg_type),
+            )
+            .child(
+                div()
+                    .px_2()
+                    .py_1()
+                    .text_color(rgb(0xfbbf24))
+                    .whitespace_nowrap()
+                    .child(id_str),
+            )
+            .child(div().px_2().py_1().whitespace_nowrap().child(dlc_str))
+            .child(
+                div()
+                    .px_2()
+                    .py_1()
+                    .text_color(rgb(0xa78bfa))
+                    .whitespace_nowrap()
+                    .child(data_str),
+            )
+            .into_any_element()
+    }
+
+    /// Select row `index` of the log and scroll to it, if the filters show it
+    pub(super) fn jump_to_row(&mut self, index: usize, cx: &mut Context<Self>) {
+        let rows = self.filtered_indices(cx);
+        match rows.binary_search(&index) {
+            Ok(position) => {
+                self.select_row_at(&rows, position, cx);
+                self.current_view = AppView::LogView;
+            }
+            Err(_) => {
+                self.status_msg = format!(
+                    "❌ Row #{} is hidden by the current filters",
+                    crate::handlers::sequence_number(index)
+                )
+                .into()
+            }
+        }
+        cx.notify();
+    }
+
+    /// Select and scroll to the first visible row at or after `timestamp_ns`,
+    /// or the last one if the log ends before
+    pub(super) fn jump_to_time(&mut self, timestamp_ns: u64, cx: &mut Context<Self>) {
+        let log = self.log_view.read(cx);
+        let rows = self.filtered_indices(cx);
+        let position = rows
+            .partition_point(|&row| log.messages[row].timestamp() < timestamp_ns)
+            .min(rows.len().saturating_sub(1));
+        self.select_row_at(&rows, position, cx);
+    }
+
+    /// Select the row at `position` of the visible `rows` and scroll it into view
+    pub(super) fn select_row_at(
+        &mut self,
+        rows: &[usize],
+        position: usize,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(&row) = rows.get(position) {
+            // A single frame is found in the trace
+            self.log_view.update(cx, |log, _| {
+                log.fixed_view = false;
+                log.selection.select_only(row);
+                log.message_list.scroll_to(position);
+            });
+            self.cursor_moved(cx);
+        }
+    }
+}
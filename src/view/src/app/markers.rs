@@ -0,0 +1,420 @@
+//! The markers and highlights dialogs
+
+use super::state::{AppModal, AppView, CanViewApp};
+use crate::handlers::{Marker, TimeDisplay};
+use crate::ui::components::Modal;
+use blf::LogObject;
+use gpui::{prelude::*, *};
+use gpui_component::input::Input;
+
+impl CanViewApp {
+    /// Open the markers dialog with a fresh name field
+    pub fn open_markers_dialog(&mut self, cx: &mut Context<Self>) {
+        self.marker_name_input = None;
+        self.open_modal(AppModal::Markers, cx);
+    }
+
+    /// Put a marker at the first selected row, named from the dialog's field
+    fn add_marker_at_selection(&mut self, cx: &mut Context<Self>) {
+        let log = self.log_view.read(cx);
+        let Some(timestamp_ns) = log
+            .selection
+            .indices()
+            .next()
+            .and_then(|index| log.messages.get(index))
+            .map(LogObject::timestamp)
+        else {
+            self.status_msg = "❌ Select the row to mark first".into();
+            cx.notify();
+            return;
+        };
+        let name = self
+            .marker_name_input
+            .as_ref()
+            .map(|input| input.read(cx).value().trim().to_string())
+            .filter(|name| !name.is_empty())
+            .unwrap_or_else(|| format!("Marker {}", self.markers.len() + 1));
+
+        self.status_msg = format!("⚑ Marker '{}' added", name).into();
+        crate::handlers::insert_marker(&mut self.markers, Marker::new(timestamp_ns, name));
+        self.markers_unsaved = true;
+        self.marker_name_input = None;
+        cx.notify();
+    }
+
+    /// Select and scroll to the first visible row at or after marker `index`
+    fn jump_to_marker(&mut self, index: usize, cx: &mut Context<Self>) {
+        let Some(marker) = self.markers.get(index) else {
+            return;
+        };
+        self.jump_to_time(marker.timestamp_ns, cx);
+        self.current_view = AppView::LogView;
+        self.close_modal(AppModal::Markers, cx);
+    }
+
+    /// Open the highlights dialog with the saved highlights
+    pub fn open_highlights_dialog(&mut self, cx: &mut Context<Self>) {
+        self.highlights_input = None;
+        self.open_modal(AppModal::Highlights, cx);
+    }
+
+    /// Save the highlights of the dialog and recolor the minimap
+    fn apply_highlights(&mut self, cx: &mut Context<Self>) {
+        let text = self
+            .highlights_input
+            .as_ref()
+            .map(|input| input.read(cx).value().to_string())
+            .unwrap_or_default();
+        let highlights = match crate::handlers::parse_highlights(&text) {
+            Ok(highlights) => highlights,
+            Err(e) => {
+                self.status_msg = format!("❌ {}", e).into();
+                cx.notify();
+                return;
+            }
+        };
+        if let Some(minimap) = &mut self.minimap {
+            minimap.mark_highlights(&self.log_view.read(cx).messages, &highlights);
+        }
+        self.status_msg = format!("◐ {} highlights", highlights.len()).into();
+        self.app_config.highlights = highlights;
+        self.save_config(cx);
+        self.close_modal(AppModal::Highlights, cx);
+    }
+
+    pub(super) fn render_highlights_modal(
+        &self,
+        view: Entity<CanViewApp>,
+        on_close: impl Fn(&mut Window, &mut App) + Clone + 'static,
+    ) -> Modal {
+        let muted = |text: &'static str| div().text_xs().text_color(rgb(0x646473)).child(text);
+        let swatches = crate::handlers::NAMED_COLORS.map(|(name, color)| {
+            div()
+                .flex()
+                .items_center()
+                .gap_1()
+                .text_xs()
+                .text_color(rgb(0x9ca3af))
+                .child(div().size(px(10.)).rounded(px(2.)).bg(rgb(color)))
+                .child(name)
+        });
+
+        Modal::new("highlights-modal")
+            .title("Highlights")
+            .width(px(520.))
+            .child(muted(
+                "Highlights separated by ';': 0x123 orange, 2:0x123 #ff8800 (channel 2 only), \
+                 ch3 green for a whole channel. ID highlights win over channel ones.",
+            ))
+            .child(div().flex().flex_wrap().gap_3().children(swatches))
+            .when_some(self.highlights_input.as_ref(), |modal, input| {
+                modal.child(Input::new(input))
+            })
+            .child(muted(
+                "Highlighted rows are tinted in the log, and marked in the minimap and the \
+                 channel tree. Highlights are saved with the configuration.",
+            ))
+            .footer(
+                div()
+                    .flex()
+                    .gap_2()
+                    .child(Self::render_modal_button(
+                        "highlights-cancel",
+                        "Cancel",
+                        false,
+                        on_close.clone(),
+                    ))
+                    .child(Self::render_modal_button(
+                        "highlights-apply",
+                        "Apply",
+                        true,
+                        {
+                            let view = view.clone();
+                            move |_window, cx| {
+                                view.update(cx, |app, cx| app.apply_highlights(cx));
+                            }
+                        },
+                    )),
+            )
+            .on_close(on_close)
+    }
+
+    /// Read markers from a sidecar, an ASC log or a BLF saved by CANoe
+    fn import_markers(&mut self, cx: &mut Context<Self>) {
+        cx.spawn(async move |this, cx| {
+            let Some(file) = rfd::AsyncFileDialog::new()
+                .add_filter("Marker lists", &["txt", "asc", "blf", "gz", "zip"])
+                .pick_file()
+                .await
+            else {
+                return;
+            };
+            let path = file.path().to_path_buf();
+            let result = cx
+                .background_executor()
+                .spawn({
+                    let path = path.clone();
+                    async move {
+                        let is_log = path.extension().is_some_and(|ext| {
+                            ["blf", "gz", "zip"]
+                                .iter()
+                                .any(|log| ext.eq_ignore_ascii_case(log))
+                        });
+                        if is_log {
+                            crate::handlers::read_log_file(&path)
+                                .map(|result| crate::handlers::markers_from_log(&result.objects))
+                        } else {
+                            std::fs::read_to_string(&path)
+                                .map_err(|e| e.to_string())
+                                .and_then(|text| crate::handlers::parse_marker_comments(&text))
+                        }
+                    }
+                })
+                .await;
+
+            let _ = this.update(cx, |app, cx| {
+                match result {
+                    Ok(markers) => {
+                        app.status_msg = format!("⚑ Imported {} markers", markers.len()).into();
+                        for marker in markers {
+                            crate::handlers::insert_marker(&mut app.markers, marker);
+                        }
+                        app.markers_unsaved = true;
+                    }
+                    Err(e) => {
+                        app.status_msg = format!("❌ Marker import: {}", e).into();
+                    }
+                }
+                cx.notify();
+            });
+        })
+        .detach();
+    }
+
+    /// Write the markers as a sidecar of ASC comment lines
+    fn export_markers(&mut self, cx: &mut Context<Self>) {
+        let text = crate::handlers::format_marker_comments(&self.markers);
+        let count = self.markers.len();
+        cx.spawn(async move |this, cx| {
+            let Some(file) = rfd::AsyncFileDialog::new()
+                .add_filter("Marker list", &["txt"])
+                .set_file_name("markers.txt")
+                .save_file()
+                .await
+            else {
+                return;
+            };
+            let path = file.path().to_path_buf();
+            let result = cx
+                .background_executor()
+                .spawn({
+                    let path = path.clone();
+                    async move { std::fs::write(&path, text) }
+                })
+                .await;
+
+            let _ = this.update(cx, |app, cx| {
+                // The sidecar can be imported again
+                app.markers_unsaved &= result.is_err();
+                app.status_msg = match result {
+                    Ok(()) => format!("⚑ Exported {} markers to {}", count, path.display()).into(),
+                    Err(e) => format!("❌ Marker export failed: {}", e).into(),
+                };
+                cx.notify();
+            });
+        })
+        .detach();
+    }
+
+    /// Save the log as BLF with the markers as global markers CANoe shows
+    fn save_log_with_markers(&mut self, cx: &mut Context<Self>) {
+        if self.log_view.read(cx).messages.is_empty() {
+            self.status_msg = "❌ No log to save".into();
+            cx.notify();
+            return;
+        }
+        let messages = &self.log_view.read(cx).messages;
+        let objects = crate::handlers::with_markers(messages, &self.markers);
+        let start_time = self.start_time;
+        let manifest_sources = self.manifest_sources();
+        cx.spawn(async move |this, cx| {
+            let Some(file) = rfd::AsyncFileDialog::new()
+                .add_filter("BLF Files", &["blf"])
+                .set_file_name("marked.blf")
+                .save_file()
+                .await
+            else {
+                return;
+            };
+            let path = file.path().to_path_buf();
+            let result = cx
+                .background_executor()
+                .spawn({
+                    let path = path.clone();
+                    async move {
+                        let count = crate::handlers::save_capture(&path, &objects, start_time)?;
+                        if let Some(sources) = manifest_sources {
+                            crate::handlers::write_export_manifest(&path, &sources)?;
+                        }
+                        Ok::<_, String>(count)
+                    }
+                })
+                .await;
+
+            let _ = this.update(cx, |app, cx| {
+                app.markers_unsaved &= result.is_err();
+                app.status_msg = match result {
+                    Ok(count) => format!(
+                        "💾 Saved {} objects with markers to {}",
+                        count,
+                        path.display()
+                    )
+                    .into(),
+                    Err(e) => format!("❌ Save failed: {}", e).into(),
+                };
+                cx.notify();
+            });
+        })
+        .detach();
+    }
+
+    pub(super) fn render_markers_modal(
+        &self,
+        view: Entity<CanViewApp>,
+        on_close: impl Fn(&mut Window, &mut App) + Clone + 'static,
+    ) -> Modal {
+        let muted = |text: &'static str| div().text_xs().text_color(rgb(0x646473)).child(text);
+        let time = TimeDisplay::new(self.start_time, &self.app_config.preferences);
+
+        Modal::new("markers-modal")
+            .title("Markers")
+            .width(px(520.))
+            .child(muted(
+                "Markers are saved into the BLF as global markers, so CANoe shows them \
+                 when the log is opened there. Marker lists can also be exported as ASC \
+                 comment lines next to an unchanged log.",
+            ))
+            .child(
+                div()
+                    .flex()
+                    .gap_2()
+                    .items_center()
+                    .when_some(self.marker_name_input.as_ref(), |el, input| {
+                        el.child(div().flex_1().child(Input::new(input)))
+                    })
+                    .child(Self::render_modal_button(
+                        "marker-add",
+                        "Add at selection",
+                        false,
+                        {
+                            let view = view.clone();
+                            move |_window, cx| {
+                                view.update(cx, |app, cx| app.add_marker_at_selection(cx));
+                            }
+                        },
+                    )),
+            )
+            .when(self.markers.is_empty(), |modal| {
+                modal.child(muted("No markers yet."))
+            })
+            .child(
+                div()
+                    .flex()
+                    .flex_col()
+                    .gap_1()
+                    .text_xs()
+                    .text_color(rgb(0x9ca3af))
+                    .children(self.markers.iter().enumerate().map(|(index, marker)| {
+                        div()
+                            .flex()
+                            .gap_2()
+                            .child(
+                                div()
+                                    .id(("marker-jump", index))
+                                    .flex_1()
+                                    .cursor_pointer()
+                                    .hover(|style| style.text_color(rgb(0xcdd6f4)))
+                                    .on_mouse_down(gpui::MouseButton::Left, {
+                                        let view = view.clone();
+                                        move |_event, _window, cx| {
+                                            cx.stop_propagation();
+                                            view.update(cx, |app, cx| {
+                                                app.jump_to_marker(index, cx)
+                                            });
+                                        }
+                                    })
+                                    .child(format!(
+                                        "⚑ {}  {}",
+                                        time.format(marker.timestamp_ns),
+                                        marker.name
+                                    )),
+                            )
+                            .child(
+                                div()
+                                    .id(("marker-remove", index))
+                                    .cursor_pointer()
+                                    .hover(|style| style.text_color(rgb(0xef4444)))
+                                    .on_mouse_down(gpui::MouseButton::Left, {
+                                        let view = view.clone();
+                                        move |_event, _window, cx| {
+                                            cx.stop_propagation();
+                                            view.update(cx, |app, cx| {
+                                                if index < app.markers.len() {
+                                                    app.markers.remove(index);
+                                                    app.markers_unsaved = true;
+                                                }
+                                                cx.notify();
+                                            });
+                                        }
+                                    })
+                                    .child("×"),
+                            )
+                    })),
+            )
+            .footer(
+                div()
+                    .flex()
+                    .gap_2()
+                    .child(Self::render_modal_button(
+                        "markers-import",
+                        "Import…",
+                        false,
+                        {
+                            let view = view.clone();
+                            move |_window, cx| {
+                                view.update(cx, |app, cx| app.import_markers(cx));
+                            }
+                        },
+                    ))
+                    .child(Self::render_modal_button(
+                        "markers-export",
+                        "Export…",
+                        false,
+                        {
+                            let view = view.clone();
+                            move |_window, cx| {
+                                view.update(cx, |app, cx| app.export_markers(cx));
+                            }
+                        },
+                    ))
+                    .child(Self::render_modal_button(
+                        "markers-close",
+                        "Close",
+                        false,
+                        on_close.clone(),
+                    ))
+                    .child(Self::render_modal_button(
+                        "markers-save",
+                        "Save BLF…",
+                        true,
+                        {
+                            let view = view.clone();
+                            move |_window, cx| {
+                                view.update(cx, |app, cx| app.save_log_with_markers(cx));
+                            }
+                        },
+                    )),
+            )
+            .on_close(on_close)
+    }
+}
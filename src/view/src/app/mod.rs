@@ -2,11 +2,38 @@
 //!
 //! Contains the core application state and implementation.
 
+mod batch;
+mod channels;
+mod charts;
+mod compare;
+mod correlation;
+mod events;
+mod export;
+mod filters;
+mod find;
+mod gps;
+mod health;
 mod impls;
+mod library;
+mod live;
+mod log_view;
+mod markers;
+mod modals;
+mod panels;
+mod preferences;
+mod sequence;
 mod state;
+mod states;
+mod stats;
+mod status_bar;
+mod time_sync;
+mod top_bar;
+mod triggers;
+mod video;
 
 // Re-export the main types
-pub use state::{AppView, CanViewApp, LibraryDialogType, LibraryManager, ScrollbarDragState};
+pub use events::AppEvent;
+pub use state::{AppModal, AppView, CanViewApp, LibraryDialogType, LibraryManager};
 
 // Define actions for text input handling (public, so other modules can use them)
 // Note: actions! macro defines the types in the current scope, not in a separate module
@@ -0,0 +1,360 @@
+//! The modal layer and the dialogs that belong to no other panel
+
+use super::state::{AppModal, CanViewApp, OpenAction};
+use crate::handlers::LoadMode;
+use crate::ui::components::Modal;
+use gpui::{prelude::*, *};
+
+impl CanViewApp {
+    /// Open a dialog on top of the modal stack
+    pub fn open_modal(&mut self, modal: AppModal, cx: &mut Context<Self>) {
+        self.modals.push(modal);
+        cx.notify();
+    }
+
+    /// Close a dialog wherever it sits in the modal stack
+    pub fn close_modal(&mut self, modal: AppModal, cx: &mut Context<Self>) {
+        if self.modals.close(&modal) {
+            cx.notify();
+        }
+    }
+
+    /// Render every open modal, bottom first; only the topmost one is focused
+    pub(super) fn render_modal_layer(
+        &mut self,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Vec<AnyElement> {
+        let Some(top) = self.modals.top().copied() else {
+            return Vec::new();
+        };
+
+        // Keep focus inside the active modal
+        let focus = self
+            .modal_focus
+            .get_or_insert_with(|| cx.focus_handle())
+            .clone();
+        if !focus.contains_focused(window, cx) {
+            focus.focus(window, cx);
+        }
+
+        let view = cx.entity().clone();
+        let cx: &App = cx;
+        self.modals
+            .iter()
+            .map(|&modal| {
+                let on_close = {
+                    let view = view.clone();
+                    move |_window: &mut Window, cx: &mut App| {
+                        view.update(cx, |app, cx| app.close_modal(modal, cx));
+                    }
+                };
+                let layer = match modal {
+                    AppModal::About => Self::render_about_modal(on_close),
+                    AppModal::Health => self.render_health_modal(view.clone(), on_close),
+                    AppModal::Preferences => self.render_preferences_modal(view.clone(), on_close),
+                    AppModal::Export => self.render_export_modal(view.clone(), on_close, cx),
+                    AppModal::TimeSync => self.render_time_sync_modal(view.clone(), on_close, cx),
+                    AppModal::Triggers => self.render_triggers_modal(view.clone(), on_close),
+                    AppModal::Find => self.render_find_modal(view.clone(), on_close),
+                    AppModal::Sequence => self.render_sequence_modal(view.clone(), on_close),
+                    AppModal::States => self.render_states_modal(view.clone(), on_close),
+                    AppModal::Markers => self.render_markers_modal(view.clone(), on_close),
+                    AppModal::Highlights => self.render_highlights_modal(view.clone(), on_close),
+                    AppModal::Compare => self.render_compare_modal(view.clone(), on_close),
+                    AppModal::Correlation => self.render_correlation_modal(view.clone(), on_close),
+                    AppModal::Simulation => {
+                        self.render_simulation_modal(view.clone(), on_close, cx)
+                    }
+                    AppModal::Replay => self.render_replay_modal(view.clone(), on_close, cx),
+                    AppModal::Capture => self.render_capture_modal(view.clone(), on_close),
+                    AppModal::Mqtt => self.render_mqtt_modal(view.clone(), on_close),
+                    AppModal::WatchFolder => self.render_watch_modal(view.clone(), on_close),
+                    AppModal::MemoryLimit => self.render_memory_limit_modal(view.clone(), on_close),
+                    AppModal::OpenLog => self.render_open_log_modal(view.clone(), on_close, cx),
+                    AppModal::AssignVersion => {
+                        self.render_assign_version_modal(view.clone(), on_close, cx)
+                    }
+                    AppModal::VersionNotes => {
+                        self.render_version_notes_modal(view.clone(), on_close, cx)
+                    }
+                    AppModal::DuplicateImport => {
+                        self.render_duplicate_import_modal(view.clone(), on_close, cx)
+                    }
+                    AppModal::MappingCheck => {
+                        self.render_mapping_check_modal(view.clone(), on_close, cx)
+                    }
+                };
+                if modal == top {
+                    layer.focus(&focus).build().into_any_element()
+                } else {
+                    layer.build().into_any_element()
+                }
+            })
+            .collect()
+    }
+
+    fn render_memory_limit_modal(
+        &self,
+        view: Entity<CanViewApp>,
+        on_close: impl Fn(&mut Window, &mut App) + Clone + 'static,
+    ) -> Modal {
+        let muted = |text: String| div().text_xs().text_color(rgb(0x646473)).child(text);
+        let (name, estimate) = self
+            .pending_load
+            .as_ref()
+            .map(|(path, estimate)| {
+                let name = path
+                    .file_name()
+                    .map(|name| name.to_string_lossy().into_owned())
+                    .unwrap_or_default();
+                (name, *estimate)
+            })
+            .unwrap_or_default();
+        let limit_mb = self.app_config.preferences.memory_limit_mb;
+        let button =
+            |id: &'static str, label: &'static str, primary: bool, mode: Option<LoadMode>| {
+                let view = view.clone();
+                Self::render_modal_button(id, label, primary, move |_window, cx| {
+                    view.update(cx, |app, cx| app.resolve_pending_load(mode, cx));
+                })
+            };
+
+        Modal::new("memory-limit-modal")
+            .title("Log too large")
+            .width(px(520.))
+            .child(muted(format!(
+                "{} needs about {} once loaded, over the memory limit of {}.",
+                name,
+                crate::handlers::format_bytes(estimate),
+                crate::handlers::format_bytes(u64::from(limit_mb) * 1024 * 1024)
+            )))
+            .child(muted(
+                "Stream lists the first frames up to the limit; Index only lists none. \
+                 Both keep statistics of the whole log. The limit is set in Preferences."
+                    .to_string(),
+            ))
+            .footer(
+                div()
+                    .flex()
+                    .gap_2()
+                    .child(button("memory-cancel", "Cancel", false, None))
+                    .child(button(
+                        "memory-index",
+                        "Index only",
+                        false,
+                        Some(LoadMode::IndexOnly),
+                    ))
+                    .child(button(
+                        "memory-stream",
+                        "Stream",
+                        true,
+                        Some(LoadMode::Stream),
+                    )),
+            )
+            .on_close(on_close)
+    }
+
+    fn render_open_log_modal(
+        &self,
+        view: Entity<CanViewApp>,
+        on_close: impl Fn(&mut Window, &mut App) + Clone + 'static,
+        cx: &App,
+    ) -> Modal {
+        let row = |label: &'static str, value: String| {
+            div()
+                .flex()
+                .gap_3()
+                .text_xs()
+                .child(div().w(px(140.)).text_color(rgb(0x646473)).child(label))
+                .child(div().text_color(rgb(0xcdd6f4)).child(value))
+        };
+        let mut modal = Modal::new("open-log-modal")
+            .title("Open log")
+            .width(px(440.));
+        if let Some((path, probe)) = &self.pending_open {
+            let format = match probe.compression {
+                Some(compression) => format!("{}, {}", probe.format.label(), compression.label()),
+                None => probe.format.label().to_string(),
+            };
+            let about = if probe.objects_exact { "" } else { "about " };
+            modal = modal
+                .child(row(
+                    "File",
+                    path.file_name()
+                        .map(|name| name.to_string_lossy().into_owned())
+                        .unwrap_or_default(),
+                ))
+                .child(row("Format", format))
+                .child(row("Size", crate::handlers::format_bytes(probe.file_size)))
+                .child(row("Objects", format!("{}{}", about, probe.objects)))
+                .child(row(
+                    "Duration",
+                    probe
+                        .duration_ns
+                        .map_or("unknown".to_string(), crate::handlers::format_log_duration),
+                ))
+                .child(row(
+                    "Memory once loaded",
+                    format!(
+                        "about {}",
+                        crate::handlers::format_bytes(
+                            probe.objects * crate::handlers::MEMORY_PER_FRAME
+                        )
+                    ),
+                ));
+        }
+
+        // A loaded log is not dropped without asking
+        let loaded = !self.log_view.read(cx).messages.is_empty();
+        let markers_at_risk = self.markers_unsaved && !self.markers.is_empty();
+        if loaded {
+            let current = self
+                .log_path
+                .as_ref()
+                .and_then(|path| path.file_name())
+                .map_or("The live capture".to_string(), |name| {
+                    name.to_string_lossy().into_owned()
+                });
+            let note = |text: String, color: u32| {
+                div().pt_2().text_xs().text_color(rgb(color)).child(text)
+            };
+            modal = modal.child(note(
+                format!(
+                    "{} is loaded. Replace drops it with its filters; Merge puts both logs on \
+                     one timeline; New window keeps it open here.",
+                    current
+                ),
+                0x646473,
+            ));
+            if markers_at_risk {
+                modal = modal.child(note(
+                    format!(
+                        "⚠ {} markers are not saved. Replace discards them; Merge and New \
+                         window keep them.",
+                        self.markers.len()
+                    ),
+                    0xf59e0b,
+                ));
+            }
+        }
+        let button = |id: &'static str, label: &'static str, primary: bool, action: OpenAction| {
+            let view = view.clone();
+            Self::render_modal_button(id, label, primary, move |_window, cx| {
+                view.update(cx, |app, cx| app.confirm_open_log(action, cx));
+            })
+        };
+        let replace_label = if markers_at_risk {
+            "Replace, discard markers"
+        } else {
+            "Replace"
+        };
+
+        modal
+            .footer(
+                div()
+                    .flex()
+                    .gap_2()
+                    .child(Self::render_modal_button(
+                        "open-log-cancel",
+                        "Cancel",
+                        false,
+                        on_close.clone(),
+                    ))
+                    .when(loaded, |el| {
+                        el.child(button(
+                            "open-log-window",
+                            "New window",
+                            false,
+                            OpenAction::NewWindow,
+                        ))
+                        .when(self.can_merge_log(cx), |el| {
+                            el.child(button("open-log-merge", "Merge", false, OpenAction::Merge))
+                        })
+                        .child(button(
+                            "open-log-replace",
+                            replace_label,
+                            true,
+                            OpenAction::Replace,
+                        ))
+                    })
+                    .when(!loaded, |el| {
+                        el.child(button("open-log-open", "Open", true, OpenAction::Replace))
+                    }),
+            )
+            .on_close(on_close)
+    }
+
+    /// Footer button of a dialog
+    pub(super) fn render_modal_button(
+        id: &'static str,
+        label: &'static str,
+        primary: bool,
+        on_click: impl Fn(&mut Window, &mut App) + 'static,
+    ) -> impl IntoElement {
+        div()
+            .id(id)
+            .px_3()
+            .py_1()
+            .text_xs()
+            .rounded(px(3.))
+            .cursor_pointer()
+            .when(primary, |el| {
+                el.bg(rgb(0x1e3a8a))
+                    .text_color(rgb(0xffffff))
+                    .hover(|style| style.bg(rgb(0x1d4ed8)))
+            })
+            .when(!primary, |el| {
+                el.text_color(rgb(0x9ca3af))
+                    .hover(|style| style.bg(rgb(0x252f3a)))
+            })
+            .on_mouse_down(gpui::MouseButton::Left, move |_event, window, cx| {
+                cx.stop_propagation();
+                on_click(window, cx);
+            })
+            .child(label)
+    }
+
+    fn render_about_modal(on_close: impl Fn(&mut Window, &mut App) + Clone + 'static) -> Modal {
+        Modal::new("about-modal")
+            .title("About CANVIEW")
+            .width(px(360.))
+            .child(
+                div()
+                    .text_lg()
+                    .font_weight(FontWeight::BOLD)
+                    .text_color(rgb(0xffffff))
+                    .child("CANVIEW"),
+            )
+            .child(div().text_color(rgb(0x9ca3af)).child(format!(
+                "Bus Data Analyzer · v{}",
+                env!("CARGO_PKG_VERSION")
+            )))
+            .child(
+                div()
+                    .text_xs()
+                    .text_color(rgb(0x646473))
+                    .child("BLF log viewer with DBC / LDF signal decoding"),
+            )
+            .footer(
+                div()
+                    .id("about-modal-ok")
+                    .px_3()
+                    .py_1()
+                    .text_xs()
+                    .bg(rgb(0x1a1f2e))
+                    .rounded(px(3.))
+                    .cursor_pointer()
+                    .hover(|style| style.bg(rgb(0x252f3a)))
+                    .on_mouse_down(gpui::MouseButton::Left, {
+                        let on_close = on_close.clone();
+                        move |_event, window, cx| {
+                            cx.stop_propagation();
+                            on_close(window, cx);
+                        }
+                    })
+                    .child("OK"),
+            )
+            .on_close(on_close)
+    }
+}
@@ -0,0 +1,390 @@
+//! Panels placed around the log view or in windows of their own
+//!
+//! The statistics, charts, watch and detail panels each have a
+//! [`PanelPlacement`] in the preferences, so the layout is saved with the
+//! configuration. Docked panels are laid out by
+//! [`CanViewApp::render_docked_panels`]; a floating panel gets a
+//! [`PanelWindow`] that renders it from the app entity and redraws whenever
+//! the app notifies, so it follows the selected row like a docked one.
+//!
+//! The detail panel docked to the bottom stays right under the log table,
+//! above the other bottom panels, where the selected row's signals used to be.
+
+use super::charts::{chart_color, format_chart_value};
+use super::state::CanViewApp;
+use crate::handlers::{DecodedSignal, ExportFrame};
+use crate::models::preferences::{PanelDock, PanelPlacement};
+use gpui::{prelude::*, *};
+
+/// A panel that can be docked or floated
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DockPanel {
+    Statistics,
+    Charts,
+    /// Values of the charted signals at the selected row
+    Watch,
+    /// Decoded signals of the selected row
+    Details,
+}
+
+impl DockPanel {
+    pub const ALL: [DockPanel; 4] = [
+        DockPanel::Statistics,
+        DockPanel::Charts,
+        DockPanel::Watch,
+        DockPanel::Details,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            DockPanel::Statistics => "Statistics",
+            DockPanel::Charts => "Charts",
+            DockPanel::Watch => "Watch",
+            DockPanel::Details => "Details",
+        }
+    }
+
+    /// Width when docked left or right, in pixels
+    fn width(&self) -> f32 {
+        match self {
+            DockPanel::Statistics | DockPanel::Charts => 500.0,
+            DockPanel::Watch | DockPanel::Details => 280.0,
+        }
+    }
+}
+
+/// Window showing one floating panel of a [`CanViewApp`]
+pub struct PanelWindow {
+    app: WeakEntity<CanViewApp>,
+    panel: DockPanel,
+    _observe: Subscription,
+}
+
+impl PanelWindow {
+    fn new(
+        app: Entity<CanViewApp>,
+        panel: DockPanel,
+        window: &Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let handle = window.window_handle();
+        // Closing the window by hand puts the panel away for good
+        cx.on_release(move |this: &mut PanelWindow, cx| {
+            let Some(app) = this.app.upgrade() else {
+                return;
+            };
+            app.update(cx, |app, cx| {
+                if app.panel_windows.get(&this.panel) == Some(&handle) {
+                    app.panel_windows.remove(&this.panel);
+                    app.set_panel_placement(this.panel, PanelPlacement::Hidden, cx);
+                }
+            });
+        })
+        .detach();
+        Self {
+            app: app.downgrade(),
+            panel,
+            _observe: cx.observe(&app, |_, _, cx| cx.notify()),
+        }
+    }
+}
+
+impl Render for PanelWindow {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let root = div()
+            .size_full()
+            .flex()
+            .flex_col()
+            .overflow_hidden()
+            .bg(rgb(0x0c0c0e))
+            .text_color(rgb(0xcdd6f4));
+        let Some(app) = self.app.upgrade() else {
+            return root;
+        };
+        let state = app.read(cx);
+        window.set_rem_size(px(16.) * state.app_config.preferences.ui_scale());
+        root.font(state.ui_font())
+            .child(state.render_panel(self.panel, app.clone(), cx))
+    }
+}
+
+impl CanViewApp {
+    pub(super) fn panel_placement(&self, panel: DockPanel) -> PanelPlacement {
+        let preferences = &self.app_config.preferences;
+        match panel {
+            DockPanel::Statistics => preferences.stats_panel,
+            DockPanel::Charts => preferences.chart_panel,
+            DockPanel::Watch => preferences.watch_panel,
+            DockPanel::Details => preferences.detail_panel,
+        }
+    }
+
+    /// Move `panel` and save the layout with the configuration
+    pub(super) fn set_panel_placement(
+        &mut self,
+        panel: DockPanel,
+        placement: PanelPlacement,
+        cx: &mut Context<Self>,
+    ) {
+        let mut preferences = self.app_config.preferences.clone();
+        match panel {
+            DockPanel::Statistics => preferences.stats_panel = placement,
+            DockPanel::Charts => preferences.chart_panel = placement,
+            DockPanel::Watch => preferences.watch_panel = placement,
+            DockPanel::Details => preferences.detail_panel = placement,
+        }
+        self.set_preferences(preferences, cx);
+    }
+
+    /// Open a window for each panel placed in one and close the others
+    ///
+    /// Windows are opened and closed once the current update is over, since
+    /// a new window draws right away and reads the app.
+    pub(super) fn sync_panel_windows(&mut self, cx: &mut Context<Self>) {
+        for panel in DockPanel::ALL {
+            let floating = self.panel_placement(panel) == PanelPlacement::Window;
+            if floating && !self.panel_windows.contains_key(&panel) {
+                let app = cx.entity();
+                cx.defer(move |cx| Self::open_panel_window(app, panel, cx));
+            } else if !floating && let Some(handle) = self.panel_windows.remove(&panel) {
+                cx.defer(move |cx| {
+                    let _ = handle.update(cx, |_, window, _| window.remove_window());
+                });
+            }
+        }
+    }
+
+    /// Close every panel window, e.g. when the app goes away
+    pub(super) fn close_panel_windows(&mut self, cx: &mut App) {
+        for (_, handle) in self.panel_windows.drain() {
+            cx.defer(move |cx| {
+                let _ = handle.update(cx, |_, window, _| window.remove_window());
+            });
+        }
+    }
+
+    fn open_panel_window(app: Entity<CanViewApp>, panel: DockPanel, cx: &mut App) {
+        // Another sync may have opened it, or moved the panel back, meanwhile
+        let state = app.read(cx);
+        if state.panel_placement(panel) != PanelPlacement::Window
+            || state.panel_windows.contains_key(&panel)
+        {
+            return;
+        }
+        let options = WindowOptions {
+            titlebar: Some(TitlebarOptions {
+                title: Some(format!("CANVIEW - {}", panel.label()).into()),
+                appears_transparent: false,
+                traffic_light_position: None,
+            }),
+            kind: WindowKind::Normal,
+            ..Default::default()
+        };
+        let opened = cx.open_window(options, {
+            let app = app.clone();
+            move |window, cx| cx.new(|cx| PanelWindow::new(app, panel, window, cx))
+        });
+        app.update(cx, |app, cx| {
+            match opened {
+                Ok(handle) => {
+                    app.panel_windows.insert(panel, handle.into());
+                }
+                Err(e) => app.status_msg = format!("❌ {} window: {}", panel.label(), e).into(),
+            }
+            cx.notify();
+        });
+    }
+
+    /// Contents of `panel`, wherever it is placed
+    pub(super) fn render_panel(
+        &self,
+        panel: DockPanel,
+        view: Entity<CanViewApp>,
+        cx: &App,
+    ) -> AnyElement {
+        match panel {
+            DockPanel::Statistics => self.render_stats_view(cx).into_any_element(),
+            DockPanel::Charts => self.render_chart_view(view).into_any_element(),
+            DockPanel::Watch => self.render_watch_panel(cx).into_any_element(),
+            DockPanel::Details => self.render_detail_panel(view, cx).into_any_element(),
+        }
+    }
+
+    /// The log surrounded by the panels docked to its edges
+    pub(super) fn render_docked_panels(
+        &self,
+        log: AnyElement,
+        view: Entity<CanViewApp>,
+        cx: &App,
+    ) -> impl IntoElement {
+        let mut panels: Vec<(PanelDock, Pixels, AnyElement)> = Vec::new();
+        if self.log_view.read(cx).show_channel_tree {
+            panels.push((
+                self.app_config.preferences.channel_tree_dock,
+                px(220.),
+                self.render_channel_tree(view.clone(), cx)
+                    .into_any_element(),
+            ));
+        }
+        for panel in DockPanel::ALL {
+            let Some(dock) = self.panel_placement(panel).dock() else {
+                continue;
+            };
+            // Docked to the bottom, the details are part of the log column
+            if panel == DockPanel::Details && dock == PanelDock::Bottom {
+                continue;
+            }
+            panels.push((
+                dock,
+                px(panel.width()),
+                self.render_panel(panel, view.clone(), cx),
+            ));
+        }
+
+        let mut left = Vec::new();
+        let mut right = Vec::new();
+        let mut bottom = Vec::new();
+        for (dock, width, panel) in panels {
+            let frame = div()
+                .flex_none()
+                .overflow_hidden()
+                .border_color(rgb(0x2a2a2a));
+            match dock {
+                PanelDock::Left => left.push(frame.w(width).h_full().border_r_1().child(panel)),
+                PanelDock::Right => right.push(frame.w(width).h_full().border_l_1().child(panel)),
+                PanelDock::Bottom => bottom.push(frame.flex_1().h_full().border_r_1().child(panel)),
+            }
+        }
+
+        div()
+            .size_full()
+            .flex()
+            .flex_col()
+            .child(
+                div()
+                    .flex_1()
+                    .w_full()
+                    .flex()
+                    .overflow_hidden()
+                    .children(left)
+                    .child(log)
+                    .children(right),
+            )
+            .when(!bottom.is_empty(), |el| {
+                el.child(
+                    div()
+                        .h(px(220.))
+                        .w_full()
+                        .flex_none()
+                        .flex()
+                        .border_t_1()
+                        .border_color(rgb(0x2a2a2a))
+                        .children(bottom),
+                )
+            })
+    }
+
+    /// The single selected row as a bus frame, with its decoded signals
+    pub(super) fn selected_row_signals(
+        &self,
+        cx: &App,
+    ) -> Option<(ExportFrame, Vec<DecodedSignal>)> {
+        let log = self.log_view.read(cx);
+        if log.selection.len() != 1 {
+            return None;
+        }
+        let index = log.selection.indices().next()?;
+        let frame = ExportFrame::from_log_object(log.messages.get(index)?)?;
+        let signals = crate::handlers::decode_frame(&frame, &self.dbc_channels, &self.ldf_channels);
+        (!signals.is_empty()).then_some((frame, signals))
+    }
+
+    fn render_detail_panel(&self, view: Entity<CanViewApp>, cx: &App) -> impl IntoElement {
+        let panel = div()
+            .id("detail-panel")
+            .size_full()
+            .overflow_y_scroll()
+            .bg(rgb(0x0f0f11));
+        match self.selected_row_signals(cx) {
+            Some((frame, signals)) => panel.child(Self::render_signal_strip(
+                view,
+                frame,
+                signals,
+                self.app_config.preferences.number_format,
+            )),
+            None => panel.child(
+                div()
+                    .p_3()
+                    .text_xs()
+                    .text_color(rgb(0x646473))
+                    .child("Select a decodable row to see its signals"),
+            ),
+        }
+    }
+
+    /// Charted signals with the value each holds at the selected row
+    fn render_watch_panel(&self, cx: &App) -> impl IntoElement {
+        let numbers = self.app_config.preferences.number_format;
+        let time = self.cursor_time(cx);
+
+        div()
+            .id("watch-panel")
+            .size_full()
+            .flex()
+            .flex_col()
+            .overflow_y_scroll()
+            .bg(rgb(0x0f0f11))
+            .text_xs()
+            .child(
+                div()
+                    .h(px(32.))
+                    .px_3()
+                    .flex()
+                    .items_center()
+                    .justify_between()
+                    .text_color(rgb(0x646473))
+                    .child("WATCH")
+                    .child(time.map_or("no row selected".to_string(), |time_ns| {
+                        format!("{:.6} s", time_ns as f64 / 1_000_000_000.0)
+                    })),
+            )
+            .when(self.charts.series.is_empty(), |el| {
+                el.child(
+                    div()
+                        .px_3()
+                        .text_color(rgb(0x646473))
+                        .child("Chart signals to watch their values at the selected row"),
+                )
+            })
+            .children(self.charts.series.iter().enumerate().map(|(i, series)| {
+                let signal = &series.signal;
+                let value = time
+                    .and_then(|time_ns| series.value_at(time_ns))
+                    .map_or("–".to_string(), |value| {
+                        format_chart_value(numbers, value, signal.decimals, &signal.unit)
+                    });
+                div()
+                    .px_3()
+                    .py_1()
+                    .flex()
+                    .items_center()
+                    .gap_2()
+                    .child(
+                        div()
+                            .flex_none()
+                            .size(px(8.))
+                            .rounded(px(4.))
+                            .bg(rgb(chart_color(i))),
+                    )
+                    .child(
+                        div()
+                            .flex_1()
+                            .overflow_hidden()
+                            .whitespace_nowrap()
+                            .text_color(rgb(0x9ca3af))
+                            .child(signal.label()),
+                    )
+                    .child(div().text_color(rgb(0xd1d5db)).child(value))
+            }))
+    }
+}
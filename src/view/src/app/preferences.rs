@@ -0,0 +1,351 @@
+//! The preferences dialog
+
+use super::panels::DockPanel;
+use super::state::CanViewApp;
+use crate::models::preferences::{
+    CaptureHistory, DATA_FONTS, DecimalSeparator, ExportFormat, IdBase, Language, MAX_ROW_HEIGHT,
+    MAX_SIGNAL_LINES, MIN_ROW_HEIGHT, PanelDock, PanelPlacement, Preferences, SignalPrecision,
+    Theme, TimeZoneDisplay, TimestampFormat, TimestampOrder, TimestampPrecision,
+};
+use crate::ui::components::Modal;
+use gpui::{prelude::*, *};
+
+impl CanViewApp {
+    pub(super) fn render_preferences_modal(
+        &self,
+        view: Entity<CanViewApp>,
+        on_close: impl Fn(&mut Window, &mut App) + Clone + 'static,
+    ) -> Modal {
+        let preferences = &self.app_config.preferences;
+        let row_heights = [MIN_ROW_HEIGHT, 20.0, 22.0, 24.0, 28.0, MAX_ROW_HEIGHT];
+
+        Modal::new("preferences-modal")
+            .title("Preferences")
+            .width(px(480.))
+            .child(Self::render_preference_choice(
+                view.clone(),
+                "Timestamp",
+                TimestampFormat::ALL.map(|format| (format, format.label().into())),
+                preferences.timestamp_format,
+                |preferences, value| preferences.timestamp_format = value,
+            ))
+            .child(Self::render_preference_choice(
+                view.clone(),
+                "Time precision",
+                TimestampPrecision::ALL.map(|precision| (precision, precision.label().into())),
+                preferences.timestamp_precision,
+                |preferences, value| preferences.timestamp_precision = value,
+            ))
+            .child(Self::render_preference_choice(
+                view.clone(),
+                "Time zone",
+                TimeZoneDisplay::ALL.map(|zone| (zone, zone.label().into())),
+                preferences.time_zone,
+                |preferences, value| preferences.time_zone = value,
+            ))
+            .child(Self::render_preference_choice(
+                view.clone(),
+                "Logger clock",
+                [
+                    (None, "This PC".into()),
+                    (Some(0), "UTC".into()),
+                    (Some(60), "UTC+1".into()),
+                    (Some(120), "UTC+2".into()),
+                    (Some(480), "UTC+8".into()),
+                    (Some(-300), "UTC-5".into()),
+                ],
+                preferences.logger_utc_offset_min,
+                |preferences, value| preferences.logger_utc_offset_min = value,
+            ))
+            .child(Self::render_preference_choice(
+                view.clone(),
+                "ID base",
+                IdBase::ALL.map(|base| (base, base.label().into())),
+                preferences.id_base,
+                |preferences, value| preferences.id_base = value,
+            ))
+            .child(Self::render_preference_choice(
+                view.clone(),
+                "Row height",
+                row_heights.map(|height| (height, format!("{}", height).into())),
+                preferences.row_height(),
+                |preferences, value| preferences.row_height = value,
+            ))
+            .child(Self::render_preference_choice(
+                view.clone(),
+                "Signal lines",
+                [1, 2, 3, MAX_SIGNAL_LINES].map(|lines| (lines, format!("{}", lines).into())),
+                preferences.signal_lines(),
+                |preferences, value| preferences.signal_lines = value,
+            ))
+            .child(Self::render_preference_choice(
+                view.clone(),
+                "Decimal mark",
+                DecimalSeparator::ALL.map(|separator| (separator, separator.label().into())),
+                preferences.number_format.decimal_separator,
+                |preferences, value| preferences.number_format.decimal_separator = value,
+            ))
+            .child(Self::render_preference_choice(
+                view.clone(),
+                "Signal decimals",
+                SignalPrecision::ALL.map(|precision| (precision, precision.label().into())),
+                preferences.number_format.precision,
+                |preferences, value| preferences.number_format.precision = value,
+            ))
+            .child(Self::render_preference_choice(
+                view.clone(),
+                "Data font",
+                DATA_FONTS.map(|font| {
+                    let label = if font.is_empty() { "Default" } else { font };
+                    (font, label.into())
+                }),
+                DATA_FONTS
+                    .into_iter()
+                    .find(|font| *font == preferences.data_font.trim())
+                    .unwrap_or_default(),
+                |preferences, value| preferences.data_font = value.to_string(),
+            ))
+            .child(Self::render_preference_choice(
+                view.clone(),
+                "UI scale",
+                [0.8, 1.0, 1.25, 1.5, 2.0]
+                    .map(|scale| (scale, format!("{:.0}%", scale * 100.0).into())),
+                preferences.ui_scale(),
+                |preferences, value| preferences.ui_scale = value,
+            ))
+            .child(Self::render_preference_choice(
+                view.clone(),
+                "Theme",
+                Theme::ALL.map(|theme| (theme, theme.label().into())),
+                preferences.theme,
+                |preferences, value| preferences.theme = value,
+            ))
+            .child(Self::render_preference_choice(
+                view.clone(),
+                "Language",
+                Language::ALL.map(|language| (language, language.label().into())),
+                preferences.language,
+                |preferences, value| preferences.language = value,
+            ))
+            .child(Self::render_preference_choice(
+                view.clone(),
+                "Export format",
+                ExportFormat::ALL.map(|format| (format, format.label().into())),
+                preferences.default_export_format,
+                |preferences, value| preferences.default_export_format = value,
+            ))
+            .child(Self::render_preference_choice(
+                view.clone(),
+                "Export manifest",
+                [(false, "Off".into()), (true, "SHA-256".into())],
+                preferences.export_manifest,
+                |preferences, value| preferences.export_manifest = value,
+            ))
+            .child(Self::render_preference_choice(
+                view.clone(),
+                "Live history",
+                CaptureHistory::ALL.map(|history| (history, history.label().into())),
+                preferences.capture_history,
+                |preferences, value| preferences.capture_history = value,
+            ))
+            .child(Self::render_preference_choice(
+                view.clone(),
+                "Rotate at size",
+                [
+                    (0, "Off".into()),
+                    (100, "100 MB".into()),
+                    (500, "500 MB".into()),
+                    (1024, "1 GB".into()),
+                ],
+                preferences.rotate_size_mb,
+                |preferences, value| preferences.rotate_size_mb = value,
+            ))
+            .child(Self::render_preference_choice(
+                view.clone(),
+                "Rotate every",
+                [
+                    (0, "Off".into()),
+                    (10, "10 min".into()),
+                    (30, "30 min".into()),
+                    (60, "1 h".into()),
+                ],
+                preferences.rotate_minutes,
+                |preferences, value| preferences.rotate_minutes = value,
+            ))
+            .child(Self::render_preference_choice(
+                view.clone(),
+                "Memory limit",
+                [
+                    (0, "Off".into()),
+                    (1024, "1 GB".into()),
+                    (4096, "4 GB".into()),
+                    (8192, "8 GB".into()),
+                ],
+                preferences.memory_limit_mb,
+                |preferences, value| preferences.memory_limit_mb = value,
+            ))
+            .child(Self::render_preference_choice(
+                view.clone(),
+                "Health summary",
+                [(true, "On open".into()), (false, "Off".into())],
+                preferences.show_health_summary,
+                |preferences, value| preferences.show_health_summary = value,
+            ))
+            .child(Self::render_preference_choice(
+                view.clone(),
+                "Report gaps over",
+                [
+                    (50, "50 ms".into()),
+                    (100, "100 ms".into()),
+                    (500, "500 ms".into()),
+                    (1000, "1 s".into()),
+                ],
+                preferences.health_gap_ms,
+                |preferences, value| preferences.health_gap_ms = value,
+            ))
+            .child(Self::render_preference_choice(
+                view.clone(),
+                "Channel tree",
+                PanelDock::ALL.map(|dock| (dock, dock.label().into())),
+                preferences.channel_tree_dock,
+                |preferences, value| preferences.channel_tree_dock = value,
+            ))
+            .children(DockPanel::ALL.map(|panel| {
+                Self::render_choice_row(
+                    view.clone(),
+                    panel.label(),
+                    PanelPlacement::ALL.map(|placement| (placement, placement.label().into())),
+                    self.panel_placement(panel),
+                    |_| true,
+                    move |app, placement, cx| app.set_panel_placement(panel, placement, cx),
+                )
+            }))
+            .child(Self::render_preference_choice(
+                view.clone(),
+                "Out-of-order times",
+                TimestampOrder::ALL.map(|order| (order, order.label().into())),
+                preferences.timestamp_order,
+                |preferences, value| preferences.timestamp_order = value,
+            ))
+            .footer(
+                div()
+                    .flex()
+                    .gap_2()
+                    .child(
+                        div()
+                            .id("preferences-reset")
+                            .px_3()
+                            .py_1()
+                            .text_xs()
+                            .text_color(rgb(0x9ca3af))
+                            .rounded(px(3.))
+                            .cursor_pointer()
+                            .hover(|style| style.bg(rgb(0x252f3a)))
+                            .on_mouse_down(gpui::MouseButton::Left, move |_event, _window, cx| {
+                                cx.stop_propagation();
+                                view.update(cx, |app, cx| {
+                                    app.set_preferences(Preferences::default(), cx)
+                                });
+                            })
+                            .child("Reset to defaults"),
+                    )
+                    .child(
+                        div()
+                            .id("preferences-done")
+                            .px_3()
+                            .py_1()
+                            .text_xs()
+                            .bg(rgb(0x1a1f2e))
+                            .rounded(px(3.))
+                            .cursor_pointer()
+                            .hover(|style| style.bg(rgb(0x252f3a)))
+                            .on_mouse_down(gpui::MouseButton::Left, {
+                                let on_close = on_close.clone();
+                                move |_event, window, cx| {
+                                    cx.stop_propagation();
+                                    on_close(window, cx);
+                                }
+                            })
+                            .child("Done"),
+                    ),
+            )
+            .on_close(on_close)
+    }
+
+    /// One labelled row of mutually exclusive options in the Preferences dialog
+    pub(super) fn render_preference_choice<T: Copy + PartialEq + 'static, const N: usize>(
+        view: Entity<CanViewApp>,
+        label: &'static str,
+        options: [(T, SharedString); N],
+        current: T,
+        update: fn(&mut Preferences, T),
+    ) -> impl IntoElement {
+        Self::render_choice_row(
+            view,
+            label,
+            options,
+            current,
+            |_| true,
+            move |app, value, cx| {
+                let mut preferences = app.app_config.preferences.clone();
+                update(&mut preferences, value);
+                app.set_preferences(preferences, cx);
+            },
+        )
+    }
+
+    /// Labelled segmented control; disabled options are dimmed and ignore clicks
+    pub(super) fn render_choice_row<T: Copy + PartialEq + 'static>(
+        view: Entity<CanViewApp>,
+        label: &'static str,
+        options: impl IntoIterator<Item = (T, SharedString)>,
+        current: T,
+        is_enabled: impl Fn(T) -> bool,
+        on_pick: impl Fn(&mut CanViewApp, T, &mut Context<CanViewApp>) + 'static,
+    ) -> impl IntoElement {
+        let on_pick = std::rc::Rc::new(on_pick);
+
+        div()
+            .flex()
+            .items_center()
+            .justify_between()
+            .child(div().text_xs().text_color(rgb(0x9ca3af)).child(label))
+            .child(
+                div()
+                    .flex()
+                    .border_1()
+                    .border_color(rgb(0x2a2a2a))
+                    .rounded(px(3.))
+                    .overflow_hidden()
+                    .children(options.into_iter().map(|(value, text)| {
+                        let is_selected = value == current;
+                        let enabled = is_enabled(value);
+                        let view = view.clone();
+                        let on_pick = on_pick.clone();
+                        div()
+                            .px_2()
+                            .py(px(2.))
+                            .text_xs()
+                            .when(is_selected, |el| {
+                                el.bg(rgb(0x1e3a8a)).text_color(rgb(0xffffff))
+                            })
+                            .when(!is_selected && enabled, |el| {
+                                el.text_color(rgb(0x9ca3af))
+                                    .cursor_pointer()
+                                    .hover(|style| style.bg(rgb(0x252f3a)))
+                            })
+                            .when(!enabled, |el| el.text_color(rgb(0x4b5563)))
+                            .when(enabled, |el| {
+                                el.on_mouse_down(
+                                    gpui::MouseButton::Left,
+                                    move |_event, _window, cx| {
+                                        cx.stop_propagation();
+                                        view.update(cx, |app, cx| on_pick(app, value, cx));
+                                    },
+                                )
+                            })
+                            .child(text)
+                    })),
+            )
+    }
+}
@@ -0,0 +1,162 @@
+//! The sequence dialog: frame loss found through rolling counters
+
+use super::state::{AppModal, CanViewApp};
+use crate::handlers::{AnalysisKind, SequenceChecker};
+use crate::ui::components::Modal;
+use gpui::{prelude::*, *};
+use gpui_component::input::Input;
+
+impl CanViewApp {
+    /// Open the sequence counter dialog with the counters of the last run
+    pub fn open_sequence_dialog(&mut self, cx: &mut Context<Self>) {
+        self.sequence_input = None;
+        self.open_modal(AppModal::Sequence, cx);
+    }
+
+    /// Check the marked sequence counters over the log and mark every gap
+    ///
+    /// In live mode the same counters keep being checked as frames arrive.
+    fn run_sequence_check(&mut self, cx: &mut Context<Self>) {
+        let text = self
+            .sequence_input
+            .as_ref()
+            .map(|input| input.read(cx).value().to_string())
+            .unwrap_or_default();
+        let counters = match crate::handlers::parse_sequence_counters(&text) {
+            Ok(counters) => counters,
+            Err(e) => {
+                self.status_msg = format!("❌ {}", e).into();
+                cx.notify();
+                return;
+            }
+        };
+
+        self.cancel_analysis(AnalysisKind::SequenceCheck);
+        let messages = std::sync::Arc::new(self.log_view.read(cx).messages.clone());
+        let dbc_channels = self.dbc_channels.clone();
+        let ldf_channels = self.ldf_channels.clone();
+        let job = self
+            .analysis_pool
+            .submit(AnalysisKind::SequenceCheck, move |_| {
+                crate::handlers::find_sequence_gaps(
+                    &messages,
+                    counters,
+                    &dbc_channels,
+                    &ldf_channels,
+                )
+            });
+        let progress = job.progress().clone();
+        let checked = self.log_view.read(cx).messages.len();
+        self.status_msg = "# Checking sequence counters...".into();
+        self.track_analysis_job(progress.clone(), cx);
+        self.close_modal(AppModal::Sequence, cx);
+
+        cx.spawn(async move |this, cx| {
+            let result = job.await;
+            let _ = this.update(cx, |app, cx| {
+                if progress.is_cancelled() {
+                    return;
+                }
+                match result {
+                    Ok(mut checker) => {
+                        // Frames that arrived live while the check ran
+                        for msg in app.log_view.read(cx).messages.iter().skip(checked) {
+                            checker.feed(msg, &app.dbc_channels, &app.ldf_channels);
+                        }
+                        app.status_msg = format!(
+                            "# {} gaps, {} frames lost",
+                            checker.gaps().len(),
+                            checker.skipped()
+                        )
+                        .into();
+                        app.sequence_checker = checker;
+                    }
+                    Err(e) => app.status_msg = format!("❌ {}", e).into(),
+                }
+                cx.notify();
+            });
+        })
+        .detach();
+    }
+
+    pub(super) fn render_sequence_modal(
+        &self,
+        view: Entity<CanViewApp>,
+        on_close: impl Fn(&mut Window, &mut App) + Clone + 'static,
+    ) -> Modal {
+        let muted = |text: &'static str| div().text_xs().text_color(rgb(0x646473)).child(text);
+        let counters = self.sequence_checker.counters();
+        let gaps = self.sequence_checker.gaps();
+
+        Modal::new("sequence-modal")
+            .title("Sequence counters")
+            .width(px(520.))
+            .child(muted(
+                "Counters separated by ';': 0x123 AliveCounter, 2:0x123 AliveCounter \
+                 (channel 2 only), 0x123 AliveCounter 14 (wraps after 14).",
+            ))
+            .when_some(self.sequence_input.as_ref(), |modal, input| {
+                modal.child(Input::new(input))
+            })
+            .child(muted(
+                "Counters wrap at the signal's maximum unless given. Repeated values \
+                 count as resends; every jump is marked with a red line in the log.",
+            ))
+            .when(!gaps.is_empty(), |modal| {
+                modal.child(
+                    div()
+                        .flex()
+                        .flex_col()
+                        .gap_1()
+                        .text_xs()
+                        .text_color(rgb(0x9ca3af))
+                        .children(counters.iter().enumerate().map(|(index, counter)| {
+                            let (count, skipped) = gaps
+                                .iter()
+                                .filter(|gap| gap.counter == index)
+                                .fold((0, 0), |(count, skipped), gap| {
+                                    (count + 1, skipped + gap.skipped)
+                                });
+                            div().child(format!(
+                                "{}: {} gaps, {} frames lost",
+                                counter, count, skipped
+                            ))
+                        })),
+                )
+            })
+            .footer(
+                div()
+                    .flex()
+                    .gap_2()
+                    .child(Self::render_modal_button(
+                        "sequence-clear",
+                        "Clear",
+                        false,
+                        {
+                            let view = view.clone();
+                            move |_window, cx| {
+                                view.update(cx, |app, cx| {
+                                    app.sequence_checker = SequenceChecker::default();
+                                    app.sequence_input = None;
+                                    app.status_msg = "# Sequence counters cleared".into();
+                                    app.close_modal(AppModal::Sequence, cx);
+                                });
+                            }
+                        },
+                    ))
+                    .child(Self::render_modal_button(
+                        "sequence-cancel",
+                        "Cancel",
+                        false,
+                        on_close.clone(),
+                    ))
+                    .child(Self::render_modal_button("sequence-run", "Check", true, {
+                        let view = view.clone();
+                        move |_window, cx| {
+                            view.update(cx, |app, cx| app.run_sequence_check(cx));
+                        }
+                    })),
+            )
+            .on_close(on_close)
+    }
+}
@@ -3,11 +3,13 @@
 //! This module contains the core application state structures.
 
 use blf::LogObject;
-use gpui::{Bounds, Entity, Pixels, UniformListScrollHandle};
+use gpui::{AnyWindowHandle, Bounds, Entity, Pixels};
 use parser::dbc::DbcDatabase;
 use parser::ldf::LdfDatabase;
-use std::collections::HashMap;
+use std::cell::{Cell, RefCell};
+use std::collections::{BTreeSet, HashMap};
 use std::path::PathBuf;
+use std::rc::Rc;
 
 // Import AppConfig and ChannelMapping from crate root (defined in main.rs)
 use crate::{AppConfig, ChannelMapping, ChannelType};
@@ -15,26 +17,80 @@ use crate::{AppConfig, ChannelMapping, ChannelType};
 // Import the real LibraryManager from the library module
 pub use crate::library::LibraryManager;
 
-// Import DatabaseType for library filtering
-use crate::models::library::DatabaseType;
+use super::panels::DockPanel;
+
+use crate::library::MappingIssue;
+use crate::models::library::{DatabaseType, SignalLibrary};
 
 // Import gpui-component input support
 use gpui_component::input::InputState;
 
+use crate::handlers::{
+    AnalysisPool, BatchJob, BusStats, CaptureState, ChannelTree, ChartState, CorrelationMatrix,
+    ExportScope, FilterState, FilteredRows, FixedRow, FixedRows, FolderWatcher, GpsFix,
+    IdStatsIndex, JobProgress, LoadMode, LogHealth, LogProbe, LogSources, Marker, Minimap,
+    MqttPublisher, RowDetails, RowSelection, RunComparison, SequenceChecker, SignalOverride,
+    SignalRef, SimulatedFrame, StateDefinition, StateTimeline, TimeOffsets, TreeNode, TriggerEvent,
+    TriggerRule, VideoPane, VideoSync,
+};
+use crate::models::preferences::ExportFormat;
+use crate::ui::components::modal::ModalStack;
+use crate::ui::components::virtual_list::{DEFAULT_ROW_HEIGHT, VirtualListHandle};
+
+/// Row height of the ID / channel / type filter dropdowns, in pixels
+pub const FILTER_ROW_HEIGHT: f32 = 24.0;
+
 /// Application view modes
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum AppView {
     LogView,
     ConfigView,
     LibraryView,
+    GpsView,
+    StatsView,
+    ChartView,
+    StatesView,
+    BatchView,
 }
 
-/// State for tracking scrollbar drag operation
-#[derive(Clone)]
-pub struct ScrollbarDragState {
-    pub start_y: Pixels,
-    pub start_scroll_offset: f32,
-    pub filtered_count: usize, // Number of filtered messages at drag start
+/// Dialogs shown in the modal layer
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AppModal {
+    About,
+    Health,
+    Preferences,
+    Export,
+    TimeSync,
+    Triggers,
+    Find,
+    Sequence,
+    States,
+    Markers,
+    Highlights,
+    Compare,
+    Correlation,
+    Simulation,
+    Replay,
+    Capture,
+    AssignVersion,
+    VersionNotes,
+    DuplicateImport,
+    MappingCheck,
+    Mqtt,
+    WatchFolder,
+    MemoryLimit,
+    OpenLog,
+}
+
+/// How the Open log dialog opens a log while another one is loaded
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OpenAction {
+    /// Drop the loaded log, its filters and markers
+    Replace,
+    /// Add the log's frames to the loaded ones on one timeline
+    Merge,
+    /// Keep the loaded log and open the new one in another window
+    NewWindow,
 }
 
 /// Main application state
@@ -42,13 +98,135 @@ pub struct CanViewApp {
     // View state
     pub current_view: AppView,
 
-    // Data
-    pub messages: Vec<LogObject>,
+    // Modal layer (topmost entry receives keyboard input)
+    pub modals: ModalStack<AppModal>,
+    pub modal_focus: Option<gpui::FocusHandle>,
+
+    // Export dialog
+    pub export_format: ExportFormat,
+    pub export_plugin: Option<usize>, // Index into `app_config.export_plugins`, replaces the format
+    pub export_scope: ExportScope,
+    pub export_decoded: bool,
+    pub export_range_start_input: Option<Entity<InputState>>,
+    pub export_range_end_input: Option<Entity<InputState>>,
+    pub export_progress: Option<(usize, usize)>, // (rows written, total) while running
+
+    // The log and its list, the filters on it and the signal libraries; each is an
+    // entity of its own, so panels and tests can work on one without the others
+    pub log_view: Entity<LogViewState>,
+    pub filters: Entity<FilterState>,
+    pub library: Entity<LibraryState>,
+
+    pub time_offsets: TimeOffsets, // Clock skew correction per channel
+
+    // Time sync dialog
+    pub time_offset_inputs: HashMap<u16, Entity<InputState>>,
+
+    // GPS track, from GPS events in the log or an imported NMEA/CSV file
+    pub gps_fixes: Vec<GpsFix>,
+    pub gps_file: Option<PathBuf>,
+    pub gps_list: VirtualListHandle,
+
+    // Video recorded alongside the log, shown in the video pane at the time cursor
+    pub video: Option<VideoSync>,
+    pub video_pane: VideoPane,
+    /// Cursor time and wall clock when the video started playing
+    pub video_playback: Option<(u64, std::time::Instant)>,
+    pub show_video_bar: bool,
+    pub video_position_input: Option<Entity<InputState>>,
+
+    // Trigger rules and the segment boundaries they produced
+    pub trigger_rules: Vec<TriggerRule>,
+    pub trigger_pre_s: f64,
+    pub trigger_post_s: f64,
+    pub trigger_auto_save: bool,
+    pub trigger_events: Vec<TriggerEvent>,
+    pub trigger_rules_input: Option<Entity<InputState>>,
+    pub trigger_pre_input: Option<Entity<InputState>>,
+    pub trigger_post_input: Option<Entity<InputState>>,
+
+    // Find dialog: the condition searched for by "Find next"
+    pub find_rule: Option<TriggerRule>,
+    pub find_input: Option<Entity<InputState>>,
+
+    // Sequence counters and the gaps found in them
+    pub sequence_checker: SequenceChecker,
+    pub sequence_input: Option<Entity<InputState>>,
+
+    // State machines followed over the log and their timelines, in the same order
+    pub state_definitions: Vec<StateDefinition>,
+    pub state_definitions_input: Option<Entity<InputState>>,
+    pub state_timelines: Vec<StateTimeline>,
+
+    // Triage summary of the loaded log
+    pub log_health: Option<LogHealth>,
+    // Density strip above the log list; only built for loaded files
+    pub minimap: Option<Minimap>,
+    pub highlights_input: Option<Entity<InputState>>,
+
+    // Measurement markers in time order, saved as CANoe global markers
+    pub markers: Vec<Marker>,
+    pub markers_unsaved: bool, // Markers changed since the log was loaded or they were saved
+    pub marker_name_input: Option<Entity<InputState>>,
+
+    // A/B comparison against a second log: signals to compare and the last report
+    pub compare_signals: Vec<SignalRef>,
+    pub compare_signals_input: Option<Entity<InputState>>,
+    pub comparison: Option<(PathBuf, RunComparison)>,
+    pub compare_running: bool,
+
+    // Correlation of resampled signals over a time range and the last result
+    pub correlation_signals: Vec<SignalRef>,
+    pub correlation_signals_input: Option<Entity<InputState>>,
+    pub correlation_start_input: Option<Entity<InputState>>,
+    pub correlation_end_input: Option<Entity<InputState>>,
+    pub correlation_step_input: Option<Entity<InputState>>,
+    pub correlation_step_ms: f64,
+    pub correlation: Option<CorrelationMatrix>,
+
+    // Analyses running on the worker pool, listed with their progress in the status bar
+    pub analysis_pool: AnalysisPool,
+    pub analysis_jobs: Vec<JobProgress>,
+
+    // Live mode: the simulation / replay / capture source, running on its own thread,
+    // the history kept for "Save" and the running recording, if any
+    pub capture: Entity<CaptureState>,
+    pub capture_interface_input: Option<Entity<InputState>>,
+    pub capture_device_menu: Option<usize>, // Mapping whose device picker is open
+    pub simulation_frames: Vec<SimulatedFrame>,
+    pub simulation_input: Option<Entity<InputState>>,
+    pub signal_overrides: Vec<SignalOverride>, // Re-encoded into outgoing frames
+    pub signal_overrides_input: Option<Entity<InputState>>,
+    // MQTT publisher of decoded signals in live mode, settings in `app_config.mqtt`
+    pub mqtt_publisher: Option<MqttPublisher>,
+    pub mqtt_broker_input: Option<Entity<InputState>>,
+    pub mqtt_topic_input: Option<Entity<InputState>>,
+    pub mqtt_signals_input: Option<Entity<InputState>>,
+
+    // Folder watched for new logs, settings in `app_config.watch`
+    pub folder_watcher: Option<FolderWatcher>,
+    pub watch_queue: Vec<PathBuf>, // New logs waiting to be opened, oldest first
+    pub watch_folder_input: Option<Entity<InputState>>,
+
+    // Batch tab: files, pipeline settings and per-file progress
+    pub batch_jobs: Vec<BatchJob>,
+    pub batch_format: ExportFormat,
+    pub batch_decoded: bool,
+    pub batch_destination: Option<PathBuf>,
+    pub batch_filter_input: Option<Entity<InputState>>,
+    pub batch_running: bool,
+
     pub dbc_channels: HashMap<u16, DbcDatabase>,
     pub ldf_channels: HashMap<u16, LdfDatabase>,
     pub app_config: AppConfig,
-    pub selected_signals: Vec<String>,
+    pub charts: ChartState, // Charted signals, their time span and cursors
+    pub chart_plot: Rc<Cell<(f32, f32)>>, // Left edge and width of the plots, measured when painted
+    pub panel_windows: HashMap<DockPanel, AnyWindowHandle>, // Windows of the floating panels
     pub start_time: Option<chrono::NaiveDateTime>,
+    pub log_path: Option<PathBuf>, // BLF the messages were loaded from; none in live mode
+    pub load_mode: LoadMode,       // How much of the loaded BLF is held in memory
+    pub pending_load: Option<(PathBuf, u64)>, // Log over the memory limit and its estimate
+    pub pending_open: Option<(PathBuf, LogProbe)>, // Log picked in the Open dialog, not yet loaded
 
     // Configuration
     pub config_dir: Option<PathBuf>,
@@ -63,37 +241,171 @@ pub struct CanViewApp {
     pub saved_window_bounds: Option<Bounds<Pixels>>,
     pub display_bounds: Option<Bounds<Pixels>>,
 
+    // Status message
+    pub status_msg: gpui::SharedString,
+
+    // Channel configuration dialog state
+    pub show_channel_config_dialog: bool,
+    pub new_channel_id: String,
+    pub new_channel_name: String,
+    pub new_channel_db_path: String,
+    pub editing_channel_index: Option<usize>, // None for adding new, Some(index) for editing
+    pub channel_id_input: Option<Entity<InputState>>,
+    pub channel_name_input: Option<Entity<InputState>>,
+    pub show_add_channel_input: bool, // Controls inline input display in channel list
+    pub channel_db_path_input: Option<Entity<InputState>>, // For database path input
+    pub new_channel_type: ChannelType, // Store selected channel type (CAN/LIN)
+}
+
+/// Library dialog type
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LibraryDialogType {
+    Create,
+    AddVersion,
+    QuickImport,
+}
+
+/// The loaded log and the state of the list showing it
+pub struct LogViewState {
+    pub messages: Vec<LogObject>,
+    pub selection: RowSelection,              // Indices into `messages`
+    pub id_stats: IdStatsIndex,               // Built in the background when a log is loaded
+    pub bus_stats: BusStats,                  // Per-channel rates, updated as messages arrive
+    pub log_summary: blf::LogSummary,         // Object counts, time span and channels of `messages`
+    pub id_index: blf::IdIndex,               // Positions in `messages` of each (channel, ID)
+    pub log_sources: LogSources, // Files a merged log was read from and the file of each message
+    pub row_details: Rc<RefCell<RowDetails>>, // Decoded signals of the list rows
+    pub filtered_rows: RefCell<FilteredRows>, // Rows passing the filters, kept between frames
+    pub fixed_view: bool,        // Log view shows one row per ID instead of the trace
+    pub fixed_rows: RefCell<FixedRows>, // IDs of the fixed view, kept between frames
+
     // Scroll state
-    pub list_scroll_handle: UniformListScrollHandle,
-    pub scrollbar_drag_state: Option<ScrollbarDragState>,
-    pub scroll_offset: Pixels,
-    pub list_container_height: f32,
+    pub message_list: VirtualListHandle,
 
     // Display settings
     pub id_display_decimal: bool, // true for decimal, false for hexadecimal
 
-    // ID filter
-    pub id_filter: Option<u32>,
+    // ID filter input
     pub id_filter_text: gpui::SharedString,
     pub show_id_filter_input: bool,
 
     // Filter dropdown state
-    pub filter_scroll_offset: Pixels,
-    pub filter_scroll_handle: UniformListScrollHandle,
-    pub mouse_over_filter_dropdown: bool,
-    pub dropdown_just_opened: bool,
+    pub id_filter_list: VirtualListHandle,
 
-    // Channel filter
-    pub channel_filter: Option<u16>,
+    // Channel filter input
     pub channel_filter_text: gpui::SharedString,
     pub show_channel_filter_input: bool,
-    pub channel_filter_scroll_offset: Pixels,
-    pub channel_filter_scroll_handle: UniformListScrollHandle,
+    pub channel_filter_list: VirtualListHandle,
 
-    // Status message
-    pub status_msg: gpui::SharedString,
+    // Message type filter dropdown
+    pub show_type_filter_input: bool,
+    pub type_filter_list: VirtualListHandle,
+
+    // Bus / channel / ID tree sidebar; unchecked nodes are filtered out
+    pub channel_tree: ChannelTree,
+    pub tree_toggled: BTreeSet<TreeNode>, // Buses start expanded, channels collapsed; these are flipped
+    pub show_channel_tree: bool,
+}
 
-    // Library management
+impl LogViewState {
+    pub fn new() -> Self {
+        Self {
+            messages: Vec::new(),
+            selection: RowSelection::new(),
+            id_stats: IdStatsIndex::default(),
+            bus_stats: BusStats::new(),
+            log_summary: blf::LogSummary::default(),
+            id_index: blf::IdIndex::default(),
+            log_sources: LogSources::default(),
+            row_details: Default::default(),
+            filtered_rows: Default::default(),
+            fixed_view: false,
+            fixed_rows: Default::default(),
+            message_list: VirtualListHandle::new(DEFAULT_ROW_HEIGHT),
+            id_display_decimal: true,
+            id_filter_text: gpui::SharedString::from(""),
+            show_id_filter_input: false,
+            id_filter_list: VirtualListHandle::new(FILTER_ROW_HEIGHT),
+            channel_filter_text: gpui::SharedString::from(""),
+            show_channel_filter_input: false,
+            channel_filter_list: VirtualListHandle::new(FILTER_ROW_HEIGHT),
+            show_type_filter_input: false,
+            type_filter_list: VirtualListHandle::new(FILTER_ROW_HEIGHT),
+            channel_tree: ChannelTree::default(),
+            tree_toggled: BTreeSet::new(),
+            show_channel_tree: false,
+        }
+    }
+
+    /// Drop the log and everything derived from it
+    pub fn clear(&mut self) {
+        self.messages.clear();
+        self.filtered_rows.borrow_mut().clear();
+        self.id_stats = IdStatsIndex::default();
+        self.bus_stats.clear();
+        self.log_summary = blf::LogSummary::default();
+        self.id_index = blf::IdIndex::default();
+        self.log_sources = LogSources::default();
+        self.channel_tree.clear();
+    }
+
+    /// Append frames arriving in live mode and index them
+    pub fn extend(&mut self, batch: Vec<LogObject>) {
+        self.bus_stats.extend(&batch);
+        self.log_summary.extend(&batch);
+        self.id_index.extend(&batch);
+        self.channel_tree.extend(&batch);
+        self.messages.extend(batch);
+    }
+
+    /// Whether the children of `node` are shown in the tree sidebar
+    pub fn is_tree_expanded(&self, node: TreeNode) -> bool {
+        matches!(node, TreeNode::Bus(_)) != self.tree_toggled.contains(&node)
+    }
+
+    /// Show the children of `node` in the tree sidebar if they are hidden, or hide them
+    pub fn toggle_tree_expanded(&mut self, node: TreeNode) {
+        if !self.tree_toggled.remove(&node) {
+            self.tree_toggled.insert(node);
+        }
+    }
+
+    /// Indices (into `messages`) of the rows passing `filters`
+    pub fn filtered_indices(&self, filters: &FilterState) -> Rc<Vec<usize>> {
+        self.filtered_rows.borrow_mut().get(
+            filters,
+            &self.messages,
+            &self.id_index,
+            &self.log_sources,
+        )
+    }
+
+    /// IDs of the fixed view with their latest frames, in the order shown
+    pub fn fixed_view_rows(&self, filters: &FilterState) -> Vec<FixedRow> {
+        let rows = self.filtered_indices(filters);
+        let generation = self.filtered_rows.borrow().generation();
+        let mut fixed = self.fixed_rows.borrow_mut();
+        fixed.update(&self.messages, &rows, generation);
+        fixed.rows()
+    }
+
+    /// Indices (into `messages`) of the rows shown, in the order shown
+    pub fn displayed_rows(&self, filters: &FilterState) -> Rc<Vec<usize>> {
+        if self.fixed_view {
+            Rc::new(
+                self.fixed_view_rows(filters)
+                    .iter()
+                    .map(|row| row.index)
+                    .collect(),
+            )
+        } else {
+            self.filtered_indices(filters)
+        }
+    }
+}
+
+/// Signal libraries and the state of the library view and its dialogs
+pub struct LibraryState {
     pub library_manager: LibraryManager,
     pub selected_library_id: Option<String>,
     pub selected_version_id: Option<String>, // Add selected version ID
@@ -105,86 +417,34 @@ pub struct CanViewApp {
     pub new_version_cursor_position: usize,
     pub show_library_dialog: bool,
 
-    // gpui-component input support for library management
+    // gpui-component inputs of the library dialogs
     pub library_name_input: Option<Entity<InputState>>,
     pub version_name_input: Option<Entity<InputState>>,
     pub library_dialog_type: LibraryDialogType,
     pub library_search_query: String,
     pub library_filter_type: Option<DatabaseType>,
-
-    // Channel configuration dialog state
-    pub show_channel_config_dialog: bool,
-    pub new_channel_id: String,
-    pub new_channel_name: String,
-    pub new_channel_db_path: String,
-    pub editing_channel_index: Option<usize>, // None for adding new, Some(index) for editing
-    pub channel_id_input: Option<Entity<InputState>>,
-    pub channel_name_input: Option<Entity<InputState>>,
-    pub show_add_channel_input: bool, // Controls inline input display in channel list
-    pub channel_db_path_input: Option<Entity<InputState>>, // For database path input
-    pub new_channel_type: ChannelType, // Store selected channel type (CAN/LIN)
-    pub pending_file_path: Option<std::sync::mpsc::Receiver<Option<String>>>, // For file dialog result
-
-    // Deprecated: These fields are kept for backward compatibility during migration
-    #[deprecated(note = "Use library_name_input instead")]
-    pub focused_library_input: Option<String>,
-    #[deprecated(note = "Use library_name_input instead")]
-    pub is_editing_library_name: bool,
-    #[deprecated(note = "Use library_name_input instead")]
-    pub library_input_state: crate::ui::components::ime_text_input::ImeTextInputState,
-    #[deprecated(note = "Not needed with gpui-component Input")]
-    pub library_focus_handle: Option<gpui::FocusHandle>,
-    #[deprecated(note = "Not needed with gpui-component Input")]
-    pub ime_handler_registered: bool,
+    pub library_search_input: Option<Entity<InputState>>,
+    // Library version being assigned to channels, as (library id, version name)
+    pub version_assignment: Option<(String, String)>,
+    pub assign_channels: BTreeSet<u16>,
+    // Library version whose author and release notes are being edited
+    pub version_notes_target: Option<(String, String)>,
+    pub version_author_input: Option<Entity<InputState>>,
+    pub version_notes_input: Option<Entity<InputState>>,
+    // Database file being imported and the (library id, version name) already holding it
+    pub duplicate_import: Option<(PathBuf, String, String)>,
+    // Problems found by the last "Validate" of the channel mappings
+    pub mapping_issues: Vec<MappingIssue>,
+    // Bumped by each background database load; older loads stop installing
+    pub database_load_generation: u64,
 }
 
-/// Library dialog type
-#[derive(Debug, Clone, Copy, PartialEq)]
-pub enum LibraryDialogType {
-    Create,
-    AddVersion,
-    QuickImport,
-}
-
-impl CanViewApp {
-    /// Create a new CanViewApp instance with default state
-    pub fn new_state() -> Self {
+impl LibraryState {
+    pub fn new() -> Self {
         Self {
-            current_view: AppView::LogView,
-            messages: Vec::new(),
-            status_msg: gpui::SharedString::from(""),
-            dbc_channels: HashMap::new(),
-            ldf_channels: HashMap::new(),
-            app_config: AppConfig::default(),
-            selected_signals: Vec::new(),
-            start_time: None,
-            config_dir: None,
-            config_file_path: None,
-            signal_storage: crate::library::SignalLibraryStorage::new().ok(),
-            is_maximized: false,
-            is_streaming_mode: false,
-            saved_window_bounds: None,
-            display_bounds: None,
-            list_scroll_handle: UniformListScrollHandle::new(),
-            scrollbar_drag_state: None,
-            scroll_offset: gpui::px(0.0),
-            list_container_height: 850.0,
-            id_display_decimal: false,
-            id_filter: None,
-            id_filter_text: gpui::SharedString::from(""),
-            show_id_filter_input: false,
-            filter_scroll_offset: gpui::px(0.0),
-            filter_scroll_handle: UniformListScrollHandle::new(),
-            mouse_over_filter_dropdown: false,
-            dropdown_just_opened: false,
-            channel_filter: None,
-            channel_filter_text: gpui::SharedString::from(""),
-            show_channel_filter_input: false,
-            channel_filter_scroll_offset: gpui::px(0.0),
-            channel_filter_scroll_handle: UniformListScrollHandle::new(),
             library_manager: LibraryManager::new(),
             selected_library_id: None,
-            selected_version_id: None, // Initialize selected version ID
+            selected_version_id: None,
             new_library_name: String::new(),
             library_cursor_position: 0,
             library_versions_expanded: true,
@@ -192,31 +452,36 @@ impl CanViewApp {
             new_version_name: String::new(),
             new_version_cursor_position: 0,
             show_library_dialog: false,
+            library_name_input: None,
+            version_name_input: None,
             library_dialog_type: LibraryDialogType::Create,
             library_search_query: String::new(),
             library_filter_type: None,
-            // gpui-component input support
-            library_name_input: None, // Will be initialized when cx is available
-            version_name_input: None, // Will be initialized when cx is available
-            // Channel configuration dialog
-            show_channel_config_dialog: false,
-            new_channel_id: String::new(),
-            new_channel_name: String::new(),
-            new_channel_db_path: String::new(),
-            editing_channel_index: None,
-            channel_id_input: None, // Will be initialized when cx is available
-            channel_name_input: None, // Will be initialized when cx is available
-            show_add_channel_input: false,
-            channel_db_path_input: None, // Will be initialized when cx is available
-            new_channel_type: ChannelType::CAN, // Default to CAN
-            pending_file_path: None,     // For file dialog result
-            // Deprecated fields for backward compatibility
-            focused_library_input: None,
-            is_editing_library_name: false,
-            library_input_state: crate::ui::components::ime_text_input::ImeTextInputState::default(
-            ),
-            library_focus_handle: None,
-            ime_handler_registered: false,
+            library_search_input: None,
+            version_assignment: None,
+            assign_channels: BTreeSet::new(),
+            version_notes_target: None,
+            version_author_input: None,
+            version_notes_input: None,
+            duplicate_import: None,
+            mapping_issues: Vec::new(),
+            database_load_generation: 0,
         }
     }
+
+    /// Libraries matching the search text and type filter, with only their matching versions
+    pub fn search_results(&self) -> Vec<SignalLibrary> {
+        self.library_manager
+            .libraries()
+            .iter()
+            .filter_map(|library| {
+                library.search(&self.library_search_query, self.library_filter_type)
+            })
+            .collect()
+    }
+
+    /// Whether the search text or type filter hides any library
+    pub fn is_filtered(&self) -> bool {
+        !self.library_search_query.trim().is_empty() || self.library_filter_type.is_some()
+    }
 }
@@ -0,0 +1,327 @@
+//! The States view: named states tracked from signal values, as timelines
+
+use super::state::{AppModal, AppView, CanViewApp};
+use crate::ui::components::Modal;
+use blf::LogObject;
+use gpui::{prelude::*, *};
+use gpui_component::input::Input;
+
+impl CanViewApp {
+    /// Open the states dialog with the current definitions
+    pub fn open_states_dialog(&mut self, cx: &mut Context<Self>) {
+        self.state_definitions_input = None;
+        self.open_modal(AppModal::States, cx);
+    }
+
+    /// Follow the state machines entered in the dialog and show their timelines
+    fn apply_state_definitions(&mut self, cx: &mut Context<Self>) {
+        let text = self
+            .state_definitions_input
+            .as_ref()
+            .map(|input| input.read(cx).value().to_string())
+            .unwrap_or_default();
+        match crate::handlers::parse_state_definitions(&text) {
+            Ok(definitions) => {
+                self.state_definitions = definitions;
+                self.refresh_state_timelines(cx);
+                self.status_msg =
+                    format!("◧ {} state machines tracked", self.state_definitions.len()).into();
+                self.current_view = AppView::StatesView;
+                self.close_modal(AppModal::States, cx);
+            }
+            Err(e) => {
+                self.status_msg = format!("❌ {}", e).into();
+                cx.notify();
+            }
+        }
+    }
+
+    /// Rebuild the state timelines from the messages held now
+    fn refresh_state_timelines(&mut self, cx: &App) {
+        let messages = &self.log_view.read(cx).messages;
+        self.state_timelines = self
+            .state_definitions
+            .iter()
+            .map(|definition| {
+                crate::handlers::track_states(
+                    messages,
+                    definition,
+                    &self.dbc_channels,
+                    &self.ldf_channels,
+                )
+            })
+            .collect();
+    }
+
+    /// One timeline lane per state machine, with duration statistics per state
+    pub(super) fn render_states_view(
+        &self,
+        view: Entity<CanViewApp>,
+        cx: &App,
+    ) -> impl IntoElement {
+        const STATE_COLORS: [u32; 8] = [
+            0x3b82f6, 0x22c55e, 0xf59e0b, 0xef4444, 0xa855f7, 0x14b8a6, 0xec4899, 0x84cc16,
+        ];
+        let columns: [(&str, f32); 6] = [
+            ("STATE", 160.),
+            ("ENTRIES", 90.),
+            ("TOTAL (s)", 110.),
+            ("SHARE", 80.),
+            ("MIN (s)", 110.),
+            ("MEAN / MAX (s)", 160.),
+        ];
+        let seconds = |ns: u64| format!("{:.3}", ns as f64 / 1_000_000_000.0);
+        let messages = &self.log_view.read(cx).messages;
+        let log_start = messages.first().map_or(0, LogObject::timestamp);
+        let log_end = messages.last().map_or(0, LogObject::timestamp);
+        let log_span = log_end.saturating_sub(log_start).max(1) as f32;
+        let toolbar_button =
+            |id: &'static str,
+             label: &'static str,
+             on_click: fn(&mut CanViewApp, &mut Context<CanViewApp>)| {
+                let view = view.clone();
+                div()
+                    .id(id)
+                    .px_3()
+                    .py_1()
+                    .text_xs()
+                    .rounded(px(3.))
+                    .cursor_pointer()
+                    .text_color(rgb(0x9ca3af))
+                    .bg(rgb(0x1a1a1a))
+                    .hover(|style| style.bg(rgb(0x252f3a)).text_color(rgb(0xcdd6f4)))
+                    .on_mouse_down(MouseButton::Left, move |_event, _window, cx| {
+                        view.update(cx, |app, cx| on_click(app, cx));
+                    })
+                    .child(label)
+            };
+
+        let lanes = self.state_timelines.iter().map(|timeline| {
+            let stats = timeline.stats();
+            let covered = timeline
+                .end_ns()
+                .zip(timeline.start_ns())
+                .map_or(0, |(end, start)| end - start)
+                .max(1);
+            let color = |state: &str| {
+                let index = stats
+                    .iter()
+                    .position(|stats| stats.state == state)
+                    .unwrap_or(0);
+                rgb(STATE_COLORS[index % STATE_COLORS.len()])
+            };
+            let segments: Vec<AnyElement> = timeline
+                .spans
+                .iter()
+                .map(|span| {
+                    let left = span.start_ns.saturating_sub(log_start) as f32 / log_span;
+                    let width = span.duration_ns() as f32 / log_span;
+                    div()
+                        .absolute()
+                        .top_0()
+                        .bottom_0()
+                        .left(relative(left))
+                        .w(relative(width))
+                        .min_w(px(1.))
+                        .px_1()
+                        .overflow_hidden()
+                        .whitespace_nowrap()
+                        .bg(color(&span.state))
+                        .text_xs()
+                        .text_color(rgb(0x0c0c0e))
+                        .child(span.state.clone())
+                        .into_any_element()
+                })
+                .collect();
+            let rows = stats.iter().map(|stats| {
+                let cells = [
+                    stats.state.clone(),
+                    stats.entries.to_string(),
+                    seconds(stats.total_ns),
+                    format!("{:.1}%", stats.total_ns as f64 * 100.0 / covered as f64),
+                    seconds(stats.min_ns),
+                    format!("{} / {}", seconds(stats.mean_ns()), seconds(stats.max_ns)),
+                ];
+                div()
+                    .flex()
+                    .h(px(22.))
+                    .items_center()
+                    .border_b_1()
+                    .border_color(rgb(0x2a2a2a))
+                    .text_xs()
+                    .text_color(rgb(0xd1d5db))
+                    .children(cells.into_iter().zip(columns).enumerate().map(
+                        |(index, (text, (_, width)))| {
+                            div()
+                                .w(px(width))
+                                .px_3()
+                                .flex_shrink_0()
+                                .flex()
+                                .items_center()
+                                .gap_2()
+                                .when(index == 0, |el| {
+                                    el.child(
+                                        div().size(px(8.)).rounded(px(2.)).bg(color(&stats.state)),
+                                    )
+                                })
+                                .child(text)
+                        },
+                    ))
+            });
+
+            div()
+                .flex()
+                .flex_col()
+                .border_b_1()
+                .border_color(rgb(0x2a2a2a))
+                .child(
+                    div()
+                        .h(px(32.))
+                        .px_3()
+                        .flex()
+                        .items_center()
+                        .gap_3()
+                        .child(
+                            div()
+                                .w(px(140.))
+                                .flex_shrink_0()
+                                .text_xs()
+                                .font_weight(FontWeight::MEDIUM)
+                                .text_color(rgb(0xcdd6f4))
+                                .child(timeline.name.clone()),
+                        )
+                        .child(
+                            div()
+                                .flex_1()
+                                .h(px(20.))
+                                .relative()
+                                .overflow_hidden()
+                                .rounded(px(3.))
+                                .bg(rgb(0x141414))
+                                .children(segments)
+                                .when(timeline.spans.is_empty(), |el| {
+                                    el.px_2()
+                                        .text_xs()
+                                        .text_color(rgb(0x6b7280))
+                                        .child("Signal not found in the log")
+                                }),
+                        ),
+                )
+                .child(
+                    div()
+                        .h(px(24.))
+                        .flex()
+                        .items_center()
+                        .bg(rgb(0x141414))
+                        .text_xs()
+                        .font_weight(FontWeight::MEDIUM)
+                        .text_color(rgb(0x646473))
+                        .children(columns.iter().map(|(title, width)| {
+                            div().w(px(*width)).px_3().flex_shrink_0().child(*title)
+                        })),
+                )
+                .children(rows)
+        });
+
+        div()
+            .size_full()
+            .flex()
+            .flex_col()
+            .child(
+                // Toolbar
+                div()
+                    .h(px(36.))
+                    .px_3()
+                    .flex()
+                    .items_center()
+                    .justify_between()
+                    .border_b_1()
+                    .border_color(rgb(0x2a2a2a))
+                    .child(div().text_xs().text_color(rgb(0x9ca3af)).child(format!(
+                        "{} state machines · {} s of log",
+                        self.state_timelines.len(),
+                        seconds(log_end.saturating_sub(log_start))
+                    )))
+                    .child(
+                        div()
+                            .flex()
+                            .gap_2()
+                            .child(toolbar_button("states-refresh", "Refresh", |app, cx| {
+                                app.refresh_state_timelines(cx);
+                                cx.notify();
+                            }))
+                            .child(toolbar_button(
+                                "states-edit",
+                                "Edit states…",
+                                |app, cx| app.open_states_dialog(cx),
+                            )),
+                    ),
+            )
+            .child(
+                div()
+                    .id("state-lanes")
+                    .flex_1()
+                    .flex()
+                    .flex_col()
+                    .overflow_y_scroll()
+                    .when(self.state_timelines.is_empty(), |parent| {
+                        parent.child(
+                            div()
+                                .flex_1()
+                                .flex()
+                                .items_center()
+                                .justify_center()
+                                .text_sm()
+                                .text_color(rgb(0x6b7280))
+                                .child(
+                                    "No state machines yet. Define named states from a \
+                                     signal with Edit states….",
+                                ),
+                        )
+                    })
+                    .children(lanes),
+            )
+    }
+
+    pub(super) fn render_states_modal(
+        &self,
+        view: Entity<CanViewApp>,
+        on_close: impl Fn(&mut Window, &mut App) + Clone + 'static,
+    ) -> Modal {
+        let muted = |text: &'static str| div().text_xs().text_color(rgb(0x646473)).child(text);
+
+        Modal::new("states-modal")
+            .title("State machines")
+            .width(px(560.))
+            .child(muted(
+                "Definitions separated by ';': Ignition = 0x100 IgnSwitch names the states \
+                 from the signal's value table; Gear = 2:0x200 GearPos [0=Park, 1=Reverse] \
+                 names them explicitly (channel 2 only).",
+            ))
+            .when_some(self.state_definitions_input.as_ref(), |modal, input| {
+                modal.child(Input::new(input))
+            })
+            .child(muted(
+                "A state lasts from the frame that enters it to the frame that leaves it; \
+                 the last state lasts until the end of the log.",
+            ))
+            .footer(
+                div()
+                    .flex()
+                    .gap_2()
+                    .child(Self::render_modal_button(
+                        "states-cancel",
+                        "Cancel",
+                        false,
+                        on_close.clone(),
+                    ))
+                    .child(Self::render_modal_button("states-apply", "Apply", true, {
+                        let view = view.clone();
+                        move |_window, cx| {
+                            view.update(cx, |app, cx| app.apply_state_definitions(cx));
+                        }
+                    })),
+            )
+            .on_close(on_close)
+    }
+}
@@ -0,0 +1,110 @@
+//! The Stats view: per-channel bus statistics
+
+use super::state::CanViewApp;
+use crate::handlers::RATE_WINDOW_NS;
+use gpui::{prelude::*, *};
+
+impl CanViewApp {
+    /// Per-channel frame rate, bus load and error counters
+    pub(super) fn render_stats_view(&self, cx: &App) -> impl IntoElement {
+        let log = self.log_view.read(cx);
+        let columns: [(&str, f32); 5] = [
+            ("CH", 60.),
+            ("FRAMES", 120.),
+            ("RATE (fps)", 110.),
+            ("BUS LOAD", 100.),
+            ("ERRORS", 100.),
+        ];
+        let rows: Vec<[String; 5]> = log
+            .bus_stats
+            .channels()
+            .map(|(channel, stats)| {
+                [
+                    channel.to_string(),
+                    stats.frames.to_string(),
+                    format!("{:.1}", stats.frame_rate),
+                    format!("{:.1}%", stats.bus_load * 100.0),
+                    stats.errors.to_string(),
+                ]
+            })
+            .collect();
+
+        div()
+            .size_full()
+            .flex()
+            .flex_col()
+            .child(
+                // Summary
+                div()
+                    .h(px(36.))
+                    .px_3()
+                    .flex()
+                    .items_center()
+                    .border_b_1()
+                    .border_color(rgb(0x2a2a2a))
+                    .text_xs()
+                    .text_color(rgb(0x9ca3af))
+                    .child(format!(
+                        "{} channels · {:.0} fps total · {} errors · rates over the last {} s",
+                        rows.len(),
+                        log.bus_stats.total_frame_rate(),
+                        log.bus_stats.total_errors(),
+                        RATE_WINDOW_NS / 1_000_000_000
+                    )),
+            )
+            .child(
+                // Column headers
+                div()
+                    .h(px(28.))
+                    .flex()
+                    .items_center()
+                    .bg(rgb(0x141414))
+                    .border_b_1()
+                    .border_color(rgb(0x2a2a2a))
+                    .text_xs()
+                    .font_weight(FontWeight::MEDIUM)
+                    .text_color(rgb(0x646473))
+                    .children(columns.iter().map(|(title, width)| {
+                        div().w(px(*width)).px_3().flex_shrink_0().child(*title)
+                    })),
+            )
+            .child(
+                div()
+                    .flex_1()
+                    .flex()
+                    .flex_col()
+                    .when(rows.is_empty(), |parent| {
+                        parent.child(
+                            div()
+                                .flex_1()
+                                .flex()
+                                .items_center()
+                                .justify_center()
+                                .text_sm()
+                                .text_color(rgb(0x6b7280))
+                                .child("No bus traffic yet. Open a log or start live mode."),
+                        )
+                    })
+                    .children(rows.into_iter().map(|cells| {
+                        div()
+                            .flex()
+                            .w_full()
+                            .h(px(log.message_list.row_height()))
+                            .items_center()
+                            .bg(rgb(0x181818))
+                            .border_b_1()
+                            .border_color(rgb(0x2a2a2a))
+                            .text_xs()
+                            .text_color(rgb(0xd1d5db))
+                            .children(cells.into_iter().zip(columns).map(|(text, (_, width))| {
+                                div()
+                                    .w(px(width))
+                                    .px_3()
+                                    .flex_shrink_0()
+                                    .whitespace_nowrap()
+                                    .child(text)
+                            }))
+                    })),
+            )
+    }
+}
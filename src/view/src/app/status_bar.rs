@@ -0,0 +1,171 @@
+//! The status bar at the bottom of the window
+
+use super::state::CanViewApp;
+use crate::handlers::LoadMode;
+use gpui::{prelude::*, *};
+
+impl CanViewApp {
+    pub(super) fn render_status_bar(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        div()
+            .h(px(24.))
+            .bg(rgb(0x1e1e1e))
+            .border_t_1()
+            .border_color(rgb(0x2a2a2a))
+            .flex()
+            .items_center()
+            .justify_between()
+            .px_3()
+            .text_xs()
+            .text_color(rgb(0x9ca3af))
+            .child(
+                // Left: File info
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_3()
+                    .child(div().child(format!(
+                        "{} messages",
+                        self.log_view.read(cx).messages.len()
+                    )))
+                    .child({
+                        let messages = &self.log_view.read(cx).messages;
+                        let memory = crate::handlers::log_memory(messages);
+                        let limit_mb = self.app_config.preferences.memory_limit_mb;
+                        let over = crate::handlers::exceeds_memory_limit(memory, limit_mb);
+                        div()
+                            .when(over, |el| el.text_color(rgb(0xf59e0b)))
+                            .child(format!(
+                                "{} in memory",
+                                crate::handlers::format_bytes(memory)
+                            ))
+                    })
+                    .when(!self.log_view.read(cx).selection.is_empty(), |el| {
+                        el.child(
+                            div()
+                                .id("export-selected")
+                                .px_1()
+                                .rounded(px(3.))
+                                .text_color(rgb(0x60a5fa))
+                                .cursor_pointer()
+                                .hover(|style| style.bg(rgb(0x252f3a)))
+                                .on_mouse_down(MouseButton::Left, {
+                                    let view = cx.entity().clone();
+                                    move |_event, _window, cx| {
+                                        view.update(cx, |app, cx| app.export_selected(cx));
+                                    }
+                                })
+                                .child(format!(
+                                    "{} selected · Export…",
+                                    self.log_view.read(cx).selection.len()
+                                )),
+                        )
+                    })
+                    .when(self.is_streaming_mode, |el| {
+                        el.child(
+                            div()
+                                .id("save-capture")
+                                .px_1()
+                                .rounded(px(3.))
+                                .text_color(rgb(0x60a5fa))
+                                .cursor_pointer()
+                                .hover(|style| style.bg(rgb(0x252f3a)))
+                                .on_mouse_down(MouseButton::Left, {
+                                    let view = cx.entity().clone();
+                                    move |_event, _window, cx| {
+                                        view.update(cx, |app, cx| app.save_capture_history(cx));
+                                    }
+                                })
+                                .child(format!(
+                                    "💾 Save last {:.1} s ({} msgs)",
+                                    self.capture.read(cx).buffer.span_ns() as f64 / 1_000_000_000.0,
+                                    self.capture.read(cx).buffer.len()
+                                )),
+                        )
+                        .child(
+                            div()
+                                .id("toggle-recording")
+                                .px_1()
+                                .rounded(px(3.))
+                                .text_color(if self.capture.read(cx).recorder.is_some() {
+                                    rgb(0xef4444)
+                                } else {
+                                    rgb(0x60a5fa)
+                                })
+                                .cursor_pointer()
+                                .hover(|style| style.bg(rgb(0x252f3a)))
+                                .on_mouse_down(MouseButton::Left, {
+                                    let view = cx.entity().clone();
+                                    move |_event, _window, cx| {
+                                        view.update(cx, |app, cx| app.toggle_recording(cx));
+                                    }
+                                })
+                                .child(match &self.capture.read(cx).recorder {
+                                    Some(recorder) => {
+                                        format!("⏹ Stop recording (file {})", recorder.file_count())
+                                    }
+                                    None => "⏺ Record…".to_string(),
+                                }),
+                        )
+                    })
+                    .child(div().child(format!("{} DBC channels", self.dbc_channels.len())))
+                    .child(div().child(format!("{} LIN channels", self.ldf_channels.len()))),
+            )
+            .child(
+                // Right: Status with resize handle
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_3()
+                    .when(self.is_streaming_mode, |el| {
+                        el.child(div().child(format!(
+                            "{:.0} fps · load {:.0}% · {} errors",
+                            self.log_view.read(cx).bus_stats.total_frame_rate(),
+                            self.log_view.read(cx).bus_stats.peak_bus_load() * 100.0,
+                            self.log_view.read(cx).bus_stats.total_errors()
+                        )))
+                    })
+                    .when(!self.sequence_checker.gaps().is_empty(), |el| {
+                        el.child(
+                            div()
+                                .text_color(rgb(0xef4444))
+                                .child(format!("{} frames lost", self.sequence_checker.skipped())),
+                        )
+                    })
+                    .children(self.analysis_jobs.iter().map(|job| {
+                        div().text_color(rgb(0x60a5fa)).child(match job.fraction() {
+                            Some(fraction) => {
+                                format!("⚙ {} {:.0}%", job.kind.label(), fraction * 100.0)
+                            }
+                            None => format!("⚙ {}…", job.kind.label()),
+                        })
+                    }))
+                    .child(div().child(match self.load_mode {
+                        _ if self.is_streaming_mode => "Streaming Mode",
+                        LoadMode::Stream => "Streamed Log",
+                        LoadMode::IndexOnly => "Index Only",
+                        LoadMode::Full => "Normal Mode",
+                    }))
+                    .child(div().child(self.status_msg.clone()))
+                    .child(
+                        // Resize handle in bottom-right corner
+                        div()
+                            .ml_2()
+                            .w(px(16.))
+                            .h(px(16.))
+                            .flex()
+                            .items_center()
+                            .justify_center()
+                            .child(
+                                div()
+                                    .w(px(10.))
+                                    .h(px(10.))
+                                    .border_r_2()
+                                    .border_b_2()
+                                    .border_color(rgb(0x6b7280))
+                                    .opacity(0.5),
+                            )
+                            .hover(|style| style.opacity(1.0)),
+                    ),
+            )
+    }
+}
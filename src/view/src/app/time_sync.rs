@@ -0,0 +1,221 @@
+//! The time sync dialog: per-channel time offsets
+
+use super::state::{AppModal, CanViewApp};
+use crate::handlers::TimeOffsets;
+use crate::ui::components::Modal;
+use gpui::{prelude::*, *};
+use gpui_component::input::Input;
+
+impl CanViewApp {
+    /// Open the time sync dialog with fields reflecting the current offsets
+    pub fn open_time_sync_dialog(&mut self, cx: &mut Context<Self>) {
+        self.time_offset_inputs.clear();
+        self.open_modal(AppModal::TimeSync, cx);
+    }
+
+    /// Channel and raw timestamp of the two selected rows, when they are on different channels
+    fn selected_sync_events(&self, cx: &App) -> Option<((u16, u64), (u16, u64))> {
+        let log = self.log_view.read(cx);
+        if log.selection.len() != 2 {
+            return None;
+        }
+        let mut events = log.selection.indices().filter_map(|index| {
+            let msg = log.messages.get(index)?;
+            Some((crate::handlers::message_channel(msg)?, msg.timestamp()))
+        });
+        let (first, second) = (events.next()?, events.next()?);
+        (first.0 != second.0).then_some((first, second))
+    }
+
+    /// Shift the channel of `target` so its selected event lines up with `reference`
+    fn align_on_selected_events(
+        &mut self,
+        reference: (u16, u64),
+        target: (u16, u64),
+        cx: &mut Context<Self>,
+    ) {
+        self.time_offsets
+            .align(reference.0, reference.1, target.0, target.1);
+        self.refresh_log_gps(cx);
+        self.time_offset_inputs.clear(); // Re-seeded from the new offsets on next render
+        self.status_msg = format!(
+            "⏱ Channel {} aligned to channel {} ({} ms)",
+            target.0,
+            reference.0,
+            crate::handlers::format_offset_ms(self.time_offsets.get(target.0))
+        )
+        .into();
+        cx.notify();
+    }
+
+    /// Read the offset fields; keeps the dialog open if one does not parse
+    fn apply_time_offsets(&mut self, cx: &mut Context<Self>) {
+        let parsed: Result<TimeOffsets, String> = self.time_offset_inputs.iter().try_fold(
+            TimeOffsets::new(),
+            |mut offsets, (&channel, input)| {
+                let offset_ns = crate::handlers::parse_offset_ms(&input.read(cx).value())
+                    .map_err(|e| format!("Channel {}: {}", channel, e))?;
+                offsets.set(channel, offset_ns);
+                Ok(offsets)
+            },
+        );
+        match parsed {
+            Ok(offsets) => {
+                self.time_offsets = offsets;
+                self.refresh_log_gps(cx);
+            }
+            Err(e) => {
+                self.status_msg = format!("❌ {}", e).into();
+                cx.notify();
+                return;
+            }
+        }
+        self.status_msg = if self.time_offsets.is_empty() {
+            "⏱ Time offsets cleared".into()
+        } else {
+            "⏱ Time offsets applied".into()
+        };
+        self.close_modal(AppModal::TimeSync, cx);
+    }
+
+    pub(super) fn render_time_sync_modal(
+        &self,
+        view: Entity<CanViewApp>,
+        on_close: impl Fn(&mut Window, &mut App) + Clone + 'static,
+        cx: &App,
+    ) -> Modal {
+        let mut channels: Vec<_> = self.time_offset_inputs.iter().collect();
+        channels.sort_by_key(|(channel, _)| **channel);
+        let events = self.selected_sync_events(cx);
+
+        Modal::new("time-sync-modal")
+            .title("Time Sync")
+            .width(px(460.))
+            .child(div().text_xs().text_color(rgb(0x646473)).child(
+                "Offsets are added to the timestamps of each channel in the log view, \
+                         copied series and exports.",
+            ))
+            .when(channels.is_empty(), |modal| {
+                modal.child(
+                    div()
+                        .text_xs()
+                        .text_color(rgb(0x646473))
+                        .child("No channels loaded."),
+                )
+            })
+            .children(channels.into_iter().map(|(channel, input)| {
+                div()
+                    .flex()
+                    .items_center()
+                    .justify_between()
+                    .child(
+                        div()
+                            .text_xs()
+                            .text_color(rgb(0x9ca3af))
+                            .child(format!("Channel {}", channel)),
+                    )
+                    .child(
+                        div()
+                            .flex()
+                            .items_center()
+                            .gap_2()
+                            .child(div().w(px(120.)).child(Input::new(input)))
+                            .child(div().text_xs().text_color(rgb(0x646473)).child("ms")),
+                    )
+            }))
+            .child(
+                div()
+                    .pt_2()
+                    .border_t_1()
+                    .border_color(rgb(0x2a2a2a))
+                    .flex()
+                    .flex_col()
+                    .gap_2()
+                    .child(
+                        div()
+                            .text_xs()
+                            .text_color(rgb(0x9ca3af))
+                            .child("Align on a common event"),
+                    )
+                    .child(match events {
+                        Some((first, second)) => div()
+                            .flex()
+                            .gap_2()
+                            .child(Self::render_modal_button(
+                                "align-second-to-first",
+                                "Shift second row's channel",
+                                false,
+                                {
+                                    let view = view.clone();
+                                    move |_window, cx| {
+                                        view.update(cx, |app, cx| {
+                                            app.align_on_selected_events(first, second, cx)
+                                        });
+                                    }
+                                },
+                            ))
+                            .child(Self::render_modal_button(
+                                "align-first-to-second",
+                                "Shift first row's channel",
+                                false,
+                                {
+                                    let view = view.clone();
+                                    move |_window, cx| {
+                                        view.update(cx, |app, cx| {
+                                            app.align_on_selected_events(second, first, cx)
+                                        });
+                                    }
+                                },
+                            ))
+                            .into_any_element(),
+                        None => div()
+                            .text_xs()
+                            .text_color(rgb(0x646473))
+                            .child(
+                                "Select the same event on two channels \
+                                 (ctrl-click one row on each) to line them up.",
+                            )
+                            .into_any_element(),
+                    }),
+            )
+            .footer(
+                div()
+                    .flex()
+                    .gap_2()
+                    .child(Self::render_modal_button(
+                        "time-sync-reset",
+                        "Reset",
+                        false,
+                        {
+                            let view = view.clone();
+                            move |_window, cx| {
+                                view.update(cx, |app, cx| {
+                                    app.time_offsets = TimeOffsets::new();
+                                    app.time_offset_inputs.clear();
+                                    app.refresh_log_gps(cx);
+                                    cx.notify();
+                                });
+                            }
+                        },
+                    ))
+                    .child(Self::render_modal_button(
+                        "time-sync-cancel",
+                        "Cancel",
+                        false,
+                        on_close.clone(),
+                    ))
+                    .child(Self::render_modal_button(
+                        "time-sync-apply",
+                        "Apply",
+                        true,
+                        {
+                            let view = view.clone();
+                            move |_window, cx| {
+                                view.update(cx, |app, cx| app.apply_time_offsets(cx));
+                            }
+                        },
+                    )),
+            )
+            .on_close(on_close)
+    }
+}
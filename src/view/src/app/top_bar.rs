@@ -0,0 +1,876 @@
+//! The bar at the top of the window: the views, the tools and the window controls
+
+use super::state::{AppModal, AppView, CanViewApp};
+use gpui::{prelude::*, *};
+
+impl CanViewApp {
+    pub(super) fn render_top_bar(
+        &self,
+        view: &Entity<CanViewApp>,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        div()
+            .h(px(48.)) // Slightly shorter, more like Zed
+            .bg(rgb(0x0c0c0e)) // Zed's panel background
+            .flex()
+            .items_center()
+            .px_4()
+            .border_b_1()
+            .border_color(rgb(0x1a1a1a)) // Very subtle border
+            .child(
+                // Left: App branding and navigation tabs
+                div()
+                    .flex_none()
+                    .flex()
+                    .items_center()
+                    .h_full()
+                    .gap_4()
+                    .child(div().when(cfg!(target_os = "macos"), |div| {
+                        div.w(px(80.)).window_control_area(WindowControlArea::Drag)
+                    }))
+                    .child(
+                        div()
+                            .h_full()
+                            .flex()
+                            .items_center()
+                            .gap_0()
+                            .child(
+                                div()
+                                    .h_full()
+                                    .flex() // Center text
+                                    .items_center()
+                                    .px_4() // Larger horizontal padding
+                                    .text_xs()
+                                    .font_weight(FontWeight::MEDIUM)
+                                    .cursor_pointer()
+                                    // BG logic remains related to active state
+                                    .bg(if self.current_view == AppView::LogView {
+                                        rgb(0x1e1e2e)
+                                    } else {
+                                        rgb(0x0c0c0e)
+                                    })
+                                    .text_color(if self.current_view == AppView::LogView {
+                                        rgb(0xcdd6f4)
+                                    } else {
+                                        rgb(0x646473)
+                                    })
+                                    .hover(|style| {
+                                        if self.current_view != AppView::LogView {
+                                            style.bg(rgb(0x151515)).text_color(rgb(0x9399b2))
+                                        } else {
+                                            style
+                                        }
+                                    })
+                                    .id("logs_tab")
+                                    .on_mouse_down(gpui::MouseButton::Left, {
+                                        let view = view.clone();
+                                        move |_event, _, cx| {
+                                            cx.stop_propagation();
+                                            view.update(cx, |this, cx| {
+                                                this.current_view = AppView::LogView;
+                                                cx.notify();
+                                            });
+                                        }
+                                    })
+                                    .child("Logs"),
+                            )
+                            .child(
+                                div()
+                                    .h_full()
+                                    .flex()
+                                    .items_center()
+                                    .px_4()
+                                    .text_xs()
+                                    .font_weight(FontWeight::MEDIUM)
+                                    .cursor_pointer()
+                                    .bg(if self.current_view == AppView::LibraryView {
+                                        rgb(0x1e1e2e)
+                                    } else {
+                                        rgb(0x0c0c0e)
+                                    })
+                                    .text_color(if self.current_view == AppView::LibraryView {
+                                        rgb(0xcdd6f4)
+                                    } else {
+                                        rgb(0x646473)
+                                    })
+                                    .hover(|style| {
+                                        if self.current_view != AppView::LibraryView {
+                                            style.bg(rgb(0x151515)).text_color(rgb(0x9399b2))
+                                        } else {
+                                            style
+                                        }
+                                    })
+                                    .id("library_tab")
+                                    .on_mouse_down(gpui::MouseButton::Left, {
+                                        let view = view.clone();
+                                        move |_event, _, cx| {
+                                            cx.stop_propagation();
+                                            view.update(cx, |this, cx| {
+                                                this.current_view = AppView::LibraryView;
+                                                cx.notify();
+                                            });
+                                        }
+                                    })
+                                    .child("Library"),
+                            )
+                            .child(
+                                div()
+                                    .h_full()
+                                    .flex()
+                                    .items_center()
+                                    .px_4()
+                                    .text_xs()
+                                    .font_weight(FontWeight::MEDIUM)
+                                    .cursor_pointer()
+                                    .bg(if self.current_view == AppView::GpsView {
+                                        rgb(0x1e1e2e)
+                                    } else {
+                                        rgb(0x0c0c0e)
+                                    })
+                                    .text_color(if self.current_view == AppView::GpsView {
+                                        rgb(0xcdd6f4)
+                                    } else {
+                                        rgb(0x646473)
+                                    })
+                                    .hover(|style| {
+                                        if self.current_view != AppView::GpsView {
+                                            style.bg(rgb(0x151515)).text_color(rgb(0x9399b2))
+                                        } else {
+                                            style
+                                        }
+                                    })
+                                    .id("gps_tab")
+                                    .on_mouse_down(gpui::MouseButton::Left, {
+                                        let view = view.clone();
+                                        move |_event, _, cx| {
+                                            cx.stop_propagation();
+                                            view.update(cx, |this, cx| {
+                                                this.current_view = AppView::GpsView;
+                                                cx.notify();
+                                            });
+                                        }
+                                    })
+                                    .child("GPS"),
+                            )
+                            .child(
+                                div()
+                                    .h_full()
+                                    .flex()
+                                    .items_center()
+                                    .px_4()
+                                    .text_xs()
+                                    .font_weight(FontWeight::MEDIUM)
+                                    .cursor_pointer()
+                                    .bg(if self.current_view == AppView::StatsView {
+                                        rgb(0x1e1e2e)
+                                    } else {
+                                        rgb(0x0c0c0e)
+                                    })
+                                    .text_color(if self.current_view == AppView::StatsView {
+                                        rgb(0xcdd6f4)
+                                    } else {
+                                        rgb(0x646473)
+                                    })
+                                    .hover(|style| {
+                                        if self.current_view != AppView::StatsView {
+                                            style.bg(rgb(0x151515)).text_color(rgb(0x9399b2))
+                                        } else {
+                                            style
+                                        }
+                                    })
+                                    .id("stats_tab")
+                                    .on_mouse_down(gpui::MouseButton::Left, {
+                                        let view = view.clone();
+                                        move |_event, _, cx| {
+                                            cx.stop_propagation();
+                                            view.update(cx, |this, cx| {
+                                                this.current_view = AppView::StatsView;
+                                                cx.notify();
+                                            });
+                                        }
+                                    })
+                                    .child("Statistics"),
+                            )
+                            .child(
+                                div()
+                                    .h_full()
+                                    .flex()
+                                    .items_center()
+                                    .px_4()
+                                    .text_xs()
+                                    .font_weight(FontWeight::MEDIUM)
+                                    .cursor_pointer()
+                                    .bg(if self.current_view == AppView::ChartView {
+                                        rgb(0x1e1e2e)
+                                    } else {
+                                        rgb(0x0c0c0e)
+                                    })
+                                    .text_color(if self.current_view == AppView::ChartView {
+                                        rgb(0xcdd6f4)
+                                    } else {
+                                        rgb(0x646473)
+                                    })
+                                    .hover(|style| {
+                                        if self.current_view != AppView::ChartView {
+                                            style.bg(rgb(0x151515)).text_color(rgb(0x9399b2))
+                                        } else {
+                                            style
+                                        }
+                                    })
+                                    .id("charts_tab")
+                                    .on_mouse_down(gpui::MouseButton::Left, {
+                                        let view = view.clone();
+                                        move |_event, _, cx| {
+                                            cx.stop_propagation();
+                                            view.update(cx, |this, cx| {
+                                                this.current_view = AppView::ChartView;
+                                                cx.notify();
+                                            });
+                                        }
+                                    })
+                                    .child("Charts"),
+                            )
+                            .child(
+                                div()
+                                    .h_full()
+                                    .flex()
+                                    .items_center()
+                                    .px_4()
+                                    .text_xs()
+                                    .font_weight(FontWeight::MEDIUM)
+                                    .cursor_pointer()
+                                    .bg(if self.current_view == AppView::StatesView {
+                                        rgb(0x1e1e2e)
+                                    } else {
+                                        rgb(0x0c0c0e)
+                                    })
+                                    .text_color(if self.current_view == AppView::StatesView {
+                                        rgb(0xcdd6f4)
+                                    } else {
+                                        rgb(0x646473)
+                                    })
+                                    .hover(|style| {
+                                        if self.current_view != AppView::StatesView {
+                                            style.bg(rgb(0x151515)).text_color(rgb(0x9399b2))
+                                        } else {
+                                            style
+                                        }
+                                    })
+                                    .id("states_tab")
+                                    .on_mouse_down(gpui::MouseButton::Left, {
+                                        let view = view.clone();
+                                        move |_event, _, cx| {
+                                            cx.stop_propagation();
+                                            view.update(cx, |this, cx| {
+                                                this.current_view = AppView::StatesView;
+                                                cx.notify();
+                                            });
+                                        }
+                                    })
+                                    .child("States"),
+                            )
+                            .child(
+                                div()
+                                    .h_full()
+                                    .flex()
+                                    .items_center()
+                                    .px_4()
+                                    .text_xs()
+                                    .font_weight(FontWeight::MEDIUM)
+                                    .cursor_pointer()
+                                    .bg(if self.current_view == AppView::BatchView {
+                                        rgb(0x1e1e2e)
+                                    } else {
+                                        rgb(0x0c0c0e)
+                                    })
+                                    .text_color(if self.current_view == AppView::BatchView {
+                                        rgb(0xcdd6f4)
+                                    } else {
+                                        rgb(0x646473)
+                                    })
+                                    .hover(|style| {
+                                        if self.current_view != AppView::BatchView {
+                                            style.bg(rgb(0x151515)).text_color(rgb(0x9399b2))
+                                        } else {
+                                            style
+                                        }
+                                    })
+                                    .id("batch_tab")
+                                    .on_mouse_down(gpui::MouseButton::Left, {
+                                        let view = view.clone();
+                                        move |_event, _, cx| {
+                                            cx.stop_propagation();
+                                            view.update(cx, |this, cx| {
+                                                this.current_view = AppView::BatchView;
+                                                cx.notify();
+                                            });
+                                        }
+                                    })
+                                    .child("Batch"),
+                            ),
+                    ),
+            )
+            .child(div().flex_1().window_control_area(WindowControlArea::Drag))
+            .child(
+                // Center: Status and stats - Zed style
+                div()
+                    .flex_none()
+                    // Removed Drag area from center to avoid confusion
+                    .flex()
+                    .items_center()
+                    .h_full()
+                    .gap_4()
+                    .child(
+                        div()
+                            .text_xs()
+                            .text_color(rgb(0x646473)) // Zed's muted
+                            .child(self.status_msg.clone()),
+                    )
+                    .child(div().w(px(1.0)).h(px(12.0)).bg(rgb(0x1a1a1a))) // Subtle divider
+                    .child(
+                        div()
+                            .flex()
+                            .items_center()
+                            .gap_2() // Tighter spacing
+                            .text_xs()
+                            .text_color(rgb(0x9ca3af))
+                            .child(format!("{} msgs", self.log_view.read(cx).messages.len()))
+                            .child(format!("{} DBC", self.dbc_channels.len()))
+                            .child(format!("{} LIN", self.ldf_channels.len())),
+                    ),
+            )
+            .child(div().flex_1().window_control_area(WindowControlArea::Drag))
+            .child(
+                // Right: Action buttons and window controls
+                div()
+                    .flex_none()
+                    .flex()
+                    .items_center()
+                    .h_full()
+                    .gap_2()
+                    .child(
+                        div()
+                            .px_2()
+                            .py(px(1.5))
+                            .text_xs()
+                            .text_color(if self.is_simulating(cx) {
+                                rgb(0x22c55e)
+                            } else {
+                                rgb(0x646473) // Zed's muted
+                            })
+                            .rounded(px(3.))
+                            .cursor_pointer()
+                            .hover(|style| style.bg(rgb(0x252f3a)).text_color(rgb(0xcdd6f4)))
+                            .id("simulation_btn")
+                            .on_mouse_down(gpui::MouseButton::Left, {
+                                let view = view.clone();
+                                move |_event, _, cx| {
+                                    cx.stop_propagation();
+                                    view.update(cx, |app, cx| app.open_simulation_dialog(cx));
+                                }
+                            })
+                            .child("📡"),
+                    )
+                    .child(
+                        div()
+                            .px_2()
+                            .py(px(1.5))
+                            .text_xs()
+                            .text_color(if self.is_replaying(cx) {
+                                rgb(0x22c55e)
+                            } else {
+                                rgb(0x646473) // Zed's muted
+                            })
+                            .rounded(px(3.))
+                            .cursor_pointer()
+                            .hover(|style| style.bg(rgb(0x252f3a)).text_color(rgb(0xcdd6f4)))
+                            .id("replay_btn")
+                            .on_mouse_down(gpui::MouseButton::Left, {
+                                let view = view.clone();
+                                move |_event, _, cx| {
+                                    cx.stop_propagation();
+                                    view.update(cx, |app, cx| app.open_replay_dialog(cx));
+                                }
+                            })
+                            .child("🔁"),
+                    )
+                    .child(
+                        div()
+                            .px_2()
+                            .py(px(1.5))
+                            .text_xs()
+                            .text_color(if self.is_capturing(cx) {
+                                rgb(0x22c55e)
+                            } else {
+                                rgb(0x646473) // Zed's muted
+                            })
+                            .rounded(px(3.))
+                            .cursor_pointer()
+                            .hover(|style| style.bg(rgb(0x252f3a)).text_color(rgb(0xcdd6f4)))
+                            .id("capture_btn")
+                            .on_mouse_down(gpui::MouseButton::Left, {
+                                let view = view.clone();
+                                move |_event, _, cx| {
+                                    cx.stop_propagation();
+                                    view.update(cx, |app, cx| {
+                                        // Stops a running capture
+                                        if app.is_capturing(cx) {
+                                            app.stop_live_source(cx);
+                                            cx.notify();
+                                        } else {
+                                            app.open_capture_dialog(cx);
+                                        }
+                                    });
+                                }
+                            })
+                            .child(if self.is_capturing(cx) { "⏹" } else { "🔌" }),
+                    )
+                    .child(
+                        div()
+                            .px_2()
+                            .py(px(1.5))
+                            .text_xs()
+                            .text_color(if self.mqtt_publisher.is_some() {
+                                rgb(0x22c55e)
+                            } else {
+                                rgb(0x646473) // Zed's muted
+                            })
+                            .rounded(px(3.))
+                            .cursor_pointer()
+                            .hover(|style| style.bg(rgb(0x252f3a)).text_color(rgb(0xcdd6f4)))
+                            .id("mqtt_btn")
+                            .on_mouse_down(gpui::MouseButton::Left, {
+                                let view = view.clone();
+                                move |_event, _, cx| {
+                                    cx.stop_propagation();
+                                    view.update(cx, |app, cx| app.open_mqtt_dialog(cx));
+                                }
+                            })
+                            .child("📤"),
+                    )
+                    .child(
+                        div()
+                            .px_2()
+                            .py(px(1.5))
+                            .text_xs()
+                            .text_color(if self.folder_watcher.is_some() {
+                                rgb(0x22c55e)
+                            } else {
+                                rgb(0x646473) // Zed's muted
+                            })
+                            .rounded(px(3.))
+                            .cursor_pointer()
+                            .hover(|style| style.bg(rgb(0x252f3a)).text_color(rgb(0xcdd6f4)))
+                            .id("watch_btn")
+                            .on_mouse_down(gpui::MouseButton::Left, {
+                                let view = view.clone();
+                                move |_event, _, cx| {
+                                    cx.stop_propagation();
+                                    view.update(cx, |app, cx| app.open_watch_dialog(cx));
+                                }
+                            })
+                            .child(if self.watch_queue.is_empty() {
+                                "📥".to_string()
+                            } else {
+                                format!("📥 {}", self.watch_queue.len())
+                            }),
+                    )
+                    .child(
+                        div()
+                            .px_2()
+                            .py(px(1.5))
+                            .text_xs()
+                            .text_color(if self.show_video_bar {
+                                rgb(0xcdd6f4)
+                            } else {
+                                rgb(0x646473) // Zed's muted
+                            })
+                            .rounded(px(3.))
+                            .cursor_pointer()
+                            .hover(|style| style.bg(rgb(0x252f3a)).text_color(rgb(0xcdd6f4)))
+                            .id("video_btn")
+                            .on_mouse_down(gpui::MouseButton::Left, {
+                                let view = view.clone();
+                                move |_event, _, cx| {
+                                    cx.stop_propagation();
+                                    view.update(cx, |app, cx| {
+                                        app.show_video_bar = !app.show_video_bar;
+                                        cx.notify();
+                                    });
+                                }
+                            })
+                            .child("🎬"),
+                    )
+                    .child(
+                        div()
+                            .px_2()
+                            .py(px(1.5))
+                            .text_xs()
+                            .text_color(match &self.log_health {
+                                Some(health) if !health.is_clean() => rgb(0xf59e0b),
+                                _ => rgb(0x646473), // Zed's muted
+                            })
+                            .rounded(px(3.))
+                            .cursor_pointer()
+                            .hover(|style| style.bg(rgb(0x252f3a)).text_color(rgb(0xcdd6f4)))
+                            .id("health_btn")
+                            .on_mouse_down(gpui::MouseButton::Left, {
+                                let view = view.clone();
+                                move |_event, _, cx| {
+                                    cx.stop_propagation();
+                                    view.update(cx, |app, cx| app.open_health_dialog(cx));
+                                }
+                            })
+                            .child("♥"),
+                    )
+                    .child(
+                        div()
+                            .px_2()
+                            .py(px(1.5))
+                            .text_xs()
+                            .text_color(rgb(0x646473)) // Zed's muted
+                            .rounded(px(3.))
+                            .cursor_pointer()
+                            .hover(|style| style.bg(rgb(0x252f3a)).text_color(rgb(0xcdd6f4)))
+                            .id("time_sync_btn")
+                            .on_mouse_down(gpui::MouseButton::Left, {
+                                let view = view.clone();
+                                move |_event, _, cx| {
+                                    cx.stop_propagation();
+                                    view.update(cx, |app, cx| app.open_time_sync_dialog(cx));
+                                }
+                            })
+                            .child("⏱"),
+                    )
+                    .child(
+                        div()
+                            .px_2()
+                            .py(px(1.5))
+                            .text_xs()
+                            .text_color(if self.trigger_events.is_empty() {
+                                rgb(0x646473) // Zed's muted
+                            } else {
+                                rgb(0xf59e0b)
+                            })
+                            .rounded(px(3.))
+                            .cursor_pointer()
+                            .hover(|style| style.bg(rgb(0x252f3a)).text_color(rgb(0xcdd6f4)))
+                            .id("trigger_btn")
+                            .on_mouse_down(gpui::MouseButton::Left, {
+                                let view = view.clone();
+                                move |_event, _, cx| {
+                                    cx.stop_propagation();
+                                    view.update(cx, |app, cx| app.open_triggers_dialog(cx));
+                                }
+                            })
+                            .child("⚡"),
+                    )
+                    .child(
+                        div()
+                            .px_2()
+                            .py(px(1.5))
+                            .text_xs()
+                            .text_color(rgb(0x646473)) // Zed's muted
+                            .rounded(px(3.))
+                            .cursor_pointer()
+                            .hover(|style| style.bg(rgb(0x252f3a)).text_color(rgb(0xcdd6f4)))
+                            .id("find_btn")
+                            .on_mouse_down(gpui::MouseButton::Left, {
+                                let view = view.clone();
+                                move |_event, _, cx| {
+                                    cx.stop_propagation();
+                                    view.update(cx, |app, cx| app.open_find_dialog(cx));
+                                }
+                            })
+                            .child("🔍"),
+                    )
+                    .child(
+                        div()
+                            .px_2()
+                            .py(px(1.5))
+                            .text_xs()
+                            .text_color(if self.sequence_checker.gaps().is_empty() {
+                                rgb(0x646473) // Zed's muted
+                            } else {
+                                rgb(0xef4444)
+                            })
+                            .rounded(px(3.))
+                            .cursor_pointer()
+                            .hover(|style| style.bg(rgb(0x252f3a)).text_color(rgb(0xcdd6f4)))
+                            .id("sequence_btn")
+                            .on_mouse_down(gpui::MouseButton::Left, {
+                                let view = view.clone();
+                                move |_event, _, cx| {
+                                    cx.stop_propagation();
+                                    view.update(cx, |app, cx| app.open_sequence_dialog(cx));
+                                }
+                            })
+                            .child("#"),
+                    )
+                    .child(
+                        div()
+                            .px_2()
+                            .py(px(1.5))
+                            .text_xs()
+                            .text_color(if self.markers.is_empty() {
+                                rgb(0x646473) // Zed's muted
+                            } else {
+                                rgb(0x3b82f6)
+                            })
+                            .rounded(px(3.))
+                            .cursor_pointer()
+                            .hover(|style| style.bg(rgb(0x252f3a)).text_color(rgb(0xcdd6f4)))
+                            .id("markers_btn")
+                            .on_mouse_down(gpui::MouseButton::Left, {
+                                let view = view.clone();
+                                move |_event, _, cx| {
+                                    cx.stop_propagation();
+                                    view.update(cx, |app, cx| app.open_markers_dialog(cx));
+                                }
+                            })
+                            .child("⚑"),
+                    )
+                    .child(
+                        div()
+                            .px_2()
+                            .py(px(1.5))
+                            .text_xs()
+                            .text_color(if self.app_config.highlights.is_empty() {
+                                rgb(0x646473) // Zed's muted
+                            } else {
+                                rgb(0xcdd6f4)
+                            })
+                            .rounded(px(3.))
+                            .cursor_pointer()
+                            .hover(|style| style.bg(rgb(0x252f3a)).text_color(rgb(0xcdd6f4)))
+                            .id("highlights_btn")
+                            .on_mouse_down(gpui::MouseButton::Left, {
+                                let view = view.clone();
+                                move |_event, _, cx| {
+                                    cx.stop_propagation();
+                                    view.update(cx, |app, cx| app.open_highlights_dialog(cx));
+                                }
+                            })
+                            .child("◐"),
+                    )
+                    .child(
+                        div()
+                            .px_2()
+                            .py(px(1.5))
+                            .text_xs()
+                            .text_color(if self.log_view.read(cx).show_channel_tree {
+                                rgb(0xcdd6f4)
+                            } else {
+                                rgb(0x646473) // Zed's muted
+                            })
+                            .rounded(px(3.))
+                            .cursor_pointer()
+                            .hover(|style| style.bg(rgb(0x252f3a)).text_color(rgb(0xcdd6f4)))
+                            .id("channel_tree_btn")
+                            .on_mouse_down(gpui::MouseButton::Left, {
+                                let view = view.clone();
+                                move |_event, _, cx| {
+                                    cx.stop_propagation();
+                                    view.update(cx, |app, cx| {
+                                        app.log_view.update(cx, |log, cx| {
+                                            log.show_channel_tree = !log.show_channel_tree;
+                                            cx.notify();
+                                        });
+                                    });
+                                }
+                            })
+                            .child("☰"),
+                    )
+                    .child(
+                        div()
+                            .px_2()
+                            .py(px(1.5))
+                            .text_xs()
+                            .text_color(rgb(0x646473)) // Zed's muted
+                            .rounded(px(3.))
+                            .cursor_pointer()
+                            .hover(|style| style.bg(rgb(0x252f3a)).text_color(rgb(0xcdd6f4)))
+                            .id("compare_btn")
+                            .on_mouse_down(gpui::MouseButton::Left, {
+                                let view = view.clone();
+                                move |_event, _, cx| {
+                                    cx.stop_propagation();
+                                    view.update(cx, |app, cx| app.open_compare_dialog(cx));
+                                }
+                            })
+                            .child("⇄"),
+                    )
+                    .child(
+                        div()
+                            .px_2()
+                            .py(px(1.5))
+                            .text_xs()
+                            .text_color(rgb(0x646473)) // Zed's muted
+                            .rounded(px(3.))
+                            .cursor_pointer()
+                            .hover(|style| style.bg(rgb(0x252f3a)).text_color(rgb(0xcdd6f4)))
+                            .id("correlation_btn")
+                            .on_mouse_down(gpui::MouseButton::Left, {
+                                let view = view.clone();
+                                move |_event, _, cx| {
+                                    cx.stop_propagation();
+                                    view.update(cx, |app, cx| app.open_correlation_dialog(cx));
+                                }
+                            })
+                            .child("≈"),
+                    )
+                    .child(
+                        div()
+                            .px_2()
+                            .py(px(1.5))
+                            .text_xs()
+                            .text_color(rgb(0x646473)) // Zed's muted
+                            .rounded(px(3.))
+                            .cursor_pointer()
+                            .hover(|style| style.bg(rgb(0x252f3a)).text_color(rgb(0xcdd6f4)))
+                            .id("preferences_btn")
+                            .on_mouse_down(gpui::MouseButton::Left, {
+                                let view = view.clone();
+                                move |_event, _, cx| {
+                                    cx.stop_propagation();
+                                    view.update(cx, |app, cx| {
+                                        app.open_modal(AppModal::Preferences, cx)
+                                    });
+                                }
+                            })
+                            .child("⚙"),
+                    )
+                    .child(
+                        div()
+                            .px_2()
+                            .py(px(1.5))
+                            .text_xs()
+                            .text_color(rgb(0x646473)) // Zed's muted
+                            .rounded(px(3.))
+                            .cursor_pointer()
+                            .hover(|style| style.bg(rgb(0x252f3a)).text_color(rgb(0xcdd6f4)))
+                            .id("about_btn")
+                            .on_mouse_down(gpui::MouseButton::Left, {
+                                let view = view.clone();
+                                move |_event, _, cx| {
+                                    cx.stop_propagation();
+                                    view.update(cx, |app, cx| app.open_modal(AppModal::About, cx));
+                                }
+                            })
+                            .child("?"),
+                    )
+                    .child(
+                        div()
+                            .px_3()
+                            .py(px(1.5))
+                            .text_xs()
+                            .font_weight(FontWeight::MEDIUM)
+                            .text_color(rgb(0x9399b2))
+                            .bg(rgb(0x151515)) // Very subtle
+                            .rounded(px(3.)) // Smaller radius
+                            .cursor_pointer()
+                            .hover(|style| style.bg(rgb(0x1c1c1c)).text_color(rgb(0xcdd6f4)))
+                            .id("export_btn")
+                            .on_mouse_down(gpui::MouseButton::Left, {
+                                let view = view.clone();
+                                move |_event, _, cx| {
+                                    cx.stop_propagation();
+                                    view.update(cx, |app, cx| app.open_export_dialog(cx));
+                                }
+                            })
+                            .child("Export"),
+                    )
+                    .child(
+                        div()
+                            .px_3()
+                            .py(px(1.5))
+                            .text_xs()
+                            .font_weight(FontWeight::MEDIUM)
+                            .text_color(rgb(0xcdd6f4)) // Zed's text
+                            .bg(rgb(0x1a1f2e)) // Zed-style subtle green
+                            .rounded(px(3.)) // Smaller radius
+                            .cursor_pointer()
+                            .hover(|style| style.bg(rgb(0x252f3a))) // Subtle hover
+                            .id("open_blf_btn")
+                            .on_mouse_down(gpui::MouseButton::Left, {
+                                let view = view.clone();
+                                move |_event, _, cx| {
+                                    cx.stop_propagation();
+                                    view.update(cx, |app, cx| app.open_log_dialog(cx));
+                                }
+                            })
+                            .child("Open log"),
+                    )
+                    .child(
+                        // Window controls separator
+                        div().w(px(12.)), // Smaller separator
+                    )
+                    .child(
+                        // Minimize button - Zed style
+                        div()
+                            .w(px(28.)) // Slightly smaller
+                            .h(px(28.))
+                            .flex()
+                            .items_center()
+                            .justify_center()
+                            .cursor_pointer()
+                            .hover(|style| style.bg(rgb(0x121212))) // Very subtle hover
+                            .child(div().w(px(10.)).h(px(1.)).bg(rgb(0x646473))) // Zed's muted
+                            .id("minimize_btn")
+                            .on_mouse_down(gpui::MouseButton::Left, {
+                                let view = view.clone();
+                                move |_event, window, cx| {
+                                    cx.stop_propagation();
+                                    window.minimize_window();
+                                    view.update(cx, |_, cx| cx.notify());
+                                }
+                            }),
+                    )
+                    .child(
+                        // Maximize/Restore button - Zed style
+                        div()
+                            .w(px(28.)) // Slightly smaller
+                            .h(px(28.))
+                            .flex()
+                            .items_center()
+                            .justify_center()
+                            .cursor_pointer()
+                            .hover(|style| style.bg(rgb(0x121212))) // Very subtle hover
+                            .child(
+                                div()
+                                    .w(px(9.))
+                                    .h(px(9.))
+                                    .border_1()
+                                    .border_color(rgb(0x646473)), // Zed's muted
+                            )
+                            .id("maximize_btn")
+                            .on_mouse_down(gpui::MouseButton::Left, {
+                                let view = view.clone();
+                                move |_event, window, cx| {
+                                    cx.stop_propagation();
+                                    view.update(cx, |this, cx| {
+                                        this.toggle_maximize(window, cx);
+                                        cx.notify();
+                                    });
+                                }
+                            }),
+                    )
+                    .child(
+                        // Close button - Zed style
+                        div()
+                            .w(px(28.)) // Slightly smaller
+                            .h(px(28.))
+                            .flex()
+                            .items_center()
+                            .justify_center()
+                            .cursor_pointer()
+                            .hover(|style| style.bg(rgb(0x3a1a1a))) // Subtle red hover
+                            .child(div().text_sm().text_color(rgb(0x646473)).child("×")) // Zed's muted
+                            .on_mouse_down(gpui::MouseButton::Left, move |_event, window, cx| {
+                                cx.stop_propagation();
+                                window.remove_window();
+                            }),
+                    ),
+            )
+    }
+}
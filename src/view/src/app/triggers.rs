@@ -0,0 +1,231 @@
+//! The triggers dialog: trigger rules and the windows saved around them
+
+use super::state::{AppModal, CanViewApp};
+use crate::ui::components::Modal;
+use blf::LogObject;
+use gpui::{prelude::*, *};
+use gpui_component::input::{Input, InputState};
+
+impl CanViewApp {
+    /// Open the trigger dialog with fields reflecting the last run
+    pub fn open_triggers_dialog(&mut self, cx: &mut Context<Self>) {
+        self.trigger_rules_input = None;
+        self.trigger_pre_input = None;
+        self.trigger_post_input = None;
+        self.open_modal(AppModal::Triggers, cx);
+    }
+
+    /// Run the trigger rules over the log and mark a segment boundary at each firing
+    ///
+    /// Retriggers are held off for the post-trigger window so windows don't overlap.
+    fn run_triggers(&mut self, cx: &mut Context<Self>) {
+        let log = self.log_view.read(cx);
+        let read = |input: &Option<Entity<InputState>>| {
+            input
+                .as_ref()
+                .map(|input| input.read(cx).value().to_string())
+                .unwrap_or_default()
+        };
+        let window_s = |text: String, name: &str| {
+            text.trim()
+                .parse::<f64>()
+                .ok()
+                .filter(|s| s.is_finite() && *s >= 0.0)
+                .ok_or_else(|| format!("Invalid {} window: '{}'", name, text.trim()))
+        };
+        let parsed = crate::handlers::parse_trigger_rules(&read(&self.trigger_rules_input))
+            .and_then(|rules| {
+                if rules.is_empty() {
+                    return Err("Enter at least one trigger rule".to_string());
+                }
+                let pre_s = window_s(read(&self.trigger_pre_input), "pre-trigger")?;
+                let post_s = window_s(read(&self.trigger_post_input), "post-trigger")?;
+                Ok((rules, pre_s, post_s))
+            });
+        let (rules, pre_s, post_s) = match parsed {
+            Ok(parsed) => parsed,
+            Err(e) => {
+                self.status_msg = format!("❌ {}", e).into();
+                cx.notify();
+                return;
+            }
+        };
+
+        self.trigger_events = crate::handlers::find_triggers(
+            &log.messages,
+            rules.clone(),
+            (post_s * 1_000_000_000.0) as u64,
+            &self.dbc_channels,
+            &self.ldf_channels,
+        );
+        self.trigger_rules = rules;
+        self.trigger_pre_s = pre_s;
+        self.trigger_post_s = post_s;
+
+        let segments = crate::handlers::segment_ranges(log.messages.len(), &self.trigger_events);
+        self.status_msg = format!(
+            "⚡ {} triggers, {} segments",
+            self.trigger_events.len(),
+            segments.len()
+        )
+        .into();
+        if self.trigger_auto_save && !self.trigger_events.is_empty() {
+            self.save_trigger_windows(cx);
+        }
+        self.close_modal(AppModal::Triggers, cx);
+    }
+
+    /// Save the pre/post window around each trigger as its own BLF file
+    fn save_trigger_windows(&mut self, cx: &mut Context<Self>) {
+        let log = self.log_view.read(cx);
+        let windows: Vec<Vec<LogObject>> = crate::handlers::trigger_windows(
+            &log.messages,
+            &self.trigger_events,
+            (self.trigger_pre_s * 1_000_000_000.0) as u64,
+            (self.trigger_post_s * 1_000_000_000.0) as u64,
+        )
+        .into_iter()
+        .map(|window| log.messages[window].to_vec())
+        .collect();
+        let start_time = self.start_time;
+        let manifest_sources = self.manifest_sources();
+
+        cx.spawn(async move |this, cx| {
+            let Some(folder) = rfd::AsyncFileDialog::new()
+                .set_title("Folder for trigger windows")
+                .pick_folder()
+                .await
+            else {
+                return;
+            };
+            let dir = folder.path().to_path_buf();
+            let result = cx
+                .background_executor()
+                .spawn({
+                    let dir = dir.clone();
+                    async move {
+                        let paths = crate::handlers::save_trigger_windows(
+                            &dir,
+                            "capture",
+                            windows.iter().map(Vec::as_slice),
+                            start_time,
+                        )?;
+                        if let Some(sources) = manifest_sources {
+                            let manifest = dir.join(format!(
+                                "capture_triggers{}",
+                                crate::handlers::MANIFEST_SUFFIX
+                            ));
+                            crate::handlers::write_manifest(&manifest, &paths, &sources)?;
+                        }
+                        Ok::<_, String>(paths)
+                    }
+                })
+                .await;
+            let _ = this.update(cx, |app, cx| {
+                app.status_msg = match result {
+                    Ok(paths) => format!(
+                        "⚡ Saved {} trigger windows to {}",
+                        paths.len(),
+                        dir.display()
+                    )
+                    .into(),
+                    Err(e) => format!("❌ Saving trigger windows failed: {}", e).into(),
+                };
+                cx.notify();
+            });
+        })
+        .detach();
+    }
+
+    pub(super) fn render_triggers_modal(
+        &self,
+        view: Entity<CanViewApp>,
+        on_close: impl Fn(&mut Window, &mut App) + Clone + 'static,
+    ) -> Modal {
+        let muted = |text: &'static str| div().text_xs().text_color(rgb(0x646473)).child(text);
+        let window_field = |label: &'static str, input: Option<&Entity<InputState>>| {
+            div()
+                .flex()
+                .items_center()
+                .justify_between()
+                .child(div().text_xs().text_color(rgb(0x9ca3af)).child(label))
+                .child(
+                    div()
+                        .flex()
+                        .items_center()
+                        .gap_2()
+                        .when_some(input, |el, input| {
+                            el.child(div().w(px(120.)).child(Input::new(input)))
+                        })
+                        .child(muted("s")),
+                )
+        };
+
+        Modal::new("triggers-modal")
+            .title("Triggers")
+            .width(px(520.))
+            .child(muted(
+                "Rules separated by ';': id 0x123, id 2:0x123 (channel 2 only), \
+                 signal 0x100 Speed > 50, 0x123 byte3 bit2 == 1, error.",
+            ))
+            .when_some(self.trigger_rules_input.as_ref(), |modal, input| {
+                modal.child(Input::new(input))
+            })
+            .child(window_field("Pre-trigger", self.trigger_pre_input.as_ref()))
+            .child(window_field(
+                "Post-trigger",
+                self.trigger_post_input.as_ref(),
+            ))
+            .child(Self::render_choice_row(
+                view.clone(),
+                "On trigger",
+                [
+                    (false, "Mark segments".into()),
+                    (true, "Mark and save windows".into()),
+                ],
+                self.trigger_auto_save,
+                |_| true,
+                |app, auto_save, cx| {
+                    app.trigger_auto_save = auto_save;
+                    cx.notify();
+                },
+            ))
+            .child(muted(
+                "Each trigger starts a new segment, marked with an amber line in the log. \
+                 Saved windows are written as BLF files to a folder chosen next.",
+            ))
+            .footer(
+                div()
+                    .flex()
+                    .gap_2()
+                    .child(Self::render_modal_button(
+                        "triggers-clear",
+                        "Clear",
+                        false,
+                        {
+                            let view = view.clone();
+                            move |_window, cx| {
+                                view.update(cx, |app, cx| {
+                                    app.trigger_events.clear();
+                                    app.status_msg = "⚡ Trigger marks cleared".into();
+                                    cx.notify();
+                                });
+                            }
+                        },
+                    ))
+                    .child(Self::render_modal_button(
+                        "triggers-cancel",
+                        "Cancel",
+                        false,
+                        on_close.clone(),
+                    ))
+                    .child(Self::render_modal_button("triggers-run", "Run", true, {
+                        let view = view.clone();
+                        move |_window, cx| {
+                            view.update(cx, |app, cx| app.run_triggers(cx));
+                        }
+                    })),
+            )
+            .on_close(on_close)
+    }
+}
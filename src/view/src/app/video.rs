@@ -0,0 +1,311 @@
+//! The video bar: a video synced to the log and following the time cursor
+
+use super::state::CanViewApp;
+use crate::handlers::VideoSync;
+use gpui::{prelude::*, *};
+use gpui_component::input::Input;
+use std::time::Duration;
+
+/// Interval at which a playing video moves the time cursor
+const VIDEO_PLAYBACK_TICK: Duration = Duration::from_millis(100);
+
+/// Height of the frame area of the video pane
+const VIDEO_PANE_HEIGHT: f32 = 240.0;
+
+impl CanViewApp {
+    /// Corrected timestamp of the first selected row, used as the time cursor
+    pub(super) fn cursor_time(&self, cx: &App) -> Option<u64> {
+        let log = self.log_view.read(cx);
+        let index = log.selection.indices().next()?;
+        log.messages
+            .get(index)
+            .map(|msg| self.time_offsets.timestamp(msg))
+    }
+
+    fn open_video_file(&mut self, cx: &mut Context<Self>) {
+        cx.spawn(async move |this, cx| {
+            let Some(file) = rfd::AsyncFileDialog::new()
+                .add_filter("Video", &["mp4", "mov", "mkv", "avi"])
+                .pick_file()
+                .await
+            else {
+                return;
+            };
+            let path = file.path().to_path_buf();
+            let _ = this.update(cx, |app, cx| {
+                app.status_msg = format!("🎬 Video: {}", path.display()).into();
+                app.video = Some(VideoSync::new(path));
+                app.video_pane.clear();
+                app.video_playback = None;
+                app.seek_video_to_cursor(cx);
+                cx.notify();
+            });
+        })
+        .detach();
+    }
+
+    fn close_video(&mut self, cx: &mut Context<Self>) {
+        self.video = None;
+        self.video_pane.clear();
+        self.video_playback = None;
+        cx.notify();
+    }
+
+    /// Show the frame matching the time cursor in the video pane
+    pub(super) fn seek_video_to_cursor(&mut self, cx: &mut Context<Self>) {
+        if let Some(log_ns) = self.cursor_time(cx) {
+            self.seek_video(log_ns, cx);
+        }
+    }
+
+    /// Show the frame at log time `log_ns`; nothing before the video starts
+    fn seek_video(&mut self, log_ns: u64, cx: &mut Context<Self>) {
+        let Some(seconds) = self
+            .video
+            .as_ref()
+            .and_then(|video| video.video_position(log_ns))
+        else {
+            return;
+        };
+        if let Some(position) = self.video_pane.seek(seconds) {
+            self.extract_video_frame(position, cx);
+        }
+    }
+
+    /// Run ffmpeg for the frame at `position` ms, then for the latest seek made meanwhile
+    fn extract_video_frame(&mut self, position: u64, cx: &mut Context<Self>) {
+        let Some(video) = &self.video else {
+            return;
+        };
+        let path = video.path.clone();
+        let dir = video.frames_dir();
+        cx.spawn(async move |this, cx| {
+            let result = cx
+                .background_executor()
+                .spawn({
+                    let path = path.clone();
+                    async move { crate::handlers::extract_frame(&path, &dir, position) }
+                })
+                .await;
+            let _ = this.update(cx, |app, cx| {
+                // The video was closed or replaced while ffmpeg ran
+                if app.video.as_ref().map(|video| &video.path) != Some(&path) {
+                    return;
+                }
+                if let Some(next) = app.video_pane.finished(position, result) {
+                    app.extract_video_frame(next, cx);
+                }
+                cx.notify();
+            });
+        })
+        .detach();
+    }
+
+    /// Play the video from the time cursor, moving the cursor along, or pause it
+    fn toggle_video_playback(&mut self, cx: &mut Context<Self>) {
+        if self.video_playback.take().is_some() {
+            cx.notify();
+            return;
+        }
+        let start = self.cursor_time(cx).or_else(|| {
+            let log = self.log_view.read(cx);
+            let first = *self.filtered_indices(cx).first()?;
+            Some(self.time_offsets.timestamp(&log.messages[first]))
+        });
+        let Some(start_ns) = start else {
+            self.status_msg = "❌ No rows to play the video along".into();
+            cx.notify();
+            return;
+        };
+        let started = std::time::Instant::now();
+        self.video_playback = Some((start_ns, started));
+        self.seek_video(start_ns, cx);
+        cx.spawn(async move |this, cx| {
+            loop {
+                cx.background_executor().timer(VIDEO_PLAYBACK_TICK).await;
+                let playing = this
+                    .update(cx, |app, cx| app.tick_video_playback(started, cx))
+                    .unwrap_or(false);
+                if !playing {
+                    break;
+                }
+            }
+        })
+        .detach();
+        cx.notify();
+    }
+
+    /// Move the time cursor and the frame to the playback position;
+    /// false once the playback started at `started` was paused or reached the end
+    fn tick_video_playback(&mut self, started: std::time::Instant, cx: &mut Context<Self>) -> bool {
+        let Some((start_ns, wall)) = self.video_playback else {
+            return false;
+        };
+        if wall != started {
+            return false;
+        }
+        let log_ns = start_ns.saturating_add(wall.elapsed().as_nanos() as u64);
+        let rows = self.filtered_indices(cx);
+        let log = self.log_view.read(cx);
+        // Last visible row at or before the playback position
+        let position =
+            rows.partition_point(|&row| self.time_offsets.timestamp(&log.messages[row]) <= log_ns);
+        if position == rows.len() {
+            self.video_playback = None;
+            self.status_msg = "🎬 Video playback reached the end of the log".into();
+            cx.notify();
+            return false;
+        }
+        let moved = position
+            .checked_sub(1)
+            .filter(|&position| log.selection.indices().next() != Some(rows[position]));
+        if let Some(position) = moved {
+            self.select_row_at(&rows, position, cx);
+        }
+        self.seek_video(log_ns, cx);
+        cx.notify();
+        true
+    }
+
+    /// Set the offset so the selected row lines up with the typed video position
+    fn align_video_to_cursor(&mut self, cx: &mut Context<Self>) {
+        let Some(log_ns) = self.cursor_time(cx) else {
+            self.status_msg = "❌ Select the row of the event seen in the video".into();
+            cx.notify();
+            return;
+        };
+        let text = self
+            .video_position_input
+            .as_ref()
+            .map(|input| input.read(cx).value().to_string())
+            .unwrap_or_default();
+        match crate::handlers::parse_video_position(&text) {
+            Ok(video_s) => {
+                if let Some(video) = &mut self.video {
+                    video.align(log_ns, video_s);
+                    self.status_msg = format!(
+                        "🎬 Video offset set to {:+.3} s",
+                        video.offset_ns as f64 / 1_000_000_000.0
+                    )
+                    .into();
+                }
+                self.seek_video(log_ns, cx);
+            }
+            Err(e) => self.status_msg = format!("❌ {}", e).into(),
+        }
+        cx.notify();
+    }
+
+    /// Video pane: the frame at the time cursor above the playback and offset controls
+    pub(super) fn render_video_bar(&self, view: Entity<CanViewApp>, cx: &App) -> impl IntoElement {
+        type Action = fn(&mut CanViewApp, &mut Context<CanViewApp>);
+        let button = |id: &'static str, label: &'static str, on_click: Action| {
+            let view = view.clone();
+            Self::render_modal_button(id, label, false, move |_window, cx| {
+                view.update(cx, |app, cx| on_click(app, cx));
+            })
+        };
+        let muted = |text: String| div().text_xs().text_color(rgb(0x646473)).child(text);
+
+        let controls = div()
+            .h(px(40.))
+            .px_3()
+            .flex()
+            .items_center()
+            .gap_3()
+            .text_xs()
+            .text_color(rgb(0x9ca3af));
+        let pane = div()
+            .flex()
+            .flex_col()
+            .bg(rgb(0x141414))
+            .border_t_1()
+            .border_color(rgb(0x2a2a2a));
+
+        let Some(video) = &self.video else {
+            return pane.child(
+                controls
+                    .child(muted("No video loaded".to_string()))
+                    .child(button("video-open", "Open video…", |app, cx| {
+                        app.open_video_file(cx)
+                    })),
+            );
+        };
+
+        let cursor = match self.cursor_time(cx) {
+            Some(log_ns) => match video.video_position(log_ns) {
+                Some(position) => format!(
+                    "Cursor → {}",
+                    crate::handlers::format_video_position(position)
+                ),
+                None => "Cursor is before the video starts".to_string(),
+            },
+            None => "Select a row to sync".to_string(),
+        };
+        let frame_status = match (&self.video_pane.error, self.video_pane.extracting) {
+            (Some(error), _) => Some(format!("❌ {}", error)),
+            (None, Some(_)) if self.video_pane.shown.is_none() => {
+                Some("Loading frame…".to_string())
+            }
+            _ => None,
+        };
+
+        pane.child(
+            div()
+                .h(px(VIDEO_PANE_HEIGHT))
+                .flex()
+                .items_center()
+                .justify_center()
+                .bg(rgb(0x000000))
+                .when_some(self.video_pane.shown.as_ref(), |el, (_, image)| {
+                    el.child(
+                        img(image.clone())
+                            .size_full()
+                            .object_fit(ObjectFit::Contain),
+                    )
+                })
+                .when_some(frame_status, |el, status| el.child(muted(status))),
+        )
+        .child(
+            controls
+                .child(
+                    div()
+                        .max_w(px(200.))
+                        .overflow_hidden()
+                        .whitespace_nowrap()
+                        .child(
+                            video
+                                .path
+                                .file_name()
+                                .map(|name| name.to_string_lossy().to_string())
+                                .unwrap_or_default(),
+                        ),
+                )
+                .child(muted(format!(
+                    "offset {:+.3} s",
+                    video.offset_ns as f64 / 1_000_000_000.0
+                )))
+                .child(muted(cursor))
+                .child(button(
+                    "video-play",
+                    if self.video_playback.is_some() {
+                        "⏸ Pause"
+                    } else {
+                        "▶ Play"
+                    },
+                    |app, cx| app.toggle_video_playback(cx),
+                ))
+                .child(div().flex_1())
+                .child(muted("Selected row is at".to_string()))
+                .when_some(self.video_position_input.as_ref(), |el, input| {
+                    el.child(div().w(px(100.)).child(Input::new(input)))
+                })
+                .child(button("video-align", "Align", |app, cx| {
+                    app.align_video_to_cursor(cx)
+                }))
+                .child(button("video-close", "Close video", |app, cx| {
+                    app.close_video(cx)
+                })),
+        )
+    }
+}
@@ -1,57 +1,31 @@
 //! Configuration file I/O operations
 //!
-//! Handles loading and saving configuration files.
+//! Handles loading and saving configuration files. The pickers are async
+//! (`rfd::AsyncFileDialog`); reading and writing are plain blocking calls
+//! meant to run on the background executor.
 
 use crate::AppConfig;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
-/// Load configuration from a file selected by the user
-pub fn load_config_from_file() -> Option<(AppConfig, PathBuf, String)> {
-    if let Some(path) = rfd::FileDialog::new()
+/// Ask the user for a configuration file to open
+pub async fn pick_config_file() -> Option<PathBuf> {
+    rfd::AsyncFileDialog::new()
         .add_filter("Config Files", &["json"])
         .pick_file()
-    {
-        if let Ok(content) = std::fs::read_to_string(&path) {
-            match serde_json::from_str::<AppConfig>(&content) {
-                Ok(config) => {
-                    let status_msg = "Configuration loaded successfully".to_string();
-                    return Some((config, path, status_msg));
-                }
-                Err(e) => {
-                    let status_msg = format!("Config Error: {}", e);
-                    return Some((AppConfig::default(), path, status_msg));
-                }
-            }
-        }
-    }
-    None
+        .await
+        .map(|file| file.path().to_path_buf())
 }
 
-/// Save configuration to a file
-pub fn save_config_to_file(
-    config: &AppConfig,
-    default_path: Option<PathBuf>,
-) -> Option<(PathBuf, String)> {
-    let path = if let Some(existing_path) = default_path {
-        existing_path
-    } else {
-        rfd::FileDialog::new()
-            .add_filter("Config Files", &["json"])
-            .save_file()?
-    };
+/// Read and parse a configuration file
+pub fn read_config_file(path: &Path) -> Result<AppConfig, String> {
+    let content =
+        std::fs::read_to_string(path).map_err(|e| format!("Failed to read config: {}", e))?;
+    serde_json::from_str::<AppConfig>(&content).map_err(|e| format!("Config Error: {}", e))
+}
 
-    match serde_json::to_string_pretty(config) {
-        Ok(content) => {
-            if std::fs::write(&path, content).is_ok() {
-                let status_msg = "Configuration saved successfully".to_string();
-                Some((path, status_msg))
-            } else {
-                Some((path, "Failed to write config file".to_string()))
-            }
-        }
-        Err(e) => {
-            let status_msg = format!("Failed to serialize config: {}", e);
-            Some((path, status_msg))
-        }
-    }
+/// Serialize a configuration to `path`
+pub fn write_config_file(config: &AppConfig, path: &Path) -> Result<(), String> {
+    let content = serde_json::to_string_pretty(config)
+        .map_err(|e| format!("Failed to serialize config: {}", e))?;
+    std::fs::write(path, content).map_err(|_| "Failed to write config file".to_string())
 }
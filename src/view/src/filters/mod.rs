@@ -6,15 +6,7 @@ use blf::LogObject;
 pub fn filter_by_id(messages: &[LogObject], filter_id: u32) -> Vec<LogObject> {
     messages
         .iter()
-        .filter(|msg| match msg {
-            LogObject::CanMessage(can_msg) => can_msg.id == filter_id,
-            LogObject::CanMessage2(can_msg) => can_msg.id == filter_id,
-            LogObject::CanFdMessage(fd_msg) => fd_msg.id == filter_id,
-            LogObject::CanFdMessage64(fd_msg) => fd_msg.id == filter_id,
-            LogObject::LinMessage(lin_msg) => lin_msg.id as u32 == filter_id,
-            LogObject::LinMessage2(_) => false,
-            _ => false,
-        })
+        .filter(|msg| msg.id() == Some(filter_id))
         .cloned()
         .collect()
 }
@@ -23,7 +15,7 @@ pub fn filter_by_id(messages: &[LogObject], filter_id: u32) -> Vec<LogObject> {
 pub fn filter_by_channel(messages: &[LogObject], filter_ch: u16) -> Vec<LogObject> {
     messages
         .iter()
-        .filter(|msg| msg.channel() == filter_ch)
+        .filter(|msg| msg.channel() == Some(filter_ch))
         .cloned()
         .collect()
 }
@@ -36,18 +28,7 @@ pub fn filter_by_id_and_channel(
 ) -> Vec<LogObject> {
     messages
         .iter()
-        .filter(|msg| {
-            let matches_id = match msg {
-                LogObject::CanMessage(can_msg) => can_msg.id == filter_id,
-                LogObject::CanMessage2(can_msg) => can_msg.id == filter_id,
-                LogObject::CanFdMessage(fd_msg) => fd_msg.id == filter_id,
-                LogObject::CanFdMessage64(fd_msg) => fd_msg.id == filter_id,
-                LogObject::LinMessage(lin_msg) => lin_msg.id as u32 == filter_id,
-                LogObject::LinMessage2(_) => false,
-                _ => false,
-            };
-            matches_id && msg.channel() == filter_ch
-        })
+        .filter(|msg| msg.id() == Some(filter_id) && msg.channel() == Some(filter_ch))
         .cloned()
         .collect()
 }
@@ -56,17 +37,7 @@ pub fn filter_by_id_and_channel(
 pub fn get_unique_ids(messages: &[LogObject]) -> Vec<u32> {
     use std::collections::HashSet;
 
-    let mut ids = HashSet::new();
-    for msg in messages {
-        match msg {
-            LogObject::CanMessage(can_msg) => { ids.insert(can_msg.id); }
-            LogObject::CanMessage2(can_msg) => { ids.insert(can_msg.id); }
-            LogObject::CanFdMessage(fd_msg) => { ids.insert(fd_msg.id); }
-            LogObject::CanFdMessage64(fd_msg) => { ids.insert(fd_msg.id); }
-            LogObject::LinMessage(lin_msg) => { ids.insert(lin_msg.id as u32); }
-            _ => {}
-        }
-    }
+    let ids: HashSet<u32> = messages.iter().filter_map(LogObject::id).collect();
 
     let mut sorted_ids: Vec<_> = ids.into_iter().collect();
     sorted_ids.sort_unstable();
@@ -77,10 +48,7 @@ pub fn get_unique_ids(messages: &[LogObject]) -> Vec<u32> {
 pub fn get_unique_channels(messages: &[LogObject]) -> Vec<u16> {
     use std::collections::HashSet;
 
-    let mut channels = HashSet::new();
-    for msg in messages {
-        channels.insert(msg.channel());
-    }
+    let channels: HashSet<u16> = messages.iter().filter_map(LogObject::channel).collect();
 
     let mut sorted_channels: Vec<_> = channels.into_iter().collect();
     sorted_channels.sort_unstable();
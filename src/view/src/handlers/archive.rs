@@ -0,0 +1,283 @@
+//! Compressed logs
+//!
+//! Loggers often upload `run.blf.gz`, or a `.zip` holding a single log. Such
+//! files are recognised by their leading bytes rather than the extension and
+//! unpacked on the fly, so they open like a plain BLF without extracting them
+//! first.
+
+use crate::handlers::{read_candump_file, read_trc_file};
+use blf::{BlfReader, BlfResult, BlfStream, FileStatistics, IdIndex, LogObject, LogSummary};
+use std::borrow::Cow;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Cursor, Read};
+use std::path::Path;
+
+/// Extensions offered by the open dialogs; `.log` is a candump text log and
+/// `.trc` a PEAK trace
+pub const LOG_FILE_EXTENSIONS: [&str; 6] = ["blf", "bin", "gz", "zip", "log", "trc"];
+
+const GZIP_MAGIC: &[u8] = &[0x1F, 0x8B];
+const ZIP_MAGIC: &[u8] = b"PK\x03\x04";
+/// Enough of a log to hold its statistics header
+const HEADER_PROBE_LEN: u64 = 1024;
+/// Containers parsed ahead of the consumer of a [`stream_log_file`]
+const STREAM_CAPACITY: usize = 4;
+
+/// How a log file is packed, told by its leading bytes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Compression {
+    Gzip,
+    Zip,
+}
+
+impl Compression {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Gzip => "gzip",
+            Self::Zip => "ZIP",
+        }
+    }
+}
+
+/// The log inside `data`: gunzipped, the single log of a ZIP archive, or `data` itself
+pub fn unpack_log(data: &[u8]) -> Result<Cow<'_, [u8]>, String> {
+    if data.starts_with(GZIP_MAGIC) {
+        let mut unpacked = Vec::new();
+        flate2::read::MultiGzDecoder::new(data)
+            .read_to_end(&mut unpacked)
+            .map_err(|e| format!("Corrupt gzip file: {}", e))?;
+        return Ok(Cow::Owned(unpacked));
+    }
+    if data.starts_with(ZIP_MAGIC) {
+        return unzip_log(data).map(Cow::Owned);
+    }
+    Ok(Cow::Borrowed(data))
+}
+
+/// Contents of the one log in a ZIP archive, itself unpacked if compressed
+fn unzip_log(data: &[u8]) -> Result<Vec<u8>, String> {
+    let mut archive =
+        zip::ZipArchive::new(Cursor::new(data)).map_err(|e| format!("Corrupt ZIP file: {}", e))?;
+    let files: Vec<String> = archive
+        .file_names()
+        .filter(|name| !name.ends_with('/'))
+        .map(str::to_string)
+        .collect();
+    // Archives often carry a readme or config next to the log
+    let logs: Vec<&String> = files
+        .iter()
+        .filter(|name| {
+            let name = name.to_ascii_lowercase();
+            name.ends_with(".blf") || name.ends_with(".blf.gz")
+        })
+        .collect();
+    let name = match (logs.as_slice(), files.as_slice()) {
+        ([log], _) => *log,
+        ([], [file]) => file,
+        ([], _) => return Err("The ZIP file holds no BLF log".to_string()),
+        (logs, _) => {
+            return Err(format!(
+                "The ZIP file holds {} logs; extract the one to open",
+                logs.len()
+            ));
+        }
+    };
+
+    let mut entry = archive
+        .by_name(name)
+        .map_err(|e| format!("{}: {}", name, e))?;
+    let mut contents = Vec::new();
+    entry
+        .read_to_end(&mut contents)
+        .map_err(|e| format!("{}: {}", name, e))?;
+    // Nested archives are not followed, only a gzipped log
+    if contents.starts_with(GZIP_MAGIC) {
+        return unpack_log(&contents).map(Cow::into_owned);
+    }
+    Ok(contents)
+}
+
+/// A lazy reader over the log at `path`
+///
+/// Plain and gzipped logs are read as the objects are consumed, so even
+/// multi-GB files are never held in memory whole. ZIP archives keep their
+/// directory at the end and are unpacked in memory first.
+pub fn open_log_file(path: &Path) -> Result<BlfReader<Box<dyn Read + Send>>, String> {
+    let error = |e: std::io::Error| format!("{}: {}", path.display(), e);
+    let mut file = BufReader::new(File::open(path).map_err(error)?);
+    let head = file.fill_buf().map_err(error)?;
+    let inner: Box<dyn Read + Send> = if head.starts_with(GZIP_MAGIC) {
+        Box::new(BufReader::new(flate2::read::MultiGzDecoder::new(file)))
+    } else if head.starts_with(ZIP_MAGIC) {
+        let mut data = Vec::new();
+        file.read_to_end(&mut data).map_err(error)?;
+        let log = unzip_log(&data).map_err(|e| format!("{}: {}", path.display(), e))?;
+        Box::new(Cursor::new(log))
+    } else {
+        Box::new(file)
+    };
+    BlfReader::new(inner).map_err(|e| format!("{}: {:?}", path.display(), e))
+}
+
+/// Parse the log at `path` on a background thread
+///
+/// The parser stays a few containers ahead of the consumer and waits for it
+/// when it falls behind, so parsing overlaps with whatever the consumer does
+/// without the whole log piling up in between.
+pub fn stream_log_file(path: &Path) -> Result<(FileStatistics, BlfStream), String> {
+    let reader = open_log_file(path)?;
+    let file_stats = reader.file_stats().clone();
+    Ok((file_stats, BlfStream::spawn(reader, STREAM_CAPACITY)))
+}
+
+/// Read a BLF from disk, unpacking it first if it is compressed
+pub fn read_log_file(path: &Path) -> Result<BlfResult, String> {
+    let mut objects = Vec::new();
+    let mut summary = LogSummary::default();
+    let mut id_index = IdIndex::default();
+    let file_stats = visit_log_file(path, |batch| {
+        summary.extend(&batch);
+        id_index.extend(&batch);
+        objects.extend(batch);
+    })?;
+    Ok(BlfResult {
+        file_stats,
+        objects,
+        summary,
+        id_index,
+    })
+}
+
+/// Read a BLF from disk without collecting its objects, a container at a time
+///
+/// A candump or TRC text log is parsed whole and handed over as a single batch.
+pub fn visit_log_file(
+    path: &Path,
+    mut on_objects: impl FnMut(Vec<LogObject>),
+) -> Result<FileStatistics, String> {
+    if let Some((file_stats, log)) = read_candump_file(path)? {
+        on_objects(log.objects);
+        return Ok(file_stats);
+    }
+    if let Some((file_stats, log)) = read_trc_file(path)? {
+        on_objects(log.objects);
+        return Ok(file_stats);
+    }
+    let (file_stats, mut stream) = stream_log_file(path)?;
+    while let Some(batch) = stream.recv_batch() {
+        on_objects(batch.map_err(|e| format!("{}: {:?}", path.display(), e))?);
+    }
+    Ok(file_stats)
+}
+
+/// The first `len` bytes of the log at `path`, unpacked if the file is
+/// compressed, and how it was compressed
+///
+/// A ZIP archive keeps its directory at the end and is unpacked whole.
+pub fn read_log_head(path: &Path, len: u64) -> Result<(Option<Compression>, Vec<u8>), String> {
+    let error = |e: std::io::Error| format!("{}: {}", path.display(), e);
+    let mut head = Vec::new();
+    File::open(path)
+        .and_then(|file| file.take(len).read_to_end(&mut head))
+        .map_err(error)?;
+    if head.starts_with(GZIP_MAGIC) {
+        head.clear();
+        File::open(path)
+            .and_then(|file| {
+                flate2::read::MultiGzDecoder::new(file)
+                    .take(len)
+                    .read_to_end(&mut head)
+            })
+            .map_err(error)?;
+        return Ok((Some(Compression::Gzip), head));
+    }
+    if head.starts_with(ZIP_MAGIC) {
+        let data = std::fs::read(path).map_err(error)?;
+        let log = unzip_log(&data).map_err(|e| format!("{}: {}", path.display(), e))?;
+        return Ok((Some(Compression::Zip), log));
+    }
+    Ok((None, head))
+}
+
+/// The statistics header of a log, reading no more of the file than needed
+pub fn read_log_header(path: &Path) -> Result<FileStatistics, String> {
+    let (_, head) = read_log_head(path, HEADER_PROBE_LEN)?;
+    FileStatistics::read(&mut Cursor::new(&head[..]))
+        .map_err(|e| format!("{}: {:?}", path.display(), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn gzip(data: &[u8]) -> Vec<u8> {
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(data).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    fn zip(files: &[(&str, &[u8])]) -> Vec<u8> {
+        let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        for (name, data) in files {
+            writer
+                .start_file(*name, zip::write::SimpleFileOptions::default())
+                .unwrap();
+            writer.write_all(data).unwrap();
+        }
+        writer.finish().unwrap().into_inner()
+    }
+
+    #[test]
+    fn test_unpack_log() {
+        let log = b"LOGG and some frames".as_slice();
+        assert_eq!(unpack_log(log).unwrap(), log);
+        assert_eq!(unpack_log(&gzip(log)).unwrap(), log);
+
+        let archive = zip(&[("readme.txt", b"hi"), ("logs/run.blf", log)]);
+        assert_eq!(unpack_log(&archive).unwrap(), log);
+        let archive = zip(&[("run.blf.gz", &gzip(log))]);
+        assert_eq!(unpack_log(&archive).unwrap(), log);
+        let archive = zip(&[("upload.bin", log)]);
+        assert_eq!(unpack_log(&archive).unwrap(), log);
+
+        assert!(unpack_log(&zip(&[("a.blf", log), ("b.blf", log)])).is_err());
+        assert!(unpack_log(&zip(&[("a.txt", b"a"), ("b.txt", b"b")])).is_err());
+        assert!(unpack_log(&gzip(log)[..8]).is_err());
+    }
+
+    #[test]
+    fn test_read_log_file_streams_plain_and_gzipped_logs() {
+        let frames: Vec<LogObject> = (0..2_000)
+            .map(|id| {
+                LogObject::CanMessage(blf::CanMessage {
+                    id,
+                    ..Default::default()
+                })
+            })
+            .collect();
+        let mut writer = blf::BlfWriter::new(
+            Cursor::new(Vec::new()),
+            blf::SystemTime::from_timestamp_nanos(0),
+        )
+        .unwrap();
+        for frame in &frames {
+            writer.write_object(frame).unwrap();
+        }
+        writer.finish().unwrap();
+        let log = writer.into_inner().into_inner();
+
+        let dir = std::env::temp_dir().join(format!("canview_archive_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        for (name, data) in [("run.blf", log.clone()), ("run.blf.gz", gzip(&log))] {
+            let path = dir.join(name);
+            std::fs::write(&path, data).unwrap();
+            let result = read_log_file(&path).unwrap();
+            assert_eq!(result.file_stats.object_count, 2_000);
+            assert_eq!(result.objects.len(), 2_000);
+            assert_eq!(result.objects[1_999].id(), Some(1_999));
+            assert_eq!(result.summary.count(blf::ObjectType::CanMessage), 2_000);
+        }
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,216 @@
+//! Export to Vector ASC
+//!
+//! ASC is the text log of CANoe and CANalyzer, so an export in it can be
+//! opened there directly. The header declares hex IDs and absolute
+//! timestamps: every line carries seconds since the measurement start, the
+//! same time base as the log view. CAN and CAN FD frames are written; other
+//! objects, LIN included, have no line here and are skipped.
+//!
+//! ```text
+//! date Thu Oct 17 09:30:00.000 am 2026
+//! base hex  timestamps absolute
+//! internal events logged
+//! // version 9.0.0
+//! Begin Triggerblock Thu Oct 17 09:30:00.000 am 2026
+//!    0.000000 Start of measurement
+//!    0.012345 1  1A0             Rx   d 8 01 02 03 04 05 06 07 08
+//! End TriggerBlock
+//! ```
+
+use blf::{CanFdMessage, CanFdMessage64, CanMessage2, LogObject};
+
+/// Closes the trigger block opened by [`asc_header`]
+pub const ASC_FOOTER: &str = "End TriggerBlock\n";
+
+/// Set in a BLF CAN ID for 29-bit identifiers
+const EXTENDED_ID_FLAG: u32 = 0x8000_0000;
+
+/// Flags column of a CANFD line
+const ASC_FD_FLAG_EDL: u32 = 0x1000;
+const ASC_FD_FLAG_BRS: u32 = 0x2000;
+const ASC_FD_FLAG_ESI: u32 = 0x4000;
+
+/// Header up to the start of measurement, dated with the log's start time
+pub fn asc_header(start_time: Option<chrono::NaiveDateTime>) -> String {
+    let date = start_time
+        .unwrap_or_default()
+        .format("%a %b %d %I:%M:%S%.3f %P %Y")
+        .to_string();
+    format!(
+        "date {date}\nbase hex  timestamps absolute\ninternal events logged\n\
+         // version 9.0.0\nBegin Triggerblock {date}\n   0.000000 Start of measurement\n"
+    )
+}
+
+/// The ASC line of a CAN or CAN FD frame at `timestamp_ns`, `None` for other objects
+pub fn format_asc_frame(msg: &LogObject, timestamp_ns: u64) -> Option<String> {
+    let time_s = timestamp_ns as f64 / 1_000_000_000.0;
+    let line = match msg {
+        LogObject::CanMessage(m) => can_line(m.channel, m.id, m.flags, m.dlc, &m.data),
+        LogObject::CanMessage2(m) => can_line(m.channel, m.id, m.flags, m.dlc, &m.data),
+        LogObject::CanFdMessage(m) if m.can_fd_flags & CanFdMessage::FD_FLAG_EDL == 0 => {
+            can_line(m.channel, m.id, m.flags, m.dlc, &m.data)
+        }
+        LogObject::CanFdMessage(m) => {
+            let mut flags = ASC_FD_FLAG_EDL;
+            if m.can_fd_flags & CanFdMessage::FD_FLAG_BRS != 0 {
+                flags |= ASC_FD_FLAG_BRS;
+            }
+            if m.can_fd_flags & CanFdMessage::FD_FLAG_ESI != 0 {
+                flags |= ASC_FD_FLAG_ESI;
+            }
+            let len = m.data.len().min(m.valid_data_bytes as usize);
+            let tx = m.flags & CanFdMessage::FLAG_TX != 0;
+            can_fd_line(m.channel, tx, m.id, m.dlc, flags, &m.data[..len])
+        }
+        LogObject::CanFdMessage64(m) => {
+            let len = m.data.len().min(m.valid_data_bytes as usize);
+            if !m.is_fd_frame() {
+                let flags = if m.is_tx() { CanMessage2::FLAG_TX } else { 0 };
+                let remote = m.flags & CanFdMessage64::FLAG_REMOTE_FRAME != 0;
+                let flags = if remote {
+                    flags | CanMessage2::FLAG_RTR
+                } else {
+                    flags
+                };
+                can_line(m.channel as u16, m.id, flags, m.dlc, &m.data[..len])
+            } else {
+                let flags = m.flags & (ASC_FD_FLAG_EDL | ASC_FD_FLAG_BRS | ASC_FD_FLAG_ESI);
+                can_fd_line(
+                    m.channel as u16,
+                    m.is_tx(),
+                    m.id,
+                    m.dlc,
+                    flags,
+                    &m.data[..len],
+                )
+            }
+        }
+        _ => return None,
+    };
+    Some(format!("{:>11.6} {}\n", time_s, line))
+}
+
+/// `1A0` for a standard ID, `18FEF100x` for an extended one
+fn asc_id(id: u32) -> String {
+    let extended = id & EXTENDED_ID_FLAG != 0 || id > 0x7FF;
+    let id = id & !EXTENDED_ID_FLAG;
+    if extended {
+        format!("{:X}x", id)
+    } else {
+        format!("{:X}", id)
+    }
+}
+
+fn direction(tx: bool) -> &'static str {
+    if tx { "Tx" } else { "Rx" }
+}
+
+fn hex_bytes(data: &[u8]) -> String {
+    data.iter()
+        .map(|b| format!("{:02X}", b))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// `1  1A0             Rx   d 8 01 02 …`, or `r 8` without data for a remote frame
+fn can_line(channel: u16, id: u32, flags: u8, dlc: u8, data: &[u8]) -> String {
+    let dir = direction(flags & CanMessage2::FLAG_TX != 0);
+    let head = format!("{}  {:<15} {:<4}", channel, asc_id(id), dir);
+    if flags & CanMessage2::FLAG_RTR != 0 {
+        return format!("{} r {:x}", head, dlc);
+    }
+    let len = data.len().min(dlc.min(8) as usize);
+    format!("{} d {:x} {}", head, dlc, hex_bytes(&data[..len]))
+        .trim_end()
+        .to_string()
+}
+
+/// `CANFD   1 Rx        1A0 <name> 1 0 f 64 01 02 … <duration> <length> <flags> …`
+///
+/// Bus timing is not in the log, so the duration, length, CRC and bit timing
+/// columns are written as zero.
+fn can_fd_line(channel: u16, tx: bool, id: u32, dlc: u8, flags: u32, data: &[u8]) -> String {
+    let brs = (flags & ASC_FD_FLAG_BRS != 0) as u8;
+    let esi = (flags & ASC_FD_FLAG_ESI != 0) as u8;
+    let mut line = format!(
+        "CANFD {:>3} {:<4} {:>8}  {:>32} {} {} {:x} {:>2}",
+        channel,
+        direction(tx),
+        asc_id(id),
+        "",
+        brs,
+        esi,
+        dlc,
+        data.len()
+    );
+    if !data.is_empty() {
+        line.push(' ');
+        line.push_str(&hex_bytes(data));
+    }
+    line.push_str(&format!(
+        " {:>8} {:>4} {:>8X} {:>8} {:>8} {:>8} {:>8} {:>8}",
+        0, 0, flags, 0, 0, 0, 0, 0
+    ));
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use blf::CanMessage;
+
+    #[test]
+    fn test_asc_lines() {
+        let start = chrono::NaiveDate::from_ymd_opt(2026, 10, 17)
+            .unwrap()
+            .and_hms_milli_opt(21, 5, 3, 250)
+            .unwrap();
+        let header = asc_header(Some(start));
+        assert!(header.starts_with("date Sat Oct 17 09:05:03.250 pm 2026\nbase hex"));
+        assert!(header.ends_with(
+            "Begin Triggerblock Sat Oct 17 09:05:03.250 pm 2026\n   0.000000 Start of measurement\n"
+        ));
+
+        let can = LogObject::CanMessage(CanMessage {
+            channel: 1,
+            id: 0x1A0,
+            dlc: 2,
+            data: [0x01, 0xAB, 0, 0, 0, 0, 0, 0],
+            ..Default::default()
+        });
+        assert_eq!(
+            format_asc_frame(&can, 12_345_000).unwrap(),
+            "   0.012345 1  1A0             Rx   d 2 01 AB\n"
+        );
+
+        let extended = LogObject::CanMessage(CanMessage {
+            channel: 2,
+            id: 0x18FE_F100 | EXTENDED_ID_FLAG,
+            flags: CanMessage2::FLAG_TX | CanMessage2::FLAG_RTR,
+            dlc: 8,
+            ..Default::default()
+        });
+        assert_eq!(
+            format_asc_frame(&extended, 1_500_000_000).unwrap(),
+            "   1.500000 2  18FEF100x       Tx   r 8\n"
+        );
+
+        let mut fd = CanFdMessage {
+            channel: 1,
+            id: 0x123,
+            dlc: 9,
+            valid_data_bytes: 12,
+            can_fd_flags: CanFdMessage::FD_FLAG_EDL | CanFdMessage::FD_FLAG_BRS,
+            ..Default::default()
+        };
+        fd.data[..12].copy_from_slice(&[0x11; 12]);
+        let line = format_asc_frame(&LogObject::CanFdMessage(fd), 0).unwrap();
+        assert!(line.starts_with("   0.000000 CANFD   1 Rx        123  "));
+        assert!(
+            line.contains(" 1 0 9 12 11 11 11 11 11 11 11 11 11 11 11 11        0    0     3000 ")
+        );
+
+        assert!(format_asc_frame(&LogObject::GpsEvent(Default::default()), 0).is_none());
+    }
+}
@@ -0,0 +1,283 @@
+//! Batch processing of many logs
+//!
+//! The Batch tab runs one pipeline over a list of files: each log is read
+//! (compressed ones included), filtered with a `canview query` condition and
+//! exported in the chosen format into a destination folder. Every file is a
+//! job of its own, so a corrupt log fails only its own job and the summary
+//! lists what went wrong.
+
+use crate::handlers::{
+    BlfExport, CsvExport, Mf4Export, ParquetExport, QueryCondition, TimeOffsets,
+    append_sqlite_rows, create_sqlite_export, finish_sqlite_export, format_candump_rows,
+    format_footer, format_header, format_rows, is_format_supported, naive_start_time,
+    numbered_messages, read_log_file, write_export_manifest,
+};
+use crate::models::preferences::{CsvOptions, ExportFormat};
+use parser::dbc::DbcDatabase;
+use parser::ldf::LdfDatabase;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Filter, format and destination applied to every file of a batch
+#[derive(Debug, Clone, PartialEq)]
+pub struct BatchPipeline {
+    /// Frames kept; all of them when `None`
+    pub condition: Option<QueryCondition>,
+    pub format: ExportFormat,
+    /// Include decoded signals (CSV and JSON)
+    pub decoded: bool,
+    /// Delimiter, time and ID layout of CSV output
+    pub csv: CsvOptions,
+    pub destination: PathBuf,
+    /// Write a SHA-256 manifest next to each output
+    pub manifest: bool,
+}
+
+impl BatchPipeline {
+    /// Output of `input`: `run.blf.gz` → `<destination>/run.csv`
+    pub fn output_path(&self, input: &Path) -> PathBuf {
+        let name = input
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let lower = name.to_ascii_lowercase();
+        let stem = [".blf.gz", ".blf", ".bin", ".gz", ".zip"]
+            .iter()
+            .find(|suffix| lower.ends_with(*suffix))
+            .map_or(name.as_str(), |suffix| &name[..name.len() - suffix.len()]);
+        self.destination
+            .join(format!("{}.{}", stem, self.format.extension()))
+    }
+
+    /// Run the pipeline over one log; returns the output and the number of rows written
+    pub fn run(
+        &self,
+        input: &Path,
+        dbc_channels: &HashMap<u16, DbcDatabase>,
+        ldf_channels: &HashMap<u16, LdfDatabase>,
+    ) -> Result<(PathBuf, usize), String> {
+        if !is_format_supported(self.format) {
+            return Err(format!("{} export is not available", self.format.label()));
+        }
+        let output = self.output_path(input);
+        if output == input {
+            return Err(format!("{} would overwrite the input", output.display()));
+        }
+        let log = read_log_file(input)?;
+        let messages = match &self.condition {
+            Some(condition) => numbered_messages(
+                &log.objects,
+                condition.matching_indices(&log.objects, dbc_channels, ldf_channels),
+            ),
+            None => numbered_messages(&log.objects, 0..log.objects.len()),
+        };
+        let offsets = TimeOffsets::new();
+        let start_time = naive_start_time(&log.file_stats.measurement_start_time);
+
+        let rows = if self.format == ExportFormat::Sqlite {
+            create_sqlite_export(&output)?;
+            let rows =
+                append_sqlite_rows(&output, &messages, dbc_channels, ldf_channels, &offsets)?;
+            finish_sqlite_export(&output)?;
+            rows
+        } else if self.format == ExportFormat::Mf4 {
+            let export = Mf4Export::new(&messages, dbc_channels, ldf_channels, start_time);
+            export.create(&output)?;
+            let rows = export.append(&output, &messages, dbc_channels, ldf_channels, &offsets)?;
+            export.finish(&output)?;
+            rows
+        } else if self.format == ExportFormat::Csv {
+            let export = CsvExport::new(
+                self.csv,
+                self.decoded,
+                &messages,
+                dbc_channels,
+                ldf_channels,
+                start_time,
+            );
+            let (text, rows) = export.rows(&messages, dbc_channels, ldf_channels, &offsets);
+            std::fs::write(&output, export.header() + &text)
+                .map_err(|e| format!("{}: {}", output.display(), e))?;
+            rows
+        } else if self.format == ExportFormat::Blf {
+            let export = BlfExport::new(start_time);
+            export.create(&output)?;
+            let rows = export.append(&messages, &offsets)?;
+            export.finish()?;
+            rows
+        } else if self.format == ExportFormat::Parquet {
+            let export = ParquetExport::new(self.decoded);
+            export.create(&output)?;
+            let rows = export.append(&messages, dbc_channels, ldf_channels, &offsets)?;
+            export.finish()?;
+            rows
+        } else if self.format == ExportFormat::Candump {
+            let (text, rows) = format_candump_rows(&messages, &offsets, start_time);
+            std::fs::write(&output, text).map_err(|e| format!("{}: {}", output.display(), e))?;
+            rows
+        } else {
+            let (text, rows) = format_rows(
+                self.format,
+                self.decoded,
+                &messages,
+                dbc_channels,
+                ldf_channels,
+                &offsets,
+                true,
+            );
+            let text = format_header(self.format, self.decoded, start_time)
+                + &text
+                + &format_footer(self.format, rows > 0);
+            std::fs::write(&output, text).map_err(|e| format!("{}: {}", output.display(), e))?;
+            rows
+        };
+        if self.manifest {
+            write_export_manifest(&output, &[input.to_path_buf()])?;
+        }
+        Ok((output, rows))
+    }
+}
+
+/// Progress of one file of a batch
+#[derive(Debug, Clone, PartialEq)]
+pub enum BatchStatus {
+    Pending,
+    Running,
+    Done { output: PathBuf, rows: usize },
+    Failed(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BatchJob {
+    pub input: PathBuf,
+    pub status: BatchStatus,
+}
+
+impl BatchJob {
+    pub fn new(input: PathBuf) -> Self {
+        Self {
+            input,
+            status: BatchStatus::Pending,
+        }
+    }
+}
+
+impl BatchStatus {
+    pub fn label(&self) -> &'static str {
+        match self {
+            BatchStatus::Pending => "pending",
+            BatchStatus::Running => "running",
+            BatchStatus::Done { .. } => "done",
+            BatchStatus::Failed(_) => "failed",
+        }
+    }
+}
+
+/// "3 done, 1 failed, 2 pending", leaving out the empty counts
+pub fn batch_summary(jobs: &[BatchJob]) -> String {
+    let parts: Vec<String> = ["done", "failed", "running", "pending"]
+        .into_iter()
+        .map(|label| {
+            let count = jobs
+                .iter()
+                .filter(|job| job.status.label() == label)
+                .count();
+            (count, label)
+        })
+        .filter(|(count, _)| *count > 0)
+        .map(|(count, label)| format!("{} {}", count, label))
+        .collect();
+    if parts.is_empty() {
+        "No files".to_string()
+    } else {
+        parts.join(", ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::handlers::parse_condition;
+    use blf::{CanMessage, LogObject};
+
+    #[test]
+    fn test_batch_pipeline() {
+        let dir = std::env::temp_dir().join(format!("canview_batch_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let frames: Vec<LogObject> = [0x100, 0x200, 0x100]
+            .into_iter()
+            .enumerate()
+            .map(|(i, id)| {
+                let mut msg = CanMessage {
+                    channel: 1,
+                    id,
+                    dlc: 8,
+                    ..Default::default()
+                };
+                msg.header.object_time_stamp = i as u64 * 1_000;
+                LogObject::CanMessage(msg)
+            })
+            .collect();
+        let input = dir.join("run.blf");
+        blf::write_blf_to_file(&input, blf::SystemTime::from_timestamp_nanos(0), &frames).unwrap();
+
+        let pipeline = BatchPipeline {
+            condition: Some(parse_condition("id == 0x100").unwrap()),
+            format: ExportFormat::Csv,
+            decoded: false,
+            csv: CsvOptions::default(),
+            destination: dir.clone(),
+            manifest: true,
+        };
+        assert_eq!(
+            pipeline.output_path(Path::new("/in/Run.BLF.gz")),
+            dir.join("Run.csv")
+        );
+        let (output, rows) = pipeline
+            .run(&input, &HashMap::new(), &HashMap::new())
+            .unwrap();
+        assert_eq!(rows, 2);
+        let csv = std::fs::read_to_string(&output).unwrap();
+        // Sequence numbers of the kept frames in the source log
+        let seqs: Vec<&str> = csv.lines().skip(1).map(|line| &line[..1]).collect();
+        assert_eq!(seqs, vec!["1", "3"]);
+        assert!(dir.join("run.csv.manifest.json").exists());
+
+        let blf = BatchPipeline {
+            format: ExportFormat::Blf,
+            destination: dir.join("blf"),
+            ..pipeline.clone()
+        };
+        std::fs::create_dir_all(&blf.destination).unwrap();
+        let (output_blf, rows_blf) = blf.run(&input, &HashMap::new(), &HashMap::new()).unwrap();
+        assert_eq!(rows_blf, 2);
+        assert_eq!(read_log_file(&output_blf).unwrap().objects.len(), 2);
+        let in_place = BatchPipeline {
+            destination: dir.clone(),
+            ..blf
+        };
+        assert!(
+            in_place
+                .run(&input, &HashMap::new(), &HashMap::new())
+                .is_err()
+        );
+
+        let missing = pipeline.run(&dir.join("missing.blf"), &HashMap::new(), &HashMap::new());
+        assert!(missing.is_err());
+
+        let jobs = vec![
+            BatchJob {
+                input: input.clone(),
+                status: BatchStatus::Done { output, rows },
+            },
+            BatchJob {
+                input: dir.join("missing.blf"),
+                status: BatchStatus::Failed(missing.unwrap_err()),
+            },
+            BatchJob::new(input),
+        ];
+        assert_eq!(batch_summary(&jobs), "1 done, 1 failed, 1 pending");
+        assert_eq!(batch_summary(&[]), "No files");
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,476 @@
+//! candump log import and export
+//!
+//! `candump -l` from the Linux can-utils writes one frame per line, stamped
+//! with the Unix time it was received, and `canplayer` replays such files
+//! onto a SocketCAN interface:
+//!
+//! ```text
+//! (1760686200.012345) can0 1A0#0102030405060708
+//! (1760686200.013000) can1 18FEF100#R
+//! (1760686200.020000) can0 123##1112233
+//! ```
+//!
+//! Interfaces map to channels by their number, `can0` being channel 1. CAN FD
+//! frames use `##` followed by a flags digit (1 = BRS, 2 = ESI). Error frames
+//! and other objects have no line here; they are skipped on both sides.
+
+use crate::handlers::{NumberedMessage, TimeOffsets, can_id_digits};
+use blf::{
+    CanFdMessage, CanFdMessage64, CanMessage, CanMessage2, FileStatistics, LogObject, SystemTime,
+};
+use chrono::{Local, NaiveDateTime, TimeZone};
+use std::collections::HashMap;
+use std::io::Read;
+use std::path::Path;
+
+/// Set in a BLF CAN ID for 29-bit identifiers
+const EXTENDED_ID_FLAG: u32 = 0x8000_0000;
+/// Set in a SocketCAN ID for error frames
+const SOCKETCAN_ERROR_FLAG: u32 = 0x2000_0000;
+
+/// Flags digit of a CAN FD frame
+const CANFD_BRS: u8 = 0x1;
+const CANFD_ESI: u8 = 0x2;
+
+/// Payload lengths of the CAN FD DLCs above 8
+const FD_LENGTHS: [usize; 7] = [12, 16, 20, 24, 32, 48, 64];
+
+/// Frames of a candump log, timed from the earliest one
+#[derive(Debug, Clone, PartialEq)]
+pub struct CandumpLog {
+    pub objects: Vec<LogObject>,
+    /// Time of the earliest frame in this computer's zone, to the millisecond
+    pub start_time: Option<NaiveDateTime>,
+    /// Error frames, which have no object to load them into
+    pub skipped_errors: usize,
+}
+
+/// Whether `head`, the first bytes of a file, look like a candump log
+pub fn is_candump_log(head: &[u8]) -> bool {
+    head.trim_ascii_start().starts_with(b"(")
+}
+
+/// Read the candump log at `path`, or `None` if the file holds something else
+pub fn read_candump_file(path: &Path) -> Result<Option<(FileStatistics, CandumpLog)>, String> {
+    let error = |e: std::io::Error| format!("{}: {}", path.display(), e);
+    let mut head = Vec::new();
+    std::fs::File::open(path)
+        .and_then(|file| file.take(64).read_to_end(&mut head))
+        .map_err(error)?;
+    if !is_candump_log(&head) {
+        return Ok(None);
+    }
+    let text = std::fs::read_to_string(path).map_err(error)?;
+    let log = parse_candump(&text).map_err(|e| format!("{}: {}", path.display(), e))?;
+    let file_stats = text_log_statistics(text.len() as u64, &log.objects, log.start_time);
+    Ok(Some((file_stats, log)))
+}
+
+/// Statistics header for a text log of `size` bytes holding `objects`
+pub(crate) fn text_log_statistics(
+    size: u64,
+    objects: &[LogObject],
+    start_time: Option<NaiveDateTime>,
+) -> FileStatistics {
+    let start = start_time.map_or(SystemTime::from_timestamp_nanos(0), system_time);
+    let last = objects.iter().map(LogObject::timestamp).max().unwrap_or(0);
+    FileStatistics {
+        statistics_size: 0,
+        api_number: 0,
+        application_id: 0,
+        compression_level: 0,
+        application_major: 0,
+        application_minor: 0,
+        file_size: size,
+        uncompressed_file_size: size,
+        object_count: objects.len() as u32,
+        application_build: 0,
+        last_object_time: SystemTime::from_timestamp_nanos(
+            start.to_timestamp_nanos() + last as i64,
+        ),
+        measurement_start_time: start,
+    }
+}
+
+/// DLC of a CAN FD payload of `len` bytes, `None` if no DLC has that length
+pub(crate) fn fd_dlc(len: usize) -> Option<u8> {
+    match len {
+        0..=8 => Some(len as u8),
+        _ => FD_LENGTHS
+            .iter()
+            .position(|&fd_len| fd_len == len)
+            .map(|index| 9 + index as u8),
+    }
+}
+
+/// Payload length of a CAN FD frame with DLC `dlc`
+pub(crate) fn fd_len(dlc: u8) -> usize {
+    match dlc {
+        0..=8 => dlc as usize,
+        _ => FD_LENGTHS[(dlc.min(15) - 9) as usize],
+    }
+}
+
+/// Parse the lines of a candump log
+pub fn parse_candump(text: &str) -> Result<CandumpLog, String> {
+    let mut frames = Vec::new();
+    let mut channels: HashMap<&str, u16> = HashMap::new();
+    let mut skipped_errors = 0;
+
+    for (number, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let error = |message: &str| format!("line {}: {}", number + 1, message);
+        let mut fields = line.split_whitespace();
+        let (Some(time), Some(interface), Some(frame)) =
+            (fields.next(), fields.next(), fields.next())
+        else {
+            return Err(error("expected '(time) interface frame'"));
+        };
+        let time_ns = time
+            .strip_prefix('(')
+            .and_then(|time| time.strip_suffix(')'))
+            .and_then(parse_seconds)
+            .ok_or_else(|| error(&format!("invalid time '{}'", time)))?;
+        // Logs written with -x end in T or R
+        let tx = fields.next() == Some("T");
+
+        let next_channel = channels.len() as u16 + 1;
+        let channel = *channels
+            .entry(interface)
+            .or_insert_with(|| interface_channel(interface).unwrap_or(next_channel));
+        match parse_frame(frame, channel, tx).map_err(|e| error(&e))? {
+            Some(object) => frames.push((time_ns, object)),
+            None => skipped_errors += 1,
+        }
+    }
+
+    // The start is kept to the millisecond, as in a BLF header; the rest
+    // stays in the frame timestamps
+    let start_ns = frames
+        .iter()
+        .map(|(time_ns, _)| *time_ns)
+        .min()
+        .map(|first_ns| first_ns - first_ns % 1_000_000);
+    let objects = frames
+        .into_iter()
+        .map(|(time_ns, mut object)| {
+            set_timestamp(&mut object, time_ns - start_ns.unwrap_or(0));
+            object
+        })
+        .collect();
+    let start_time = start_ns.map(|start_ns| {
+        chrono::DateTime::from_timestamp_nanos(start_ns as i64)
+            .with_timezone(&Local)
+            .naive_local()
+    });
+    Ok(CandumpLog {
+        objects,
+        start_time,
+        skipped_errors,
+    })
+}
+
+/// The candump line of a CAN or CAN FD frame at `timestamp_ns` after `start_time`
+///
+/// Without a start time, seconds since the measurement start are written.
+/// Other objects have no line and give `None`.
+pub fn format_candump_frame(
+    msg: &LogObject,
+    timestamp_ns: u64,
+    start_time: Option<NaiveDateTime>,
+) -> Option<String> {
+    let (channel, frame) = match msg {
+        LogObject::CanMessage(m) => (m.channel, can_frame(m.id, m.flags, m.dlc, &m.data)),
+        LogObject::CanMessage2(m) => (m.channel, can_frame(m.id, m.flags, m.dlc, &m.data)),
+        LogObject::CanFdMessage(m) if m.can_fd_flags & CanFdMessage::FD_FLAG_EDL == 0 => {
+            (m.channel, can_frame(m.id, m.flags, m.dlc, &m.data))
+        }
+        LogObject::CanFdMessage(m) => {
+            let mut flags = 0;
+            if m.can_fd_flags & CanFdMessage::FD_FLAG_BRS != 0 {
+                flags |= CANFD_BRS;
+            }
+            if m.can_fd_flags & CanFdMessage::FD_FLAG_ESI != 0 {
+                flags |= CANFD_ESI;
+            }
+            let len = m.data.len().min(m.valid_data_bytes as usize);
+            (m.channel, can_fd_frame(m.id, flags, &m.data[..len]))
+        }
+        LogObject::CanFdMessage64(m) => {
+            let len = m.data.len().min(m.valid_data_bytes as usize);
+            let frame = if m.is_fd_frame() {
+                let mut flags = 0;
+                if m.flags & CanFdMessage64::FLAG_BRS != 0 {
+                    flags |= CANFD_BRS;
+                }
+                if m.flags & CanFdMessage64::FLAG_ESI != 0 {
+                    flags |= CANFD_ESI;
+                }
+                can_fd_frame(m.id, flags, &m.data[..len])
+            } else {
+                let remote = m.flags & CanFdMessage64::FLAG_REMOTE_FRAME != 0;
+                let flags = if remote { CanMessage2::FLAG_RTR } else { 0 };
+                can_frame(m.id, flags, m.dlc, &m.data[..len])
+            };
+            (m.channel as u16, frame)
+        }
+        _ => return None,
+    };
+
+    let start_ns = start_time
+        .and_then(|start| Local.from_local_datetime(&start).earliest())
+        .and_then(|start| start.timestamp_nanos_opt())
+        .map_or(0, |start_ns| start_ns.max(0) as u64);
+    let time_ns = start_ns + timestamp_ns;
+    Some(format!(
+        "({}.{:06}) can{} {}\n",
+        time_ns / 1_000_000_000,
+        time_ns % 1_000_000_000 / 1_000,
+        channel.saturating_sub(1),
+        frame
+    ))
+}
+
+/// Format a chunk of messages as candump lines, times corrected by `offsets`
+///
+/// Returns the text and the number of lines written.
+pub fn format_candump_rows(
+    messages: &[NumberedMessage],
+    offsets: &TimeOffsets,
+    start_time: Option<NaiveDateTime>,
+) -> (String, usize) {
+    let mut out = String::new();
+    let mut rows = 0;
+    for (_, msg) in messages {
+        if let Some(line) = format_candump_frame(msg, offsets.timestamp(msg), start_time) {
+            out.push_str(&line);
+            rows += 1;
+        }
+    }
+    (out, rows)
+}
+
+fn hex_bytes(data: &[u8]) -> String {
+    data.iter().map(|b| format!("{:02X}", b)).collect()
+}
+
+/// `1A0#0102`, or `1A0#R` for a remote frame
+fn can_frame(id: u32, flags: u8, dlc: u8, data: &[u8]) -> String {
+    if flags & CanMessage2::FLAG_RTR != 0 {
+        return format!("{}#R", can_id_digits(id));
+    }
+    let len = data.len().min(dlc.min(8) as usize);
+    format!("{}#{}", can_id_digits(id), hex_bytes(&data[..len]))
+}
+
+/// `123##1112233`: the flags digit, then the payload
+fn can_fd_frame(id: u32, flags: u8, data: &[u8]) -> String {
+    format!("{}##{:X}{}", can_id_digits(id), flags, hex_bytes(data))
+}
+
+/// `1760686200.012345` as nanoseconds
+fn parse_seconds(text: &str) -> Option<u64> {
+    let (seconds, fraction) = text.split_once('.').unwrap_or((text, ""));
+    if fraction.len() > 9 || !fraction.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let nanos = format!("{:0<9}", fraction).parse::<u64>().ok()?;
+    seconds
+        .parse::<u64>()
+        .ok()?
+        .checked_mul(1_000_000_000)?
+        .checked_add(nanos)
+}
+
+/// Channel of a numbered interface: `can0` and `vcan0` are channel 1
+pub(crate) fn interface_channel(interface: &str) -> Option<u16> {
+    let digits = interface.len()
+        - interface
+            .trim_end_matches(|c: char| c.is_ascii_digit())
+            .len();
+    interface[interface.len() - digits..]
+        .parse::<u16>()
+        .ok()?
+        .checked_add(1)
+}
+
+fn parse_hex_bytes(text: &str) -> Result<Vec<u8>, String> {
+    // cansend accepts dots between bytes
+    let text = text.replace('.', "");
+    if !text.len().is_multiple_of(2) {
+        return Err(format!("odd number of hex digits in '{}'", text));
+    }
+    (0..text.len())
+        .step_by(2)
+        .map(|i| {
+            u8::from_str_radix(&text[i..i + 2], 16)
+                .map_err(|_| format!("invalid data byte '{}'", &text[i..i + 2]))
+        })
+        .collect()
+}
+
+/// The object of one `ID#DATA` field; `None` for an error frame
+fn parse_frame(frame: &str, channel: u16, tx: bool) -> Result<Option<LogObject>, String> {
+    let (id_text, payload) = frame
+        .split_once('#')
+        .ok_or_else(|| format!("expected ID#DATA, got '{}'", frame))?;
+    let raw_id =
+        u32::from_str_radix(id_text, 16).map_err(|_| format!("invalid ID '{}'", id_text))?;
+    if id_text.len() == 8 && raw_id & SOCKETCAN_ERROR_FLAG != 0 {
+        return Ok(None);
+    }
+    let id = match id_text.len() {
+        3 if raw_id <= 0x7FF => raw_id,
+        8 if raw_id <= 0x1FFF_FFFF => raw_id | EXTENDED_ID_FLAG,
+        _ => return Err(format!("invalid ID '{}'", id_text)),
+    };
+
+    if let Some(fd) = payload.strip_prefix('#') {
+        let mut chars = fd.chars();
+        let flags = chars
+            .next()
+            .and_then(|c| c.to_digit(16))
+            .ok_or_else(|| format!("missing CAN FD flags in '{}'", frame))?
+            as u8;
+        let data = parse_hex_bytes(chars.as_str())?;
+        let dlc = fd_dlc(data.len())
+            .ok_or_else(|| format!("{} bytes is not a CAN FD length", data.len()))?;
+        let mut can_fd_flags = CanFdMessage::FD_FLAG_EDL;
+        if flags & CANFD_BRS != 0 {
+            can_fd_flags |= CanFdMessage::FD_FLAG_BRS;
+        }
+        if flags & CANFD_ESI != 0 {
+            can_fd_flags |= CanFdMessage::FD_FLAG_ESI;
+        }
+        let mut msg = CanFdMessage {
+            channel,
+            flags: if tx { CanFdMessage::FLAG_TX } else { 0 },
+            dlc,
+            id,
+            can_fd_flags,
+            valid_data_bytes: data.len() as u8,
+            ..Default::default()
+        };
+        msg.data[..data.len()].copy_from_slice(&data);
+        return Ok(Some(LogObject::CanFdMessage(msg)));
+    }
+
+    let mut msg = CanMessage {
+        channel,
+        id,
+        flags: if tx { CanMessage2::FLAG_TX } else { 0 },
+        ..Default::default()
+    };
+    if let Some(remote) = payload.strip_prefix('R') {
+        msg.flags |= CanMessage2::FLAG_RTR;
+        // `R` may carry the requested length
+        msg.dlc = remote.parse::<u8>().unwrap_or(0).min(8);
+    } else {
+        // A trailing `_N` gives a DLC above 8 for 8 data bytes
+        let (data, dlc) = match payload.split_once('_') {
+            Some((data, dlc)) => (data, u8::from_str_radix(dlc, 16).ok()),
+            None => (payload, None),
+        };
+        let data = parse_hex_bytes(data)?;
+        if data.len() > 8 {
+            return Err(format!("{} data bytes in a CAN frame", data.len()));
+        }
+        msg.dlc = dlc.filter(|_| data.len() == 8).unwrap_or(data.len() as u8);
+        msg.data[..data.len()].copy_from_slice(&data);
+    }
+    Ok(Some(LogObject::CanMessage(msg)))
+}
+
+fn set_timestamp(object: &mut LogObject, timestamp_ns: u64) {
+    match object {
+        LogObject::CanMessage(m) => m.header.object_time_stamp = timestamp_ns,
+        LogObject::CanFdMessage(m) => m.header.object_time_stamp = timestamp_ns,
+        _ => {}
+    }
+}
+
+/// `start` as the wall-clock fields of a BLF header
+pub(crate) fn system_time(start: NaiveDateTime) -> SystemTime {
+    SystemTime::from_timestamp_nanos(start.and_utc().timestamp_nanos_opt().unwrap_or(0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_candump_round_trip() {
+        let text = "(1760686200.012345) can0 1A0#0102030405060708\n\
+                    (1760686200.013000) can1 18FEF100#R\n\
+                    (1760686200.014000) can0 20000080#0000000000000000\n\
+                    \n\
+                    (1760686200.020000) vcan0 123##1112233 T\n";
+        let log = parse_candump(text).unwrap();
+        assert_eq!(log.objects.len(), 3);
+        assert_eq!(log.skipped_errors, 1);
+        let start = log.start_time.unwrap();
+        assert_eq!(
+            start.and_utc().timestamp_subsec_nanos() % 1_000_000,
+            0,
+            "the start is whole milliseconds"
+        );
+
+        let LogObject::CanMessage(can) = &log.objects[0] else {
+            panic!("expected a CAN frame");
+        };
+        assert_eq!((can.channel, can.id, can.dlc), (1, 0x1A0, 8));
+        assert_eq!(can.header.object_time_stamp, 345_000);
+        let LogObject::CanMessage(remote) = &log.objects[1] else {
+            panic!("expected a CAN frame");
+        };
+        assert_eq!(remote.channel, 2);
+        assert_eq!(remote.id, 0x18FE_F100 | EXTENDED_ID_FLAG);
+        assert!(remote.flags & CanMessage2::FLAG_RTR != 0);
+        let LogObject::CanFdMessage(fd) = &log.objects[2] else {
+            panic!("expected a CAN FD frame");
+        };
+        assert_eq!((fd.channel, fd.valid_data_bytes), (1, 3));
+        assert!(fd.flags & CanFdMessage::FLAG_TX != 0);
+        assert_eq!(
+            fd.can_fd_flags,
+            CanFdMessage::FD_FLAG_EDL | CanFdMessage::FD_FLAG_BRS
+        );
+
+        let lines: Vec<String> = log
+            .objects
+            .iter()
+            .filter_map(|msg| format_candump_frame(msg, msg.timestamp(), log.start_time))
+            .collect();
+        assert_eq!(
+            lines,
+            [
+                "(1760686200.012345) can0 1A0#0102030405060708\n",
+                "(1760686200.013000) can1 18FEF100#R\n",
+                "(1760686200.020000) can0 123##1112233\n",
+            ]
+        );
+        assert_eq!(
+            format_candump_frame(&log.objects[0], 1_500_000_000, None).unwrap(),
+            "(1.500000) can0 1A0#0102030405060708\n"
+        );
+        assert!(format_candump_frame(&LogObject::GpsEvent(Default::default()), 0, None).is_none());
+        let numbered: Vec<NumberedMessage> = log.objects.into_iter().map(|msg| (1, msg)).collect();
+        let (text, rows) = format_candump_rows(&numbered, &TimeOffsets::new(), None);
+        assert_eq!(rows, 3);
+        assert!(text.starts_with("(0.000345) can0 1A0#"));
+
+        assert!(is_candump_log(b"  (1.0) can0 1A0#"));
+        assert!(!is_candump_log(b"LOGG"));
+        assert_eq!(
+            parse_candump("(1.0) can0 1A0#123").unwrap_err(),
+            "line 1: odd number of hex digits in '123'"
+        );
+        assert_eq!(
+            parse_candump("(x) can0 1A0#").unwrap_err(),
+            "line 1: invalid time '(x)'"
+        );
+    }
+}
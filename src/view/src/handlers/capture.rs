@@ -0,0 +1,522 @@
+//! Live capture history and recording
+//!
+//! In live mode every received message also goes into a [`CaptureBuffer`]
+//! bounded by the [`CaptureHistory`] preference, so "Save" writes what
+//! happened before the button was pressed, not just what follows it.
+//! Continuous recordings go through a [`RotatingBlfWriter`] configured by
+//! [`rotation_policy`]; objects the BLF writer has no layout for, like
+//! `LinMessage2` frames replayed from a log, are counted and reported when
+//! the recording stops. A [`LiveSource`] is the backend producing the
+//! frames: hardware [`CaptureSource`]s (SocketCAN interfaces, Vector XL
+//! channels) or, without hardware, a simulation or replay. It runs on its own
+//! thread as a [`LiveFeed`] the UI drains once per frame.
+
+use crate::handlers::{Replayer, Simulator, SocketCanSource, XlChannel, XlSource, blf_start_time};
+use crate::models::ChannelType;
+use crate::models::preferences::{CaptureHistory, Preferences};
+use blf::{BlfStream, LogObject, ObjectType, RotatingBlfWriter, RotationPolicy, blf_channel};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, VecDeque};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+/// Ticks a [`LiveFeed`] runs ahead of the UI before it waits for it
+const LIVE_FEED_CAPACITY: usize = 64;
+
+/// Bounded history of live messages, oldest first
+#[derive(Debug, Clone)]
+pub struct CaptureBuffer {
+    limit: CaptureHistory,
+    messages: VecDeque<LogObject>,
+}
+
+impl CaptureBuffer {
+    pub fn new(limit: CaptureHistory) -> Self {
+        Self {
+            limit,
+            messages: VecDeque::new(),
+        }
+    }
+
+    pub fn limit(&self) -> CaptureHistory {
+        self.limit
+    }
+
+    /// Change the bound; a smaller one drops the oldest messages right away
+    pub fn set_limit(&mut self, limit: CaptureHistory) {
+        self.limit = limit;
+        self.evict();
+    }
+
+    pub fn push(&mut self, msg: LogObject) {
+        self.messages.push_back(msg);
+        self.evict();
+    }
+
+    pub fn extend(&mut self, messages: impl IntoIterator<Item = LogObject>) {
+        self.messages.extend(messages);
+        self.evict();
+    }
+
+    fn evict(&mut self) {
+        match self.limit {
+            CaptureHistory::Messages(count) => {
+                let excess = self.messages.len().saturating_sub(count as usize);
+                self.messages.drain(..excess);
+            }
+            CaptureHistory::Seconds(seconds) => {
+                let Some(newest) = self.messages.back().map(LogObject::timestamp) else {
+                    return;
+                };
+                let oldest_kept = newest.saturating_sub(seconds as u64 * 1_000_000_000);
+                let excess = self
+                    .messages
+                    .iter()
+                    .position(|msg| msg.timestamp() >= oldest_kept)
+                    .unwrap_or(self.messages.len());
+                self.messages.drain(..excess);
+            }
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.messages.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.messages.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.messages.clear();
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &LogObject> {
+        self.messages.iter()
+    }
+
+    /// Time covered by the buffered messages, in nanoseconds
+    pub fn span_ns(&self) -> u64 {
+        match (self.messages.front(), self.messages.back()) {
+            (Some(first), Some(last)) => last.timestamp().saturating_sub(first.timestamp()),
+            _ => 0,
+        }
+    }
+
+    /// Copy of the buffered messages, e.g. to save them off the UI thread
+    pub fn snapshot(&self) -> Vec<LogObject> {
+        self.messages.iter().cloned().collect()
+    }
+}
+
+/// A hardware interface live frames are captured from
+///
+/// Sources are polled from the feed thread and must not block.
+pub trait CaptureSource: Send + std::fmt::Debug {
+    /// Interface name for status messages, e.g. `can0` or `VN1630 CH1`
+    fn name(&self) -> String;
+
+    /// Frames received since the last call
+    fn read_frames(&mut self) -> Vec<LogObject>;
+
+    /// Whether reading failed, e.g. because the interface went away
+    fn is_closed(&self) -> bool;
+}
+
+/// Hardware a channel is captured from, chosen per channel mapping
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(tag = "backend", rename_all = "snake_case")]
+pub enum CaptureDevice {
+    SocketCan { interface: String },
+    VectorXl(XlChannel),
+}
+
+impl CaptureDevice {
+    pub fn label(&self) -> String {
+        match self {
+            CaptureDevice::SocketCan { interface } => interface.clone(),
+            CaptureDevice::VectorXl(channel) => channel.label(),
+        }
+    }
+
+    /// Open the device for a `channel_type` channel; its frames go on `channel`
+    pub fn open(
+        &self,
+        channel: u16,
+        channel_type: ChannelType,
+    ) -> Result<Box<dyn CaptureSource>, String> {
+        match self {
+            CaptureDevice::SocketCan { interface } => {
+                if channel_type != ChannelType::CAN {
+                    return Err(format!("{} can only capture CAN", interface));
+                }
+                Ok(Box::new(SocketCanSource::open(interface, channel)?))
+            }
+            CaptureDevice::VectorXl(xl_channel) => Ok(Box::new(XlSource::open(
+                *xl_channel,
+                channel,
+                channel_type == ChannelType::LIN,
+            )?)),
+        }
+    }
+}
+
+/// Capture devices connected to this computer: SocketCAN interfaces, then
+/// Vector XL channels
+pub fn capture_devices() -> Vec<CaptureDevice> {
+    let socketcan = crate::handlers::socketcan_interfaces()
+        .into_iter()
+        .map(|interface| CaptureDevice::SocketCan { interface });
+    let xl = crate::handlers::xl_channels()
+        .into_iter()
+        .map(CaptureDevice::VectorXl);
+    socketcan.chain(xl).collect()
+}
+
+/// Backend feeding live mode
+#[derive(Debug)]
+pub enum LiveSource {
+    Simulation(Simulator),
+    Replay(Replayer),
+    /// One or more hardware channels, merged in time order
+    Capture(Vec<Box<dyn CaptureSource>>),
+}
+
+/// Which kind of [`LiveSource`] a feed runs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LiveSourceKind {
+    Simulation,
+    Replay,
+    Capture,
+}
+
+impl LiveSource {
+    /// Frames due up to `now_ns` (time since the source was started)
+    pub fn advance(&mut self, now_ns: u64) -> Vec<LogObject> {
+        match self {
+            LiveSource::Simulation(simulator) => simulator.advance(now_ns),
+            LiveSource::Replay(replayer) => replayer.advance(now_ns),
+            // Frames keep the receive time stamped by the hardware or kernel
+            LiveSource::Capture(sources) => {
+                let mut frames: Vec<LogObject> = sources
+                    .iter_mut()
+                    .flat_map(|source| source.read_frames())
+                    .collect();
+                if sources.len() > 1 {
+                    frames.sort_by_key(LogObject::timestamp);
+                }
+                frames
+            }
+        }
+    }
+
+    /// A replay ends with the log, a capture once all its interfaces failed;
+    /// the simulation runs until stopped
+    pub fn is_finished(&self) -> bool {
+        match self {
+            LiveSource::Simulation(_) => false,
+            LiveSource::Replay(replayer) => replayer.is_finished(),
+            LiveSource::Capture(sources) => sources.iter().all(|source| source.is_closed()),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            LiveSource::Simulation(_) => "Simulation",
+            LiveSource::Replay(_) => "Replay",
+            LiveSource::Capture(_) => "Capture",
+        }
+    }
+
+    pub fn kind(&self) -> LiveSourceKind {
+        match self {
+            LiveSource::Simulation(_) => LiveSourceKind::Simulation,
+            LiveSource::Replay(_) => LiveSourceKind::Replay,
+            LiveSource::Capture(_) => LiveSourceKind::Capture,
+        }
+    }
+
+    /// Run the source on its own thread, advancing it every `tick`
+    pub fn spawn(mut self, tick: Duration) -> LiveFeed {
+        let name = self.name();
+        let kind = self.kind();
+        let (sender, frames) = blf_channel(LIVE_FEED_CAPACITY);
+        std::thread::spawn(move || {
+            let started = Instant::now();
+            // Ends with the source or once the feed is dropped
+            loop {
+                std::thread::sleep(tick);
+                let batch = self.advance(started.elapsed().as_nanos() as u64);
+                if !sender.send(batch) || self.is_finished() {
+                    break;
+                }
+            }
+        });
+        LiveFeed { name, kind, frames }
+    }
+}
+
+/// A [`LiveSource`] running in the background; dropping it stops the source
+#[derive(Debug)]
+pub struct LiveFeed {
+    name: &'static str,
+    kind: LiveSourceKind,
+    frames: BlfStream,
+}
+
+impl LiveFeed {
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    pub fn kind(&self) -> LiveSourceKind {
+        self.kind
+    }
+
+    /// Frames produced since the last poll, without waiting for more
+    pub fn poll(&mut self) -> Vec<LogObject> {
+        let mut frames = Vec::new();
+        while let Some(batch) = self.frames.try_recv_batch() {
+            frames.extend(batch.unwrap_or_default());
+        }
+        frames
+    }
+
+    /// The source is done and every frame has been polled
+    pub fn is_finished(&self) -> bool {
+        self.frames.is_finished()
+    }
+}
+
+/// Live mode: the running source, the history it filled and the recording
+pub struct CaptureState {
+    pub buffer: CaptureBuffer,
+    /// Split into files by the rotation preferences
+    pub recorder: Option<RotatingBlfWriter>,
+    pub feed: Option<LiveFeed>,
+    /// SocketCAN interface of the last capture from the dialog
+    pub interface: String,
+    /// Connected devices, listed when a channel's device picker opens
+    pub devices: Vec<CaptureDevice>,
+}
+
+impl CaptureState {
+    pub fn new(limit: CaptureHistory) -> Self {
+        Self {
+            buffer: CaptureBuffer::new(limit),
+            recorder: None,
+            feed: None,
+            interface: "can0".to_string(),
+            devices: Vec::new(),
+        }
+    }
+
+    /// Kind of the running source, if any
+    pub fn source_kind(&self) -> Option<LiveSourceKind> {
+        self.feed.as_ref().map(LiveFeed::kind)
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.feed.is_some()
+    }
+}
+
+/// Write buffered history to a BLF file; returns the number of objects written
+pub fn save_capture(
+    path: &Path,
+    messages: &[LogObject],
+    start_time: Option<chrono::NaiveDateTime>,
+) -> Result<u32, String> {
+    blf::write_blf_to_file(path, blf_start_time(start_time), messages)
+        .map(|stats| stats.object_count)
+        .map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+/// File rotation limits from the preferences; zero disables a limit
+pub fn rotation_policy(preferences: &Preferences) -> RotationPolicy {
+    RotationPolicy {
+        max_bytes: (preferences.rotate_size_mb > 0)
+            .then(|| preferences.rotate_size_mb as u64 * 1024 * 1024),
+        max_duration_ns: (preferences.rotate_minutes > 0)
+            .then(|| preferences.rotate_minutes as u64 * 60 * 1_000_000_000),
+    }
+}
+
+/// Start a recording at `base_path`; files are named `<stem>_001.blf`, `<stem>_002.blf`, …
+pub fn start_recording(
+    base_path: &Path,
+    start_time: Option<chrono::NaiveDateTime>,
+    preferences: &Preferences,
+) -> RotatingBlfWriter {
+    RotatingBlfWriter::new(
+        base_path,
+        blf_start_time(start_time),
+        rotation_policy(preferences),
+    )
+}
+
+/// "120 LinMessage, 3 CanDriverError" for the objects a recording left out
+pub fn skipped_objects(skipped: &BTreeMap<u32, u64>) -> Option<String> {
+    if skipped.is_empty() {
+        return None;
+    }
+    let types: Vec<String> = skipped
+        .iter()
+        .map(|(&object_type, count)| format!("{} {:?}", count, ObjectType::from(object_type)))
+        .collect();
+    Some(types.join(", "))
+}
+
+/// Close `recorder`; returns "42 messages into 2 files" and what was left out
+pub fn finish_recording(recorder: RotatingBlfWriter) -> Result<String, String> {
+    let skipped = skipped_objects(recorder.skipped());
+    let files = recorder.finish().map_err(|e| e.to_string())?;
+    let count: u32 = files.iter().map(|(_, stats)| stats.object_count).sum();
+    let mut summary = format!("{} messages into {} files", count, files.len());
+    if let Some(skipped) = skipped {
+        summary += &format!(", left out {}", skipped);
+    }
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::handlers::test_support::can;
+
+    #[test]
+    fn test_message_limit_keeps_newest() {
+        let mut buffer = CaptureBuffer::new(CaptureHistory::Messages(3));
+        buffer.extend((1..=5).map(|t| can(0).at(t).build()));
+        let kept: Vec<_> = buffer.iter().map(LogObject::timestamp).collect();
+        assert_eq!(kept, vec![3, 4, 5]);
+
+        buffer.set_limit(CaptureHistory::Messages(1));
+        assert_eq!(buffer.len(), 1);
+    }
+
+    #[test]
+    fn test_finish_recording_reports_skipped_types() {
+        let dir = std::env::temp_dir().join(format!("canview_rec_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let mut recorder = start_recording(&dir.join("run.blf"), None, &Preferences::default());
+        let lin = LogObject::LinMessage(blf::LinMessage {
+            channel: 2,
+            id: 0x3C,
+            dlc: 8,
+            ..Default::default()
+        });
+        let lin2 = LogObject::LinMessage2(blf::LinMessage2 {
+            header: Default::default(),
+            channel: 2,
+            id: 0x3D,
+            dlc: 8,
+            data: [0; 8],
+            crc: 0,
+            dir: 0,
+            simulated: 0,
+            is_etf: 0,
+            resp_baudrate: None,
+            exact_header_baudrate: None,
+        });
+        for msg in [can(0).build(), lin, can(0).at(1_000).build(), lin2] {
+            recorder.write_object(&msg).unwrap();
+        }
+        assert_eq!(
+            finish_recording(recorder).unwrap(),
+            "3 messages into 1 files, left out 1 LinMessage2"
+        );
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_live_feed_delivers_replay_in_background() {
+        let replayer = Replayer::new(&[
+            can(0).build(),
+            can(0).at(1_000_000).build(),
+            can(0).at(2_000_000).build(),
+        ]);
+        let mut state = CaptureState::new(CaptureHistory::default());
+        assert_eq!(state.source_kind(), None);
+        state.feed = Some(LiveSource::Replay(replayer).spawn(Duration::from_millis(1)));
+        assert!(state.is_running());
+        assert_eq!(state.source_kind(), Some(LiveSourceKind::Replay));
+        let mut feed = state.feed.take().unwrap();
+        let mut received = Vec::new();
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while !feed.is_finished() && Instant::now() < deadline {
+            received.extend(feed.poll());
+            std::thread::sleep(Duration::from_millis(1));
+        }
+        assert!(feed.is_finished());
+        let times: Vec<_> = received.iter().map(LogObject::timestamp).collect();
+        assert_eq!(times, vec![0, 1_000_000, 2_000_000]);
+    }
+
+    #[derive(Debug)]
+    struct FakeSource(Vec<u64>);
+
+    impl CaptureSource for FakeSource {
+        fn name(&self) -> String {
+            "fake".into()
+        }
+
+        fn read_frames(&mut self) -> Vec<LogObject> {
+            self.0.drain(..).map(|t| can(0).at(t).build()).collect()
+        }
+
+        fn is_closed(&self) -> bool {
+            self.0.is_empty()
+        }
+    }
+
+    #[test]
+    fn test_capture_merges_sources_in_time_order() {
+        let mut source = LiveSource::Capture(vec![
+            Box::new(FakeSource(vec![1, 4])),
+            Box::new(FakeSource(vec![2, 3])),
+        ]);
+        assert!(!source.is_finished());
+        let times: Vec<_> = source.advance(0).iter().map(LogObject::timestamp).collect();
+        assert_eq!(times, vec![1, 2, 3, 4]);
+        assert!(source.is_finished());
+
+        let device = CaptureDevice::VectorXl(XlChannel {
+            hw_type: 57,
+            hw_index: 0,
+            hw_channel: 1,
+        });
+        let json = serde_json::to_string(&device).unwrap();
+        assert_eq!(
+            serde_json::from_str::<CaptureDevice>(&json).unwrap(),
+            device
+        );
+        let socketcan = CaptureDevice::SocketCan {
+            interface: "can0".into(),
+        };
+        assert!(socketcan.open(1, ChannelType::LIN).is_err());
+    }
+
+    #[test]
+    fn test_rotation_policy_from_preferences() {
+        let mut preferences = Preferences::default();
+        assert_eq!(rotation_policy(&preferences), RotationPolicy::default());
+        preferences.rotate_size_mb = 2;
+        preferences.rotate_minutes = 1;
+        let policy = rotation_policy(&preferences);
+        assert_eq!(policy.max_bytes, Some(2 * 1024 * 1024));
+        assert_eq!(policy.max_duration_ns, Some(60_000_000_000));
+    }
+
+    #[test]
+    fn test_time_limit_keeps_window_before_newest() {
+        let second = 1_000_000_000;
+        let mut buffer = CaptureBuffer::new(CaptureHistory::Seconds(10));
+        for t in [0, 5, 9, 12, 20] {
+            buffer.push(can(0).at(t * second).build());
+        }
+        let kept: Vec<_> = buffer.iter().map(|msg| msg.timestamp() / second).collect();
+        assert_eq!(kept, vec![12, 20]);
+        assert_eq!(buffer.span_ns(), 8 * second);
+    }
+}
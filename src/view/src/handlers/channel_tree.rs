@@ -0,0 +1,320 @@
+//! Bus → channel → ID overview used by the tree sidebar
+//!
+//! [`ChannelTree`] counts messages per node and, like [`BusStats`], is
+//! updated message by message so it also follows live traffic.
+//! [`TreeFilter`] remembers which nodes are unchecked; a message passes when
+//! neither its bus, its channel nor its ID is unchecked.
+//!
+//! [`BusStats`]: crate::handlers::BusStats
+
+use crate::handlers::MessageKind;
+use blf::LogObject;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Bus a message travelled on; error frames belong to the bus they occurred on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum Bus {
+    Can,
+    Lin,
+    FlexRay,
+    Ethernet,
+    Other,
+}
+
+impl Bus {
+    pub fn of(msg: &LogObject) -> Self {
+        match MessageKind::of(msg) {
+            MessageKind::Can | MessageKind::CanFd => Bus::Can,
+            MessageKind::Lin => Bus::Lin,
+            MessageKind::FlexRay => Bus::FlexRay,
+            MessageKind::Ethernet => Bus::Ethernet,
+            MessageKind::Other => Bus::Other,
+            MessageKind::Error => match msg {
+                LogObject::CanErrorFrame(_)
+                | LogObject::CanOverloadFrame(_)
+                | LogObject::CanDriverError(_) => Bus::Can,
+                LogObject::FlexRayVFrError(_) => Bus::FlexRay,
+                _ => Bus::Lin,
+            },
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Bus::Can => "CAN",
+            Bus::Lin => "LIN",
+            Bus::FlexRay => "FlexRay",
+            Bus::Ethernet => "Ethernet",
+            Bus::Other => "Other",
+        }
+    }
+}
+
+/// One node of the tree
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum TreeNode {
+    Bus(Bus),
+    Channel(Bus, u16),
+    Id(Bus, u16, u32),
+}
+
+impl TreeNode {
+    /// The node itself followed by its ancestors, innermost first
+    fn path(self) -> impl Iterator<Item = TreeNode> {
+        let ancestors = match self {
+            TreeNode::Bus(_) => [None, None],
+            TreeNode::Channel(bus, _) => [Some(TreeNode::Bus(bus)), None],
+            TreeNode::Id(bus, channel, _) => [
+                Some(TreeNode::Channel(bus, channel)),
+                Some(TreeNode::Bus(bus)),
+            ],
+        };
+        std::iter::once(self).chain(ancestors.into_iter().flatten())
+    }
+
+    /// Whether `other` lies below this node
+    fn contains(self, other: TreeNode) -> bool {
+        other != self && other.path().any(|node| node == self)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+struct ChannelCounts {
+    count: usize,
+    ids: BTreeMap<u32, usize>,
+}
+
+/// Message counts per bus, channel and ID
+#[derive(Debug, Clone, Default)]
+pub struct ChannelTree {
+    buses: BTreeMap<Bus, BTreeMap<u16, ChannelCounts>>,
+}
+
+impl ChannelTree {
+    pub fn build(messages: &[LogObject]) -> Self {
+        let mut tree = Self::default();
+        tree.extend(messages);
+        tree
+    }
+
+    /// Count one message; objects without a channel are not part of the tree
+    pub fn record(&mut self, msg: &LogObject) {
+        let Some(channel) = msg.channel() else {
+            return;
+        };
+        let counts = self
+            .buses
+            .entry(Bus::of(msg))
+            .or_default()
+            .entry(channel)
+            .or_default();
+        counts.count += 1;
+        if let Some(id) = msg.id() {
+            *counts.ids.entry(id).or_default() += 1;
+        }
+    }
+
+    pub fn extend<'a>(&mut self, messages: impl IntoIterator<Item = &'a LogObject>) {
+        for msg in messages {
+            self.record(msg);
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.buses.clear();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.buses.is_empty()
+    }
+
+    /// Direct children of `node`, or the buses for `None`, in ascending order
+    pub fn children(&self, node: Option<TreeNode>) -> Vec<TreeNode> {
+        match node {
+            None => self.buses.keys().map(|&bus| TreeNode::Bus(bus)).collect(),
+            Some(TreeNode::Bus(bus)) => self
+                .buses
+                .get(&bus)
+                .into_iter()
+                .flat_map(|channels| channels.keys())
+                .map(|&channel| TreeNode::Channel(bus, channel))
+                .collect(),
+            Some(TreeNode::Channel(bus, channel)) => self
+                .buses
+                .get(&bus)
+                .and_then(|channels| channels.get(&channel))
+                .into_iter()
+                .flat_map(|counts| counts.ids.keys())
+                .map(|&id| TreeNode::Id(bus, channel, id))
+                .collect(),
+            Some(TreeNode::Id(..)) => Vec::new(),
+        }
+    }
+
+    /// Messages below `node`
+    pub fn count(&self, node: TreeNode) -> usize {
+        match node {
+            TreeNode::Bus(bus) => self.buses.get(&bus).map_or(0, |channels| {
+                channels.values().map(|counts| counts.count).sum()
+            }),
+            TreeNode::Channel(bus, channel) => self
+                .buses
+                .get(&bus)
+                .and_then(|channels| channels.get(&channel))
+                .map_or(0, |counts| counts.count),
+            TreeNode::Id(bus, channel, id) => self
+                .buses
+                .get(&bus)
+                .and_then(|channels| channels.get(&channel))
+                .and_then(|counts| counts.ids.get(&id))
+                .copied()
+                .unwrap_or(0),
+        }
+    }
+}
+
+/// Checkbox state of a tree node
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckState {
+    Checked,
+    /// Some nodes below are unchecked
+    Partial,
+    Unchecked,
+}
+
+/// Unchecked tree nodes
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TreeFilter {
+    hidden: BTreeSet<TreeNode>,
+}
+
+impl TreeFilter {
+    pub fn is_empty(&self) -> bool {
+        self.hidden.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.hidden.clear();
+    }
+
+    /// Whether `msg` passes; objects without a channel always do
+    pub fn matches(&self, msg: &LogObject) -> bool {
+        if self.hidden.is_empty() {
+            return true;
+        }
+        let Some(channel) = msg.channel() else {
+            return true;
+        };
+        let bus = Bus::of(msg);
+        let node = match msg.id() {
+            Some(id) => TreeNode::Id(bus, channel, id),
+            None => TreeNode::Channel(bus, channel),
+        };
+        !node.path().any(|node| self.hidden.contains(&node))
+    }
+
+    pub fn state(&self, node: TreeNode) -> CheckState {
+        if node.path().any(|node| self.hidden.contains(&node)) {
+            CheckState::Unchecked
+        } else if self.hidden.iter().any(|&hidden| node.contains(hidden)) {
+            CheckState::Partial
+        } else {
+            CheckState::Checked
+        }
+    }
+
+    /// Uncheck a checked node; check an unchecked or partly checked one
+    /// together with everything below it
+    pub fn toggle(&mut self, node: TreeNode, tree: &ChannelTree) {
+        let checked = self.state(node) == CheckState::Checked;
+        self.hidden.retain(|&hidden| !node.contains(hidden));
+        if checked {
+            self.hidden.insert(node);
+            return;
+        }
+
+        // An unchecked ancestor is split into its children, outermost first, so
+        // the child on the way to `node` is split in turn
+        let ancestors: Vec<TreeNode> = node.path().skip(1).collect();
+        for &ancestor in ancestors.iter().rev() {
+            if self.hidden.remove(&ancestor) {
+                for child in tree.children(Some(ancestor)) {
+                    if child != node {
+                        self.hidden.insert(child);
+                    }
+                }
+            }
+        }
+        self.hidden.remove(&node);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::handlers::test_support::can;
+    use blf::CanErrorFrame;
+
+    fn log() -> Vec<LogObject> {
+        vec![
+            can(0x100).build(),
+            can(0x100).build(),
+            can(0x200).build(),
+            can(0x100).channel(2).build(),
+            LogObject::CanErrorFrame(CanErrorFrame {
+                channel: 2,
+                ..Default::default()
+            }),
+        ]
+    }
+
+    #[test]
+    fn test_tree_counts() {
+        let tree = ChannelTree::build(&log());
+        assert_eq!(tree.children(None), vec![TreeNode::Bus(Bus::Can)]);
+        assert_eq!(tree.count(TreeNode::Bus(Bus::Can)), 5);
+        assert_eq!(tree.count(TreeNode::Channel(Bus::Can, 2)), 2);
+        assert_eq!(tree.count(TreeNode::Id(Bus::Can, 1, 0x100)), 2);
+        assert_eq!(
+            tree.children(Some(TreeNode::Channel(Bus::Can, 1))),
+            vec![
+                TreeNode::Id(Bus::Can, 1, 0x100),
+                TreeNode::Id(Bus::Can, 1, 0x200)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_toggle_drives_filter() {
+        let log = log();
+        let tree = ChannelTree::build(&log);
+        let mut filter = TreeFilter::default();
+        let visible = |filter: &TreeFilter| log.iter().filter(|msg| filter.matches(msg)).count();
+
+        filter.toggle(TreeNode::Bus(Bus::Can), &tree);
+        assert_eq!(visible(&filter), 0);
+        assert_eq!(
+            filter.state(TreeNode::Channel(Bus::Can, 1)),
+            CheckState::Unchecked
+        );
+
+        // Checking one ID below an unchecked bus brings back only that ID
+        filter.toggle(TreeNode::Id(Bus::Can, 1, 0x200), &tree);
+        assert_eq!(visible(&filter), 1);
+        assert_eq!(filter.state(TreeNode::Bus(Bus::Can)), CheckState::Partial);
+        assert_eq!(
+            filter.state(TreeNode::Channel(Bus::Can, 1)),
+            CheckState::Partial
+        );
+        assert_eq!(
+            filter.state(TreeNode::Channel(Bus::Can, 2)),
+            CheckState::Unchecked
+        );
+
+        // Checking a partly checked bus shows everything again
+        filter.toggle(TreeNode::Bus(Bus::Can), &tree);
+        assert!(filter.is_empty());
+        assert_eq!(visible(&filter), log.len());
+    }
+}
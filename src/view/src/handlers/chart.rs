@@ -0,0 +1,621 @@
+//! Signal time series for the Charts view
+//!
+//! [`chart_signals`] lists what the loaded DBC and LDF files can decode, and
+//! [`ChartSeries`] holds one signal decoded over the whole log. The charts
+//! share a [`ChartViewport`], the time span on screen, which zooms around the
+//! mouse and pans by fractions of its width. [`ChartSeries::polyline`] turns
+//! the samples in view into a few points per pixel column, so an hour of a
+//! 10 ms signal draws as fast as a second of it.
+//!
+//! Signals of very different ranges, RPM next to a temperature, can share
+//! one plot: the [`ChartLayout::Overlay`] layout scales each against the
+//! left or right [`ChartAxis`], and [`ChartLayout::Normalized`] stretches
+//! every signal over the full height.
+
+use crate::handlers::hex_frame_id;
+use parser::dbc::DbcDatabase;
+use parser::ldf::LdfDatabase;
+use std::collections::HashMap;
+
+/// Line colors, one per chart in turn
+pub const CHART_COLORS: [u32; 6] = [0x60a5fa, 0x22c55e, 0xf59e0b, 0xf472b6, 0xa78bfa, 0x2dd4bf];
+
+/// Narrowest time span the charts zoom to
+pub const MIN_CHART_SPAN_NS: u64 = 10_000;
+
+/// A signal that can be charted: where it is sent and how its values read
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ChartSignal {
+    pub channel: u16,
+    pub id: u32,
+    /// Sent in a LIN frame rather than a CAN one
+    pub lin: bool,
+    pub name: String,
+    pub unit: String,
+    /// Fractional digits the signal's scaling resolves
+    pub decimals: usize,
+}
+
+impl ChartSignal {
+    /// "Speed · CH1 0x100"
+    pub fn label(&self) -> String {
+        let kind = if self.lin { "LIN" } else { "CAN" };
+        format!(
+            "{} · CH{} {}",
+            self.name,
+            self.channel,
+            hex_frame_id(kind, self.id)
+        )
+    }
+}
+
+/// Every signal the databases decode, by channel, frame ID and name
+pub fn chart_signals(
+    dbc_channels: &HashMap<u16, DbcDatabase>,
+    ldf_channels: &HashMap<u16, LdfDatabase>,
+) -> Vec<ChartSignal> {
+    let mut signals = Vec::new();
+    for (&channel, db) in dbc_channels {
+        for message in db.messages.values() {
+            signals.extend(message.signals.values().map(|signal| ChartSignal {
+                channel,
+                id: message.id,
+                lin: false,
+                name: signal.name.clone(),
+                unit: signal.unit.clone(),
+                decimals: signal.decimals(),
+            }));
+        }
+    }
+    for (&channel, db) in ldf_channels {
+        for frame in db.frames.values() {
+            signals.extend(frame.signals.iter().map(|mapping| ChartSignal {
+                channel,
+                id: frame.id,
+                lin: true,
+                name: mapping.signal_name.clone(),
+                unit: String::new(),
+                decimals: 0,
+            }));
+        }
+    }
+    signals.sort_by(|a, b| (a.channel, a.id, &a.name).cmp(&(b.channel, b.id, &b.name)));
+    signals
+}
+
+/// How the charted signals share the plot area
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ChartLayout {
+    /// One plot per signal, each scaled to its own values
+    #[default]
+    Stacked,
+    /// One plot, signals scaled to the left or the right axis
+    Overlay,
+    /// One plot, every signal scaled to its own values
+    Normalized,
+}
+
+impl ChartLayout {
+    pub const ALL: [ChartLayout; 3] = [Self::Stacked, Self::Overlay, Self::Normalized];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Stacked => "Stacked",
+            Self::Overlay => "Two axes",
+            Self::Normalized => "Normalized",
+        }
+    }
+}
+
+/// Y axis a signal is scaled to in the [`ChartLayout::Overlay`] layout
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ChartAxis {
+    #[default]
+    Left,
+    Right,
+}
+
+impl ChartAxis {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Left => "L",
+            Self::Right => "R",
+        }
+    }
+
+    pub fn other(&self) -> Self {
+        match self {
+            Self::Left => Self::Right,
+            Self::Right => Self::Left,
+        }
+    }
+}
+
+/// The time span shown by the charts
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChartViewport {
+    pub start_ns: u64,
+    pub end_ns: u64,
+}
+
+impl ChartViewport {
+    pub fn new(start_ns: u64, end_ns: u64) -> Self {
+        Self {
+            start_ns,
+            end_ns: end_ns.max(start_ns + 1),
+        }
+    }
+
+    pub fn span_ns(&self) -> u64 {
+        self.end_ns - self.start_ns
+    }
+
+    /// Position of `time_ns` across the chart, 0.0 at the left edge
+    pub fn fraction_of(&self, time_ns: u64) -> f64 {
+        (time_ns as f64 - self.start_ns as f64) / self.span_ns() as f64
+    }
+
+    /// Time at `fraction` of the width, clamped to the span
+    pub fn time_at(&self, fraction: f64) -> u64 {
+        self.start_ns + (self.span_ns() as f64 * fraction.clamp(0.0, 1.0)).round() as u64
+    }
+
+    /// Scale the span by `factor` (below 1 zooms in), keeping the time at
+    /// `anchor` in place, within `full`
+    pub fn zoom(&self, factor: f64, anchor: f64, full: ChartViewport) -> Self {
+        let max_span = full.span_ns();
+        let span = ((self.span_ns() as f64 * factor).round() as u64)
+            .clamp(MIN_CHART_SPAN_NS.min(max_span), max_span);
+        let pivot = self.time_at(anchor);
+        let start = pivot.saturating_sub((span as f64 * anchor.clamp(0.0, 1.0)).round() as u64);
+        Self::new(start, start + span).within(full)
+    }
+
+    /// Move by `fraction` of the width (positive moves later), within `full`
+    pub fn pan(&self, fraction: f64, full: ChartViewport) -> Self {
+        let shift = (self.span_ns() as f64 * fraction).round() as i64;
+        let start = self.start_ns.saturating_add_signed(shift);
+        Self::new(start, start + self.span_ns()).within(full)
+    }
+
+    /// Shifted, and shrunk if need be, to lie inside `full`
+    fn within(self, full: ChartViewport) -> Self {
+        let span = self.span_ns().min(full.span_ns());
+        let start = self.start_ns.clamp(full.start_ns, full.end_ns - span);
+        Self::new(start, start + span)
+    }
+}
+
+/// One signal decoded over the whole log
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChartSeries {
+    pub signal: ChartSignal,
+    /// `(timestamp_ns, value)` in time order
+    pub points: Vec<(u64, f64)>,
+    /// Axis of the signal in the overlay layout
+    pub axis: ChartAxis,
+}
+
+impl ChartSeries {
+    /// First and last sample time
+    pub fn time_range(&self) -> Option<(u64, u64)> {
+        Some((self.points.first()?.0, self.points.last()?.0))
+    }
+
+    /// Value held at `time_ns`: that of the last sample at or before it
+    pub fn value_at(&self, time_ns: u64) -> Option<f64> {
+        let after = self.points.partition_point(|&(t, _)| t <= time_ns);
+        after.checked_sub(1).map(|index| self.points[index].1)
+    }
+
+    /// Samples in `viewport`, with the one before and after so lines run to the edges
+    fn visible(&self, viewport: ChartViewport) -> &[(u64, f64)] {
+        let first = self
+            .points
+            .partition_point(|&(t, _)| t < viewport.start_ns)
+            .saturating_sub(1);
+        let last = self.points.partition_point(|&(t, _)| t <= viewport.end_ns);
+        &self.points[first..(last + 1).min(self.points.len())]
+    }
+
+    /// Lowest and highest finite value in `viewport`, never an empty range
+    pub fn value_range(&self, viewport: ChartViewport) -> Option<(f64, f64)> {
+        let (min, max) = self
+            .visible(viewport)
+            .iter()
+            .map(|&(_, value)| value)
+            .filter(|value| value.is_finite())
+            .fold(None, |range: Option<(f64, f64)>, value| match range {
+                Some((min, max)) => Some((min.min(value), max.max(value))),
+                None => Some((value, value)),
+            })?;
+        if min == max {
+            Some((min - 1.0, max + 1.0))
+        } else {
+            let margin = (max - min) * 0.05;
+            Some((min - margin, max + margin))
+        }
+    }
+
+    /// Line through the samples in `viewport` as `(x, y)` fractions of the
+    /// plot, y measured from the top
+    ///
+    /// Samples sharing one of `columns` pixel columns are reduced to their
+    /// lowest and highest value, so spikes stay visible at any zoom.
+    pub fn polyline(
+        &self,
+        viewport: ChartViewport,
+        range: (f64, f64),
+        columns: usize,
+    ) -> Vec<(f32, f32)> {
+        let (low, high) = range;
+        let y_of = |value: f64| (1.0 - (value - low) / (high - low)) as f32;
+        let column_of =
+            |time_ns: u64| (viewport.fraction_of(time_ns) * columns as f64).floor() as i64;
+
+        let mut line = Vec::new();
+        let mut samples = self
+            .visible(viewport)
+            .iter()
+            .filter(|(_, value)| value.is_finite())
+            .peekable();
+        while let Some(&(time_ns, value)) = samples.next() {
+            let column = column_of(time_ns);
+            let (mut min, mut max) = ((time_ns, value), (time_ns, value));
+            let mut last = (time_ns, value);
+            while let Some(&&(t, v)) = samples.peek() {
+                if column_of(t) != column {
+                    break;
+                }
+                if v < min.1 {
+                    min = (t, v);
+                }
+                if v > max.1 {
+                    max = (t, v);
+                }
+                last = (t, v);
+                samples.next();
+            }
+            // Enter and leave the column at its first and last sample
+            let mut extremes = [min, max];
+            extremes.sort_by_key(|&(t, _)| t);
+            for (t, v) in [(time_ns, value), extremes[0], extremes[1], last] {
+                let point = (viewport.fraction_of(t) as f32, y_of(v));
+                if line.last() != Some(&point) {
+                    line.push(point);
+                }
+            }
+        }
+        line
+    }
+}
+
+/// Time span covering every series, `None` if none has samples
+pub fn full_viewport(series: &[ChartSeries]) -> Option<ChartViewport> {
+    let (start, end) = series
+        .iter()
+        .filter_map(ChartSeries::time_range)
+        .reduce(|(start, end), (first, last)| (start.min(first), end.max(last)))?;
+    Some(ChartViewport::new(start, end))
+}
+
+/// What a press on a plot drags
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ChartDrag {
+    /// The view, from the pointer x and span where the press started
+    Pan { start_x: f32, from: ChartViewport },
+    /// Cursor A (0) or B (1)
+    Cursor(usize),
+}
+
+/// Signals charted and how they are viewed
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ChartState {
+    /// Decoded signals, one chart each, in the order they were picked
+    pub series: Vec<ChartSeries>,
+    /// Span on screen; `None` shows the whole log
+    pub viewport: Option<ChartViewport>,
+    /// Times of cursors A and B
+    pub cursors: [Option<u64>; 2],
+    pub drag: Option<ChartDrag>,
+    pub layout: ChartLayout,
+}
+
+impl ChartState {
+    pub fn is_charted(&self, signal: &ChartSignal) -> bool {
+        self.series.iter().any(|series| &series.signal == signal)
+    }
+
+    pub fn remove(&mut self, signal: &ChartSignal) {
+        self.series.retain(|series| &series.signal != signal);
+        if self.series.is_empty() {
+            self.reset_view();
+        }
+    }
+
+    /// Whole log again, cursors removed
+    pub fn reset_view(&mut self) {
+        self.viewport = None;
+        self.cursors = [None; 2];
+        self.drag = None;
+    }
+
+    /// Span covered by the charted signals
+    pub fn full_viewport(&self) -> Option<ChartViewport> {
+        full_viewport(&self.series)
+    }
+
+    /// Span on screen
+    pub fn viewport(&self) -> Option<ChartViewport> {
+        let full = self.full_viewport()?;
+        Some(self.viewport.map_or(full, |viewport| viewport.within(full)))
+    }
+
+    /// Zoom by `factor` around `anchor`, a fraction of the width
+    pub fn zoom(&mut self, factor: f64, anchor: f64) {
+        if let (Some(viewport), Some(full)) = (self.viewport(), self.full_viewport()) {
+            self.viewport = Some(viewport.zoom(factor, anchor, full));
+        }
+    }
+
+    /// Pan by `fraction` of the width
+    pub fn pan(&mut self, fraction: f64) {
+        if let (Some(viewport), Some(full)) = (self.viewport(), self.full_viewport()) {
+            self.viewport = Some(viewport.pan(fraction, full));
+        }
+    }
+
+    /// Move the series at `index` to the other axis
+    pub fn toggle_axis(&mut self, index: usize) {
+        if let Some(series) = self.series.get_mut(index) {
+            series.axis = series.axis.other();
+        }
+    }
+
+    /// Series scaled to `axis`
+    pub fn on_axis(&self, axis: ChartAxis) -> impl Iterator<Item = &ChartSeries> {
+        self.series.iter().filter(move |series| series.axis == axis)
+    }
+
+    /// Values spanned by `axis` in `viewport`: the union of its series' ranges
+    pub fn axis_range(&self, axis: ChartAxis, viewport: ChartViewport) -> Option<(f64, f64)> {
+        self.on_axis(axis)
+            .filter_map(|series| series.value_range(viewport))
+            .reduce(|(low, high), (min, max)| (low.min(min), high.max(max)))
+    }
+
+    /// Units of the signals on `axis`, each once: "rpm, °C"
+    pub fn axis_unit(&self, axis: ChartAxis) -> String {
+        let mut units: Vec<&str> = Vec::new();
+        for series in self.on_axis(axis) {
+            let unit = series.signal.unit.as_str();
+            if !unit.is_empty() && !units.contains(&unit) {
+                units.push(unit);
+            }
+        }
+        units.join(", ")
+    }
+
+    /// Values the series at `index` is scaled to in `viewport` under the current layout
+    pub fn plot_range(&self, index: usize, viewport: ChartViewport) -> Option<(f64, f64)> {
+        let series = self.series.get(index)?;
+        match self.layout {
+            ChartLayout::Overlay => self.axis_range(series.axis, viewport),
+            ChartLayout::Stacked | ChartLayout::Normalized => series.value_range(viewport),
+        }
+    }
+
+    /// Continue a pan drag, the pointer `dx` pixels from where it started on a plot `width` wide
+    pub fn drag_to(&mut self, dx: f32, width: f32) {
+        if let (Some(ChartDrag::Pan { from, .. }), Some(full)) = (self.drag, self.full_viewport())
+            && width > 0.0
+        {
+            self.viewport = Some(from.pan(-(dx / width) as f64, full));
+        }
+    }
+
+    /// The cursor closest to `fraction` of the width of `viewport`, if it is
+    /// within `tolerance` of it
+    pub fn cursor_near(
+        &self,
+        fraction: f64,
+        viewport: ChartViewport,
+        tolerance: f64,
+    ) -> Option<usize> {
+        self.cursors
+            .iter()
+            .enumerate()
+            .filter_map(|(index, time)| {
+                Some((index, (viewport.fraction_of((*time)?) - fraction).abs()))
+            })
+            .filter(|&(_, distance)| distance <= tolerance)
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(index, _)| index)
+    }
+
+    /// Time from cursor A to cursor B, negative when B is the earlier
+    pub fn cursor_span_ns(&self) -> Option<i64> {
+        let [Some(a), Some(b)] = self.cursors else {
+            return None;
+        };
+        Some(b as i64 - a as i64)
+    }
+
+    /// Change of `series` from cursor A to cursor B
+    pub fn cursor_delta(&self, series: &ChartSeries) -> Option<f64> {
+        let [Some(a), Some(b)] = self.cursors else {
+            return None;
+        };
+        Some(series.value_at(b)? - series.value_at(a)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn series(points: Vec<(u64, f64)>) -> ChartSeries {
+        ChartSeries {
+            signal: ChartSignal {
+                channel: 1,
+                id: 0x100,
+                lin: false,
+                name: "Speed".to_string(),
+                unit: "km/h".to_string(),
+                decimals: 1,
+            },
+            points,
+            axis: ChartAxis::Left,
+        }
+    }
+
+    #[test]
+    fn test_chart_signals_from_databases() {
+        let dbc = parser::dbc::DbcParser::new()
+            .parse(
+                "BO_ 256 Engine: 8 ECU\n\
+                 \x20SG_ Speed : 0|16@1+ (0.1,0) [0|1000] \"km/h\" Vector__XXX\n\
+                 \x20SG_ Gear : 16|8@1+ (1,0) [0|8] \"\" Vector__XXX\n",
+            )
+            .unwrap();
+        let signals = chart_signals(&HashMap::from([(2, dbc)]), &HashMap::new());
+        let names: Vec<&str> = signals.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, ["Gear", "Speed"]);
+        assert_eq!(signals[1].decimals, 1);
+        assert_eq!(signals[1].label(), "Speed · CH2 0x100");
+    }
+
+    #[test]
+    fn test_viewport_zoom_and_pan_stay_inside_the_log() {
+        let full = ChartViewport::new(0, 10_000_000);
+        // Zooming in around the middle keeps the middle in place
+        let zoomed = full.zoom(0.5, 0.5, full);
+        assert_eq!(zoomed, ChartViewport::new(2_500_000, 7_500_000));
+        // Zooming in at the left edge keeps the left edge
+        assert_eq!(full.zoom(0.1, 0.0, full), ChartViewport::new(0, 1_000_000));
+        // Panning stops at the ends
+        assert_eq!(
+            zoomed.pan(1.0, full),
+            ChartViewport::new(5_000_000, 10_000_000)
+        );
+        assert_eq!(zoomed.pan(-2.0, full), ChartViewport::new(0, 5_000_000));
+        // Zooming out never goes past the whole log, in never below the minimum
+        assert_eq!(zoomed.zoom(10.0, 0.5, full), full);
+        assert_eq!(full.zoom(1e-9, 0.5, full).span_ns(), MIN_CHART_SPAN_NS);
+    }
+
+    #[test]
+    fn test_value_at_holds_the_last_sample() {
+        let speed = series(vec![(100, 1.0), (200, 2.0), (300, 3.0)]);
+        assert_eq!(speed.value_at(50), None);
+        assert_eq!(speed.value_at(100), Some(1.0));
+        assert_eq!(speed.value_at(250), Some(2.0));
+        assert_eq!(speed.value_at(1_000), Some(3.0));
+        assert_eq!(full_viewport(&[speed]), Some(ChartViewport::new(100, 300)));
+    }
+
+    #[test]
+    fn test_polyline_keeps_extremes_per_column() {
+        // A spike among a thousand samples squeezed into two columns
+        let mut points: Vec<(u64, f64)> = (0..1_000).map(|t| (t, 0.0)).collect();
+        points[400].1 = 10.0;
+        let speed = series(points);
+        let viewport = ChartViewport::new(0, 999);
+        let range = speed.value_range(viewport).unwrap();
+        assert_eq!(range, (-0.5, 10.5));
+
+        let line = speed.polyline(viewport, range, 2);
+        assert!(line.len() <= 8);
+        let top = line.iter().map(|&(_, y)| y).fold(f32::MAX, f32::min);
+        assert!(top < 0.05, "spike lost: {:?}", line);
+        assert_eq!(line.first().unwrap().0, 0.0);
+        assert_eq!(line.last().unwrap().0, 1.0);
+
+        // A flat signal still gets a range to draw in
+        assert_eq!(
+            series(vec![(0, 5.0)]).value_range(viewport),
+            Some((4.0, 6.0))
+        );
+    }
+
+    #[test]
+    fn test_chart_state_views_the_charted_signals() {
+        let mut charts = ChartState::default();
+        assert_eq!(charts.viewport(), None);
+        let speed = series(vec![(0, 1.0), (1_000_000, 2.0)]);
+        charts.series.push(speed.clone());
+        assert!(charts.is_charted(&speed.signal));
+        assert_eq!(charts.viewport(), Some(ChartViewport::new(0, 1_000_000)));
+
+        charts.zoom(0.5, 0.0);
+        assert_eq!(charts.viewport(), Some(ChartViewport::new(0, 500_000)));
+        // Dragging the plot right by a quarter of its width shows earlier times
+        charts.pan(0.5);
+        charts.drag = Some(ChartDrag::Pan {
+            start_x: 100.0,
+            from: charts.viewport().unwrap(),
+        });
+        charts.drag_to(100.0, 400.0);
+        assert_eq!(
+            charts.viewport(),
+            Some(ChartViewport::new(125_000, 625_000))
+        );
+
+        charts.cursors[0] = Some(10);
+        charts.remove(&speed.signal);
+        assert_eq!(charts, ChartState::default());
+    }
+
+    #[test]
+    fn test_overlay_scales_series_per_axis() {
+        let viewport = ChartViewport::new(0, 100);
+        let mut rpm = series(vec![(0, 800.0), (100, 6_000.0)]);
+        rpm.signal.name = "Rpm".to_string();
+        rpm.signal.unit = "rpm".to_string();
+        let mut torque = series(vec![(0, 100.0), (100, 400.0)]);
+        torque.signal.unit = "Nm".to_string();
+        let temp = series(vec![(0, 20.0), (100, 90.0)]);
+        let mut charts = ChartState {
+            series: vec![rpm, torque, temp],
+            ..ChartState::default()
+        };
+        charts.toggle_axis(1);
+        charts.toggle_axis(2);
+        assert_eq!(charts.series[2].axis, ChartAxis::Right);
+        assert_eq!(charts.axis_unit(ChartAxis::Right), "Nm, km/h");
+
+        // Stacked and normalized plots scale every signal to itself
+        assert_eq!(charts.plot_range(2, viewport), Some((16.5, 93.5)));
+        charts.layout = ChartLayout::Normalized;
+        assert_eq!(charts.plot_range(2, viewport), Some((16.5, 93.5)));
+        // Overlaid, the right axis spans both of its signals
+        charts.layout = ChartLayout::Overlay;
+        assert_eq!(charts.plot_range(0, viewport), Some((540.0, 6_260.0)));
+        assert_eq!(charts.plot_range(2, viewport), Some((16.5, 415.0)));
+        assert_eq!(charts.plot_range(3, viewport), None);
+    }
+
+    #[test]
+    fn test_cursor_measurements() {
+        let viewport = ChartViewport::new(0, 1_000);
+        let speed = series(vec![(100, 10.0), (300, 25.5), (600, 40.0)]);
+        let mut charts = ChartState {
+            series: vec![speed.clone()],
+            ..ChartState::default()
+        };
+        assert_eq!(charts.cursor_span_ns(), None);
+        assert_eq!(charts.cursor_delta(&speed), None);
+
+        charts.cursors = [Some(650), Some(150)];
+        assert_eq!(charts.cursor_span_ns(), Some(-500));
+        assert_eq!(charts.cursor_delta(&speed), Some(-30.0));
+        // Cursor A holds no value before the first sample
+        charts.cursors[0] = Some(50);
+        assert_eq!(charts.cursor_delta(&speed), None);
+
+        // A press grabs the closer cursor within the tolerance
+        assert_eq!(charts.cursor_near(0.16, viewport, 0.02), Some(1));
+        assert_eq!(charts.cursor_near(0.07, viewport, 0.03), Some(0));
+        assert_eq!(charts.cursor_near(0.5, viewport, 0.02), None);
+    }
+}
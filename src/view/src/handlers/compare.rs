@@ -0,0 +1,393 @@
+//! Comparison of two measurement runs
+//!
+//! For A/B testing of an ECU the same drive is logged twice. [`compare_runs`]
+//! puts the per-ID and per-channel statistics of both logs side by side and
+//! keeps only what differs: IDs that appeared or disappeared, cycle times
+//! that drifted, error counts and the spread of a few chosen signals.
+//! [`format_report`] turns the result into plain text for the clipboard or a
+//! file.
+
+use crate::handlers::{BusStats, ExportFrame, IdStatsIndex, decode_frame, parse_id};
+use blf::LogObject;
+use parser::dbc::DbcDatabase;
+use parser::ldf::LdfDatabase;
+use std::collections::{BTreeSet, HashMap};
+use std::fmt;
+
+/// Cycle time change, in percent, above which an ID is reported as drifted
+pub const DEFAULT_DRIFT_PCT: f64 = 5.0;
+
+/// A signal whose values are compared between the runs
+#[derive(Debug, Clone, PartialEq)]
+pub struct SignalRef {
+    /// Only frames on this channel, or on every channel
+    pub channel: Option<u16>,
+    pub id: u32,
+    pub signal: String,
+}
+
+impl fmt::Display for SignalRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.channel {
+            Some(channel) => write!(f, "{}:0x{:X} {}", channel, self.id, self.signal),
+            None => write!(f, "0x{:X} {}", self.id, self.signal),
+        }
+    }
+}
+
+/// Parse signals separated by `;` or newlines
+///
+/// Syntax: `0x123 EngineSpeed` and `2:0x123 EngineSpeed` (channel 2 only).
+pub fn parse_signal_refs(text: &str) -> Result<Vec<SignalRef>, String> {
+    text.split([';', '\n'])
+        .map(str::trim)
+        .filter(|signal| !signal.is_empty())
+        .map(|signal| {
+            let Some((target, name)) = signal.split_once(char::is_whitespace) else {
+                return Err(format!("Expected '<id> <signal>' in '{}'", signal));
+            };
+            let (channel, id) = match target.split_once(':') {
+                Some((channel, id)) => (
+                    Some(
+                        channel
+                            .parse()
+                            .map_err(|_| format!("Invalid channel: '{}'", channel))?,
+                    ),
+                    parse_id(id)?,
+                ),
+                None => (None, parse_id(target)?),
+            };
+            Ok(SignalRef {
+                channel,
+                id,
+                signal: name.trim().to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Count, range and mean of a signal over one run
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SignalSummary {
+    pub count: usize,
+    pub min: f64,
+    pub max: f64,
+    pub mean: f64,
+}
+
+impl SignalSummary {
+    fn of(values: &[f64]) -> Option<Self> {
+        if values.is_empty() {
+            return None;
+        }
+        Some(Self {
+            count: values.len(),
+            min: values.iter().copied().fold(f64::INFINITY, f64::min),
+            max: values.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+            mean: values.iter().sum::<f64>() / values.len() as f64,
+        })
+    }
+}
+
+/// Decoded values of `signal` over `messages`
+fn signal_values(
+    messages: &[LogObject],
+    signal: &SignalRef,
+    dbc_channels: &HashMap<u16, DbcDatabase>,
+    ldf_channels: &HashMap<u16, LdfDatabase>,
+) -> Vec<f64> {
+    messages
+        .iter()
+        .filter_map(ExportFrame::from_log_object)
+        .filter(|frame| {
+            frame.id == signal.id && signal.channel.is_none_or(|ch| ch == frame.channel)
+        })
+        .filter_map(|frame| {
+            decode_frame(&frame, dbc_channels, ldf_channels)
+                .into_iter()
+                .find(|decoded| decoded.name == signal.signal)
+                .map(|decoded| decoded.value)
+                .filter(|value| value.is_finite())
+        })
+        .collect()
+}
+
+/// An ID seen in both runs whose cycle time changed
+#[derive(Debug, Clone, PartialEq)]
+pub struct CycleDrift {
+    pub channel: u16,
+    pub id: u32,
+    pub cycle_a_ms: f64,
+    pub cycle_b_ms: f64,
+}
+
+impl CycleDrift {
+    /// Change of run B against run A, in percent
+    pub fn drift_pct(&self) -> f64 {
+        (self.cycle_b_ms - self.cycle_a_ms) / self.cycle_a_ms * 100.0
+    }
+}
+
+/// Error frames of one channel in both runs
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ErrorCounts {
+    pub channel: u16,
+    pub errors_a: u64,
+    pub errors_b: u64,
+}
+
+/// Differences between run A and run B
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct RunComparison {
+    /// (channel, ID) pairs only in run B
+    pub new_ids: Vec<(u16, u32)>,
+    /// (channel, ID) pairs only in run A
+    pub missing_ids: Vec<(u16, u32)>,
+    /// IDs whose mean cycle time changed by more than the threshold, largest first
+    pub drifted: Vec<CycleDrift>,
+    /// Channels with error frames in either run
+    pub errors: Vec<ErrorCounts>,
+    pub signals: Vec<(SignalRef, Option<SignalSummary>, Option<SignalSummary>)>,
+}
+
+impl RunComparison {
+    /// Whether nothing worth reporting differs
+    pub fn is_identical(&self) -> bool {
+        self.new_ids.is_empty()
+            && self.missing_ids.is_empty()
+            && self.drifted.is_empty()
+            && self.errors.iter().all(|e| e.errors_a == e.errors_b)
+            && self.signals.iter().all(|(_, a, b)| a == b)
+    }
+}
+
+/// Compare run `b` against run `a`
+pub fn compare_runs(
+    a: &[LogObject],
+    b: &[LogObject],
+    signals: &[SignalRef],
+    drift_threshold_pct: f64,
+    dbc_channels: &HashMap<u16, DbcDatabase>,
+    ldf_channels: &HashMap<u16, LdfDatabase>,
+) -> RunComparison {
+    let (ids_a, ids_b) = (IdStatsIndex::build(a), IdStatsIndex::build(b));
+    let keys_a: BTreeSet<(u16, u32)> = ids_a.iter().map(|(key, _)| key).collect();
+    let keys_b: BTreeSet<(u16, u32)> = ids_b.iter().map(|(key, _)| key).collect();
+
+    let mut drifted: Vec<CycleDrift> = keys_a
+        .intersection(&keys_b)
+        .filter_map(|&(channel, id)| {
+            let drift = CycleDrift {
+                channel,
+                id,
+                cycle_a_ms: ids_a.get(channel, id)?.mean_cycle_ms()?,
+                cycle_b_ms: ids_b.get(channel, id)?.mean_cycle_ms()?,
+            };
+            (drift.cycle_a_ms > 0.0 && drift.drift_pct().abs() > drift_threshold_pct)
+                .then_some(drift)
+        })
+        .collect();
+    drifted.sort_by(|x, y| y.drift_pct().abs().total_cmp(&x.drift_pct().abs()));
+
+    let (bus_a, bus_b) = (BusStats::build(a), BusStats::build(b));
+    let errors_of = |stats: &BusStats, channel: u16| {
+        stats
+            .channels()
+            .find(|&(ch, _)| ch == channel)
+            .map_or(0, |(_, stats)| stats.errors)
+    };
+    let channels: BTreeSet<u16> = bus_a
+        .channels()
+        .chain(bus_b.channels())
+        .map(|(ch, _)| ch)
+        .collect();
+    let errors = channels
+        .into_iter()
+        .map(|channel| ErrorCounts {
+            channel,
+            errors_a: errors_of(&bus_a, channel),
+            errors_b: errors_of(&bus_b, channel),
+        })
+        .filter(|counts| counts.errors_a + counts.errors_b > 0)
+        .collect();
+
+    let signals = signals
+        .iter()
+        .map(|signal| {
+            let summary = |messages: &[LogObject]| {
+                SignalSummary::of(&signal_values(messages, signal, dbc_channels, ldf_channels))
+            };
+            (signal.clone(), summary(a), summary(b))
+        })
+        .collect();
+
+    RunComparison {
+        new_ids: keys_b.difference(&keys_a).copied().collect(),
+        missing_ids: keys_a.difference(&keys_b).copied().collect(),
+        drifted,
+        errors,
+        signals,
+    }
+}
+
+/// Plain-text report of `comparison`
+pub fn format_report(comparison: &RunComparison, name_a: &str, name_b: &str) -> String {
+    let ids = |ids: &[(u16, u32)]| {
+        ids.iter()
+            .map(|(channel, id)| format!("  CH{} 0x{:X}\n", channel, id))
+            .collect::<String>()
+    };
+    let summary = |summary: &Option<SignalSummary>| match summary {
+        Some(s) => format!(
+            "{} values, {:.3} … {:.3}, mean {:.3}",
+            s.count, s.min, s.max, s.mean
+        ),
+        None => "not found".to_string(),
+    };
+
+    let mut out = format!("Run comparison\n  A: {}\n  B: {}\n", name_a, name_b);
+    if comparison.is_identical() {
+        out.push_str("\nNo differences found.\n");
+    }
+    if !comparison.new_ids.is_empty() {
+        out.push_str(&format!("\nNew IDs in B ({}):\n", comparison.new_ids.len()));
+        out.push_str(&ids(&comparison.new_ids));
+    }
+    if !comparison.missing_ids.is_empty() {
+        out.push_str(&format!(
+            "\nMissing IDs in B ({}):\n",
+            comparison.missing_ids.len()
+        ));
+        out.push_str(&ids(&comparison.missing_ids));
+    }
+    if !comparison.drifted.is_empty() {
+        out.push_str(&format!(
+            "\nCycle time drift ({}):\n",
+            comparison.drifted.len()
+        ));
+        for drift in &comparison.drifted {
+            out.push_str(&format!(
+                "  CH{} 0x{:X}: {:.3} ms -> {:.3} ms ({:+.1}%)\n",
+                drift.channel,
+                drift.id,
+                drift.cycle_a_ms,
+                drift.cycle_b_ms,
+                drift.drift_pct()
+            ));
+        }
+    }
+    if !comparison.errors.is_empty() {
+        out.push_str("\nError frames:\n");
+        for counts in &comparison.errors {
+            out.push_str(&format!(
+                "  CH{}: {} -> {}\n",
+                counts.channel, counts.errors_a, counts.errors_b
+            ));
+        }
+    }
+    if !comparison.signals.is_empty() {
+        out.push_str("\nSignals:\n");
+        for (signal, a, b) in &comparison.signals {
+            out.push_str(&format!(
+                "  {}\n    A: {}\n    B: {}\n",
+                signal,
+                summary(a),
+                summary(b)
+            ));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::handlers::test_support::can;
+    use blf::CanErrorFrame;
+
+    /// `count` frames of `id` every `cycle_ms`, with byte 0 counting up from `first`
+    fn cyclic(id: u32, cycle_ms: u64, count: u64, first: u8) -> Vec<LogObject> {
+        (0..count)
+            .map(|i| {
+                can(id)
+                    .at(i * cycle_ms * 1_000_000)
+                    .byte(0, first + i as u8)
+                    .build()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_signal_refs() {
+        let signals = parse_signal_refs("0x100 Counter; 2:0x200 Engine Speed").unwrap();
+        assert_eq!(signals[0].to_string(), "0x100 Counter");
+        assert_eq!(signals[1].channel, Some(2));
+        assert_eq!(signals[1].signal, "Engine Speed");
+        assert!(parse_signal_refs("0x100").is_err());
+    }
+
+    #[test]
+    fn test_compare_runs() {
+        let db = parser::dbc::DbcParser::new()
+            .parse("BO_ 256 Status: 8 ECU\n SG_ Counter : 0|8@1+ (1,0) [0|255] \"\" Vector__XXX\n")
+            .unwrap();
+        let dbc_channels = HashMap::from([(1, db)]);
+
+        let mut a = cyclic(0x100, 10, 5, 0);
+        a.extend(cyclic(0x200, 100, 3, 0));
+        a.extend(cyclic(0x300, 20, 3, 0));
+        let mut b = cyclic(0x100, 10, 5, 10);
+        b.extend(cyclic(0x200, 120, 3, 0));
+        b.extend(cyclic(0x400, 20, 3, 0));
+        let mut error = CanErrorFrame {
+            channel: 1,
+            ..Default::default()
+        };
+        error.header.object_time_stamp = 5;
+        b.push(LogObject::CanErrorFrame(error));
+
+        let signals = parse_signal_refs("0x100 Counter; 0x100 Missing").unwrap();
+        let comparison = compare_runs(
+            &a,
+            &b,
+            &signals,
+            DEFAULT_DRIFT_PCT,
+            &dbc_channels,
+            &HashMap::new(),
+        );
+
+        assert_eq!(comparison.new_ids, vec![(1, 0x400)]);
+        assert_eq!(comparison.missing_ids, vec![(1, 0x300)]);
+        assert_eq!(comparison.drifted.len(), 1);
+        assert_eq!(comparison.drifted[0].id, 0x200);
+        assert!((comparison.drifted[0].drift_pct() - 20.0).abs() < 1e-9);
+        assert_eq!(
+            comparison.errors,
+            vec![ErrorCounts {
+                channel: 1,
+                errors_a: 0,
+                errors_b: 1
+            }]
+        );
+        let (_, counter_a, counter_b) = &comparison.signals[0];
+        assert_eq!(counter_a.unwrap().mean, 2.0);
+        assert_eq!(counter_b.unwrap().max, 14.0);
+        assert_eq!(comparison.signals[1].1, None);
+        assert!(!comparison.is_identical());
+
+        let report = format_report(&comparison, "a.blf", "b.blf");
+        assert!(report.contains("CH1 0x200: 100.000 ms -> 120.000 ms (+20.0%)"));
+        assert!(report.contains("CH1: 0 -> 1"));
+        assert!(
+            compare_runs(
+                &a,
+                &a,
+                &[],
+                DEFAULT_DRIFT_PCT,
+                &dbc_channels,
+                &HashMap::new()
+            )
+            .is_identical()
+        );
+    }
+}
@@ -0,0 +1,192 @@
+//! Pairwise correlation of signals over a time range
+//!
+//! Signals arrive at their own cycle times, so each one is first resampled
+//! onto a common time grid by holding its last value. [`correlation_matrix`]
+//! then computes the Pearson coefficient of every pair over the grid points
+//! where both signals have a value, which shows which inputs move together
+//! around an event.
+
+use crate::handlers::{ExportFrame, SignalRef, TimeOffsets, decode_frame};
+use blf::LogObject;
+use parser::dbc::DbcDatabase;
+use parser::ldf::LdfDatabase;
+use std::collections::HashMap;
+use std::ops::RangeInclusive;
+
+/// Default spacing of the resampling grid, in milliseconds
+pub const DEFAULT_CORRELATION_STEP_MS: f64 = 10.0;
+
+/// Most grid points computed; longer ranges get a coarser step
+pub const MAX_CORRELATION_SAMPLES: u64 = 1_000_000;
+
+/// Correlation coefficients of every pair of signals
+#[derive(Debug, Clone, PartialEq)]
+pub struct CorrelationMatrix {
+    pub signals: Vec<SignalRef>,
+    pub start_ns: u64,
+    pub end_ns: u64,
+    pub step_ns: u64,
+    /// Row-major, `None` where a signal is missing or constant in the range
+    coefficients: Vec<Option<f64>>,
+}
+
+impl CorrelationMatrix {
+    pub fn get(&self, row: usize, column: usize) -> Option<f64> {
+        self.coefficients[row * self.signals.len() + column]
+    }
+}
+
+/// Decoded values of `signal` with their corrected timestamps
+fn series(
+    messages: &[LogObject],
+    signal: &SignalRef,
+    dbc_channels: &HashMap<u16, DbcDatabase>,
+    ldf_channels: &HashMap<u16, LdfDatabase>,
+    offsets: &TimeOffsets,
+) -> Vec<(u64, f64)> {
+    let mut points: Vec<(u64, f64)> = messages
+        .iter()
+        .filter_map(|msg| ExportFrame::with_offsets(msg, offsets))
+        .filter(|frame| {
+            frame.id == signal.id && signal.channel.is_none_or(|ch| ch == frame.channel)
+        })
+        .filter_map(|frame| {
+            decode_frame(&frame, dbc_channels, ldf_channels)
+                .into_iter()
+                .find(|decoded| decoded.name == signal.signal)
+                .map(|decoded| (frame.timestamp_ns, decoded.value))
+                .filter(|(_, value)| value.is_finite())
+        })
+        .collect();
+    // Offsets can reorder frames of different channels
+    points.sort_by_key(|(timestamp, _)| *timestamp);
+    points
+}
+
+/// Values of `series` at `start_ns`, `start_ns + step_ns`, ... up to `end_ns`
+///
+/// Each point holds the last value at or before it; points before the first
+/// value are `None`.
+pub fn resample(
+    series: &[(u64, f64)],
+    start_ns: u64,
+    end_ns: u64,
+    step_ns: u64,
+) -> Vec<Option<f64>> {
+    let mut next = 0;
+    let mut last = None;
+    (start_ns..=end_ns)
+        .step_by(step_ns.max(1) as usize)
+        .map(|time| {
+            while let Some(&(timestamp, value)) = series.get(next) {
+                if timestamp > time {
+                    break;
+                }
+                last = Some(value);
+                next += 1;
+            }
+            last
+        })
+        .collect()
+}
+
+/// Pearson correlation over the points where both series have a value
+///
+/// `None` with fewer than two such points or when either series is constant.
+pub fn pearson(a: &[Option<f64>], b: &[Option<f64>]) -> Option<f64> {
+    let pairs: Vec<(f64, f64)> = a
+        .iter()
+        .zip(b)
+        .filter_map(|(&a, &b)| Some((a?, b?)))
+        .collect();
+    if pairs.len() < 2 {
+        return None;
+    }
+    let n = pairs.len() as f64;
+    let mean_a = pairs.iter().map(|(a, _)| a).sum::<f64>() / n;
+    let mean_b = pairs.iter().map(|(_, b)| b).sum::<f64>() / n;
+    let (mut cov, mut var_a, mut var_b) = (0.0, 0.0, 0.0);
+    for (a, b) in &pairs {
+        cov += (a - mean_a) * (b - mean_b);
+        var_a += (a - mean_a).powi(2);
+        var_b += (b - mean_b).powi(2);
+    }
+    if var_a == 0.0 || var_b == 0.0 {
+        return None;
+    }
+    Some((cov / (var_a * var_b).sqrt()).clamp(-1.0, 1.0))
+}
+
+/// Correlate `signals` over `range` (in ns) on a grid of `step_ns`
+///
+/// Times are corrected by `offsets`, as shown in the log. The value a signal
+/// had when the range starts counts as its first point.
+pub fn correlation_matrix(
+    messages: &[LogObject],
+    signals: &[SignalRef],
+    range: RangeInclusive<u64>,
+    step_ns: u64,
+    dbc_channels: &HashMap<u16, DbcDatabase>,
+    ldf_channels: &HashMap<u16, LdfDatabase>,
+    offsets: &TimeOffsets,
+) -> CorrelationMatrix {
+    let (start_ns, end_ns) = range.into_inner();
+    let step_ns = step_ns
+        .max(1)
+        .max(end_ns.saturating_sub(start_ns) / MAX_CORRELATION_SAMPLES);
+    let samples: Vec<Vec<Option<f64>>> = signals
+        .iter()
+        .map(|signal| {
+            let points = series(messages, signal, dbc_channels, ldf_channels, offsets);
+            resample(&points, start_ns, end_ns, step_ns)
+        })
+        .collect();
+
+    let n = signals.len();
+    let mut coefficients = vec![None; n * n];
+    for row in 0..n {
+        for column in row..n {
+            let r = pearson(&samples[row], &samples[column]);
+            coefficients[row * n + column] = r;
+            coefficients[column * n + row] = r;
+        }
+    }
+    CorrelationMatrix {
+        signals: signals.to_vec(),
+        start_ns,
+        end_ns,
+        step_ns,
+        coefficients,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resample_holds_last_value() {
+        let series = [(1_000, 1.0), (2_500, 2.0), (2_600, 3.0)];
+        assert_eq!(
+            resample(&series, 0, 4_000, 1_000),
+            vec![None, Some(1.0), Some(1.0), Some(3.0), Some(3.0)]
+        );
+    }
+
+    #[test]
+    fn test_pearson() {
+        let x: Vec<Option<f64>> = (0..10).map(|i| Some(i as f64)).collect();
+        let doubled: Vec<Option<f64>> = x.iter().map(|v| v.map(|v| 2.0 * v + 1.0)).collect();
+        let inverted: Vec<Option<f64>> = x.iter().map(|v| v.map(|v| -v)).collect();
+        let constant = vec![Some(5.0); 10];
+        let mut partial = doubled.clone();
+        partial[..8].fill(None);
+
+        assert!((pearson(&x, &doubled).unwrap() - 1.0).abs() < 1e-12);
+        assert!((pearson(&x, &inverted).unwrap() + 1.0).abs() < 1e-12);
+        assert_eq!(pearson(&x, &constant), None);
+        assert!((pearson(&x, &partial).unwrap() - 1.0).abs() < 1e-12);
+        partial[8] = None;
+        assert_eq!(pearson(&x, &partial), None);
+    }
+}
@@ -0,0 +1,318 @@
+//! CSV export with a column per decoded signal
+//!
+//! Unlike the fixed layout of [`format_rows`](crate::handlers::format_rows),
+//! which export plugins rely on, this writer follows [`CsvOptions`]: the
+//! delimiter, wall-clock or relative time, and hex or decimal IDs. With
+//! decoding on, every signal of the frames being exported gets a column of
+//! its own, so a spreadsheet can plot it directly; a row fills the columns
+//! of its own frame and leaves the others empty.
+//!
+//! [`CsvExport::new`] settles the columns up front, after which
+//! [`CsvExport::rows`] can format the messages in chunks like the other
+//! export formats. The dialog, batch jobs and anything else holding numbered
+//! messages and databases share it.
+
+use crate::handlers::{ExportFrame, NumberedMessage, TimeOffsets, decode_frame, hex_frame_id};
+use crate::models::preferences::{CsvOptions, IdBase, TimestampFormat};
+use parser::dbc::DbcDatabase;
+use parser::ldf::LdfDatabase;
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// A signal column: where its values come from and its header
+#[derive(Debug, Clone, PartialEq)]
+struct SignalColumn {
+    channel: u16,
+    id: u32,
+    name: String,
+    header: String,
+}
+
+/// Columns and options of one CSV export
+#[derive(Debug, Clone)]
+pub struct CsvExport {
+    options: CsvOptions,
+    start_time: Option<chrono::NaiveDateTime>,
+    columns: Vec<SignalColumn>,
+    /// Column index of each (channel, frame ID, signal name)
+    column_of: HashMap<(u16, u32, String), usize>,
+}
+
+impl CsvExport {
+    /// Lay out the columns for `messages`; without `decoded` only the frame columns are written
+    ///
+    /// `start_time` is the measurement start that absolute times count from.
+    pub fn new(
+        options: CsvOptions,
+        decoded: bool,
+        messages: &[NumberedMessage],
+        dbc_channels: &HashMap<u16, DbcDatabase>,
+        ldf_channels: &HashMap<u16, LdfDatabase>,
+        start_time: Option<chrono::NaiveDateTime>,
+    ) -> Self {
+        // Unit and frame type of each signal
+        let mut signals: BTreeMap<(u16, u32, String), (String, &str)> = BTreeMap::new();
+        if decoded {
+            let mut seen = HashSet::new();
+            for frame in messages
+                .iter()
+                .filter_map(|(_, msg)| ExportFrame::from_log_object(msg))
+            {
+                if !seen.insert((frame.channel, frame.id)) {
+                    continue;
+                }
+                for signal in decode_frame(&frame, dbc_channels, ldf_channels) {
+                    signals.insert(
+                        (frame.channel, frame.id, signal.name),
+                        (signal.unit, frame.kind),
+                    );
+                }
+            }
+        }
+
+        // A name used by several frames is told apart by channel and ID
+        let mut name_uses: HashMap<&str, usize> = HashMap::new();
+        for (_, _, name) in signals.keys() {
+            *name_uses.entry(name).or_default() += 1;
+        }
+        let columns: Vec<SignalColumn> = signals
+            .iter()
+            .map(|((channel, id, name), (unit, kind))| {
+                let mut header = name.clone();
+                if name_uses[name.as_str()] > 1 {
+                    header = format!(
+                        "{} ({}:{})",
+                        header,
+                        channel,
+                        format_id(kind, *id, options.id_base)
+                    );
+                }
+                if !unit.is_empty() {
+                    header = format!("{} [{}]", header, unit);
+                }
+                SignalColumn {
+                    channel: *channel,
+                    id: *id,
+                    name: name.clone(),
+                    header,
+                }
+            })
+            .collect();
+        let column_of = columns
+            .iter()
+            .enumerate()
+            .map(|(index, column)| ((column.channel, column.id, column.name.clone()), index))
+            .collect();
+
+        Self {
+            options,
+            start_time,
+            columns,
+            column_of,
+        }
+    }
+
+    /// Header line
+    pub fn header(&self) -> String {
+        let time = match self.options.time_format {
+            TimestampFormat::Absolute if self.start_time.is_some() => "time",
+            _ => "time_s",
+        };
+        let fields = ["seq", time, "channel", "type", "id", "dlc", "data"]
+            .into_iter()
+            .map(str::to_string)
+            .chain(self.columns.iter().map(|column| column.header.clone()));
+        self.line(fields)
+    }
+
+    /// Format a chunk of messages; returns the text and the number of rows written
+    pub fn rows(
+        &self,
+        messages: &[NumberedMessage],
+        dbc_channels: &HashMap<u16, DbcDatabase>,
+        ldf_channels: &HashMap<u16, LdfDatabase>,
+        offsets: &TimeOffsets,
+    ) -> (String, usize) {
+        let mut out = String::new();
+        let mut rows = 0;
+        for (seq, frame) in messages
+            .iter()
+            .filter_map(|(seq, msg)| Some((seq, ExportFrame::with_offsets(msg, offsets)?)))
+        {
+            let mut cells = vec![String::new(); self.columns.len()];
+            if !self.columns.is_empty() {
+                for signal in decode_frame(&frame, dbc_channels, ldf_channels) {
+                    let key = (frame.channel, frame.id, signal.name);
+                    if let Some(&index) = self.column_of.get(&key) {
+                        cells[index] = match signal.text {
+                            Some(text) => text,
+                            None if signal.value.is_finite() => {
+                                self.options.numbers.format(signal.value, signal.decimals)
+                            }
+                            None => String::new(),
+                        };
+                    }
+                }
+            }
+            let fields = [
+                seq.to_string(),
+                self.format_time(frame.timestamp_ns),
+                frame.channel.to_string(),
+                frame.kind.to_string(),
+                format_id(frame.kind, frame.id, self.options.id_base),
+                frame.data.len().to_string(),
+                frame.data_hex(),
+            ]
+            .into_iter()
+            .chain(cells);
+            out.push_str(&self.line(fields));
+            rows += 1;
+        }
+        (out, rows)
+    }
+
+    fn format_time(&self, timestamp_ns: u64) -> String {
+        match (self.options.time_format, self.start_time) {
+            (TimestampFormat::Absolute, Some(start)) => (start
+                + chrono::Duration::nanoseconds(timestamp_ns as i64))
+            .format("%Y-%m-%d %H:%M:%S%.6f")
+            .to_string(),
+            _ => self
+                .options
+                .numbers
+                .decimal_separator
+                .localize(format!("{:.6}", timestamp_ns as f64 / 1_000_000_000.0)),
+        }
+    }
+
+    fn line(&self, fields: impl IntoIterator<Item = String>) -> String {
+        let delimiter = self.options.delimiter.as_char();
+        let mut line = fields
+            .into_iter()
+            .map(|field| escape(&field, delimiter))
+            .collect::<Vec<_>>()
+            .join(&delimiter.to_string());
+        line.push('\n');
+        line
+    }
+}
+
+fn format_id(kind: &str, id: u32, base: IdBase) -> String {
+    match base {
+        IdBase::Hex => hex_frame_id(kind, id),
+        IdBase::Decimal => id.to_string(),
+    }
+}
+
+/// Quote a field holding the delimiter, a quote or a line break
+fn escape(field: &str, delimiter: char) -> String {
+    if field.contains([delimiter, '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::handlers::test_support::can;
+    use crate::models::preferences::{
+        CsvDelimiter, DecimalSeparator, NumberFormat, SignalPrecision,
+    };
+    use parser::dbc::DbcParser;
+
+    #[test]
+    fn test_signal_columns_and_options() {
+        let dbc = DbcParser::new()
+            .parse(
+                "BO_ 256 Engine: 8 ECU\n\
+                 \x20SG_ Speed : 0|16@1+ (0.5,0) [0|1000] \"rpm\" Vector__XXX\n\
+                 \x20SG_ Gear : 16|8@1+ (1,0) [0|8] \"\" Vector__XXX\n\
+                 BO_ 512 Brake: 8 ECU\n\
+                 \x20SG_ Speed : 0|8@1+ (1,0) [0|255] \"km/h\" Vector__XXX\n",
+            )
+            .unwrap();
+        let dbc_channels = HashMap::from([(1, dbc)]);
+        let messages = vec![
+            (
+                1,
+                can(0x100)
+                    .at(1_500_000)
+                    .data([0x10, 0x00, 3, 0, 0, 0, 0, 0])
+                    .build(),
+            ),
+            (
+                1,
+                can(0x200)
+                    .at(2_000_000)
+                    .data([0x2A, 0, 0, 0, 0, 0, 0, 0])
+                    .build(),
+            ),
+        ];
+        let ldf_channels = HashMap::new();
+        let offsets = TimeOffsets::new();
+
+        let export = CsvExport::new(
+            CsvOptions::default(),
+            true,
+            &messages,
+            &dbc_channels,
+            &ldf_channels,
+            None,
+        );
+        assert_eq!(
+            export.header(),
+            "seq,time_s,channel,type,id,dlc,data,Gear,Speed (1:0x100) [rpm],Speed (1:0x200) [km/h]\n"
+        );
+        let (text, rows) = export.rows(&messages, &dbc_channels, &ldf_channels, &offsets);
+        assert_eq!(rows, 2);
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(
+            lines[0],
+            "1,0.001500,1,CAN,0x100,8,10 00 03 00 00 00 00 00,3,8.0,"
+        );
+        assert!(lines[1].ends_with(",0x200,8,2A 00 00 00 00 00 00 00,,,42"));
+
+        let start = chrono::NaiveDate::from_ymd_opt(2026, 10, 17)
+            .unwrap()
+            .and_hms_opt(8, 0, 0)
+            .unwrap();
+        let options = CsvOptions {
+            delimiter: CsvDelimiter::Semicolon,
+            time_format: TimestampFormat::Absolute,
+            id_base: IdBase::Decimal,
+            numbers: NumberFormat {
+                decimal_separator: DecimalSeparator::Comma,
+                precision: SignalPrecision::Fixed(2),
+            },
+        };
+        let export = CsvExport::new(
+            options,
+            false,
+            &messages,
+            &dbc_channels,
+            &ldf_channels,
+            Some(start),
+        );
+        assert_eq!(export.header(), "seq;time;channel;type;id;dlc;data\n");
+        let (text, _) = export.rows(&messages[..1], &dbc_channels, &ldf_channels, &offsets);
+        assert_eq!(
+            text,
+            "1;2026-10-17 08:00:00.001500;1;CAN;256;8;10 00 03 00 00 00 00 00\n"
+        );
+
+        // A decimal comma in the values and relative times
+        let options = CsvOptions {
+            time_format: TimestampFormat::Relative,
+            ..options
+        };
+        let export = CsvExport::new(options, true, &messages, &dbc_channels, &ldf_channels, None);
+        let (text, _) = export.rows(&messages[..1], &dbc_channels, &ldf_channels, &offsets);
+        assert_eq!(
+            text,
+            "1;0,001500;1;CAN;256;8;10 00 03 00 00 00 00 00;3,00;8,00;\n"
+        );
+        assert_eq!(escape("a;b", ';'), "\"a;b\"");
+        assert_eq!(escape("a,b", ';'), "a,b");
+    }
+}
@@ -0,0 +1,819 @@
+//! Export of log messages
+//!
+//! Formatting is split into header / rows / footer so the caller can write a
+//! large log in chunks on the background executor and report progress
+//! between chunks. Only bus frames (CAN, CAN FD, LIN) are exported; other
+//! log objects are skipped. ASC lines come from [`format_asc_frame`].
+//! BLF is binary and goes through [`BlfExport`] instead of the text writers,
+//! as Parquet goes through [`ParquetExport`](crate::handlers::ParquetExport).
+
+use crate::handlers::{
+    ASC_FOOTER, TimeOffsets, asc_header, blf_start_time, format_asc_frame, hex_frame_id,
+    skipped_objects,
+};
+use crate::models::preferences::{ExportFormat, NumberFormat};
+use blf::{BlfWriter, LinMessage, LinMessage2, LogObject};
+use parser::dbc::DbcDatabase;
+use parser::ldf::LdfDatabase;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::Path;
+use std::sync::Mutex;
+
+/// Number of messages formatted per background task
+pub const EXPORT_CHUNK_SIZE: usize = 5_000;
+
+/// Which messages are exported
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum ExportScope {
+    /// Every loaded message
+    All,
+    /// Messages passing the current ID / channel filters
+    #[default]
+    Filtered,
+    /// Rows selected in the log view
+    Selected,
+    /// Messages whose timestamp falls in a range (seconds from start)
+    TimeRange,
+}
+
+impl ExportScope {
+    pub const ALL: [ExportScope; 4] = [
+        ExportScope::All,
+        ExportScope::Filtered,
+        ExportScope::Selected,
+        ExportScope::TimeRange,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ExportScope::All => "All",
+            ExportScope::Filtered => "Filtered",
+            ExportScope::Selected => "Selected",
+            ExportScope::TimeRange => "Time range",
+        }
+    }
+}
+
+/// Whether `format` has a writer in this build
+pub fn is_format_supported(format: ExportFormat) -> bool {
+    matches!(
+        format,
+        ExportFormat::Blf
+            | ExportFormat::Asc
+            | ExportFormat::Candump
+            | ExportFormat::Csv
+            | ExportFormat::Json
+            | ExportFormat::Mf4
+            | ExportFormat::Sqlite
+    ) || (format == ExportFormat::Parquet && cfg!(feature = "parquet"))
+}
+
+/// A message paired with its sequence number
+pub type NumberedMessage = (u64, LogObject);
+
+/// Sequence number of the message at `index` of the loaded log
+///
+/// Numbers start at 1 and follow the order the messages are held in once
+/// loaded, so a frame keeps its number through filters, selections and
+/// every export format, and a row from a report can be found in the others.
+pub fn sequence_number(index: usize) -> u64 {
+    index as u64 + 1
+}
+
+/// The messages at `indices` with their sequence numbers
+pub fn numbered_messages(
+    messages: &[LogObject],
+    indices: impl IntoIterator<Item = usize>,
+) -> Vec<NumberedMessage> {
+    indices
+        .into_iter()
+        .filter_map(|index| Some((sequence_number(index), messages.get(index)?.clone())))
+        .collect()
+}
+
+/// Messages with a corrected timestamp in `[start_s, end_s]`, in seconds from the measurement start
+pub fn messages_in_time_range(
+    messages: &[LogObject],
+    offsets: &TimeOffsets,
+    start_s: f64,
+    end_s: f64,
+) -> Vec<NumberedMessage> {
+    let indices = messages.iter().enumerate().filter_map(|(index, msg)| {
+        let seconds = offsets.timestamp(msg) as f64 / 1_000_000_000.0;
+        (seconds >= start_s && seconds <= end_s).then_some(index)
+    });
+    numbered_messages(messages, indices)
+}
+
+/// Parse the "from" / "to" fields of the time range scope
+pub fn parse_time_range(start: &str, end: &str) -> Result<(f64, f64), String> {
+    let parse = |text: &str, name: &str| {
+        text.trim()
+            .parse::<f64>()
+            .map_err(|_| format!("Invalid {} time: '{}'", name, text.trim()))
+    };
+    let start_s = parse(start, "start")?;
+    let end_s = parse(end, "end")?;
+    if end_s < start_s {
+        return Err("End time is before start time".to_string());
+    }
+    Ok((start_s, end_s))
+}
+
+/// The exportable part of a bus frame
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExportFrame {
+    pub timestamp_ns: u64,
+    pub channel: u16,
+    pub kind: &'static str,
+    pub id: u32,
+    pub data: Vec<u8>,
+}
+
+impl ExportFrame {
+    /// Extract a frame with its time corrected by `offsets`
+    pub fn with_offsets(msg: &LogObject, offsets: &TimeOffsets) -> Option<Self> {
+        Self::from_log_object(msg).map(|frame| Self {
+            timestamp_ns: offsets.timestamp(msg),
+            ..frame
+        })
+    }
+
+    /// Extract a frame, or `None` for objects that are not bus frames
+    pub fn from_log_object(msg: &LogObject) -> Option<Self> {
+        let (channel, kind, id, data): (u16, &'static str, u32, &[u8]) = match msg {
+            LogObject::CanMessage(m) => (
+                m.channel,
+                "CAN",
+                m.id,
+                &m.data[..m.data.len().min(m.dlc as usize)],
+            ),
+            LogObject::CanMessage2(m) => (
+                m.channel,
+                "CAN2",
+                m.id,
+                &m.data[..m.data.len().min(m.dlc as usize)],
+            ),
+            LogObject::CanFdMessage(m) => (
+                m.channel,
+                "CAN_FD",
+                m.id,
+                &m.data[..m.data.len().min(m.valid_data_bytes as usize)],
+            ),
+            LogObject::CanFdMessage64(m) => (
+                m.channel as u16,
+                "CAN_FD64",
+                m.id,
+                &m.data[..m.data.len().min(m.valid_data_bytes as usize)],
+            ),
+            LogObject::LinMessage(m) => (
+                m.channel,
+                "LIN",
+                m.id as u32,
+                &m.data[..m.data.len().min(m.dlc as usize)],
+            ),
+            LogObject::LinMessage2(m) => (
+                m.channel,
+                "LIN",
+                m.id as u32,
+                &m.data[..m.data.len().min(m.dlc as usize)],
+            ),
+            _ => return None,
+        };
+
+        Some(Self {
+            timestamp_ns: msg.timestamp(),
+            channel,
+            kind,
+            id,
+            data: data.to_vec(),
+        })
+    }
+
+    /// Payload as space-separated hex bytes
+    pub fn data_hex(&self) -> String {
+        self.data
+            .iter()
+            .map(|b| format!("{:02X}", b))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// A decoded signal value
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecodedSignal {
+    pub name: String,
+    pub raw: u64,
+    pub value: f64,
+    pub unit: String,
+    /// Value table entry for `raw`, e.g. "Reverse"
+    pub text: Option<String>,
+    /// Fractional digits the signal's scaling resolves
+    pub decimals: usize,
+}
+
+impl DecodedSignal {
+    /// The value in `numbers`' precision and decimal separator
+    pub fn format_value(&self, numbers: NumberFormat) -> String {
+        numbers.format(self.value, self.decimals)
+    }
+
+    /// Value table text if there is one, otherwise the value and unit
+    pub fn display_value(&self, numbers: NumberFormat) -> String {
+        match &self.text {
+            Some(text) => text.clone(),
+            None if self.unit.is_empty() => self.format_value(numbers),
+            None => format!("{} {}", self.format_value(numbers), self.unit),
+        }
+    }
+}
+
+/// Decode a frame against the database assigned to its channel
+///
+/// Multiplexed signals the frame does not select are left out. Signals are
+/// sorted by name so the output is stable.
+pub fn decode_frame(
+    frame: &ExportFrame,
+    dbc_channels: &HashMap<u16, DbcDatabase>,
+    ldf_channels: &HashMap<u16, LdfDatabase>,
+) -> Vec<DecodedSignal> {
+    let mut signals = if frame.kind == "LIN" {
+        ldf_channels
+            .get(&frame.channel)
+            .and_then(|db| {
+                db.frames
+                    .values()
+                    .find(|f| f.id == frame.id)
+                    .map(|f| (db, f))
+            })
+            .map(|(db, ldf_frame)| {
+                ldf_frame
+                    .signals
+                    .iter()
+                    .filter_map(|mapping| {
+                        db.signals.get(&mapping.signal_name).map(|signal| {
+                            let raw = signal.decode(&frame.data, mapping.offset);
+                            DecodedSignal {
+                                name: signal.name.clone(),
+                                raw: raw as u64,
+                                value: raw as f64,
+                                unit: String::new(),
+                                text: None,
+                                decimals: 0,
+                            }
+                        })
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default()
+    } else {
+        dbc_channels
+            .get(&frame.channel)
+            .and_then(|db| db.messages.get(&frame.id))
+            .map(|message| {
+                message
+                    .active_signals(&frame.data)
+                    .map(|signal| {
+                        let decoded = signal.decode(&frame.data);
+                        DecodedSignal {
+                            name: signal.name.clone(),
+                            raw: decoded.raw,
+                            value: decoded.physical,
+                            unit: signal.unit.clone(),
+                            text: decoded.text.map(str::to_string),
+                            decimals: signal.decimals(),
+                        }
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default()
+    };
+    signals.sort_by(|a, b| a.name.cmp(&b.name));
+    signals
+}
+
+/// Values of one signal over the whole log as `(timestamp_ns, value)` pairs
+///
+/// Only frames with the given channel and ID are decoded. `messages` may be
+/// the whole log or just that ID's frames, e.g. looked up in a [`blf::IdIndex`].
+pub fn signal_series<'a>(
+    messages: impl IntoIterator<Item = &'a LogObject>,
+    channel: u16,
+    id: u32,
+    signal_name: &str,
+    dbc_channels: &HashMap<u16, DbcDatabase>,
+    ldf_channels: &HashMap<u16, LdfDatabase>,
+    offsets: &TimeOffsets,
+) -> Vec<(u64, f64)> {
+    messages
+        .into_iter()
+        .filter_map(|msg| ExportFrame::with_offsets(msg, offsets))
+        .filter(|frame| frame.channel == channel && frame.id == id)
+        .filter_map(|frame| {
+            decode_frame(&frame, dbc_channels, ldf_channels)
+                .into_iter()
+                .find(|signal| signal.name == signal_name)
+                .map(|signal| (frame.timestamp_ns, signal.value))
+        })
+        .collect()
+}
+
+/// Two-column CSV of a signal series, ready to paste into a spreadsheet
+pub fn format_series_csv(signal_name: &str, unit: &str, points: &[(u64, f64)]) -> String {
+    let column = if unit.is_empty() {
+        signal_name.to_string()
+    } else {
+        format!("{} [{}]", signal_name, unit)
+    };
+    let mut out = format!("time_s,{}\n", csv_escape(&column));
+    for (timestamp_ns, value) in points {
+        out.push_str(&format!(
+            "{:.6},{}\n",
+            *timestamp_ns as f64 / 1_000_000_000.0,
+            value
+        ));
+    }
+    out
+}
+
+/// Decoded signals in one field, e.g. "Gear=Reverse; Speed=12.5km/h"
+pub fn signals_text(signals: &[DecodedSignal]) -> String {
+    signals
+        .iter()
+        .map(|s| match &s.text {
+            Some(text) => format!("{}={}", s.name, text),
+            None => format!("{}={}{}", s.name, s.value, s.unit),
+        })
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+pub fn csv_escape(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+fn json_escape(text: &str) -> String {
+    serde_json::to_string(text).unwrap_or_else(|_| "\"\"".to_string())
+}
+
+/// Text written before the first row; ASC dates its header with `start_time`
+pub fn format_header(
+    format: ExportFormat,
+    decoded: bool,
+    start_time: Option<chrono::NaiveDateTime>,
+) -> String {
+    match format {
+        ExportFormat::Asc => asc_header(start_time),
+        ExportFormat::Csv if decoded => "seq,time_s,channel,type,id,dlc,data,signals\n".to_string(),
+        ExportFormat::Csv => "seq,time_s,channel,type,id,dlc,data\n".to_string(),
+        ExportFormat::Json => "[\n".to_string(),
+        _ => String::new(),
+    }
+}
+
+/// Text written after the last row
+pub fn format_footer(format: ExportFormat, wrote_rows: bool) -> String {
+    match format {
+        ExportFormat::Json if wrote_rows => "\n]\n".to_string(),
+        ExportFormat::Json => "]\n".to_string(),
+        ExportFormat::Asc => ASC_FOOTER.to_string(),
+        _ => String::new(),
+    }
+}
+
+/// Format a chunk of messages
+///
+/// `first_row` tells whether nothing has been written yet (JSON needs it to
+/// place separators). Returns the text and the number of rows written.
+pub fn format_rows(
+    format: ExportFormat,
+    decoded: bool,
+    messages: &[NumberedMessage],
+    dbc_channels: &HashMap<u16, DbcDatabase>,
+    ldf_channels: &HashMap<u16, LdfDatabase>,
+    offsets: &TimeOffsets,
+    first_row: bool,
+) -> (String, usize) {
+    let mut out = String::new();
+    let mut rows = 0;
+
+    for (seq, msg, frame) in messages
+        .iter()
+        .filter_map(|(seq, msg)| Some((seq, msg, ExportFrame::with_offsets(msg, offsets)?)))
+    {
+        if format == ExportFormat::Asc {
+            // ASC has no column for decoded signals
+            if let Some(line) = format_asc_frame(msg, frame.timestamp_ns) {
+                out.push_str(&line);
+                rows += 1;
+            }
+            continue;
+        }
+        let signals = if decoded {
+            decode_frame(&frame, dbc_channels, ldf_channels)
+        } else {
+            Vec::new()
+        };
+        let time_s = format!("{:.6}", frame.timestamp_ns as f64 / 1_000_000_000.0);
+
+        match format {
+            ExportFormat::Csv => {
+                out.push_str(&format!(
+                    "{},{},{},{},{},{},{}",
+                    seq,
+                    time_s,
+                    frame.channel,
+                    frame.kind,
+                    hex_frame_id(frame.kind, frame.id),
+                    frame.data.len(),
+                    frame.data_hex()
+                ));
+                if decoded {
+                    out.push(',');
+                    out.push_str(&csv_escape(&signals_text(&signals)));
+                }
+                out.push('\n');
+            }
+            ExportFormat::Json => {
+                if !(first_row && rows == 0) {
+                    out.push_str(",\n");
+                }
+                out.push_str(&format!(
+                    "  {{\"seq\": {}, \"time_s\": {}, \"channel\": {}, \"type\": \"{}\", \"id\": {}, \"data\": \"{}\"",
+                    seq,
+                    time_s,
+                    frame.channel,
+                    frame.kind,
+                    frame.id,
+                    frame.data_hex()
+                ));
+                if decoded {
+                    let fields = signals
+                        .iter()
+                        .map(|s| {
+                            format!(
+                                "{{\"name\": {}, \"raw\": {}, \"value\": {}, \"unit\": {}, \"text\": {}}}",
+                                json_escape(&s.name),
+                                s.raw,
+                                if s.value.is_finite() { s.value.to_string() } else { "null".to_string() },
+                                json_escape(&s.unit),
+                                s.text.as_deref().map_or("null".to_string(), json_escape)
+                            )
+                        })
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    out.push_str(&format!(", \"signals\": [{}]", fields));
+                }
+                out.push('}');
+            }
+            _ => continue,
+        }
+        rows += 1;
+    }
+
+    (out, rows)
+}
+
+/// BLF export kept open across chunks
+///
+/// [`BlfExport::create`] writes a placeholder header dated with the
+/// measurement start, [`BlfExport::append`] writes the frames of a chunk with
+/// their corrected timestamps, and [`BlfExport::finish`] rewrites the header.
+/// LIN frames are written as `LinMessage` objects, `LinMessage2` included;
+/// objects the writer has no layout for are left out and reported by `finish`.
+pub struct BlfExport {
+    start_time: Option<chrono::NaiveDateTime>,
+    writer: Mutex<Option<BlfWriter<BufWriter<File>>>>,
+}
+
+impl BlfExport {
+    pub fn new(start_time: Option<chrono::NaiveDateTime>) -> Self {
+        Self {
+            start_time,
+            writer: Mutex::new(None),
+        }
+    }
+
+    pub fn create(&self, path: &Path) -> Result<(), String> {
+        let writer = BlfWriter::create(path, blf_start_time(self.start_time))
+            .map_err(|e| format!("{}: {}", path.display(), e))?;
+        *self.writer.lock().unwrap() = Some(writer);
+        Ok(())
+    }
+
+    /// Write the bus frames of `messages`; returns the number written
+    pub fn append(
+        &self,
+        messages: &[NumberedMessage],
+        offsets: &TimeOffsets,
+    ) -> Result<usize, String> {
+        let mut writer = self.writer.lock().unwrap();
+        let writer = writer.as_mut().ok_or("The BLF export was not created")?;
+        let mut rows = 0;
+        for (_, msg) in messages {
+            if ExportFrame::from_log_object(msg).is_none() {
+                continue;
+            }
+            let mut msg = match msg {
+                LogObject::LinMessage2(lin) => LogObject::LinMessage(lin_message(lin)),
+                msg => msg.clone(),
+            };
+            msg.set_timestamp(offsets.timestamp(&msg));
+            if writer.write_object(&msg).map_err(|e| e.to_string())? {
+                rows += 1;
+            }
+        }
+        Ok(rows)
+    }
+
+    /// Finish the file; returns what was left out, e.g. "2 LinMessage"
+    pub fn finish(&self) -> Result<Option<String>, String> {
+        let mut writer = self
+            .writer
+            .lock()
+            .unwrap()
+            .take()
+            .ok_or("The BLF export was not created")?;
+        writer.finish().map_err(|e| e.to_string())?;
+        Ok(skipped_objects(writer.skipped()))
+    }
+}
+
+/// A `LinMessage2` as the `LinMessage` the BLF writer has a layout for
+fn lin_message(msg: &LinMessage2) -> LinMessage {
+    LinMessage {
+        header: msg.header.clone(),
+        channel: msg.channel,
+        id: msg.id,
+        dlc: msg.dlc,
+        data: msg.data,
+        // LIN checksums are one byte
+        crc: msg.crc as u8,
+        dir: msg.dir,
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::handlers::test_support::can;
+
+    #[test]
+    fn test_blf_export_shifts_timestamps_and_writes_lin() {
+        let path = std::env::temp_dir().join(format!("canview_export_{}.blf", std::process::id()));
+        let mut lin = blf::LinMessage {
+            channel: 1,
+            id: 0x21,
+            dlc: 2,
+            data: [0xAA, 0x55, 0, 0, 0, 0, 0, 0],
+            ..Default::default()
+        };
+        lin.header.object_time_stamp = 1_500;
+        let mut lin2 = blf::LinMessage2 {
+            header: Default::default(),
+            channel: 2,
+            id: 0x22,
+            dlc: 1,
+            data: [0x11, 0, 0, 0, 0, 0, 0, 0],
+            crc: 0x3C,
+            dir: 1,
+            simulated: 0,
+            is_etf: 0,
+            resp_baudrate: None,
+            exact_header_baudrate: None,
+        };
+        lin2.header.object_time_stamp = 1_800;
+        let messages = numbered_messages(
+            &[
+                can(0x100).at(1_000).data([1; 8]).build(),
+                LogObject::LinMessage(lin),
+                LogObject::LinMessage2(lin2),
+                can(0x200).channel(2).at(2_000).data([2; 8]).build(),
+            ],
+            0..4,
+        );
+        let mut offsets = TimeOffsets::new();
+        offsets.set(2, 500);
+
+        let export = BlfExport::new(None);
+        export.create(&path).unwrap();
+        let rows: usize = messages
+            .chunks(2)
+            .map(|chunk| export.append(chunk, &offsets).unwrap())
+            .sum();
+        assert_eq!(rows, 4);
+        assert_eq!(export.finish().unwrap(), None);
+
+        let log = crate::handlers::read_log_file(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(log.file_stats.object_count, 4);
+        let frames: Vec<_> = log
+            .objects
+            .iter()
+            .filter_map(ExportFrame::from_log_object)
+            .map(|frame| (frame.kind, frame.id, frame.timestamp_ns, frame.data))
+            .collect();
+        assert_eq!(
+            frames,
+            vec![
+                ("CAN", 0x100, 1_000, vec![1; 8]),
+                ("LIN", 0x21, 1_500, vec![0xAA, 0x55]),
+                ("LIN", 0x22, 2_300, vec![0x11]),
+                ("CAN", 0x200, 2_500, vec![2; 8]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_time_range() {
+        assert_eq!(parse_time_range("1.5", " 3 "), Ok((1.5, 3.0)));
+        assert!(parse_time_range("abc", "3").is_err());
+        assert!(parse_time_range("5", "3").is_err());
+    }
+
+    #[test]
+    fn test_messages_in_time_range_is_inclusive() {
+        let messages = vec![
+            can(0x100).at(500_000_000).data([0; 8]).build(),
+            can(0x101).at(1_000_000_000).data([0; 8]).build(),
+            can(0x102).at(2_000_000_000).data([0; 8]).build(),
+        ];
+        let selected = messages_in_time_range(&messages, &TimeOffsets::new(), 1.0, 2.0);
+        // Numbers follow the position in the log, not in the range
+        assert_eq!(
+            selected.iter().map(|(seq, _)| *seq).collect::<Vec<_>>(),
+            vec![2, 3]
+        );
+        // Shifting channel 1 back by half a second moves the first message out
+        let mut offsets = TimeOffsets::new();
+        offsets.set(1, -500_000_000);
+        assert_eq!(
+            messages_in_time_range(&messages, &offsets, 1.0, 2.0).len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_csv_raw_rows() {
+        let messages = vec![(
+            7,
+            can(0x123)
+                .channel(2)
+                .at(1_500_000_000)
+                .data([1, 2, 3, 4, 5, 6, 7, 8])
+                .build(),
+        )];
+        let (text, rows) = format_rows(
+            ExportFormat::Csv,
+            false,
+            &messages,
+            &HashMap::new(),
+            &HashMap::new(),
+            &TimeOffsets::new(),
+            true,
+        );
+        assert_eq!(rows, 1);
+        assert_eq!(text, "7,1.500000,2,CAN,0x123,8,01 02 03 04 05 06 07 08\n");
+    }
+
+    #[test]
+    fn test_json_chunks_join_into_valid_document() {
+        let messages = vec![
+            can(1).data([0; 8]).build(),
+            can(2).at(1_000).data([0; 8]).build(),
+            can(3).at(2_000).data([0; 8]).build(),
+        ];
+        let first = numbered_messages(&messages, 0..1);
+        let second = numbered_messages(&messages, 1..3);
+        let offsets = TimeOffsets::new();
+        let (a, a_rows) = format_rows(
+            ExportFormat::Json,
+            true,
+            &first,
+            &HashMap::new(),
+            &HashMap::new(),
+            &offsets,
+            true,
+        );
+        let (b, _) = format_rows(
+            ExportFormat::Json,
+            true,
+            &second,
+            &HashMap::new(),
+            &HashMap::new(),
+            &offsets,
+            a_rows == 0,
+        );
+        let document = format!(
+            "{}{}{}{}",
+            format_header(ExportFormat::Json, true, None),
+            a,
+            b,
+            format_footer(ExportFormat::Json, true)
+        );
+        let parsed: serde_json::Value = serde_json::from_str(&document).unwrap();
+        assert_eq!(parsed.as_array().unwrap().len(), 3);
+        assert_eq!(parsed[2]["id"], 3);
+        assert_eq!(parsed[2]["seq"], 3);
+    }
+
+    #[test]
+    fn test_format_series_csv() {
+        let text = format_series_csv("Speed", "km/h", &[(0, 1.5), (250_000_000, 2.0)]);
+        assert_eq!(text, "time_s,Speed [km/h]\n0.000000,1.5\n0.250000,2\n");
+        assert_eq!(format_series_csv("a,b", "", &[]), "time_s,\"a,b\"\n");
+    }
+
+    #[test]
+    fn test_signal_series_matches_channel_and_id() {
+        let dbc = parser::dbc::DbcParser::new()
+            .parse(
+                "BO_ 256 Engine: 8 ECU\n SG_ Speed : 0|8@1+ (0.5,0) [0|127] \"km/h\" Vector__XXX\n",
+            )
+            .unwrap();
+        let dbc_channels = HashMap::from([(1, dbc)]);
+        let messages = vec![
+            can(0x100).data([10, 0, 0, 0, 0, 0, 0, 0]).build(),
+            can(0x100)
+                .channel(2)
+                .at(500)
+                .data([20, 0, 0, 0, 0, 0, 0, 0])
+                .build(),
+            can(0x101).at(750).data([30, 0, 0, 0, 0, 0, 0, 0]).build(),
+            can(0x100).at(1_000).data([40, 0, 0, 0, 0, 0, 0, 0]).build(),
+        ];
+        let offsets = TimeOffsets::new();
+        let series = signal_series(
+            &messages,
+            1,
+            0x100,
+            "Speed",
+            &dbc_channels,
+            &HashMap::new(),
+            &offsets,
+        );
+        assert_eq!(series, vec![(0, 5.0), (1_000, 20.0)]);
+        let series = signal_series(
+            &messages,
+            1,
+            0x100,
+            "Rpm",
+            &dbc_channels,
+            &HashMap::new(),
+            &offsets,
+        );
+        assert!(series.is_empty());
+    }
+
+    #[test]
+    fn test_decoded_rows_carry_raw_and_value_text() {
+        let dbc = parser::dbc::DbcParser::new()
+            .parse(
+                "BO_ 256 Engine: 8 ECU\n SG_ Gear : 0|8@1+ (1,0) [0|3] \"\" Vector__XXX\n\
+                 VAL_ 256 Gear 0 \"Park\" 1 \"Drive\" ;\n",
+            )
+            .unwrap();
+        let dbc_channels = HashMap::from([(1, dbc)]);
+        let messages = vec![(1, can(0x100).data([1, 0, 0, 0, 0, 0, 0, 0]).build())];
+        let offsets = TimeOffsets::new();
+
+        let (csv, _) = format_rows(
+            ExportFormat::Csv,
+            true,
+            &messages,
+            &dbc_channels,
+            &HashMap::new(),
+            &offsets,
+            true,
+        );
+        assert!(csv.ends_with(",Gear=Drive\n"));
+
+        let (json, _) = format_rows(
+            ExportFormat::Json,
+            true,
+            &messages,
+            &dbc_channels,
+            &HashMap::new(),
+            &offsets,
+            true,
+        );
+        let parsed: serde_json::Value = serde_json::from_str(json.trim()).unwrap();
+        assert_eq!(parsed["signals"][0]["raw"], 1);
+        assert_eq!(parsed["signals"][0]["text"], "Drive");
+    }
+
+    #[test]
+    fn test_csv_escape() {
+        assert_eq!(csv_escape("a=1"), "a=1");
+        assert_eq!(csv_escape("a,b"), "\"a,b\"");
+        assert_eq!(csv_escape("say \"hi\""), "\"say \"\"hi\"\"\"");
+    }
+}
@@ -0,0 +1,149 @@
+//! Export formats provided by plugins
+//!
+//! A plugin is an external converter declared in the configuration file.
+//! CanView exports the chosen messages as CSV or JSON to an intermediate
+//! file and runs the plugin to turn it into the proprietary format, so teams
+//! add formats by shipping a config entry and a tool rather than a fork.
+//!
+//! ```json
+//! "export_plugins": [{
+//!     "name": "MDF",
+//!     "extension": "mf4",
+//!     "command": ["csv2mdf", "--in", "{input}", "--out", "{output}"]
+//! }]
+//! ```
+
+use crate::models::preferences::ExportFormat;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// An entry of the export dialog's format row
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ExportChoice {
+    Format(ExportFormat),
+    /// Index into the configured plugins
+    Plugin(usize),
+}
+
+/// An export format registered by a plugin
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExportPlugin {
+    /// Name shown in the export dialog
+    pub name: String,
+    /// Extension of the files it writes, without the leading dot
+    pub extension: String,
+    /// Program and its arguments; `{input}` and `{output}` are replaced by the paths
+    pub command: Vec<String>,
+    /// Format of the intermediate file handed to the plugin, CSV or JSON
+    #[serde(default)]
+    pub input: ExportFormat,
+}
+
+impl ExportPlugin {
+    /// Why the plugin cannot run, if it cannot
+    pub fn validate(&self) -> Result<(), String> {
+        if self.name.trim().is_empty() {
+            return Err("Export plugin without a name".to_string());
+        }
+        if self.command.is_empty() {
+            return Err(format!("Export plugin {} has no command", self.name));
+        }
+        if !matches!(self.input, ExportFormat::Csv | ExportFormat::Json) {
+            return Err(format!(
+                "Export plugin {} takes {}; only CSV and JSON are handed to plugins",
+                self.name,
+                self.input.label()
+            ));
+        }
+        Ok(())
+    }
+
+    /// Where the intermediate export for `output` is written
+    pub fn intermediate_path(&self, output: &Path) -> PathBuf {
+        let name = output
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        std::env::temp_dir().join(format!(
+            "canview_{}_{}.{}",
+            std::process::id(),
+            name,
+            self.input.extension()
+        ))
+    }
+
+    /// Convert the intermediate export at `input` into `output`
+    pub fn run(&self, input: &Path, output: &Path) -> Result<(), String> {
+        self.validate()?;
+        let substitute = |arg: &String| {
+            arg.replace("{input}", &input.to_string_lossy())
+                .replace("{output}", &output.to_string_lossy())
+        };
+        let result = Command::new(&self.command[0])
+            .args(self.command[1..].iter().map(substitute))
+            .output()
+            .map_err(|e| format!("{}: {}: {}", self.name, self.command[0], e))?;
+        if !result.status.success() {
+            let stderr = String::from_utf8_lossy(&result.stderr);
+            let reason = stderr
+                .lines()
+                .rev()
+                .find(|line| !line.trim().is_empty())
+                .map_or_else(|| result.status.to_string(), str::to_string);
+            return Err(format!("{}: {}", self.name, reason));
+        }
+        if !output.exists() {
+            return Err(format!("{} wrote no {}", self.name, output.display()));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(unix)]
+    #[test]
+    fn test_export_plugin() {
+        let dir = std::env::temp_dir().join(format!("canview_plugin_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let plugin: ExportPlugin = serde_json::from_str(
+            r#"{"name": "Upper", "extension": "up",
+                "command": ["sh", "-c", "tr a-z A-Z < \"$0\" > \"$1\"", "{input}", "{output}"]}"#,
+        )
+        .unwrap();
+        assert_eq!(plugin.input, ExportFormat::Csv);
+
+        let output = dir.join("export.up");
+        let input = plugin.intermediate_path(&output);
+        assert!(input.to_string_lossy().ends_with("export.up.csv"));
+        std::fs::write(&input, "seq,id\n1,0x1a\n").unwrap();
+        plugin.run(&input, &output).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&output).unwrap(),
+            "SEQ,ID\n1,0X1A\n"
+        );
+
+        let failing = ExportPlugin {
+            command: vec![
+                "sh".into(),
+                "-c".into(),
+                "echo 'bad input' >&2; exit 3".into(),
+            ],
+            ..plugin.clone()
+        };
+        assert_eq!(
+            failing.run(&input, &dir.join("x.up")),
+            Err("Upper: bad input".to_string())
+        );
+        let blf = ExportPlugin {
+            input: ExportFormat::Blf,
+            ..plugin
+        };
+        assert!(blf.validate().is_err());
+        let _ = std::fs::remove_file(&input);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
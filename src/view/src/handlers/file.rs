@@ -2,52 +2,65 @@
 //!
 //! This module contains utility functions for file operations
 //! such as importing database files and opening BLF files.
+//!
+//! Pickers use `rfd::AsyncFileDialog` and must be awaited from a spawned
+//! task so the window keeps rendering while the native dialog is open.
 
 use std::path::PathBuf;
 
-/// Import a database file (DBC or LDF)
+/// File dialog filter (name, extension) for a database type
+///
+/// # Arguments
+/// * `db_type` - Either "dbc" or "ldf"
+pub fn database_filter(db_type: &str) -> Result<(&'static str, &'static str), String> {
+    match db_type {
+        "dbc" => Ok(("DBC Files", "dbc")),
+        "ldf" => Ok(("LDF Files", "ldf")),
+        _ => Err("Invalid database type".to_string()),
+    }
+}
+
+/// Pick a database file (DBC or LDF)
 ///
 /// # Arguments
 /// * `db_type` - Either "dbc" or "ldf"
 ///
 /// # Returns
 /// Result containing the file path, or an error message
-pub fn import_database_file(db_type: &str) -> Result<PathBuf, String> {
-    let filter_extension = match db_type {
-        "dbc" => "DBC Files",
-        "ldf" => "LDF Files",
-        _ => return Err("Invalid database type".to_string()),
-    };
-
-    let file_ext = match db_type {
-        "dbc" => "dbc",
-        "ldf" => "ldf",
-        _ => return Err("Invalid database type".to_string()),
-    };
-
-    if let Some(path) = rfd::FileDialog::new()
-        .add_filter(filter_extension, &[file_ext])
+pub async fn pick_database_file(db_type: &str) -> Result<PathBuf, String> {
+    let (filter_name, file_ext) = database_filter(db_type)?;
+
+    rfd::AsyncFileDialog::new()
+        .add_filter(filter_name, &[file_ext])
         .pick_file()
-    {
-        Ok(path)
-    } else {
-        Err("No file selected".to_string())
-    }
+        .await
+        .map(|file| file.path().to_path_buf())
+        .ok_or_else(|| "No file selected".to_string())
 }
 
-/// Import any database file (DBC or LDF)
+/// Pick any database file (DBC or LDF)
 ///
 /// # Returns
 /// Result containing the file path, or an error message
-pub fn import_any_database_file() -> Result<PathBuf, String> {
-    if let Some(path) = rfd::FileDialog::new()
+pub async fn pick_any_database_file() -> Result<PathBuf, String> {
+    rfd::AsyncFileDialog::new()
         .add_filter("Database Files", &["dbc", "ldf"])
         .pick_file()
-    {
-        Ok(path)
-    } else {
-        Err("No file selected".to_string())
-    }
+        .await
+        .map(|file| file.path().to_path_buf())
+        .ok_or_else(|| "No file selected".to_string())
+}
+
+/// Pick a folder of database files
+///
+/// # Returns
+/// Result containing the folder path, or an error message
+pub async fn pick_database_folder() -> Result<PathBuf, String> {
+    rfd::AsyncFileDialog::new()
+        .pick_folder()
+        .await
+        .map(|folder| folder.path().to_path_buf())
+        .ok_or_else(|| "No folder selected".to_string())
 }
 
 #[cfg(test)]
@@ -55,8 +68,14 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_import_database_file_invalid_type() {
-        let result = import_database_file("invalid");
+    fn test_database_filter_invalid_type() {
+        let result = database_filter("invalid");
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_database_filter_known_types() {
+        assert_eq!(database_filter("dbc"), Ok(("DBC Files", "dbc")));
+        assert_eq!(database_filter("ldf"), Ok(("LDF Files", "ldf")));
+    }
 }
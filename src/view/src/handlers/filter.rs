@@ -1,10 +1,86 @@
 //! Filter handling utilities
 //!
 //! This module contains utility functions for filtering operations
-//! such as extracting unique channels from message lists.
+//! such as extracting unique channels from message lists. Channels and IDs
+//! come from `LogObject::channel` and `LogObject::id`, which cover every
+//! object type. [`FilterState`] holds the filters of the log view and
+//! [`FilteredRows`] the rows passing them, so the view does not filter the
+//! whole log on every frame.
 
-use blf::LogObject;
-use std::collections::HashSet;
+use crate::handlers::{LogSources, TreeFilter};
+use blf::{IdIndex, LogObject};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashSet};
+use std::rc::Rc;
+
+/// Message category used by the TYPE column filter
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum MessageKind {
+    Can,
+    CanFd,
+    Lin,
+    Error,
+    FlexRay,
+    Ethernet,
+    Other,
+}
+
+impl MessageKind {
+    /// All categories, in the order the filter dropdown lists them
+    pub const ALL: [MessageKind; 7] = [
+        MessageKind::Can,
+        MessageKind::CanFd,
+        MessageKind::Lin,
+        MessageKind::Error,
+        MessageKind::FlexRay,
+        MessageKind::Ethernet,
+        MessageKind::Other,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            MessageKind::Can => "CAN",
+            MessageKind::CanFd => "CAN FD",
+            MessageKind::Lin => "LIN",
+            MessageKind::Error => "Error",
+            MessageKind::FlexRay => "FlexRay",
+            MessageKind::Ethernet => "Ethernet",
+            MessageKind::Other => "Other",
+        }
+    }
+
+    /// Category of a log object
+    ///
+    /// Error and overload frames of every bus count as `Error`, so an
+    /// error-only view needs a single selection.
+    pub fn of(msg: &LogObject) -> Self {
+        match msg {
+            LogObject::CanMessage(_) | LogObject::CanMessage2(_) => MessageKind::Can,
+            LogObject::CanFdMessage(_) | LogObject::CanFdMessage64(_) => MessageKind::CanFd,
+            LogObject::LinMessage(_) | LogObject::LinMessage2(_) => MessageKind::Lin,
+            LogObject::CanErrorFrame(_)
+            | LogObject::CanOverloadFrame(_)
+            | LogObject::CanDriverError(_)
+            | LogObject::LinCrcError(_)
+            | LogObject::LinReceiveError(_)
+            | LogObject::LinSendError(_)
+            | LogObject::LinSlaveTimeout(_)
+            | LogObject::LinSyncError(_)
+            | LogObject::FlexRayVFrError(_) => MessageKind::Error,
+            LogObject::FlexRayData(_)
+            | LogObject::FlexRaySync(_)
+            | LogObject::FlexRayV6Message(_)
+            | LogObject::FlexRayV6StartCycleEvent(_)
+            | LogObject::FlexRayStatusEvent(_)
+            | LogObject::FlexRayVFrStatus(_)
+            | LogObject::FlexRayVFrStartCycle(_)
+            | LogObject::FlexRayVFrReceiveMsg(_)
+            | LogObject::FlexRayVFrReceiveMsgEx(_) => MessageKind::FlexRay,
+            LogObject::EthernetFrame(_) => MessageKind::Ethernet,
+            _ => MessageKind::Other,
+        }
+    }
+}
 
 /// Extract unique channel numbers from a list of log objects
 ///
@@ -23,37 +99,28 @@ use std::collections::HashSet;
 /// // Returns: vec![1, 2, 3]
 /// ```
 pub fn extract_unique_channels(messages: &[LogObject]) -> Vec<u16> {
-    let mut unique_channels = HashSet::new();
-
-    for msg in messages.iter() {
-        match msg {
-            LogObject::CanMessage(m) => {
-                unique_channels.insert(m.channel);
-            }
-            LogObject::CanMessage2(m) => {
-                unique_channels.insert(m.channel);
-            }
-            LogObject::CanFdMessage(m) => {
-                unique_channels.insert(m.channel);
-            }
-            LogObject::CanFdMessage64(m) => {
-                unique_channels.insert(m.channel as u16);
-            }
-            LogObject::LinMessage(m) => {
-                unique_channels.insert(m.channel);
-            }
-            LogObject::LinMessage2(_) => {
-                // LIN2 doesn't have channel info
-            }
-            _ => {}
-        }
-    }
+    let unique_channels: HashSet<u16> = messages.iter().filter_map(LogObject::channel).collect();
 
     let mut channel_list: Vec<u16> = unique_channels.into_iter().collect();
     channel_list.sort();
     channel_list
 }
 
+/// Extract unique frame IDs from a list of log objects
+///
+/// # Arguments
+/// * `messages` - Slice of log objects to process
+///
+/// # Returns
+/// A sorted vector of unique frame IDs
+pub fn extract_unique_ids(messages: &[LogObject]) -> Vec<u32> {
+    let unique_ids: HashSet<u32> = messages.iter().filter_map(LogObject::id).collect();
+
+    let mut id_list: Vec<u32> = unique_ids.into_iter().collect();
+    id_list.sort();
+    id_list
+}
+
 /// Filter messages by channel number
 ///
 /// # Arguments
@@ -79,23 +146,263 @@ pub fn filter_by_channel(
     messages
         .iter()
         .filter(|msg| {
-            match msg {
-                LogObject::CanMessage(m) => selected_channels.contains(&m.channel),
-                LogObject::CanMessage2(m) => selected_channels.contains(&m.channel),
-                LogObject::CanFdMessage(m) => selected_channels.contains(&m.channel),
-                LogObject::CanFdMessage64(m) => selected_channels.contains(&(m.channel as u16)),
-                LogObject::LinMessage(m) => selected_channels.contains(&m.channel),
-                LogObject::LinMessage2(_) => true, // Include LIN2 if any filter is active
-                _ => false,
-            }
+            msg.channel()
+                .is_some_and(|channel| selected_channels.contains(&channel))
         })
         .cloned()
         .collect()
 }
 
+/// Check a message against the log view's ID and channel filters
+///
+/// With no filter active every object matches. An ID filter only matches
+/// objects carrying a frame ID, a channel filter only objects on a channel.
+///
+/// # Arguments
+/// * `msg` - Log object to test
+/// * `id_filter` - Required message ID, if any
+/// * `channel_filter` - Required channel, if any
+pub fn matches_id_and_channel(
+    msg: &LogObject,
+    id_filter: Option<u32>,
+    channel_filter: Option<u16>,
+) -> bool {
+    if id_filter.is_none() && channel_filter.is_none() {
+        return true;
+    }
+
+    id_filter.is_none_or(|filter| msg.id() == Some(filter))
+        && channel_filter.is_none_or(|filter| msg.channel() == Some(filter))
+}
+
+/// Log view column that a quick filter can be taken from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FilterColumn {
+    Channel,
+    Type,
+    Id,
+}
+
+/// One active filter, shown as a breadcrumb chip above the log view
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ActiveFilter {
+    Id(u32),
+    Channel(u16),
+    Type(MessageKind),
+    /// Source file of a merged log
+    File(u16),
+}
+
+impl ActiveFilter {
+    /// Filter on the value `msg` shows in `column`, if it has one
+    pub fn from_cell(msg: &LogObject, column: FilterColumn) -> Option<Self> {
+        match column {
+            FilterColumn::Channel => msg.channel().map(ActiveFilter::Channel),
+            FilterColumn::Type => Some(ActiveFilter::Type(MessageKind::of(msg))),
+            FilterColumn::Id => msg.id().map(ActiveFilter::Id),
+        }
+    }
+
+    /// Chip text, with the ID in the log view's current base
+    pub fn label(&self, decimal: bool) -> String {
+        match self {
+            ActiveFilter::Id(id) if decimal => format!("ID: {}", id),
+            ActiveFilter::Id(id) => format!("ID: 0x{:X}", id),
+            ActiveFilter::Channel(channel) => format!("CH: {}", channel),
+            ActiveFilter::Type(kind) => format!("TYPE: {}", kind.label()),
+            ActiveFilter::File(source) => format!("FILE: #{}", source + 1),
+        }
+    }
+}
+
+/// Active filters in breadcrumb order: ID, channel, then each selected type
+pub fn active_filters(
+    id_filter: Option<u32>,
+    channel_filter: Option<u16>,
+    type_filter: &BTreeSet<MessageKind>,
+) -> Vec<ActiveFilter> {
+    id_filter
+        .map(ActiveFilter::Id)
+        .into_iter()
+        .chain(channel_filter.map(ActiveFilter::Channel))
+        .chain(type_filter.iter().copied().map(ActiveFilter::Type))
+        .collect()
+}
+
+/// Check a message against the TYPE filter; an empty selection matches all
+pub fn matches_type(msg: &LogObject, type_filter: &BTreeSet<MessageKind>) -> bool {
+    type_filter.is_empty() || type_filter.contains(&MessageKind::of(msg))
+}
+
+/// The ID, channel, type, file and tree filters of the log view
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FilterState {
+    pub id: Option<u32>,
+    pub channel: Option<u16>,
+    /// Empty = all types
+    pub types: BTreeSet<MessageKind>,
+    /// Source files of a merged log; empty = all files
+    pub files: BTreeSet<u16>,
+    /// Unchecked nodes of the channel tree sidebar
+    pub tree: TreeFilter,
+}
+
+impl FilterState {
+    /// Whether `msg` passes every filter but the file filter
+    pub fn matches(&self, msg: &LogObject) -> bool {
+        matches_id_and_channel(msg, self.id, self.channel)
+            && matches_type(msg, &self.types)
+            && self.tree.matches(msg)
+    }
+
+    /// Whether the object at `position`, read from one of `sources`, passes the file filter
+    pub fn matches_file(&self, position: usize, sources: &LogSources) -> bool {
+        self.files.is_empty() || self.files.contains(&sources.tag(position))
+    }
+
+    /// Positions in `messages` passing the filters; `index` lists the frames by
+    /// ID and `sources` the file of each
+    pub fn indices(
+        &self,
+        messages: &[LogObject],
+        index: &IdIndex,
+        sources: &LogSources,
+    ) -> Vec<usize> {
+        let passes = |position: usize, msg: &LogObject| {
+            self.matches(msg) && self.matches_file(position, sources)
+        };
+        if let Some(id) = self.id {
+            // Only the frames of that ID need checking
+            return index
+                .matching(self.channel, Some(id))
+                .into_iter()
+                .filter(|&position| {
+                    messages
+                        .get(position)
+                        .is_some_and(|msg| passes(position, msg))
+                })
+                .collect();
+        }
+        messages
+            .iter()
+            .enumerate()
+            .filter(|&(position, msg)| passes(position, msg))
+            .map(|(position, _)| position)
+            .collect()
+    }
+
+    /// Narrow by one filter, replacing any filter on the same column
+    pub fn apply(&mut self, filter: ActiveFilter) {
+        match filter {
+            ActiveFilter::Id(id) => self.id = Some(id),
+            ActiveFilter::Channel(channel) => self.channel = Some(channel),
+            ActiveFilter::Type(kind) => self.types = BTreeSet::from([kind]),
+            ActiveFilter::File(source) => self.files = BTreeSet::from([source]),
+        }
+    }
+
+    /// Drop one filter
+    pub fn remove(&mut self, filter: ActiveFilter) {
+        match filter {
+            ActiveFilter::Id(_) => self.id = None,
+            ActiveFilter::Channel(_) => self.channel = None,
+            ActiveFilter::Type(kind) => {
+                self.types.remove(&kind);
+            }
+            ActiveFilter::File(source) => {
+                self.files.remove(&source);
+            }
+        }
+    }
+
+    /// Add or remove one type from the TYPE filter; `None` shows all types
+    pub fn toggle_type(&mut self, kind: Option<MessageKind>) {
+        match kind {
+            None => self.types.clear(),
+            Some(kind) => {
+                if !self.types.remove(&kind) {
+                    self.types.insert(kind);
+                }
+            }
+        }
+    }
+
+    /// Add or remove one file from the file filter
+    pub fn toggle_file(&mut self, source: u16) {
+        if !self.files.remove(&source) {
+            self.files.insert(source);
+        }
+    }
+
+    /// Active ID / channel / type / file filters in breadcrumb order
+    pub fn active(&self) -> Vec<ActiveFilter> {
+        let mut filters = active_filters(self.id, self.channel, &self.types);
+        filters.extend(self.files.iter().copied().map(ActiveFilter::File));
+        filters
+    }
+}
+
+/// Rows of the log passing the filters, kept until the log or the filters change
+#[derive(Debug, Default)]
+pub struct FilteredRows {
+    filters: FilterState,
+    /// Messages the rows were taken from
+    len: usize,
+    rows: Option<Rc<Vec<usize>>>,
+    /// Counts the times the whole log was refiltered
+    generation: u64,
+}
+
+impl FilteredRows {
+    /// Positions in `messages` passing `filters`
+    ///
+    /// Messages appended since the last call, as in live mode, are filtered
+    /// on their own; anything else refilters the whole log.
+    pub fn get(
+        &mut self,
+        filters: &FilterState,
+        messages: &[LogObject],
+        index: &IdIndex,
+        sources: &LogSources,
+    ) -> Rc<Vec<usize>> {
+        match &mut self.rows {
+            Some(rows) if self.filters == *filters && self.len <= messages.len() => {
+                if self.len < messages.len() {
+                    let added = (self.len..messages.len()).filter(|&position| {
+                        filters.matches(&messages[position])
+                            && filters.matches_file(position, sources)
+                    });
+                    Rc::make_mut(rows).extend(added);
+                    self.len = messages.len();
+                }
+                rows.clone()
+            }
+            _ => {
+                let rows = Rc::new(filters.indices(messages, index, sources));
+                self.generation += 1;
+                self.filters = filters.clone();
+                self.len = messages.len();
+                self.rows = Some(rows.clone());
+                rows
+            }
+        }
+    }
+
+    /// Forget the rows, once the messages were replaced
+    pub fn clear(&mut self) {
+        self.rows = None;
+    }
+
+    /// Changes whenever the rows were refiltered rather than appended to
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::handlers::test_support::can;
 
     #[test]
     fn test_extract_unique_channels_empty() {
@@ -104,6 +411,160 @@ mod tests {
         assert_eq!(channels, vec![]);
     }
 
+    #[test]
+    fn test_matches_id_and_channel() {
+        let msg = LogObject::CanMessage(blf::CanMessage {
+            id: 0x100,
+            channel: 2,
+            ..Default::default()
+        });
+        assert!(matches_id_and_channel(&msg, None, None));
+        assert!(matches_id_and_channel(&msg, Some(0x100), None));
+        assert!(matches_id_and_channel(&msg, Some(0x100), Some(2)));
+        assert!(!matches_id_and_channel(&msg, Some(0x101), Some(2)));
+        assert!(!matches_id_and_channel(&msg, None, Some(1)));
+    }
+
+    #[test]
+    fn test_channel_filter_covers_error_frames() {
+        let messages = vec![
+            LogObject::CanMessage(blf::CanMessage {
+                id: 0x100,
+                channel: 1,
+                ..Default::default()
+            }),
+            LogObject::CanErrorFrame(blf::CanErrorFrame {
+                channel: 2,
+                ..Default::default()
+            }),
+        ];
+        assert_eq!(extract_unique_channels(&messages), vec![1, 2]);
+        assert_eq!(filter_by_channel(&messages, &HashSet::from([2])).len(), 1);
+        assert!(matches_id_and_channel(&messages[1], None, Some(2)));
+        assert!(!matches_id_and_channel(&messages[1], Some(0x100), Some(2)));
+    }
+
+    #[test]
+    fn test_matches_type() {
+        let can = LogObject::CanMessage(blf::CanMessage::default());
+        let error = LogObject::CanErrorFrame(blf::CanErrorFrame::default());
+        let errors_only = BTreeSet::from([MessageKind::Error]);
+        assert!(matches_type(&can, &BTreeSet::new()));
+        assert!(!matches_type(&can, &errors_only));
+        assert!(matches_type(&error, &errors_only));
+        assert!(matches_type(
+            &can,
+            &BTreeSet::from([MessageKind::Can, MessageKind::Error])
+        ));
+    }
+
+    #[test]
+    fn test_quick_filter_from_cell() {
+        let msg = LogObject::CanMessage2(blf::CanMessage2 {
+            id: 0x1AB,
+            channel: 3,
+            ..Default::default()
+        });
+        assert_eq!(
+            ActiveFilter::from_cell(&msg, FilterColumn::Id),
+            Some(ActiveFilter::Id(0x1AB))
+        );
+        assert_eq!(
+            ActiveFilter::from_cell(&msg, FilterColumn::Channel),
+            Some(ActiveFilter::Channel(3))
+        );
+        assert_eq!(
+            ActiveFilter::from_cell(&msg, FilterColumn::Type),
+            Some(ActiveFilter::Type(MessageKind::Can))
+        );
+
+        let error = LogObject::CanErrorFrame(blf::CanErrorFrame::default());
+        assert_eq!(ActiveFilter::from_cell(&error, FilterColumn::Id), None);
+    }
+
+    #[test]
+    fn test_active_filters_order_and_labels() {
+        let types = BTreeSet::from([MessageKind::Error, MessageKind::Can]);
+        let filters = active_filters(Some(0x10), Some(1), &types);
+        let labels: Vec<String> = filters.iter().map(|f| f.label(false)).collect();
+        assert_eq!(labels, ["ID: 0x10", "CH: 1", "TYPE: CAN", "TYPE: Error"]);
+        assert_eq!(filters[0].label(true), "ID: 16");
+        assert!(active_filters(None, None, &BTreeSet::new()).is_empty());
+    }
+
+    #[test]
+    fn test_filter_state() {
+        let messages = vec![
+            can(0x100).build(),
+            can(0x100).channel(2).build(),
+            LogObject::CanErrorFrame(blf::CanErrorFrame::default()),
+            can(0x200).build(),
+        ];
+        let index = IdIndex::from_objects(&messages);
+        let mut sources = LogSources::default();
+        let mut filters = FilterState::default();
+        assert_eq!(filters.indices(&messages, &index, &sources), [0, 1, 2, 3]);
+
+        filters.apply(ActiveFilter::Id(0x100));
+        assert_eq!(filters.indices(&messages, &index, &sources), [0, 1]);
+        filters.apply(ActiveFilter::Channel(2));
+        assert_eq!(filters.indices(&messages, &index, &sources), [1]);
+        assert_eq!(
+            filters.active(),
+            [ActiveFilter::Id(0x100), ActiveFilter::Channel(2)]
+        );
+        filters.remove(ActiveFilter::Id(0x100));
+        filters.remove(ActiveFilter::Channel(2));
+
+        filters.toggle_type(Some(MessageKind::Error));
+        assert_eq!(filters.indices(&messages, &index, &sources), [2]);
+        filters.toggle_type(Some(MessageKind::Can));
+        assert_eq!(filters.indices(&messages, &index, &sources), [0, 1, 2, 3]);
+        filters.apply(ActiveFilter::Type(MessageKind::Can));
+        assert_eq!(filters.indices(&messages, &index, &sources), [0, 1, 3]);
+        filters.toggle_type(None);
+        assert_eq!(filters, FilterState::default());
+
+        // Files of a merged log
+        sources.tags = vec![0, 1, 1, 0];
+        filters.toggle_file(1);
+        assert_eq!(filters.indices(&messages, &index, &sources), [1, 2]);
+        assert_eq!(filters.active(), [ActiveFilter::File(1)]);
+        filters.apply(ActiveFilter::Id(0x100));
+        assert_eq!(filters.indices(&messages, &index, &sources), [1]);
+        filters.remove(ActiveFilter::Id(0x100));
+        filters.toggle_file(1);
+        assert_eq!(filters, FilterState::default());
+    }
+
+    #[test]
+    fn test_filtered_rows_follow_appended_messages() {
+        let on = |channel| can(0).channel(channel).build();
+        let mut messages = vec![on(1), on(2), on(1)];
+        let sources = LogSources::default();
+        let mut filters = FilterState::default();
+        filters.apply(ActiveFilter::Channel(1));
+        let mut cache = FilteredRows::default();
+        let index = IdIndex::from_objects(&messages);
+        assert_eq!(*cache.get(&filters, &messages, &index, &sources), [0, 2]);
+
+        messages.extend([on(2), on(1)]);
+        let index = IdIndex::from_objects(&messages);
+        assert_eq!(*cache.get(&filters, &messages, &index, &sources), [0, 2, 4]);
+
+        filters.apply(ActiveFilter::Channel(2));
+        assert_eq!(*cache.get(&filters, &messages, &index, &sources), [1, 3]);
+
+        // A longer log replacing this one looks like a grown one until cleared
+        messages = vec![on(2); 6];
+        let index = IdIndex::from_objects(&messages);
+        cache.clear();
+        assert_eq!(
+            *cache.get(&filters, &messages, &index, &sources),
+            [0, 1, 2, 3, 4, 5]
+        );
+    }
+
     #[test]
     fn test_filter_by_channel_empty_selection() {
         let messages: Vec<LogObject> = vec![];
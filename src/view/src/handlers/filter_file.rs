@@ -0,0 +1,188 @@
+//! Shareable filter files
+//!
+//! A `.cvfilter` file holds the filters of the log view, the time span it
+//! showed and the log they were set on, so a teammate opening the same log
+//! can apply it and see the same rows. The log is told by its file names,
+//! object count and measurement start rather than its path, which differs
+//! from one machine to the next. Files of a merged log are listed by name
+//! for the same reason.
+
+use crate::handlers::{FilterState, LogSources, MessageKind, TreeFilter};
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::path::Path;
+
+/// Extension of filter files
+pub const FILTER_FILE_EXTENSION: &str = "cvfilter";
+
+/// The log a filter file was made on
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LogReference {
+    /// File names joined by " + " for a merged log; empty for a live capture
+    pub name: String,
+    pub objects: u64,
+    /// Measurement start, "2025-10-17 12:00:05.000"
+    pub start_time: Option<String>,
+}
+
+impl LogReference {
+    pub fn new(sources: &LogSources, objects: usize, start_time: Option<NaiveDateTime>) -> Self {
+        Self {
+            name: (0..sources.files.len() as u16)
+                .map(|source| sources.name(source))
+                .collect::<Vec<_>>()
+                .join(" + "),
+            objects: objects as u64,
+            start_time: start_time.map(|start| start.format("%Y-%m-%d %H:%M:%S%.3f").to_string()),
+        }
+    }
+
+    /// How this log differs from the `loaded` one; empty when they are the same
+    pub fn differences(&self, loaded: &LogReference) -> Vec<String> {
+        let mut differences = Vec::new();
+        if self.name != loaded.name {
+            differences.push(format!("file {}", self.name));
+        }
+        if self.objects != loaded.objects {
+            differences.push(format!("{} objects", self.objects));
+        }
+        if self.start_time != loaded.start_time {
+            let start = self.start_time.as_deref().unwrap_or("unknown");
+            differences.push(format!("start {}", start));
+        }
+        differences
+    }
+}
+
+/// Contents of a filter file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FilterFile {
+    /// Program and version that wrote the file
+    pub tool: String,
+    pub log: LogReference,
+    pub id: Option<u32>,
+    pub channel: Option<u16>,
+    /// Empty = all types
+    pub types: BTreeSet<MessageKind>,
+    /// Unchecked nodes of the channel tree
+    pub tree: TreeFilter,
+    /// Names of the shown files of a merged log; empty = all files
+    pub files: Vec<String>,
+    /// First and last timestamp on screen, in ns as the log counts them
+    pub time_range_ns: Option<(u64, u64)>,
+}
+
+impl FilterFile {
+    pub fn new(
+        log: LogReference,
+        filters: &FilterState,
+        sources: &LogSources,
+        time_range_ns: Option<(u64, u64)>,
+    ) -> Self {
+        Self {
+            tool: format!("canview {}", env!("CARGO_PKG_VERSION")),
+            log,
+            id: filters.id,
+            channel: filters.channel,
+            types: filters.types.clone(),
+            tree: filters.tree.clone(),
+            files: filters
+                .files
+                .iter()
+                .map(|&source| sources.name(source))
+                .collect(),
+            time_range_ns,
+        }
+    }
+
+    /// The filters for a log read from `sources`; files it does not have are dropped
+    pub fn filter_state(&self, sources: &LogSources) -> FilterState {
+        FilterState {
+            id: self.id,
+            channel: self.channel,
+            types: self.types.clone(),
+            tree: self.tree.clone(),
+            files: (0..sources.files.len() as u16)
+                .filter(|&source| self.files.contains(&sources.name(source)))
+                .collect(),
+        }
+    }
+}
+
+/// Write `file` to `path` as JSON
+pub fn write_filter_file(path: &Path, file: &FilterFile) -> Result<(), String> {
+    let text = serde_json::to_string_pretty(file).map_err(|e| e.to_string())?;
+    std::fs::write(path, text + "\n").map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+/// Read a filter file written by [`write_filter_file`]
+pub fn read_filter_file(path: &Path) -> Result<FilterFile, String> {
+    let text = std::fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    serde_json::from_str(&text).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::handlers::{ActiveFilter, ChannelTree, TreeNode};
+    use blf::LogObject;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_filter_file_round_trip() {
+        let messages = vec![
+            LogObject::CanMessage(blf::CanMessage {
+                channel: 1,
+                id: 0x100,
+                ..Default::default()
+            }),
+            LogObject::CanMessage(blf::CanMessage {
+                channel: 2,
+                id: 0x200,
+                ..Default::default()
+            }),
+        ];
+        let sources = LogSources {
+            files: vec![PathBuf::from("/logs/a.blf"), PathBuf::from("/logs/b.blf")],
+            tags: vec![0, 1],
+        };
+        let mut filters = FilterState::default();
+        filters.apply(ActiveFilter::Channel(1));
+        filters.toggle_type(Some(MessageKind::Can));
+        filters.toggle_file(1);
+        let tree = ChannelTree::build(&messages);
+        filters
+            .tree
+            .toggle(TreeNode::Channel(crate::handlers::Bus::Can, 2), &tree);
+        let start = chrono::NaiveDate::from_ymd_opt(2025, 10, 17)
+            .unwrap()
+            .and_hms_opt(12, 0, 5)
+            .unwrap();
+        let log = LogReference::new(&sources, messages.len(), Some(start));
+        assert_eq!(log.name, "a.blf + b.blf");
+
+        let path = std::env::temp_dir().join(format!("canview_{}.cvfilter", std::process::id()));
+        let file = FilterFile::new(log.clone(), &filters, &sources, Some((1_000, 2_000)));
+        write_filter_file(&path, &file).unwrap();
+        let read = read_filter_file(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(read, file);
+        assert_eq!(read.files, ["b.blf"]);
+        assert_eq!(read.filter_state(&sources), filters);
+        assert!(read.log.differences(&log).is_empty());
+
+        // Applied onto another log: the missing file is dropped and the log told apart
+        let other = LogSources::single(Path::new("/home/me/b.blf"));
+        assert_eq!(read.filter_state(&other).files, BTreeSet::from([0]));
+        let loaded = LogReference::new(&other, 5, None);
+        assert_eq!(
+            read.log.differences(&loaded),
+            [
+                "file a.blf + b.blf",
+                "2 objects",
+                "start 2025-10-17 12:00:05.000"
+            ]
+        );
+    }
+}
@@ -0,0 +1,128 @@
+//! Fixed ("overwrite") view of the log
+//!
+//! Instead of listing every frame in time order, the fixed view gives each
+//! ID one row that shows its latest frame, the time since the frame before
+//! and how many frames it had, the way CANoe's trace window watches
+//! periodic traffic. [`FixedRows`] is built from the filtered rows and, like
+//! them, follows live traffic by taking only the rows added since.
+
+use crate::handlers::Bus;
+use blf::LogObject;
+use std::collections::BTreeMap;
+
+/// One ID of the fixed view
+#[derive(Debug, Clone, PartialEq)]
+pub struct FixedRow {
+    /// Position of the latest frame in the log
+    pub index: usize,
+    pub count: usize,
+    /// Time between the latest frame and the one before
+    pub cycle_ns: Option<u64>,
+}
+
+/// IDs of the filtered rows with their latest frames, in bus, channel and ID order
+///
+/// Objects without a channel or an ID, like error frames, have no row.
+#[derive(Debug, Default)]
+pub struct FixedRows {
+    rows: BTreeMap<(Bus, u16, u32), FixedRow>,
+    /// Generation of the filtered rows taken in
+    generation: Option<u64>,
+    /// Filtered rows taken in
+    seen: usize,
+}
+
+impl FixedRows {
+    /// Take in the filtered `rows` of `messages`; `generation` tells whether they
+    /// were refiltered since the last call or only grew
+    pub fn update(&mut self, messages: &[LogObject], rows: &[usize], generation: u64) {
+        if self.generation != Some(generation) || rows.len() < self.seen {
+            self.rows.clear();
+            self.seen = 0;
+            self.generation = Some(generation);
+        }
+        for &index in &rows[self.seen..] {
+            let Some(msg) = messages.get(index) else {
+                continue;
+            };
+            let (Some(channel), Some(id)) = (msg.channel(), msg.id()) else {
+                continue;
+            };
+            let timestamp = msg.timestamp();
+            let row = self
+                .rows
+                .entry((Bus::of(msg), channel, id))
+                .or_insert(FixedRow {
+                    index,
+                    count: 0,
+                    cycle_ns: None,
+                });
+            if row.count > 0 {
+                let previous = messages
+                    .get(row.index)
+                    .map_or(timestamp, LogObject::timestamp);
+                row.cycle_ns = Some(timestamp.saturating_sub(previous));
+            }
+            row.index = index;
+            row.count += 1;
+        }
+        self.seen = rows.len();
+    }
+
+    /// The rows in display order
+    pub fn rows(&self) -> Vec<FixedRow> {
+        self.rows.values().cloned().collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::handlers::test_support::can;
+
+    #[test]
+    fn test_fixed_rows_keep_latest_frame_per_id() {
+        let mut messages = vec![
+            can(0x200).build(),
+            can(0x100).at(5_000_000).build(),
+            can(0x200).at(10_000_000).build(),
+            LogObject::CanErrorFrame(blf::CanErrorFrame::default()),
+        ];
+        let mut fixed = FixedRows::default();
+        fixed.update(&messages, &[0, 1, 2, 3], 1);
+        assert_eq!(
+            fixed.rows(),
+            [
+                FixedRow {
+                    index: 1,
+                    count: 1,
+                    cycle_ns: None
+                },
+                FixedRow {
+                    index: 2,
+                    count: 2,
+                    cycle_ns: Some(10_000_000)
+                },
+            ]
+        );
+
+        // Live traffic: only the new rows are taken in
+        messages.push(can(0x100).at(25_000_000).build());
+        fixed.update(&messages, &[0, 1, 2, 3, 4], 1);
+        assert_eq!(fixed.rows()[0].index, 4);
+        assert_eq!(fixed.rows()[0].cycle_ns, Some(20_000_000));
+
+        // Refiltered rows start over
+        fixed.update(&messages, &[2], 2);
+        assert_eq!(fixed.len(), 1);
+        assert_eq!(fixed.rows()[0].count, 1);
+    }
+}
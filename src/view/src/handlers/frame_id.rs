@@ -0,0 +1,66 @@
+//! Hex notation of frame IDs
+//!
+//! Standard CAN IDs are padded to 3 hex digits, extended ones to 8 and LIN
+//! IDs to 2, so the width alone tells the frame format apart: `0x123` is a
+//! standard ID, `0x00000123` the same number sent as an extended one. The
+//! log, the exports and the copied text all use these helpers.
+
+/// Flag set on the ID of extended frames by the capture and import backends
+const EXTENDED_ID_FLAG: u32 = 0x8000_0000;
+
+/// Whether `id` is a 29-bit ID: flagged, or too wide for 11 bits
+pub fn is_extended_id(id: u32) -> bool {
+    id & EXTENDED_ID_FLAG != 0 || id > 0x7FF
+}
+
+/// `id` without the extended flag
+pub fn bare_can_id(id: u32) -> u32 {
+    id & !EXTENDED_ID_FLAG
+}
+
+/// `1A0` for a standard ID, `18FEF100` for an extended one
+pub fn can_id_digits(id: u32) -> String {
+    if is_extended_id(id) {
+        format!("{:08X}", bare_can_id(id))
+    } else {
+        format!("{:03X}", id)
+    }
+}
+
+/// `0x1A0` for a standard ID, `0x18FEF100` for an extended one
+pub fn hex_can_id(id: u32) -> String {
+    format!("0x{}", can_id_digits(id))
+}
+
+/// `0x3C`
+pub fn hex_lin_id(id: u32) -> String {
+    format!("0x{:02X}", id)
+}
+
+/// Hex ID of a frame of the given type, as named by the log table and exports
+pub fn hex_frame_id(kind: &str, id: u32) -> String {
+    if kind.starts_with("LIN") {
+        hex_lin_id(id)
+    } else {
+        hex_can_id(id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hex_ids_are_padded_per_frame_format() {
+        assert_eq!(hex_can_id(0x1A), "0x01A");
+        assert_eq!(hex_can_id(0x7FF), "0x7FF");
+        assert_eq!(hex_can_id(0x800), "0x00000800");
+        assert_eq!(hex_can_id(0x123 | EXTENDED_ID_FLAG), "0x00000123");
+        assert_eq!(hex_can_id(0x18FE_F100 | EXTENDED_ID_FLAG), "0x18FEF100");
+        assert_eq!(hex_lin_id(0x3C), "0x3C");
+        assert_eq!(hex_lin_id(1), "0x01");
+        assert_eq!(hex_frame_id("LIN", 1), "0x01");
+        assert_eq!(hex_frame_id("CAN_FD", 1), "0x001");
+        assert_eq!(can_id_digits(0x1A0), "1A0");
+    }
+}
@@ -0,0 +1,376 @@
+//! GPS track handling
+//!
+//! Position fixes come either from `GPS_EVENT` objects inside the BLF or
+//! from an accompanying NMEA or CSV file. All fixes are kept on the log
+//! timeline (nanoseconds from the measurement start) so they can be
+//! correlated with bus events and exported as a GPX track.
+
+use crate::handlers::TimeOffsets;
+use blf::LogObject;
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// One position fix on the log timeline
+#[derive(Debug, Clone, PartialEq)]
+pub struct GpsFix {
+    pub timestamp_ns: u64,
+    pub latitude: f64,
+    pub longitude: f64,
+    pub altitude: Option<f64>,
+    /// Ground speed in km/h
+    pub speed_kmh: Option<f64>,
+    pub course: Option<f64>,
+}
+
+/// Fixes recorded in the log itself, with time sync offsets applied
+pub fn fixes_from_log(messages: &[LogObject], offsets: &TimeOffsets) -> Vec<GpsFix> {
+    let mut fixes: Vec<GpsFix> = messages
+        .iter()
+        .filter_map(|msg| match msg {
+            LogObject::GpsEvent(gps) => Some(GpsFix {
+                timestamp_ns: offsets.timestamp(msg),
+                latitude: gps.latitude,
+                longitude: gps.longitude,
+                altitude: Some(gps.altitude),
+                speed_kmh: Some(gps.speed),
+                course: Some(gps.course),
+            }),
+            _ => None,
+        })
+        .collect();
+    fixes.sort_by_key(|fix| fix.timestamp_ns);
+    fixes
+}
+
+/// Load fixes from an NMEA (`.nmea`, `.txt`, `.log`) or CSV file
+pub fn read_gps_file(
+    path: &Path,
+    start_time: Option<NaiveDateTime>,
+) -> Result<Vec<GpsFix>, String> {
+    let text = std::fs::read_to_string(path).map_err(|e| format!("Failed to read file: {}", e))?;
+    let is_csv = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("csv"));
+    let fixes = if is_csv {
+        parse_gps_csv(&text)?
+    } else {
+        parse_nmea(&text, start_time)
+    };
+    if fixes.is_empty() {
+        return Err("No GPS fixes found".to_string());
+    }
+    Ok(fixes)
+}
+
+/// `ddmm.mmmm` + hemisphere to signed degrees
+fn nmea_coordinate(value: &str, hemisphere: &str) -> Option<f64> {
+    let raw: f64 = value.parse().ok()?;
+    let degrees = (raw / 100.0).trunc();
+    let decimal = degrees + (raw - degrees * 100.0) / 60.0;
+    match hemisphere {
+        "N" | "E" => Some(decimal),
+        "S" | "W" => Some(-decimal),
+        _ => None,
+    }
+}
+
+fn nmea_time(value: &str) -> Option<NaiveTime> {
+    NaiveTime::parse_from_str(value, "%H%M%S%.f")
+        .or_else(|_| NaiveTime::parse_from_str(value, "%H%M%S"))
+        .ok()
+}
+
+/// Payload fields of a sentence, or `None` if its checksum is wrong
+fn nmea_fields(line: &str) -> Option<Vec<&str>> {
+    let sentence = line.trim().strip_prefix('$')?;
+    let body = match sentence.split_once('*') {
+        Some((body, checksum)) => {
+            let expected = u8::from_str_radix(checksum.trim(), 16).ok()?;
+            let actual = body.bytes().fold(0u8, |acc, byte| acc ^ byte);
+            if expected != actual {
+                return None;
+            }
+            body
+        }
+        None => sentence,
+    };
+    Some(body.split(',').collect())
+}
+
+/// Parse RMC sentences (position, speed, course, date) and take the
+/// altitude from GGA sentences with the same time of day
+///
+/// Fix times are placed on the log timeline relative to `start_time`; without
+/// a start time they are relative to the first fix. Fixes before the start of
+/// the measurement are dropped.
+pub fn parse_nmea(text: &str, start_time: Option<NaiveDateTime>) -> Vec<GpsFix> {
+    let mut altitudes: HashMap<String, f64> = HashMap::new();
+    // (time field, date and time, fix without its timestamp yet)
+    let mut rmc: Vec<(String, NaiveDateTime, GpsFix)> = Vec::new();
+
+    for fields in text.lines().filter_map(nmea_fields) {
+        let Some(kind) = fields
+            .first()
+            .filter(|kind| kind.len() == 5)
+            .map(|kind| &kind[2..])
+        else {
+            continue;
+        };
+        match kind {
+            "RMC" if fields.len() >= 10 && fields[2] == "A" => {
+                let date = NaiveDate::parse_from_str(fields[9], "%d%m%y").ok();
+                let time = nmea_time(fields[1]);
+                let latitude = nmea_coordinate(fields[3], fields[4]);
+                let longitude = nmea_coordinate(fields[5], fields[6]);
+                if let (Some(date), Some(time), Some(latitude), Some(longitude)) =
+                    (date, time, latitude, longitude)
+                {
+                    rmc.push((
+                        fields[1].to_string(),
+                        NaiveDateTime::new(date, time),
+                        GpsFix {
+                            timestamp_ns: 0,
+                            latitude,
+                            longitude,
+                            altitude: None,
+                            speed_kmh: fields[7].parse::<f64>().ok().map(|knots| knots * 1.852),
+                            course: fields[8].parse::<f64>().ok(),
+                        },
+                    ));
+                }
+            }
+            "GGA" if fields.len() >= 10 && fields[6] != "0" => {
+                if let Ok(altitude) = fields[9].parse::<f64>() {
+                    altitudes.insert(fields[1].to_string(), altitude);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let Some(origin) = start_time.or_else(|| rmc.iter().map(|fix| fix.1).min()) else {
+        return Vec::new();
+    };
+    let mut fixes: Vec<GpsFix> = rmc
+        .into_iter()
+        .filter_map(|(time_key, time, fix)| {
+            let offset = (time - origin).num_nanoseconds()?;
+            Some(GpsFix {
+                timestamp_ns: u64::try_from(offset).ok()?,
+                altitude: altitudes.get(&time_key).copied(),
+                ..fix
+            })
+        })
+        .collect();
+    fixes.sort_by_key(|fix| fix.timestamp_ns);
+    fixes
+}
+
+/// Parse a CSV with a header row naming at least `time_s`, `lat`/`latitude`
+/// and `lon`/`longitude`; `alt`/`altitude`, `speed` (km/h) and `course` are optional
+///
+/// `time_s` is in seconds from the measurement start.
+pub fn parse_gps_csv(text: &str) -> Result<Vec<GpsFix>, String> {
+    let mut lines = text.lines().filter(|line| !line.trim().is_empty());
+    let header: Vec<String> = lines
+        .next()
+        .ok_or("Empty CSV file")?
+        .split(',')
+        .map(|name| name.trim().to_ascii_lowercase())
+        .collect();
+    let column = |names: &[&str]| {
+        header
+            .iter()
+            .position(|name| names.contains(&name.as_str()))
+    };
+
+    let time = column(&["time_s", "time"]).ok_or("Missing time_s column")?;
+    let latitude = column(&["lat", "latitude"]).ok_or("Missing latitude column")?;
+    let longitude = column(&["lon", "lng", "longitude"]).ok_or("Missing longitude column")?;
+    let altitude = column(&["alt", "altitude"]);
+    let speed = column(&["speed", "speed_kmh"]);
+    let course = column(&["course", "heading"]);
+
+    let mut fixes = Vec::new();
+    for (line_no, line) in lines.enumerate() {
+        let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+        let number = |index: usize| {
+            fields
+                .get(index)
+                .and_then(|value| value.parse::<f64>().ok())
+        };
+        let optional = |index: Option<usize>| index.and_then(number);
+        let (Some(seconds), Some(lat), Some(lon)) =
+            (number(time), number(latitude), number(longitude))
+        else {
+            return Err(format!("Invalid values on line {}", line_no + 2));
+        };
+        if seconds < 0.0 {
+            continue;
+        }
+        fixes.push(GpsFix {
+            timestamp_ns: (seconds * 1_000_000_000.0).round() as u64,
+            latitude: lat,
+            longitude: lon,
+            altitude: optional(altitude),
+            speed_kmh: optional(speed),
+            course: optional(course),
+        });
+    }
+    fixes.sort_by_key(|fix| fix.timestamp_ns);
+    Ok(fixes)
+}
+
+/// Position at `timestamp_ns`, interpolated between the surrounding fixes
+///
+/// `fixes` must be sorted by time. Returns `None` outside the track.
+pub fn position_at(fixes: &[GpsFix], timestamp_ns: u64) -> Option<(f64, f64)> {
+    let next = fixes.partition_point(|fix| fix.timestamp_ns < timestamp_ns);
+    let after = fixes.get(next)?;
+    if after.timestamp_ns == timestamp_ns {
+        return Some((after.latitude, after.longitude));
+    }
+    let before = fixes.get(next.checked_sub(1)?)?;
+    let t = (timestamp_ns - before.timestamp_ns) as f64
+        / (after.timestamp_ns - before.timestamp_ns) as f64;
+    Some((
+        before.latitude + (after.latitude - before.latitude) * t,
+        before.longitude + (after.longitude - before.longitude) * t,
+    ))
+}
+
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn gpx_time(start_time: Option<NaiveDateTime>, timestamp_ns: u64) -> Option<String> {
+    start_time.map(|start| {
+        (start + chrono::Duration::nanoseconds(timestamp_ns as i64))
+            .format("%Y-%m-%dT%H:%M:%S%.3f")
+            .to_string()
+    })
+}
+
+/// GPX document with the track and one waypoint per bus event
+///
+/// `events` are `(timestamp_ns, name)` pairs; each becomes a waypoint at the
+/// interpolated position, events outside the track are skipped. Times are
+/// only written when the measurement start time is known.
+pub fn format_gpx(
+    fixes: &[GpsFix],
+    events: &[(u64, String)],
+    start_time: Option<NaiveDateTime>,
+) -> String {
+    let mut out = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <gpx version=\"1.1\" creator=\"CanView\" xmlns=\"http://www.topografix.com/GPX/1/1\">\n",
+    );
+
+    for (timestamp_ns, name) in events {
+        let Some((lat, lon)) = position_at(fixes, *timestamp_ns) else {
+            continue;
+        };
+        out.push_str(&format!("  <wpt lat=\"{:.7}\" lon=\"{:.7}\">\n", lat, lon));
+        if let Some(time) = gpx_time(start_time, *timestamp_ns) {
+            out.push_str(&format!("    <time>{}</time>\n", time));
+        }
+        out.push_str(&format!(
+            "    <name>{}</name>\n  </wpt>\n",
+            xml_escape(name)
+        ));
+    }
+
+    out.push_str("  <trk>\n    <name>CanView track</name>\n    <trkseg>\n");
+    for fix in fixes {
+        out.push_str(&format!(
+            "      <trkpt lat=\"{:.7}\" lon=\"{:.7}\">",
+            fix.latitude, fix.longitude
+        ));
+        if let Some(altitude) = fix.altitude {
+            out.push_str(&format!("<ele>{:.1}</ele>", altitude));
+        }
+        if let Some(time) = gpx_time(start_time, fix.timestamp_ns) {
+            out.push_str(&format!("<time>{}</time>", time));
+        }
+        out.push_str("</trkpt>\n");
+    }
+    out.push_str("    </trkseg>\n  </trk>\n</gpx>\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fix(timestamp_ns: u64, latitude: f64, longitude: f64) -> GpsFix {
+        GpsFix {
+            timestamp_ns,
+            latitude,
+            longitude,
+            altitude: None,
+            speed_kmh: None,
+            course: None,
+        }
+    }
+
+    #[test]
+    fn test_parse_nmea_rmc_with_gga_altitude() {
+        let text = "\
+$GPGGA,123519,4807.038,N,01131.000,E,1,08,0.9,545.4,M,46.9,M,,*47
+$GPRMC,123519,A,4807.038,N,01131.000,E,022.4,084.4,230394,003.1,W*6A
+$GPRMC,123520,A,4807.038,N,01131.000,W,022.4,084.4,230394,003.1,W*00
+$GPRMC,123521,V,4807.038,N,01131.000,E,022.4,084.4,230394,003.1,W
+";
+        let start = NaiveDate::from_ymd_opt(1994, 3, 23)
+            .unwrap()
+            .and_hms_opt(12, 35, 0)
+            .unwrap();
+        let fixes = parse_nmea(text, Some(start));
+
+        // The second RMC has a bad checksum, the third is not valid
+        assert_eq!(fixes.len(), 1);
+        assert_eq!(fixes[0].timestamp_ns, 19_000_000_000);
+        assert!((fixes[0].latitude - 48.1173).abs() < 1e-9);
+        assert!((fixes[0].longitude - 11.516_666_666).abs() < 1e-6);
+        assert_eq!(fixes[0].altitude, Some(545.4));
+        assert!((fixes[0].speed_kmh.unwrap() - 41.4848).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_parse_gps_csv() {
+        let text = "time_s,Latitude,Longitude,speed\n0.5,48.1,11.5,30\n1.0,48.2,11.6,\n";
+        let fixes = parse_gps_csv(text).unwrap();
+        assert_eq!(fixes.len(), 2);
+        assert_eq!(fixes[0].timestamp_ns, 500_000_000);
+        assert_eq!(fixes[0].speed_kmh, Some(30.0));
+        assert_eq!(fixes[1].speed_kmh, None);
+        assert!(parse_gps_csv("time_s,lat\n1,2\n").is_err());
+    }
+
+    #[test]
+    fn test_position_at_interpolates() {
+        let fixes = vec![fix(0, 10.0, 20.0), fix(1_000, 12.0, 22.0)];
+        assert_eq!(position_at(&fixes, 500), Some((11.0, 21.0)));
+        assert_eq!(position_at(&fixes, 1_000), Some((12.0, 22.0)));
+        assert_eq!(position_at(&fixes, 1_001), None);
+    }
+
+    #[test]
+    fn test_format_gpx_places_events_on_track() {
+        let fixes = vec![fix(0, 10.0, 20.0), fix(1_000, 12.0, 22.0)];
+        let events = [
+            (500, "0x123 <brake>".to_string()),
+            (5_000, "late".to_string()),
+        ];
+        let gpx = format_gpx(&fixes, &events, None);
+        assert!(gpx.contains("<wpt lat=\"11.0000000\" lon=\"21.0000000\">"));
+        assert!(gpx.contains("<name>0x123 &lt;brake&gt;</name>"));
+        assert!(!gpx.contains("late"));
+        assert_eq!(gpx.matches("<trkpt").count(), 2);
+    }
+}
@@ -0,0 +1,522 @@
+//! Streaming decoded signals over gRPC
+//!
+//! `canview serve --grpc HOST:PORT` adds the `canview.v1.SignalStream`
+//! service defined in `proto/canview.proto` next to the HTTP API. Each
+//! subscription runs its own [`SignalFeed`]: a [`LiveSource`] (a replayed
+//! BLF, the bus simulation or a capture device) whose frames are decoded with
+//! the server's databases and narrowed down by [`SignalFilter`]s as they go
+//! out.
+//!
+//! Subscribers only replay files under the directory given with
+//! `--replay-dir`; without it they cannot make the server read files.
+//!
+//! The service itself needs the `grpc` feature (tonic, prost and tokio, plus
+//! `protoc` at build time); the feed is plain Rust and always built.
+
+use crate::handlers::{
+    CaptureDevice, ExportFrame, LiveSource, Replayer, Simulator, decode_frame, parse_id,
+    parse_simulation, read_log_file,
+};
+use crate::models::ChannelType;
+use parser::dbc::DbcDatabase;
+use parser::ldf::LdfDatabase;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// How often a subscription checks its source for new frames
+pub const FEED_INTERVAL: Duration = Duration::from_millis(20);
+
+/// Signals a subscriber wants; unset fields match everything
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SignalFilter {
+    pub channel: Option<u16>,
+    pub id: Option<u32>,
+    pub name: Option<String>,
+}
+
+impl SignalFilter {
+    pub fn matches(&self, channel: u16, id: u32, name: &str) -> bool {
+        self.channel.is_none_or(|c| c == channel)
+            && self.id.is_none_or(|i| i == id)
+            && self.name.as_deref().is_none_or(|n| n == name)
+    }
+}
+
+impl fmt::Display for SignalFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(channel) = self.channel {
+            write!(f, "{}:", channel)?;
+        }
+        match self.id {
+            Some(id) => write!(f, "0x{:X}", id)?,
+            None => write!(f, "*")?,
+        }
+        if let Some(name) = &self.name {
+            write!(f, " {}", name)?;
+        }
+        Ok(())
+    }
+}
+
+/// Parse signal filters separated by `;` or newlines
+///
+/// Syntax: `[channel:]id [signal]`, e.g. `0x100 Speed`, `2:0x200` (every
+/// signal of the frame on channel 2) or `*` for every frame.
+pub fn parse_signal_filters(text: &str) -> Result<Vec<SignalFilter>, String> {
+    text.split([';', '\n'])
+        .map(str::trim)
+        .filter(|filter| !filter.is_empty())
+        .map(|filter| {
+            let words: Vec<&str> = filter.split_whitespace().collect();
+            let (target, name) = match words.as_slice() {
+                [target] => (*target, None),
+                [target, name] => (*target, Some(name.to_string())),
+                _ => return Err(format!("Expected '<id> [signal]' in '{}'", filter)),
+            };
+            let (channel, id) = match target.split_once(':') {
+                Some((channel, id)) => (
+                    Some(
+                        channel
+                            .parse()
+                            .map_err(|_| format!("Invalid channel: '{}'", channel))?,
+                    ),
+                    id,
+                ),
+                None => (None, target),
+            };
+            let id = if id == "*" { None } else { Some(parse_id(id)?) };
+            Ok(SignalFilter { channel, id, name })
+        })
+        .collect()
+}
+
+/// One decoded signal of one frame
+#[derive(Debug, Clone, PartialEq)]
+pub struct SignalUpdate {
+    pub timestamp_ns: u64,
+    pub channel: u16,
+    pub id: u32,
+    pub name: String,
+    pub value: f64,
+    pub raw: u64,
+    pub unit: String,
+    pub text: Option<String>,
+}
+
+/// Where a subscription takes its frames from
+#[derive(Debug, Clone, PartialEq)]
+pub enum FeedSource {
+    /// BLF file replayed at its original pace
+    Replay(PathBuf),
+    /// Frames in the syntax of [`parse_simulation`]
+    Simulation(String),
+    /// Frames received by a device, put on `channel`
+    Capture {
+        device: CaptureDevice,
+        channel: u16,
+        channel_type: ChannelType,
+    },
+}
+
+impl FeedSource {
+    /// Open the source; a replay reads the whole file up front
+    pub fn open(&self) -> Result<LiveSource, String> {
+        match self {
+            FeedSource::Replay(path) => {
+                let result = read_log_file(path)?;
+                Ok(LiveSource::Replay(Replayer::new(&result.objects)))
+            }
+            FeedSource::Simulation(text) => Ok(LiveSource::Simulation(Simulator::new(
+                parse_simulation(text)?,
+            ))),
+            FeedSource::Capture {
+                device,
+                channel,
+                channel_type,
+            } => Ok(LiveSource::Capture(vec![
+                device.open(*channel, *channel_type)?,
+            ])),
+        }
+    }
+}
+
+/// File a subscriber may replay: `path` resolved inside `replay_dir`
+///
+/// Relative paths are taken from `replay_dir`; `..` and symbolic links are
+/// resolved before the check, so they cannot lead out of it.
+#[cfg(any(feature = "grpc", test))]
+pub fn replay_path(path: &str, replay_dir: Option<&std::path::Path>) -> Result<PathBuf, String> {
+    let replay_dir =
+        replay_dir.ok_or("Replays are disabled; start the server with --replay-dir")?;
+    let dir = replay_dir
+        .canonicalize()
+        .map_err(|e| format!("{}: {}", replay_dir.display(), e))?;
+    let file = dir
+        .join(path)
+        .canonicalize()
+        .map_err(|e| format!("{}: {}", path, e))?;
+    if !file.starts_with(&dir) {
+        return Err(format!("{} is outside {}", path, replay_dir.display()));
+    }
+    Ok(file)
+}
+
+/// A source decoded into signal updates as time passes
+pub struct SignalFeed {
+    source: LiveSource,
+    /// Source time per unit of wall time
+    speed: f64,
+    filters: Vec<SignalFilter>,
+    dbc_channels: Arc<HashMap<u16, DbcDatabase>>,
+    ldf_channels: Arc<HashMap<u16, LdfDatabase>>,
+}
+
+impl SignalFeed {
+    /// A speed of zero or less runs in real time
+    pub fn new(
+        source: LiveSource,
+        speed: f64,
+        filters: Vec<SignalFilter>,
+        dbc_channels: Arc<HashMap<u16, DbcDatabase>>,
+        ldf_channels: Arc<HashMap<u16, LdfDatabase>>,
+    ) -> Self {
+        Self {
+            source,
+            speed: if speed > 0.0 { speed } else { 1.0 },
+            filters,
+            dbc_channels,
+            ldf_channels,
+        }
+    }
+
+    /// Updates for the frames due `elapsed` after the feed started
+    pub fn poll(&mut self, elapsed: Duration) -> Vec<SignalUpdate> {
+        let now_ns = (elapsed.as_nanos() as f64 * self.speed) as u64;
+        let mut updates = Vec::new();
+        for frame in self
+            .source
+            .advance(now_ns)
+            .iter()
+            .filter_map(ExportFrame::from_log_object)
+        {
+            for signal in decode_frame(&frame, &self.dbc_channels, &self.ldf_channels) {
+                let wanted = self.filters.is_empty()
+                    || self
+                        .filters
+                        .iter()
+                        .any(|filter| filter.matches(frame.channel, frame.id, &signal.name));
+                if wanted {
+                    updates.push(SignalUpdate {
+                        timestamp_ns: frame.timestamp_ns,
+                        channel: frame.channel,
+                        id: frame.id,
+                        name: signal.name,
+                        value: signal.value,
+                        raw: signal.raw,
+                        unit: signal.unit,
+                        text: signal.text,
+                    });
+                }
+            }
+        }
+        updates
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.source.is_finished()
+    }
+}
+
+#[cfg(not(feature = "grpc"))]
+pub fn serve_grpc(
+    _addr: &str,
+    _replay_dir: Option<PathBuf>,
+    _dbc_channels: Arc<HashMap<u16, DbcDatabase>>,
+    _ldf_channels: Arc<HashMap<u16, LdfDatabase>>,
+) -> Result<(), String> {
+    Err("This build has no gRPC support; rebuild with --features grpc".to_string())
+}
+
+#[cfg(feature = "grpc")]
+pub use service::serve_grpc;
+
+#[cfg(feature = "grpc")]
+mod service {
+    use super::*;
+    use crate::handlers::XlChannel;
+    use tokio::sync::mpsc;
+    use tokio_stream::wrappers::ReceiverStream;
+    use tonic::{Request, Response, Status};
+
+    pub mod proto {
+        tonic::include_proto!("canview.v1");
+    }
+
+    use proto::signal_stream_server::{SignalStream, SignalStreamServer};
+    use proto::subscribe_request::Source;
+
+    /// Updates buffered per subscriber before the feed waits for the client
+    const CHANNEL_CAPACITY: usize = 1024;
+
+    struct SignalStreamService {
+        replay_dir: Option<PathBuf>,
+        dbc_channels: Arc<HashMap<u16, DbcDatabase>>,
+        ldf_channels: Arc<HashMap<u16, LdfDatabase>>,
+    }
+
+    impl From<SignalUpdate> for proto::SignalUpdate {
+        fn from(update: SignalUpdate) -> Self {
+            Self {
+                timestamp_ns: update.timestamp_ns,
+                channel: update.channel as u32,
+                id: update.id,
+                name: update.name,
+                value: update.value,
+                raw: update.raw,
+                unit: update.unit,
+                text: update.text.unwrap_or_default(),
+            }
+        }
+    }
+
+    impl From<proto::SignalFilter> for SignalFilter {
+        fn from(filter: proto::SignalFilter) -> Self {
+            Self {
+                channel: (filter.channel != 0).then_some(filter.channel as u16),
+                id: filter.id,
+                name: (!filter.name.is_empty()).then_some(filter.name),
+            }
+        }
+    }
+
+    impl TryFrom<proto::CaptureSource> for FeedSource {
+        type Error = Status;
+
+        fn try_from(capture: proto::CaptureSource) -> Result<Self, Status> {
+            use proto::capture_source::Device;
+
+            let device = match capture.device {
+                Some(Device::Socketcan(interface)) => CaptureDevice::SocketCan { interface },
+                Some(Device::VectorXl(channel)) => CaptureDevice::VectorXl(XlChannel {
+                    hw_type: channel.hw_type,
+                    hw_index: channel.hw_index,
+                    hw_channel: channel.hw_channel,
+                }),
+                None => return Err(Status::invalid_argument("Missing capture device")),
+            };
+            let channel = u16::try_from(capture.channel.max(1))
+                .map_err(|_| Status::invalid_argument("Invalid channel"))?;
+            Ok(FeedSource::Capture {
+                device,
+                channel,
+                channel_type: if capture.lin {
+                    ChannelType::LIN
+                } else {
+                    ChannelType::CAN
+                },
+            })
+        }
+    }
+
+    #[tonic::async_trait]
+    impl SignalStream for SignalStreamService {
+        type SubscribeStream = ReceiverStream<Result<proto::SignalUpdate, Status>>;
+
+        async fn subscribe(
+            &self,
+            request: Request<proto::SubscribeRequest>,
+        ) -> Result<Response<Self::SubscribeStream>, Status> {
+            let request = request.into_inner();
+            let source = match request.source {
+                Some(Source::ReplayPath(path)) => FeedSource::Replay(
+                    replay_path(&path, self.replay_dir.as_deref())
+                        .map_err(Status::permission_denied)?,
+                ),
+                Some(Source::Simulation(text)) => FeedSource::Simulation(text),
+                Some(Source::Capture(capture)) => FeedSource::try_from(capture)?,
+                None => return Err(Status::invalid_argument("Missing source")),
+            };
+            // Reading a large BLF must not stall the other subscriptions
+            let source = tokio::task::spawn_blocking(move || source.open())
+                .await
+                .map_err(|e| Status::internal(e.to_string()))?
+                .map_err(Status::invalid_argument)?;
+            let mut feed = SignalFeed::new(
+                source,
+                request.speed,
+                request
+                    .filters
+                    .into_iter()
+                    .map(SignalFilter::from)
+                    .collect(),
+                self.dbc_channels.clone(),
+                self.ldf_channels.clone(),
+            );
+
+            let (tx, rx) = mpsc::channel(CHANNEL_CAPACITY);
+            tokio::spawn(async move {
+                let start = tokio::time::Instant::now();
+                let mut interval = tokio::time::interval(FEED_INTERVAL);
+                loop {
+                    interval.tick().await;
+                    for update in feed.poll(start.elapsed()) {
+                        if tx.send(Ok(update.into())).await.is_err() {
+                            return; // Client hung up
+                        }
+                    }
+                    if feed.is_finished() {
+                        return;
+                    }
+                }
+            });
+            Ok(Response::new(ReceiverStream::new(rx)))
+        }
+    }
+
+    /// Run the gRPC service until the process is stopped
+    pub fn serve_grpc(
+        addr: &str,
+        replay_dir: Option<PathBuf>,
+        dbc_channels: Arc<HashMap<u16, DbcDatabase>>,
+        ldf_channels: Arc<HashMap<u16, LdfDatabase>>,
+    ) -> Result<(), String> {
+        let socket_addr = addr.parse().map_err(|e| format!("{}: {}", addr, e))?;
+        let service = SignalStreamService {
+            replay_dir,
+            dbc_channels,
+            ldf_channels,
+        };
+        let runtime = tokio::runtime::Runtime::new().map_err(|e| e.to_string())?;
+        runtime
+            .block_on(
+                tonic::transport::Server::builder()
+                    .add_service(SignalStreamServer::new(service))
+                    .serve(socket_addr),
+            )
+            .map_err(|e| format!("{}: {}", addr, e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parser::dbc::DbcParser;
+
+    const DBC: &str = r#"
+BO_ 256 Engine: 8 ECU
+ SG_ Speed : 0|16@1+ (1,0) [0|65535] "rpm" ECU
+ SG_ Counter : 56|8@1+ (1,0) [0|255] "" ECU
+"#;
+
+    fn feed(filters: Vec<SignalFilter>) -> SignalFeed {
+        let source = FeedSource::Simulation("0x100 10ms".to_string())
+            .open()
+            .unwrap();
+        let dbc = DbcParser::new().parse(DBC).unwrap();
+        SignalFeed::new(
+            source,
+            0.0,
+            filters,
+            Arc::new(HashMap::from([(1, dbc)])),
+            Arc::new(HashMap::new()),
+        )
+    }
+
+    #[test]
+    fn test_signal_filter() {
+        let filter = SignalFilter {
+            id: Some(0x100),
+            name: Some("Speed".to_string()),
+            ..Default::default()
+        };
+        assert!(filter.matches(3, 0x100, "Speed"));
+        assert!(!filter.matches(3, 0x100, "Counter"));
+        assert!(!filter.matches(3, 0x200, "Speed"));
+        assert!(SignalFilter::default().matches(1, 0x7FF, "Anything"));
+    }
+
+    #[test]
+    fn test_parse_signal_filters() {
+        let filters = parse_signal_filters("0x100 Speed; 2:0x200\n*").unwrap();
+        assert_eq!(
+            filters[0],
+            SignalFilter {
+                channel: None,
+                id: Some(0x100),
+                name: Some("Speed".to_string()),
+            }
+        );
+        assert_eq!(filters[1].to_string(), "2:0x200");
+        assert_eq!(filters[2], SignalFilter::default());
+        assert!(parse_signal_filters("0x100 Speed extra").is_err());
+        assert!(parse_signal_filters("x:0x100").is_err());
+    }
+
+    #[test]
+    fn test_signal_feed() {
+        let mut all = feed(Vec::new());
+        let updates = all.poll(Duration::from_millis(25));
+        // Frames at 0, 10 and 20 ms, two signals each, sorted by name
+        assert_eq!(updates.len(), 6);
+        assert_eq!(updates[1].name, "Speed");
+        assert_eq!(updates[1].unit, "rpm");
+        assert!(all.poll(Duration::from_millis(25)).is_empty());
+        assert!(!all.is_finished());
+
+        let mut counter = feed(vec![SignalFilter {
+            name: Some("Counter".to_string()),
+            ..Default::default()
+        }]);
+        let updates = counter.poll(Duration::from_millis(25));
+        let counters: Vec<f64> = updates.iter().map(|update| update.value).collect();
+        assert_eq!(counters, vec![0.0, 1.0, 2.0]);
+        assert!(updates.iter().all(|update| update.name == "Counter"));
+    }
+
+    #[test]
+    fn test_feed_source() {
+        assert!(
+            FeedSource::Simulation("nonsense".to_string())
+                .open()
+                .is_err()
+        );
+        assert!(
+            FeedSource::Replay(PathBuf::from("/nonexistent.blf"))
+                .open()
+                .is_err()
+        );
+        let capture = FeedSource::Capture {
+            device: CaptureDevice::SocketCan {
+                interface: "can0".to_string(),
+            },
+            channel: 1,
+            channel_type: ChannelType::LIN,
+        };
+        assert_eq!(capture.open().unwrap_err(), "can0 can only capture CAN");
+    }
+
+    #[test]
+    fn test_replay_path_stays_in_replay_dir() {
+        let root = std::env::temp_dir().join(format!("canview_replay_{}", std::process::id()));
+        let dir = root.join("logs");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("run.blf"), b"").unwrap();
+        std::fs::write(root.join("secret.blf"), b"").unwrap();
+
+        let allowed = replay_path("run.blf", Some(&dir)).unwrap();
+        assert_eq!(allowed, dir.canonicalize().unwrap().join("run.blf"));
+        let absolute = dir.join("run.blf");
+        assert!(replay_path(absolute.to_str().unwrap(), Some(&dir)).is_ok());
+        assert!(replay_path("../secret.blf", Some(&dir)).is_err());
+        let outside = root.join("secret.blf");
+        assert!(replay_path(outside.to_str().unwrap(), Some(&dir)).is_err());
+        assert!(replay_path("missing.blf", Some(&dir)).is_err());
+        assert!(replay_path("run.blf", None).is_err());
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
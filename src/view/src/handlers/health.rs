@@ -0,0 +1,199 @@
+//! Quick triage of a freshly loaded log
+//!
+//! [`check_log_health`] collects what is worth knowing before a detailed
+//! analysis: how long the measurement ran, how much each channel carried,
+//! where error frames occurred, where nothing was logged for longer than a
+//! threshold and which objects the reader did not understand.
+
+use crate::handlers::MessageKind;
+use blf::{LogObject, LogSummary, TimestampReport};
+use std::collections::BTreeMap;
+
+/// Gaps listed individually by the summary; the rest are only counted
+pub const MAX_LISTED_GAPS: usize = 10;
+
+/// Frames and error frames seen on one channel
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ChannelHealth {
+    pub frames: usize,
+    pub errors: usize,
+}
+
+/// A stretch with no objects in the log
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimeGap {
+    /// Row of the first object after the gap
+    pub index: usize,
+    pub start_ns: u64,
+    pub end_ns: u64,
+}
+
+impl TimeGap {
+    pub fn duration_ns(&self) -> u64 {
+        self.end_ns - self.start_ns
+    }
+}
+
+/// Summary of a log for triage
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LogHealth {
+    pub objects: usize,
+    pub start_ns: u64,
+    pub end_ns: u64,
+    pub channels: BTreeMap<u16, ChannelHealth>,
+    pub error_frames: usize,
+    /// Gaps longer than the threshold, longest first
+    pub gaps: Vec<TimeGap>,
+    /// Objects the reader kept as raw data, counted by object type
+    pub unknown_types: BTreeMap<u32, usize>,
+    pub timestamps: TimestampReport,
+}
+
+impl LogHealth {
+    pub fn duration_ns(&self) -> u64 {
+        self.end_ns - self.start_ns
+    }
+
+    /// Whether nothing needs a closer look
+    pub fn is_clean(&self) -> bool {
+        self.error_frames == 0
+            && self.gaps.is_empty()
+            && self.unknown_types.is_empty()
+            && self.timestamps.is_clean()
+    }
+}
+
+/// Summarize `messages`, reporting silences longer than `gap_ns`
+///
+/// `summary` and `timestamps` were gathered while loading; the time span is
+/// taken from the summary rather than another pass over the messages.
+pub fn check_log_health(
+    messages: &[LogObject],
+    summary: &LogSummary,
+    timestamps: TimestampReport,
+    gap_ns: u64,
+) -> LogHealth {
+    let mut health = LogHealth {
+        objects: messages.len(),
+        start_ns: summary.first_timestamp.unwrap_or(0),
+        end_ns: summary.last_timestamp.unwrap_or(0),
+        timestamps,
+        ..LogHealth::default()
+    };
+
+    for msg in messages {
+        let is_error = MessageKind::of(msg) == MessageKind::Error;
+        if is_error {
+            health.error_frames += 1;
+        }
+        if let Some(channel) = msg.channel() {
+            let counts = health.channels.entry(channel).or_default();
+            counts.frames += 1;
+            if is_error {
+                counts.errors += 1;
+            }
+        }
+        if let LogObject::Unhandled { object_type, .. } = msg {
+            *health.unknown_types.entry(*object_type).or_default() += 1;
+        }
+    }
+
+    health.gaps = find_gaps(messages, gap_ns);
+    health
+        .gaps
+        .sort_by_key(|gap| std::cmp::Reverse(gap.duration_ns()));
+    health
+}
+
+/// Stretches longer than `gap_ns` without any object, in time order
+///
+/// Gaps are measured on the order the log is shown in; an object stepping
+/// back in time does not end a gap.
+pub fn find_gaps(messages: &[LogObject], gap_ns: u64) -> Vec<TimeGap> {
+    let mut gaps = Vec::new();
+    let mut latest: Option<u64> = None;
+    for (index, msg) in messages.iter().enumerate() {
+        let timestamp = msg.timestamp();
+        if let Some(start_ns) = latest
+            && timestamp.saturating_sub(start_ns) > gap_ns
+        {
+            gaps.push(TimeGap {
+                index,
+                start_ns,
+                end_ns: timestamp,
+            });
+        }
+        latest = Some(latest.map_or(timestamp, |latest| latest.max(timestamp)));
+    }
+    gaps
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::handlers::test_support::can;
+    use blf::CanErrorFrame;
+
+    #[test]
+    fn test_log_health() {
+        const MS: u64 = 1_000_000;
+        let mut error = CanErrorFrame {
+            channel: 2,
+            ..Default::default()
+        };
+        error.header.object_time_stamp = 500 * MS;
+        let messages = vec![
+            can(0x100).at(MS).build(),
+            can(0x100).channel(2).at(50 * MS).build(),
+            can(0x100).at(100 * MS).build(),
+            // 400 ms of silence
+            LogObject::CanErrorFrame(error),
+            LogObject::Unhandled {
+                object_type: 999,
+                timestamp: 500 * MS,
+                data: Vec::new(),
+            },
+            // 150 ms of silence, below the 200 ms threshold
+            can(0x100).at(650 * MS).build(),
+            can(0x100).at(800 * MS).build(),
+        ];
+
+        let summary = LogSummary::from_objects(&messages);
+        let health = check_log_health(&messages, &summary, TimestampReport::default(), 200 * MS);
+        assert_eq!(health.duration_ns(), 799 * MS);
+        assert_eq!(
+            health.channels[&1],
+            ChannelHealth {
+                frames: 4,
+                errors: 0
+            }
+        );
+        assert_eq!(
+            health.channels[&2],
+            ChannelHealth {
+                frames: 2,
+                errors: 1
+            }
+        );
+        assert_eq!(health.error_frames, 1);
+        assert_eq!(
+            health.gaps,
+            vec![TimeGap {
+                index: 3,
+                start_ns: 100 * MS,
+                end_ns: 500 * MS
+            }]
+        );
+        assert_eq!(health.unknown_types, BTreeMap::from([(999, 1)]));
+        assert!(!health.is_clean());
+
+        let summary = LogSummary::from_objects(&messages[..3]);
+        let health = check_log_health(
+            &messages[..3],
+            &summary,
+            TimestampReport::default(),
+            200 * MS,
+        );
+        assert!(health.is_clean());
+    }
+}
@@ -0,0 +1,172 @@
+//! Colors assigned to IDs and channels
+//!
+//! A [`Highlight`] gives the messages of one ID, or of a whole channel, a
+//! color that the log list, the minimap and the channel tree all use, so key
+//! messages are easy to follow from view to view. Highlights are saved with
+//! the configuration.
+
+use crate::handlers::parse_id;
+use blf::LogObject;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Colors that can be given by name, as `0xRRGGBB`
+pub const NAMED_COLORS: [(&str, u32); 8] = [
+    ("red", 0xef4444),
+    ("orange", 0xf97316),
+    ("yellow", 0xeab308),
+    ("green", 0x22c55e),
+    ("teal", 0x14b8a6),
+    ("blue", 0x3b82f6),
+    ("purple", 0xa855f7),
+    ("pink", 0xec4899),
+];
+
+/// Messages a highlight applies to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum HighlightTarget {
+    /// One ID, on one channel or on every channel
+    Id { channel: Option<u16>, id: u32 },
+    /// Everything on a channel
+    Channel { channel: u16 },
+}
+
+/// A color for an ID or a channel
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Highlight {
+    pub target: HighlightTarget,
+    /// `0xRRGGBB`
+    pub color: u32,
+}
+
+impl fmt::Display for Highlight {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.target {
+            HighlightTarget::Id {
+                channel: Some(channel),
+                id,
+            } => write!(f, "{}:0x{:X} ", channel, id)?,
+            HighlightTarget::Id { channel: None, id } => write!(f, "0x{:X} ", id)?,
+            HighlightTarget::Channel { channel } => write!(f, "ch{} ", channel)?,
+        }
+        match NAMED_COLORS.iter().find(|(_, color)| *color == self.color) {
+            Some((name, _)) => write!(f, "{}", name),
+            None => write!(f, "#{:06x}", self.color),
+        }
+    }
+}
+
+/// Parse highlights separated by `;` or newlines
+///
+/// Syntax: `0x123 orange`, `2:0x123 #ff8800` (channel 2 only) and
+/// `ch3 green` for a whole channel. Colors are one of [`NAMED_COLORS`] or
+/// `#rrggbb`.
+pub fn parse_highlights(text: &str) -> Result<Vec<Highlight>, String> {
+    text.split([';', '\n'])
+        .map(str::trim)
+        .filter(|highlight| !highlight.is_empty())
+        .map(|highlight| {
+            let Some((target, color)) = highlight.split_once(char::is_whitespace) else {
+                return Err(format!("Expected '<id> <color>' in '{}'", highlight));
+            };
+            let target = if let Some(channel) = target.strip_prefix("ch") {
+                HighlightTarget::Channel {
+                    channel: channel
+                        .parse()
+                        .map_err(|_| format!("Invalid channel: '{}'", channel))?,
+                }
+            } else {
+                match target.split_once(':') {
+                    Some((channel, id)) => HighlightTarget::Id {
+                        channel: Some(
+                            channel
+                                .parse()
+                                .map_err(|_| format!("Invalid channel: '{}'", channel))?,
+                        ),
+                        id: parse_id(id)?,
+                    },
+                    None => HighlightTarget::Id {
+                        channel: None,
+                        id: parse_id(target)?,
+                    },
+                }
+            };
+            Ok(Highlight {
+                target,
+                color: parse_color(color.trim())?,
+            })
+        })
+        .collect()
+}
+
+fn parse_color(text: &str) -> Result<u32, String> {
+    if let Some(hex) = text.strip_prefix('#') {
+        return u32::from_str_radix(hex, 16)
+            .ok()
+            .filter(|_| hex.len() == 6)
+            .ok_or_else(|| format!("Invalid color: '{}'", text));
+    }
+    NAMED_COLORS
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(text))
+        .map(|(_, color)| *color)
+        .ok_or_else(|| format!("Unknown color: '{}'", text))
+}
+
+/// Color for messages on `channel` with `id`
+///
+/// ID highlights win over channel highlights; among equals the first one
+/// listed wins.
+pub fn highlight_color(highlights: &[Highlight], channel: u16, id: Option<u32>) -> Option<u32> {
+    let by_id = highlights.iter().find(|highlight| match highlight.target {
+        HighlightTarget::Id {
+            channel: target_channel,
+            id: target_id,
+        } => id == Some(target_id) && target_channel.is_none_or(|ch| ch == channel),
+        HighlightTarget::Channel { .. } => false,
+    });
+    by_id
+        .or_else(|| {
+            highlights
+                .iter()
+                .find(|highlight| highlight.target == HighlightTarget::Channel { channel })
+        })
+        .map(|highlight| highlight.color)
+}
+
+/// Color of `msg`; objects without a channel are never highlighted
+pub fn message_highlight(highlights: &[Highlight], msg: &LogObject) -> Option<u32> {
+    if highlights.is_empty() {
+        return None;
+    }
+    highlight_color(highlights, msg.channel()?, msg.id())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_highlights_roundtrip() {
+        let highlights = parse_highlights("0x123 orange; 2:0x200 #12ab34; ch3 Green").unwrap();
+        let text: Vec<String> = highlights.iter().map(Highlight::to_string).collect();
+        assert_eq!(text, vec!["0x123 orange", "2:0x200 #12ab34", "ch3 green"]);
+        assert_eq!(parse_highlights(&text.join("; ")).unwrap(), highlights);
+
+        assert!(parse_highlights("0x123").is_err());
+        assert!(parse_highlights("0x123 mauve").is_err());
+        assert!(parse_highlights("0x123 #fff").is_err());
+        assert!(parse_highlights("chX red").is_err());
+    }
+
+    #[test]
+    fn test_id_highlight_wins_over_channel() {
+        let highlights = parse_highlights("ch2 green; 2:0x200 red; 0x200 blue").unwrap();
+        assert_eq!(highlight_color(&highlights, 2, Some(0x200)), Some(0xef4444));
+        assert_eq!(highlight_color(&highlights, 1, Some(0x200)), Some(0x3b82f6));
+        assert_eq!(highlight_color(&highlights, 2, Some(0x300)), Some(0x22c55e));
+        assert_eq!(highlight_color(&highlights, 2, None), Some(0x22c55e));
+        assert_eq!(highlight_color(&highlights, 1, Some(0x300)), None);
+    }
+}
@@ -0,0 +1,151 @@
+//! Integrity manifests for exported files
+//!
+//! With the "Manifest" preference on, every export also writes a JSON
+//! manifest holding the size and SHA-256 of the written files and of the
+//! log(s) they were made from, so validation records can show which data an
+//! artifact came from and that it has not changed since.
+//!
+//! Artifacts are listed by file name relative to the manifest, which always
+//! sits next to them; sources keep the full path they were opened from.
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+/// Appended to an artifact's file name to name its manifest
+pub const MANIFEST_SUFFIX: &str = ".manifest.json";
+
+/// A hashed file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ManifestEntry {
+    pub path: String,
+    pub bytes: u64,
+    /// Lower-case hex digest
+    pub sha256: String,
+}
+
+impl ManifestEntry {
+    /// Hash the file at `path`, recording it under `name`
+    pub fn hash(path: &Path, name: String) -> Result<Self, String> {
+        let (bytes, sha256) = sha256_file(path)?;
+        Ok(Self {
+            path: name,
+            bytes,
+            sha256,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Manifest {
+    /// Program and version that wrote the artifacts
+    pub tool: String,
+    /// Local time the manifest was written, RFC 3339
+    pub created: String,
+    /// Logs the artifacts were made from; empty for live captures
+    pub sources: Vec<ManifestEntry>,
+    pub artifacts: Vec<ManifestEntry>,
+}
+
+/// Size and SHA-256 of a file, read in blocks so large logs need little memory
+pub fn sha256_file(path: &Path) -> Result<(u64, String), String> {
+    let mut file = std::fs::File::open(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0u8; 64 * 1024];
+    let mut bytes = 0u64;
+    loop {
+        let read = file
+            .read(&mut buffer)
+            .map_err(|e| format!("{}: {}", path.display(), e))?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+        bytes += read as u64;
+    }
+    let digest = hasher
+        .finalize()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect();
+    Ok((bytes, digest))
+}
+
+/// Manifest of a single exported file: `export.csv` → `export.csv.manifest.json`
+pub fn manifest_path(artifact: &Path) -> PathBuf {
+    let mut name = artifact.file_name().unwrap_or_default().to_os_string();
+    name.push(MANIFEST_SUFFIX);
+    artifact.with_file_name(name)
+}
+
+/// Hash `artifacts` and `sources` and write the manifest to `path`
+pub fn write_manifest(
+    path: &Path,
+    artifacts: &[PathBuf],
+    sources: &[PathBuf],
+) -> Result<Manifest, String> {
+    let artifacts = artifacts
+        .iter()
+        .map(|artifact| {
+            let name = artifact.file_name().unwrap_or_default();
+            ManifestEntry::hash(artifact, name.to_string_lossy().into_owned())
+        })
+        .collect::<Result<_, _>>()?;
+    let sources = sources
+        .iter()
+        .map(|source| ManifestEntry::hash(source, source.display().to_string()))
+        .collect::<Result<_, _>>()?;
+    let manifest = Manifest {
+        tool: format!("canview {}", env!("CARGO_PKG_VERSION")),
+        created: chrono::Local::now().to_rfc3339(),
+        sources,
+        artifacts,
+    };
+    let text = serde_json::to_string_pretty(&manifest).map_err(|e| e.to_string())?;
+    std::fs::write(path, text + "\n").map_err(|e| format!("{}: {}", path.display(), e))?;
+    Ok(manifest)
+}
+
+/// Write the manifest of a single exported file next to it; returns its path
+pub fn write_export_manifest(artifact: &Path, sources: &[PathBuf]) -> Result<PathBuf, String> {
+    let path = manifest_path(artifact);
+    write_manifest(&path, &[artifact.to_path_buf()], sources)?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_manifest() {
+        let dir = std::env::temp_dir().join(format!("canview_manifest_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let artifact = dir.join("export.csv");
+        let source = dir.join("log.blf");
+        std::fs::write(&artifact, "abc").unwrap();
+        std::fs::write(&source, "").unwrap();
+
+        let path = manifest_path(&artifact);
+        assert_eq!(path, dir.join("export.csv.manifest.json"));
+        let manifest = write_manifest(&path, &[artifact], std::slice::from_ref(&source)).unwrap();
+        assert_eq!(manifest.artifacts[0].path, "export.csv");
+        assert_eq!(manifest.artifacts[0].bytes, 3);
+        assert_eq!(
+            manifest.artifacts[0].sha256,
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        assert_eq!(manifest.sources[0].path, source.display().to_string());
+        assert_eq!(
+            manifest.sources[0].sha256,
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+
+        let written: Manifest =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(written, manifest);
+        assert!(write_manifest(&path, &[dir.join("missing.csv")], &[]).is_err());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,211 @@
+//! Measurement markers shared with CANoe
+//!
+//! CANoe keeps markers as `GLOBAL_MARKER` objects inside the BLF, so markers
+//! found in a loaded log become [`Marker`]s and [`with_markers`] puts them
+//! back when the log is saved. For logs that should stay untouched the list
+//! can also travel as a sidecar of ASC comment lines, which can be pasted
+//! into an ASC file as well:
+//!
+//! ```text
+//! // Marker 12.345678 Brake test<TAB>Pedal fully pressed
+//! ```
+//!
+//! Time is in seconds from the measurement start; the optional description
+//! follows the name after a tab character.
+
+use blf::{GlobalMarker, LogObject};
+
+/// Marker group written to the BLF, shown as the group name in CANoe
+pub const MARKER_GROUP: &str = "CanView";
+
+/// Background color of written markers (0x00BBGGRR, as in CANoe)
+const MARKER_COLOR: u32 = 0x0000_A5FF;
+
+const COMMENT_PREFIX: &str = "// Marker ";
+
+/// A named point in time of the measurement
+#[derive(Debug, Clone, PartialEq)]
+pub struct Marker {
+    pub timestamp_ns: u64,
+    pub name: String,
+    pub description: String,
+}
+
+impl Marker {
+    pub fn new(timestamp_ns: u64, name: impl Into<String>) -> Self {
+        Self {
+            timestamp_ns,
+            name: name.into(),
+            description: String::new(),
+        }
+    }
+
+    /// The marker as a `GLOBAL_MARKER` object
+    pub fn to_log_object(&self) -> LogObject {
+        LogObject::GlobalMarker(GlobalMarker {
+            commented_event_type: 0,
+            foreground_color: 0,
+            background_color: MARKER_COLOR,
+            is_relocatable: 1,
+            group_name: MARKER_GROUP.to_string(),
+            marker_name: self.name.clone(),
+            description: self.description.clone(),
+            timestamp: self.timestamp_ns,
+        })
+    }
+}
+
+/// Markers stored in a log, in time order
+pub fn markers_from_log(messages: &[LogObject]) -> Vec<Marker> {
+    let mut markers: Vec<Marker> = messages
+        .iter()
+        .filter_map(|msg| match msg {
+            LogObject::GlobalMarker(marker) => Some(Marker {
+                timestamp_ns: marker.timestamp,
+                name: marker.marker_name.clone(),
+                description: marker.description.clone(),
+            }),
+            _ => None,
+        })
+        .collect();
+    markers.sort_by_key(|marker| marker.timestamp_ns);
+    markers
+}
+
+/// `messages` with their own markers replaced by `markers`, merged in time order
+pub fn with_markers(messages: &[LogObject], markers: &[Marker]) -> Vec<LogObject> {
+    let mut objects: Vec<LogObject> = messages
+        .iter()
+        .filter(|msg| !matches!(msg, LogObject::GlobalMarker(_)))
+        .cloned()
+        .collect();
+    for marker in markers {
+        let at = objects.partition_point(|msg| msg.timestamp() <= marker.timestamp_ns);
+        objects.insert(at, marker.to_log_object());
+    }
+    objects
+}
+
+/// Add `marker`, keeping the list in time order
+pub fn insert_marker(markers: &mut Vec<Marker>, marker: Marker) {
+    let at = markers.partition_point(|m| m.timestamp_ns <= marker.timestamp_ns);
+    markers.insert(at, marker);
+}
+
+/// Marker placed after the row before `index` and up to row `index` itself
+pub fn marker_at_row<'a>(
+    markers: &'a [Marker],
+    messages: &[LogObject],
+    index: usize,
+) -> Option<&'a Marker> {
+    let timestamp = messages.get(index)?.timestamp();
+    let from = match index.checked_sub(1).and_then(|prev| messages.get(prev)) {
+        Some(prev) => markers.partition_point(|m| m.timestamp_ns <= prev.timestamp()),
+        None => 0,
+    };
+    markers[from..]
+        .first()
+        .filter(|m| m.timestamp_ns <= timestamp)
+}
+
+/// Sidecar text with one ASC comment line per marker
+pub fn format_marker_comments(markers: &[Marker]) -> String {
+    markers
+        .iter()
+        .map(|marker| {
+            let mut line = format!(
+                "{}{:.6} {}",
+                COMMENT_PREFIX,
+                marker.timestamp_ns as f64 / 1_000_000_000.0,
+                marker.name.replace(['\t', '\n'], " ")
+            );
+            if !marker.description.is_empty() {
+                line.push('\t');
+                line.push_str(&marker.description.replace(['\t', '\n'], " "));
+            }
+            line.push('\n');
+            line
+        })
+        .collect()
+}
+
+/// Markers from the comment lines of a sidecar or ASC file
+///
+/// Lines that are not marker comments are skipped, so a whole ASC log can be
+/// read as well.
+pub fn parse_marker_comments(text: &str) -> Result<Vec<Marker>, String> {
+    let mut markers = Vec::new();
+    for (line_no, line) in text.lines().enumerate() {
+        let Some(rest) = line.trim_start().strip_prefix(COMMENT_PREFIX) else {
+            continue;
+        };
+        let (time, rest) = rest
+            .trim_start()
+            .split_once(' ')
+            .unwrap_or((rest.trim(), ""));
+        let seconds: f64 = time
+            .parse()
+            .ok()
+            .filter(|seconds: &f64| seconds.is_finite() && *seconds >= 0.0)
+            .ok_or_else(|| format!("Line {}: invalid marker time '{}'", line_no + 1, time))?;
+        let (name, description) = rest.split_once('\t').unwrap_or((rest, ""));
+        insert_marker(
+            &mut markers,
+            Marker {
+                timestamp_ns: (seconds * 1_000_000_000.0).round() as u64,
+                name: name.trim().to_string(),
+                description: description.trim().to_string(),
+            },
+        );
+    }
+    Ok(markers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::handlers::test_support::can;
+
+    #[test]
+    fn test_markers_roundtrip_through_log() {
+        let mut markers = Vec::new();
+        insert_marker(&mut markers, Marker::new(2_500, "Second"));
+        insert_marker(&mut markers, Marker::new(1_000, "First"));
+
+        let log = vec![
+            can(0x100).build(),
+            can(0x100).at(1_000).build(),
+            can(0x100).at(3_000).build(),
+        ];
+        let saved = with_markers(&log, &markers);
+        let timestamps: Vec<u64> = saved.iter().map(LogObject::timestamp).collect();
+        assert_eq!(timestamps, vec![0, 1_000, 1_000, 2_500, 3_000]);
+        assert!(matches!(saved[2], LogObject::GlobalMarker(_)));
+        assert_eq!(markers_from_log(&saved), markers);
+        assert_eq!(marker_at_row(&markers, &log, 1), Some(&markers[0]));
+        assert_eq!(marker_at_row(&markers, &log, 2), Some(&markers[1]));
+        assert_eq!(marker_at_row(&markers, &log, 0), None);
+
+        // Saving again replaces the old markers instead of duplicating them
+        assert_eq!(with_markers(&saved, &markers[..1]).len(), 4);
+    }
+
+    #[test]
+    fn test_marker_comments_roundtrip() {
+        let mut marker = Marker::new(12_345_678_000, "Brake test");
+        marker.description = "Pedal fully pressed".to_string();
+        let markers = vec![Marker::new(500_000, "Start"), marker];
+
+        let text = format_marker_comments(&markers);
+        assert_eq!(
+            text,
+            "// Marker 0.000500 Start\n// Marker 12.345678 Brake test\tPedal fully pressed\n"
+        );
+        let asc = format!(
+            "date Mon Mar 3 12:00:00 2025\n{}   1.000000 1  100  Rx   d 0\n",
+            text
+        );
+        assert_eq!(parse_marker_comments(&asc), Ok(markers));
+        assert!(parse_marker_comments("// Marker soon Start").is_err());
+    }
+}
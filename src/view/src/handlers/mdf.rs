@@ -0,0 +1,664 @@
+//! Export to ASAM MDF 4 (`.mf4`)
+//!
+//! MDF is what measurement tools such as CANape and asammdf read. Every
+//! message with a database definition becomes a channel group named after
+//! it, holding a time master channel (seconds from the measurement start)
+//! and one channel per signal. A signal channel carries the raw value and,
+//! for scaled DBC signals, a linear conversion to the physical value, so a
+//! tool can show either. Frames without a definition are left out.
+//!
+//! Multiplexed signals are only present in the frames that select them, so
+//! groups holding any get invalidation bytes after the signal values. A
+//! signal's bit is set in the records of frames that do not carry it, and
+//! tools skip those samples instead of reading a value of 0.
+//!
+//! The file is written in the same chunks as the other formats:
+//! [`Mf4Export::create`] writes the identification and header blocks and
+//! opens the data block, [`Mf4Export::append`] adds one record per frame,
+//! and [`Mf4Export::finish`] appends the group and channel blocks and fills
+//! in the links. Records of all groups share one unsorted data group, told
+//! apart by a 2-byte record ID.
+//!
+//! ```text
+//! ID  HD ─┬─ DT  [id|time|signals…|invalid] [id|time|signals…|invalid] …
+//!         ├─ FH ── MD
+//!         └─ DG ── CG Engine ── CN time, CN Speed ── CC, …
+//!                  CG Brake  ── …
+//! ```
+
+use crate::handlers::{ExportFrame, NumberedMessage, TimeOffsets, decode_frame};
+use parser::dbc::DbcDatabase;
+use parser::ldf::LdfDatabase;
+use std::collections::HashMap;
+use std::io::{Seek, SeekFrom, Write};
+use std::path::Path;
+
+/// The header block always follows the 64-byte identification block
+const HD_OFFSET: u64 = 64;
+/// Block header, links and data of the header block
+const HD_SIZE: u64 = 24 + 6 * 8 + 32;
+const DT_OFFSET: u64 = HD_OFFSET + HD_SIZE;
+/// Bytes of the record ID in front of every record
+const RECORD_ID_SIZE: u8 = 2;
+/// Bytes of the time and of each signal in a record
+const VALUE_SIZE: u32 = 8;
+
+const DATA_TYPE_UNSIGNED: u8 = 0;
+const DATA_TYPE_SIGNED: u8 = 2;
+const DATA_TYPE_FLOAT: u8 = 4;
+
+/// Channel flag: the channel's invalidation bit is to be read
+const CN_FLAG_INVALIDATION_BIT: u32 = 1 << 1;
+
+/// A signal channel of a group
+#[derive(Debug, Clone, PartialEq)]
+struct Mf4Signal {
+    name: String,
+    unit: String,
+    /// Bit length of a signed signal, whose raw value is sign-extended
+    signed_bits: Option<u32>,
+    /// Physical value = offset + factor × raw, when not the identity
+    linear: Option<(f64, f64)>,
+    /// Present only in the frames whose multiplexor selects it
+    multiplexed: bool,
+}
+
+/// A channel group: the frames of one message
+#[derive(Debug, Clone, PartialEq)]
+struct Mf4Group {
+    name: String,
+    channel: u16,
+    id: u32,
+    lin: bool,
+    /// Sorted by name, the order [`decode_frame`] returns them in
+    signals: Vec<Mf4Signal>,
+    cycles: u64,
+}
+
+impl Mf4Group {
+    fn record_size(&self) -> u32 {
+        VALUE_SIZE * (1 + self.signals.len() as u32)
+    }
+
+    /// Bytes after the values holding one invalidation bit per signal,
+    /// none when every signal is in every frame
+    fn invalidation_bytes(&self) -> u32 {
+        if self.signals.iter().any(|signal| signal.multiplexed) {
+            self.signals.len().div_ceil(8) as u32
+        } else {
+            0
+        }
+    }
+}
+
+/// Channel groups and start time of one MDF export
+#[derive(Debug, Clone)]
+pub struct Mf4Export {
+    groups: Vec<Mf4Group>,
+    /// Group index of each (channel, LIN, frame ID)
+    group_of: HashMap<(u16, bool, u32), usize>,
+    start_time: Option<chrono::NaiveDateTime>,
+}
+
+impl Mf4Export {
+    /// Lay out a channel group for each message of `messages` with a definition
+    ///
+    /// The chunks later passed to [`append`](Self::append) must together be
+    /// `messages`, since the group sizes are counted here.
+    pub fn new(
+        messages: &[NumberedMessage],
+        dbc_channels: &HashMap<u16, DbcDatabase>,
+        ldf_channels: &HashMap<u16, LdfDatabase>,
+        start_time: Option<chrono::NaiveDateTime>,
+    ) -> Self {
+        let mut groups: Vec<Mf4Group> = Vec::new();
+        let mut group_of: HashMap<(u16, bool, u32), usize> = HashMap::new();
+        let mut undefined = std::collections::HashSet::new();
+        for frame in messages
+            .iter()
+            .filter_map(|(_, msg)| ExportFrame::from_log_object(msg))
+        {
+            let key = (frame.channel, frame.kind == "LIN", frame.id);
+            if let Some(&index) = group_of.get(&key) {
+                groups[index].cycles += 1;
+                continue;
+            }
+            if undefined.contains(&key) {
+                continue;
+            }
+            match group_for(&frame, dbc_channels, ldf_channels) {
+                Some(group) => {
+                    group_of.insert(key, groups.len());
+                    groups.push(group);
+                }
+                None => {
+                    undefined.insert(key);
+                }
+            }
+        }
+        Self {
+            groups,
+            group_of,
+            start_time,
+        }
+    }
+
+    /// Replace `path` with the identification and header blocks and an open data block
+    pub fn create(&self, path: &Path) -> Result<(), String> {
+        let mut out = identification_block();
+        out.extend(self.header_block(0, 0));
+        out.extend(block(b"DT", &[], &[]));
+        std::fs::write(path, out).map_err(|e| format!("{}: {}", path.display(), e))
+    }
+
+    /// Append a record for each frame of `messages` with a group; returns the record count
+    pub fn append(
+        &self,
+        path: &Path,
+        messages: &[NumberedMessage],
+        dbc_channels: &HashMap<u16, DbcDatabase>,
+        ldf_channels: &HashMap<u16, LdfDatabase>,
+        offsets: &TimeOffsets,
+    ) -> Result<usize, String> {
+        let mut out = Vec::new();
+        let mut records = 0;
+        for frame in messages
+            .iter()
+            .filter_map(|(_, msg)| ExportFrame::with_offsets(msg, offsets))
+        {
+            let key = (frame.channel, frame.kind == "LIN", frame.id);
+            let Some(&index) = self.group_of.get(&key) else {
+                continue;
+            };
+            let group = &self.groups[index];
+            let mut values = vec![0u64; group.signals.len()];
+            let mut present = vec![false; group.signals.len()];
+            for signal in decode_frame(&frame, dbc_channels, ldf_channels) {
+                let Ok(position) = group.signals.binary_search_by(|s| s.name.cmp(&signal.name))
+                else {
+                    continue;
+                };
+                values[position] = match group.signals[position].signed_bits {
+                    Some(bits) => sign_extend(signal.raw, bits) as u64,
+                    None => signal.raw,
+                };
+                present[position] = true;
+            }
+            out.extend((index as u16 + 1).to_le_bytes());
+            out.extend((frame.timestamp_ns as f64 / 1_000_000_000.0).to_le_bytes());
+            for value in values {
+                out.extend(value.to_le_bytes());
+            }
+            let mut invalid = vec![0u8; group.invalidation_bytes() as usize];
+            for (position, signal) in group.signals.iter().enumerate() {
+                if signal.multiplexed && !present[position] {
+                    invalid[position / 8] |= 1 << (position % 8);
+                }
+            }
+            out.extend(invalid);
+            records += 1;
+        }
+        std::fs::OpenOptions::new()
+            .append(true)
+            .open(path)
+            .and_then(|mut file| file.write_all(&out))
+            .map_err(|e| format!("{}: {}", path.display(), e))?;
+        Ok(records)
+    }
+
+    /// Close the data block and write the group and channel blocks
+    pub fn finish(&self, path: &Path) -> Result<(), String> {
+        let io_error = |e: std::io::Error| format!("{}: {}", path.display(), e);
+        let mut file = std::fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(path)
+            .map_err(io_error)?;
+        let data_end = file.seek(SeekFrom::End(0)).map_err(io_error)?;
+
+        // Metadata goes after the data block, which ends on any byte
+        let base = data_end.next_multiple_of(8);
+        let mut meta = BlockList::new(base);
+
+        let mut next_group = 0;
+        for (index, group) in self.groups.iter().enumerate().rev() {
+            next_group = self.write_group(&mut meta, index, group, next_group);
+        }
+        let data_group = meta.push(block(
+            b"DG",
+            &[0, next_group, DT_OFFSET, 0],
+            &[RECORD_ID_SIZE, 0, 0, 0, 0, 0, 0, 0],
+        ));
+        let history_comment = meta.push(text_block(
+            b"MD",
+            &format!(
+                "<FHcomment>\n<TX>Exported from a bus log</TX>\n<tool_id>CANVIEW</tool_id>\n\
+                 <tool_vendor>CANVIEW</tool_vendor>\n<tool_version>{}</tool_version>\n\
+                 </FHcomment>",
+                env!("CARGO_PKG_VERSION")
+            ),
+        ));
+        let now = chrono::Utc::now().timestamp_nanos_opt().unwrap_or(0) as u64;
+        let mut history = now.to_le_bytes().to_vec();
+        // Time zone and DST offsets of zero, valid: the time is UTC
+        history.extend([0, 0, 0, 0, 0b10, 0, 0, 0]);
+        let file_history = meta.push(block(b"FH", &[0, history_comment], &history));
+
+        let mut tail = vec![0; (base - data_end) as usize];
+        tail.extend(meta.bytes);
+        file.write_all(&tail).map_err(io_error)?;
+
+        // The data block's length and the header's links are now known
+        file.seek(SeekFrom::Start(DT_OFFSET + 8))
+            .map_err(io_error)?;
+        file.write_all(&(data_end - DT_OFFSET).to_le_bytes())
+            .map_err(io_error)?;
+        file.seek(SeekFrom::Start(HD_OFFSET)).map_err(io_error)?;
+        file.write_all(&self.header_block(data_group, file_history))
+            .map_err(io_error)?;
+        Ok(())
+    }
+
+    /// Write the blocks of `group` and return the offset of its CG block
+    fn write_group(
+        &self,
+        meta: &mut BlockList,
+        index: usize,
+        group: &Mf4Group,
+        next_group: u64,
+    ) -> u64 {
+        let mut next_channel = 0;
+        for (position, signal) in group.signals.iter().enumerate().rev() {
+            let name = meta.push(text_block(b"TX", &signal.name));
+            let unit = if signal.unit.is_empty() {
+                0
+            } else {
+                meta.push(text_block(b"TX", &signal.unit))
+            };
+            let conversion = match signal.linear {
+                Some((offset, factor)) => meta.push(linear_conversion(offset, factor)),
+                None => 0,
+            };
+            let data_type = match signal.signed_bits {
+                Some(_) => DATA_TYPE_SIGNED,
+                None => DATA_TYPE_UNSIGNED,
+            };
+            let byte_offset = VALUE_SIZE * (1 + position as u32);
+            let invalidation_bit = signal.multiplexed.then_some(position as u32);
+            next_channel = meta.push(channel_block(
+                [next_channel, name, conversion, unit],
+                0,
+                data_type,
+                byte_offset,
+                invalidation_bit,
+            ));
+        }
+        let time_name = meta.push(text_block(b"TX", "time"));
+        let time_unit = meta.push(text_block(b"TX", "s"));
+        // Master channel synchronised on time
+        let time = meta.push(channel_block(
+            [next_channel, time_name, 0, time_unit],
+            2,
+            DATA_TYPE_FLOAT,
+            0,
+            None,
+        ));
+
+        let name = meta.push(text_block(b"TX", &group.name));
+        let comment = meta.push(text_block(
+            b"TX",
+            &format!(
+                "{} channel {}, ID 0x{:X}",
+                if group.lin { "LIN" } else { "CAN" },
+                group.channel,
+                group.id
+            ),
+        ));
+        let mut data = Vec::new();
+        data.extend((index as u64 + 1).to_le_bytes());
+        data.extend(group.cycles.to_le_bytes());
+        data.extend(0u16.to_le_bytes()); // flags
+        data.extend((b'.' as u16).to_le_bytes()); // path separator
+        data.extend([0; 4]);
+        data.extend(group.record_size().to_le_bytes());
+        data.extend(group.invalidation_bytes().to_le_bytes());
+        meta.push(block(
+            b"CG",
+            &[next_group, time, name, 0, 0, comment],
+            &data,
+        ))
+    }
+
+    fn header_block(&self, data_group: u64, file_history: u64) -> Vec<u8> {
+        // A BLF start time is the logger's wall clock, so it is flagged as local time
+        let (start_ns, time_flags) = match self.start_time {
+            Some(start) => (start.and_utc().timestamp_nanos_opt().unwrap_or(0) as u64, 1),
+            None => (0, 0),
+        };
+        let mut data = start_ns.to_le_bytes().to_vec();
+        data.extend([0, 0, 0, 0, time_flags, 0, 0, 0]);
+        data.extend(0f64.to_le_bytes()); // start angle
+        data.extend(0f64.to_le_bytes()); // start distance
+        block(b"HD", &[data_group, file_history, 0, 0, 0, 0], &data)
+    }
+}
+
+/// The group of `frame`'s message, `None` without a definition for it
+fn group_for(
+    frame: &ExportFrame,
+    dbc_channels: &HashMap<u16, DbcDatabase>,
+    ldf_channels: &HashMap<u16, LdfDatabase>,
+) -> Option<Mf4Group> {
+    let (name, mut signals) = if frame.kind == "LIN" {
+        let db = ldf_channels.get(&frame.channel)?;
+        let ldf_frame = db.frames.values().find(|f| f.id == frame.id)?;
+        let signals = ldf_frame
+            .signals
+            .iter()
+            .filter(|mapping| db.signals.contains_key(&mapping.signal_name))
+            .map(|mapping| Mf4Signal {
+                name: mapping.signal_name.clone(),
+                unit: String::new(),
+                signed_bits: None,
+                linear: None,
+                multiplexed: false,
+            })
+            .collect::<Vec<_>>();
+        (ldf_frame.name.clone(), signals)
+    } else {
+        let message = dbc_channels.get(&frame.channel)?.messages.get(&frame.id)?;
+        let signals = message
+            .signals
+            .values()
+            .map(|signal| Mf4Signal {
+                name: signal.name.clone(),
+                unit: signal.unit.clone(),
+                signed_bits: (signal.value_type == '-').then_some(signal.signal_size),
+                linear: (signal.factor != 1.0 || signal.offset != 0.0)
+                    .then_some((signal.offset, signal.factor)),
+                multiplexed: signal.multiplexing.selector().is_some(),
+            })
+            .collect::<Vec<_>>();
+        (message.name.clone(), signals)
+    };
+    signals.sort_by(|a, b| a.name.cmp(&b.name));
+    Some(Mf4Group {
+        name,
+        channel: frame.channel,
+        id: frame.id,
+        lin: frame.kind == "LIN",
+        signals,
+        cycles: 1,
+    })
+}
+
+fn sign_extend(raw: u64, bits: u32) -> i64 {
+    if bits == 0 || bits >= 64 {
+        return raw as i64;
+    }
+    let shift = 64 - bits;
+    ((raw << shift) as i64) >> shift
+}
+
+/// Blocks laid out one after another from `base`, each 8-byte aligned
+struct BlockList {
+    base: u64,
+    bytes: Vec<u8>,
+}
+
+impl BlockList {
+    fn new(base: u64) -> Self {
+        Self {
+            base,
+            bytes: Vec::new(),
+        }
+    }
+
+    /// Append a block and return its file offset
+    fn push(&mut self, block: Vec<u8>) -> u64 {
+        let offset = self.base + self.bytes.len() as u64;
+        self.bytes.extend(block);
+        offset
+    }
+}
+
+fn identification_block() -> Vec<u8> {
+    let mut out = Vec::with_capacity(HD_OFFSET as usize);
+    out.extend(b"MDF     4.10    CANVIEW ");
+    out.extend([0; 4]);
+    out.extend(410u16.to_le_bytes());
+    out.resize(HD_OFFSET as usize, 0);
+    out
+}
+
+/// `##<id>` block with its links and data, padded to 8 bytes
+///
+/// The length of a block without links or data (the open data block) is
+/// filled in later.
+fn block(id: &[u8; 2], links: &[u64], data: &[u8]) -> Vec<u8> {
+    let length = 24 + 8 * links.len() + data.len();
+    let mut out = Vec::with_capacity(length.next_multiple_of(8));
+    out.extend(b"##");
+    out.extend(id);
+    out.extend([0; 4]);
+    out.extend((length as u64).to_le_bytes());
+    out.extend((links.len() as u64).to_le_bytes());
+    for link in links {
+        out.extend(link.to_le_bytes());
+    }
+    out.extend(data);
+    out.resize(length.next_multiple_of(8), 0);
+    out
+}
+
+/// TX or MD block holding zero-terminated `text`
+fn text_block(id: &[u8; 2], text: &str) -> Vec<u8> {
+    let mut data = text.as_bytes().to_vec();
+    data.push(0);
+    block(id, &[], &data)
+}
+
+/// CN block of an 8-byte value at `byte_offset`; links are next, name, conversion and unit
+///
+/// `invalidation_bit` is the channel's bit in the record's invalidation bytes.
+fn channel_block(
+    links: [u64; 4],
+    channel_type: u8,
+    data_type: u8,
+    byte_offset: u32,
+    invalidation_bit: Option<u32>,
+) -> Vec<u8> {
+    let [next, name, conversion, unit] = links;
+    let sync_type = if channel_type == 2 { 1 } else { 0 };
+    let mut data = vec![channel_type, sync_type, data_type, 0];
+    data.extend(byte_offset.to_le_bytes());
+    data.extend((VALUE_SIZE * 8).to_le_bytes()); // bit count
+    let flags = invalidation_bit.map_or(0, |_| CN_FLAG_INVALIDATION_BIT);
+    data.extend(flags.to_le_bytes());
+    data.extend(invalidation_bit.unwrap_or(0).to_le_bytes());
+    data.extend([0; 4]); // precision, attachments
+    data.extend([0; 48]); // value and limit ranges
+    block(b"CN", &[next, 0, name, 0, conversion, 0, unit, 0], &data)
+}
+
+/// CC block computing `offset + factor × raw`
+fn linear_conversion(offset: f64, factor: f64) -> Vec<u8> {
+    let mut data = vec![1, 0];
+    data.extend(0u16.to_le_bytes()); // flags
+    data.extend(0u16.to_le_bytes()); // references
+    data.extend(2u16.to_le_bytes()); // parameters
+    data.extend(0f64.to_le_bytes()); // physical range
+    data.extend(0f64.to_le_bytes());
+    data.extend(offset.to_le_bytes());
+    data.extend(factor.to_le_bytes());
+    block(b"CC", &[0, 0, 0, 0], &data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use blf::{CanMessage, LogObject};
+    use parser::dbc::DbcParser;
+
+    fn u64_at(bytes: &[u8], offset: u64) -> u64 {
+        let offset = offset as usize;
+        u64::from_le_bytes(bytes[offset..offset + 8].try_into().unwrap())
+    }
+
+    fn u32_at(bytes: &[u8], offset: u64) -> u32 {
+        let offset = offset as usize;
+        u32::from_le_bytes(bytes[offset..offset + 4].try_into().unwrap())
+    }
+
+    fn block_id(bytes: &[u8], offset: u64) -> &str {
+        std::str::from_utf8(&bytes[offset as usize..offset as usize + 4]).unwrap()
+    }
+
+    #[test]
+    fn test_mf4_export() {
+        let path = std::env::temp_dir().join(format!("canview_export_{}.mf4", std::process::id()));
+        let dbc = DbcParser::new()
+            .parse(
+                "BO_ 256 Engine: 8 ECU\n\
+                 \x20SG_ Speed : 0|16@1+ (0.5,0) [0|1000] \"rpm\" Vector__XXX\n\
+                 \x20SG_ Torque : 16|8@1- (1,0) [-128|127] \"Nm\" Vector__XXX\n",
+            )
+            .unwrap();
+        let dbc_channels = HashMap::from([(1, dbc)]);
+        let messages: Vec<NumberedMessage> = [(0x100, 0x10), (0x300, 0), (0x100, 0x20)]
+            .into_iter()
+            .enumerate()
+            .map(|(i, (id, speed))| {
+                let mut msg = CanMessage {
+                    channel: 1,
+                    id,
+                    dlc: 8,
+                    data: [speed, 0, 0xFE, 0, 0, 0, 0, 0],
+                    ..Default::default()
+                };
+                msg.header.object_time_stamp = i as u64 * 1_000_000;
+                (i as u64 + 1, LogObject::CanMessage(msg))
+            })
+            .collect();
+        let ldf_channels = HashMap::new();
+        let offsets = TimeOffsets::new();
+
+        let export = Mf4Export::new(&messages, &dbc_channels, &ldf_channels, None);
+        export.create(&path).unwrap();
+        let records: usize = messages
+            .chunks(2)
+            .map(|chunk| {
+                export
+                    .append(&path, chunk, &dbc_channels, &ldf_channels, &offsets)
+                    .unwrap()
+            })
+            .sum();
+        export.finish(&path).unwrap();
+        // The frame without a definition is left out
+        assert_eq!(records, 2);
+
+        let bytes = std::fs::read(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(&bytes[..16], b"MDF     4.10    ");
+        assert_eq!(block_id(&bytes, HD_OFFSET), "##HD");
+        assert_eq!(block_id(&bytes, DT_OFFSET), "##DT");
+        // Two records of ID, time, Speed and Torque
+        let record = 2 + 3 * 8;
+        assert_eq!(u64_at(&bytes, DT_OFFSET + 8), 24 + 2 * record);
+        let second = (DT_OFFSET + 24 + record) as usize;
+        assert_eq!(&bytes[second..second + 2], &[1, 0]);
+        assert_eq!(
+            f64::from_le_bytes(bytes[second + 2..second + 10].try_into().unwrap()),
+            0.002
+        );
+        assert_eq!(u64_at(&bytes, second as u64 + 10), 0x20);
+        assert_eq!(u64_at(&bytes, second as u64 + 18) as i64, -2);
+
+        let data_group = u64_at(&bytes, HD_OFFSET + 24);
+        assert_eq!(block_id(&bytes, data_group), "##DG");
+        assert_eq!(u64_at(&bytes, data_group + 24 + 16), DT_OFFSET);
+        let group = u64_at(&bytes, data_group + 24 + 8);
+        assert_eq!(block_id(&bytes, group), "##CG");
+        // No second group, record ID 1, two cycles
+        assert_eq!(u64_at(&bytes, group + 24), 0);
+        assert_eq!(u64_at(&bytes, group + 24 + 48), 1);
+        assert_eq!(u64_at(&bytes, group + 24 + 56), 2);
+        let time = u64_at(&bytes, group + 24 + 8);
+        assert_eq!(block_id(&bytes, time), "##CN");
+        assert_eq!(bytes[time as usize + 24 + 64], 2);
+        let speed = u64_at(&bytes, time + 24);
+        let conversion = u64_at(&bytes, speed + 24 + 32);
+        assert_eq!(block_id(&bytes, conversion), "##CC");
+        assert_eq!(block_id(&bytes, u64_at(&bytes, HD_OFFSET + 32)), "##FH");
+        // No multiplexed signals, no invalidation bytes
+        assert_eq!(u32_at(&bytes, group + 24 + 48 + 28), 0);
+    }
+
+    #[test]
+    fn test_mf4_invalidates_inactive_multiplexed_signals() {
+        let path = std::env::temp_dir().join(format!("canview_mux_{}.mf4", std::process::id()));
+        let dbc = DbcParser::new()
+            .parse(
+                "BO_ 512 Muxed: 8 ECU\n\
+                 \x20SG_ Mode M : 0|8@1+ (1,0) [0|255] \"\" Vector__XXX\n\
+                 \x20SG_ A m1 : 8|8@1+ (1,0) [0|255] \"\" Vector__XXX\n\
+                 \x20SG_ B m2 : 8|8@1+ (1,0) [0|255] \"\" Vector__XXX\n",
+            )
+            .unwrap();
+        let dbc_channels = HashMap::from([(1, dbc)]);
+        let messages: Vec<NumberedMessage> = [[1, 5], [2, 7]]
+            .into_iter()
+            .enumerate()
+            .map(|(i, [mode, value])| {
+                let msg = CanMessage {
+                    channel: 1,
+                    id: 512,
+                    dlc: 8,
+                    data: [mode, value, 0, 0, 0, 0, 0, 0],
+                    ..Default::default()
+                };
+                (i as u64 + 1, LogObject::CanMessage(msg))
+            })
+            .collect();
+        let ldf_channels = HashMap::new();
+
+        let export = Mf4Export::new(&messages, &dbc_channels, &ldf_channels, None);
+        export.create(&path).unwrap();
+        export
+            .append(
+                &path,
+                &messages,
+                &dbc_channels,
+                &ldf_channels,
+                &TimeOffsets::new(),
+            )
+            .unwrap();
+        export.finish(&path).unwrap();
+        let bytes = std::fs::read(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        // ID, time, A, B and Mode, then one invalidation byte
+        let record = 2 + 4 * 8 + 1;
+        assert_eq!(u64_at(&bytes, DT_OFFSET + 8), 24 + 2 * record);
+        let first = (DT_OFFSET + 24) as usize;
+        assert_eq!(u64_at(&bytes, first as u64 + 10), 5);
+        // B is not in the first frame, A not in the second
+        assert_eq!(bytes[first + record as usize - 1], 0b010);
+        assert_eq!(bytes[first + 2 * record as usize - 1], 0b001);
+
+        let data_group = u64_at(&bytes, HD_OFFSET + 24);
+        let group = u64_at(&bytes, data_group + 24 + 8);
+        assert_eq!(u32_at(&bytes, group + 24 + 48 + 28), 1);
+        let time = u64_at(&bytes, group + 24 + 8);
+        let a = u64_at(&bytes, time + 24);
+        let b = u64_at(&bytes, a + 24);
+        let mode = u64_at(&bytes, b + 24);
+        let flags_and_bit = |channel: u64| {
+            let flags = channel + 24 + 64 + 12;
+            (u32_at(&bytes, flags), u32_at(&bytes, flags + 4))
+        };
+        assert_eq!(flags_and_bit(a), (CN_FLAG_INVALIDATION_BIT, 0));
+        assert_eq!(flags_and_bit(b), (CN_FLAG_INVALIDATION_BIT, 1));
+        assert_eq!(flags_and_bit(mode), (0, 0));
+    }
+}
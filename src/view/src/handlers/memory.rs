@@ -0,0 +1,163 @@
+//! Memory use of loaded logs
+//!
+//! Every frame of a loaded log is held as a [`LogObject`], which is far
+//! larger than the same frame in the compressed BLF. Before a log is loaded
+//! its footprint is estimated from the object count in its header; when that
+//! exceeds the memory limit the user can load it in a reduced mode instead.
+
+use crate::handlers::{BusStats, IdStatsIndex, visit_log_file};
+use blf::{BlfResult, FileStatistics, IdIndex, LogObject, LogSummary};
+use std::path::Path;
+
+/// Memory held by one loaded frame; longer payloads (CAN FD, Ethernet) add their bytes
+pub const MEMORY_PER_FRAME: u64 = std::mem::size_of::<LogObject>() as u64;
+
+/// Smallest object on disk, used when a header does not count its objects
+const MIN_OBJECT_BYTES: u64 = 48;
+
+/// How a log over the memory limit is loaded
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LoadMode {
+    /// Every frame
+    Full,
+    /// The first frames up to the memory limit; statistics cover the whole log
+    Stream,
+    /// No frames, only the statistics of the whole log
+    IndexOnly,
+}
+
+/// Memory taken by `objects` once loaded
+pub fn log_memory(objects: &[LogObject]) -> u64 {
+    objects.len() as u64 * MEMORY_PER_FRAME
+}
+
+/// Objects in a log, from its statistics header; a guess from the size when
+/// the header does not count them
+pub fn estimated_object_count(stats: &FileStatistics) -> u64 {
+    match stats.object_count {
+        0 => stats.uncompressed_file_size / MIN_OBJECT_BYTES,
+        count => count as u64,
+    }
+}
+
+/// Memory a log will take once loaded, from its statistics header
+pub fn estimated_log_memory(stats: &FileStatistics) -> u64 {
+    estimated_object_count(stats) * MEMORY_PER_FRAME
+}
+
+/// Whether `bytes` exceed a limit in megabytes; a limit of 0 is no limit
+pub fn exceeds_memory_limit(bytes: u64, limit_mb: u32) -> bool {
+    limit_mb > 0 && bytes > limit_mb as u64 * 1024 * 1024
+}
+
+/// "512 KB", "37.5 MB", "1.2 GB"
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    match unit {
+        0 | 1 => format!("{:.0} {}", value, UNITS[unit]),
+        _ => format!("{:.1} {}", value, UNITS[unit]),
+    }
+}
+
+/// A log loaded in a reduced mode, with the statistics of all its frames
+pub struct ReducedLog {
+    /// The frames kept; its summary covers the whole file, its ID index the frames kept
+    pub log: BlfResult,
+    pub id_stats: IdStatsIndex,
+    pub bus_stats: BusStats,
+    /// Frames in the file, kept or not
+    pub total_objects: usize,
+}
+
+/// Read the log at `path` keeping at most `limit_bytes` of frames (none for
+/// [`LoadMode::IndexOnly`]); the statistics are built as the frames go by
+pub fn read_log_reduced(
+    path: &Path,
+    mode: LoadMode,
+    limit_bytes: u64,
+) -> Result<ReducedLog, String> {
+    let keep = match mode {
+        LoadMode::Full => usize::MAX,
+        LoadMode::Stream => (limit_bytes / MEMORY_PER_FRAME) as usize,
+        LoadMode::IndexOnly => 0,
+    };
+    let mut objects = Vec::new();
+    let mut id_stats = IdStatsIndex::default();
+    let mut bus_stats = BusStats::new();
+    let mut summary = LogSummary::default();
+    let mut id_index = IdIndex::default();
+    let mut total_objects = 0;
+    let file_stats = visit_log_file(path, |batch| {
+        summary.extend(&batch);
+        id_stats.extend(&batch);
+        bus_stats.extend(&batch);
+        total_objects += batch.len();
+        let room = keep.saturating_sub(objects.len());
+        let kept: Vec<LogObject> = batch.into_iter().take(room).collect();
+        id_index.extend(&kept);
+        objects.extend(kept);
+    })?;
+    Ok(ReducedLog {
+        log: BlfResult {
+            file_stats,
+            objects,
+            summary,
+            id_index,
+        },
+        id_stats,
+        bus_stats,
+        total_objects,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use blf::CanMessage;
+
+    #[test]
+    fn test_reduced_loads() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(3 * 1024), "3 KB");
+        assert_eq!(format_bytes(1536 * 1024 * 1024), "1.5 GB");
+        assert!(exceeds_memory_limit(2 * 1024 * 1024, 1));
+        assert!(!exceeds_memory_limit(u64::MAX, 0));
+
+        let path = std::env::temp_dir().join(format!("canview_memory_{}.blf", std::process::id()));
+        let frames: Vec<LogObject> = (0..100)
+            .map(|i| {
+                let mut msg = CanMessage {
+                    channel: 1,
+                    id: 0x100 + (i % 2),
+                    dlc: 8,
+                    ..Default::default()
+                };
+                msg.header.object_time_stamp = i as u64 * 1_000_000;
+                LogObject::CanMessage(msg)
+            })
+            .collect();
+        blf::write_blf_to_file(&path, blf::SystemTime::from_timestamp_nanos(0), &frames).unwrap();
+
+        let header = crate::handlers::read_log_header(&path).unwrap();
+        assert_eq!(estimated_log_memory(&header), 100 * MEMORY_PER_FRAME);
+
+        let index = read_log_reduced(&path, LoadMode::IndexOnly, u64::MAX).unwrap();
+        assert!(index.log.objects.is_empty());
+        assert_eq!(index.total_objects, 100);
+        assert_eq!(index.log.summary.last_timestamp, Some(99_000_000));
+        assert_eq!(index.id_stats.get(1, 0x100).unwrap().count, 50);
+
+        let stream = read_log_reduced(&path, LoadMode::Stream, 10 * MEMORY_PER_FRAME).unwrap();
+        assert_eq!(stream.log.objects.len(), 10);
+        assert_eq!(stream.log.objects[9].timestamp(), 9_000_000);
+        assert_eq!(stream.total_objects, 100);
+        assert_eq!(stream.bus_stats.channels().count(), 1);
+        let _ = std::fs::remove_file(&path);
+    }
+}
@@ -0,0 +1,206 @@
+//! Merging a second log onto the loaded one
+//!
+//! Both logs count their timestamps from their own measurement start, so
+//! the merged log starts at the earlier of the two and the frames of the
+//! later one are shifted by the time between the starts. Logs without a
+//! start time are taken to start together. [`LogSources`] remembers which
+//! file each object came from, so the log view can tell them apart.
+
+use crate::handlers::{Marker, system_time};
+use blf::{BlfResult, IdIndex, LogObject, LogSummary};
+use chrono::NaiveDateTime;
+use std::path::{Path, PathBuf};
+
+/// Row edge colors of the files of a merged log, in the order they were added
+pub const SOURCE_COLORS: [u32; 6] = [0x22d3ee, 0xf472b6, 0xa3e635, 0xfbbf24, 0xa78bfa, 0xfb923c];
+
+/// The files the loaded log was read from
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LogSources {
+    /// In the order they were added
+    pub files: Vec<PathBuf>,
+    /// Index into `files` of each object; empty while there is only one file
+    pub tags: Vec<u16>,
+}
+
+impl LogSources {
+    /// A log read from `path` alone
+    pub fn single(path: &Path) -> Self {
+        Self {
+            files: vec![path.to_path_buf()],
+            tags: Vec::new(),
+        }
+    }
+
+    /// Whether the log was merged from more than one file
+    pub fn is_merged(&self) -> bool {
+        self.files.len() > 1
+    }
+
+    /// File of the object at `position`
+    pub fn tag(&self, position: usize) -> u16 {
+        self.tags.get(position).copied().unwrap_or(0)
+    }
+
+    /// Row edge color of `source`
+    pub fn color(source: u16) -> u32 {
+        SOURCE_COLORS[usize::from(source) % SOURCE_COLORS.len()]
+    }
+
+    /// File name of `source`
+    pub fn name(&self, source: u16) -> String {
+        self.files
+            .get(usize::from(source))
+            .and_then(|path| path.file_name())
+            .map_or_else(
+                || format!("#{}", source + 1),
+                |name| name.to_string_lossy().into_owned(),
+            )
+    }
+}
+
+/// The loaded log a second one is merged onto
+pub struct MergeBase {
+    /// Objects with the file each came from
+    pub objects: Vec<(LogObject, u16)>,
+    pub files: Vec<PathBuf>,
+    pub start_time: Option<NaiveDateTime>,
+}
+
+impl MergeBase {
+    /// The loaded `messages` with `markers` in place of the log's own
+    ///
+    /// Markers not saved yet travel along as objects of the loaded log; they
+    /// count as its first file.
+    pub fn new(
+        messages: &[LogObject],
+        sources: &LogSources,
+        markers: &[Marker],
+        start_time: Option<NaiveDateTime>,
+    ) -> Self {
+        let mut objects: Vec<(LogObject, u16)> = messages
+            .iter()
+            .enumerate()
+            .filter(|(_, msg)| !matches!(msg, LogObject::GlobalMarker(_)))
+            .map(|(position, msg)| (msg.clone(), sources.tag(position)))
+            .collect();
+        for marker in markers {
+            let at = objects.partition_point(|(msg, _)| msg.timestamp() <= marker.timestamp_ns);
+            objects.insert(at, (marker.to_log_object(), 0));
+        }
+        Self {
+            objects,
+            files: sources.files.clone(),
+            start_time,
+        }
+    }
+}
+
+/// `log`, read from `path`, and `base` on one timeline, in time order
+pub fn merge_logs(base: MergeBase, log: BlfResult, path: &Path) -> (BlfResult, LogSources) {
+    let log_start = crate::handlers::naive_start_time(&log.file_stats.measurement_start_time);
+    let start = match (base.start_time, log_start) {
+        (Some(a), Some(b)) => Some(a.min(b)),
+        (a, b) => a.or(b),
+    };
+    let shift = |from: Option<NaiveDateTime>| match (from, start) {
+        (Some(from), Some(start)) => (from - start).num_nanoseconds().unwrap_or(0).max(0) as u64,
+        _ => 0,
+    };
+    let (base_shift, log_shift) = (shift(base.start_time), shift(log_start));
+
+    let shifted = |objects: Vec<(LogObject, u16)>, by: u64| {
+        objects.into_iter().map(move |(mut object, tag)| {
+            if by > 0 {
+                object.set_timestamp(object.timestamp() + by);
+            }
+            (object, tag)
+        })
+    };
+    let mut files = base.files;
+    let tag = files.len() as u16;
+    files.push(path.to_path_buf());
+    let added = log
+        .objects
+        .into_iter()
+        .map(|object| (object, tag))
+        .collect();
+    let mut tagged: Vec<(LogObject, u16)> = shifted(base.objects, base_shift)
+        .chain(shifted(added, log_shift))
+        .collect();
+    // Stable, so frames sharing a timestamp keep the loaded log first
+    tagged.sort_by_key(|(object, _)| object.timestamp());
+    let (objects, tags): (Vec<LogObject>, Vec<u16>) = tagged.into_iter().unzip();
+
+    let mut file_stats = log.file_stats;
+    file_stats.object_count = objects.len() as u32;
+    if let Some(start) = start {
+        file_stats.measurement_start_time = system_time(start);
+    }
+    let merged = BlfResult {
+        file_stats,
+        summary: LogSummary::from_objects(&objects),
+        id_index: IdIndex::from_objects(&objects),
+        objects,
+    };
+    (merged, LogSources { files, tags })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::handlers::test_support::can;
+
+    #[test]
+    fn test_merge_logs_on_one_timeline() {
+        let at = |seconds: u32| {
+            chrono::NaiveDate::from_ymd_opt(2025, 10, 17)
+                .unwrap()
+                .and_hms_opt(12, 0, seconds)
+                .unwrap()
+        };
+        // The loaded log starts at :10, the added one at :05
+        let loaded = [can(1).build(), can(1).at(2_000_000_000).build()];
+        let base = MergeBase::new(
+            &loaded,
+            &LogSources::single(Path::new("a.blf")),
+            &[],
+            Some(at(10)),
+        );
+        let objects = vec![can(2).build(), can(2).at(6_000_000_000).build()];
+        let log = BlfResult {
+            file_stats: crate::handlers::text_log_statistics(0, &objects, Some(at(5))),
+            objects,
+            summary: Default::default(),
+            id_index: Default::default(),
+        };
+
+        let (merged, sources) = merge_logs(base, log, Path::new("b.blf"));
+        let frames: Vec<(Option<u32>, u64)> = merged
+            .objects
+            .iter()
+            .map(|object| (object.id(), object.timestamp()))
+            .collect();
+        assert_eq!(
+            frames,
+            [
+                (Some(2), 0),
+                (Some(1), 5_000_000_000),
+                (Some(2), 6_000_000_000),
+                (Some(1), 7_000_000_000)
+            ]
+        );
+        assert_eq!(
+            crate::handlers::naive_start_time(&merged.file_stats.measurement_start_time),
+            Some(at(5))
+        );
+        assert_eq!(merged.file_stats.object_count, 4);
+        assert_eq!(merged.id_index.positions(1, 1).len(), 2);
+
+        // Every object keeps the file it came from
+        assert!(sources.is_merged());
+        assert_eq!(sources.tags, [1, 0, 1, 0]);
+        assert_eq!(sources.name(1), "b.blf");
+        assert_ne!(LogSources::color(0), LogSources::color(1));
+    }
+}
@@ -0,0 +1,118 @@
+//! Message density over the whole log, drawn as a strip above the list
+//!
+//! [`Minimap`] divides the measurement into equal time bins and counts the
+//! objects in each, together with the gaps found by [`find_gaps`], so an
+//! hour-long log can be navigated by time instead of by scrollbar position.
+//! Bins holding a highlighted ID carry its color as well.
+
+use crate::handlers::{Highlight, TimeGap, find_gaps, message_highlight};
+use blf::LogObject;
+
+/// Number of time bins drawn across the strip
+pub const MINIMAP_BINS: usize = 400;
+
+/// Object counts per time bin and the large gaps of a log
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Minimap {
+    pub start_ns: u64,
+    pub end_ns: u64,
+    pub bins: Vec<usize>,
+    pub gaps: Vec<TimeGap>,
+    /// Highlight color of the first highlighted object in each bin
+    pub highlights: Vec<Option<u32>>,
+}
+
+impl Minimap {
+    /// Count `messages` into `bins` equal time bins and find gaps over `gap_ns`
+    pub fn build(messages: &[LogObject], bins: usize, gap_ns: u64) -> Self {
+        let start_ns = messages.iter().map(LogObject::timestamp).min().unwrap_or(0);
+        let end_ns = messages.iter().map(LogObject::timestamp).max().unwrap_or(0);
+        let mut minimap = Self {
+            start_ns,
+            end_ns,
+            bins: vec![0; bins.max(1)],
+            gaps: find_gaps(messages, gap_ns),
+            highlights: vec![None; bins.max(1)],
+        };
+        for msg in messages {
+            let bin = minimap.bin_of(msg.timestamp());
+            minimap.bins[bin] += 1;
+        }
+        minimap
+    }
+
+    /// Color the bins holding messages picked out by `highlights`
+    pub fn mark_highlights(&mut self, messages: &[LogObject], highlights: &[Highlight]) {
+        self.highlights.fill(None);
+        if highlights.is_empty() {
+            return;
+        }
+        for msg in messages {
+            let bin = self.bin_of(msg.timestamp());
+            if self.highlights[bin].is_none() {
+                self.highlights[bin] = message_highlight(highlights, msg);
+            }
+        }
+    }
+
+    pub fn max_count(&self) -> usize {
+        self.bins.iter().copied().max().unwrap_or(0)
+    }
+
+    /// Position of `timestamp_ns` across the strip, from 0.0 to 1.0
+    pub fn fraction_of(&self, timestamp_ns: u64) -> f32 {
+        let span = self.end_ns.saturating_sub(self.start_ns);
+        if span == 0 {
+            return 0.0;
+        }
+        (timestamp_ns.saturating_sub(self.start_ns) as f64 / span as f64).min(1.0) as f32
+    }
+
+    /// Bin holding `timestamp_ns`
+    fn bin_of(&self, timestamp_ns: u64) -> usize {
+        let last = self.bins.len() - 1;
+        ((self.fraction_of(timestamp_ns) as f64 * self.bins.len() as f64) as usize).min(last)
+    }
+
+    /// Start time of `bin`
+    pub fn bin_start_ns(&self, bin: usize) -> u64 {
+        let span = self.end_ns.saturating_sub(self.start_ns) as f64;
+        self.start_ns + (span * bin as f64 / self.bins.len() as f64) as u64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::handlers::test_support::can;
+
+    #[test]
+    fn test_minimap_bins_and_gaps() {
+        let messages: Vec<LogObject> = [1_000, 1_500, 2_000, 9_000, 10_000, 11_000]
+            .into_iter()
+            .map(|t| can(0x100).at(t).build())
+            .collect();
+        let mut minimap = Minimap::build(&messages, 5, 2_000);
+
+        assert_eq!(minimap.bins, vec![3, 0, 0, 0, 3]);
+        assert_eq!(minimap.max_count(), 3);
+        assert_eq!(minimap.gaps.len(), 1);
+        assert_eq!(
+            (minimap.gaps[0].start_ns, minimap.gaps[0].end_ns),
+            (2_000, 9_000)
+        );
+        assert_eq!(minimap.bin_start_ns(3), 7_000);
+        assert_eq!(minimap.fraction_of(6_000), 0.5);
+
+        let highlights = crate::handlers::parse_highlights("0x100 red").unwrap();
+        minimap.mark_highlights(&messages[3..], &highlights);
+        assert_eq!(
+            minimap.highlights,
+            vec![None, None, None, None, Some(0xef4444)]
+        );
+
+        let empty = Minimap::build(&[], MINIMAP_BINS, 2_000);
+        assert_eq!(empty.max_count(), 0);
+        assert_eq!(empty.fraction_of(5_000), 0.0);
+    }
+}
@@ -2,8 +2,106 @@
 //!
 //! This module contains event handlers and their helper functions.
 
+pub mod archive;
+pub mod asc;
+pub mod batch;
+pub mod candump;
+pub mod capture;
+pub mod channel_tree;
+pub mod chart;
+pub mod compare;
+pub mod correlation;
+pub mod csv;
+pub mod export;
+pub mod export_plugin;
 pub mod file;
 pub mod filter;
+pub mod filter_file;
+pub mod fixed_view;
+pub mod frame_id;
+pub mod gps;
+pub mod grpc;
+pub mod health;
+pub mod highlight;
+pub mod manifest;
+pub mod markers;
+pub mod mdf;
+pub mod memory;
+pub mod merge;
+pub mod minimap;
+pub mod mqtt;
+pub mod parquet_export;
+pub mod probe;
+pub mod query;
+pub mod replay;
+pub mod row_details;
+pub mod selection;
+pub mod sequence;
+pub mod serve;
+pub mod simulation;
+pub mod socketcan;
+pub mod sqlite;
+pub mod state_machine;
+pub mod stats;
+pub mod table;
+#[cfg(test)]
+pub(crate) mod test_support;
+pub mod time_display;
+pub mod timesync;
+pub mod trc;
+pub mod trigger;
+pub mod vector_xl;
+pub mod video;
+pub mod watch;
+pub mod worker;
 
+pub use archive::*;
+pub use asc::*;
+pub use batch::*;
+pub use candump::*;
+pub use capture::*;
+pub use channel_tree::*;
+pub use chart::*;
+pub use compare::*;
+pub use correlation::*;
+pub use csv::*;
+pub use export::*;
+pub use export_plugin::*;
 pub use file::*;
 pub use filter::*;
+pub use filter_file::*;
+pub use fixed_view::*;
+pub use frame_id::*;
+pub use gps::*;
+pub use grpc::*;
+pub use health::*;
+pub use highlight::*;
+pub use manifest::*;
+pub use markers::*;
+pub use mdf::*;
+pub use memory::*;
+pub use merge::*;
+pub use minimap::*;
+pub use mqtt::*;
+pub use parquet_export::*;
+pub use probe::*;
+pub use query::*;
+pub use replay::*;
+pub use row_details::*;
+pub use selection::*;
+pub use sequence::*;
+pub use serve::*;
+pub use simulation::*;
+pub use socketcan::*;
+pub use sqlite::*;
+pub use state_machine::*;
+pub use stats::*;
+pub use table::*;
+pub use time_display::*;
+pub use timesync::*;
+pub use trc::*;
+pub use trigger::*;
+pub use vector_xl::*;
+pub use video::*;
+pub use watch::*;
+pub use worker::*;
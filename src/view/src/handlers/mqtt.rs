@@ -0,0 +1,340 @@
+//! Publishing decoded signals to an MQTT broker
+//!
+//! While live mode runs, the signals selected in [`MqttSettings`] are
+//! published one topic per signal, `<prefix>/<channel>/<id>/<signal>`, with a
+//! small JSON payload, so IoT dashboards can plot them without knowing about
+//! CAN. Only what that needs of MQTT 3.1.1 is implemented: a clean session
+//! without keep-alive and QoS 0 publishes. The connection lives on its own
+//! thread behind a bounded queue: a slow broker never holds up the UI, and
+//! when it falls too far behind the newest values are dropped (and counted)
+//! rather than piling up in memory.
+
+use crate::handlers::{ExportFrame, SignalFilter, decode_frame};
+use blf::LogObject;
+use parser::dbc::DbcDatabase;
+use parser::ldf::LdfDatabase;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::HashMap;
+use std::io::{Read, Write};
+use std::net::TcpStream;
+use std::sync::mpsc::{Receiver, SyncSender, TryRecvError, TrySendError, channel, sync_channel};
+use std::time::Duration;
+
+/// Broker used when none is configured
+pub const DEFAULT_MQTT_BROKER: &str = "localhost:1883";
+
+/// How long connecting and the broker's CONNACK may take
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Batches queued for the broker before new ones are dropped
+const OUTGOING_CAPACITY: usize = 256;
+
+/// Broker, topics and signals of the MQTT publisher, saved with the configuration
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MqttSettings {
+    /// `host:port` of the broker
+    #[serde(default = "default_broker")]
+    pub broker: String,
+    #[serde(default = "default_topic_prefix")]
+    pub topic_prefix: String,
+    /// Signals to publish; none publishes nothing
+    #[serde(default)]
+    pub signals: Vec<SignalFilter>,
+}
+
+fn default_broker() -> String {
+    DEFAULT_MQTT_BROKER.to_string()
+}
+
+fn default_topic_prefix() -> String {
+    "canview".to_string()
+}
+
+impl Default for MqttSettings {
+    fn default() -> Self {
+        Self {
+            broker: default_broker(),
+            topic_prefix: default_topic_prefix(),
+            signals: Vec::new(),
+        }
+    }
+}
+
+impl MqttSettings {
+    /// Topic and JSON payload of every selected signal in `batch`
+    pub fn messages(
+        &self,
+        batch: &[LogObject],
+        dbc_channels: &HashMap<u16, DbcDatabase>,
+        ldf_channels: &HashMap<u16, LdfDatabase>,
+    ) -> Vec<(String, Vec<u8>)> {
+        let prefix = self.topic_prefix.trim_end_matches('/');
+        let mut messages = Vec::new();
+        for frame in batch.iter().filter_map(ExportFrame::from_log_object) {
+            // Decoding is the expensive part, skip frames nobody asked for
+            if !self.signals.iter().any(|filter| {
+                filter.channel.is_none_or(|c| c == frame.channel)
+                    && filter.id.is_none_or(|i| i == frame.id)
+            }) {
+                continue;
+            }
+            for signal in decode_frame(&frame, dbc_channels, ldf_channels) {
+                if !self
+                    .signals
+                    .iter()
+                    .any(|filter| filter.matches(frame.channel, frame.id, &signal.name))
+                {
+                    continue;
+                }
+                let topic = format!(
+                    "{}/{}/0x{:X}/{}",
+                    prefix, frame.channel, frame.id, signal.name
+                );
+                let payload = json!({
+                    "timestamp_ns": frame.timestamp_ns,
+                    "value": signal.value,
+                    "raw": signal.raw,
+                    "unit": signal.unit,
+                    "text": signal.text,
+                });
+                messages.push((topic, payload.to_string().into_bytes()));
+            }
+        }
+        messages
+    }
+}
+
+/// Append the variable-length "remaining length" of an MQTT fixed header
+fn push_remaining_length(packet: &mut Vec<u8>, mut length: usize) {
+    loop {
+        let mut byte = (length % 128) as u8;
+        length /= 128;
+        if length > 0 {
+            byte |= 0x80;
+        }
+        packet.push(byte);
+        if length == 0 {
+            break;
+        }
+    }
+}
+
+/// Append a length-prefixed UTF-8 string or binary field
+fn push_field(packet: &mut Vec<u8>, field: &[u8]) {
+    packet.extend_from_slice(&(field.len() as u16).to_be_bytes());
+    packet.extend_from_slice(field);
+}
+
+/// CONNECT with a clean session and keep-alive disabled
+pub fn connect_packet(client_id: &str) -> Vec<u8> {
+    let mut body = Vec::new();
+    push_field(&mut body, b"MQTT");
+    body.push(4); // Protocol level 3.1.1
+    body.push(0x02); // Clean session
+    body.extend_from_slice(&0u16.to_be_bytes()); // Keep-alive off
+    push_field(&mut body, client_id.as_bytes());
+
+    let mut packet = vec![0x10];
+    push_remaining_length(&mut packet, body.len());
+    packet.extend(body);
+    packet
+}
+
+/// PUBLISH at QoS 0, which needs no packet identifier or acknowledgement
+pub fn publish_packet(topic: &str, payload: &[u8]) -> Vec<u8> {
+    let mut packet = vec![0x30];
+    push_remaining_length(&mut packet, 2 + topic.len() + payload.len());
+    push_field(&mut packet, topic.as_bytes());
+    packet.extend_from_slice(payload);
+    packet
+}
+
+/// Check a CONNACK; the broker's reason if it refused the connection
+pub fn check_connack(packet: &[u8; 4]) -> Result<(), String> {
+    if packet[0] != 0x20 || packet[1] != 2 {
+        return Err("Not an MQTT broker".to_string());
+    }
+    match packet[3] {
+        0 => Ok(()),
+        1 => Err("Broker refused the protocol version".to_string()),
+        2 => Err("Broker refused the client ID".to_string()),
+        3 => Err("Broker unavailable".to_string()),
+        4 | 5 => Err("Broker requires authentication".to_string()),
+        code => Err(format!("Broker refused the connection ({})", code)),
+    }
+}
+
+fn connect(broker: &str, client_id: &str) -> Result<TcpStream, String> {
+    let addr = std::net::ToSocketAddrs::to_socket_addrs(broker)
+        .map_err(|e| format!("{}: {}", broker, e))?
+        .next()
+        .ok_or_else(|| format!("{}: no address", broker))?;
+    let mut stream = TcpStream::connect_timeout(&addr, CONNECT_TIMEOUT)
+        .map_err(|e| format!("{}: {}", broker, e))?;
+    stream
+        .set_read_timeout(Some(CONNECT_TIMEOUT))
+        .map_err(|e| e.to_string())?;
+    stream
+        .write_all(&connect_packet(client_id))
+        .map_err(|e| format!("{}: {}", broker, e))?;
+    let mut connack = [0u8; 4];
+    stream
+        .read_exact(&mut connack)
+        .map_err(|e| format!("{}: {}", broker, e))?;
+    check_connack(&connack)?;
+    Ok(stream)
+}
+
+/// A connection to the broker, fed from the UI thread
+pub struct MqttPublisher {
+    settings: MqttSettings,
+    outgoing: SyncSender<Vec<(String, Vec<u8>)>>,
+    errors: Receiver<String>,
+    published: usize,
+    dropped: usize,
+}
+
+impl MqttPublisher {
+    /// Connect in the background; failures show up in [`Self::error`]
+    pub fn start(settings: MqttSettings) -> Self {
+        let (outgoing, batches) = sync_channel::<Vec<(String, Vec<u8>)>>(OUTGOING_CAPACITY);
+        let (report, errors) = channel();
+        let broker = settings.broker.clone();
+        let client_id = format!("canview-{}", std::process::id());
+        std::thread::spawn(move || {
+            let mut stream = match connect(&broker, &client_id) {
+                Ok(stream) => stream,
+                Err(e) => {
+                    let _ = report.send(e);
+                    return;
+                }
+            };
+            // Ends when the publisher is dropped
+            for batch in batches {
+                for (topic, payload) in batch {
+                    if let Err(e) = stream.write_all(&publish_packet(&topic, &payload)) {
+                        let _ = report.send(format!("{}: {}", broker, e));
+                        return;
+                    }
+                }
+            }
+            let _ = stream.write_all(&[0xE0, 0x00]); // DISCONNECT
+        });
+        Self {
+            settings,
+            outgoing,
+            errors,
+            published: 0,
+            dropped: 0,
+        }
+    }
+
+    pub fn settings(&self) -> &MqttSettings {
+        &self.settings
+    }
+
+    /// Number of signal values handed to the connection so far
+    pub fn published(&self) -> usize {
+        self.published
+    }
+
+    /// Number of signal values dropped because the broker fell behind
+    pub fn dropped(&self) -> usize {
+        self.dropped
+    }
+
+    /// Queue the selected signals of `batch`, dropping them if the queue is full
+    pub fn publish(
+        &mut self,
+        batch: &[LogObject],
+        dbc_channels: &HashMap<u16, DbcDatabase>,
+        ldf_channels: &HashMap<u16, LdfDatabase>,
+    ) {
+        let messages = self.settings.messages(batch, dbc_channels, ldf_channels);
+        if messages.is_empty() {
+            return;
+        }
+        let count = messages.len();
+        match self.outgoing.try_send(messages) {
+            Ok(()) => self.published += count,
+            Err(TrySendError::Full(_)) => self.dropped += count,
+            // The connection thread is gone; `error` reports why
+            Err(TrySendError::Disconnected(_)) => {}
+        }
+    }
+
+    /// Why the connection failed or dropped, once it has
+    pub fn error(&self) -> Option<String> {
+        match self.errors.try_recv() {
+            Ok(e) => Some(e),
+            Err(TryRecvError::Disconnected) => Some("Connection closed".to_string()),
+            Err(TryRecvError::Empty) => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use blf::CanMessage;
+    use parser::dbc::DbcParser;
+
+    #[test]
+    fn test_packets() {
+        let mut length = Vec::new();
+        push_remaining_length(&mut length, 321);
+        assert_eq!(length, vec![0xC1, 0x02]);
+
+        assert_eq!(
+            connect_packet("cv"),
+            vec![
+                0x10, 14, 0, 4, b'M', b'Q', b'T', b'T', 4, 0x02, 0, 0, 0, 2, b'c', b'v'
+            ]
+        );
+        assert_eq!(
+            publish_packet("a/b", b"{}"),
+            vec![0x30, 7, 0, 3, b'a', b'/', b'b', b'{', b'}']
+        );
+        assert!(check_connack(&[0x20, 2, 0, 0]).is_ok());
+        assert!(check_connack(&[0x20, 2, 0, 5]).is_err());
+        assert!(check_connack(&[0x48, 0x54, 0x54, 0x50]).is_err());
+    }
+
+    #[test]
+    fn test_messages() {
+        let dbc = DbcParser::new()
+            .parse(
+                "BO_ 256 Engine: 8 ECU\n SG_ Speed : 0|16@1+ (1,0) [0|65535] \"rpm\" ECU\n \
+                 SG_ Gear : 16|8@1+ (1,0) [0|8] \"\" ECU\n",
+            )
+            .unwrap();
+        let dbc_channels = HashMap::from([(1, dbc)]);
+        let mut msg = CanMessage::default();
+        msg.header.object_time_stamp = 5_000;
+        msg.channel = 1;
+        msg.id = 0x100;
+        msg.dlc = 8;
+        msg.data[..3].copy_from_slice(&[0xE8, 0x03, 2]);
+        let batch = [LogObject::CanMessage(msg)];
+
+        let settings = MqttSettings {
+            topic_prefix: "car/".to_string(),
+            signals: crate::handlers::parse_signal_filters("0x100 Speed").unwrap(),
+            ..Default::default()
+        };
+        let messages = settings.messages(&batch, &dbc_channels, &HashMap::new());
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].0, "car/1/0x100/Speed");
+        let payload: serde_json::Value = serde_json::from_slice(&messages[0].1).unwrap();
+        assert_eq!(payload["value"], 1000.0);
+        assert_eq!(payload["unit"], "rpm");
+
+        let nothing = MqttSettings::default();
+        assert!(
+            nothing
+                .messages(&batch, &dbc_channels, &HashMap::new())
+                .is_empty()
+        );
+    }
+}
@@ -0,0 +1,266 @@
+//! Parquet export
+//!
+//! One row per bus frame with the columns of the CSV export: `seq`, `time_s`,
+//! `channel`, `type`, `id`, `dlc`, `data` (raw bytes) and, for decoded
+//! exports, `signals` as the CSV writes them. Each chunk passed to
+//! [`ParquetExport::append`] becomes a row group, so a large log is written
+//! without holding it all in memory.
+//!
+//! The arrow and parquet crates are only built with `--features parquet`;
+//! without them the format is listed but disabled.
+
+use crate::handlers::{NumberedMessage, TimeOffsets};
+use parser::dbc::DbcDatabase;
+use parser::ldf::LdfDatabase;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Parquet export kept open across chunks
+///
+/// [`ParquetExport::create`] opens the file, [`ParquetExport::append`] writes
+/// a row group per chunk and [`ParquetExport::finish`] writes the footer.
+pub struct ParquetExport {
+    #[cfg(feature = "parquet")]
+    decoded: bool,
+    #[cfg(feature = "parquet")]
+    writer: std::sync::Mutex<Option<writer::Writer>>,
+}
+
+#[cfg(not(feature = "parquet"))]
+impl ParquetExport {
+    pub fn new(_decoded: bool) -> Self {
+        Self {}
+    }
+
+    pub fn create(&self, _path: &Path) -> Result<(), String> {
+        Err("This build has no Parquet support; rebuild with --features parquet".to_string())
+    }
+
+    pub fn append(
+        &self,
+        _messages: &[NumberedMessage],
+        _dbc_channels: &HashMap<u16, DbcDatabase>,
+        _ldf_channels: &HashMap<u16, LdfDatabase>,
+        _offsets: &TimeOffsets,
+    ) -> Result<usize, String> {
+        Err("The Parquet export was not created".to_string())
+    }
+
+    pub fn finish(&self) -> Result<(), String> {
+        Err("The Parquet export was not created".to_string())
+    }
+}
+
+#[cfg(feature = "parquet")]
+impl ParquetExport {
+    pub fn new(decoded: bool) -> Self {
+        Self {
+            decoded,
+            writer: std::sync::Mutex::new(None),
+        }
+    }
+
+    pub fn create(&self, path: &Path) -> Result<(), String> {
+        let writer = writer::Writer::create(path, self.decoded)
+            .map_err(|e| format!("{}: {}", path.display(), e))?;
+        *self.writer.lock().unwrap() = Some(writer);
+        Ok(())
+    }
+
+    /// Write the bus frames of `messages` as one row group; returns the number written
+    pub fn append(
+        &self,
+        messages: &[NumberedMessage],
+        dbc_channels: &HashMap<u16, DbcDatabase>,
+        ldf_channels: &HashMap<u16, LdfDatabase>,
+        offsets: &TimeOffsets,
+    ) -> Result<usize, String> {
+        let mut writer = self.writer.lock().unwrap();
+        let writer = writer
+            .as_mut()
+            .ok_or("The Parquet export was not created")?;
+        writer.append(messages, dbc_channels, ldf_channels, offsets)
+    }
+
+    /// Write the footer; the file is not readable before
+    pub fn finish(&self) -> Result<(), String> {
+        let writer = self
+            .writer
+            .lock()
+            .unwrap()
+            .take()
+            .ok_or("The Parquet export was not created")?;
+        writer.finish()
+    }
+}
+
+#[cfg(feature = "parquet")]
+mod writer {
+    use super::*;
+    use crate::handlers::{ExportFrame, decode_frame, signals_text};
+    use arrow_array::{
+        ArrayRef, BinaryArray, Float64Array, RecordBatch, StringArray, UInt8Array, UInt16Array,
+        UInt32Array, UInt64Array,
+    };
+    use arrow_schema::{DataType, Field, Schema, SchemaRef};
+    use parquet::arrow::ArrowWriter;
+    use parquet::basic::Compression;
+    use parquet::file::properties::WriterProperties;
+    use std::fs::File;
+    use std::sync::Arc;
+
+    pub struct Writer {
+        schema: SchemaRef,
+        decoded: bool,
+        inner: ArrowWriter<File>,
+    }
+
+    impl Writer {
+        pub fn create(path: &Path, decoded: bool) -> Result<Self, String> {
+            let mut fields = vec![
+                Field::new("seq", DataType::UInt64, false),
+                Field::new("time_s", DataType::Float64, false),
+                Field::new("channel", DataType::UInt16, false),
+                Field::new("type", DataType::Utf8, false),
+                Field::new("id", DataType::UInt32, false),
+                Field::new("dlc", DataType::UInt8, false),
+                Field::new("data", DataType::Binary, false),
+            ];
+            if decoded {
+                fields.push(Field::new("signals", DataType::Utf8, false));
+            }
+            let schema = Arc::new(Schema::new(fields));
+            let file = File::create(path).map_err(|e| e.to_string())?;
+            let properties = WriterProperties::builder()
+                .set_compression(Compression::SNAPPY)
+                .build();
+            let inner = ArrowWriter::try_new(file, schema.clone(), Some(properties))
+                .map_err(|e| e.to_string())?;
+            Ok(Self {
+                schema,
+                decoded,
+                inner,
+            })
+        }
+
+        pub fn append(
+            &mut self,
+            messages: &[NumberedMessage],
+            dbc_channels: &HashMap<u16, DbcDatabase>,
+            ldf_channels: &HashMap<u16, LdfDatabase>,
+            offsets: &TimeOffsets,
+        ) -> Result<usize, String> {
+            let rows: Vec<(u64, ExportFrame)> = messages
+                .iter()
+                .filter_map(|(seq, msg)| Some((*seq, ExportFrame::with_offsets(msg, offsets)?)))
+                .collect();
+            if rows.is_empty() {
+                return Ok(0);
+            }
+            let mut columns: Vec<ArrayRef> = vec![
+                Arc::new(UInt64Array::from_iter_values(
+                    rows.iter().map(|(seq, _)| *seq),
+                )),
+                Arc::new(Float64Array::from_iter_values(
+                    rows.iter()
+                        .map(|(_, frame)| frame.timestamp_ns as f64 / 1_000_000_000.0),
+                )),
+                Arc::new(UInt16Array::from_iter_values(
+                    rows.iter().map(|(_, frame)| frame.channel),
+                )),
+                Arc::new(StringArray::from_iter_values(
+                    rows.iter().map(|(_, frame)| frame.kind),
+                )),
+                Arc::new(UInt32Array::from_iter_values(
+                    rows.iter().map(|(_, frame)| frame.id),
+                )),
+                Arc::new(UInt8Array::from_iter_values(
+                    rows.iter().map(|(_, frame)| frame.data.len() as u8),
+                )),
+                Arc::new(BinaryArray::from_iter_values(
+                    rows.iter().map(|(_, frame)| &frame.data),
+                )),
+            ];
+            if self.decoded {
+                columns.push(Arc::new(StringArray::from_iter_values(rows.iter().map(
+                    |(_, frame)| signals_text(&decode_frame(frame, dbc_channels, ldf_channels)),
+                ))));
+            }
+            let batch =
+                RecordBatch::try_new(self.schema.clone(), columns).map_err(|e| e.to_string())?;
+            self.inner.write(&batch).map_err(|e| e.to_string())?;
+            // One row group per chunk
+            self.inner.flush().map_err(|e| e.to_string())?;
+            Ok(rows.len())
+        }
+
+        pub fn finish(self) -> Result<(), String> {
+            self.inner.close().map(|_| ()).map_err(|e| e.to_string())
+        }
+    }
+}
+
+#[cfg(all(test, feature = "parquet"))]
+mod tests {
+    use super::*;
+    use crate::handlers::numbered_messages;
+    use crate::handlers::test_support::can;
+    use arrow_array::{Array, BinaryArray, Float64Array, StringArray, UInt64Array};
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+    #[test]
+    fn test_parquet_export_reads_back_one_row_group_per_chunk() {
+        let path =
+            std::env::temp_dir().join(format!("canview_export_{}.parquet", std::process::id()));
+        let messages = numbered_messages(
+            &[
+                can(0x100).at(1_000_000_000).data([1; 8]).build(),
+                can(0x200).channel(2).at(2_000_000_000).data([2; 8]).build(),
+                can(0x300).at(3_000_000_000).data([3; 8]).build(),
+            ],
+            0..3,
+        );
+        let (dbc, ldf) = (HashMap::new(), HashMap::new());
+        let mut offsets = TimeOffsets::new();
+        offsets.set(2, 500_000_000);
+
+        let export = ParquetExport::new(false);
+        export.create(&path).unwrap();
+        let rows: usize = messages
+            .chunks(2)
+            .map(|chunk| export.append(chunk, &dbc, &ldf, &offsets).unwrap())
+            .sum();
+        export.finish().unwrap();
+        assert_eq!(rows, 3);
+
+        let file = std::fs::File::open(&path).unwrap();
+        let builder = ParquetRecordBatchReaderBuilder::try_new(file).unwrap();
+        assert_eq!(builder.metadata().num_row_groups(), 2);
+        let (mut seq, mut time, mut kind, mut data) = (vec![], vec![], vec![], vec![]);
+        for batch in builder.build().unwrap() {
+            let batch = batch.unwrap();
+            assert!(batch.column_by_name("signals").is_none());
+            let column = |name: &str| batch.column_by_name(name).unwrap().clone();
+            let column_seq = column("seq");
+            let column_time = column("time_s");
+            let column_kind = column("type");
+            let column_data = column("data");
+            let column_seq = column_seq.as_any().downcast_ref::<UInt64Array>().unwrap();
+            let column_time = column_time.as_any().downcast_ref::<Float64Array>().unwrap();
+            let column_kind = column_kind.as_any().downcast_ref::<StringArray>().unwrap();
+            let column_data = column_data.as_any().downcast_ref::<BinaryArray>().unwrap();
+            for row in 0..batch.num_rows() {
+                seq.push(column_seq.value(row));
+                time.push(column_time.value(row));
+                kind.push(column_kind.value(row).to_string());
+                data.push(column_data.value(row).to_vec());
+            }
+        }
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(seq, vec![1, 2, 3]);
+        assert_eq!(time, vec![1.0, 2.5, 3.0]);
+        assert_eq!(kind, vec!["CAN"; 3]);
+        assert_eq!(data, vec![vec![1; 8], vec![2; 8], vec![3; 8]]);
+    }
+}
@@ -0,0 +1,270 @@
+//! Pre-load summary of a log file
+//!
+//! The Open dialog reads a little of a picked file before loading it. The
+//! format is told by the leading bytes, whatever the extension says: a BLF
+//! starts with `LOGG`, a TRC file with a `;` comment and a candump log with
+//! a `(time)` stamp, each possibly gzipped or zipped. A BLF's statistics
+//! header gives its object count and duration; for text logs both are
+//! estimated from samples at the start and the end of the file.
+
+use crate::handlers::{
+    Compression, estimated_object_count, is_candump_log, is_trc_log, parse_candump, parse_trc,
+    read_log_head,
+};
+use blf::{FileStatistics, LogObject};
+use std::fs::File;
+use std::io::{Cursor, Read, Seek, SeekFrom};
+use std::path::Path;
+
+/// Bytes read from each end of a text log
+const TEXT_SAMPLE_LEN: u64 = 64 * 1024;
+
+/// A log format CanView reads
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogFormat {
+    Blf,
+    Candump,
+    Trc,
+}
+
+impl LogFormat {
+    /// Format of a log starting with `head`, `None` for anything else
+    pub fn detect(head: &[u8]) -> Option<Self> {
+        if head.starts_with(b"LOGG") {
+            Some(Self::Blf)
+        } else if is_trc_log(head) {
+            Some(Self::Trc)
+        } else if is_candump_log(head) {
+            Some(Self::Candump)
+        } else {
+            None
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Blf => "Vector BLF",
+            Self::Candump => "candump log",
+            Self::Trc => "PEAK trace (TRC)",
+        }
+    }
+}
+
+/// What a log file holds, read before loading it
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogProbe {
+    pub format: LogFormat,
+    pub compression: Option<Compression>,
+    /// Size on disk
+    pub file_size: u64,
+    pub objects: u64,
+    /// `objects` was counted rather than estimated
+    pub objects_exact: bool,
+    /// Time from the first to the last object, if it could be told
+    pub duration_ns: Option<u64>,
+}
+
+/// Read enough of the log at `path` to describe it
+pub fn probe_log_file(path: &Path) -> Result<LogProbe, String> {
+    let error = |e: std::io::Error| format!("{}: {}", path.display(), e);
+    let file_size = std::fs::metadata(path).map_err(error)?.len();
+    let (compression, head) = read_log_head(path, TEXT_SAMPLE_LEN)?;
+    let Some(format) = LogFormat::detect(&head) else {
+        return Err(format!("{}: not a BLF, candump or TRC log", path.display()));
+    };
+
+    if format == LogFormat::Blf {
+        let stats = FileStatistics::read(&mut Cursor::new(&head[..]))
+            .map_err(|e| format!("{}: {:?}", path.display(), e))?;
+        let start = stats.measurement_start_time.to_timestamp_nanos();
+        let last = stats.last_object_time.to_timestamp_nanos();
+        return Ok(LogProbe {
+            format,
+            compression,
+            file_size,
+            objects: estimated_object_count(&stats),
+            objects_exact: stats.object_count > 0,
+            duration_ns: (last > start).then(|| (last - start) as u64),
+        });
+    }
+
+    // Text logs are only read unpacked
+    if let Some(compression) = compression {
+        return Err(format!(
+            "{}: a {} {} cannot be opened; unpack it first",
+            path.display(),
+            compression.label(),
+            format.label()
+        ));
+    }
+    let whole = file_size <= TEXT_SAMPLE_LEN;
+    let head = complete_lines(&head, whole);
+    let head_log =
+        parse_text_log(format, head).map_err(|e| format!("{}: {}", path.display(), e))?;
+    let objects = if whole || head.is_empty() {
+        head_log.objects.len() as u64
+    } else {
+        (head_log.objects.len() as f64 * file_size as f64 / head.len() as f64).round() as u64
+    };
+    let duration_ns = if whole {
+        head_log.objects.iter().map(LogObject::timestamp).max()
+    } else {
+        read_tail(path, file_size)
+            .ok()
+            .and_then(|tail| text_log_duration(format, head, &tail, &head_log))
+    };
+    Ok(LogProbe {
+        format,
+        compression,
+        file_size,
+        objects,
+        objects_exact: whole,
+        duration_ns,
+    })
+}
+
+/// Objects of a text log sample and the time they are counted from
+struct TextSample {
+    objects: Vec<LogObject>,
+    start_time: Option<chrono::NaiveDateTime>,
+}
+
+fn parse_text_log(format: LogFormat, text: &str) -> Result<TextSample, String> {
+    match format {
+        LogFormat::Candump => parse_candump(text).map(|log| TextSample {
+            objects: log.objects,
+            start_time: log.start_time,
+        }),
+        LogFormat::Trc => parse_trc(text).map(|log| TextSample {
+            objects: log.objects,
+            start_time: log.start_time,
+        }),
+        LogFormat::Blf => Ok(TextSample {
+            objects: Vec::new(),
+            start_time: None,
+        }),
+    }
+}
+
+/// `sample` up to its last line break, the whole of it if it is the whole file
+fn complete_lines(sample: &[u8], whole: bool) -> &str {
+    let end = if whole {
+        sample.len()
+    } else {
+        sample
+            .iter()
+            .rposition(|&b| b == b'\n')
+            .map_or(0, |i| i + 1)
+    };
+    // Older PCAN-View versions write the local code page
+    std::str::from_utf8(&sample[..end])
+        .unwrap_or_else(|e| std::str::from_utf8(&sample[..e.valid_up_to()]).unwrap_or_default())
+}
+
+/// The last lines of the file, without the partial line they start in
+fn read_tail(path: &Path, file_size: u64) -> std::io::Result<String> {
+    let mut file = File::open(path)?;
+    file.seek(SeekFrom::Start(file_size.saturating_sub(TEXT_SAMPLE_LEN)))?;
+    let mut tail = Vec::new();
+    file.read_to_end(&mut tail)?;
+    let start = tail
+        .iter()
+        .position(|&b| b == b'\n')
+        .map_or(tail.len(), |i| i + 1);
+    Ok(String::from_utf8_lossy(&tail[start..]).into_owned())
+}
+
+/// Time from the first object of a text log to the last, from its `head`
+/// and `tail` lines
+///
+/// candump lines carry absolute times, so the tail is parsed on its own. TRC
+/// times count from the start of the file and need the column layout of
+/// its header, so the tail is parsed behind the header's comment lines.
+fn text_log_duration(
+    format: LogFormat,
+    head: &str,
+    tail: &str,
+    head_log: &TextSample,
+) -> Option<u64> {
+    let tail_text = match format {
+        LogFormat::Trc => {
+            let header: String = head
+                .lines()
+                .take_while(|line| line.trim_start().starts_with(';'))
+                .map(|line| format!("{}\n", line))
+                .collect();
+            header + tail
+        }
+        LogFormat::Candump | LogFormat::Blf => tail.to_string(),
+    };
+    let tail_log = parse_text_log(format, &tail_text).ok()?;
+    let last = tail_log.objects.iter().map(LogObject::timestamp).max()?;
+    match format {
+        LogFormat::Trc => Some(last),
+        LogFormat::Candump | LogFormat::Blf => {
+            let first = head_log.objects.iter().map(LogObject::timestamp).min()?;
+            let offset = (tail_log.start_time? - head_log.start_time?).num_nanoseconds()?;
+            (last as i64 + offset - first as i64).try_into().ok()
+        }
+    }
+}
+
+/// "45.0 s", "12 min 05 s", "2 h 03 min"
+pub fn format_log_duration(duration_ns: u64) -> String {
+    let seconds = duration_ns as f64 / 1_000_000_000.0;
+    let whole = seconds as u64;
+    match whole {
+        0..60 => format!("{:.1} s", seconds),
+        60..3_600 => format!("{} min {:02} s", whole / 60, whole % 60),
+        _ => format!("{} h {:02} min", whole / 3_600, whole % 3_600 / 60),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_probe_tells_formats_by_content() {
+        let dir = std::env::temp_dir().join(format!("canview_probe_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        // A candump log named like a BLF, long enough to be sampled
+        let lines: String = (0..10_000)
+            .map(|i| format!("(1760686200.{:06}) can0 1A0#0102030405060708\n", i * 100))
+            .collect();
+        let path = dir.join("run.blf");
+        std::fs::write(&path, &lines).unwrap();
+        let probe = probe_log_file(&path).unwrap();
+        assert_eq!(probe.format, LogFormat::Candump);
+        assert_eq!(probe.compression, None);
+        assert!(!probe.objects_exact);
+        assert!(
+            (9_900..=10_100).contains(&probe.objects),
+            "{}",
+            probe.objects
+        );
+        assert_eq!(probe.duration_ns, Some(999_900_000));
+
+        // A short TRC file is read whole
+        let trc = ";$FILEVERSION=1.1\n\
+                   ;$STARTTIME=45217.4236111111\n\
+                   \x20     1)      1059.9  Rx         0300  8  00 00 00 00 04 00 00 00\n\
+                   \x20     2)      3059.9  Rx         0300  8  00 00 00 00 04 00 00 00\n";
+        let path = dir.join("trace.log");
+        std::fs::write(&path, trc).unwrap();
+        let probe = probe_log_file(&path).unwrap();
+        assert_eq!(probe.format, LogFormat::Trc);
+        assert_eq!((probe.objects, probe.objects_exact), (2, true));
+        assert_eq!(probe.duration_ns, Some(3_059_900_000));
+
+        let path = dir.join("notes.trc");
+        std::fs::write(&path, "not a log").unwrap();
+        assert!(probe_log_file(&path).is_err());
+        let _ = std::fs::remove_dir_all(&dir);
+
+        assert_eq!(format_log_duration(12_340_000_000), "12.3 s");
+        assert_eq!(format_log_duration(725_000_000_000), "12 min 05 s");
+        assert_eq!(format_log_duration(7_380_000_000_000), "2 h 03 min");
+    }
+}
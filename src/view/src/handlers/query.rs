@@ -0,0 +1,779 @@
+//! Scripted queries over a log
+//!
+//! `canview query in.blf --select "time,id,EngineSpeed" --where "ch==1 && id==0x1A0"`
+//! prints one row per matching frame, so analyses can be scripted without
+//! the GUI. A [`Query`] is a projection ([`QueryColumn`]s) and a selection
+//! ([`QueryCondition`]) over the bus frames of a log; names that are not frame
+//! fields are decoded signals.
+//!
+//! Frame fields: `seq` (sequence number, as in the viewer's `#` column),
+//! `time` (seconds from the start), `time_ns`, `ch` /
+//! `channel`, `id`, `type` (`CAN`, `CAN2`, `CAN_FD`, `CAN_FD64`, `LIN`),
+//! `dlc`, `data` and `byte0`, `byte1`, …
+
+use crate::handlers::{
+    ExportFrame, csv_escape, decode_frame, load_channel_databases, parse_database_option, parse_id,
+    read_log_file, sequence_number,
+};
+use crate::models::ChannelType;
+use blf::LogObject;
+use parser::dbc::DbcDatabase;
+use parser::ldf::LdfDatabase;
+use serde_json::{Map, Value as JsonValue};
+use std::collections::HashMap;
+use std::fmt;
+use std::path::PathBuf;
+
+/// Columns printed when `--select` is not given
+pub const DEFAULT_SELECT: &str = "seq,time,ch,type,id,dlc,data";
+
+pub const QUERY_USAGE: &str = "\
+Usage: canview query FILE.blf [--select COLUMNS] [--where CONDITION] [--format csv|json|table]
+                     [--output FILE] [--config FILE] [--dbc CH=PATH]... [--ldf CH=PATH]...
+
+  --select   Comma-separated frame fields and signal names (default seq,time,ch,type,id,dlc,data)
+  --where    e.g. \"ch==1 && id==0x1A0 && EngineSpeed > 3000\"; && || ! and parentheses
+  --format   csv (default), json or table
+  --output   Write to a file instead of standard output
+  --config   Configuration whose channel mappings provide the databases
+  --dbc      DBC for a CAN channel, e.g. --dbc 1=powertrain.dbc
+  --ldf      LDF for a LIN channel, e.g. --ldf 2=body.ldf";
+
+/// A field of the frame itself
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FrameField {
+    /// Sequence number of the frame in the log, as in the viewer and exports
+    Seq,
+    Time,
+    TimeNs,
+    Channel,
+    Id,
+    Type,
+    Dlc,
+    Data,
+    Byte(usize),
+}
+
+impl FrameField {
+    fn from_name(name: &str) -> Option<Self> {
+        let field = match name.to_ascii_lowercase().as_str() {
+            "seq" => FrameField::Seq,
+            "time" => FrameField::Time,
+            "time_ns" => FrameField::TimeNs,
+            "ch" | "channel" => FrameField::Channel,
+            "id" => FrameField::Id,
+            "type" => FrameField::Type,
+            "dlc" => FrameField::Dlc,
+            "data" => FrameField::Data,
+            other => FrameField::Byte(other.strip_prefix("byte")?.parse().ok()?),
+        };
+        Some(field)
+    }
+}
+
+/// A projected or compared value: a frame field or a decoded signal
+#[derive(Debug, Clone, PartialEq)]
+pub enum QueryColumn {
+    FrameField(FrameField),
+    Signal(String),
+}
+
+impl QueryColumn {
+    fn from_name(name: &str) -> Self {
+        FrameField::from_name(name).map_or_else(
+            || QueryColumn::Signal(name.to_string()),
+            QueryColumn::FrameField,
+        )
+    }
+
+    fn is_signal(&self) -> bool {
+        matches!(self, QueryColumn::Signal(_))
+    }
+}
+
+/// The value of a column on one frame
+#[derive(Debug, Clone, PartialEq)]
+pub enum QueryValue {
+    Number(f64),
+    Text(String),
+}
+
+impl fmt::Display for QueryValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            QueryValue::Number(number) => write!(f, "{}", number),
+            QueryValue::Text(text) => write!(f, "{}", text),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CompareOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+impl CompareOp {
+    fn holds(self, left: &QueryValue, right: &QueryValue) -> bool {
+        let ordering = match (left, right) {
+            (QueryValue::Number(a), QueryValue::Number(b)) => a.partial_cmp(b),
+            (QueryValue::Text(a), QueryValue::Text(b)) => {
+                Some(a.to_ascii_lowercase().cmp(&b.to_ascii_lowercase()))
+            }
+            _ => None,
+        };
+        let Some(ordering) = ordering else {
+            return false;
+        };
+        match self {
+            CompareOp::Eq => ordering.is_eq(),
+            CompareOp::Ne => ordering.is_ne(),
+            CompareOp::Lt => ordering.is_lt(),
+            CompareOp::Le => ordering.is_le(),
+            CompareOp::Gt => ordering.is_gt(),
+            CompareOp::Ge => ordering.is_ge(),
+        }
+    }
+}
+
+/// One side of a comparison
+#[derive(Debug, Clone, PartialEq)]
+pub enum QueryOperand {
+    QueryColumn(QueryColumn),
+    Literal(QueryValue),
+}
+
+/// The `--where` condition
+#[derive(Debug, Clone, PartialEq)]
+pub enum QueryCondition {
+    Compare(QueryOperand, CompareOp, QueryOperand),
+    And(Box<QueryCondition>, Box<QueryCondition>),
+    Or(Box<QueryCondition>, Box<QueryCondition>),
+    Not(Box<QueryCondition>),
+}
+
+impl QueryCondition {
+    /// Whether the condition reads any decoded signal
+    fn uses_signals(&self) -> bool {
+        match self {
+            QueryCondition::Compare(left, _, right) => [left, right]
+                .iter()
+                .any(|operand| matches!(operand, QueryOperand::QueryColumn(column) if column.is_signal())),
+            QueryCondition::And(a, b) | QueryCondition::Or(a, b) => a.uses_signals() || b.uses_signals(),
+            QueryCondition::Not(condition) => condition.uses_signals(),
+        }
+    }
+
+    /// Indices of the frames in `messages` the condition holds for
+    pub fn matching_indices(
+        &self,
+        messages: &[LogObject],
+        dbc_channels: &HashMap<u16, DbcDatabase>,
+        ldf_channels: &HashMap<u16, LdfDatabase>,
+    ) -> Vec<usize> {
+        frame_rows(messages, self.uses_signals(), dbc_channels, ldf_channels)
+            .filter(|(_, row)| self.holds(row))
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    /// A comparison with a signal the frame does not carry is false
+    fn holds(&self, row: &Row) -> bool {
+        match self {
+            QueryCondition::Compare(left, op, right) => {
+                match (row.operand(left), row.operand(right)) {
+                    (Some(left), Some(right)) => op.holds(&left, &right),
+                    _ => false,
+                }
+            }
+            QueryCondition::And(a, b) => a.holds(row) && b.holds(row),
+            QueryCondition::Or(a, b) => a.holds(row) || b.holds(row),
+            QueryCondition::Not(condition) => !condition.holds(row),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Word(String),
+    Number(f64),
+    Text(String),
+    Compare(CompareOp),
+    And,
+    Or,
+    Not,
+    Open,
+    Close,
+}
+
+fn tokenize(text: &str) -> Result<Vec<Token>, String> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+        let (token, len) = match (c, next) {
+            (c, _) if c.is_whitespace() => {
+                i += 1;
+                continue;
+            }
+            ('&', Some('&')) => (Token::And, 2),
+            ('|', Some('|')) => (Token::Or, 2),
+            ('=', Some('=')) => (Token::Compare(CompareOp::Eq), 2),
+            ('!', Some('=')) => (Token::Compare(CompareOp::Ne), 2),
+            ('<', Some('=')) => (Token::Compare(CompareOp::Le), 2),
+            ('>', Some('=')) => (Token::Compare(CompareOp::Ge), 2),
+            ('<', _) => (Token::Compare(CompareOp::Lt), 1),
+            ('>', _) => (Token::Compare(CompareOp::Gt), 1),
+            ('!', _) => (Token::Not, 1),
+            ('(', _) => (Token::Open, 1),
+            (')', _) => (Token::Close, 1),
+            ('"' | '\'', _) => {
+                let end = chars[i + 1..]
+                    .iter()
+                    .position(|&ch| ch == c)
+                    .ok_or("Unterminated string")?;
+                let text: String = chars[i + 1..i + 1 + end].iter().collect();
+                (Token::Text(text), end + 2)
+            }
+            (c, _) if c.is_alphanumeric() || c == '_' || c == '.' || c == '-' => {
+                let len = 1 + chars[i + 1..]
+                    .iter()
+                    .position(|&ch| !(ch.is_alphanumeric() || ch == '_' || ch == '.'))
+                    .unwrap_or(chars.len() - i - 1);
+                let word: String = chars[i..i + len].iter().collect();
+                let number = if word.starts_with(|ch: char| ch.is_ascii_digit() || ch == '-') {
+                    let hex = word.starts_with("0x") || word.starts_with("0X");
+                    let parsed = if hex {
+                        parse_id(&word).ok().map(f64::from)
+                    } else {
+                        word.parse().ok()
+                    };
+                    Some(parsed.ok_or_else(|| format!("Invalid number: '{}'", word))?)
+                } else {
+                    None
+                };
+                match number {
+                    Some(number) => (Token::Number(number), len),
+                    None => (Token::Word(word), len),
+                }
+            }
+            (c, _) => return Err(format!("Unexpected '{}'", c)),
+        };
+        tokens.push(token);
+        i += len;
+    }
+    Ok(tokens)
+}
+
+/// Recursive descent over the tokens: `||` binds weaker than `&&`, `!` and
+/// parentheses bind tightest
+struct ConditionParser {
+    tokens: Vec<Token>,
+    next: usize,
+}
+
+impl ConditionParser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.next)
+    }
+
+    fn take(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.next).cloned();
+        self.next += 1;
+        token
+    }
+
+    fn or(&mut self) -> Result<QueryCondition, String> {
+        let mut condition = self.and()?;
+        while self.peek() == Some(&Token::Or) {
+            self.next += 1;
+            condition = QueryCondition::Or(Box::new(condition), Box::new(self.and()?));
+        }
+        Ok(condition)
+    }
+
+    fn and(&mut self) -> Result<QueryCondition, String> {
+        let mut condition = self.unary()?;
+        while self.peek() == Some(&Token::And) {
+            self.next += 1;
+            condition = QueryCondition::And(Box::new(condition), Box::new(self.unary()?));
+        }
+        Ok(condition)
+    }
+
+    fn unary(&mut self) -> Result<QueryCondition, String> {
+        match self.peek() {
+            Some(Token::Not) => {
+                self.next += 1;
+                Ok(QueryCondition::Not(Box::new(self.unary()?)))
+            }
+            Some(Token::Open) => {
+                self.next += 1;
+                let condition = self.or()?;
+                match self.take() {
+                    Some(Token::Close) => Ok(condition),
+                    _ => Err("Missing ')'".to_string()),
+                }
+            }
+            _ => self.comparison(),
+        }
+    }
+
+    fn comparison(&mut self) -> Result<QueryCondition, String> {
+        let left = self.operand()?;
+        let op = match self.take() {
+            Some(Token::Compare(op)) => op,
+            _ => return Err("Expected ==, !=, <, <=, > or >=".to_string()),
+        };
+        let mut right = self.operand()?;
+        // `type == CAN_FD` reads naturally; the bare word is a type, not a signal
+        if left == QueryOperand::QueryColumn(QueryColumn::FrameField(FrameField::Type))
+            && let QueryOperand::QueryColumn(QueryColumn::Signal(word)) = &right
+        {
+            right = QueryOperand::Literal(QueryValue::Text(word.clone()));
+        }
+        Ok(QueryCondition::Compare(left, op, right))
+    }
+
+    fn operand(&mut self) -> Result<QueryOperand, String> {
+        match self.take() {
+            Some(Token::Word(word)) => Ok(QueryOperand::QueryColumn(QueryColumn::from_name(&word))),
+            Some(Token::Number(number)) => Ok(QueryOperand::Literal(QueryValue::Number(number))),
+            Some(Token::Text(text)) => Ok(QueryOperand::Literal(QueryValue::Text(text))),
+            Some(token) => Err(format!("Unexpected {:?}", token)),
+            None => Err("QueryCondition ends too early".to_string()),
+        }
+    }
+}
+
+/// Parse a `--where` condition
+pub fn parse_condition(text: &str) -> Result<QueryCondition, String> {
+    let mut parser = ConditionParser {
+        tokens: tokenize(text)?,
+        next: 0,
+    };
+    let condition = parser.or()?;
+    match parser.peek() {
+        None => Ok(condition),
+        Some(token) => Err(format!("Unexpected {:?}", token)),
+    }
+}
+
+/// Parse a `--select` list
+pub fn parse_columns(text: &str) -> Result<Vec<(String, QueryColumn)>, String> {
+    let columns: Vec<(String, QueryColumn)> = text
+        .split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(|name| (name.to_string(), QueryColumn::from_name(name)))
+        .collect();
+    if columns.is_empty() {
+        return Err("Select at least one column".to_string());
+    }
+    Ok(columns)
+}
+
+/// One frame with the values of its decoded signals
+struct Row {
+    seq: u64,
+    frame: ExportFrame,
+    signals: HashMap<String, f64>,
+}
+
+impl Row {
+    fn value(&self, column: &QueryColumn) -> Option<QueryValue> {
+        let frame = &self.frame;
+        let value = match column {
+            QueryColumn::FrameField(FrameField::Seq) => QueryValue::Number(self.seq as f64),
+            QueryColumn::FrameField(FrameField::Time) => {
+                QueryValue::Number(frame.timestamp_ns as f64 / 1e9)
+            }
+            QueryColumn::FrameField(FrameField::TimeNs) => {
+                QueryValue::Number(frame.timestamp_ns as f64)
+            }
+            QueryColumn::FrameField(FrameField::Channel) => {
+                QueryValue::Number(frame.channel as f64)
+            }
+            QueryColumn::FrameField(FrameField::Id) => QueryValue::Number(frame.id as f64),
+            QueryColumn::FrameField(FrameField::Type) => QueryValue::Text(frame.kind.to_string()),
+            QueryColumn::FrameField(FrameField::Dlc) => QueryValue::Number(frame.data.len() as f64),
+            QueryColumn::FrameField(FrameField::Data) => QueryValue::Text(frame.data_hex()),
+            QueryColumn::FrameField(FrameField::Byte(index)) => {
+                QueryValue::Number(*frame.data.get(*index)? as f64)
+            }
+            QueryColumn::Signal(name) => QueryValue::Number(*self.signals.get(name)?),
+        };
+        Some(value)
+    }
+
+    fn operand(&self, operand: &QueryOperand) -> Option<QueryValue> {
+        match operand {
+            QueryOperand::QueryColumn(column) => self.value(column),
+            QueryOperand::Literal(value) => Some(value.clone()),
+        }
+    }
+
+    /// Text of a cell; IDs in hex and times with fixed precision like the export
+    fn cell(&self, column: &QueryColumn) -> String {
+        match (column, self.value(column)) {
+            (QueryColumn::FrameField(FrameField::Id), _) => format!("0x{:X}", self.frame.id),
+            (QueryColumn::FrameField(FrameField::Time), Some(QueryValue::Number(seconds))) => {
+                format!("{:.6}", seconds)
+            }
+            (_, Some(value)) => value.to_string(),
+            (_, None) => String::new(),
+        }
+    }
+}
+
+/// The bus frames of `messages` with their index, decoded only when `decode` is set
+fn frame_rows<'a>(
+    messages: &'a [LogObject],
+    decode: bool,
+    dbc_channels: &'a HashMap<u16, DbcDatabase>,
+    ldf_channels: &'a HashMap<u16, LdfDatabase>,
+) -> impl Iterator<Item = (usize, Row)> + 'a {
+    messages.iter().enumerate().filter_map(move |(index, msg)| {
+        let frame = ExportFrame::from_log_object(msg)?;
+        let signals = if decode {
+            decode_frame(&frame, dbc_channels, ldf_channels)
+                .into_iter()
+                .map(|signal| (signal.name, signal.value))
+                .collect()
+        } else {
+            HashMap::new()
+        };
+        let row = Row {
+            seq: sequence_number(index),
+            frame,
+            signals,
+        };
+        Some((index, row))
+    })
+}
+
+/// Output format of `canview query`
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum QueryFormat {
+    #[default]
+    Csv,
+    Json,
+    /// Aligned columns for reading in a terminal
+    Table,
+}
+
+/// A projection and selection over the frames of a log
+#[derive(Debug, Clone, PartialEq)]
+pub struct Query {
+    pub columns: Vec<(String, QueryColumn)>,
+    pub condition: Option<QueryCondition>,
+}
+
+impl Query {
+    pub fn parse(select: &str, condition: Option<&str>) -> Result<Self, String> {
+        Ok(Self {
+            columns: parse_columns(select)?,
+            condition: condition.map(parse_condition).transpose()?,
+        })
+    }
+
+    /// Rows of the matching frames, one cell per column
+    pub fn run(
+        &self,
+        messages: &[LogObject],
+        dbc_channels: &HashMap<u16, DbcDatabase>,
+        ldf_channels: &HashMap<u16, LdfDatabase>,
+    ) -> Vec<Vec<String>> {
+        let decode = self.columns.iter().any(|(_, column)| column.is_signal())
+            || self
+                .condition
+                .as_ref()
+                .is_some_and(QueryCondition::uses_signals);
+        frame_rows(messages, decode, dbc_channels, ldf_channels)
+            .filter(|(_, row)| {
+                self.condition
+                    .as_ref()
+                    .is_none_or(|condition| condition.holds(row))
+            })
+            .map(|(_, row)| {
+                self.columns
+                    .iter()
+                    .map(|(_, column)| row.cell(column))
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Format the rows with a header naming the columns
+    pub fn format(&self, rows: &[Vec<String>], format: QueryFormat) -> String {
+        let names: Vec<&str> = self.columns.iter().map(|(name, _)| name.as_str()).collect();
+        match format {
+            QueryFormat::Csv => {
+                std::iter::once(names.iter().map(|name| csv_escape(name)).collect())
+                    .chain(
+                        rows.iter()
+                            .map(|row| row.iter().map(|cell| csv_escape(cell)).collect()),
+                    )
+                    .map(|cells: Vec<String>| cells.join(",") + "\n")
+                    .collect()
+            }
+            QueryFormat::Json => {
+                let rows: Vec<JsonValue> = rows
+                    .iter()
+                    .map(|row| {
+                        let object: Map<String, JsonValue> = names
+                            .iter()
+                            .zip(row)
+                            .map(|(name, cell)| (name.to_string(), json_cell(cell)))
+                            .collect();
+                        JsonValue::Object(object)
+                    })
+                    .collect();
+                serde_json::to_string_pretty(&rows).unwrap_or_default() + "\n"
+            }
+            QueryFormat::Table => {
+                let widths: Vec<usize> = names
+                    .iter()
+                    .enumerate()
+                    .map(|(index, name)| {
+                        rows.iter()
+                            .map(|row| row[index].len())
+                            .chain([name.len()])
+                            .max()
+                            .unwrap_or(0)
+                    })
+                    .collect();
+                let line = |cells: Vec<&str>| {
+                    cells
+                        .iter()
+                        .zip(&widths)
+                        .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+                        .collect::<Vec<_>>()
+                        .join("  ")
+                        .trim_end()
+                        .to_string()
+                        + "\n"
+                };
+                std::iter::once(line(names.clone()))
+                    .chain(
+                        rows.iter()
+                            .map(|row| line(row.iter().map(String::as_str).collect())),
+                    )
+                    .collect()
+            }
+        }
+    }
+}
+
+/// Numbers stay numbers in JSON; IDs, payloads and types stay text
+fn json_cell(cell: &str) -> JsonValue {
+    if cell.is_empty() {
+        return JsonValue::Null;
+    }
+    cell.parse::<f64>()
+        .ok()
+        .and_then(serde_json::Number::from_f64)
+        .map_or_else(|| JsonValue::String(cell.to_string()), JsonValue::Number)
+}
+
+/// Command line of `canview query`
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueryOptions {
+    pub input: PathBuf,
+    pub query: Query,
+    pub format: QueryFormat,
+    pub output: Option<PathBuf>,
+    pub config: Option<PathBuf>,
+    /// Databases as (channel type, channel, path)
+    pub databases: Vec<(ChannelType, u16, PathBuf)>,
+}
+
+impl QueryOptions {
+    /// Parse the arguments following `query`
+    pub fn parse(args: &[String]) -> Result<Self, String> {
+        let mut input = None;
+        let mut select = DEFAULT_SELECT.to_string();
+        let mut condition = None;
+        let mut format = QueryFormat::default();
+        let mut output = None;
+        let mut config = None;
+        let mut databases = Vec::new();
+
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            let mut value = || {
+                args.next()
+                    .cloned()
+                    .ok_or_else(|| format!("Missing value after {}", arg))
+            };
+            match arg.as_str() {
+                "--select" => select = value()?,
+                "--where" => condition = Some(value()?),
+                "--format" => {
+                    format = match value()?.to_ascii_lowercase().as_str() {
+                        "csv" => QueryFormat::Csv,
+                        "json" => QueryFormat::Json,
+                        "table" => QueryFormat::Table,
+                        other => return Err(format!("Unknown format '{}'", other)),
+                    }
+                }
+                "--output" => output = Some(PathBuf::from(value()?)),
+                "--config" => config = Some(PathBuf::from(value()?)),
+                "--dbc" | "--ldf" => databases.push(parse_database_option(arg, &value()?)?),
+                other if other.starts_with("--") => {
+                    return Err(format!("Unknown option '{}'", other));
+                }
+                other if input.is_none() => input = Some(PathBuf::from(other)),
+                other => return Err(format!("Unexpected argument '{}'", other)),
+            }
+        }
+
+        Ok(Self {
+            input: input.ok_or("Missing the BLF file to query")?,
+            query: Query::parse(&select, condition.as_deref())
+                .map_err(|e| format!("Invalid query: {}", e))?,
+            format,
+            output,
+            config,
+            databases,
+        })
+    }
+}
+
+/// Run a query from the command line; returns the number of rows
+pub fn run_query(options: &QueryOptions) -> Result<usize, String> {
+    let (dbc_channels, ldf_channels) =
+        load_channel_databases(options.config.as_deref(), &options.databases)?;
+    let result = read_log_file(&options.input)?;
+    let rows = options
+        .query
+        .run(&result.objects, &dbc_channels, &ldf_channels);
+    let text = options.query.format(&rows, options.format);
+    match &options.output {
+        Some(path) => {
+            std::fs::write(path, text).map_err(|e| format!("{}: {}", path.display(), e))?
+        }
+        None => print!("{}", text),
+    }
+    Ok(rows.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::handlers::test_support::can;
+    use parser::dbc::DbcParser;
+
+    #[test]
+    fn test_parse_condition() {
+        let condition = parse_condition("ch==1 && (id==0x1A0 || !type == CAN_FD)").unwrap();
+        let QueryCondition::And(left, right) = condition else {
+            panic!("expected &&");
+        };
+        assert_eq!(
+            *left,
+            QueryCondition::Compare(
+                QueryOperand::QueryColumn(QueryColumn::FrameField(FrameField::Channel)),
+                CompareOp::Eq,
+                QueryOperand::Literal(QueryValue::Number(1.0))
+            )
+        );
+        let QueryCondition::Or(_, not) = *right else {
+            panic!("expected ||");
+        };
+        assert_eq!(
+            *not,
+            QueryCondition::Not(Box::new(QueryCondition::Compare(
+                QueryOperand::QueryColumn(QueryColumn::FrameField(FrameField::Type)),
+                CompareOp::Eq,
+                QueryOperand::Literal(QueryValue::Text("CAN_FD".to_string()))
+            )))
+        );
+
+        assert!(parse_condition("EngineSpeed >= -40.5").is_ok());
+        assert!(parse_condition("ch == 1 &&").is_err());
+        assert!(parse_condition("(ch == 1").is_err());
+        assert!(parse_condition("ch 1").is_err());
+        assert!(parse_condition("name == 'unterminated").is_err());
+    }
+
+    #[test]
+    fn test_query_run() {
+        let dbc = DbcParser::new()
+            .parse("BO_ 416 Engine: 8 ECU\n SG_ EngineSpeed : 0|16@1+ (1,0) [0|8000] \"rpm\" ECU\n")
+            .unwrap();
+        let dbc_channels = HashMap::from([(1, dbc)]);
+        let messages = vec![
+            can(0x1A0)
+                .at(1_000_000)
+                .data([0xB8, 0x0B, 0, 0, 0, 0, 0, 0])
+                .build(), // 3000 rpm
+            can(0x1A0)
+                .channel(2)
+                .at(2_000_000)
+                .data([0xA0, 0x0F, 0, 0, 0, 0, 0, 0])
+                .build(),
+            can(0x1A0)
+                .at(3_000_000)
+                .data([0xA0, 0x0F, 0, 0, 0, 0, 0, 0])
+                .build(), // 4000 rpm
+            can(0x200).at(4_000_000).data([0; 8]).build(),
+        ];
+
+        let query = Query::parse("time,id,EngineSpeed", Some("ch==1 && id==0x1A0")).unwrap();
+        let rows = query.run(&messages, &dbc_channels, &HashMap::new());
+        assert_eq!(
+            rows,
+            vec![
+                vec!["0.001000", "0x1A0", "3000"],
+                vec!["0.003000", "0x1A0", "4000"],
+            ]
+        );
+        assert_eq!(
+            query.format(&rows, QueryFormat::Csv),
+            "time,id,EngineSpeed\n0.001000,0x1A0,3000\n0.003000,0x1A0,4000\n"
+        );
+        let json: JsonValue =
+            serde_json::from_str(&query.format(&rows, QueryFormat::Json)).unwrap();
+        assert_eq!(json[1]["EngineSpeed"], 4000.0);
+        assert_eq!(json[1]["id"], "0x1A0");
+
+        // Frames without the signal never match a comparison on it
+        let fast = Query::parse("seq,ch,byte0", Some("EngineSpeed > 3500")).unwrap();
+        assert_eq!(
+            fast.run(&messages, &dbc_channels, &HashMap::new()),
+            vec![vec!["3", "1", "160"]]
+        );
+    }
+
+    #[test]
+    fn test_query_options() {
+        let args: Vec<String> = [
+            "in.blf",
+            "--select",
+            "time,id",
+            "--where",
+            "id == 0x100",
+            "--format",
+            "table",
+            "--dbc",
+            "1=pt.dbc",
+        ]
+        .into_iter()
+        .map(String::from)
+        .collect();
+        let options = QueryOptions::parse(&args).unwrap();
+        assert_eq!(options.input, PathBuf::from("in.blf"));
+        assert_eq!(options.format, QueryFormat::Table);
+        assert_eq!(options.query.columns.len(), 2);
+        assert_eq!(options.databases.len(), 1);
+        assert!(QueryOptions::parse(&["--select".to_string(), "id".to_string()]).is_err());
+        assert!(QueryOptions::parse(&["in.blf".to_string(), "--format".to_string()]).is_err());
+    }
+}
@@ -0,0 +1,300 @@
+//! Log replay with signal injection
+//!
+//! [`Replayer`] sends the CAN frames of a loaded log again at their original
+//! pace, as a live-mode backend. [`SignalOverride`]s replace selected signals
+//! with a fixed value or a ramp, re-encoding the payload through the DBC of
+//! the frame's channel. They are applied to each batch as it goes out, so
+//! they can be edited while the replay runs.
+
+use crate::handlers::{parse_duration_ms, parse_id};
+use blf::LogObject;
+use parser::dbc::DbcDatabase;
+use std::collections::HashMap;
+use std::fmt;
+
+/// Value written into an overridden signal
+#[derive(Debug, Clone, PartialEq)]
+pub enum InjectedValue {
+    Constant(f64),
+    /// Goes from `from` to `to` over `period_s`, then starts over
+    Ramp {
+        from: f64,
+        to: f64,
+        period_s: f64,
+    },
+}
+
+impl InjectedValue {
+    /// Physical value at `time_ns` after the start of the replay
+    pub fn at(&self, time_ns: u64) -> f64 {
+        match *self {
+            InjectedValue::Constant(value) => value,
+            InjectedValue::Ramp { from, to, period_s } => {
+                let period_ns = (period_s * 1_000_000_000.0) as u64;
+                let phase = (time_ns % period_ns) as f64 / period_ns as f64;
+                from + (to - from) * phase
+            }
+        }
+    }
+}
+
+/// A signal of frame `id` whose value is replaced on every channel with a DBC for it
+#[derive(Debug, Clone, PartialEq)]
+pub struct SignalOverride {
+    pub id: u32,
+    pub signal: String,
+    pub value: InjectedValue,
+}
+
+impl fmt::Display for SignalOverride {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "0x{:X} {} ", self.id, self.signal)?;
+        match self.value {
+            InjectedValue::Constant(value) => write!(f, "= {}", value),
+            InjectedValue::Ramp { from, to, period_s } => {
+                write!(f, "ramp {}..{} {}s", from, to, period_s)
+            }
+        }
+    }
+}
+
+fn parse_value(text: &str) -> Result<f64, String> {
+    text.parse::<f64>()
+        .ok()
+        .filter(|value| value.is_finite())
+        .ok_or_else(|| format!("Invalid value: '{}'", text))
+}
+
+/// Parse overrides separated by `;` or newlines
+///
+/// Syntax: `0x100 Speed = 50` and `0x100 Speed ramp 0..120 10s`.
+pub fn parse_signal_overrides(text: &str) -> Result<Vec<SignalOverride>, String> {
+    text.split([';', '\n'])
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .map(|entry| {
+            let words: Vec<&str> = entry.split_whitespace().collect();
+            let (id, signal, value) = match words.as_slice() {
+                [id, signal, "=", value] => {
+                    (id, signal, InjectedValue::Constant(parse_value(value)?))
+                }
+                [id, signal, "ramp", range, period] => {
+                    let (from, to) = range
+                        .split_once("..")
+                        .ok_or_else(|| format!("Expected 'from..to' in '{}'", entry))?;
+                    let period_s = parse_duration_ms(period)
+                        .ok_or_else(|| format!("Invalid ramp period: '{}'", period))?
+                        / 1000.0;
+                    let ramp = InjectedValue::Ramp {
+                        from: parse_value(from)?,
+                        to: parse_value(to)?,
+                        period_s,
+                    };
+                    (id, signal, ramp)
+                }
+                _ => return Err(format!("Unknown override: '{}'", entry)),
+            };
+            Ok(SignalOverride {
+                id: parse_id(id)?,
+                signal: signal.to_string(),
+                value,
+            })
+        })
+        .collect()
+}
+
+/// Rewrite the overridden signals of a CAN frame; returns whether anything changed
+///
+/// Frames without a DBC on their channel, or whose message lacks the signal,
+/// are left untouched.
+pub fn apply_signal_overrides(
+    msg: &mut LogObject,
+    overrides: &[SignalOverride],
+    dbc_channels: &HashMap<u16, DbcDatabase>,
+) -> bool {
+    let time_ns = msg.timestamp();
+    let (channel, id, data): (u16, u32, &mut [u8]) = match msg {
+        LogObject::CanMessage(m) => {
+            let len = m.data.len().min(m.dlc as usize);
+            (m.channel, m.id, &mut m.data[..len])
+        }
+        LogObject::CanMessage2(m) => {
+            let len = m.data.len().min(m.dlc as usize);
+            (m.channel, m.id, &mut m.data[..len])
+        }
+        LogObject::CanFdMessage(m) => {
+            let len = m.data.len().min(m.valid_data_bytes as usize);
+            (m.channel, m.id, &mut m.data[..len])
+        }
+        LogObject::CanFdMessage64(m) => {
+            let len = m.data.len().min(m.valid_data_bytes as usize);
+            (m.channel as u16, m.id, &mut m.data[..len])
+        }
+        _ => return false,
+    };
+    let Some(message) = dbc_channels
+        .get(&channel)
+        .and_then(|db| db.messages.get(&id))
+    else {
+        return false;
+    };
+
+    let mut changed = false;
+    for entry in overrides.iter().filter(|entry| entry.id == id) {
+        if let Some(signal) = message.signals.get(&entry.signal) {
+            signal.encode(entry.value.at(time_ns), data);
+            changed = true;
+        }
+    }
+    changed
+}
+
+/// Sends the CAN frames of a log again, timed relative to its first frame
+#[derive(Debug, Clone)]
+pub struct Replayer {
+    frames: Vec<LogObject>,
+    next: usize,
+}
+
+impl Replayer {
+    /// Keep the CAN frames of `messages`, shifted so the first one is at zero
+    pub fn new(messages: &[LogObject]) -> Self {
+        let mut frames: Vec<LogObject> = messages
+            .iter()
+            .filter(|msg| {
+                matches!(
+                    msg,
+                    LogObject::CanMessage(_)
+                        | LogObject::CanMessage2(_)
+                        | LogObject::CanFdMessage(_)
+                        | LogObject::CanFdMessage64(_)
+                )
+            })
+            .cloned()
+            .collect();
+        let first_ns = frames.iter().map(LogObject::timestamp).min().unwrap_or(0);
+        for frame in &mut frames {
+            let header = match frame {
+                LogObject::CanMessage(m) => &mut m.header,
+                LogObject::CanMessage2(m) => &mut m.header,
+                LogObject::CanFdMessage(m) => &mut m.header,
+                LogObject::CanFdMessage64(m) => &mut m.header,
+                _ => unreachable!(),
+            };
+            header.object_time_stamp -= first_ns;
+        }
+        frames.sort_by_key(LogObject::timestamp);
+        Self { frames, next: 0 }
+    }
+
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Number of frames sent so far
+    pub fn position(&self) -> usize {
+        self.next
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.next >= self.frames.len()
+    }
+
+    /// Frames due up to `now_ns` (time since the start of the replay)
+    pub fn advance(&mut self, now_ns: u64) -> Vec<LogObject> {
+        let end = self.next
+            + self.frames[self.next..].partition_point(|frame| frame.timestamp() <= now_ns);
+        let batch = self.frames[self.next..end].to_vec();
+        self.next = end;
+        batch
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::handlers::test_support::can;
+    use parser::dbc::DbcParser;
+
+    #[test]
+    fn test_parse_signal_overrides() {
+        let overrides =
+            parse_signal_overrides("0x100 Speed = 50; 256 Rpm ramp 0..3000 10s").unwrap();
+        assert_eq!(overrides[0].value, InjectedValue::Constant(50.0));
+        assert_eq!(
+            overrides[1],
+            SignalOverride {
+                id: 0x100,
+                signal: "Rpm".to_string(),
+                value: InjectedValue::Ramp {
+                    from: 0.0,
+                    to: 3000.0,
+                    period_s: 10.0,
+                },
+            }
+        );
+        assert_eq!(overrides[1].to_string(), "0x100 Rpm ramp 0..3000 10s");
+        assert_eq!(overrides[1].value.at(2_500_000_000), 750.0);
+        assert!(parse_signal_overrides("0x100 Speed ramp 0 10s").is_err());
+        assert!(parse_signal_overrides("0x100 Speed = fast").is_err());
+    }
+
+    #[test]
+    fn test_apply_signal_overrides_reencodes_payload() {
+        let db = DbcParser::new()
+            .parse(
+                "BO_ 256 Engine: 8 ECU\n SG_ Speed : 8|16@1+ (0.1,0) [0|6553.5] \"km/h\" Vector__XXX\n",
+            )
+            .unwrap();
+        let dbc_channels = HashMap::from([(1, db)]);
+        let overrides = parse_signal_overrides("0x100 Speed = 88.8").unwrap();
+
+        let mut msg = can(0x100).build();
+        assert!(apply_signal_overrides(&mut msg, &overrides, &dbc_channels));
+        let LogObject::CanMessage(m) = &msg else {
+            unreachable!()
+        };
+        let speed = dbc_channels[&1].messages[&0x100].signals["Speed"]
+            .decode(&m.data)
+            .physical;
+        assert!((speed - 88.8).abs() < 1e-9);
+
+        // No DBC on channel 2, and another ID on channel 1
+        assert!(!apply_signal_overrides(
+            &mut can(0x100).channel(2).build(),
+            &overrides,
+            &dbc_channels
+        ));
+        assert!(!apply_signal_overrides(
+            &mut can(0x200).build(),
+            &overrides,
+            &dbc_channels
+        ));
+    }
+
+    #[test]
+    fn test_replayer_keeps_original_pace() {
+        let messages = vec![
+            can(1).at(5_000).build(),
+            can(2).at(6_000).build(),
+            can(3).at(9_000).build(),
+        ];
+        let mut replayer = Replayer::new(&messages);
+        assert_eq!(replayer.len(), 3);
+
+        let first: Vec<u64> = replayer
+            .advance(1_000)
+            .iter()
+            .map(LogObject::timestamp)
+            .collect();
+        assert_eq!(first, vec![0, 1_000]);
+        assert!(replayer.advance(3_999).is_empty());
+        assert_eq!(replayer.advance(10_000).len(), 1);
+        assert!(replayer.is_finished());
+        assert_eq!(replayer.position(), 3);
+    }
+}
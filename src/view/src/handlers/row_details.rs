@@ -0,0 +1,238 @@
+//! Decoded signals in the message list, decoded off the UI thread under load
+//!
+//! Each row of the log shows its decoded signals after the payload. One row
+//! is cheap to decode, but a page of them per frame adds up while the list
+//! scrolls fast or the window is busy. [`RowDetails`] watches how far the
+//! list moved and how long the last rows took to build; while either is too
+//! much, rows show the raw payload only and are queued, decoded on the
+//! background executor and filled in once ready.
+
+use crate::handlers::{ExportFrame, decode_frame};
+use crate::models::preferences::NumberFormat;
+use blf::LogObject;
+use parser::dbc::DbcDatabase;
+use parser::ldf::LdfDatabase;
+use std::collections::{BTreeSet, HashMap};
+use std::time::{Duration, Instant};
+
+/// Building the visible rows may take this long before decoding is deferred
+pub const FRAME_BUDGET: Duration = Duration::from_millis(12);
+
+/// Decoding stays deferred this long after the last busy frame
+pub const SETTLE_TIME: Duration = Duration::from_millis(150);
+
+/// Decoded rows kept; the cache starts over when it grows past this
+const MAX_CACHED_ROWS: usize = 20_000;
+
+/// "Speed=12.5 km/h, Gear=Drive", empty if no database decodes the frame
+pub fn signal_summary(
+    msg: &LogObject,
+    dbc_channels: &HashMap<u16, DbcDatabase>,
+    ldf_channels: &HashMap<u16, LdfDatabase>,
+    numbers: NumberFormat,
+) -> String {
+    ExportFrame::from_log_object(msg)
+        .map(|frame| {
+            decode_frame(&frame, dbc_channels, ldf_channels)
+                .iter()
+                .map(|signal| format!("{}={}", signal.name, signal.display_value(numbers)))
+                .collect::<Vec<_>>()
+                .join(", ")
+        })
+        .unwrap_or_default()
+}
+
+/// Decoded text of the rows, keyed by message index, and the load of the list
+#[derive(Debug, Default)]
+pub struct RowDetails {
+    texts: HashMap<usize, String>,
+    /// Rows shown raw, waiting for the background
+    queued: BTreeSet<usize>,
+    /// Bumped whenever the log or the databases change, so late results are dropped
+    generation: u64,
+    numbers: NumberFormat,
+    decoding: bool,
+    first_row: usize,
+    frame_start: Option<Instant>,
+    last_frame: Duration,
+    busy_until: Option<Instant>,
+}
+
+impl RowDetails {
+    /// Start building the rows from `first_row`; returns whether they may decode now
+    pub fn begin_frame(&mut self, first_row: usize, rows: usize, now: Instant) -> bool {
+        let jump = first_row.abs_diff(self.first_row) > rows.max(1);
+        if jump {
+            // Rows still waiting from before the jump are off screen now
+            self.queued.clear();
+        }
+        if jump || self.last_frame > FRAME_BUDGET {
+            self.busy_until = Some(now + SETTLE_TIME);
+        }
+        self.first_row = first_row;
+        self.frame_start = Some(now);
+        self.busy_until.is_none_or(|until| now >= until)
+    }
+
+    /// The rows are built; their cost decides whether the next frame may decode
+    pub fn end_frame(&mut self, now: Instant) {
+        if let Some(start) = self.frame_start.take() {
+            self.last_frame = now.saturating_duration_since(start);
+        }
+    }
+
+    /// Decoded text of the row showing `msg`, or `None` while it waits or has no signals
+    pub fn text(
+        &mut self,
+        index: usize,
+        msg: &LogObject,
+        decode_now: bool,
+        dbc_channels: &HashMap<u16, DbcDatabase>,
+        ldf_channels: &HashMap<u16, LdfDatabase>,
+    ) -> Option<String> {
+        if !self.texts.contains_key(&index) {
+            if !decode_now {
+                self.queued.insert(index);
+                return None;
+            }
+            let text = signal_summary(msg, dbc_channels, ldf_channels, self.numbers);
+            self.insert(index, text);
+        }
+        self.texts
+            .get(&index)
+            .filter(|text| !text.is_empty())
+            .cloned()
+    }
+
+    /// Whether rows wait to be decoded in the background
+    pub fn has_queue(&self) -> bool {
+        !self.queued.is_empty()
+    }
+
+    /// Rows to decode in the background, unless a batch is already running
+    pub fn take_queue(&mut self) -> Option<(u64, Vec<usize>)> {
+        if self.decoding || self.queued.is_empty() {
+            return None;
+        }
+        self.decoding = true;
+        let rows = std::mem::take(&mut self.queued).into_iter().collect();
+        Some((self.generation, rows))
+    }
+
+    /// Store a background batch; returns false if the log changed meanwhile
+    pub fn fill(&mut self, generation: u64, texts: Vec<(usize, String)>) -> bool {
+        self.decoding = false;
+        if generation != self.generation {
+            return false;
+        }
+        for (index, text) in texts {
+            self.queued.remove(&index);
+            self.insert(index, text);
+        }
+        true
+    }
+
+    /// Format the rows' values with `numbers` from now on
+    pub fn set_number_format(&mut self, numbers: NumberFormat) {
+        if numbers != self.numbers {
+            self.numbers = numbers;
+            self.clear();
+        }
+    }
+
+    /// Format of the values, for rows decoded in the background
+    pub fn number_format(&self) -> NumberFormat {
+        self.numbers
+    }
+
+    /// Forget every decoded row, after the log or a database changed
+    pub fn clear(&mut self) {
+        self.texts.clear();
+        self.queued.clear();
+        self.generation += 1;
+    }
+
+    fn insert(&mut self, index: usize, text: String) {
+        if self.texts.len() >= MAX_CACHED_ROWS {
+            self.texts.clear();
+        }
+        self.texts.insert(index, text);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::handlers::test_support::can;
+
+    #[test]
+    fn test_rows_decode_when_idle_and_queue_under_load() {
+        let dbc = parser::dbc::DbcParser::new()
+            .parse(
+                "BO_ 256 Engine: 8 ECU\n SG_ Speed : 0|8@1+ (0.5,0) [0|127] \"km/h\" Vector__XXX\n",
+            )
+            .unwrap();
+        let dbc_channels = HashMap::from([(1, dbc)]);
+        let ldf_channels = HashMap::new();
+        let frame = |id| can(id).byte(0, 20).build();
+        let (speed, other) = (frame(0x100), frame(0x200));
+        assert_eq!(
+            signal_summary(
+                &speed,
+                &dbc_channels,
+                &ldf_channels,
+                NumberFormat::default()
+            ),
+            "Speed=10.0 km/h"
+        );
+
+        let mut details = RowDetails::default();
+        let start = Instant::now();
+        assert!(details.begin_frame(0, 40, start));
+        let text = details.text(0, &speed, true, &dbc_channels, &ldf_channels);
+        assert_eq!(text.as_deref(), Some("Speed=10.0 km/h"));
+        assert_eq!(
+            details.text(1, &other, true, &dbc_channels, &ldf_channels),
+            None
+        );
+        details.end_frame(start);
+
+        // A jump of more than a page defers decoding until the list settles
+        assert!(!details.begin_frame(5_000, 40, start));
+        assert_eq!(
+            details.text(5_000, &speed, false, &dbc_channels, &ldf_channels),
+            None
+        );
+        details.end_frame(start + FRAME_BUDGET * 2);
+        // Slow rows keep it deferred even without scrolling
+        assert!(!details.begin_frame(5_000, 40, start + SETTLE_TIME));
+        details.end_frame(start + SETTLE_TIME);
+        assert!(details.begin_frame(5_000, 40, start + SETTLE_TIME * 3));
+
+        assert!(details.has_queue());
+        let (generation, rows) = details.take_queue().unwrap();
+        assert_eq!(rows, vec![5_000]);
+        assert!(details.take_queue().is_none());
+        assert!(details.fill(generation, vec![(5_000, "Speed=10 km/h".to_string())]));
+        let text = details.text(5_000, &speed, false, &dbc_channels, &ldf_channels);
+        assert_eq!(text.as_deref(), Some("Speed=10 km/h"));
+
+        // Results for a log that has since been replaced are dropped
+        details.text(7, &speed, false, &dbc_channels, &ldf_channels);
+        let (generation, _) = details.take_queue().unwrap();
+        details.clear();
+        assert!(!details.fill(generation, vec![(7, "stale".to_string())]));
+        assert_eq!(
+            details.text(7, &other, true, &dbc_channels, &ldf_channels),
+            None
+        );
+
+        // Another number format decodes the rows again
+        details.set_number_format(NumberFormat {
+            decimal_separator: crate::models::preferences::DecimalSeparator::Comma,
+            ..NumberFormat::default()
+        });
+        let text = details.text(0, &speed, true, &dbc_channels, &ldf_channels);
+        assert_eq!(text.as_deref(), Some("Speed=10,0 km/h"));
+    }
+}
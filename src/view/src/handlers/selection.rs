@@ -0,0 +1,122 @@
+//! Row selection in the log view
+//!
+//! Selected rows are stored as indices into the full message list rather
+//! than into the filtered view, so a selection survives filter changes and
+//! always maps back to the same messages.
+
+use std::collections::BTreeSet;
+
+/// Set of selected message indices plus the anchor used for range selection
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RowSelection {
+    selected: BTreeSet<usize>,
+    anchor: Option<usize>,
+}
+
+impl RowSelection {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Plain click: select only `index`
+    pub fn select_only(&mut self, index: usize) {
+        self.selected.clear();
+        self.selected.insert(index);
+        self.anchor = Some(index);
+    }
+
+    /// Ctrl/cmd click: add or remove `index`, keeping the rest
+    pub fn toggle(&mut self, index: usize) {
+        if !self.selected.remove(&index) {
+            self.selected.insert(index);
+        }
+        self.anchor = Some(index);
+    }
+
+    /// Shift click: select every row displayed between the anchor and `index`
+    ///
+    /// `rows` holds the message indices in display order. Without an anchor
+    /// (or when the anchor is filtered out) this behaves like a plain click.
+    pub fn extend_to(&mut self, index: usize, rows: &[usize]) {
+        let anchor_pos = self
+            .anchor
+            .and_then(|anchor| rows.iter().position(|&row| row == anchor));
+        let target_pos = rows.iter().position(|&row| row == index);
+
+        match (anchor_pos, target_pos) {
+            (Some(a), Some(b)) => {
+                let (start, end) = if a <= b { (a, b) } else { (b, a) };
+                self.selected.clear();
+                self.selected.extend(rows[start..=end].iter().copied());
+            }
+            _ => self.select_only(index),
+        }
+    }
+
+    /// Select every displayed row
+    pub fn select_all(&mut self, rows: &[usize]) {
+        self.selected = rows.iter().copied().collect();
+        self.anchor = rows.first().copied();
+    }
+
+    pub fn clear(&mut self) {
+        self.selected.clear();
+        self.anchor = None;
+    }
+
+    pub fn is_selected(&self, index: usize) -> bool {
+        self.selected.contains(&index)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.selected.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.selected.len()
+    }
+
+    /// Selected message indices in ascending (log) order
+    pub fn indices(&self) -> impl Iterator<Item = usize> + '_ {
+        self.selected.iter().copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_click_and_toggle() {
+        let mut selection = RowSelection::new();
+        selection.select_only(4);
+        selection.toggle(7);
+        selection.toggle(2);
+        assert_eq!(selection.indices().collect::<Vec<_>>(), vec![2, 4, 7]);
+        selection.toggle(4);
+        assert_eq!(selection.indices().collect::<Vec<_>>(), vec![2, 7]);
+        selection.select_only(9);
+        assert_eq!(selection.indices().collect::<Vec<_>>(), vec![9]);
+    }
+
+    #[test]
+    fn test_extend_follows_display_order() {
+        // Filtered view showing messages 1, 5, 6 and 10
+        let rows = [1, 5, 6, 10];
+        let mut selection = RowSelection::new();
+        selection.select_only(10);
+        selection.extend_to(5, &rows);
+        assert_eq!(selection.indices().collect::<Vec<_>>(), vec![5, 6, 10]);
+        // The anchor stays put, so a second shift click re-spans from it
+        selection.extend_to(1, &rows);
+        assert_eq!(selection.indices().collect::<Vec<_>>(), vec![1, 5, 6, 10]);
+    }
+
+    #[test]
+    fn test_extend_without_visible_anchor_selects_one() {
+        let mut selection = RowSelection::new();
+        selection.select_only(3);
+        selection.extend_to(6, &[5, 6]);
+        assert_eq!(selection.indices().collect::<Vec<_>>(), vec![6]);
+    }
+}
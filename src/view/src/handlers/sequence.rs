@@ -0,0 +1,306 @@
+//! Frame-loss detection via sequence counters
+//!
+//! Many ECUs put a rolling counter into their frames. When a
+//! [`SequenceCounter`] is set for a message, [`SequenceChecker`] follows the
+//! decoded counter value frame by frame and reports every jump as a
+//! [`SequenceGap`] — the usual sign that a logger dropped frames. Like the
+//! trigger engine it is fed one message at a time, so it runs on a loaded log
+//! and on live traffic alike.
+
+use crate::handlers::{ExportFrame, decode_frame, parse_id};
+use blf::LogObject;
+use parser::dbc::DbcDatabase;
+use parser::ldf::LdfDatabase;
+use std::collections::HashMap;
+use std::fmt;
+
+/// A signal marked as the sequence counter of a message
+#[derive(Debug, Clone, PartialEq)]
+pub struct SequenceCounter {
+    /// Only frames on this channel, or on every channel
+    pub channel: Option<u16>,
+    pub id: u32,
+    pub signal: String,
+    /// Value after which the counter wraps to zero; defaults to the signal's
+    /// maximum raw value
+    pub wrap: Option<u64>,
+}
+
+impl fmt::Display for SequenceCounter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.channel {
+            Some(channel) => write!(f, "{}:0x{:X} {}", channel, self.id, self.signal)?,
+            None => write!(f, "0x{:X} {}", self.id, self.signal)?,
+        }
+        if let Some(wrap) = self.wrap {
+            write!(f, " {}", wrap)?;
+        }
+        Ok(())
+    }
+}
+
+/// Parse counters separated by `;` or newlines
+///
+/// Syntax: `0x123 AliveCounter`, `2:0x123 AliveCounter` (channel 2 only) and
+/// `0x123 AliveCounter 14` (counter wraps after 14).
+pub fn parse_sequence_counters(text: &str) -> Result<Vec<SequenceCounter>, String> {
+    text.split([';', '\n'])
+        .map(str::trim)
+        .filter(|counter| !counter.is_empty())
+        .map(|counter| {
+            let words: Vec<&str> = counter.split_whitespace().collect();
+            let (target, signal, wrap) = match words.as_slice() {
+                [target, signal] => (*target, *signal, None),
+                [target, signal, wrap] => (
+                    *target,
+                    *signal,
+                    Some(
+                        wrap.parse::<u64>()
+                            .map_err(|_| format!("Invalid wrap value: '{}'", wrap))?,
+                    ),
+                ),
+                _ => return Err(format!("Expected '<id> <signal> [wrap]' in '{}'", counter)),
+            };
+            let (channel, id) = match target.split_once(':') {
+                Some((channel, id)) => (
+                    Some(
+                        channel
+                            .parse()
+                            .map_err(|_| format!("Invalid channel: '{}'", channel))?,
+                    ),
+                    parse_id(id)?,
+                ),
+                None => (None, parse_id(target)?),
+            };
+            Ok(SequenceCounter {
+                channel,
+                id,
+                signal: signal.to_string(),
+                wrap,
+            })
+        })
+        .collect()
+}
+
+/// Width in bits of `signal` in the database assigned to the frame's channel
+fn signal_size(
+    frame: &ExportFrame,
+    signal: &str,
+    dbc_channels: &HashMap<u16, DbcDatabase>,
+    ldf_channels: &HashMap<u16, LdfDatabase>,
+) -> Option<u32> {
+    if frame.kind == "LIN" {
+        ldf_channels
+            .get(&frame.channel)
+            .and_then(|db| db.signals.get(signal))
+            .map(|signal| signal.size)
+    } else {
+        dbc_channels
+            .get(&frame.channel)
+            .and_then(|db| db.messages.get(&frame.id))
+            .and_then(|message| message.signals.get(signal))
+            .map(|signal| signal.signal_size)
+    }
+}
+
+/// Counter values that were expected but never seen
+#[derive(Debug, Clone, PartialEq)]
+pub struct SequenceGap {
+    /// Position of the frame after the gap in the fed sequence
+    pub index: usize,
+    pub timestamp_ns: u64,
+    pub channel: u16,
+    pub id: u32,
+    /// Index of the counter in [`SequenceChecker::counters`]
+    pub counter: usize,
+    pub expected: u64,
+    pub actual: u64,
+    /// Number of counter values skipped
+    pub skipped: u64,
+}
+
+/// Follows sequence counters message by message
+#[derive(Debug, Clone, Default)]
+pub struct SequenceChecker {
+    counters: Vec<SequenceCounter>,
+    /// Last value of each (counter, channel)
+    last: HashMap<(usize, u16), u64>,
+    gaps: Vec<SequenceGap>,
+    next_index: usize,
+}
+
+impl SequenceChecker {
+    pub fn new(counters: Vec<SequenceCounter>) -> Self {
+        Self {
+            counters,
+            ..Self::default()
+        }
+    }
+
+    pub fn counters(&self) -> &[SequenceCounter] {
+        &self.counters
+    }
+
+    pub fn gaps(&self) -> &[SequenceGap] {
+        &self.gaps
+    }
+
+    /// Total number of counter values skipped over all gaps
+    pub fn skipped(&self) -> u64 {
+        self.gaps.iter().map(|gap| gap.skipped).sum()
+    }
+
+    /// Forget all values and gaps seen so far, keeping the counters
+    pub fn reset(&mut self) {
+        self.last.clear();
+        self.gaps.clear();
+        self.next_index = 0;
+    }
+
+    /// Check the next message; returns the gap if a counter jumped
+    pub fn feed(
+        &mut self,
+        msg: &LogObject,
+        dbc_channels: &HashMap<u16, DbcDatabase>,
+        ldf_channels: &HashMap<u16, LdfDatabase>,
+    ) -> Option<&SequenceGap> {
+        let index = self.next_index;
+        self.next_index += 1;
+        if self.counters.is_empty() {
+            return None;
+        }
+        let frame = ExportFrame::from_log_object(msg)?;
+
+        let gaps_before = self.gaps.len();
+        let mut decoded = None;
+        for (counter_index, counter) in self.counters.iter().enumerate() {
+            if counter.id != frame.id || counter.channel.is_some_and(|ch| ch != frame.channel) {
+                continue;
+            }
+            let signals =
+                decoded.get_or_insert_with(|| decode_frame(&frame, dbc_channels, ldf_channels));
+            let Some(actual) = signals
+                .iter()
+                .find(|decoded| decoded.name == counter.signal)
+                .map(|decoded| decoded.raw)
+            else {
+                continue;
+            };
+            let Some(wrap) = counter.wrap.or_else(|| {
+                signal_size(&frame, &counter.signal, dbc_channels, ldf_channels)
+                    .filter(|&size| size > 0 && size < 64)
+                    .map(|size| (1u64 << size) - 1)
+            }) else {
+                continue;
+            };
+
+            let key = (counter_index, frame.channel);
+            if let Some(&last) = self.last.get(&key) {
+                let modulus = wrap + 1;
+                let expected = (last + 1) % modulus;
+                // A repeated value is a resend, not a loss
+                if actual != expected && actual != last {
+                    let skipped = (actual % modulus + modulus - expected) % modulus;
+                    self.gaps.push(SequenceGap {
+                        index,
+                        timestamp_ns: frame.timestamp_ns,
+                        channel: frame.channel,
+                        id: frame.id,
+                        counter: counter_index,
+                        expected,
+                        actual,
+                        skipped,
+                    });
+                }
+            }
+            self.last.insert(key, actual);
+        }
+        self.gaps[gaps_before..].first()
+    }
+}
+
+/// Run `counters` over a whole log
+pub fn find_sequence_gaps(
+    messages: &[LogObject],
+    counters: Vec<SequenceCounter>,
+    dbc_channels: &HashMap<u16, DbcDatabase>,
+    ldf_channels: &HashMap<u16, LdfDatabase>,
+) -> SequenceChecker {
+    let mut checker = SequenceChecker::new(counters);
+    for msg in messages {
+        checker.feed(msg, dbc_channels, ldf_channels);
+    }
+    checker
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::handlers::test_support::can;
+
+    fn counter_db() -> HashMap<u16, DbcDatabase> {
+        let db = parser::dbc::DbcParser::new()
+            .parse("BO_ 256 Status: 8 ECU\n SG_ Counter : 0|4@1+ (1,0) [0|15] \"\" Vector__XXX\n")
+            .unwrap();
+        HashMap::from([(1, db)])
+    }
+
+    #[test]
+    fn test_parse_sequence_counters() {
+        let counters = parse_sequence_counters("0x100 Counter; 2:0x200 Alive 14").unwrap();
+        assert_eq!(
+            counters,
+            vec![
+                SequenceCounter {
+                    channel: None,
+                    id: 0x100,
+                    signal: "Counter".to_string(),
+                    wrap: None,
+                },
+                SequenceCounter {
+                    channel: Some(2),
+                    id: 0x200,
+                    signal: "Alive".to_string(),
+                    wrap: Some(14),
+                },
+            ]
+        );
+        assert_eq!(counters[1].to_string(), "2:0x200 Alive 14");
+        assert!(parse_sequence_counters("0x100").is_err());
+    }
+
+    #[test]
+    fn test_gaps_across_wrap() {
+        let counters = parse_sequence_counters("0x100 Counter").unwrap();
+        // 4-bit counter: 14, 15, 0 is continuous; 0 -> 3 skips 1 and 2
+        let messages: Vec<LogObject> = [14, 15, 0, 3, 3, 4, 15, 1]
+            .iter()
+            .enumerate()
+            .map(|(i, &value)| can(0x100).at(i as u64 * 10).byte(0, value).build())
+            .collect();
+        let checker = find_sequence_gaps(&messages, counters, &counter_db(), &HashMap::new());
+
+        let gaps: Vec<(usize, u64, u64, u64)> = checker
+            .gaps()
+            .iter()
+            .map(|gap| (gap.index, gap.expected, gap.actual, gap.skipped))
+            .collect();
+        assert_eq!(gaps, vec![(3, 1, 3, 2), (6, 5, 15, 10), (7, 0, 1, 1)]);
+        assert_eq!(checker.skipped(), 13);
+    }
+
+    #[test]
+    fn test_wrap_override_and_channel_filter() {
+        let counters = parse_sequence_counters("1:0x100 Counter 2").unwrap();
+        let messages = vec![
+            can(0x100).byte(0, 2).build(),
+            can(0x100).at(10).build(),
+            can(0x100).channel(2).at(20).byte(0, 2).build(),
+            can(0x100).at(30).byte(0, 2).build(),
+        ];
+        let checker = find_sequence_gaps(&messages, counters, &counter_db(), &HashMap::new());
+        assert_eq!(checker.gaps().len(), 1);
+        assert_eq!(checker.gaps()[0].index, 3);
+        assert_eq!(checker.gaps()[0].skipped, 1);
+    }
+}
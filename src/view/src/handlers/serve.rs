@@ -0,0 +1,753 @@
+//! Headless decode server
+//!
+//! `canview serve` answers HTTP/JSON requests without opening a window, so
+//! dashboards and CI jobs can load a BLF and query its frames and decoded
+//! signals. Requests are handled one at a time on the calling thread; the
+//! server is meant for a local tool or a build agent, not for the open
+//! network.
+//!
+//! Browsers send an `Origin` header with their requests. Those are refused
+//! unless the origin was allowed with `--allow-origin`, and even then `/load`
+//! is refused, so a web page cannot make the server read local files.
+//!
+//! | Request | Does |
+//! |---|---|
+//! | `GET /health` | Liveness check |
+//! | `POST /load?path=/logs/run.blf` | Load a BLF from the server's disk, not from web pages |
+//! | `POST /upload` | Load the BLF sent as the request body |
+//! | `GET /messages?channel=1&id=0x100&type=CAN&offset=0&limit=100&decode=1` | Frames, optionally decoded |
+//! | `GET /signals?channel=1&id=0x100&name=EngineSpeed` | One signal over the whole log |
+//!
+//! Databases come from `--dbc CH=PATH`, `--ldf CH=PATH` and the channel
+//! mappings of `--config`. `--grpc HOST:PORT` also streams decoded signals
+//! over gRPC, replaying files from `--replay-dir`, see [`crate::handlers::grpc`].
+
+use crate::handlers::{
+    ExportFrame, MessageKind, TimeOffsets, decode_frame, matches_id_and_channel, matches_type,
+    sequence_number, serve_grpc, signal_series, unpack_log,
+};
+use crate::models::{AppConfig, ChannelType};
+use blf::{BlfParser, FileStatistics, LogObject};
+use parser::dbc::DbcDatabase;
+use parser::ldf::LdfDatabase;
+use serde_json::{Value, json};
+use std::collections::{BTreeSet, HashMap};
+use std::io::{BufRead, BufReader, Cursor, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Address served when `--addr` is not given
+pub const DEFAULT_SERVE_ADDR: &str = "127.0.0.1:8080";
+
+/// Largest request body accepted by `/upload`
+pub const MAX_UPLOAD_BYTES: usize = 1 << 30;
+
+/// Frames returned by `/messages` when `limit` is not given
+const DEFAULT_LIMIT: usize = 1000;
+
+/// Largest request line plus headers accepted
+const MAX_HEADER_BYTES: u64 = 8 * 1024;
+
+/// Most headers accepted in one request
+const MAX_HEADERS: usize = 100;
+
+/// Status of a request whose headers are over the limits
+const HEADERS_TOO_LARGE: u16 = 431;
+
+/// How long a client may leave a read or write hanging before it is dropped
+const CLIENT_TIMEOUT: Duration = Duration::from_secs(10);
+
+pub const SERVE_USAGE: &str = "\
+Usage: canview serve [--addr HOST:PORT] [--grpc HOST:PORT] [--replay-dir DIR]
+                     [--config FILE] [--allow-origin URL] [--dbc CH=PATH]...
+                     [--ldf CH=PATH]...
+
+  --addr          Address to listen on (default 127.0.0.1:8080)
+  --grpc          Also stream decoded signals over gRPC on this address
+  --replay-dir    Directory gRPC subscribers may replay BLF files from
+  --config        Configuration whose channel mappings provide the databases
+  --allow-origin  Web page allowed to query the server, e.g. http://localhost:3000
+  --dbc           DBC for a CAN channel, e.g. --dbc 1=powertrain.dbc
+  --ldf           LDF for a LIN channel, e.g. --ldf 2=body.ldf";
+
+/// Command line of `canview serve`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ServeOptions {
+    pub addr: String,
+    /// Address of the gRPC signal stream, if enabled
+    pub grpc: Option<String>,
+    /// Directory gRPC subscribers may replay files from; none by default
+    pub replay_dir: Option<PathBuf>,
+    pub config: Option<PathBuf>,
+    /// Origin of the web page allowed to send requests; none by default
+    pub allow_origin: Option<String>,
+    /// Databases as (channel type, channel, path)
+    pub databases: Vec<(ChannelType, u16, PathBuf)>,
+}
+
+impl ServeOptions {
+    /// Parse the arguments following `serve`
+    pub fn parse(args: &[String]) -> Result<Self, String> {
+        let mut options = Self {
+            addr: DEFAULT_SERVE_ADDR.to_string(),
+            grpc: None,
+            replay_dir: None,
+            config: None,
+            allow_origin: None,
+            databases: Vec::new(),
+        };
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            let mut value = || {
+                args.next()
+                    .cloned()
+                    .ok_or_else(|| format!("Missing value after {}", arg))
+            };
+            match arg.as_str() {
+                "--addr" => options.addr = value()?,
+                "--grpc" => options.grpc = Some(value()?),
+                "--replay-dir" => options.replay_dir = Some(PathBuf::from(value()?)),
+                "--config" => options.config = Some(PathBuf::from(value()?)),
+                "--allow-origin" => options.allow_origin = Some(value()?),
+                "--dbc" | "--ldf" => options
+                    .databases
+                    .push(parse_database_option(arg, &value()?)?),
+                other => return Err(format!("Unknown option '{}'", other)),
+            }
+        }
+        Ok(options)
+    }
+}
+
+/// DBCs and LDFs by channel
+pub type ChannelDatabases = (HashMap<u16, DbcDatabase>, HashMap<u16, LdfDatabase>);
+
+/// Parse the `CH=PATH` value of `--dbc` or `--ldf`
+pub fn parse_database_option(
+    option: &str,
+    value: &str,
+) -> Result<(ChannelType, u16, PathBuf), String> {
+    let (channel, path) = value
+        .split_once('=')
+        .and_then(|(channel, path)| Some((channel.parse().ok()?, path)))
+        .ok_or_else(|| format!("Expected CH=PATH after {}, got '{}'", option, value))?;
+    let channel_type = if option == "--dbc" {
+        ChannelType::CAN
+    } else {
+        ChannelType::LIN
+    };
+    Ok((channel_type, channel, PathBuf::from(path)))
+}
+
+/// Load the databases of a configuration's channel mappings, then `databases`
+///
+/// Later entries replace earlier ones on the same channel, so `--dbc` and
+/// `--ldf` override the configuration.
+pub fn load_channel_databases(
+    config: Option<&Path>,
+    databases: &[(ChannelType, u16, PathBuf)],
+) -> Result<ChannelDatabases, String> {
+    let mut entries: Vec<(ChannelType, u16, String)> = Vec::new();
+    if let Some(config) = config {
+        let text =
+            std::fs::read_to_string(config).map_err(|e| format!("{}: {}", config.display(), e))?;
+        let config: AppConfig =
+            serde_json::from_str(&text).map_err(|e| format!("{}: {}", config.display(), e))?;
+        entries.extend(
+            config
+                .mappings
+                .into_iter()
+                .filter(|mapping| !mapping.path.is_empty())
+                .map(|mapping| (mapping.channel_type, mapping.channel_id, mapping.path)),
+        );
+    }
+    entries.extend(databases.iter().map(|(channel_type, channel, path)| {
+        (*channel_type, *channel, path.to_string_lossy().to_string())
+    }));
+
+    let mut dbc_channels = HashMap::new();
+    let mut ldf_channels = HashMap::new();
+    for (channel_type, channel, path) in entries {
+        match crate::library::load_database_file(&path, channel_type)? {
+            crate::library::Database::Dbc(dbc) => {
+                dbc_channels.insert(channel, dbc);
+            }
+            crate::library::Database::Ldf(ldf) => {
+                ldf_channels.insert(channel, ldf);
+            }
+        }
+    }
+    Ok((dbc_channels, ldf_channels))
+}
+
+/// A parsed HTTP request
+#[derive(Debug, Clone, PartialEq)]
+pub struct Request {
+    pub method: String,
+    pub path: String,
+    pub query: HashMap<String, String>,
+    /// `Origin` header, sent by browsers
+    pub origin: Option<String>,
+    pub body: Vec<u8>,
+}
+
+impl Request {
+    /// Read one request from `reader`; errors carry the status to answer with
+    pub fn read(reader: &mut impl BufRead) -> Result<Self, (u16, String)> {
+        let mut budget = MAX_HEADER_BYTES;
+        let line = read_head_line(reader, &mut budget)?;
+        let mut parts = line.split_whitespace();
+        let (Some(method), Some(target)) = (parts.next(), parts.next()) else {
+            return Err((400, "Malformed request line".to_string()));
+        };
+        let (path, query) = target.split_once('?').unwrap_or((target, ""));
+        let mut request = Self {
+            method: method.to_uppercase(),
+            path: path.to_string(),
+            query: parse_query(query),
+            origin: None,
+            body: Vec::new(),
+        };
+
+        let mut content_length = 0;
+        let mut headers = 0;
+        loop {
+            let header = read_head_line(reader, &mut budget)?;
+            let header = header.trim_end();
+            if header.is_empty() {
+                break;
+            }
+            headers += 1;
+            if headers > MAX_HEADERS {
+                return Err((
+                    HEADERS_TOO_LARGE,
+                    format!("More than {} headers", MAX_HEADERS),
+                ));
+            }
+            let Some((name, value)) = header.split_once(':') else {
+                continue;
+            };
+            let name = name.trim();
+            if name.eq_ignore_ascii_case("content-length") {
+                content_length = value
+                    .trim()
+                    .parse()
+                    .map_err(|_| (400, "Bad Content-Length".to_string()))?;
+            } else if name.eq_ignore_ascii_case("origin") {
+                request.origin = Some(value.trim().to_string());
+            }
+        }
+        if content_length > MAX_UPLOAD_BYTES {
+            return Err((400, format!("Body over {} bytes", MAX_UPLOAD_BYTES)));
+        }
+        // The buffer grows with the bytes that arrive, not with what the client announced
+        reader
+            .take(content_length as u64)
+            .read_to_end(&mut request.body)
+            .map_err(|e| (400, e.to_string()))?;
+        if request.body.len() < content_length {
+            return Err((
+                400,
+                format!(
+                    "Body ended after {} of {} bytes",
+                    request.body.len(),
+                    content_length
+                ),
+            ));
+        }
+        Ok(request)
+    }
+
+    fn param(&self, name: &str) -> Option<&str> {
+        self.query
+            .get(name)
+            .map(String::as_str)
+            .filter(|value| !value.is_empty())
+    }
+
+    /// Optional numeric parameter; IDs may be written in hex as `0x1A0`
+    fn number<T: TryFrom<u64>>(&self, name: &str) -> Result<Option<T>, String> {
+        let Some(text) = self.param(name) else {
+            return Ok(None);
+        };
+        let parsed = match text.strip_prefix("0x").or_else(|| text.strip_prefix("0X")) {
+            Some(hex) => u64::from_str_radix(hex, 16).ok(),
+            None => text.parse().ok(),
+        };
+        parsed
+            .and_then(|value| T::try_from(value).ok())
+            .map(Some)
+            .ok_or_else(|| format!("Invalid {} '{}'", name, text))
+    }
+}
+
+/// Read the request line or a header, taking its bytes from `budget`
+///
+/// A line that does not end before the budget runs out is refused, so a
+/// client cannot make the server buffer an endless header.
+fn read_head_line(reader: &mut impl BufRead, budget: &mut u64) -> Result<String, (u16, String)> {
+    let mut line = String::new();
+    let read = reader
+        .by_ref()
+        .take(*budget)
+        .read_line(&mut line)
+        .map_err(|e| (400, e.to_string()))?;
+    *budget -= read as u64;
+    if *budget == 0 && !line.ends_with('\n') {
+        return Err((
+            HEADERS_TOO_LARGE,
+            format!("Request headers over {} bytes", MAX_HEADER_BYTES),
+        ));
+    }
+    Ok(line)
+}
+
+/// Decode `a=1&b=x%20y` into a map
+fn parse_query(query: &str) -> HashMap<String, String> {
+    query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (name, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (percent_decode(name), percent_decode(value))
+        })
+        .collect()
+}
+
+fn percent_decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let byte = match bytes[i] {
+            b'+' => b' ',
+            b'%' => match text
+                .get(i + 1..i + 3)
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+            {
+                Some(byte) => {
+                    i += 2;
+                    byte
+                }
+                None => b'%',
+            },
+            byte => byte,
+        };
+        decoded.push(byte);
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// Loaded log and databases answering the requests
+#[derive(Default)]
+pub struct DecodeServer {
+    pub messages: Vec<LogObject>,
+    /// Where the current log came from, a path or "upload"
+    pub source: Option<String>,
+    pub dbc_channels: HashMap<u16, DbcDatabase>,
+    pub ldf_channels: HashMap<u16, LdfDatabase>,
+    /// Origin of the web page allowed to send requests
+    pub allow_origin: Option<String>,
+}
+
+impl DecodeServer {
+    /// Load the databases named by `options`
+    pub fn with_databases(options: &ServeOptions) -> Result<Self, String> {
+        let (dbc_channels, ldf_channels) =
+            load_channel_databases(options.config.as_deref(), &options.databases)?;
+        Ok(Self {
+            dbc_channels,
+            ldf_channels,
+            allow_origin: options.allow_origin.clone(),
+            ..Self::default()
+        })
+    }
+
+    /// Answer one request with a status code and a JSON body
+    pub fn handle(&mut self, request: &Request) -> (u16, Value) {
+        if let Some(origin) = &request.origin {
+            if self.allow_origin.as_ref() != Some(origin) {
+                return (
+                    403,
+                    json!({ "error": format!("Origin {} not allowed", origin) }),
+                );
+            }
+            if request.path == "/load" {
+                return (
+                    403,
+                    json!({ "error": "Web pages cannot /load; use /upload" }),
+                );
+            }
+        }
+        let result = match (request.method.as_str(), request.path.as_str()) {
+            ("GET", "/health") => Ok(json!({ "status": "ok" })),
+            ("POST", "/load") => self.load(request),
+            ("POST", "/upload") => self.upload(request),
+            ("GET", "/messages") => self.query_messages(request),
+            ("GET", "/signals") => self.query_signal(request),
+            (_, "/health" | "/load" | "/upload" | "/messages" | "/signals") => {
+                return (405, json!({ "error": "Method not allowed" }));
+            }
+            _ => return (404, json!({ "error": "Not found" })),
+        };
+        match result {
+            Ok(body) => (200, body),
+            Err(e) => (400, json!({ "error": e })),
+        }
+    }
+
+    fn load(&mut self, request: &Request) -> Result<Value, String> {
+        let path = request.param("path").ok_or("Missing 'path'")?;
+        let data = std::fs::read(path).map_err(|e| format!("{}: {}", path, e))?;
+        self.replace_log(parse_blf(&data)?, path.to_string())
+    }
+
+    fn upload(&mut self, request: &Request) -> Result<Value, String> {
+        if request.body.is_empty() {
+            return Err("Send the BLF file as the request body".to_string());
+        }
+        self.replace_log(parse_blf(&request.body)?, "upload".to_string())
+    }
+
+    fn replace_log(&mut self, messages: Vec<LogObject>, source: String) -> Result<Value, String> {
+        self.messages = messages;
+        self.source = Some(source);
+        Ok(json!({ "source": self.source, "messages": self.messages.len() }))
+    }
+
+    fn query_messages(&self, request: &Request) -> Result<Value, String> {
+        let channel = request.number::<u16>("channel")?;
+        let id = request.number::<u32>("id")?;
+        let offset = request.number::<usize>("offset")?.unwrap_or(0);
+        let limit = request.number::<usize>("limit")?.unwrap_or(DEFAULT_LIMIT);
+        let decode = matches!(request.param("decode"), Some("1" | "true"));
+        let types = match request.param("type") {
+            Some(types) => parse_types(types)?,
+            None => BTreeSet::new(),
+        };
+
+        // Objects without a frame are left out of the total as well as the page
+        let matching = self
+            .messages
+            .iter()
+            .enumerate()
+            .filter(|(_, msg)| {
+                matches_id_and_channel(msg, id, channel) && matches_type(msg, &types)
+            })
+            .filter_map(|(index, msg)| {
+                Some((sequence_number(index), ExportFrame::from_log_object(msg)?))
+            });
+        let total = matching.clone().count();
+        let frames: Vec<Value> = matching
+            .skip(offset)
+            .take(limit)
+            .map(|(seq, frame)| {
+                let mut value = json!({
+                    "seq": seq,
+                    "time_ns": frame.timestamp_ns,
+                    "channel": frame.channel,
+                    "type": frame.kind,
+                    "id": frame.id,
+                    "data": frame.data.iter().map(|b| format!("{:02X}", b)).collect::<Vec<_>>().join(" "),
+                });
+                if decode {
+                    value["signals"] = decode_frame(&frame, &self.dbc_channels, &self.ldf_channels)
+                        .into_iter()
+                        .map(|signal| {
+                            json!({
+                                "name": signal.name,
+                                "value": signal.value,
+                                "raw": signal.raw,
+                                "unit": signal.unit,
+                                "text": signal.text,
+                            })
+                        })
+                        .collect();
+                }
+                value
+            })
+            .collect();
+        Ok(json!({ "total": total, "offset": offset, "messages": frames }))
+    }
+
+    fn query_signal(&self, request: &Request) -> Result<Value, String> {
+        let channel = request
+            .number::<u16>("channel")?
+            .ok_or("Missing 'channel'")?;
+        let id = request.number::<u32>("id")?.ok_or("Missing 'id'")?;
+        let name = request.param("name").ok_or("Missing 'name'")?;
+        let points: Vec<Value> = signal_series(
+            &self.messages,
+            channel,
+            id,
+            name,
+            &self.dbc_channels,
+            &self.ldf_channels,
+            &TimeOffsets::new(),
+        )
+        .into_iter()
+        .map(|(time_ns, value)| json!([time_ns, value]))
+        .collect();
+        Ok(json!({ "name": name, "channel": channel, "id": id, "points": points }))
+    }
+}
+
+/// Message kinds named in a `type=CAN,LIN` parameter
+fn parse_types(text: &str) -> Result<BTreeSet<MessageKind>, String> {
+    text.split(',')
+        .map(|name| {
+            let name = name.trim().replace([' ', '_'], "").to_lowercase();
+            MessageKind::ALL
+                .into_iter()
+                .find(|kind| kind.label().replace(' ', "").to_lowercase() == name)
+                .ok_or_else(|| format!("Unknown type '{}'", name))
+        })
+        .collect()
+}
+
+/// Parse a whole BLF file held in memory, unpacking it first if it is compressed
+fn parse_blf(data: &[u8]) -> Result<Vec<LogObject>, String> {
+    let data = unpack_log(data)?;
+    let data = &data[..];
+    let mut cursor = Cursor::new(data);
+    FileStatistics::read(&mut cursor).map_err(|e| format!("Not a BLF file: {:?}", e))?;
+    BlfParser::new()
+        .parse(&data[cursor.position() as usize..])
+        .map_err(|e| format!("BLF parse error: {:?}", e))
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        403 => "Forbidden",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        431 => "Request Header Fields Too Large",
+        _ => "Error",
+    }
+}
+
+fn respond(
+    stream: &mut TcpStream,
+    status: u16,
+    body: &Value,
+    allow_origin: Option<&str>,
+) -> std::io::Result<()> {
+    let body = body.to_string();
+    let cors = allow_origin
+        .map(|origin| {
+            format!(
+                "Access-Control-Allow-Origin: {}\r\nVary: Origin\r\n",
+                origin
+            )
+        })
+        .unwrap_or_default();
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\
+         {}Connection: close\r\n\r\n{}",
+        status,
+        reason(status),
+        body.len(),
+        cors,
+        body
+    )
+}
+
+/// Run the decode server until the process is stopped
+pub fn serve(options: ServeOptions) -> Result<(), String> {
+    let mut server = DecodeServer::with_databases(&options)?;
+    if let Some(addr) = options.grpc.clone() {
+        let dbc_channels = Arc::new(server.dbc_channels.clone());
+        let ldf_channels = Arc::new(server.ldf_channels.clone());
+        let replay_dir = options.replay_dir.clone();
+        // Fail before listening on HTTP when this build cannot stream
+        if !cfg!(feature = "grpc") {
+            return serve_grpc(&addr, replay_dir, dbc_channels, ldf_channels);
+        }
+        eprintln!("📡 Streaming signals over gRPC on {}", addr);
+        std::thread::spawn(move || {
+            if let Err(e) = serve_grpc(&addr, replay_dir, dbc_channels, ldf_channels) {
+                eprintln!("❌ gRPC server stopped: {}", e);
+            }
+        });
+    }
+    let listener =
+        TcpListener::bind(&options.addr).map_err(|e| format!("{}: {}", options.addr, e))?;
+    eprintln!(
+        "🌐 Serving on http://{} ({} DBC, {} LDF channels)",
+        options.addr,
+        server.dbc_channels.len(),
+        server.ldf_channels.len()
+    );
+
+    for stream in listener.incoming() {
+        let Ok(mut stream) = stream else {
+            continue;
+        };
+        // One stalled client would otherwise hold up every other request
+        if let Err(e) = stream
+            .set_read_timeout(Some(CLIENT_TIMEOUT))
+            .and_then(|_| stream.set_write_timeout(Some(CLIENT_TIMEOUT)))
+        {
+            eprintln!("⚠️  Could not set client timeouts: {}", e);
+            continue;
+        }
+        let request = stream
+            .try_clone()
+            .map_err(|e| (400, e.to_string()))
+            .and_then(|reader| Request::read(&mut BufReader::new(reader)));
+        let (status, body) = match request {
+            Ok(request) => server.handle(&request),
+            Err((status, e)) => (status, json!({ "error": e })),
+        };
+        if let Err(e) = respond(&mut stream, status, &body, server.allow_origin.as_deref()) {
+            eprintln!("⚠️  Response failed: {}", e);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::handlers::test_support::can;
+
+    fn get(target: &str) -> Request {
+        let raw = format!("GET {} HTTP/1.1\r\nHost: localhost\r\n\r\n", target);
+        Request::read(&mut raw.as_bytes()).unwrap()
+    }
+
+    #[test]
+    fn test_serve_options() {
+        let args: Vec<String> = [
+            "--addr",
+            "0.0.0.0:9000",
+            "--replay-dir",
+            "/logs",
+            "--dbc",
+            "1=pt.dbc",
+            "--ldf",
+            "2=body.ldf",
+        ]
+        .into_iter()
+        .map(String::from)
+        .collect();
+        let options = ServeOptions::parse(&args).unwrap();
+        assert_eq!(options.addr, "0.0.0.0:9000");
+        assert_eq!(options.replay_dir, Some(PathBuf::from("/logs")));
+        assert_eq!(
+            options.databases,
+            vec![
+                (ChannelType::CAN, 1, PathBuf::from("pt.dbc")),
+                (ChannelType::LIN, 2, PathBuf::from("body.ldf")),
+            ]
+        );
+        assert!(ServeOptions::parse(&["--dbc".to_string(), "pt.dbc".to_string()]).is_err());
+        assert!(ServeOptions::parse(&["--verbose".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_read_request() {
+        let raw =
+            b"POST /load?path=%2Flogs%2Frun%201.blf&x HTTP/1.1\r\nContent-Length: 3\r\n\r\nabcdef";
+        let request = Request::read(&mut &raw[..]).unwrap();
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.path, "/load");
+        assert_eq!(request.param("path"), Some("/logs/run 1.blf"));
+        assert_eq!(request.param("x"), None);
+        assert_eq!(request.body, b"abc");
+        assert!(Request::read(&mut &b"\r\n"[..]).is_err());
+
+        // A body shorter than announced is an error, not a buffer of that size
+        let raw = b"POST /upload HTTP/1.1\r\nContent-Length: 1000000000\r\n\r\nabc";
+        assert!(Request::read(&mut &raw[..]).is_err());
+    }
+
+    #[test]
+    fn test_read_request_header_limits() {
+        let headers = "X-Filler: 1\r\n".repeat(MAX_HEADERS);
+        let raw = format!("GET /health HTTP/1.1\r\n{}\r\n", headers);
+        assert!(Request::read(&mut raw.as_bytes()).is_ok());
+
+        let raw = format!("GET /health HTTP/1.1\r\n{}Host: x\r\n\r\n", headers);
+        let (status, _) = Request::read(&mut raw.as_bytes()).unwrap_err();
+        assert_eq!(status, HEADERS_TOO_LARGE);
+
+        let long = "a".repeat(MAX_HEADER_BYTES as usize);
+        let raw = format!("GET /health HTTP/1.1\r\nCookie: {}\r\n\r\n", long);
+        let (status, _) = Request::read(&mut raw.as_bytes()).unwrap_err();
+        assert_eq!(status, HEADERS_TOO_LARGE);
+        assert_eq!(Request::read(&mut &b"\r\n"[..]).unwrap_err().0, 400);
+    }
+
+    #[test]
+    fn test_requests_from_web_pages() {
+        let raw = b"POST /load?path=/etc/passwd HTTP/1.1\r\nOrigin: http://evil.test\r\n\r\n";
+        let load = Request::read(&mut &raw[..]).unwrap();
+        assert_eq!(load.origin.as_deref(), Some("http://evil.test"));
+        let mut health = get("/health");
+        health.origin = Some("http://localhost:3000".to_string());
+
+        let mut server = DecodeServer::default();
+        assert_eq!(server.handle(&load).0, 403);
+        assert_eq!(server.handle(&health).0, 403);
+
+        server.allow_origin = Some("http://localhost:3000".to_string());
+        assert_eq!(server.handle(&health).0, 200);
+        let mut load = load;
+        load.origin = server.allow_origin.clone();
+        assert_eq!(server.handle(&load).0, 403);
+    }
+
+    #[test]
+    fn test_query_messages() {
+        let mut server = DecodeServer {
+            messages: vec![
+                can(0x100).at(1_000).payload(&[0x12, 0x34]).build(),
+                can(0x100)
+                    .channel(2)
+                    .at(2_000)
+                    .payload(&[0x12, 0x34])
+                    .build(),
+                can(0x200).at(3_000).payload(&[0x12, 0x34]).build(),
+            ],
+            ..Default::default()
+        };
+
+        let (status, body) = server.handle(&get("/messages?channel=1&limit=1"));
+        assert_eq!(status, 200);
+        assert_eq!(body["total"], 2);
+        assert_eq!(body["messages"].as_array().unwrap().len(), 1);
+        assert_eq!(body["messages"][0]["id"], 0x100);
+        assert_eq!(body["messages"][0]["data"], "12 34");
+
+        let (_, body) = server.handle(&get("/messages?id=0x200&type=CAN&decode=1"));
+        assert_eq!(body["total"], 1);
+        assert_eq!(body["messages"][0]["seq"], 3);
+        assert_eq!(body["messages"][0]["signals"], json!([]));
+
+        let (_, body) = server.handle(&get("/messages?type=LIN"));
+        assert_eq!(body["total"], 0);
+
+        // Objects that are not frames are not counted
+        server.messages.push(LogObject::Unhandled {
+            object_type: 0,
+            timestamp: 4_000,
+            data: Vec::new(),
+        });
+        let (_, body) = server.handle(&get("/messages"));
+        assert_eq!(body["total"], 3);
+        assert_eq!(body["messages"].as_array().unwrap().len(), 3);
+
+        assert_eq!(server.handle(&get("/messages?id=xyz")).0, 400);
+        assert_eq!(server.handle(&get("/signals?channel=1&id=0x100")).0, 400);
+        assert_eq!(server.handle(&get("/health")).0, 200);
+        assert_eq!(server.handle(&get("/nothing")).0, 404);
+        assert_eq!(server.handle(&get("/load")).0, 405);
+    }
+}
@@ -0,0 +1,231 @@
+//! Simulated bus for development
+//!
+//! A built-in capture backend that needs no hardware: each
+//! [`SimulatedFrame`] is sent cyclically at its own rate, with a ramping
+//! 16-bit value in bytes 0–1 and a rolling counter in byte 7, so the
+//! streaming pipeline and the trace view have changing data to show.
+
+use crate::handlers::parse_id;
+use blf::{CanMessage, LogObject};
+use std::fmt;
+
+/// Traffic used when the simulation is started without a configuration
+pub const DEFAULT_SIMULATION: &str = "0x100 10ms ramp 5s; 0x200 100ms ramp 20s; 0x300 1000ms";
+
+/// One cyclic frame of the simulation
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimulatedFrame {
+    pub channel: u16,
+    pub id: u32,
+    pub period_ms: u32,
+    /// Time for the ramp in bytes 0–1 to go from 0 to 0xFFFF; constant 0 if unset
+    pub ramp_s: Option<f64>,
+}
+
+impl SimulatedFrame {
+    /// Ramp value at `time_ns` (little-endian in bytes 0–1)
+    pub fn ramp_value(&self, time_ns: u64) -> u16 {
+        match self.ramp_s {
+            Some(ramp_s) => {
+                let ramp_ns = (ramp_s * 1_000_000_000.0) as u64;
+                let phase = (time_ns % ramp_ns) as f64 / ramp_ns as f64;
+                (phase * u16::MAX as f64) as u16
+            }
+            None => 0,
+        }
+    }
+}
+
+impl fmt::Display for SimulatedFrame {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.channel != 1 {
+            write!(f, "{}:", self.channel)?;
+        }
+        write!(f, "0x{:X} {}ms", self.id, self.period_ms)?;
+        if let Some(ramp_s) = self.ramp_s {
+            write!(f, " ramp {}s", ramp_s)?;
+        }
+        Ok(())
+    }
+}
+
+/// Duration in milliseconds from `10ms` or `1.5s`
+pub fn parse_duration_ms(text: &str) -> Option<f64> {
+    let ms = match text.strip_suffix("ms") {
+        Some(ms) => ms.parse::<f64>().ok()?,
+        None => text.strip_suffix('s')?.parse::<f64>().ok()? * 1000.0,
+    };
+    (ms.is_finite() && ms >= 1.0).then_some(ms)
+}
+
+/// Parse frames separated by `;` or newlines
+///
+/// Syntax: `[channel:]id period [ramp duration]`, e.g. `0x100 10ms`,
+/// `2:0x200 100ms ramp 5s`. Frames without a channel go on channel 1.
+pub fn parse_simulation(text: &str) -> Result<Vec<SimulatedFrame>, String> {
+    text.split([';', '\n'])
+        .map(str::trim)
+        .filter(|frame| !frame.is_empty())
+        .map(|frame| {
+            let words: Vec<&str> = frame.split_whitespace().collect();
+            let (target, period, ramp) = match words.as_slice() {
+                [target, period] => (*target, *period, None),
+                [target, period, "ramp", ramp] => (*target, *period, Some(*ramp)),
+                _ => return Err(format!("Unknown frame: '{}'", frame)),
+            };
+            let (channel, id) = match target.split_once(':') {
+                Some((channel, id)) => (
+                    channel
+                        .parse()
+                        .map_err(|_| format!("Invalid channel: '{}'", channel))?,
+                    parse_id(id)?,
+                ),
+                None => (1, parse_id(target)?),
+            };
+            let period_ms =
+                parse_duration_ms(period).ok_or_else(|| format!("Invalid period: '{}'", period))?;
+            let ramp_s = ramp
+                .map(|ramp| {
+                    parse_duration_ms(ramp)
+                        .map(|ms| ms / 1000.0)
+                        .ok_or_else(|| format!("Invalid ramp: '{}'", ramp))
+                })
+                .transpose()?;
+            Ok(SimulatedFrame {
+                channel,
+                id,
+                period_ms: period_ms as u32,
+                ramp_s,
+            })
+        })
+        .collect()
+}
+
+/// Generates the frames that became due since the last call
+#[derive(Debug, Clone)]
+pub struct Simulator {
+    frames: Vec<SimulatedFrame>,
+    next_due_ns: Vec<u64>,
+    counters: Vec<u8>,
+}
+
+impl Simulator {
+    pub fn new(frames: Vec<SimulatedFrame>) -> Self {
+        Self {
+            next_due_ns: vec![0; frames.len()],
+            counters: vec![0; frames.len()],
+            frames,
+        }
+    }
+
+    pub fn frames(&self) -> &[SimulatedFrame] {
+        &self.frames
+    }
+
+    /// All frames due up to `now_ns` (time since the start), oldest first
+    pub fn advance(&mut self, now_ns: u64) -> Vec<LogObject> {
+        let mut batch = Vec::new();
+        while let Some((index, due_ns)) = self
+            .next_due_ns
+            .iter()
+            .copied()
+            .enumerate()
+            .filter(|(_, due_ns)| *due_ns <= now_ns)
+            .min_by_key(|(_, due_ns)| *due_ns)
+        {
+            let frame = &self.frames[index];
+            let mut msg = CanMessage::default();
+            msg.header.object_time_stamp = due_ns;
+            msg.channel = frame.channel;
+            msg.id = frame.id;
+            msg.dlc = 8;
+            msg.data[..2].copy_from_slice(&frame.ramp_value(due_ns).to_le_bytes());
+            msg.data[7] = self.counters[index];
+            batch.push(LogObject::CanMessage(msg));
+
+            self.counters[index] = self.counters[index].wrapping_add(1);
+            self.next_due_ns[index] = due_ns + frame.period_ms as u64 * 1_000_000;
+        }
+        batch
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_simulation() {
+        let frames = parse_simulation("0x100 10ms; 2:0x200 1s ramp 5s\n").unwrap();
+        assert_eq!(
+            frames,
+            vec![
+                SimulatedFrame {
+                    channel: 1,
+                    id: 0x100,
+                    period_ms: 10,
+                    ramp_s: None,
+                },
+                SimulatedFrame {
+                    channel: 2,
+                    id: 0x200,
+                    period_ms: 1000,
+                    ramp_s: Some(5.0),
+                },
+            ]
+        );
+        let text: Vec<String> = frames.iter().map(ToString::to_string).collect();
+        assert_eq!(text, vec!["0x100 10ms", "2:0x200 1000ms ramp 5s"]);
+
+        assert!(parse_simulation(DEFAULT_SIMULATION).is_ok());
+        assert!(parse_simulation("0x100 0ms").is_err());
+        assert!(parse_simulation("0x100 10ms ramp").is_err());
+    }
+
+    #[test]
+    fn test_advance_interleaves_by_rate() {
+        let mut simulator = Simulator::new(parse_simulation("0x1 10ms; 0x2 25ms").unwrap());
+        let batch = simulator.advance(50_000_000);
+        let sent: Vec<(u64, u32)> = batch
+            .iter()
+            .map(|msg| match msg {
+                LogObject::CanMessage(msg) => (msg.header.object_time_stamp / 1_000_000, msg.id),
+                _ => unreachable!(),
+            })
+            .collect();
+        assert_eq!(
+            sent,
+            vec![
+                (0, 1),
+                (0, 2),
+                (10, 1),
+                (20, 1),
+                (25, 2),
+                (30, 1),
+                (40, 1),
+                (50, 1),
+                (50, 2),
+            ]
+        );
+        // Nothing is sent twice, and the counter keeps rolling
+        let next = simulator.advance(60_000_000);
+        assert_eq!(next.len(), 1);
+        let LogObject::CanMessage(msg) = &next[0] else {
+            unreachable!()
+        };
+        assert_eq!(msg.data[7], 6);
+    }
+
+    #[test]
+    fn test_ramp_wraps_each_period() {
+        let frame = SimulatedFrame {
+            channel: 1,
+            id: 0x100,
+            period_ms: 10,
+            ramp_s: Some(2.0),
+        };
+        assert_eq!(frame.ramp_value(0), 0);
+        assert_eq!(frame.ramp_value(1_000_000_000), u16::MAX / 2);
+        assert_eq!(frame.ramp_value(2_000_000_000), 0);
+    }
+}
@@ -0,0 +1,359 @@
+//! Live capture from SocketCAN interfaces
+//!
+//! On Linux, CAN adapters (and `vcan` test interfaces) show up as network
+//! interfaces such as `can0`. A [`SocketCanSource`] opens a raw CAN socket on
+//! one of them and hands every received frame to live mode, where it is
+//! decoded with the DBC of its channel like any loaded frame. Frames carry
+//! the receive time the kernel stamped on them, so their timing does not
+//! depend on how often the UI drains the socket.
+
+use crate::handlers::CaptureSource;
+use blf::{CanFdMessage, CanMessage, CanMessage2, LogObject};
+use std::path::Path;
+
+/// Size of a `struct can_frame`
+pub const CAN_MTU: usize = 16;
+/// Size of a `struct canfd_frame`
+pub const CANFD_MTU: usize = 72;
+
+/// Set in a BLF CAN ID for 29-bit identifiers
+const EXTENDED_ID_FLAG: u32 = 0x8000_0000;
+/// Flags of a SocketCAN ID
+const CAN_EFF_FLAG: u32 = 0x8000_0000;
+const CAN_RTR_FLAG: u32 = 0x4000_0000;
+const CAN_ERR_FLAG: u32 = 0x2000_0000;
+const CAN_EFF_MASK: u32 = 0x1FFF_FFFF;
+const CAN_SFF_MASK: u32 = 0x7FF;
+/// Flags byte of a CAN FD frame
+const CANFD_BRS: u8 = 0x01;
+const CANFD_ESI: u8 = 0x02;
+/// `ARPHRD_CAN`, the hardware type of CAN network interfaces
+const ARPHRD_CAN: &str = "280";
+
+/// Names of the CAN interfaces of this computer, sorted
+///
+/// Empty where there is no `/sys/class/net`, i.e. on anything but Linux.
+pub fn socketcan_interfaces() -> Vec<String> {
+    let Ok(entries) = std::fs::read_dir(Path::new("/sys/class/net")) else {
+        return Vec::new();
+    };
+    let mut interfaces: Vec<String> = entries
+        .flatten()
+        .filter(|entry| {
+            std::fs::read_to_string(entry.path().join("type"))
+                .is_ok_and(|kind| kind.trim() == ARPHRD_CAN)
+        })
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .collect();
+    interfaces.sort();
+    interfaces
+}
+
+/// The object of a frame read from a CAN socket; `None` for error frames
+///
+/// `frame` is a `struct can_frame` or, for CAN FD, a `struct canfd_frame`.
+pub fn socketcan_frame(frame: &[u8], channel: u16, timestamp_ns: u64) -> Option<LogObject> {
+    if frame.len() < CAN_MTU {
+        return None;
+    }
+    let can_id = u32::from_ne_bytes([frame[0], frame[1], frame[2], frame[3]]);
+    if can_id & CAN_ERR_FLAG != 0 {
+        return None;
+    }
+    let id = if can_id & CAN_EFF_FLAG != 0 {
+        (can_id & CAN_EFF_MASK) | EXTENDED_ID_FLAG
+    } else {
+        can_id & CAN_SFF_MASK
+    };
+    let len = frame[4] as usize;
+
+    if frame.len() >= CANFD_MTU {
+        let len = len.min(64);
+        let flags = frame[5];
+        let mut can_fd_flags = CanFdMessage::FD_FLAG_EDL;
+        if flags & CANFD_BRS != 0 {
+            can_fd_flags |= CanFdMessage::FD_FLAG_BRS;
+        }
+        if flags & CANFD_ESI != 0 {
+            can_fd_flags |= CanFdMessage::FD_FLAG_ESI;
+        }
+        let mut msg = CanFdMessage {
+            channel,
+            dlc: crate::handlers::fd_dlc(len).unwrap_or(15),
+            id,
+            can_fd_flags,
+            valid_data_bytes: len as u8,
+            ..Default::default()
+        };
+        msg.header.object_time_stamp = timestamp_ns;
+        msg.data[..len].copy_from_slice(&frame[8..8 + len]);
+        return Some(LogObject::CanFdMessage(msg));
+    }
+
+    let len = len.min(8);
+    // len8_dlc keeps a DLC above 8 for frames with 8 data bytes
+    let len8_dlc = frame[7];
+    let mut msg = CanMessage {
+        channel,
+        id,
+        dlc: if len == 8 && (9..=15).contains(&len8_dlc) {
+            len8_dlc
+        } else {
+            len as u8
+        },
+        ..Default::default()
+    };
+    msg.header.object_time_stamp = timestamp_ns;
+    if can_id & CAN_RTR_FLAG != 0 {
+        msg.flags |= CanMessage2::FLAG_RTR;
+    } else {
+        msg.data[..len].copy_from_slice(&frame[8..8 + len]);
+    }
+    Some(LogObject::CanMessage(msg))
+}
+
+/// A raw CAN socket bound to one interface, read without blocking
+#[derive(Debug, Clone)]
+pub struct SocketCanSource {
+    interface: String,
+    channel: u16,
+    /// Shared by clones; closed once the last one is dropped
+    #[cfg(target_os = "linux")]
+    socket: std::sync::Arc<std::os::fd::OwnedFd>,
+    /// Unix time the socket was opened, the zero of the frame timestamps
+    opened_ns: u64,
+    closed: bool,
+}
+
+impl SocketCanSource {
+    /// Open `interface` (e.g. `can0`); its frames go on `channel`
+    #[cfg(target_os = "linux")]
+    pub fn open(interface: &str, channel: u16) -> Result<Self, String> {
+        let socket = sys::open(interface)?;
+        Ok(Self {
+            interface: interface.to_string(),
+            channel,
+            socket: std::sync::Arc::new(socket),
+            opened_ns: unix_now_ns(),
+            closed: false,
+        })
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    pub fn open(interface: &str, _channel: u16) -> Result<Self, String> {
+        Err(format!(
+            "Cannot open {}: SocketCAN capture needs Linux",
+            interface
+        ))
+    }
+
+    pub fn interface(&self) -> &str {
+        &self.interface
+    }
+
+    pub fn channel(&self) -> u16 {
+        self.channel
+    }
+}
+
+impl CaptureSource for SocketCanSource {
+    fn name(&self) -> String {
+        self.interface.clone()
+    }
+
+    /// The frames waiting on the socket, timed from when it was opened
+    fn read_frames(&mut self) -> Vec<LogObject> {
+        let mut frames = Vec::new();
+        #[cfg(target_os = "linux")]
+        while !self.closed {
+            let mut buffer = [0u8; CANFD_MTU];
+            match sys::read(&self.socket, &mut buffer) {
+                Ok(Some((len, received_ns))) => {
+                    let timestamp_ns = received_ns.saturating_sub(self.opened_ns);
+                    frames.extend(socketcan_frame(&buffer[..len], self.channel, timestamp_ns));
+                }
+                Ok(None) => break,
+                Err(e) => {
+                    log::warn!("Capture on {} stopped: {}", self.interface, e);
+                    self.closed = true;
+                }
+            }
+        }
+        frames
+    }
+
+    /// Whether reading failed, e.g. because the interface went down
+    fn is_closed(&self) -> bool {
+        self.closed
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn unix_now_ns() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_nanos() as u64)
+}
+
+#[cfg(target_os = "linux")]
+mod sys {
+    use super::{CAN_MTU, CANFD_MTU, unix_now_ns};
+    use std::ffi::CString;
+    use std::io::{Error, ErrorKind};
+    use std::os::fd::{AsRawFd, FromRawFd, OwnedFd};
+
+    /// `SIOCGSTAMP`: receive time of the last frame read
+    const SIOCGSTAMP: u64 = 0x8906;
+
+    pub fn open(interface: &str) -> Result<OwnedFd, String> {
+        let error = |what: &str| {
+            format!(
+                "Cannot open {}: {} ({})",
+                interface,
+                what,
+                Error::last_os_error()
+            )
+        };
+        let name =
+            CString::new(interface).map_err(|_| format!("Invalid interface '{}'", interface))?;
+        // SAFETY: `name` is a NUL-terminated string
+        let index = unsafe { libc::if_nametoindex(name.as_ptr()) };
+        if index == 0 {
+            return Err(format!("No CAN interface '{}'", interface));
+        }
+        // SAFETY: plain socket call; the descriptor is owned right after
+        let fd = unsafe {
+            libc::socket(
+                libc::PF_CAN,
+                libc::SOCK_RAW | libc::SOCK_NONBLOCK | libc::SOCK_CLOEXEC,
+                libc::CAN_RAW,
+            )
+        };
+        if fd < 0 {
+            return Err(error("socket"));
+        }
+        // SAFETY: `fd` is a new descriptor nothing else owns
+        let socket = unsafe { OwnedFd::from_raw_fd(fd) };
+
+        // Kernels without CAN FD refuse this and keep sending classic frames
+        let enable: libc::c_int = 1;
+        // SAFETY: the option value is a c_int of the given size
+        unsafe {
+            libc::setsockopt(
+                fd,
+                libc::SOL_CAN_RAW,
+                libc::CAN_RAW_FD_FRAMES,
+                (&enable as *const libc::c_int).cast(),
+                size_of::<libc::c_int>() as libc::socklen_t,
+            )
+        };
+
+        // SAFETY: an all-zero sockaddr_can is valid
+        let mut address: libc::sockaddr_can = unsafe { std::mem::zeroed() };
+        address.can_family = libc::AF_CAN as libc::sa_family_t;
+        address.can_ifindex = index as libc::c_int;
+        // SAFETY: `address` is a sockaddr_can of the given size
+        let bound = unsafe {
+            libc::bind(
+                fd,
+                (&address as *const libc::sockaddr_can).cast(),
+                size_of::<libc::sockaddr_can>() as libc::socklen_t,
+            )
+        };
+        if bound < 0 {
+            return Err(error("bind"));
+        }
+        Ok(socket)
+    }
+
+    /// Read one frame: its length and receive time, `None` if none is waiting
+    pub fn read(
+        socket: &OwnedFd,
+        buffer: &mut [u8; CANFD_MTU],
+    ) -> Result<Option<(usize, u64)>, String> {
+        let fd = socket.as_raw_fd();
+        // SAFETY: `buffer` is writable for its whole length
+        let len = unsafe { libc::read(fd, buffer.as_mut_ptr().cast(), buffer.len()) };
+        if len < 0 {
+            let e = Error::last_os_error();
+            return match e.kind() {
+                ErrorKind::WouldBlock | ErrorKind::Interrupted => Ok(None),
+                _ => Err(e.to_string()),
+            };
+        }
+        let len = len as usize;
+        if len != CAN_MTU && len != CANFD_MTU {
+            return Err(format!("unexpected frame of {} bytes", len));
+        }
+
+        let mut received = libc::timeval {
+            tv_sec: 0,
+            tv_usec: 0,
+        };
+        // SAFETY: SIOCGSTAMP writes a timeval
+        let stamped = unsafe { libc::ioctl(fd, SIOCGSTAMP as _, &mut received) } == 0;
+        let received_ns = if stamped {
+            received.tv_sec as u64 * 1_000_000_000 + received.tv_usec as u64 * 1_000
+        } else {
+            unix_now_ns()
+        };
+        Ok(Some((len, received_ns)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(can_id: u32, data: &[u8], fd_flags: Option<u8>) -> Vec<u8> {
+        let mut frame = vec![
+            0u8;
+            if fd_flags.is_some() {
+                CANFD_MTU
+            } else {
+                CAN_MTU
+            }
+        ];
+        frame[..4].copy_from_slice(&can_id.to_ne_bytes());
+        frame[4] = data.len() as u8;
+        frame[5] = fd_flags.unwrap_or(0);
+        frame[8..8 + data.len()].copy_from_slice(data);
+        frame
+    }
+
+    #[test]
+    fn test_socketcan_frames() {
+        let can = socketcan_frame(&frame(0x1A0, &[1, 2, 3], None), 2, 500).unwrap();
+        let LogObject::CanMessage(can) = can else {
+            panic!("expected a CAN frame");
+        };
+        assert_eq!((can.channel, can.id, can.dlc), (2, 0x1A0, 3));
+        assert_eq!(&can.data[..3], [1, 2, 3]);
+        assert_eq!(can.header.object_time_stamp, 500);
+
+        let remote = socketcan_frame(
+            &frame(0x18FE_F100 | CAN_EFF_FLAG | CAN_RTR_FLAG, &[], None),
+            1,
+            0,
+        );
+        let Some(LogObject::CanMessage(remote)) = remote else {
+            panic!("expected a CAN frame");
+        };
+        assert_eq!(remote.id, 0x18FE_F100 | EXTENDED_ID_FLAG);
+        assert!(remote.flags & CanMessage2::FLAG_RTR != 0);
+
+        let fd = socketcan_frame(&frame(0x123, &[0xAA; 12], Some(CANFD_BRS)), 1, 0);
+        let Some(LogObject::CanFdMessage(fd)) = fd else {
+            panic!("expected a CAN FD frame");
+        };
+        assert_eq!((fd.dlc, fd.valid_data_bytes), (9, 12));
+        assert_eq!(
+            fd.can_fd_flags,
+            CanFdMessage::FD_FLAG_EDL | CanFdMessage::FD_FLAG_BRS
+        );
+
+        assert!(socketcan_frame(&frame(CAN_ERR_FLAG | 0x4, &[0; 8], None), 1, 0).is_none());
+        assert!(socketcan_frame(&[0; 8], 1, 0).is_none());
+        assert!(SocketCanSource::open("nosuchcan0", 1).is_err());
+    }
+}
@@ -0,0 +1,191 @@
+//! Export to a SQLite database
+//!
+//! The export dialog writes SQLite in the same chunks as the text formats:
+//! [`create_sqlite_export`] lays out the schema, [`append_sqlite_rows`] adds
+//! one chunk of frames with their decoded signals in a single transaction,
+//! and [`finish_sqlite_export`] builds the indices once all rows are in.
+//! `messages.id` is the frame's sequence number, the same as the `seq`
+//! column of the CSV and JSON exports.
+//!
+//! ```sql
+//! messages(id, time_s, time_ns, channel, type, frame_id, dlc, data)
+//! signals(message_id, name, value, raw, unit, text)
+//! signal_values -- view joining each signal with its frame's time and ID
+//! ```
+
+use crate::handlers::{ExportFrame, NumberedMessage, TimeOffsets, decode_frame};
+use parser::dbc::DbcDatabase;
+use parser::ldf::LdfDatabase;
+use rusqlite::{Connection, params};
+use std::collections::HashMap;
+use std::path::Path;
+
+const SCHEMA: &str = "
+CREATE TABLE messages (
+    id INTEGER PRIMARY KEY,
+    time_s REAL NOT NULL,
+    time_ns INTEGER NOT NULL,
+    channel INTEGER NOT NULL,
+    type TEXT NOT NULL,
+    frame_id INTEGER NOT NULL,
+    dlc INTEGER NOT NULL,
+    data BLOB NOT NULL
+);
+CREATE TABLE signals (
+    message_id INTEGER NOT NULL REFERENCES messages(id),
+    name TEXT NOT NULL,
+    value REAL NOT NULL,
+    raw INTEGER NOT NULL,
+    unit TEXT NOT NULL,
+    text TEXT
+);
+CREATE VIEW signal_values AS
+    SELECT s.message_id, m.time_s, m.channel, m.frame_id, s.name, s.value, s.unit, s.text
+    FROM signals s JOIN messages m ON m.id = s.message_id;
+";
+
+/// Built after the last chunk; inserting into indexed tables is much slower
+const INDICES: &str = "
+CREATE INDEX messages_time ON messages(time_ns);
+CREATE INDEX messages_channel_frame ON messages(channel, frame_id);
+CREATE INDEX signals_message ON signals(message_id);
+CREATE INDEX signals_name ON signals(name, message_id);
+";
+
+fn open(path: &Path) -> Result<Connection, String> {
+    Connection::open(path).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+/// Replace `path` with an empty database holding the schema
+pub fn create_sqlite_export(path: &Path) -> Result<(), String> {
+    if path.exists() {
+        std::fs::remove_file(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    }
+    open(path)?.execute_batch(SCHEMA).map_err(|e| e.to_string())
+}
+
+/// Insert the frames of `messages` and their decoded signals; returns the frame count
+pub fn append_sqlite_rows(
+    path: &Path,
+    messages: &[NumberedMessage],
+    dbc_channels: &HashMap<u16, DbcDatabase>,
+    ldf_channels: &HashMap<u16, LdfDatabase>,
+    offsets: &TimeOffsets,
+) -> Result<usize, String> {
+    let mut connection = open(path)?;
+    let transaction = connection.transaction().map_err(|e| e.to_string())?;
+    let mut rows = 0;
+    {
+        let mut insert_message = transaction
+            .prepare(
+                "INSERT INTO messages (id, time_s, time_ns, channel, type, frame_id, dlc, data)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            )
+            .map_err(|e| e.to_string())?;
+        let mut insert_signal = transaction
+            .prepare(
+                "INSERT INTO signals (message_id, name, value, raw, unit, text)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            )
+            .map_err(|e| e.to_string())?;
+
+        for (seq, frame) in messages
+            .iter()
+            .filter_map(|(seq, msg)| Some((*seq as i64, ExportFrame::with_offsets(msg, offsets)?)))
+        {
+            insert_message
+                .execute(params![
+                    seq,
+                    frame.timestamp_ns as f64 / 1_000_000_000.0,
+                    frame.timestamp_ns as i64,
+                    frame.channel,
+                    frame.kind,
+                    frame.id,
+                    frame.data.len() as i64,
+                    frame.data,
+                ])
+                .map_err(|e| e.to_string())?;
+            for signal in decode_frame(&frame, dbc_channels, ldf_channels) {
+                insert_signal
+                    .execute(params![
+                        seq,
+                        signal.name,
+                        signal.value,
+                        signal.raw as i64,
+                        signal.unit,
+                        signal.text,
+                    ])
+                    .map_err(|e| e.to_string())?;
+            }
+            rows += 1;
+        }
+    }
+    transaction.commit().map_err(|e| e.to_string())?;
+    Ok(rows)
+}
+
+/// Index the finished tables
+pub fn finish_sqlite_export(path: &Path) -> Result<(), String> {
+    open(path)?
+        .execute_batch(INDICES)
+        .map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use blf::{CanMessage, LogObject};
+    use parser::dbc::DbcParser;
+
+    #[test]
+    fn test_sqlite_export() {
+        let path =
+            std::env::temp_dir().join(format!("canview_export_{}.sqlite", std::process::id()));
+        let dbc = DbcParser::new()
+            .parse("BO_ 256 Engine: 8 ECU\n SG_ Speed : 0|16@1+ (1,0) [0|65535] \"rpm\" ECU\n")
+            .unwrap();
+        let dbc_channels = HashMap::from([(1, dbc)]);
+        let messages: Vec<NumberedMessage> = (0..3u8)
+            .map(|i| {
+                let mut msg = CanMessage::default();
+                msg.header.object_time_stamp = i as u64 * 10_000_000;
+                msg.channel = 1 + (i % 2) as u16;
+                msg.id = 0x100;
+                msg.dlc = 8;
+                msg.data[0] = i;
+                // Numbered as if every other frame were filtered out
+                (2 * i as u64 + 1, LogObject::CanMessage(msg))
+            })
+            .collect();
+
+        create_sqlite_export(&path).unwrap();
+        let offsets = TimeOffsets::default();
+        let rows: usize = messages
+            .chunks(2)
+            .map(|chunk| {
+                append_sqlite_rows(&path, chunk, &dbc_channels, &HashMap::new(), &offsets).unwrap()
+            })
+            .sum();
+        finish_sqlite_export(&path).unwrap();
+        assert_eq!(rows, 3);
+
+        let connection = Connection::open(&path).unwrap();
+        let count: i64 = connection
+            .query_row("SELECT COUNT(*) FROM messages", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(count, 3);
+        // Only channel 1 has a DBC
+        let speeds: Vec<(i64, f64)> = connection
+            .prepare(
+                "SELECT message_id, value FROM signal_values WHERE name = 'Speed' ORDER BY time_s",
+            )
+            .unwrap()
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(speeds, vec![(1, 0.0), (5, 2.0)]);
+        drop(connection);
+        let _ = std::fs::remove_file(&path);
+    }
+}
@@ -0,0 +1,318 @@
+//! Named states tracked from signal values
+//!
+//! A [`StateDefinition`] follows one signal and names its values, such as an
+//! `IgnitionState` built from the value table of the ignition switch signal.
+//! [`track_states`] turns the log into a [`StateTimeline`]: the spans during
+//! which each state held, from which [`StateTimeline::stats`] derives how
+//! often and how long every state was entered.
+
+use crate::handlers::{ExportFrame, SignalRef, decode_frame, parse_signal_refs};
+use blf::LogObject;
+use parser::dbc::DbcDatabase;
+use parser::ldf::LdfDatabase;
+use std::collections::HashMap;
+use std::fmt;
+
+/// A state machine following one signal
+#[derive(Debug, Clone, PartialEq)]
+pub struct StateDefinition {
+    pub name: String,
+    pub signal: SignalRef,
+    /// Names for physical values; other values use the signal's value table,
+    /// or the value itself if it has none
+    pub states: Vec<(f64, String)>,
+}
+
+impl StateDefinition {
+    /// Name of the state for a decoded `value` with value table entry `text`
+    fn state_name(&self, value: f64, text: Option<&str>) -> String {
+        self.states
+            .iter()
+            .find(|(state_value, _)| *state_value == value)
+            .map(|(_, name)| name.clone())
+            .or_else(|| text.map(str::to_string))
+            .unwrap_or_else(|| value.to_string())
+    }
+}
+
+impl fmt::Display for StateDefinition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} = {}", self.name, self.signal)?;
+        if !self.states.is_empty() {
+            let states: Vec<String> = self
+                .states
+                .iter()
+                .map(|(value, name)| format!("{}={}", value, name))
+                .collect();
+            write!(f, " [{}]", states.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+/// Parse definitions separated by `;` or newlines
+///
+/// Syntax: `IgnitionState = 0x100 IgnSwitch` names the states from the
+/// signal's value table; `Gear = 2:0x200 GearPos [0=Park, 1=Reverse]` names
+/// them explicitly.
+pub fn parse_state_definitions(text: &str) -> Result<Vec<StateDefinition>, String> {
+    text.split([';', '\n'])
+        .map(str::trim)
+        .filter(|definition| !definition.is_empty())
+        .map(|definition| {
+            let Some((name, rest)) = definition.split_once('=') else {
+                return Err(format!(
+                    "Expected '<name> = <id> <signal>' in '{}'",
+                    definition
+                ));
+            };
+            let name = name.trim();
+            if name.is_empty() {
+                return Err(format!("Missing state machine name in '{}'", definition));
+            }
+            let (signal, states) = match rest.split_once('[') {
+                Some((signal, states)) => {
+                    let states = states
+                        .trim()
+                        .strip_suffix(']')
+                        .ok_or_else(|| format!("Missing ']' in '{}'", definition))?;
+                    (signal, parse_state_names(states)?)
+                }
+                None => (rest, Vec::new()),
+            };
+            let signal = match parse_signal_refs(signal)?.as_slice() {
+                [signal] => signal.clone(),
+                _ => return Err(format!("Expected '<id> <signal>' in '{}'", definition)),
+            };
+            Ok(StateDefinition {
+                name: name.to_string(),
+                signal,
+                states,
+            })
+        })
+        .collect()
+}
+
+/// Parse `0=Off, 1=Acc, 2=Run`
+fn parse_state_names(text: &str) -> Result<Vec<(f64, String)>, String> {
+    text.split(',')
+        .map(str::trim)
+        .filter(|state| !state.is_empty())
+        .map(|state| {
+            let (value, name) = state
+                .split_once('=')
+                .ok_or_else(|| format!("Expected '<value>=<name>' in '{}'", state))?;
+            let value = value
+                .trim()
+                .parse::<f64>()
+                .ok()
+                .filter(|value| value.is_finite())
+                .ok_or_else(|| format!("Invalid state value: '{}'", value.trim()))?;
+            Ok((value, name.trim().to_string()))
+        })
+        .collect()
+}
+
+/// A time span during which one state held
+#[derive(Debug, Clone, PartialEq)]
+pub struct StateSpan {
+    pub state: String,
+    pub start_ns: u64,
+    pub end_ns: u64,
+}
+
+impl StateSpan {
+    pub fn duration_ns(&self) -> u64 {
+        self.end_ns - self.start_ns
+    }
+}
+
+/// How often and how long a state held
+#[derive(Debug, Clone, PartialEq)]
+pub struct StateStats {
+    pub state: String,
+    pub entries: usize,
+    pub total_ns: u64,
+    pub min_ns: u64,
+    pub max_ns: u64,
+}
+
+impl StateStats {
+    pub fn mean_ns(&self) -> u64 {
+        self.total_ns / self.entries as u64
+    }
+}
+
+/// States of one definition over the log
+#[derive(Debug, Clone, PartialEq)]
+pub struct StateTimeline {
+    pub name: String,
+    /// Consecutive spans in time order; the first starts at the first frame
+    /// carrying the signal, the last ends with the log
+    pub spans: Vec<StateSpan>,
+}
+
+impl StateTimeline {
+    pub fn start_ns(&self) -> Option<u64> {
+        self.spans.first().map(|span| span.start_ns)
+    }
+
+    pub fn end_ns(&self) -> Option<u64> {
+        self.spans.last().map(|span| span.end_ns)
+    }
+
+    /// Statistics per state, in order of first appearance
+    pub fn stats(&self) -> Vec<StateStats> {
+        let mut stats: Vec<StateStats> = Vec::new();
+        for span in &self.spans {
+            let duration = span.duration_ns();
+            match stats.iter_mut().find(|stats| stats.state == span.state) {
+                Some(stats) => {
+                    stats.entries += 1;
+                    stats.total_ns += duration;
+                    stats.min_ns = stats.min_ns.min(duration);
+                    stats.max_ns = stats.max_ns.max(duration);
+                }
+                None => stats.push(StateStats {
+                    state: span.state.clone(),
+                    entries: 1,
+                    total_ns: duration,
+                    min_ns: duration,
+                    max_ns: duration,
+                }),
+            }
+        }
+        stats
+    }
+}
+
+/// Follow `definition` over `messages`
+///
+/// A new span starts whenever the state name changes, so values sharing a
+/// name stay one span.
+pub fn track_states(
+    messages: &[LogObject],
+    definition: &StateDefinition,
+    dbc_channels: &HashMap<u16, DbcDatabase>,
+    ldf_channels: &HashMap<u16, LdfDatabase>,
+) -> StateTimeline {
+    let signal = &definition.signal;
+    let mut spans: Vec<StateSpan> = Vec::new();
+    for msg in messages {
+        let Some(frame) = ExportFrame::from_log_object(msg) else {
+            continue;
+        };
+        if frame.id != signal.id || signal.channel.is_some_and(|ch| ch != frame.channel) {
+            continue;
+        }
+        let Some(decoded) = decode_frame(&frame, dbc_channels, ldf_channels)
+            .into_iter()
+            .find(|decoded| decoded.name == signal.signal)
+        else {
+            continue;
+        };
+        let state = definition.state_name(decoded.value, decoded.text.as_deref());
+        let timestamp = msg.timestamp();
+        if let Some(last) = spans.last_mut() {
+            last.end_ns = timestamp;
+            if last.state == state {
+                continue;
+            }
+        }
+        spans.push(StateSpan {
+            state,
+            start_ns: timestamp,
+            end_ns: timestamp,
+        });
+    }
+
+    if let (Some(last), Some(end)) = (spans.last_mut(), messages.last()) {
+        last.end_ns = last.end_ns.max(end.timestamp());
+    }
+    StateTimeline {
+        name: definition.name.clone(),
+        spans,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::handlers::test_support::can;
+    use parser::dbc::DbcParser;
+
+    fn dbc() -> HashMap<u16, DbcDatabase> {
+        let db = DbcParser::new()
+            .parse(
+                "BO_ 256 Ignition: 8 ECU\n \
+                 SG_ IgnSwitch : 0|8@1+ (1,0) [0|3] \"\" Vector__XXX\n\
+                 VAL_ 256 IgnSwitch 0 \"Off\" 2 \"Run\" ;\n",
+            )
+            .unwrap();
+        HashMap::from([(1, db)])
+    }
+
+    #[test]
+    fn test_parse_state_definitions() {
+        let definitions = parse_state_definitions(
+            "Ignition = 0x100 IgnSwitch; Gear = 2:0x200 GearPos [0=Park, 1=Reverse]",
+        )
+        .unwrap();
+        assert_eq!(definitions[0].to_string(), "Ignition = 0x100 IgnSwitch");
+        assert_eq!(
+            definitions[1].to_string(),
+            "Gear = 2:0x200 GearPos [0=Park, 1=Reverse]"
+        );
+        assert_eq!(
+            parse_state_definitions(&definitions[1].to_string()).unwrap()[0],
+            definitions[1]
+        );
+        assert!(parse_state_definitions("0x100 IgnSwitch").is_err());
+        assert!(parse_state_definitions("Gear = 0x200 GearPos [P=Park]").is_err());
+    }
+
+    #[test]
+    fn test_track_states_with_durations() {
+        let messages = vec![
+            can(0x100).at(1_000).build(),
+            can(0x200).at(1_500).byte(0, 9).build(),
+            can(0x100).at(2_000).build(),
+            can(0x100).at(3_000).byte(0, 1).build(),
+            can(0x100).at(4_000).byte(0, 2).build(),
+            can(0x100).at(7_000).build(),
+            can(0x100).at(8_000).byte(0, 2).build(),
+            can(0x200).at(10_000).byte(0, 9).build(),
+        ];
+        let mut definition = parse_state_definitions("Ignition = 0x100 IgnSwitch")
+            .unwrap()
+            .remove(0);
+        definition.states.push((1.0, "Acc".to_string()));
+
+        let timeline = track_states(&messages, &definition, &dbc(), &HashMap::new());
+        let spans: Vec<(&str, u64, u64)> = timeline
+            .spans
+            .iter()
+            .map(|span| (span.state.as_str(), span.start_ns, span.end_ns))
+            .collect();
+        assert_eq!(
+            spans,
+            vec![
+                ("Off", 1_000, 3_000),
+                ("Acc", 3_000, 4_000),
+                ("Run", 4_000, 7_000),
+                ("Off", 7_000, 8_000),
+                ("Run", 8_000, 10_000),
+            ]
+        );
+
+        let stats = timeline.stats();
+        let states: Vec<&str> = stats.iter().map(|stats| stats.state.as_str()).collect();
+        assert_eq!(states, vec!["Off", "Acc", "Run"]);
+        assert_eq!(stats[2].entries, 2);
+        assert_eq!(stats[2].total_ns, 5_000);
+        assert_eq!(
+            (stats[2].min_ns, stats[2].max_ns, stats[2].mean_ns()),
+            (2_000, 3_000, 2_500)
+        );
+    }
+}
@@ -0,0 +1,336 @@
+//! Per-ID message statistics
+//!
+//! [`IdStatsIndex`] is built once on the background executor right after a
+//! log is loaded, so hovering an ID in the log view only needs a hash lookup.
+//! [`BusStats`] keeps per-channel counters that are updated message by
+//! message, so live mode never rescans the log to show rates and bus load.
+
+use crate::handlers::{ExportFrame, MessageKind};
+use blf::LogObject;
+use std::collections::{BTreeMap, HashMap};
+
+/// Statistics of one message ID on one channel
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct IdStats {
+    pub count: usize,
+    pub first_ns: u64,
+    pub last_ns: u64,
+    /// Number of times each byte differed from the previous frame of this ID
+    pub byte_changes: Vec<usize>,
+    last_data: Vec<u8>,
+}
+
+impl IdStats {
+    fn record(&mut self, timestamp_ns: u64, data: &[u8]) {
+        if self.count == 0 {
+            self.first_ns = timestamp_ns;
+        } else {
+            if self.byte_changes.len() < data.len() {
+                self.byte_changes.resize(data.len(), 0);
+            }
+            for (i, byte) in data.iter().enumerate() {
+                if self.last_data.get(i) != Some(byte) {
+                    self.byte_changes[i] += 1;
+                }
+            }
+        }
+        self.count += 1;
+        self.last_ns = timestamp_ns;
+        self.last_data.clear();
+        self.last_data.extend_from_slice(data);
+    }
+
+    /// Mean time between consecutive frames, in milliseconds
+    pub fn mean_cycle_ms(&self) -> Option<f64> {
+        (self.count > 1).then(|| {
+            self.last_ns.saturating_sub(self.first_ns) as f64
+                / (self.count - 1) as f64
+                / 1_000_000.0
+        })
+    }
+
+    /// Which bytes change and how often, e.g. `B0 ×12, B3 ×4`
+    pub fn byte_change_summary(&self) -> String {
+        let changing: Vec<String> = self
+            .byte_changes
+            .iter()
+            .enumerate()
+            .filter(|(_, changes)| **changes > 0)
+            .map(|(i, changes)| format!("B{} ×{}", i, changes))
+            .collect();
+        if changing.is_empty() {
+            "no byte changes".to_string()
+        } else {
+            changing.join(", ")
+        }
+    }
+
+    /// Lines shown in the ID tooltip
+    pub fn tooltip_lines(&self) -> Vec<String> {
+        let seconds = |ns: u64| ns as f64 / 1_000_000_000.0;
+        vec![
+            format!("Count: {}", self.count),
+            format!("First: {:.6} s", seconds(self.first_ns)),
+            format!("Last: {:.6} s", seconds(self.last_ns)),
+            match self.mean_cycle_ms() {
+                Some(cycle) => format!("Mean cycle: {:.3} ms", cycle),
+                None => "Mean cycle: –".to_string(),
+            },
+            format!("Changes: {}", self.byte_change_summary()),
+        ]
+    }
+}
+
+/// Statistics of every (channel, ID) pair in a log
+#[derive(Debug, Clone, Default)]
+pub struct IdStatsIndex {
+    by_id: HashMap<(u16, u32), IdStats>,
+}
+
+impl IdStatsIndex {
+    pub fn build(messages: &[LogObject]) -> Self {
+        let mut index = Self::default();
+        index.extend(messages);
+        index
+    }
+
+    /// Add frames that follow the ones already indexed
+    pub fn extend<'a>(&mut self, messages: impl IntoIterator<Item = &'a LogObject>) {
+        for frame in messages
+            .into_iter()
+            .filter_map(ExportFrame::from_log_object)
+        {
+            self.by_id
+                .entry((frame.channel, frame.id))
+                .or_default()
+                .record(frame.timestamp_ns, &frame.data);
+        }
+    }
+
+    pub fn get(&self, channel: u16, id: u32) -> Option<&IdStats> {
+        self.by_id.get(&(channel, id))
+    }
+
+    /// Statistics of the ID carried by `msg`, if it is a bus frame
+    pub fn lookup(&self, msg: &LogObject) -> Option<&IdStats> {
+        self.get(msg.channel()?, msg.id()?)
+    }
+
+    /// Every (channel, ID) pair with its statistics, in no particular order
+    pub fn iter(&self) -> impl Iterator<Item = ((u16, u32), &IdStats)> + '_ {
+        self.by_id.iter().map(|(&key, stats)| (key, stats))
+    }
+
+    pub fn len(&self) -> usize {
+        self.by_id.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.by_id.is_empty()
+    }
+}
+
+/// Length of the window over which frame rate and bus load are measured
+pub const RATE_WINDOW_NS: u64 = 1_000_000_000;
+
+/// Nominal bit rates used for the bus load estimate
+const CAN_BITRATE: u64 = 500_000;
+const LIN_BITRATE: u64 = 19_200;
+
+/// Approximate time a frame occupies its bus, in nanoseconds
+///
+/// Bit stuffing and CAN FD bit rate switching are ignored, so this is an
+/// estimate good enough to spot an overloaded bus, not a measurement.
+fn frame_bus_time_ns(msg: &LogObject) -> u64 {
+    let (bits, bitrate) = match msg {
+        LogObject::CanMessage(m) => (can_frame_bits(m.id, m.dlc.min(8) as u64), CAN_BITRATE),
+        LogObject::CanMessage2(m) => (can_frame_bits(m.id, m.dlc.min(8) as u64), CAN_BITRATE),
+        LogObject::CanFdMessage(m) => {
+            (can_frame_bits(m.id, m.valid_data_bytes as u64), CAN_BITRATE)
+        }
+        LogObject::CanFdMessage64(m) => {
+            (can_frame_bits(m.id, m.valid_data_bytes as u64), CAN_BITRATE)
+        }
+        // Break, sync and PID, then data and checksum bytes of 10 bits each
+        LogObject::LinMessage(m) => (34 + (m.dlc as u64 + 1) * 10, LIN_BITRATE),
+        LogObject::LinMessage2(m) => (34 + (m.dlc as u64 + 1) * 10, LIN_BITRATE),
+        _ => return 0,
+    };
+    bits * 1_000_000_000 / bitrate
+}
+
+/// Bits of a CAN frame with `len` data bytes, including interframe space
+fn can_frame_bits(id: u32, len: u64) -> u64 {
+    let extended = id & 0x8000_0000 != 0 || id > 0x7FF;
+    if extended { 67 + 8 * len } else { 47 + 8 * len }
+}
+
+/// Running counters of one channel
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ChannelStats {
+    pub frames: u64,
+    pub errors: u64,
+    /// Frames per second over the last completed window
+    pub frame_rate: f64,
+    /// Estimated bus load over the last completed window, 0.0 to 1.0
+    pub bus_load: f64,
+    window_start_ns: u64,
+    window_frames: u64,
+    window_busy_ns: u64,
+}
+
+impl ChannelStats {
+    fn record(&mut self, msg: &LogObject) {
+        let timestamp_ns = msg.timestamp();
+        if self.frames + self.errors == 0 {
+            self.window_start_ns = timestamp_ns;
+        }
+        let elapsed = timestamp_ns.saturating_sub(self.window_start_ns);
+        if elapsed >= RATE_WINDOW_NS {
+            let seconds = elapsed as f64 / 1_000_000_000.0;
+            self.frame_rate = self.window_frames as f64 / seconds;
+            self.bus_load = (self.window_busy_ns as f64 / elapsed as f64).min(1.0);
+            self.window_start_ns = timestamp_ns;
+            self.window_frames = 0;
+            self.window_busy_ns = 0;
+        }
+
+        if MessageKind::of(msg) == MessageKind::Error {
+            self.errors += 1;
+        } else if msg.id().is_some() {
+            self.frames += 1;
+            self.window_frames += 1;
+            self.window_busy_ns += frame_bus_time_ns(msg);
+        }
+    }
+}
+
+/// Per-channel frame and error counters, updated incrementally
+#[derive(Debug, Clone, Default)]
+pub struct BusStats {
+    by_channel: BTreeMap<u16, ChannelStats>,
+}
+
+impl BusStats {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn build(messages: &[LogObject]) -> Self {
+        let mut stats = Self::new();
+        stats.extend(messages);
+        stats
+    }
+
+    /// Count one message; objects without a channel are ignored
+    pub fn record(&mut self, msg: &LogObject) {
+        if let Some(channel) = msg.channel() {
+            self.by_channel.entry(channel).or_default().record(msg);
+        }
+    }
+
+    pub fn extend<'a>(&mut self, messages: impl IntoIterator<Item = &'a LogObject>) {
+        for msg in messages {
+            self.record(msg);
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.by_channel.clear();
+    }
+
+    /// Channels in ascending order
+    pub fn channels(&self) -> impl Iterator<Item = (u16, &ChannelStats)> + '_ {
+        self.by_channel
+            .iter()
+            .map(|(&channel, stats)| (channel, stats))
+    }
+
+    /// Sum of the frame rates of all channels
+    pub fn total_frame_rate(&self) -> f64 {
+        self.by_channel.values().map(|stats| stats.frame_rate).sum()
+    }
+
+    pub fn total_errors(&self) -> u64 {
+        self.by_channel.values().map(|stats| stats.errors).sum()
+    }
+
+    /// Highest bus load of any channel
+    pub fn peak_bus_load(&self) -> f64 {
+        self.by_channel
+            .values()
+            .map(|stats| stats.bus_load)
+            .fold(0.0, f64::max)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::handlers::test_support::can;
+
+    #[test]
+    fn test_index_separates_channels() {
+        let index = IdStatsIndex::build(&[
+            can(0x100).data([0; 8]).build(),
+            can(0x100).channel(2).at(5).data([0; 8]).build(),
+            can(0x100).at(10).data([0; 8]).build(),
+        ]);
+        assert_eq!(index.len(), 2);
+        assert_eq!(index.get(1, 0x100).unwrap().count, 2);
+        assert_eq!(index.get(2, 0x100).unwrap().count, 1);
+        assert!(index.get(3, 0x100).is_none());
+    }
+
+    #[test]
+    fn test_cycle_time_and_byte_changes() {
+        let index = IdStatsIndex::build(&[
+            can(0x10).data([1, 0, 0, 0, 0, 0, 0, 0]).build(),
+            can(0x10)
+                .at(10_000_000)
+                .data([2, 0, 0, 5, 0, 0, 0, 0])
+                .build(),
+            can(0x10)
+                .at(20_000_000)
+                .data([3, 0, 0, 5, 0, 0, 0, 0])
+                .build(),
+        ]);
+        let stats = index.get(1, 0x10).unwrap();
+        assert_eq!(stats.first_ns, 0);
+        assert_eq!(stats.last_ns, 20_000_000);
+        assert_eq!(stats.mean_cycle_ms(), Some(10.0));
+        assert_eq!(stats.byte_change_summary(), "B0 ×2, B3 ×1");
+    }
+
+    #[test]
+    fn test_bus_stats_rate_load_and_errors() {
+        let mut stats = BusStats::new();
+        // 100 frames per second on channel 1 for two seconds
+        for i in 0..=200u64 {
+            stats.record(&can(0x100).at(i * 10_000_000).data([0; 8]).build());
+        }
+        let mut error = blf::CanErrorFrame {
+            channel: 1,
+            ..Default::default()
+        };
+        error.header.object_time_stamp = 2_000_000_000;
+        stats.record(&LogObject::CanErrorFrame(error));
+
+        let (channel, ch1) = stats.channels().next().unwrap();
+        assert_eq!(channel, 1);
+        assert_eq!(ch1.frames, 201);
+        assert_eq!(ch1.errors, 1);
+        assert_eq!(ch1.frame_rate, 100.0);
+        // 111 bits at 500 kbit/s, 100 times a second
+        assert!((ch1.bus_load - 0.0222).abs() < 1e-9);
+        assert_eq!(stats.total_errors(), 1);
+    }
+
+    #[test]
+    fn test_single_frame_has_no_cycle() {
+        let index = IdStatsIndex::build(&[can(0x10).data([0; 8]).build()]);
+        let stats = index.get(1, 0x10).unwrap();
+        assert_eq!(stats.mean_cycle_ms(), None);
+        assert_eq!(stats.byte_change_summary(), "no byte changes");
+    }
+}
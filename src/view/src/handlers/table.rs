@@ -0,0 +1,414 @@
+//! Cell text and column widths of the message table
+//!
+//! Everything here works on plain log objects and `f32` pixel values, so the
+//! table layout can be tested without a window. The rendering code only wraps
+//! the widths in `Pixels`.
+
+use crate::handlers::{TimeDisplay, bare_can_id, hex_can_id, hex_lin_id, shift_timestamp};
+use blf::LogObject;
+
+/// Approximate advance of one character of the monospace table font
+pub const CHAR_WIDTH: f32 = 8.0;
+
+/// Widths of the fixed table columns, in whole pixels
+///
+/// DATA is not included: it takes the rest of the row.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ColumnWidths {
+    pub time: f32,
+    pub channel: f32,
+    pub kind: f32,
+    pub id: f32,
+    pub dlc: f32,
+}
+
+impl ColumnWidths {
+    /// Smallest widths, enough for the header labels and their gear icons
+    pub const MIN: ColumnWidths = ColumnWidths {
+        time: 50.0,
+        channel: 60.0,
+        kind: 50.0,
+        id: 80.0,
+        dlc: 40.0,
+    };
+
+    /// Largest widths, so long cell text can't squeeze out the DATA column
+    pub const MAX: ColumnWidths = ColumnWidths {
+        time: 300.0,
+        channel: 80.0,
+        kind: 120.0,
+        id: 100.0,
+        dlc: 80.0,
+    };
+
+    /// Widen the columns to fit one row; TIME gets a wider margin (`px_3`)
+    fn fit(&mut self, time: &str, channel: u16, kind: &str, id: &str, dlc: &str) {
+        let width = |text_len: usize, padding: f32| text_len as f32 * CHAR_WIDTH + padding;
+        self.time = self.time.max(width(time.len(), 16.0));
+        self.channel = self.channel.max(width(channel.to_string().len(), 10.0));
+        self.kind = self.kind.max(width(kind.len(), 10.0));
+        self.id = self.id.max(width(id.len(), 10.0));
+        self.dlc = self.dlc.max(width(dlc.len(), 10.0));
+    }
+
+    /// Clamp to [`ColumnWidths::MAX`] and round to whole pixels, so every row
+    /// lines up the same way
+    fn finish(self) -> Self {
+        let max = Self::MAX;
+        Self {
+            time: self.time.min(max.time).round(),
+            channel: self.channel.min(max.channel).round(),
+            kind: self.kind.min(max.kind).round(),
+            id: self.id.min(max.id).round(),
+            dlc: self.dlc.min(max.dlc).round(),
+        }
+    }
+}
+
+/// Rows between the samples measured by [`column_widths`]
+///
+/// Small logs are scanned completely; larger ones are sampled at about
+/// 500 (up to 5000 rows) or 1000 evenly spread rows.
+pub fn sample_step(row_count: usize) -> usize {
+    if row_count > 5000 {
+        row_count / 1000
+    } else if row_count > 1000 {
+        row_count / 500
+    } else {
+        1
+    }
+}
+
+/// Column widths that fit the messages of a log
+///
+/// Computed over all messages, not the filtered rows, so the layout stays put
+/// while filters change. IDs are measured in decimal, their widest form.
+pub fn column_widths(messages: &[LogObject], time: TimeDisplay) -> ColumnWidths {
+    let mut widths = ColumnWidths::MIN;
+    for msg in messages.iter().step_by(sample_step(messages.len())) {
+        let (time_str, channel, kind, id, dlc, _data) = message_strings(msg, time, 0, true);
+        widths.fit(&time_str, channel, &kind, &id, &dlc);
+    }
+    widths.finish()
+}
+
+/// Payload bytes as space separated hex
+fn data_hex(data: &[u8], len: usize) -> String {
+    data.iter()
+        .take(len)
+        .map(|b| format!("{:02X}", b))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Cell text of a message row
+///
+/// `offset_ns` is the time sync correction of the message's channel;
+/// `decimal` shows IDs as decimal instead of hex padded to the frame format.
+///
+/// Returns `(time, channel, type, id, dlc, data)`.
+pub fn message_strings(
+    msg: &LogObject,
+    time: TimeDisplay,
+    offset_ns: i64,
+    decimal: bool,
+) -> (String, u16, String, String, String, String) {
+    let format_id = |id: u32| -> String {
+        if decimal {
+            bare_can_id(id).to_string()
+        } else {
+            hex_can_id(id)
+        }
+    };
+    let format_lin_id = |id: u8| -> String {
+        if decimal {
+            id.to_string()
+        } else {
+            hex_lin_id(id as u32)
+        }
+    };
+    let format_time = |timestamp: u64| time.format(shift_timestamp(timestamp, offset_ns));
+
+    match msg {
+        LogObject::CanMessage(can_msg) => {
+            let len = can_msg.data.len().min(can_msg.dlc as usize);
+            (
+                format_time(can_msg.header.object_time_stamp),
+                can_msg.channel,
+                "CAN".to_string(),
+                format_id(can_msg.id),
+                len.to_string(),
+                data_hex(&can_msg.data, len),
+            )
+        }
+        LogObject::CanMessage2(can_msg) => {
+            let len = can_msg.data.len().min(can_msg.dlc as usize);
+            (
+                format_time(can_msg.header.object_time_stamp),
+                can_msg.channel,
+                "CAN2".to_string(),
+                format_id(can_msg.id),
+                len.to_string(),
+                data_hex(&can_msg.data, len),
+            )
+        }
+        LogObject::CanErrorFrame(err) => (
+            format_time(err.header.object_time_stamp),
+            err.channel,
+            "CAN_ERR".to_string(),
+            "-".to_string(),
+            err.length.to_string(),
+            "-".to_string(),
+        ),
+        LogObject::CanFdMessage(fd_msg) => {
+            // The DLC is a code above 8; the payload length is stored separately
+            let len = fd_msg.data.len().min(fd_msg.valid_data_bytes as usize);
+            (
+                format_time(fd_msg.header.object_time_stamp),
+                fd_msg.channel,
+                "CAN_FD".to_string(),
+                format_id(fd_msg.id),
+                len.to_string(),
+                data_hex(&fd_msg.data, len),
+            )
+        }
+        LogObject::CanFdMessage64(fd_msg) => {
+            let len = fd_msg.data.len().min(fd_msg.valid_data_bytes as usize);
+            (
+                format_time(fd_msg.header.object_time_stamp),
+                fd_msg.channel as u16,
+                "CAN_FD64".to_string(),
+                format_id(fd_msg.id),
+                len.to_string(),
+                data_hex(&fd_msg.data, len),
+            )
+        }
+        LogObject::CanOverloadFrame(ov) => (
+            format_time(ov.header.object_time_stamp),
+            ov.channel,
+            "CAN_OV".to_string(),
+            "-".to_string(),
+            "-".to_string(),
+            "-".to_string(),
+        ),
+        LogObject::LinMessage(lin_msg) => {
+            let len = lin_msg.data.len().min(lin_msg.dlc as usize);
+            (
+                format_time(lin_msg.header.object_time_stamp),
+                lin_msg.channel,
+                "LIN".to_string(),
+                format_lin_id(lin_msg.id),
+                len.to_string(),
+                data_hex(&lin_msg.data, len),
+            )
+        }
+        LogObject::LinMessage2(lin_msg) => {
+            let len = lin_msg.data.len().min(lin_msg.dlc as usize);
+            (
+                format_time(lin_msg.header.object_time_stamp),
+                lin_msg.channel,
+                "LIN2".to_string(),
+                format_lin_id(lin_msg.id),
+                len.to_string(),
+                data_hex(&lin_msg.data, len),
+            )
+        }
+        _ => {
+            let type_name = format!("{:?}", msg);
+            (
+                "-".to_string(),
+                0_u16,
+                type_name.split('(').next().unwrap_or("UNKNOWN").to_string(),
+                "-".to_string(),
+                "-".to_string(),
+                "-".to_string(),
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::handlers::test_support::can;
+    use crate::models::preferences::TimestampPrecision;
+
+    const DATA: [u8; 8] = [0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77, 0x88];
+
+    fn relative() -> TimeDisplay {
+        TimeDisplay {
+            start: None,
+            precision: TimestampPrecision::Microseconds,
+        }
+    }
+
+    #[test]
+    fn test_message_strings() {
+        let msg = can(0x1AB).at(1_500_000_000).data(DATA).dlc(3).build();
+        let (time, channel, kind, id, dlc, data) = message_strings(&msg, relative(), 0, false);
+        assert_eq!(time, "1.500000");
+        assert_eq!(channel, 1);
+        assert_eq!(kind, "CAN");
+        assert_eq!(id, "0x1AB");
+        assert_eq!(dlc, "3");
+        assert_eq!(data, "11 22 33");
+
+        // Time sync offsets shift the shown time, clamping at zero
+        let (time, .., id, _, _) = message_strings(&msg, relative(), -2_000_000_000, true);
+        assert_eq!(time, "0.000000");
+        assert_eq!(id, "427");
+        // A DLC above the payload size shows the bytes that are there
+        let (.., dlc, data) =
+            message_strings(&can(1).data(DATA).dlc(15).build(), relative(), 0, true);
+        assert_eq!(dlc, "8");
+        assert_eq!(data.split(' ').count(), 8);
+    }
+
+    /// Every object type the table knows, edge DLCs and IDs, and a fallback
+    fn snapshot_rows() -> Vec<LogObject> {
+        let mut rows = vec![
+            can(0x000).data(DATA).dlc(0).build(),
+            can(0x7FF).at(999_999).data(DATA).build(),
+            can(0x1FFF_FFFF | 0x8000_0000)
+                .at(1_000_000_000)
+                .data(DATA)
+                .build(),
+            can(0x100).at(61_000_000_005).data(DATA).dlc(15).build(),
+        ];
+        let LogObject::CanMessage(mut remote) = can(0x101).data(DATA).dlc(4).build() else {
+            unreachable!()
+        };
+        remote.flags = blf::CanMessage2::FLAG_RTR;
+        rows.push(LogObject::CanMessage(remote));
+
+        let mut can2 = blf::CanMessage2 {
+            channel: 3,
+            id: 0x0C,
+            dlc: 8,
+            data: vec![0xDE, 0xAD, 0xBE],
+            ..Default::default()
+        };
+        can2.header.object_time_stamp = 2_000;
+        rows.push(LogObject::CanMessage2(can2));
+        rows.push(LogObject::CanErrorFrame(blf::CanErrorFrame {
+            channel: 2,
+            length: 4,
+            ..Default::default()
+        }));
+        for (dlc, len) in [(0, 0), (9, 12), (15, 64)] {
+            let mut fd = blf::CanFdMessage {
+                channel: 1,
+                id: 0x18DA_F110 | 0x8000_0000,
+                dlc,
+                valid_data_bytes: len,
+                ..Default::default()
+            };
+            for (i, byte) in fd.data.iter_mut().enumerate() {
+                *byte = i as u8;
+            }
+            rows.push(LogObject::CanFdMessage(fd));
+        }
+        rows.push(LogObject::CanFdMessage64(blf::CanFdMessage64 {
+            channel: 4,
+            id: 0x321,
+            dlc: 10,
+            valid_data_bytes: 16,
+            data: vec![0xFF; 16],
+            ..Default::default()
+        }));
+        rows.push(LogObject::CanOverloadFrame(blf::CanOverloadFrame {
+            channel: 1,
+            ..Default::default()
+        }));
+        rows.push(LogObject::LinMessage(blf::LinMessage {
+            header: blf::ObjectHeader {
+                object_time_stamp: 42,
+                ..Default::default()
+            },
+            channel: 5,
+            id: 0x3C,
+            dlc: 8,
+            data: [1, 2, 3, 4, 5, 6, 7, 8],
+            fsm_id: 0,
+            fsm_state: 0,
+            header_time: 0,
+            full_time: 0,
+            crc: 0,
+            dir: 0,
+        }));
+        rows.push(LogObject::LinMessage2(blf::LinMessage2 {
+            header: Default::default(),
+            channel: 6,
+            id: 0x01,
+            dlc: 2,
+            data: [0xA5, 0x5A, 0, 0, 0, 0, 0, 0],
+            crc: 0,
+            dir: 0,
+            simulated: 0,
+            is_etf: 0,
+            resp_baudrate: None,
+            exact_header_baudrate: None,
+        }));
+        rows.push(LogObject::LinCrcError(blf::LinCrcError::default()));
+        rows
+    }
+
+    /// Cell text of [`snapshot_rows`] against `snapshots/table_rows.txt`
+    ///
+    /// After an intended change, rerun with `UPDATE_SNAPSHOTS=1` to rewrite
+    /// the file and review its diff.
+    #[test]
+    fn test_row_strings_snapshot() {
+        let rendered: String = snapshot_rows()
+            .iter()
+            .map(|msg| {
+                let (time, channel, kind, id, dlc, data) =
+                    message_strings(msg, relative(), 0, false);
+                let (.., decimal_id, _, _) = message_strings(msg, relative(), 0, true);
+                let line =
+                    format!("{time} | {channel} | {kind} | {id} | {decimal_id} | {dlc} | {data}");
+                // No trailing spaces for editors to strip from rows without data
+                format!("{}\n", line.trim_end())
+            })
+            .collect();
+        let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("src/handlers/snapshots/table_rows.txt");
+        if std::env::var_os("UPDATE_SNAPSHOTS").is_some() {
+            std::fs::write(&path, &rendered).unwrap();
+            return;
+        }
+        let expected = std::fs::read_to_string(&path).unwrap_or_default();
+        assert!(
+            rendered == expected,
+            "row text differs from {}; rerun with UPDATE_SNAPSHOTS=1 if intended\n{}",
+            path.display(),
+            rendered
+        );
+    }
+
+    #[test]
+    fn test_column_widths() {
+        assert_eq!(column_widths(&[], relative()), ColumnWidths::MIN);
+
+        // "123456.000000" is 13 characters: 13 * 8 + 16
+        let messages = vec![can(0x100).at(123_456_000_000_000).data(DATA).build()];
+        let widths = column_widths(&messages, relative());
+        assert_eq!(widths.time, 120.0);
+        assert_eq!(widths.id, ColumnWidths::MIN.id);
+
+        // Absurdly long cells are capped
+        let mut widths = ColumnWidths::MIN;
+        widths.fit(&"9".repeat(100), 1, "CAN", &"9".repeat(20), "8");
+        let widths = widths.finish();
+        assert_eq!(widths.time, ColumnWidths::MAX.time);
+        assert_eq!(widths.id, ColumnWidths::MAX.id);
+    }
+
+    #[test]
+    fn test_sample_step() {
+        assert_eq!(sample_step(0), 1);
+        assert_eq!(sample_step(1000), 1);
+        assert_eq!(sample_step(4000), 8);
+        assert_eq!(sample_step(1_000_000), 1000);
+    }
+}
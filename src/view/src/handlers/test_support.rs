@@ -0,0 +1,60 @@
+//! Frames for the handler tests
+//!
+//! `can(0x100).channel(2).at(1_000).byte(0, 5).build()` is a classic CAN
+//! frame with ID 0x100 on channel 2 at 1 µs whose first byte is 5. Unset
+//! fields are channel 1, time 0, DLC 8 and zero data.
+
+use blf::{CanMessage, LogObject};
+
+/// Start a CAN frame with `id`
+pub fn can(id: u32) -> CanFrame {
+    CanFrame(CanMessage {
+        channel: 1,
+        id,
+        dlc: 8,
+        ..Default::default()
+    })
+}
+
+/// Classic CAN frame under construction, see [`can`]
+pub struct CanFrame(CanMessage);
+
+impl CanFrame {
+    pub fn channel(mut self, channel: u16) -> Self {
+        self.0.channel = channel;
+        self
+    }
+
+    /// Timestamp in nanoseconds
+    pub fn at(mut self, timestamp_ns: u64) -> Self {
+        self.0.header.object_time_stamp = timestamp_ns;
+        self
+    }
+
+    pub fn dlc(mut self, dlc: u8) -> Self {
+        self.0.dlc = dlc;
+        self
+    }
+
+    /// All eight data bytes; the DLC is left as it is
+    pub fn data(mut self, data: [u8; 8]) -> Self {
+        self.0.data = data;
+        self
+    }
+
+    pub fn byte(mut self, index: usize, value: u8) -> Self {
+        self.0.data[index] = value;
+        self
+    }
+
+    /// The first bytes of the data, with the DLC set to their count
+    pub fn payload(mut self, payload: &[u8]) -> Self {
+        self.0.data[..payload.len()].copy_from_slice(payload);
+        self.0.dlc = payload.len() as u8;
+        self
+    }
+
+    pub fn build(self) -> LogObject {
+        LogObject::CanMessage(self.0)
+    }
+}
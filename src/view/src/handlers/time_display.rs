@@ -0,0 +1,128 @@
+//! Formatting of message timestamps for display
+//!
+//! BLF files store the measurement start as the logger's wall-clock time
+//! without a time zone. [`TimeDisplay`] moves that start into the zone chosen
+//! in the preferences once, so formatting a row only adds the message offset.
+
+use crate::models::preferences::{
+    Preferences, TimeZoneDisplay, TimestampFormat, TimestampPrecision,
+};
+use chrono::{Duration, Local, NaiveDateTime, TimeZone};
+
+/// Measurement start `start`, recorded on the logger's clock, as a wall-clock
+/// time in `zone`
+///
+/// `logger_utc_offset_min` is the logger's offset from UTC; `None` means the
+/// logger ran in the same zone as this computer. DST changes during the
+/// measurement are not taken into account.
+pub fn start_in_zone(
+    start: NaiveDateTime,
+    zone: TimeZoneDisplay,
+    logger_utc_offset_min: Option<i32>,
+) -> NaiveDateTime {
+    if zone == TimeZoneDisplay::Logger {
+        return start;
+    }
+    let utc = match logger_utc_offset_min {
+        Some(offset) => start - Duration::minutes(offset as i64),
+        None => Local
+            .from_local_datetime(&start)
+            .earliest()
+            .map_or(start, |local| local.naive_utc()),
+    };
+    match zone {
+        TimeZoneDisplay::Utc => utc,
+        TimeZoneDisplay::Local => Local.from_utc_datetime(&utc).naive_local(),
+        TimeZoneDisplay::Logger => start,
+    }
+}
+
+/// How message timestamps are turned into TIME column text
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TimeDisplay {
+    /// Measurement start in the display zone; seconds since the start are
+    /// shown when `None`
+    pub start: Option<NaiveDateTime>,
+    pub precision: TimestampPrecision,
+}
+
+impl TimeDisplay {
+    pub fn new(start_time: Option<NaiveDateTime>, preferences: &Preferences) -> Self {
+        let start = match preferences.timestamp_format {
+            TimestampFormat::Absolute => start_time.map(|start| {
+                start_in_zone(
+                    start,
+                    preferences.time_zone,
+                    preferences.logger_utc_offset_min,
+                )
+            }),
+            TimestampFormat::Relative => None,
+        };
+        Self {
+            start,
+            precision: preferences.timestamp_precision,
+        }
+    }
+
+    pub fn format(&self, timestamp_ns: u64) -> String {
+        let digits = self.precision.digits();
+        match self.start {
+            Some(start) => {
+                let time = start + Duration::nanoseconds(timestamp_ns as i64);
+                let fraction = time.format("%.9f").to_string();
+                format!(
+                    "{}{}",
+                    time.format("%Y-%m-%d %H:%M:%S"),
+                    &fraction[..=digits]
+                )
+            }
+            None => {
+                let fraction = format!("{:09}", timestamp_ns % 1_000_000_000);
+                format!("{}.{}", timestamp_ns / 1_000_000_000, &fraction[..digits])
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn start() -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2025, 3, 1)
+            .unwrap()
+            .and_hms_milli_opt(12, 0, 0, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_precision_truncates_fraction() {
+        let mut display = TimeDisplay {
+            start: Some(start()),
+            precision: TimestampPrecision::Milliseconds,
+        };
+        assert_eq!(display.format(1_234_567_891), "2025-03-01 12:00:01.234");
+        display.precision = TimestampPrecision::Nanoseconds;
+        assert_eq!(
+            display.format(1_234_567_891),
+            "2025-03-01 12:00:01.234567891"
+        );
+
+        display.start = None;
+        assert_eq!(display.format(61_000_000_005), "61.000000005");
+        display.precision = TimestampPrecision::Microseconds;
+        assert_eq!(display.format(61_000_000_005), "61.000000");
+    }
+
+    #[test]
+    fn test_start_in_zone_with_logger_offset() {
+        // Logger at UTC+8
+        let utc = start_in_zone(start(), TimeZoneDisplay::Utc, Some(480));
+        assert_eq!(utc, start() - Duration::hours(8));
+        assert_eq!(
+            start_in_zone(start(), TimeZoneDisplay::Logger, Some(480)),
+            start()
+        );
+    }
+}
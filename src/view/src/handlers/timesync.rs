@@ -0,0 +1,136 @@
+//! Per-channel time offsets
+//!
+//! Logs recorded by several loggers carry some clock skew between channels.
+//! [`TimeOffsets`] holds a signed correction per channel which is added to
+//! every timestamp of that channel before it is displayed, copied or
+//! exported. Raw timestamps in the loaded log are never modified.
+
+use blf::LogObject;
+use std::collections::BTreeMap;
+
+/// Shift `timestamp_ns` by `offset_ns`, clamping at zero
+pub fn shift_timestamp(timestamp_ns: u64, offset_ns: i64) -> u64 {
+    timestamp_ns.saturating_add_signed(offset_ns)
+}
+
+/// Channel of a log object, for objects that belong to a bus channel
+pub fn message_channel(msg: &LogObject) -> Option<u16> {
+    msg.channel()
+}
+
+/// Time correction per channel, in nanoseconds
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TimeOffsets {
+    by_channel: BTreeMap<u16, i64>,
+}
+
+impl TimeOffsets {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn get(&self, channel: u16) -> i64 {
+        self.by_channel.get(&channel).copied().unwrap_or(0)
+    }
+
+    /// Set the offset of `channel`; zero removes the entry
+    pub fn set(&mut self, channel: u16, offset_ns: i64) {
+        if offset_ns == 0 {
+            self.by_channel.remove(&channel);
+        } else {
+            self.by_channel.insert(channel, offset_ns);
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.by_channel.is_empty()
+    }
+
+    /// Channels with a non-zero offset
+    pub fn iter(&self) -> impl Iterator<Item = (u16, i64)> + '_ {
+        self.by_channel
+            .iter()
+            .map(|(&channel, &offset)| (channel, offset))
+    }
+
+    /// Corrected timestamp of `msg`
+    pub fn timestamp(&self, msg: &LogObject) -> u64 {
+        let offset = message_channel(msg).map_or(0, |channel| self.get(channel));
+        shift_timestamp(msg.timestamp(), offset)
+    }
+
+    /// Change the offset of `target` so that its event at `target_ns` lines
+    /// up with the event of `reference` at `reference_ns` (both raw timestamps)
+    pub fn align(&mut self, reference: u16, reference_ns: u64, target: u16, target_ns: u64) {
+        if reference == target {
+            return;
+        }
+        let reference_time = reference_ns as i128 + self.get(reference) as i128;
+        let offset = (reference_time - target_ns as i128).clamp(i64::MIN as i128, i64::MAX as i128);
+        self.set(target, offset as i64);
+    }
+}
+
+/// Parse an offset typed in milliseconds; an empty field means no offset
+pub fn parse_offset_ms(text: &str) -> Result<i64, String> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Ok(0);
+    }
+    text.parse::<f64>()
+        .ok()
+        .filter(|ms| ms.is_finite())
+        .map(|ms| (ms * 1_000_000.0).round() as i64)
+        .ok_or_else(|| format!("Invalid offset: '{}'", text))
+}
+
+/// Offset in milliseconds as shown in the offset fields
+pub fn format_offset_ms(offset_ns: i64) -> String {
+    if offset_ns == 0 {
+        String::new()
+    } else {
+        format!("{}", offset_ns as f64 / 1_000_000.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::handlers::test_support::can;
+
+    #[test]
+    fn test_offsets_apply_per_channel() {
+        let mut offsets = TimeOffsets::new();
+        offsets.set(2, -1_500);
+        assert_eq!(offsets.timestamp(&can(0).at(10_000).build()), 10_000);
+        assert_eq!(
+            offsets.timestamp(&can(0).channel(2).at(10_000).build()),
+            8_500
+        );
+        // Never below the start of the measurement
+        assert_eq!(offsets.timestamp(&can(0).channel(2).at(1_000).build()), 0);
+        offsets.set(2, 0);
+        assert!(offsets.is_empty());
+    }
+
+    #[test]
+    fn test_align_uses_reference_offset() {
+        let mut offsets = TimeOffsets::new();
+        offsets.set(1, 200);
+        offsets.align(1, 1_000, 2, 5_000);
+        assert_eq!(offsets.get(2), -3_800);
+        assert_eq!(
+            offsets.timestamp(&can(0).channel(2).at(5_000).build()),
+            offsets.timestamp(&can(0).at(1_000).build())
+        );
+    }
+
+    #[test]
+    fn test_parse_and_format_offset_ms() {
+        assert_eq!(parse_offset_ms(""), Ok(0));
+        assert_eq!(parse_offset_ms(" -2.5 "), Ok(-2_500_000));
+        assert!(parse_offset_ms("abc").is_err());
+        assert_eq!(format_offset_ms(-2_500_000), "-2.5");
+        assert_eq!(format_offset_ms(0), "");
+    }
+}
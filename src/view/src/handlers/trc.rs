@@ -0,0 +1,380 @@
+//! PEAK trace (TRC) import
+//!
+//! PCAN-View and the PCAN-Basic tracer write `.trc` text files. Comment lines
+//! start with `;`; from version 1.1 on they carry `;$FILEVERSION=`, the start
+//! of the measurement as an OLE date in `;$STARTTIME=` and, from 2.0 on, the
+//! layout of the frame lines in `;$COLUMNS=`:
+//!
+//! ```text
+//! ;$FILEVERSION=2.1
+//! ;$STARTTIME=45217.4236111111
+//! ;$COLUMNS=N,O,T,B,I,d,R,L,D
+//!       1      1059.900 DT 1      0300 Rx -  8    00 00 00 00 04 00 00 00
+//!       2      1283.231 FB 2  18FEF100 Tx -  12   01 02 03 04 05 06 07 08 09 0A 0B 0C
+//! ```
+//!
+//! Times are milliseconds from the start. Versions 1.0 to 1.3 have fixed
+//! layouts instead, with the direction in the type column. Bus numbers are
+//! channels; files without a bus column are on channel 1. Error, status and
+//! event lines have no object to load them into and are skipped.
+
+use crate::handlers::{fd_dlc, text_log_statistics};
+use blf::{CanFdMessage, CanMessage, CanMessage2, FileStatistics, LogObject};
+use chrono::{NaiveDate, NaiveDateTime, TimeDelta};
+use std::io::Read;
+use std::path::Path;
+
+/// Set in a BLF CAN ID for 29-bit identifiers
+const EXTENDED_ID_FLAG: u32 = 0x8000_0000;
+
+/// Frames of a TRC file, timed from its start time
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrcLog {
+    /// File version, `1.0` when the file does not name one
+    pub version: String,
+    pub objects: Vec<LogObject>,
+    /// `;$STARTTIME`, in the zone of the computer that recorded the file
+    pub start_time: Option<NaiveDateTime>,
+    /// Error, status and event lines
+    pub skipped: usize,
+}
+
+/// Whether `head`, the first bytes of a file, look like a TRC file
+pub fn is_trc_log(head: &[u8]) -> bool {
+    head.trim_ascii_start().starts_with(b";")
+}
+
+/// Read the TRC file at `path`, or `None` if the file holds something else
+pub fn read_trc_file(path: &Path) -> Result<Option<(FileStatistics, TrcLog)>, String> {
+    let error = |e: std::io::Error| format!("{}: {}", path.display(), e);
+    let mut head = Vec::new();
+    std::fs::File::open(path)
+        .and_then(|file| file.take(64).read_to_end(&mut head))
+        .map_err(error)?;
+    if !is_trc_log(&head) {
+        return Ok(None);
+    }
+    // Older PCAN-View versions write the local code page
+    let data = std::fs::read(path).map_err(error)?;
+    let text = String::from_utf8_lossy(&data);
+    let log = parse_trc(&text).map_err(|e| format!("{}: {}", path.display(), e))?;
+    let file_stats = text_log_statistics(data.len() as u64, &log.objects, log.start_time);
+    Ok(Some((file_stats, log)))
+}
+
+/// Parse the lines of a TRC file
+pub fn parse_trc(text: &str) -> Result<TrcLog, String> {
+    let mut version = "1.0".to_string();
+    let mut start_time = None;
+    let mut columns: Option<Vec<char>> = None;
+    let mut objects = Vec::new();
+    let mut skipped = 0;
+
+    for (number, line) in text.lines().enumerate() {
+        let line = line.trim();
+        let error = |message: &str| format!("line {}: {}", number + 1, message);
+        if let Some(comment) = line.strip_prefix(';') {
+            let Some((key, value)) = comment.strip_prefix('$').and_then(|c| c.split_once('='))
+            else {
+                continue;
+            };
+            let value = value.trim();
+            match key.trim() {
+                "FILEVERSION" => version = value.to_string(),
+                "STARTTIME" => {
+                    let days = value
+                        .parse::<f64>()
+                        .map_err(|_| error(&format!("invalid start time '{}'", value)))?;
+                    start_time = Some(ole_date(days));
+                }
+                "COLUMNS" => {
+                    columns = Some(
+                        value
+                            .split(',')
+                            .filter_map(|c| c.trim().chars().next())
+                            .collect(),
+                    )
+                }
+                _ => {}
+            }
+            continue;
+        }
+        if line.is_empty() {
+            continue;
+        }
+
+        let layout = match &columns {
+            Some(columns) => columns.as_slice(),
+            None => default_columns(&version)
+                .ok_or_else(|| error(&format!("unsupported TRC version '{}'", version)))?,
+        };
+        match parse_line(line, layout, version.starts_with('1')).map_err(|e| error(&e))? {
+            Some(object) => objects.push(object),
+            None => skipped += 1,
+        }
+    }
+
+    Ok(TrcLog {
+        version,
+        objects,
+        start_time,
+        skipped,
+    })
+}
+
+/// Columns of the versions without `;$COLUMNS`
+fn default_columns(version: &str) -> Option<&'static [char]> {
+    match version {
+        "1.0" => Some(&['N', 'O', 'I', 'L', 'D']),
+        "1.1" => Some(&['N', 'O', 'T', 'I', 'L', 'D']),
+        "1.2" => Some(&['N', 'O', 'B', 'T', 'I', 'L', 'D']),
+        "1.3" => Some(&['N', 'O', 'B', 'T', 'I', 'R', 'L', 'D']),
+        "2.0" => Some(&['N', 'O', 'T', 'I', 'd', 'l', 'D']),
+        _ => None,
+    }
+}
+
+/// Kind of frame named in the type column
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FrameType {
+    Can,
+    Remote,
+    Fd { brs: bool, esi: bool },
+}
+
+/// The object of one frame line; `None` for error, status and event lines
+///
+/// In version 1 files the type column holds the direction.
+fn parse_line(line: &str, layout: &[char], version_1: bool) -> Result<Option<LogObject>, String> {
+    let mut fields = line.split_whitespace();
+    let mut timestamp_ns = 0;
+    let mut channel = 1;
+    let mut frame = FrameType::Can;
+    let mut tx = false;
+    let mut id = None;
+    let mut dlc = None;
+    let mut data = Vec::new();
+
+    for &column in layout {
+        if column == 'D' {
+            for byte in fields.by_ref() {
+                if byte == "RTR" {
+                    frame = FrameType::Remote;
+                    break;
+                }
+                data.push(
+                    u8::from_str_radix(byte, 16)
+                        .map_err(|_| format!("invalid data byte '{}'", byte))?,
+                );
+            }
+            break;
+        }
+        let Some(field) = fields.next() else {
+            return Err(format!("missing column {}", column));
+        };
+        match column {
+            'O' => {
+                timestamp_ns =
+                    parse_millis(field).ok_or_else(|| format!("invalid time offset '{}'", field))?
+            }
+            'B' => {
+                channel = field
+                    .parse::<u16>()
+                    .map_err(|_| format!("invalid bus '{}'", field))?
+            }
+            'T' if version_1 => match field {
+                "Rx" => {}
+                "Tx" => tx = true,
+                _ => return Ok(None),
+            },
+            'T' => {
+                frame = match field {
+                    "DT" => FrameType::Can,
+                    "RR" => FrameType::Remote,
+                    "FD" => FrameType::Fd {
+                        brs: false,
+                        esi: false,
+                    },
+                    "FB" => FrameType::Fd {
+                        brs: true,
+                        esi: false,
+                    },
+                    "FE" => FrameType::Fd {
+                        brs: false,
+                        esi: true,
+                    },
+                    "BI" => FrameType::Fd {
+                        brs: true,
+                        esi: true,
+                    },
+                    _ => return Ok(None),
+                }
+            }
+            'd' => tx = field == "Tx",
+            'I' => {
+                let raw = u32::from_str_radix(field, 16)
+                    .map_err(|_| format!("invalid ID '{}'", field))?;
+                id = Some(match field.len() {
+                    0..=4 if raw <= 0x7FF => raw,
+                    _ if raw <= 0x1FFF_FFFF => raw | EXTENDED_ID_FLAG,
+                    _ => return Err(format!("invalid ID '{}'", field)),
+                });
+            }
+            'L' => {
+                dlc = Some(
+                    field
+                        .parse::<u8>()
+                        .ok()
+                        .filter(|&dlc| dlc <= 15)
+                        .ok_or_else(|| format!("invalid DLC '{}'", field))?,
+                )
+            }
+            // Message number, reserved column and byte count
+            _ => {}
+        }
+    }
+    let id = id.ok_or("missing ID column")?;
+
+    if let FrameType::Fd { brs, esi } = frame {
+        let dlc = fd_dlc(data.len())
+            .ok_or_else(|| format!("{} bytes is not a CAN FD length", data.len()))?;
+        let mut can_fd_flags = CanFdMessage::FD_FLAG_EDL;
+        if brs {
+            can_fd_flags |= CanFdMessage::FD_FLAG_BRS;
+        }
+        if esi {
+            can_fd_flags |= CanFdMessage::FD_FLAG_ESI;
+        }
+        let mut msg = CanFdMessage {
+            channel,
+            flags: if tx { CanFdMessage::FLAG_TX } else { 0 },
+            dlc,
+            id,
+            can_fd_flags,
+            valid_data_bytes: data.len() as u8,
+            ..Default::default()
+        };
+        msg.header.object_time_stamp = timestamp_ns;
+        msg.data[..data.len()].copy_from_slice(&data);
+        return Ok(Some(LogObject::CanFdMessage(msg)));
+    }
+
+    if data.len() > 8 {
+        return Err(format!("{} data bytes in a CAN frame", data.len()));
+    }
+    let mut msg = CanMessage {
+        channel,
+        id,
+        flags: if tx { CanMessage2::FLAG_TX } else { 0 },
+        ..Default::default()
+    };
+    msg.header.object_time_stamp = timestamp_ns;
+    if frame == FrameType::Remote {
+        msg.flags |= CanMessage2::FLAG_RTR;
+        msg.dlc = dlc.unwrap_or(0).min(8);
+    } else {
+        msg.dlc = dlc.filter(|_| data.len() == 8).unwrap_or(data.len() as u8);
+        msg.data[..data.len()].copy_from_slice(&data);
+    }
+    Ok(Some(LogObject::CanMessage(msg)))
+}
+
+/// `1059.900` milliseconds as nanoseconds
+fn parse_millis(text: &str) -> Option<u64> {
+    let (millis, fraction) = text.split_once('.').unwrap_or((text, ""));
+    if fraction.len() > 6 || !fraction.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let nanos = format!("{:0<6}", fraction).parse::<u64>().ok()?;
+    millis
+        .parse::<u64>()
+        .ok()?
+        .checked_mul(1_000_000)?
+        .checked_add(nanos)
+}
+
+/// An OLE automation date (days since 1899-12-30), to the millisecond
+fn ole_date(days: f64) -> NaiveDateTime {
+    let epoch = NaiveDate::from_ymd_opt(1899, 12, 30)
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+        .unwrap_or_default();
+    epoch + TimeDelta::milliseconds((days * 86_400_000.0).round() as i64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_trc_versions() {
+        let v21 = ";$FILEVERSION=2.1\n\
+                   ;$STARTTIME=45217.5\n\
+                   ;$COLUMNS=N,O,T,B,I,d,R,L,D\n\
+                   ;   Message Number\n\
+                   \x20     1      1059.900 DT 1      0300 Rx -  8    00 00 00 00 04 00 00 00\n\
+                   \x20 2 1283.231 FB 2 18FEF100 Tx - 9 01 02 03 04 05 06 07 08 09 0A 0B 0C\n\
+                   \x20     3      1300.000 RR 1      0100 Rx -  4\n\
+                   \x20     4      1310.000 ER 1 - Rx - 5 04 00 00 08 00\n";
+        let log = parse_trc(v21).unwrap();
+        assert_eq!(log.version, "2.1");
+        assert_eq!(log.skipped, 1);
+        assert_eq!(
+            log.start_time,
+            NaiveDate::from_ymd_opt(2023, 10, 18).and_then(|date| date.and_hms_opt(12, 0, 0))
+        );
+        let LogObject::CanMessage(can) = &log.objects[0] else {
+            panic!("expected a CAN frame");
+        };
+        assert_eq!((can.channel, can.id, can.dlc), (1, 0x300, 8));
+        assert_eq!(can.header.object_time_stamp, 1_059_900_000);
+        assert_eq!(can.data[4], 4);
+        let LogObject::CanFdMessage(fd) = &log.objects[1] else {
+            panic!("expected a CAN FD frame");
+        };
+        assert_eq!(fd.channel, 2);
+        assert_eq!(fd.id, 0x18FE_F100 | EXTENDED_ID_FLAG);
+        assert_eq!((fd.dlc, fd.valid_data_bytes), (9, 12));
+        assert!(fd.flags & CanFdMessage::FLAG_TX != 0);
+        assert_eq!(
+            fd.can_fd_flags,
+            CanFdMessage::FD_FLAG_EDL | CanFdMessage::FD_FLAG_BRS
+        );
+        let LogObject::CanMessage(remote) = &log.objects[2] else {
+            panic!("expected a CAN frame");
+        };
+        assert!(remote.flags & CanMessage2::FLAG_RTR != 0);
+        assert_eq!(remote.dlc, 4);
+
+        let v11 = ";$FILEVERSION=1.1\n\
+                   \x20    1)      2850.1  Rx         0300  8  00 00 00 00 04 00 00 00\n\
+                   \x20    2)      2851.0  Warng  FFFFFFFF  4  00 00 00 08  BUSHEAVY\n\
+                   \x20    3)      2852.5  Tx         0100  2  RTR\n";
+        let log = parse_trc(v11).unwrap();
+        assert_eq!(
+            (log.objects.len(), log.skipped, log.start_time),
+            (2, 1, None)
+        );
+        assert_eq!(log.objects[0].timestamp(), 2_850_100_000);
+        let LogObject::CanMessage(remote) = &log.objects[1] else {
+            panic!("expected a CAN frame");
+        };
+        assert!(remote.flags & CanMessage2::FLAG_TX != 0);
+        assert!(remote.flags & CanMessage2::FLAG_RTR != 0);
+
+        let v13 = ";$FILEVERSION=1.3\n     1)   10.123 3  Rx  07FF -  1  AA\n";
+        let log = parse_trc(v13).unwrap();
+        assert_eq!(log.objects[0].channel(), Some(3));
+        assert_eq!(log.objects[0].id(), Some(0x7FF));
+
+        assert!(is_trc_log(b"\n;$FILEVERSION=2.1"));
+        assert!(!is_trc_log(b"(1.0) can0 1A0#"));
+        assert_eq!(
+            parse_trc(";$FILEVERSION=3.0\n 1 1.0 DT 0100 Rx 0\n").unwrap_err(),
+            "line 2: unsupported TRC version '3.0'"
+        );
+        assert_eq!(
+            parse_trc(";$FILEVERSION=1.1\n 1) 1.0 Rx 0100 1 XY\n").unwrap_err(),
+            "line 2: invalid data byte 'XY'"
+        );
+    }
+}
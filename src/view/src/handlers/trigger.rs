@@ -0,0 +1,605 @@
+//! Trigger-based capture segmentation
+//!
+//! Works like an oscilloscope trigger: [`TriggerRule`]s are checked against
+//! each frame as it arrives and every firing marks a segment boundary. The
+//! window of `pre` / `post` time around each trigger can be saved as its own
+//! BLF file. [`TriggerEngine`] is fed one message at a time, so the same
+//! rules run on a capture stream and on a log replayed from disk.
+//! [`trigger_onsets`] runs a single rule lazily, which the find dialog uses to
+//! jump to the next place a condition becomes true.
+
+use crate::handlers::{ExportFrame, decode_frame};
+use blf::LogObject;
+use parser::dbc::DbcDatabase;
+use parser::ldf::LdfDatabase;
+use std::collections::HashMap;
+use std::fmt;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+/// Direction of a threshold crossing
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Comparison {
+    Above,
+    Below,
+}
+
+/// A byte or a single bit of the payload compared with a value
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PayloadCondition {
+    pub byte: usize,
+    /// Bit within the byte, 0 being the least significant
+    pub bit: Option<u8>,
+    /// `==` when true, `!=` when false
+    pub equal: bool,
+    pub value: u8,
+}
+
+impl PayloadCondition {
+    /// Whether `data` satisfies the condition; `None` if the payload is too short
+    pub fn holds(&self, data: &[u8]) -> Option<bool> {
+        let byte = *data.get(self.byte)?;
+        let actual = match self.bit {
+            Some(bit) => (byte >> bit) & 1,
+            None => byte,
+        };
+        Some((actual == self.value) == self.equal)
+    }
+}
+
+impl fmt::Display for PayloadCondition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "byte{}", self.byte)?;
+        if let Some(bit) = self.bit {
+            write!(f, " bit{}", bit)?;
+        }
+        let op = if self.equal { "==" } else { "!=" };
+        match self.bit {
+            Some(_) => write!(f, " {} {}", op, self.value),
+            None => write!(f, " {} 0x{:02X}", op, self.value),
+        }
+    }
+}
+
+/// A condition that fires the trigger
+#[derive(Debug, Clone, PartialEq)]
+pub enum TriggerRule {
+    /// A frame with `id` is seen, optionally only on `channel`
+    IdSeen { channel: Option<u16>, id: u32 },
+    /// A decoded signal of frame `id` crosses `threshold`
+    SignalThreshold {
+        id: u32,
+        signal: String,
+        comparison: Comparison,
+        threshold: f64,
+    },
+    /// A payload condition becomes true on a frame, optionally only for `id`
+    /// and `channel`; each ID is followed separately
+    Payload {
+        channel: Option<u16>,
+        id: Option<u32>,
+        condition: PayloadCondition,
+    },
+    /// A CAN error frame is seen
+    ErrorFrame,
+}
+
+impl fmt::Display for TriggerRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TriggerRule::IdSeen {
+                channel: Some(channel),
+                id,
+            } => {
+                write!(f, "id {}:0x{:X}", channel, id)
+            }
+            TriggerRule::IdSeen { channel: None, id } => write!(f, "id 0x{:X}", id),
+            TriggerRule::SignalThreshold {
+                id,
+                signal,
+                comparison,
+                threshold,
+            } => {
+                let op = match comparison {
+                    Comparison::Above => '>',
+                    Comparison::Below => '<',
+                };
+                write!(f, "signal 0x{:X} {} {} {}", id, signal, op, threshold)
+            }
+            TriggerRule::Payload {
+                channel,
+                id,
+                condition,
+            } => match (channel, id) {
+                (Some(channel), Some(id)) => write!(f, "{}:0x{:X} {}", channel, id, condition),
+                (None, Some(id)) => write!(f, "0x{:X} {}", id, condition),
+                _ => write!(f, "{}", condition),
+            },
+            TriggerRule::ErrorFrame => write!(f, "error"),
+        }
+    }
+}
+
+/// Parse a frame ID written in hex (`0x123`) or decimal
+pub fn parse_id(text: &str) -> Result<u32, String> {
+    let parsed = match text.strip_prefix("0x").or_else(|| text.strip_prefix("0X")) {
+        Some(hex) => u32::from_str_radix(hex, 16),
+        None => text.parse(),
+    };
+    parsed.map_err(|_| format!("Invalid ID: '{}'", text))
+}
+
+/// Parse a payload rule such as `0x123 byte3 bit2 == 1` or `byte0 != 0x20`
+fn parse_payload_rule(rule: &str, words: &[&str]) -> Result<TriggerRule, String> {
+    let (target, rest) = match words.split_first() {
+        Some((first, rest)) if !first.starts_with("byte") => (Some(*first), rest),
+        _ => (None, words),
+    };
+    let (byte, bit, op, value) = match rest {
+        [byte, op, value] => (*byte, None, *op, *value),
+        [byte, bit, op, value] => (*byte, Some(*bit), *op, *value),
+        _ => {
+            return Err(format!(
+                "Expected '[id] byteN [bitN] == value' in '{}'",
+                rule
+            ));
+        }
+    };
+
+    let byte = byte
+        .strip_prefix("byte")
+        .and_then(|n| n.parse().ok())
+        .ok_or_else(|| format!("Invalid byte: '{}'", byte))?;
+    let bit = bit
+        .map(|bit| {
+            bit.strip_prefix("bit")
+                .and_then(|n| n.parse::<u8>().ok())
+                .filter(|&n| n < 8)
+                .ok_or_else(|| format!("Invalid bit: '{}'", bit))
+        })
+        .transpose()?;
+    let equal = match op {
+        "==" => true,
+        "!=" => false,
+        _ => return Err(format!("Expected '==' or '!=' in '{}'", rule)),
+    };
+    let value = parse_id(value)
+        .ok()
+        .and_then(|value| u8::try_from(value).ok())
+        .filter(|&value| bit.is_none() || value <= 1)
+        .ok_or_else(|| format!("Invalid value: '{}'", value))?;
+    let (channel, id) = match target.map(|target| target.split_once(':').ok_or(target)) {
+        None => (None, None),
+        Some(Ok((channel, id))) => (
+            Some(
+                channel
+                    .parse()
+                    .map_err(|_| format!("Invalid channel: '{}'", channel))?,
+            ),
+            Some(parse_id(id)?),
+        ),
+        Some(Err(id)) => (None, Some(parse_id(id)?)),
+    };
+
+    Ok(TriggerRule::Payload {
+        channel,
+        id,
+        condition: PayloadCondition {
+            byte,
+            bit,
+            equal,
+            value,
+        },
+    })
+}
+
+/// Parse rules separated by `;` or newlines
+///
+/// Syntax: `id 0x123`, `id 2:0x123` (channel 2 only),
+/// `signal 0x100 EngineSpeed > 3000`, `error` and payload conditions like
+/// `0x123 byte3 bit2 == 1` or `byte0 != 0x20` (any ID).
+pub fn parse_trigger_rules(text: &str) -> Result<Vec<TriggerRule>, String> {
+    text.split([';', '\n'])
+        .map(str::trim)
+        .filter(|rule| !rule.is_empty())
+        .map(|rule| {
+            let words: Vec<&str> = rule.split_whitespace().collect();
+            if words.iter().any(|word| word.starts_with("byte")) {
+                return parse_payload_rule(rule, &words);
+            }
+            match words.as_slice() {
+                ["error"] => Ok(TriggerRule::ErrorFrame),
+                ["id", target] => match target.split_once(':') {
+                    Some((channel, id)) => Ok(TriggerRule::IdSeen {
+                        channel: Some(
+                            channel
+                                .parse()
+                                .map_err(|_| format!("Invalid channel: '{}'", channel))?,
+                        ),
+                        id: parse_id(id)?,
+                    }),
+                    None => Ok(TriggerRule::IdSeen {
+                        channel: None,
+                        id: parse_id(target)?,
+                    }),
+                },
+                ["signal", id, signal, op, threshold] => Ok(TriggerRule::SignalThreshold {
+                    id: parse_id(id)?,
+                    signal: signal.to_string(),
+                    comparison: match *op {
+                        ">" => Comparison::Above,
+                        "<" => Comparison::Below,
+                        _ => return Err(format!("Expected '>' or '<' in '{}'", rule)),
+                    },
+                    threshold: threshold
+                        .parse()
+                        .map_err(|_| format!("Invalid threshold: '{}'", threshold))?,
+                }),
+                _ => Err(format!("Unknown trigger rule: '{}'", rule)),
+            }
+        })
+        .collect()
+}
+
+/// One firing of the trigger
+#[derive(Debug, Clone, PartialEq)]
+pub struct TriggerEvent {
+    /// Position of the message in the fed sequence
+    pub index: usize,
+    pub timestamp_ns: u64,
+    /// Index of the rule that fired
+    pub rule: usize,
+}
+
+/// Evaluates trigger rules message by message
+#[derive(Debug, Clone)]
+pub struct TriggerEngine {
+    rules: Vec<TriggerRule>,
+    /// Triggers within this time after a firing are ignored
+    hold_off_ns: u64,
+    last_fire_ns: Option<u64>,
+    /// Per rule: whether the threshold condition held on the last matching frame,
+    /// so thresholds fire on the crossing rather than on every frame past it
+    active: Vec<bool>,
+    /// The same for payload rules, per rule, channel and ID
+    payload_active: HashMap<(usize, u16, u32), bool>,
+    next_index: usize,
+}
+
+impl TriggerEngine {
+    pub fn new(rules: Vec<TriggerRule>, hold_off_ns: u64) -> Self {
+        let active = vec![false; rules.len()];
+        Self {
+            rules,
+            hold_off_ns,
+            last_fire_ns: None,
+            active,
+            payload_active: HashMap::new(),
+            next_index: 0,
+        }
+    }
+
+    pub fn rules(&self) -> &[TriggerRule] {
+        &self.rules
+    }
+
+    /// Check the next message; returns the event if the trigger fires
+    pub fn feed(
+        &mut self,
+        msg: &LogObject,
+        dbc_channels: &HashMap<u16, DbcDatabase>,
+        ldf_channels: &HashMap<u16, LdfDatabase>,
+    ) -> Option<TriggerEvent> {
+        let index = self.next_index;
+        self.next_index += 1;
+        let timestamp_ns = msg.timestamp();
+        let frame = ExportFrame::from_log_object(msg);
+
+        let mut fired = None;
+        for (rule_index, rule) in self.rules.iter().enumerate() {
+            let hit = match rule {
+                TriggerRule::ErrorFrame => matches!(msg, LogObject::CanErrorFrame(_)),
+                TriggerRule::IdSeen { channel, id } => frame.as_ref().is_some_and(|frame| {
+                    frame.id == *id && channel.is_none_or(|channel| frame.channel == channel)
+                }),
+                TriggerRule::SignalThreshold {
+                    id,
+                    signal,
+                    comparison,
+                    threshold,
+                } => {
+                    let Some(value) = frame
+                        .as_ref()
+                        .filter(|frame| frame.id == *id)
+                        .and_then(|frame| {
+                            decode_frame(frame, dbc_channels, ldf_channels)
+                                .into_iter()
+                                .find(|decoded| &decoded.name == signal)
+                        })
+                        .map(|decoded| decoded.value)
+                    else {
+                        continue;
+                    };
+                    let holds = match comparison {
+                        Comparison::Above => value > *threshold,
+                        Comparison::Below => value < *threshold,
+                    };
+                    let crossed = holds && !self.active[rule_index];
+                    self.active[rule_index] = holds;
+                    crossed
+                }
+                TriggerRule::Payload {
+                    channel,
+                    id,
+                    condition,
+                } => {
+                    let Some((frame, holds)) = frame
+                        .as_ref()
+                        .filter(|frame| {
+                            id.is_none_or(|id| frame.id == id)
+                                && channel.is_none_or(|channel| frame.channel == channel)
+                        })
+                        .and_then(|frame| Some((frame, condition.holds(&frame.data)?)))
+                    else {
+                        continue;
+                    };
+                    let was = self
+                        .payload_active
+                        .insert((rule_index, frame.channel, frame.id), holds)
+                        .unwrap_or(false);
+                    holds && !was
+                }
+            };
+            if hit && fired.is_none() {
+                fired = Some(rule_index);
+            }
+        }
+
+        let rule = fired?;
+        let held_off = self
+            .last_fire_ns
+            .is_some_and(|last| timestamp_ns < last.saturating_add(self.hold_off_ns));
+        if held_off {
+            return None;
+        }
+        self.last_fire_ns = Some(timestamp_ns);
+        Some(TriggerEvent {
+            index,
+            timestamp_ns,
+            rule,
+        })
+    }
+}
+
+/// Run `rules` over a whole log
+pub fn find_triggers(
+    messages: &[LogObject],
+    rules: Vec<TriggerRule>,
+    hold_off_ns: u64,
+    dbc_channels: &HashMap<u16, DbcDatabase>,
+    ldf_channels: &HashMap<u16, LdfDatabase>,
+) -> Vec<TriggerEvent> {
+    let mut engine = TriggerEngine::new(rules, hold_off_ns);
+    messages
+        .iter()
+        .filter_map(|msg| engine.feed(msg, dbc_channels, ldf_channels))
+        .collect()
+}
+
+/// Every firing of `rule` over `messages`, without hold-off
+///
+/// Evaluated lazily, so searching for the next onset stops at the first hit.
+pub fn trigger_onsets<'a>(
+    messages: &'a [LogObject],
+    rule: TriggerRule,
+    dbc_channels: &'a HashMap<u16, DbcDatabase>,
+    ldf_channels: &'a HashMap<u16, LdfDatabase>,
+) -> impl Iterator<Item = TriggerEvent> + 'a {
+    let mut engine = TriggerEngine::new(vec![rule], 0);
+    messages
+        .iter()
+        .filter_map(move |msg| engine.feed(msg, dbc_channels, ldf_channels))
+}
+
+/// Segments of `len` messages split at each trigger
+pub fn segment_ranges(len: usize, events: &[TriggerEvent]) -> Vec<Range<usize>> {
+    let mut bounds: Vec<usize> = events
+        .iter()
+        .map(|event| event.index)
+        .filter(|&index| index > 0 && index < len)
+        .collect();
+    bounds.insert(0, 0);
+    bounds.push(len);
+    bounds.dedup();
+    bounds.windows(2).map(|pair| pair[0]..pair[1]).collect()
+}
+
+/// Message range from `pre_ns` before to `post_ns` after each trigger
+///
+/// `messages` are expected in time order.
+pub fn trigger_windows(
+    messages: &[LogObject],
+    events: &[TriggerEvent],
+    pre_ns: u64,
+    post_ns: u64,
+) -> Vec<Range<usize>> {
+    events
+        .iter()
+        .map(|event| {
+            let from = event.timestamp_ns.saturating_sub(pre_ns);
+            let to = event.timestamp_ns.saturating_add(post_ns);
+            let start = messages.partition_point(|msg| msg.timestamp() < from);
+            let end = messages.partition_point(|msg| msg.timestamp() <= to);
+            start..end.max(start)
+        })
+        .collect()
+}
+
+/// Measurement start as stored in a BLF header; the Unix epoch when unknown
+pub fn blf_start_time(start_time: Option<chrono::NaiveDateTime>) -> blf::SystemTime {
+    let start_ns = start_time
+        .and_then(|start| start.and_utc().timestamp_nanos_opt())
+        .unwrap_or(0);
+    blf::SystemTime::from_timestamp_nanos(start_ns)
+}
+
+/// Measurement start of a BLF header as a date and time; `None` when unset
+pub fn naive_start_time(st: &blf::SystemTime) -> Option<chrono::NaiveDateTime> {
+    let date = chrono::NaiveDate::from_ymd_opt(st.year as i32, st.month as u32, st.day as u32)?;
+    let time = chrono::NaiveTime::from_hms_milli_opt(
+        st.hour as u32,
+        st.minute as u32,
+        st.second as u32,
+        st.milliseconds as u32,
+    )?;
+    Some(chrono::NaiveDateTime::new(date, time))
+}
+
+/// Write each window to `dir` as `<stem>_trigger_001.blf`, `<stem>_trigger_002.blf`, …
+pub fn save_trigger_windows<'a>(
+    dir: &Path,
+    stem: &str,
+    windows: impl IntoIterator<Item = &'a [LogObject]>,
+    start_time: Option<chrono::NaiveDateTime>,
+) -> Result<Vec<PathBuf>, String> {
+    windows
+        .into_iter()
+        .enumerate()
+        .map(|(n, window)| {
+            let path = dir.join(format!("{}_trigger_{:03}.blf", stem, n + 1));
+            blf::write_blf_to_file(&path, blf_start_time(start_time), window)
+                .map_err(|e| format!("{}: {}", path.display(), e))?;
+            Ok(path)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::handlers::test_support::can;
+    use blf::CanErrorFrame;
+
+    #[test]
+    fn test_parse_trigger_rules() {
+        let text = "id 0x123; id 2:0x10\nsignal 0x100 Speed > 50; error";
+        let rules = parse_trigger_rules(text).unwrap();
+        assert_eq!(
+            rules,
+            vec![
+                TriggerRule::IdSeen {
+                    channel: None,
+                    id: 0x123
+                },
+                TriggerRule::IdSeen {
+                    channel: Some(2),
+                    id: 0x10
+                },
+                TriggerRule::SignalThreshold {
+                    id: 0x100,
+                    signal: "Speed".to_string(),
+                    comparison: Comparison::Above,
+                    threshold: 50.0,
+                },
+                TriggerRule::ErrorFrame,
+            ]
+        );
+        assert_eq!(rules[2].to_string(), "signal 0x100 Speed > 50");
+        assert!(parse_trigger_rules("id zz").is_err());
+        assert!(parse_trigger_rules("signal 0x100 Speed = 5").is_err());
+    }
+
+    #[test]
+    fn test_threshold_fires_on_crossing_with_hold_off() {
+        let dbc = parser::dbc::DbcParser::new()
+            .parse(
+                "BO_ 256 Engine: 8 ECU\n SG_ Speed : 0|8@1+ (1,0) [0|255] \"km/h\" Vector__XXX\n",
+            )
+            .unwrap();
+        let dbc_channels = HashMap::from([(1, dbc)]);
+        let rules = parse_trigger_rules("signal 0x100 Speed > 50; error").unwrap();
+        let mut error = CanErrorFrame::default();
+        error.header.object_time_stamp = 5_000;
+        let messages = vec![
+            can(0x100).at(1_000).byte(0, 10).build(),
+            can(0x100).at(2_000).byte(0, 60).build(), // crossing
+            can(0x100).at(3_000).byte(0, 70).build(), // still above
+            can(0x100).at(4_000).byte(0, 20).build(),
+            LogObject::CanErrorFrame(error), // within hold-off of the crossing
+            can(0x100).at(9_000).byte(0, 90).build(), // second crossing
+        ];
+
+        let events = find_triggers(&messages, rules, 3_500, &dbc_channels, &HashMap::new());
+        let fired: Vec<_> = events.iter().map(|e| (e.index, e.rule)).collect();
+        assert_eq!(fired, vec![(1, 0), (5, 0)]);
+        assert_eq!(
+            segment_ranges(messages.len(), &events),
+            vec![0..1, 1..5, 5..6]
+        );
+    }
+
+    #[test]
+    fn test_payload_rule_fires_on_onset() {
+        let rules =
+            parse_trigger_rules("0x10 byte0 bit2 == 1; 2:0x20 byte1 != 0xff; byte3 == 7").unwrap();
+        assert_eq!(rules[0].to_string(), "0x10 byte0 bit2 == 1");
+        assert_eq!(rules[1].to_string(), "2:0x20 byte1 != 0xFF");
+        assert_eq!(
+            rules[2],
+            TriggerRule::Payload {
+                channel: None,
+                id: None,
+                condition: PayloadCondition {
+                    byte: 3,
+                    bit: None,
+                    equal: true,
+                    value: 7
+                },
+            }
+        );
+        assert!(parse_trigger_rules("byte0 bit8 == 1").is_err());
+        assert!(parse_trigger_rules("byte0 bit1 == 2").is_err());
+        assert!(parse_trigger_rules("byte0 > 2").is_err());
+
+        // Bit 2 of byte 0: 0, 1, 1, 0, 1 on 0x10; 0x11 interleaved never counts
+        let messages: Vec<_> = [0x00, 0x04, 0x0C, 0x08, 0x04]
+            .iter()
+            .enumerate()
+            .flat_map(|(i, &byte0)| {
+                let t = i as u64 * 1_000;
+                [
+                    can(0x10).at(t).byte(0, byte0).build(),
+                    can(0x11).at(t + 500).byte(0, 0x04).build(),
+                ]
+            })
+            .collect();
+        let no_dbc = HashMap::new();
+        let no_ldf = HashMap::new();
+        let onsets: Vec<usize> = trigger_onsets(&messages, rules[0].clone(), &no_dbc, &no_ldf)
+            .map(|event| event.index)
+            .collect();
+        assert_eq!(onsets, vec![2, 8]);
+    }
+
+    #[test]
+    fn test_trigger_windows() {
+        let messages: Vec<_> = (0..10).map(|i| can(0x10).at(i * 1_000).build()).collect();
+        let rules = vec![TriggerRule::IdSeen {
+            channel: Some(1),
+            id: 0x10,
+        }];
+        let events = find_triggers(&messages, rules, 100_000, &HashMap::new(), &HashMap::new());
+        assert_eq!(events.len(), 1);
+        assert_eq!(trigger_windows(&messages, &events, 500, 2_000), vec![0..3]);
+
+        let event = TriggerEvent {
+            index: 5,
+            timestamp_ns: 5_000,
+            rule: 0,
+        };
+        assert_eq!(
+            trigger_windows(&messages, &[event], 2_000, 1_000),
+            vec![3..7]
+        );
+    }
+}
@@ -0,0 +1,626 @@
+//! Live capture from Vector interfaces through the XL Driver Library
+//!
+//! Vector hardware (VN1610, VN1630, VN1640, …) is reached through
+//! `vxlapi64.dll`, which comes with the Vector driver setup. The library is
+//! loaded when a channel is first used, so CANVIEW builds and runs without the
+//! Vector SDK; without the driver, listing channels just finds none.
+//!
+//! An [`XlSource`] opens one hardware channel without init access: the bus
+//! parameters stay as set in Vector Hardware Config or by the application
+//! that owns the channel. CAN channels are read through the CAN FD event
+//! API, which delivers classic and FD frames alike; LIN channels listen as
+//! slave at 19200 baud when CANVIEW gets init access. Timestamps start at zero
+//! when the channel is activated.
+
+use crate::handlers::{CaptureSource, fd_len};
+use blf::{CanFdMessage, CanMessage, CanMessage2, LinMessage, LogObject, ObjectHeader};
+use serde::{Deserialize, Serialize};
+
+/// Set in a BLF CAN ID for 29-bit identifiers; the XL API uses the same bit
+const EXTENDED_ID_FLAG: u32 = 0x8000_0000;
+/// `XL_CAN_EV_TAG_RX_OK`: a frame received without error
+const XL_CAN_EV_TAG_RX_OK: u16 = 0x0400;
+/// `XL_CAN_RXMSG_FLAG_*` of a received CAN (FD) frame
+const XL_CAN_RXMSG_FLAG_EDL: u32 = 0x0001;
+const XL_CAN_RXMSG_FLAG_BRS: u32 = 0x0002;
+const XL_CAN_RXMSG_FLAG_ESI: u32 = 0x0004;
+const XL_CAN_RXMSG_FLAG_RTR: u32 = 0x0010;
+const XL_CAN_RXMSG_FLAG_EF: u32 = 0x0200;
+/// `XL_LIN_MSG`: tag of a LIN frame event
+const XL_LIN_MSG: u8 = 20;
+/// `XL_LIN_MSGFLAG_TX`: the frame was sent by this channel
+const XL_LIN_MSGFLAG_TX: u16 = 0x40;
+
+/// Size of an `XLcanRxEvent`
+pub const XL_CAN_RX_EVENT_SIZE: usize = 128;
+/// Size of an `XLevent`
+pub const XL_EVENT_SIZE: usize = 48;
+
+/// Hardware types (`XL_HWTYPE_*`) looked for when listing channels
+pub const XL_HW_TYPES: [(i32, &str); 7] = [
+    (1, "Virtual"),
+    (41, "VN7600"),
+    (45, "VN8900"),
+    (55, "VN1610"),
+    (57, "VN1630"),
+    (59, "VN1640"),
+    (63, "VN1611"),
+];
+
+/// One channel of a Vector interface
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct XlChannel {
+    /// `XL_HWTYPE_*` of the interface
+    pub hw_type: i32,
+    /// Index among the connected interfaces of that type, from 0
+    pub hw_index: i32,
+    /// Channel of the interface, from 0
+    pub hw_channel: i32,
+}
+
+impl XlChannel {
+    /// Name of the interface type, or its number if unknown
+    pub fn hw_name(&self) -> String {
+        XL_HW_TYPES
+            .iter()
+            .find(|(hw_type, _)| *hw_type == self.hw_type)
+            .map_or_else(
+                || format!("HW{}", self.hw_type),
+                |(_, name)| name.to_string(),
+            )
+    }
+
+    /// `VN1630 CH1`, or `VN1630 #2 CH1` for the second VN1630
+    pub fn label(&self) -> String {
+        if self.hw_index == 0 {
+            format!("{} CH{}", self.hw_name(), self.hw_channel + 1)
+        } else {
+            format!(
+                "{} #{} CH{}",
+                self.hw_name(),
+                self.hw_index + 1,
+                self.hw_channel + 1
+            )
+        }
+    }
+}
+
+/// The object of an `XLcanRxEvent`; `None` for anything but a received frame
+///
+/// `event` holds the event as written by `xlCanReceive`.
+pub fn xl_can_event(event: &[u8], channel: u16) -> Option<LogObject> {
+    if event.len() < XL_CAN_RX_EVENT_SIZE {
+        return None;
+    }
+    let u16_at = |offset: usize| u16::from_le_bytes([event[offset], event[offset + 1]]);
+    let u32_at = |offset: usize| u32::from_le_bytes(event[offset..offset + 4].try_into().unwrap());
+    if u16_at(4) != XL_CAN_EV_TAG_RX_OK {
+        return None;
+    }
+    let timestamp_ns = u64::from_le_bytes(event[24..32].try_into().unwrap());
+    // XL_CAN_EV_RX_MSG follows the 32 byte header
+    let can_id = u32_at(32);
+    let flags = u32_at(36);
+    if flags & XL_CAN_RXMSG_FLAG_EF != 0 {
+        return None;
+    }
+    let id = if can_id & EXTENDED_ID_FLAG != 0 {
+        (can_id & 0x1FFF_FFFF) | EXTENDED_ID_FLAG
+    } else {
+        can_id & 0x7FF
+    };
+    let dlc = event[58].min(15);
+    let data = &event[64..128];
+
+    if flags & XL_CAN_RXMSG_FLAG_EDL != 0 {
+        let len = fd_len(dlc);
+        let mut can_fd_flags = CanFdMessage::FD_FLAG_EDL;
+        if flags & XL_CAN_RXMSG_FLAG_BRS != 0 {
+            can_fd_flags |= CanFdMessage::FD_FLAG_BRS;
+        }
+        if flags & XL_CAN_RXMSG_FLAG_ESI != 0 {
+            can_fd_flags |= CanFdMessage::FD_FLAG_ESI;
+        }
+        let mut msg = CanFdMessage {
+            channel,
+            dlc,
+            id,
+            can_fd_flags,
+            valid_data_bytes: len as u8,
+            ..Default::default()
+        };
+        msg.header.object_time_stamp = timestamp_ns;
+        msg.data[..len].copy_from_slice(&data[..len]);
+        return Some(LogObject::CanFdMessage(msg));
+    }
+
+    let mut msg = CanMessage {
+        channel,
+        id,
+        dlc,
+        ..Default::default()
+    };
+    msg.header.object_time_stamp = timestamp_ns;
+    if flags & XL_CAN_RXMSG_FLAG_RTR != 0 {
+        msg.flags |= CanMessage2::FLAG_RTR;
+    } else {
+        let len = (dlc as usize).min(8);
+        msg.data[..len].copy_from_slice(&data[..len]);
+    }
+    Some(LogObject::CanMessage(msg))
+}
+
+/// The object of an `XLevent`; `None` for anything but a LIN frame
+pub fn xl_lin_event(event: &[u8], channel: u16) -> Option<LogObject> {
+    if event.len() < XL_EVENT_SIZE || event[0] != XL_LIN_MSG {
+        return None;
+    }
+    let timestamp_ns = u64::from_le_bytes(event[8..16].try_into().unwrap());
+    // s_xl_lin_msg starts the tag data at offset 16
+    let dlc = event[17].min(8);
+    let flags = u16::from_le_bytes([event[18], event[19]]);
+    let header = ObjectHeader {
+        object_time_stamp: timestamp_ns,
+        ..Default::default()
+    };
+    let mut data = [0u8; 8];
+    data[..dlc as usize].copy_from_slice(&event[20..20 + dlc as usize]);
+    Some(LogObject::LinMessage(LinMessage {
+        header,
+        channel,
+        id: event[16] & 0x3F,
+        dlc,
+        data,
+        fsm_id: 0,
+        fsm_state: 0,
+        header_time: 0,
+        full_time: 0,
+        crc: event[28],
+        dir: u8::from(flags & XL_LIN_MSGFLAG_TX != 0),
+    }))
+}
+
+/// Channels of the connected Vector interfaces, empty without the driver
+pub fn xl_channels() -> Vec<XlChannel> {
+    #[cfg(windows)]
+    {
+        sys::channels()
+    }
+    #[cfg(not(windows))]
+    {
+        Vec::new()
+    }
+}
+
+/// An activated XL Driver port on one channel, read without blocking
+#[derive(Debug)]
+pub struct XlSource {
+    xl_channel: XlChannel,
+    channel: u16,
+    #[cfg(windows)]
+    port: sys::Port,
+    closed: bool,
+}
+
+impl XlSource {
+    /// Open `xl_channel` for CAN (`lin == false`) or LIN; its frames go on `channel`
+    #[cfg(windows)]
+    pub fn open(xl_channel: XlChannel, channel: u16, lin: bool) -> Result<Self, String> {
+        let port = sys::Port::open(xl_channel, lin)
+            .map_err(|e| format!("Cannot open {}: {}", xl_channel.label(), e))?;
+        Ok(Self {
+            xl_channel,
+            channel,
+            port,
+            closed: false,
+        })
+    }
+
+    #[cfg(not(windows))]
+    pub fn open(xl_channel: XlChannel, _channel: u16, _lin: bool) -> Result<Self, String> {
+        Err(format!(
+            "Cannot open {}: the Vector XL Driver needs Windows",
+            xl_channel.label()
+        ))
+    }
+
+    pub fn xl_channel(&self) -> XlChannel {
+        self.xl_channel
+    }
+
+    pub fn channel(&self) -> u16 {
+        self.channel
+    }
+}
+
+impl CaptureSource for XlSource {
+    fn name(&self) -> String {
+        self.xl_channel.label()
+    }
+
+    fn read_frames(&mut self) -> Vec<LogObject> {
+        #[cfg(windows)]
+        if !self.closed {
+            match self.port.receive(self.channel) {
+                Ok(frames) => return frames,
+                Err(e) => {
+                    log::warn!("Capture on {} stopped: {}", self.xl_channel.label(), e);
+                    self.closed = true;
+                }
+            }
+        }
+        Vec::new()
+    }
+
+    fn is_closed(&self) -> bool {
+        self.closed
+    }
+}
+
+#[cfg(windows)]
+mod sys {
+    use super::{
+        XL_CAN_RX_EVENT_SIZE, XL_EVENT_SIZE, XL_HW_TYPES, XlChannel, xl_can_event, xl_lin_event,
+    };
+    use blf::LogObject;
+    use std::ffi::{CStr, c_char, c_void};
+    use std::sync::OnceLock;
+
+    type XlStatus = i16;
+    type XlAccess = u64;
+    type XlPortHandle = i32;
+
+    const XL_SUCCESS: XlStatus = 0;
+    const XL_ERR_QUEUE_IS_EMPTY: XlStatus = 10;
+    const XL_INVALID_PORTHANDLE: XlPortHandle = -1;
+    const XL_BUS_TYPE_CAN: u32 = 0x0000_0001;
+    const XL_BUS_TYPE_LIN: u32 = 0x0000_0002;
+    const XL_INTERFACE_VERSION: u32 = 3;
+    const XL_INTERFACE_VERSION_V4: u32 = 4;
+    const XL_ACTIVATE_RESET_CLOCK: u32 = 8;
+    const XL_LIN_SLAVE: u32 = 2;
+    const XL_LIN_VERSION_2_0: u32 = 0x20;
+    /// Receive queue sizes: events for LIN (V3), bytes for CAN (V4)
+    const LIN_QUEUE_SIZE: u32 = 1 << 14;
+    const CAN_QUEUE_SIZE: u32 = 1 << 19;
+    /// Events read per `xlReceive` call
+    const LIN_EVENT_BATCH: usize = 64;
+    /// Interfaces of one type and channels per interface probed by `channels`
+    const MAX_HW_INDEX: i32 = 4;
+    const MAX_HW_CHANNEL: i32 = 8;
+
+    /// `XLlinStatPar`
+    #[repr(C)]
+    struct LinStatPar {
+        lin_mode: u32,
+        baudrate: i32,
+        lin_version: u32,
+        reserved: u32,
+    }
+
+    /// Event buffers, aligned like the driver's structs
+    #[repr(C, align(8))]
+    struct CanRxEvent([u8; XL_CAN_RX_EVENT_SIZE]);
+    #[repr(C, align(8))]
+    #[derive(Clone, Copy)]
+    struct Event([u8; XL_EVENT_SIZE]);
+
+    #[link(name = "kernel32")]
+    unsafe extern "system" {
+        fn LoadLibraryA(name: *const c_char) -> *mut c_void;
+        fn GetProcAddress(module: *mut c_void, name: *const c_char) -> *mut c_void;
+    }
+
+    /// Entry points of `vxlapi64.dll`
+    struct Api {
+        open_driver: unsafe extern "system" fn() -> XlStatus,
+        close_driver: unsafe extern "system" fn() -> XlStatus,
+        get_channel_index: unsafe extern "system" fn(i32, i32, i32) -> i32,
+        get_channel_mask: unsafe extern "system" fn(i32, i32, i32) -> XlAccess,
+        open_port: unsafe extern "system" fn(
+            *mut XlPortHandle,
+            *const c_char,
+            XlAccess,
+            *mut XlAccess,
+            u32,
+            u32,
+            u32,
+        ) -> XlStatus,
+        activate_channel: unsafe extern "system" fn(XlPortHandle, XlAccess, u32, u32) -> XlStatus,
+        deactivate_channel: unsafe extern "system" fn(XlPortHandle, XlAccess) -> XlStatus,
+        close_port: unsafe extern "system" fn(XlPortHandle) -> XlStatus,
+        receive: unsafe extern "system" fn(XlPortHandle, *mut u32, *mut Event) -> XlStatus,
+        can_receive: unsafe extern "system" fn(XlPortHandle, *mut CanRxEvent) -> XlStatus,
+        lin_set_channel_params:
+            unsafe extern "system" fn(XlPortHandle, XlAccess, LinStatPar) -> XlStatus,
+        get_error_string: unsafe extern "system" fn(XlStatus) -> *const c_char,
+    }
+
+    impl Api {
+        fn load() -> Result<Api, String> {
+            // SAFETY: the name is NUL-terminated; a null module is checked below
+            let module = unsafe { LoadLibraryA(c"vxlapi64.dll".as_ptr()) };
+            if module.is_null() {
+                return Err("vxlapi64.dll not found, is the Vector driver installed?".into());
+            }
+            macro_rules! function {
+                ($name:literal) => {{
+                    // SAFETY: `module` is loaded and stays loaded for the process
+                    let address = unsafe { GetProcAddress(module, $name.as_ptr()) };
+                    if address.is_null() {
+                        return Err(format!("vxlapi64.dll lacks {:?}", $name));
+                    }
+                    // SAFETY: the XL API declares the function with the field's signature
+                    unsafe { std::mem::transmute_copy::<*mut c_void, _>(&address) }
+                }};
+            }
+            Ok(Api {
+                open_driver: function!(c"xlOpenDriver"),
+                close_driver: function!(c"xlCloseDriver"),
+                get_channel_index: function!(c"xlGetChannelIndex"),
+                get_channel_mask: function!(c"xlGetChannelMask"),
+                open_port: function!(c"xlOpenPort"),
+                activate_channel: function!(c"xlActivateChannel"),
+                deactivate_channel: function!(c"xlDeactivateChannel"),
+                close_port: function!(c"xlClosePort"),
+                receive: function!(c"xlReceive"),
+                can_receive: function!(c"xlCanReceive"),
+                lin_set_channel_params: function!(c"xlLinSetChannelParams"),
+                get_error_string: function!(c"xlGetErrorString"),
+            })
+        }
+
+        fn error(&self, status: XlStatus) -> String {
+            // SAFETY: the driver returns a static NUL-terminated string
+            let text = unsafe { (self.get_error_string)(status) };
+            if text.is_null() {
+                return format!("XL error {}", status);
+            }
+            // SAFETY: checked for null above
+            unsafe { CStr::from_ptr(text) }
+                .to_string_lossy()
+                .into_owned()
+        }
+
+        fn check(&self, status: XlStatus) -> Result<(), String> {
+            if status == XL_SUCCESS {
+                Ok(())
+            } else {
+                Err(self.error(status))
+            }
+        }
+    }
+
+    fn api() -> Result<&'static Api, String> {
+        static API: OnceLock<Result<Api, String>> = OnceLock::new();
+        API.get_or_init(Api::load).as_ref().map_err(Clone::clone)
+    }
+
+    pub fn channels() -> Vec<XlChannel> {
+        let Ok(api) = api() else {
+            return Vec::new();
+        };
+        // SAFETY: plain driver calls; the driver is closed again below
+        if unsafe { (api.open_driver)() } != XL_SUCCESS {
+            return Vec::new();
+        }
+        let mut channels = Vec::new();
+        for (hw_type, _) in XL_HW_TYPES {
+            for hw_index in 0..MAX_HW_INDEX {
+                for hw_channel in 0..MAX_HW_CHANNEL {
+                    // SAFETY: returns -1 for channels that don't exist
+                    if unsafe { (api.get_channel_index)(hw_type, hw_index, hw_channel) } >= 0 {
+                        channels.push(XlChannel {
+                            hw_type,
+                            hw_index,
+                            hw_channel,
+                        });
+                    }
+                }
+            }
+        }
+        // SAFETY: balances the open above
+        unsafe { (api.close_driver)() };
+        channels
+    }
+
+    /// An open port with one activated channel; closed when dropped
+    #[derive(Debug)]
+    pub struct Port {
+        handle: XlPortHandle,
+        mask: XlAccess,
+        lin: bool,
+    }
+
+    impl Port {
+        pub fn open(channel: XlChannel, lin: bool) -> Result<Port, String> {
+            let api = api()?;
+            // SAFETY: reference counted by the driver, closed in `drop`
+            api.check(unsafe { (api.open_driver)() })?;
+            let mut port = Port {
+                handle: XL_INVALID_PORTHANDLE,
+                mask: 0,
+                lin,
+            };
+            // SAFETY: plain driver call
+            port.mask = unsafe {
+                (api.get_channel_mask)(channel.hw_type, channel.hw_index, channel.hw_channel)
+            };
+            if port.mask == 0 {
+                return Err("channel not found".into());
+            }
+            let (queue_size, version, bus_type) = if lin {
+                (LIN_QUEUE_SIZE, XL_INTERFACE_VERSION, XL_BUS_TYPE_LIN)
+            } else {
+                (CAN_QUEUE_SIZE, XL_INTERFACE_VERSION_V4, XL_BUS_TYPE_CAN)
+            };
+            // Only LIN asks for init access, to set the baud rate
+            let mut permission = if lin { port.mask } else { 0 };
+            // SAFETY: the out pointers are valid for the call
+            api.check(unsafe {
+                (api.open_port)(
+                    &mut port.handle,
+                    c"CANVIEW".as_ptr(),
+                    port.mask,
+                    &mut permission,
+                    queue_size,
+                    version,
+                    bus_type,
+                )
+            })?;
+            if lin && permission & port.mask != 0 {
+                let params = LinStatPar {
+                    lin_mode: XL_LIN_SLAVE,
+                    baudrate: 19200,
+                    lin_version: XL_LIN_VERSION_2_0,
+                    reserved: 0,
+                };
+                // SAFETY: the port is open with init access to the channel
+                api.check(unsafe { (api.lin_set_channel_params)(port.handle, port.mask, params) })?;
+            }
+            // SAFETY: the port is open
+            api.check(unsafe {
+                (api.activate_channel)(port.handle, port.mask, bus_type, XL_ACTIVATE_RESET_CLOCK)
+            })?;
+            Ok(port)
+        }
+
+        /// The frames waiting in the receive queue
+        pub fn receive(&mut self, channel: u16) -> Result<Vec<LogObject>, String> {
+            let api = api()?;
+            let mut frames = Vec::new();
+            if self.lin {
+                loop {
+                    let mut events = [Event([0; XL_EVENT_SIZE]); LIN_EVENT_BATCH];
+                    let mut count = LIN_EVENT_BATCH as u32;
+                    // SAFETY: `events` holds `count` events
+                    let status =
+                        unsafe { (api.receive)(self.handle, &mut count, events.as_mut_ptr()) };
+                    if status == XL_ERR_QUEUE_IS_EMPTY {
+                        break;
+                    }
+                    api.check(status)?;
+                    let count = (count as usize).min(LIN_EVENT_BATCH);
+                    frames.extend(
+                        events[..count]
+                            .iter()
+                            .filter_map(|event| xl_lin_event(&event.0, channel)),
+                    );
+                }
+            } else {
+                loop {
+                    let mut event = CanRxEvent([0; XL_CAN_RX_EVENT_SIZE]);
+                    // SAFETY: `event` has the size of an XLcanRxEvent
+                    let status = unsafe { (api.can_receive)(self.handle, &mut event) };
+                    if status == XL_ERR_QUEUE_IS_EMPTY {
+                        break;
+                    }
+                    api.check(status)?;
+                    frames.extend(xl_can_event(&event.0, channel));
+                }
+            }
+            Ok(frames)
+        }
+    }
+
+    impl Drop for Port {
+        fn drop(&mut self) {
+            let Ok(api) = api() else {
+                return;
+            };
+            // SAFETY: undoes `open`; calls on an invalid handle just fail
+            unsafe {
+                if self.handle != XL_INVALID_PORTHANDLE {
+                    (api.deactivate_channel)(self.handle, self.mask);
+                    (api.close_port)(self.handle);
+                }
+                (api.close_driver)();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn can_event(can_id: u32, flags: u32, dlc: u8, data: &[u8]) -> [u8; XL_CAN_RX_EVENT_SIZE] {
+        let mut event = [0u8; XL_CAN_RX_EVENT_SIZE];
+        event[4..6].copy_from_slice(&XL_CAN_EV_TAG_RX_OK.to_le_bytes());
+        event[24..32].copy_from_slice(&1_500u64.to_le_bytes());
+        event[32..36].copy_from_slice(&can_id.to_le_bytes());
+        event[36..40].copy_from_slice(&flags.to_le_bytes());
+        event[58] = dlc;
+        event[64..64 + data.len()].copy_from_slice(data);
+        event
+    }
+
+    #[test]
+    fn test_xl_events() {
+        let Some(LogObject::CanMessage(can)) = xl_can_event(&can_event(0x1A0, 0, 3, &[1, 2, 3]), 2)
+        else {
+            panic!("expected a CAN frame");
+        };
+        assert_eq!((can.channel, can.id, can.dlc), (2, 0x1A0, 3));
+        assert_eq!(&can.data[..3], [1, 2, 3]);
+        assert_eq!(can.header.object_time_stamp, 1_500);
+
+        let fd = can_event(
+            0x18FE_F100 | EXTENDED_ID_FLAG,
+            XL_CAN_RXMSG_FLAG_EDL | XL_CAN_RXMSG_FLAG_BRS,
+            9,
+            &[0xAA; 12],
+        );
+        let Some(LogObject::CanFdMessage(fd)) = xl_can_event(&fd, 1) else {
+            panic!("expected a CAN FD frame");
+        };
+        assert_eq!(fd.id, 0x18FE_F100 | EXTENDED_ID_FLAG);
+        assert_eq!((fd.dlc, fd.valid_data_bytes), (9, 12));
+        assert_eq!(
+            fd.can_fd_flags,
+            CanFdMessage::FD_FLAG_EDL | CanFdMessage::FD_FLAG_BRS
+        );
+
+        // Error frames and other event tags are skipped
+        assert!(xl_can_event(&can_event(0, XL_CAN_RXMSG_FLAG_EF, 0, &[]), 1).is_none());
+        let mut tx = can_event(0x100, 0, 0, &[]);
+        tx[4..6].copy_from_slice(&0x1000u16.to_le_bytes());
+        assert!(xl_can_event(&tx, 1).is_none());
+
+        let mut lin = [0u8; XL_EVENT_SIZE];
+        lin[0] = XL_LIN_MSG;
+        lin[8..16].copy_from_slice(&2_000u64.to_le_bytes());
+        lin[16] = 0x21;
+        lin[17] = 2;
+        lin[20..22].copy_from_slice(&[0x55, 0x66]);
+        let Some(LogObject::LinMessage(lin)) = xl_lin_event(&lin, 3) else {
+            panic!("expected a LIN frame");
+        };
+        assert_eq!((lin.channel, lin.id, lin.dlc, lin.dir), (3, 0x21, 2, 0));
+        assert_eq!(&lin.data[..2], [0x55, 0x66]);
+    }
+
+    #[test]
+    fn test_xl_channel_label() {
+        let channel = XlChannel {
+            hw_type: 57,
+            hw_index: 0,
+            hw_channel: 0,
+        };
+        assert_eq!(channel.label(), "VN1630 CH1");
+        let second = XlChannel {
+            hw_index: 1,
+            hw_channel: 1,
+            ..channel
+        };
+        assert_eq!(second.label(), "VN1630 #2 CH2");
+        assert_eq!(
+            XlChannel {
+                hw_type: 99,
+                ..channel
+            }
+            .label(),
+            "HW99 CH1"
+        );
+    }
+}
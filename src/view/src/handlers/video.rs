@@ -0,0 +1,234 @@
+//! Video synchronization
+//!
+//! A video recorded alongside the log is mapped onto the log timeline with a
+//! single offset: `video time = log time + offset`. The video pane shows the
+//! frame at the time cursor and seeks whenever the cursor moves; playing moves
+//! the cursor through the log in real time and the pane follows it.
+//!
+//! There is no video decoder in this build: frames are extracted with `ffmpeg`
+//! into a cache directory, one image per [`FRAME_STEP_MS`], and shown from there.
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+/// Frames are extracted on this grid, 25 per second
+pub const FRAME_STEP_MS: u64 = 40;
+
+/// Width the frames are scaled to
+const FRAME_WIDTH: u32 = 640;
+
+/// A video file and its offset against the log
+#[derive(Debug, Clone, PartialEq)]
+pub struct VideoSync {
+    pub path: PathBuf,
+    /// Added to log time to get video time, in nanoseconds
+    pub offset_ns: i64,
+}
+
+impl VideoSync {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path, offset_ns: 0 }
+    }
+
+    /// Video position in seconds for a log timestamp, `None` before the video starts
+    pub fn video_position(&self, log_ns: u64) -> Option<f64> {
+        let position = log_ns as i128 + self.offset_ns as i128;
+        (position >= 0).then(|| position as f64 / 1_000_000_000.0)
+    }
+
+    /// Offset that makes `log_ns` show the frame at `video_s`
+    pub fn align(&mut self, log_ns: u64, video_s: f64) {
+        self.offset_ns = (video_s * 1_000_000_000.0).round() as i64 - log_ns as i64;
+    }
+
+    /// Directory the frames of this video are cached in
+    pub fn frames_dir(&self) -> PathBuf {
+        let mut hasher = DefaultHasher::new();
+        self.path.hash(&mut hasher);
+        std::env::temp_dir()
+            .join("canview-video")
+            .join(format!("{:016x}", hasher.finish()))
+    }
+}
+
+/// Frame shown by the video pane and the extraction in flight
+///
+/// Only one `ffmpeg` runs at a time. Seeks made while it runs replace each
+/// other, so a fast-moving cursor skips frames instead of queuing them.
+#[derive(Debug, Default)]
+pub struct VideoPane {
+    /// Position in ms and image of the frame on screen
+    pub shown: Option<(u64, PathBuf)>,
+    /// Position the pane should show once the running extraction ends
+    pub wanted: Option<u64>,
+    /// Position being extracted
+    pub extracting: Option<u64>,
+    /// Why the last extraction failed
+    pub error: Option<String>,
+}
+
+impl VideoPane {
+    /// Show the frame at `seconds`; the position to extract now, if one must start
+    pub fn seek(&mut self, seconds: f64) -> Option<u64> {
+        let position = frame_position_ms(seconds);
+        self.wanted = Some(position);
+        self.next_extraction()
+    }
+
+    /// Record the end of the extraction at `position`; the next one to start, if any
+    pub fn finished(&mut self, position: u64, result: Result<PathBuf, String>) -> Option<u64> {
+        self.extracting = None;
+        match result {
+            Ok(image) => {
+                self.shown = Some((position, image));
+                self.error = None;
+            }
+            Err(e) => self.error = Some(e),
+        }
+        self.next_extraction()
+    }
+
+    /// Drop the frames, the video was closed or replaced
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    fn next_extraction(&mut self) -> Option<u64> {
+        if self.extracting.is_some() {
+            return None;
+        }
+        let wanted = self.wanted.take()?;
+        if self
+            .shown
+            .as_ref()
+            .is_some_and(|(shown, _)| *shown == wanted)
+        {
+            return None;
+        }
+        self.extracting = Some(wanted);
+        Some(wanted)
+    }
+}
+
+/// Position of the frame shown at `seconds`, in ms on the [`FRAME_STEP_MS`] grid
+pub fn frame_position_ms(seconds: f64) -> u64 {
+    let ms = (seconds.max(0.0) * 1000.0) as u64;
+    ms - ms % FRAME_STEP_MS
+}
+
+/// Cached image of the frame at `position_ms`
+pub fn frame_path(dir: &Path, position_ms: u64) -> PathBuf {
+    dir.join(format!("frame-{:010}.jpg", position_ms))
+}
+
+/// Extract the frame at `position_ms` of `video` into `dir`, or reuse the cached one
+pub fn extract_frame(video: &Path, dir: &Path, position_ms: u64) -> Result<PathBuf, String> {
+    let image = frame_path(dir, position_ms);
+    if image.exists() {
+        return Ok(image);
+    }
+    std::fs::create_dir_all(dir).map_err(|e| format!("Cannot create {}: {}", dir.display(), e))?;
+    let output = Command::new("ffmpeg")
+        .args(["-hide_banner", "-loglevel", "error", "-ss"])
+        .arg(format!("{:.3}", position_ms as f64 / 1000.0))
+        .arg("-i")
+        .arg(video)
+        .args(["-frames:v", "1", "-q:v", "4", "-vf"])
+        .arg(format!("scale={}:-2", FRAME_WIDTH))
+        .arg("-y")
+        .arg(&image)
+        .stdin(Stdio::null())
+        .output()
+        .map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => "Video frames need ffmpeg on the PATH".to_string(),
+            _ => format!("Cannot run ffmpeg: {}", e),
+        })?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("ffmpeg failed: {}", stderr.trim()));
+    }
+    // Seeking past the end succeeds without writing a frame
+    if !image.exists() {
+        return Err(format!(
+            "No frame at {}",
+            format_video_position(position_ms as f64 / 1000.0)
+        ));
+    }
+    Ok(image)
+}
+
+/// Parse a video position typed as seconds or `mm:ss(.fff)`
+pub fn parse_video_position(text: &str) -> Result<f64, String> {
+    let text = text.trim();
+    let invalid = || format!("Invalid video position: '{}'", text);
+    let seconds = match text.split_once(':') {
+        Some((minutes, seconds)) => {
+            let minutes: u32 = minutes.parse().map_err(|_| invalid())?;
+            let seconds: f64 = seconds.parse().map_err(|_| invalid())?;
+            minutes as f64 * 60.0 + seconds
+        }
+        None => text.parse().map_err(|_| invalid())?,
+    };
+    if seconds.is_finite() && seconds >= 0.0 {
+        Ok(seconds)
+    } else {
+        Err(invalid())
+    }
+}
+
+/// Position as `mm:ss.fff`
+pub fn format_video_position(seconds: f64) -> String {
+    let minutes = (seconds / 60.0).floor();
+    format!("{:02}:{:06.3}", minutes as u64, seconds - minutes * 60.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_video_position_with_offset() {
+        let mut video = VideoSync::new(PathBuf::from("drive.mp4"));
+        video.offset_ns = -2_000_000_000;
+        assert_eq!(video.video_position(5_000_000_000), Some(3.0));
+        assert_eq!(video.video_position(1_000_000_000), None);
+        video.align(5_000_000_000, 12.5);
+        assert_eq!(video.video_position(5_000_000_000), Some(12.5));
+    }
+
+    #[test]
+    fn test_parse_and_format_video_position() {
+        assert_eq!(parse_video_position("12.5"), Ok(12.5));
+        assert_eq!(parse_video_position(" 01:02.5 "), Ok(62.5));
+        assert!(parse_video_position("-1").is_err());
+        assert!(parse_video_position("1:xx").is_err());
+        assert_eq!(format_video_position(62.5), "01:02.500");
+    }
+
+    #[test]
+    fn test_frame_position_on_grid() {
+        assert_eq!(frame_position_ms(0.0), 0);
+        assert_eq!(frame_position_ms(1.039), 1000);
+        assert_eq!(frame_position_ms(1.04), 1040);
+        assert_eq!(frame_position_ms(-3.0), 0);
+    }
+
+    #[test]
+    fn test_pane_runs_one_extraction_and_keeps_the_latest_seek() {
+        let mut pane = VideoPane::default();
+        assert_eq!(pane.seek(1.0), Some(1000));
+        // Seeks while ffmpeg runs replace each other
+        assert_eq!(pane.seek(2.0), None);
+        assert_eq!(pane.seek(3.0), None);
+        assert_eq!(pane.finished(1000, Ok(PathBuf::from("a.jpg"))), Some(3000));
+        assert_eq!(pane.shown, Some((1000, PathBuf::from("a.jpg"))));
+        assert_eq!(pane.finished(3000, Err("ffmpeg failed".to_string())), None);
+        assert_eq!(pane.error.as_deref(), Some("ffmpeg failed"));
+        assert_eq!(pane.shown, Some((1000, PathBuf::from("a.jpg"))));
+        // The frame on screen is not extracted again
+        assert_eq!(pane.seek(1.01), None);
+        assert_eq!(pane.extracting, None);
+    }
+}
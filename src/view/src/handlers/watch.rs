@@ -0,0 +1,161 @@
+//! Watching a folder for new logs
+//!
+//! On benches a logger drops a new file every few minutes. The folder is
+//! polled rather than subscribed to, since network shares rarely deliver
+//! file events. [`FolderWatcher`] reports a log once it is new and its size
+//! and modification time stayed the same over two scans, so a file still
+//! being written is never opened half-way.
+
+use crate::handlers::LOG_FILE_EXTENSIONS;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+/// Time between two scans of the watched folder
+pub const WATCH_INTERVAL: Duration = Duration::from_secs(2);
+
+/// What happens to a new log in the watched folder
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum WatchAction {
+    /// Open it in place of the current log
+    #[default]
+    Load,
+    /// Add it to the queue for opening by hand
+    Queue,
+}
+
+impl WatchAction {
+    pub const ALL: [WatchAction; 2] = [WatchAction::Load, WatchAction::Queue];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            WatchAction::Load => "Open",
+            WatchAction::Queue => "Queue",
+        }
+    }
+}
+
+/// Watched folder, saved with the configuration
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct WatchSettings {
+    #[serde(default)]
+    pub folder: Option<PathBuf>,
+    #[serde(default)]
+    pub action: WatchAction,
+}
+
+/// Size and modification time of a file at one scan
+pub type FileStamp = (u64, Option<SystemTime>);
+
+/// The logs in `folder` with their stamps
+pub fn scan_folder(folder: &Path) -> Result<Vec<(PathBuf, FileStamp)>, String> {
+    let entries = std::fs::read_dir(folder).map_err(|e| format!("{}: {}", folder.display(), e))?;
+    Ok(entries
+        .filter_map(Result::ok)
+        .filter(|entry| {
+            entry.path().extension().is_some_and(|ext| {
+                LOG_FILE_EXTENSIONS
+                    .iter()
+                    .any(|log| ext.eq_ignore_ascii_case(log))
+            })
+        })
+        .filter_map(|entry| {
+            let metadata = entry
+                .metadata()
+                .ok()
+                .filter(|metadata| metadata.is_file())?;
+            Some((entry.path(), (metadata.len(), metadata.modified().ok())))
+        })
+        .collect())
+}
+
+/// New-log detection over successive scans of one folder
+#[derive(Debug, Clone)]
+pub struct FolderWatcher {
+    folder: PathBuf,
+    /// Logs already reported, or present when watching started
+    known: HashSet<PathBuf>,
+    /// New logs with their stamp at the last scan
+    pending: HashMap<PathBuf, FileStamp>,
+}
+
+impl FolderWatcher {
+    /// Watch `folder`; the logs already in it are not reported
+    pub fn new(folder: PathBuf) -> Result<Self, String> {
+        let known = scan_folder(&folder)?
+            .into_iter()
+            .map(|(path, _)| path)
+            .collect();
+        Ok(Self {
+            folder,
+            known,
+            pending: HashMap::new(),
+        })
+    }
+
+    pub fn folder(&self) -> &Path {
+        &self.folder
+    }
+
+    /// Feed a scan of the folder; returns the logs that are complete, oldest first
+    pub fn update(&mut self, scan: Vec<(PathBuf, FileStamp)>) -> Vec<PathBuf> {
+        let mut ready = Vec::new();
+        let mut pending = HashMap::new();
+        for (path, stamp) in scan {
+            if self.known.contains(&path) {
+                continue;
+            }
+            // Loggers create the file before writing anything into it
+            if stamp.0 > 0 && self.pending.get(&path) == Some(&stamp) {
+                self.known.insert(path.clone());
+                ready.push((stamp.1, path));
+            } else {
+                pending.insert(path, stamp);
+            }
+        }
+        // Files deleted before they settled are forgotten
+        self.pending = pending;
+        ready.sort();
+        ready.into_iter().map(|(_, path)| path).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_folder_watcher() {
+        let folder = std::env::temp_dir().join(format!("canview_watch_{}", std::process::id()));
+        std::fs::create_dir_all(&folder).unwrap();
+        std::fs::write(folder.join("old.blf"), "old").unwrap();
+        let mut watcher = FolderWatcher::new(folder.clone()).unwrap();
+        let mut poll = || watcher.update(scan_folder(&folder).unwrap());
+
+        std::fs::write(folder.join("notes.txt"), "not a log").unwrap();
+        std::fs::write(folder.join("new.blf"), "").unwrap();
+        assert!(poll().is_empty());
+        // Still empty: the logger has not written yet
+        assert!(poll().is_empty());
+        std::fs::write(folder.join("new.blf"), "frames").unwrap();
+        assert!(poll().is_empty());
+        assert_eq!(poll(), vec![folder.join("new.blf")]);
+        // Reported once only
+        assert!(poll().is_empty());
+
+        // Growing between scans keeps it pending
+        let mut watcher = FolderWatcher::new(folder.clone()).unwrap();
+        std::fs::write(folder.join("next.blf.gz"), "a").unwrap();
+        assert!(watcher.update(scan_folder(&folder).unwrap()).is_empty());
+        std::fs::write(folder.join("next.blf.gz"), "ab").unwrap();
+        assert!(watcher.update(scan_folder(&folder).unwrap()).is_empty());
+        assert_eq!(
+            watcher.update(scan_folder(&folder).unwrap()),
+            vec![folder.join("next.blf.gz")]
+        );
+
+        assert!(FolderWatcher::new(folder.join("missing")).is_err());
+        let _ = std::fs::remove_dir_all(&folder);
+    }
+}
@@ -0,0 +1,288 @@
+//! Worker pool for analyses over the whole log
+//!
+//! Statistics, run comparisons, correlations and sequence checks walk every
+//! message, which would stall the UI if done on its thread. They are
+//! submitted to an [`AnalysisPool`] instead: a few threads of its own, so a
+//! long analysis never competes with file loading or row decoding on the
+//! background executor. Each submission returns an [`AnalysisJob`], which
+//! can be awaited for the result, and a [`JobProgress`] the status bar reads
+//! while the job runs.
+
+use std::future::Future;
+use std::panic::{AssertUnwindSafe, catch_unwind};
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Condvar, Mutex, mpsc};
+use std::task::{Context, Poll, Waker};
+
+/// Most worker threads started by [`AnalysisPool::default`]
+pub const MAX_ANALYSIS_WORKERS: usize = 4;
+
+/// What an analysis job computes, shown next to its progress
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AnalysisKind {
+    /// Statistics, health, sequence gaps and minimap of a freshly loaded log
+    LogChecks,
+    Compare,
+    Correlation,
+    SequenceCheck,
+}
+
+impl AnalysisKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            AnalysisKind::LogChecks => "Log checks",
+            AnalysisKind::Compare => "Compare",
+            AnalysisKind::Correlation => "Correlation",
+            AnalysisKind::SequenceCheck => "Sequence check",
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct ProgressState {
+    done: AtomicUsize,
+    total: AtomicUsize,
+    cancelled: AtomicBool,
+    finished: AtomicBool,
+}
+
+/// Progress of a job, shared between the worker and whoever shows it
+#[derive(Debug, Clone)]
+pub struct JobProgress {
+    pub id: u64,
+    pub kind: AnalysisKind,
+    state: Arc<ProgressState>,
+}
+
+impl JobProgress {
+    /// Announce the number of steps of the job
+    pub fn set_total(&self, total: usize) {
+        self.state.total.store(total, Ordering::Relaxed);
+    }
+
+    /// Count `steps` more steps as done
+    pub fn advance(&self, steps: usize) {
+        self.state.done.fetch_add(steps, Ordering::Relaxed);
+    }
+
+    /// Share of the steps done, `None` until a total is announced
+    pub fn fraction(&self) -> Option<f32> {
+        let total = self.state.total.load(Ordering::Relaxed);
+        (total > 0)
+            .then(|| (self.state.done.load(Ordering::Relaxed) as f32 / total as f32).min(1.0))
+    }
+
+    /// Ask the job to stop; one not started yet is skipped
+    pub fn cancel(&self) {
+        self.state.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.state.cancelled.load(Ordering::Relaxed)
+    }
+
+    /// Whether the job has ended, with a result or not
+    pub fn is_finished(&self) -> bool {
+        self.state.finished.load(Ordering::Acquire)
+    }
+}
+
+struct ResultSlot<T> {
+    result: Option<Result<T, String>>,
+    waker: Option<Waker>,
+}
+
+struct Shared<T> {
+    slot: Mutex<ResultSlot<T>>,
+    ready: Condvar,
+}
+
+impl<T> Shared<T> {
+    fn finish(&self, result: Result<T, String>) {
+        let mut slot = self.slot.lock().unwrap();
+        slot.result = Some(result);
+        if let Some(waker) = slot.waker.take() {
+            waker.wake();
+        }
+        self.ready.notify_all();
+    }
+}
+
+/// Handle on a submitted job; await it (or [`wait`](Self::wait)) for the result
+///
+/// The result is an error if the job was cancelled before it started or
+/// panicked.
+pub struct AnalysisJob<T> {
+    progress: JobProgress,
+    shared: Arc<Shared<T>>,
+}
+
+impl<T> AnalysisJob<T> {
+    pub fn progress(&self) -> &JobProgress {
+        &self.progress
+    }
+
+    /// Block the calling thread until the job ends
+    pub fn wait(self) -> Result<T, String> {
+        let mut slot = self.shared.slot.lock().unwrap();
+        loop {
+            if let Some(result) = slot.result.take() {
+                return result;
+            }
+            slot = self.shared.ready.wait(slot).unwrap();
+        }
+    }
+}
+
+impl<T> Future for AnalysisJob<T> {
+    type Output = Result<T, String>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut slot = self.shared.slot.lock().unwrap();
+        match slot.result.take() {
+            Some(result) => Poll::Ready(result),
+            None => {
+                slot.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+type Task = Box<dyn FnOnce() + Send>;
+
+/// Threads running analysis jobs in the order they were submitted
+///
+/// Clones share the same threads, which stop once every clone is dropped.
+#[derive(Clone)]
+pub struct AnalysisPool {
+    queue: mpsc::Sender<Task>,
+    next_id: Arc<AtomicU64>,
+    workers: usize,
+}
+
+impl AnalysisPool {
+    /// Start a pool of `workers` threads (at least one)
+    pub fn new(workers: usize) -> Self {
+        let workers = workers.max(1);
+        let (queue, tasks) = mpsc::channel::<Task>();
+        let tasks = Arc::new(Mutex::new(tasks));
+        for worker in 0..workers {
+            let tasks = Arc::clone(&tasks);
+            let spawned = std::thread::Builder::new()
+                .name(format!("analysis-{}", worker))
+                .spawn(move || {
+                    loop {
+                        // The lock is released before the task runs
+                        let task = tasks.lock().unwrap().recv();
+                        match task {
+                            Ok(task) => task(),
+                            Err(_) => break,
+                        }
+                    }
+                });
+            if let Err(e) = spawned {
+                log::warn!("Could not start analysis worker {}: {}", worker, e);
+            }
+        }
+        Self {
+            queue,
+            next_id: Arc::new(AtomicU64::new(1)),
+            workers,
+        }
+    }
+
+    pub fn workers(&self) -> usize {
+        self.workers
+    }
+
+    /// Queue `work`; it gets the job's progress to report steps and check for cancellation
+    pub fn submit<T, F>(&self, kind: AnalysisKind, work: F) -> AnalysisJob<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(&JobProgress) -> T + Send + 'static,
+    {
+        let progress = JobProgress {
+            id: self.next_id.fetch_add(1, Ordering::Relaxed),
+            kind,
+            state: Arc::new(ProgressState::default()),
+        };
+        let shared = Arc::new(Shared {
+            slot: Mutex::new(ResultSlot {
+                result: None,
+                waker: None,
+            }),
+            ready: Condvar::new(),
+        });
+
+        let task: Task = {
+            let progress = progress.clone();
+            let shared = Arc::clone(&shared);
+            Box::new(move || {
+                let result = if progress.is_cancelled() {
+                    Err(format!("{} cancelled", kind.label()))
+                } else {
+                    catch_unwind(AssertUnwindSafe(|| work(&progress)))
+                        .map_err(|_| format!("{} failed", kind.label()))
+                };
+                progress.state.finished.store(true, Ordering::Release);
+                shared.finish(result);
+            })
+        };
+        // Without a worker left the job runs on the caller's thread
+        if let Err(mpsc::SendError(task)) = self.queue.send(task) {
+            task();
+        }
+        AnalysisJob { progress, shared }
+    }
+}
+
+impl Default for AnalysisPool {
+    fn default() -> Self {
+        let cores = std::thread::available_parallelism().map_or(2, |n| n.get());
+        Self::new((cores / 2).clamp(1, MAX_ANALYSIS_WORKERS))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_jobs_report_progress_and_results() {
+        let pool = AnalysisPool::new(2);
+        let (release, gate) = mpsc::channel::<()>();
+        let job = pool.submit(AnalysisKind::Correlation, move |progress| {
+            progress.set_total(4);
+            progress.advance(1);
+            gate.recv().unwrap();
+            progress.advance(3);
+            42
+        });
+        let progress = job.progress().clone();
+        assert_eq!(progress.kind, AnalysisKind::Correlation);
+        assert!(!progress.is_finished());
+        release.send(()).unwrap();
+        assert_eq!(job.wait(), Ok(42));
+        assert!(progress.is_finished());
+        assert_eq!(progress.fraction(), Some(1.0));
+
+        // A panicking job reports an error and leaves the worker running
+        let failed = pool.submit(AnalysisKind::Compare, |_| -> u32 { panic!("boom") });
+        assert_eq!(failed.wait(), Err("Compare failed".to_string()));
+        let next = pool.submit(AnalysisKind::Compare, |_| 1);
+        assert_ne!(next.progress().id, progress.id);
+        assert_eq!(next.wait(), Ok(1));
+
+        // A job cancelled before a worker picks it up never runs
+        let single = AnalysisPool::new(1);
+        let (release, gate) = mpsc::channel::<()>();
+        let busy = single.submit(AnalysisKind::LogChecks, move |_| gate.recv().unwrap());
+        let queued = single.submit(AnalysisKind::SequenceCheck, |_| 7);
+        queued.progress().cancel();
+        release.send(()).unwrap();
+        assert_eq!(busy.wait(), Ok(()));
+        assert_eq!(queued.wait(), Err("Sequence check cancelled".to_string()));
+    }
+}
@@ -14,7 +14,10 @@ use parser::ldf::{LdfDatabase, LdfParser};
 use std::collections::HashMap;
 use std::collections::hash_map::DefaultHasher;
 use std::hash::{Hash, Hasher};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::RecvTimeoutError;
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, SystemTime};
 
 /// 数据库验证结果
 #[derive(Debug, Clone)]
@@ -103,6 +106,139 @@ impl LibraryManager {
         self.libraries.iter_mut().find(|lib| lib.id == id)
     }
 
+    /// 查找内容与 `path` 相同的已有数据库，返回 (库ID, 版本名称)
+    pub fn find_duplicate(&self, path: &str) -> Option<(String, String)> {
+        let checksum = crate::models::library::file_checksum(path).ok()?;
+        self.libraries.iter().find_map(|library| {
+            library
+                .find_database(&checksum)
+                .map(|version| (library.id.clone(), version.name.clone()))
+        })
+    }
+
+    /// 检查所有通道映射：通道号范围、重复通道、文件存在、类型匹配、能否解析以及关联的库版本
+    pub fn validate_mappings(&self, mappings: &[ChannelMapping]) -> Vec<MappingIssue> {
+        let mut issues = Vec::new();
+        let mut seen = std::collections::HashSet::new();
+        for (index, mapping) in mappings.iter().enumerate() {
+            let mut report = |problem| issues.push(MappingIssue { index, problem });
+
+            if mapping.channel_id == 0 || mapping.channel_id > 255 {
+                report(MappingProblem::ChannelOutOfRange);
+            }
+            if !seen.insert((mapping.channel_type, mapping.channel_id)) {
+                report(MappingProblem::DuplicateChannel);
+            }
+            if let Some(library_id) = &mapping.library_id {
+                let version_exists = self.find_library(library_id).is_some_and(|library| {
+                    mapping
+                        .version_name
+                        .as_ref()
+                        .is_none_or(|name| library.get_version(name).is_some())
+                });
+                if !version_exists {
+                    report(MappingProblem::MissingLibrary);
+                }
+            }
+
+            let path = Path::new(&mapping.path);
+            if mapping.path.is_empty() || !path.exists() {
+                report(MappingProblem::FileNotFound);
+                continue;
+            }
+            let database_type = path
+                .extension()
+                .and_then(|e| e.to_str())
+                .and_then(DatabaseType::from_extension);
+            let expected = match mapping.channel_type {
+                ChannelType::CAN => DatabaseType::DBC,
+                ChannelType::LIN => DatabaseType::LDF,
+            };
+            if database_type != Some(expected) {
+                report(MappingProblem::TypeMismatch(database_type));
+                continue;
+            }
+            match self.validate_database(&mapping.path) {
+                Ok(validation) if !validation.is_valid => report(MappingProblem::ParseError(
+                    validation.error.unwrap_or_default(),
+                )),
+                Ok(_) => {}
+                Err(e) => report(MappingProblem::ParseError(e)),
+            }
+        }
+        issues
+    }
+
+    /// 将文件夹中的 DBC/LDF 文件导入为库和版本
+    ///
+    /// 文件按去掉版本号后的文件名分组（见 [`group_database_files`]），每组对应一个库，
+    /// 已存在的同名库直接添加版本。内容与已有版本相同的文件和已存在的版本会被跳过。
+    pub fn import_folder(&mut self, dir: &Path) -> Result<FolderImport, String> {
+        let paths: Vec<PathBuf> = std::fs::read_dir(dir)
+            .map_err(|e| format!("{}: {}", dir.display(), e))?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.is_file())
+            .collect();
+
+        let mut summary = FolderImport::default();
+        for group in group_database_files(&paths) {
+            let DatabaseGroup {
+                name,
+                database_type,
+                versions,
+            } = group;
+            let channel_type = match database_type {
+                DatabaseType::DBC => ChannelType::CAN,
+                DatabaseType::LDF => ChannelType::LIN,
+            };
+            let library_id = generate_library_id(&name);
+            match self.find_library(&library_id) {
+                Some(library) if library.channel_type != channel_type => {
+                    summary.skipped.push(format!(
+                        "{}: name used by a {:?} library",
+                        name, library.channel_type
+                    ));
+                    continue;
+                }
+                Some(_) => {}
+                None => {
+                    self.create_library(name.clone(), channel_type)?;
+                    summary.libraries += 1;
+                }
+            }
+
+            for (version_name, path) in versions {
+                let file = path
+                    .file_name()
+                    .unwrap_or_default()
+                    .to_string_lossy()
+                    .to_string();
+                let path = path.to_string_lossy().to_string();
+                if let Some((_, existing)) = self.find_duplicate(&path) {
+                    summary
+                        .skipped
+                        .push(format!("{}: same content as version {}", file, existing));
+                    continue;
+                }
+                let exists = self
+                    .find_library(&library_id)
+                    .is_some_and(|library| library.get_version(&version_name).is_some());
+                if exists {
+                    summary
+                        .skipped
+                        .push(format!("{}: version {} already exists", file, version_name));
+                    continue;
+                }
+                match self.add_version(&library_id, version_name, path, "Folder import".to_string())
+                {
+                    Ok(()) => summary.versions += 1,
+                    Err(e) => summary.skipped.push(format!("{}: {}", file, e)),
+                }
+            }
+        }
+        Ok(summary)
+    }
+
     /// 创建新库
     pub fn create_library(
         &mut self,
@@ -190,13 +326,15 @@ impl LibraryManager {
             .unwrap_or_default();
 
         let date = chrono::Utc::now().format("%Y-%m-%d").to_string();
-        let mut version =
-            LibraryVersion::new(name, default_path, date).with_description(description);
+        let mut version = LibraryVersion::new(name, default_path, date)
+            .with_description(description)
+            .with_author(default_author());
 
         // 添加所有通道配置
         for channel_db in channel_dbs {
             version.add_channel_database(channel_db)?;
         }
+        version.update_checksum()?;
 
         library.add_version(version);
         Ok(())
@@ -237,7 +375,10 @@ impl LibraryManager {
 
         // 创建版本
         let date = chrono::Utc::now().format("%Y-%m-%d").to_string();
-        let version = LibraryVersion::new(name, path, date).with_description(description);
+        let mut version = LibraryVersion::new(name, path, date)
+            .with_description(description)
+            .with_author(default_author());
+        version.update_checksum()?;
 
         library.add_version(version);
         Ok(())
@@ -294,8 +435,9 @@ impl LibraryManager {
 
     /// 验证DBC文件
     fn validate_dbc(&self, path: &str) -> Result<DatabaseValidation, String> {
-        let content =
-            std::fs::read_to_string(path).map_err(|e| format!("Failed to read file: {}", e))?;
+        // DBCs are often Latin-1 or GBK rather than UTF-8
+        let (content, _) = parser::encoding::read_text_file(path)
+            .map_err(|e| format!("Failed to read file: {}", e))?;
 
         let parser = DbcParser::new();
         let db = parser
@@ -310,8 +452,9 @@ impl LibraryManager {
 
     /// 验证LDF文件
     fn validate_ldf(&self, path: &str) -> Result<DatabaseValidation, String> {
-        let content =
-            std::fs::read_to_string(path).map_err(|e| format!("Failed to read file: {}", e))?;
+        // LDFs share the DBC encoding problem
+        let (content, _) = parser::encoding::read_text_file(path)
+            .map_err(|e| format!("Failed to read file: {}", e))?;
 
         let parser = LdfParser::new();
         let db = parser
@@ -352,40 +495,131 @@ impl LibraryManager {
 
     /// 加载数据库文件
     pub fn load_database(&self, path: &str, channel_type: ChannelType) -> Result<Database, String> {
-        match channel_type {
-            ChannelType::CAN => self.load_dbc(path),
-            ChannelType::LIN => self.load_ldf(path),
-        }
+        load_database_file(path, channel_type)
     }
+}
 
-    /// 加载DBC文件
-    fn load_dbc(&self, path: &str) -> Result<Database, String> {
-        let content =
-            std::fs::read_to_string(path).map_err(|e| format!("Failed to read file: {}", e))?;
+/// 网络共享等慢速路径上单个数据库文件的加载时限
+pub const DATABASE_LOAD_TIMEOUT: Duration = Duration::from_secs(30);
 
-        let parser = DbcParser::new();
-        let db = parser
-            .parse(&content)
-            .map_err(|e| format!("DBC parse error: {}", e))?;
+/// 已解析数据库的缓存键：文件路径、修改时间、大小和通道类型
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct DatabaseKey {
+    path: PathBuf,
+    modified: Option<SystemTime>,
+    size: u64,
+    channel_type: ChannelType,
+}
+
+impl DatabaseKey {
+    fn of(path: &str, channel_type: ChannelType) -> Result<Self, String> {
+        let metadata =
+            std::fs::metadata(path).map_err(|e| format!("Failed to read file: {}", e))?;
+        Ok(Self {
+            path: PathBuf::from(path),
+            modified: metadata.modified().ok(),
+            size: metadata.len(),
+            channel_type,
+        })
+    }
+}
 
-        Ok(Database::Dbc(db))
+/// 进程内的数据库解析缓存，文件修改后对应条目自动失效
+static DATABASE_CACHE: LazyLock<Mutex<HashMap<DatabaseKey, Database>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// 加载数据库文件
+///
+/// 路径、修改时间和大小都未变化时直接返回缓存的解析结果，
+/// 重新应用版本或重新分配通道不会再次解析大型 DBC。
+pub fn load_database_file(path: &str, channel_type: ChannelType) -> Result<Database, String> {
+    let key = DatabaseKey::of(path, channel_type)?;
+    if let Some(database) = database_cache().get(&key) {
+        return Ok(database.clone());
     }
 
-    /// 加载LDF文件
-    fn load_ldf(&self, path: &str) -> Result<Database, String> {
-        let content =
-            std::fs::read_to_string(path).map_err(|e| format!("Failed to read file: {}", e))?;
+    let database = match channel_type {
+        ChannelType::CAN => load_dbc(path)?,
+        ChannelType::LIN => load_ldf(path)?,
+    };
+    let mut cache = database_cache();
+    // 同一文件的旧版本不会再被命中
+    cache.retain(|cached, _| cached.path != key.path || cached.channel_type != channel_type);
+    cache.insert(key, database.clone());
+    Ok(database)
+}
 
-        let parser = LdfParser::new();
-        let db = parser
-            .parse(&content)
-            .map_err(|e| format!("LDF parse error: {}", e))?;
+/// 文件当前内容的解析结果是否已缓存
+pub fn is_database_cached(path: &str, channel_type: ChannelType) -> bool {
+    DatabaseKey::of(path, channel_type).is_ok_and(|key| database_cache().contains_key(&key))
+}
+
+/// 清空数据库解析缓存
+pub fn clear_database_cache() {
+    database_cache().clear();
+}
 
-        Ok(Database::Ldf(db))
+fn database_cache() -> std::sync::MutexGuard<'static, HashMap<DatabaseKey, Database>> {
+    // 解析在锁外进行，锁中毒时缓存内容仍然完整
+    DATABASE_CACHE
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// 在独立线程中加载数据库文件，超过 `timeout` 仍未完成时返回错误
+///
+/// 卡在网络共享上的读取无法中止：线程继续运行，结束后结果被丢弃。
+pub fn load_database_with_timeout(
+    path: &str,
+    channel_type: ChannelType,
+    timeout: Duration,
+) -> Result<Database, String> {
+    let (sender, receiver) = std::sync::mpsc::channel();
+    let thread_path = path.to_string();
+    std::thread::spawn(move || {
+        let _ = sender.send(load_database_file(&thread_path, channel_type));
+    });
+    match receiver.recv_timeout(timeout) {
+        Ok(result) => result,
+        Err(RecvTimeoutError::Timeout) => Err(format!(
+            "Timed out after {}s reading {}",
+            timeout.as_secs(),
+            path
+        )),
+        Err(RecvTimeoutError::Disconnected) => Err(format!("Loading {} failed", path)),
     }
 }
 
+/// 加载DBC文件
+fn load_dbc(path: &str) -> Result<Database, String> {
+    // DBCs are often Latin-1 or GBK rather than UTF-8
+    let (content, _) = parser::encoding::read_text_file(path)
+        .map_err(|e| format!("Failed to read file: {}", e))?;
+
+    let parser = DbcParser::new();
+    let db = parser
+        .parse(&content)
+        .map_err(|e| format!("DBC parse error: {}", e))?;
+
+    Ok(Database::Dbc(db))
+}
+
+/// 加载LDF文件
+fn load_ldf(path: &str) -> Result<Database, String> {
+    // LDFs share the DBC encoding problem
+    let (content, _) = parser::encoding::read_text_file(path)
+        .map_err(|e| format!("Failed to read file: {}", e))?;
+
+    let parser = LdfParser::new();
+    let db = parser
+        .parse(&content)
+        .map_err(|e| format!("LDF parse error: {}", e))?;
+
+    Ok(Database::Ldf(db))
+}
+
 /// 数据库枚举（包装DBC和LDF）
+#[derive(Clone)]
 pub enum Database {
     Dbc(DbcDatabase),
     Ldf(LdfDatabase),
@@ -398,6 +632,139 @@ pub fn generate_library_id(name: &str) -> String {
     format!("lib_{:x}", hasher.finish())
 }
 
+/// 新版本的默认作者：当前系统用户名
+pub fn default_author() -> String {
+    std::env::var("USER")
+        .or_else(|_| std::env::var("USERNAME"))
+        .unwrap_or_default()
+}
+
+/// 通道映射的问题
+#[derive(Debug, Clone, PartialEq)]
+pub enum MappingProblem {
+    /// 通道号不在 1-255 范围内
+    ChannelOutOfRange,
+    /// 同一类型的通道被映射了多次
+    DuplicateChannel,
+    /// 关联的库或版本已不存在
+    MissingLibrary,
+    /// 数据库文件不存在
+    FileNotFound,
+    /// 文件类型与通道类型不符
+    TypeMismatch(Option<DatabaseType>),
+    /// 数据库解析失败
+    ParseError(String),
+}
+
+impl std::fmt::Display for MappingProblem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MappingProblem::ChannelOutOfRange => write!(f, "Channel must be between 1 and 255"),
+            MappingProblem::DuplicateChannel => write!(f, "Channel is mapped more than once"),
+            MappingProblem::MissingLibrary => write!(f, "Linked library version no longer exists"),
+            MappingProblem::FileNotFound => write!(f, "Database file not found"),
+            MappingProblem::TypeMismatch(Some(database_type)) => write!(
+                f,
+                "{} file on a channel of the other type",
+                database_type.extension().to_uppercase()
+            ),
+            MappingProblem::TypeMismatch(None) => write!(f, "Not a DBC or LDF file"),
+            MappingProblem::ParseError(e) => write!(f, "Cannot parse: {}", e),
+        }
+    }
+}
+
+/// 映射检查的结果之一
+#[derive(Debug, Clone, PartialEq)]
+pub struct MappingIssue {
+    /// 映射在列表中的下标
+    pub index: usize,
+    pub problem: MappingProblem,
+}
+
+/// 文件夹导入的结果
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FolderImport {
+    /// 新建的库数量
+    pub libraries: usize,
+    /// 新增的版本数量
+    pub versions: usize,
+    /// 跳过的文件及原因
+    pub skipped: Vec<String>,
+}
+
+/// 去掉版本号后的文件名，作为库名称（如 bmw_ptcan_v1.0.dbc → bmw_ptcan）
+pub fn library_name_from_path(path: &Path) -> String {
+    let stem = path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or_default();
+    let version = extract_version_from_path(&path.to_path_buf());
+    let digits = version.trim_start_matches('v');
+    let mut name = match stem.rfind(digits) {
+        Some(pos) if pos + digits.len() == stem.len() => &stem[..pos],
+        _ => stem,
+    };
+    // 去掉版本号前的 "v" 前缀和分隔符
+    let is_separator = |c: char| matches!(c, '_' | '-' | '.' | ' ');
+    if let Some(rest) = name.strip_suffix(['v', 'V'])
+        && (rest.is_empty() || rest.ends_with(is_separator))
+    {
+        name = rest;
+    }
+    let name = name.trim_end_matches(is_separator);
+    if name.is_empty() {
+        stem.to_string()
+    } else {
+        name.to_string()
+    }
+}
+
+/// 属于同一个库的数据库文件
+#[derive(Debug, Clone, PartialEq)]
+pub struct DatabaseGroup {
+    /// 库名称
+    pub name: String,
+    pub database_type: DatabaseType,
+    /// (版本名称, 路径)，按版本名称排序
+    pub versions: Vec<(String, PathBuf)>,
+}
+
+/// 将数据库文件按库名称和类型分组，按库名称排序
+///
+/// 不是 DBC/LDF 的文件会被忽略。
+pub fn group_database_files(paths: &[PathBuf]) -> Vec<DatabaseGroup> {
+    let mut groups: std::collections::BTreeMap<(String, String), Vec<(String, PathBuf)>> =
+        std::collections::BTreeMap::new();
+    for path in paths {
+        let Some(database_type) = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .and_then(DatabaseType::from_extension)
+        else {
+            continue;
+        };
+        groups
+            .entry((
+                library_name_from_path(path),
+                database_type.extension().to_string(),
+            ))
+            .or_default()
+            .push((extract_version_from_path(path), path.clone()));
+    }
+    groups
+        .into_iter()
+        .filter_map(|((name, extension), mut versions)| {
+            versions.sort();
+            Some(DatabaseGroup {
+                name,
+                database_type: DatabaseType::from_extension(&extension)?,
+                versions,
+            })
+        })
+        .collect()
+}
+
 /// 从文件路径提取版本号
 pub fn extract_version_from_path(path: &PathBuf) -> String {
     path.file_stem()
@@ -488,4 +855,118 @@ mod tests {
         // 如果找不到版本号，应该使用日期
         assert!(v2.starts_with("v20"));
     }
+
+    #[test]
+    fn test_validate_mappings() {
+        let mut manager = LibraryManager::new();
+        manager
+            .create_library("Body".to_string(), ChannelType::CAN)
+            .unwrap();
+        let library_id = generate_library_id("Body");
+        let mapping = |channel_id, path: &str| ChannelMapping {
+            channel_type: ChannelType::CAN,
+            channel_id,
+            path: path.to_string(),
+            description: String::new(),
+            library_id: None,
+            version_name: None,
+            capture: None,
+        };
+        let mut linked = mapping(3, "/missing/body.dbc");
+        linked.library_id = Some(library_id);
+        linked.version_name = Some("v9".to_string());
+        let mappings = vec![
+            mapping(0, "/missing/a.dbc"),
+            mapping(1, "/missing/b.dbc"),
+            mapping(1, "Cargo.toml"),
+            linked,
+        ];
+
+        let issues = manager.validate_mappings(&mappings);
+        let problems: Vec<(usize, MappingProblem)> = issues
+            .into_iter()
+            .map(|issue| (issue.index, issue.problem))
+            .collect();
+        assert_eq!(
+            problems,
+            vec![
+                (0, MappingProblem::ChannelOutOfRange),
+                (0, MappingProblem::FileNotFound),
+                (1, MappingProblem::FileNotFound),
+                (2, MappingProblem::DuplicateChannel),
+                (2, MappingProblem::TypeMismatch(None)),
+                (3, MappingProblem::MissingLibrary),
+                (3, MappingProblem::FileNotFound),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_load_database_with_timeout() {
+        let path = std::env::temp_dir().join(format!("canview_load_{}.dbc", std::process::id()));
+        std::fs::write(&path, "VERSION \"\"\n\nBU_: ECU\n").unwrap();
+        let path_str = path.to_string_lossy().to_string();
+
+        let loaded = load_database_with_timeout(&path_str, ChannelType::CAN, DATABASE_LOAD_TIMEOUT);
+        assert!(matches!(loaded, Ok(Database::Dbc(_))));
+
+        assert!(is_database_cached(&path_str, ChannelType::CAN));
+        assert!(!is_database_cached(&path_str, ChannelType::LIN));
+
+        // A changed file is parsed again
+        std::fs::write(&path, "VERSION \"2\"\n\nBU_: ECU GW\n").unwrap();
+        assert!(!is_database_cached(&path_str, ChannelType::CAN));
+        assert!(load_database_file(&path_str, ChannelType::CAN).is_ok());
+        assert!(is_database_cached(&path_str, ChannelType::CAN));
+
+        let missing = load_database_with_timeout(
+            "/missing/body.dbc",
+            ChannelType::CAN,
+            DATABASE_LOAD_TIMEOUT,
+        );
+        assert!(missing.is_err());
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_group_database_files() {
+        assert_eq!(
+            library_name_from_path(Path::new("/db/bmw_ptcan_v1.0.dbc")),
+            "bmw_ptcan"
+        );
+        assert_eq!(
+            library_name_from_path(Path::new("/db/Body-2.1.dbc")),
+            "Body"
+        );
+        assert_eq!(
+            library_name_from_path(Path::new("/db/ford_lin.ldf")),
+            "ford_lin"
+        );
+
+        let paths: Vec<PathBuf> = [
+            "/db/body_v2.0.dbc",
+            "/db/body_v1.0.dbc",
+            "/db/body_v1.0.ldf",
+            "/db/chassis_1.5.dbc",
+            "/db/readme.txt",
+        ]
+        .into_iter()
+        .map(PathBuf::from)
+        .collect();
+        let groups = group_database_files(&paths);
+
+        assert_eq!(groups.len(), 3);
+        assert_eq!(
+            (groups[0].name.as_str(), groups[0].database_type),
+            ("body", DatabaseType::DBC)
+        );
+        let names: Vec<&str> = groups[0].versions.iter().map(|(v, _)| v.as_str()).collect();
+        assert_eq!(names, ["v1.0", "v2.0"]);
+        assert_eq!(
+            (groups[1].name.as_str(), groups[1].database_type),
+            ("body", DatabaseType::LDF)
+        );
+        assert_eq!(groups[2].name, "chassis");
+        assert_eq!(groups[2].versions[0].0, "v1.5");
+    }
 }
@@ -842,6 +842,7 @@ fn render_selected_library_info(
                                                             description: String::new(),
                                                             library_id: Some(lib_id.clone()),
                                                             version_name: Some(version_name.clone()),
+                                                            capture: None,
                                                         };
                                                         this.app_config.mappings.push(new_mapping);
                                                     }
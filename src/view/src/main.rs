@@ -20,6 +20,39 @@ pub use models::{AppConfig, ChannelMapping, ChannelType};
 fn main() {
     env_logger::init();
 
+    // `canview serve ...` runs the headless decode server instead of the GUI
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.first().map(String::as_str) == Some("serve") {
+        let options = match handlers::ServeOptions::parse(&args[1..]) {
+            Ok(options) => options,
+            Err(e) => {
+                eprintln!("❌ {}\n\n{}", e, handlers::SERVE_USAGE);
+                std::process::exit(2);
+            }
+        };
+        if let Err(e) = handlers::serve(options) {
+            eprintln!("❌ {}", e);
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    // `canview query FILE.blf ...` prints the matching frames and exits
+    if args.first().map(String::as_str) == Some("query") {
+        let options = match handlers::QueryOptions::parse(&args[1..]) {
+            Ok(options) => options,
+            Err(e) => {
+                eprintln!("❌ {}\n\n{}", e, handlers::QUERY_USAGE);
+                std::process::exit(2);
+            }
+        };
+        if let Err(e) = handlers::run_query(&options) {
+            eprintln!("❌ {}", e);
+            std::process::exit(1);
+        }
+        return;
+    }
+
     let app = Application::new();
     app.run(move |cx| {
         // This must be called before using any GPUI Component features
@@ -43,7 +76,7 @@ fn main() {
                 ..Default::default()
             };
             cx.open_window(options, |window, cx| {
-                let view = cx.new(|_cx| CanViewApp::new());
+                let view = cx.new(CanViewApp::new);
                 // This first level on the window should be a Root for gpui-component
                 cx.new(|cx| gpui_component::Root::new(view, window, cx))
             })?;
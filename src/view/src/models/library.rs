@@ -115,6 +115,15 @@ pub struct LibraryVersion {
     /// 存储结构：按通道类型分组的配置
     #[serde(default)]
     pub channel_databases: Vec<ChannelDatabase>,
+    /// 发布说明（自由文本）
+    #[serde(default)]
+    pub notes: String,
+    /// 作者
+    #[serde(default)]
+    pub author: String,
+    /// 数据库文件的 CRC-32 校验和（8 位十六进制），见 [`LibraryVersion::update_checksum`]
+    #[serde(default)]
+    pub checksum: Option<String>,
 }
 
 impl LibraryVersion {
@@ -126,6 +135,9 @@ impl LibraryVersion {
             date,
             description: String::new(),
             channel_databases: Vec::new(),
+            notes: String::new(),
+            author: String::new(),
+            checksum: None,
         }
     }
 
@@ -135,20 +147,62 @@ impl LibraryVersion {
         self
     }
 
+    /// 设置作者
+    pub fn with_author(mut self, author: String) -> Self {
+        self.author = author;
+        self
+    }
+
+    /// 版本使用的数据库文件：默认路径在前，其后按通道顺序
+    pub fn database_files(&self) -> Vec<&str> {
+        std::iter::once(self.path.as_str())
+            .chain(
+                self.channel_databases
+                    .iter()
+                    .map(|db| db.database_path.as_str()),
+            )
+            .filter(|path| !path.is_empty())
+            .collect()
+    }
+
+    /// 计算数据库文件当前的校验和；没有数据库文件时返回 `Ok(None)`
+    pub fn compute_checksum(&self) -> Result<Option<String>, String> {
+        let files = self.database_files();
+        if files.is_empty() {
+            return Ok(None);
+        }
+        let mut crc = Crc32::new();
+        for path in files {
+            crc.update(&read_database(path)?);
+        }
+        Ok(Some(format!("{:08x}", crc.finish())))
+    }
+
+    /// 重新计算并记录校验和，在数据库文件变化后调用
+    pub fn update_checksum(&mut self) -> Result<(), String> {
+        self.checksum = self.compute_checksum()?;
+        Ok(())
+    }
+
+    /// 数据库文件是否与记录的校验和一致
+    ///
+    /// 未记录校验和或文件无法读取时返回 `None`
+    pub fn checksum_matches(&self) -> Option<bool> {
+        let recorded = self.checksum.as_ref()?;
+        let current = self.compute_checksum().ok()??;
+        Some(&current == recorded)
+    }
+
     /// 添加通道数据库配置
     pub fn add_channel_database(&mut self, channel_db: ChannelDatabase) -> Result<(), String> {
         // 验证通道配置
         channel_db.validate()?;
 
-        // 检查通道ID是否已存在
-        if self
-            .channel_databases
-            .iter()
-            .any(|db| db.channel_id == channel_db.channel_id)
-        {
+        // 检查同类型的通道ID是否已存在；同一通道号可同时配置 DBC 和 LDF
+        if self.is_channel_id_used(channel_db.channel_type, channel_db.channel_id) {
             return Err(format!(
-                "Channel ID {} already exists in this version",
-                channel_db.channel_id
+                "{:?} channel {} already exists in this version",
+                channel_db.channel_type, channel_db.channel_id
             ));
         }
 
@@ -156,11 +210,15 @@ impl LibraryVersion {
         Ok(())
     }
 
-    /// 获取指定通道的数据库配置
-    pub fn get_channel_database(&self, channel_id: u16) -> Option<&ChannelDatabase> {
+    /// 获取指定类型和通道的数据库配置
+    pub fn get_channel_database(
+        &self,
+        channel_type: crate::models::ChannelType,
+        channel_id: u16,
+    ) -> Option<&ChannelDatabase> {
         self.channel_databases
             .iter()
-            .find(|db| db.channel_id == channel_id)
+            .find(|db| db.channel_type == channel_type && db.channel_id == channel_id)
     }
 
     /// 获取所有CAN通道
@@ -195,11 +253,14 @@ impl LibraryVersion {
         map
     }
 
-    /// 检查通道ID是否已被使用
-    pub fn is_channel_id_used(&self, channel_id: u16) -> bool {
-        self.channel_databases
-            .iter()
-            .any(|db| db.channel_id == channel_id)
+    /// 检查指定类型的通道ID是否已被使用
+    pub fn is_channel_id_used(
+        &self,
+        channel_type: crate::models::ChannelType,
+        channel_id: u16,
+    ) -> bool {
+        self.get_channel_database(channel_type, channel_id)
+            .is_some()
     }
 
     /// 获取已使用的通道ID列表
@@ -245,6 +306,47 @@ pub struct SignalLibrary {
     pub versions: Vec<LibraryVersion>,
 }
 
+/// CRC-32（IEEE 802.3）增量计算，用于数据库文件校验和
+#[derive(Debug, Clone, Copy)]
+pub struct Crc32(u32);
+
+impl Crc32 {
+    pub fn new() -> Self {
+        Self(0xFFFF_FFFF)
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        for byte in data {
+            self.0 ^= *byte as u32;
+            for _ in 0..8 {
+                let mask = (self.0 & 1).wrapping_neg();
+                self.0 = (self.0 >> 1) ^ (0xEDB8_8320 & mask);
+            }
+        }
+    }
+
+    pub fn finish(&self) -> u32 {
+        !self.0
+    }
+}
+
+impl Default for Crc32 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 单个数据库文件的 CRC-32 校验和，格式与 [`LibraryVersion::checksum`] 相同
+pub fn file_checksum(path: &str) -> Result<String, String> {
+    let mut crc = Crc32::new();
+    crc.update(&read_database(path)?);
+    Ok(format!("{:08x}", crc.finish()))
+}
+
+fn read_database(path: &str) -> Result<Vec<u8>, String> {
+    std::fs::read(path).map_err(|e| format!("{}: {}", path, e))
+}
+
 fn default_channel_type() -> super::ChannelType {
     super::ChannelType::CAN
 }
@@ -270,6 +372,21 @@ impl SignalLibrary {
         self.versions.iter().find(|v| v.name == name)
     }
 
+    /// 查找使用了校验和为 `checksum` 的数据库文件的版本
+    ///
+    /// 单文件版本直接比较记录的校验和，其余版本逐个读取数据库文件
+    pub fn find_database(&self, checksum: &str) -> Option<&LibraryVersion> {
+        self.versions.iter().find(|version| {
+            let files = version.database_files();
+            if files.len() == 1 && version.checksum.is_some() {
+                return version.checksum.as_deref() == Some(checksum);
+            }
+            files
+                .into_iter()
+                .any(|path| file_checksum(path).is_ok_and(|crc| crc == checksum))
+        })
+    }
+
     /// 获取可变版本引用
     pub fn get_version_mut(&mut self, name: &str) -> Option<&mut LibraryVersion> {
         self.versions.iter_mut().find(|v| v.name == name)
@@ -292,6 +409,34 @@ impl SignalLibrary {
         }
     }
 
+    /// 按名称搜索并按类型过滤，返回只保留匹配版本的副本
+    ///
+    /// 搜索不区分大小写；库名匹配时保留全部版本，否则只保留名称匹配的版本。
+    /// 没有任何匹配时返回 `None`。
+    pub fn search(
+        &self,
+        query: &str,
+        database_type: Option<DatabaseType>,
+    ) -> Option<SignalLibrary> {
+        if database_type.is_some_and(|database_type| database_type != self.database_type()) {
+            return None;
+        }
+        let query = query.trim().to_lowercase();
+        if query.is_empty() || self.name.to_lowercase().contains(&query) {
+            return Some(self.clone());
+        }
+        let versions: Vec<LibraryVersion> = self
+            .versions
+            .iter()
+            .filter(|version| version.name.to_lowercase().contains(&query))
+            .cloned()
+            .collect();
+        (!versions.is_empty()).then(|| SignalLibrary {
+            versions,
+            ..self.clone()
+        })
+    }
+
     /// 移除版本
     pub fn remove_version(&mut self, name: &str) -> bool {
         if let Some(pos) = self.versions.iter().position(|v| v.name == name) {
@@ -334,6 +479,80 @@ impl SignalLibrary {
             .filter(|m| m.library_id.as_ref() == Some(&self.id))
             .find_map(|m| m.version_name.clone())
     }
+
+    /// 获取激活了指定版本的通道列表（升序）
+    pub fn version_channels(
+        &self,
+        version_name: &str,
+        mappings: &[super::ChannelMapping],
+    ) -> Vec<u16> {
+        let mut channels: Vec<u16> = mappings
+            .iter()
+            .filter(|m| {
+                m.channel_type == self.channel_type
+                    && m.library_id.as_ref() == Some(&self.id)
+                    && m.version_name.as_deref() == Some(version_name)
+            })
+            .map(|m| m.channel_id)
+            .collect();
+        channels.sort_unstable();
+        channels.dedup();
+        channels
+    }
+
+    /// 将版本激活到指定通道，创建或更新通道映射
+    ///
+    /// 列表中的通道改用此版本（该通道有单独配置的数据库时使用它，否则使用版本的默认路径）；
+    /// 之前使用此版本但不在列表中的通道映射会被移除。
+    pub fn assign_version(
+        &self,
+        version_name: &str,
+        channels: &[u16],
+        mappings: &mut Vec<super::ChannelMapping>,
+    ) -> Result<(), String> {
+        let version = self
+            .get_version(version_name)
+            .ok_or_else(|| format!("Version '{}' not found", version_name))?;
+
+        mappings.retain(|m| {
+            m.channel_type != self.channel_type
+                || channels.contains(&m.channel_id)
+                || m.library_id.as_ref() != Some(&self.id)
+                || m.version_name.as_deref() != Some(version_name)
+        });
+
+        for &channel_id in channels {
+            let path = version
+                .channel_databases
+                .iter()
+                .find(|db| db.channel_type == self.channel_type && db.channel_id == channel_id)
+                .map(|db| db.database_path.clone())
+                .unwrap_or_else(|| version.path.clone());
+            let mapping = match mappings
+                .iter_mut()
+                .find(|m| m.channel_type == self.channel_type && m.channel_id == channel_id)
+            {
+                Some(mapping) => mapping,
+                None => {
+                    mappings.push(super::ChannelMapping {
+                        channel_type: self.channel_type,
+                        channel_id,
+                        path: String::new(),
+                        description: String::new(),
+                        library_id: None,
+                        version_name: None,
+                        capture: None,
+                    });
+                    mappings.last_mut().unwrap()
+                }
+            };
+            mapping.path = path;
+            mapping.description = format!("{} {}", self.name, version.name);
+            mapping.library_id = Some(self.id.clone());
+            mapping.version_name = Some(version.name.clone());
+        }
+        Ok(())
+    }
 }
 
 /// 从版本字符串中提取数字用于排序
@@ -401,6 +620,158 @@ mod tests {
         assert_eq!(lib.versions[2].name, "v1.0");
     }
 
+    #[test]
+    fn test_library_search() {
+        let mut lib = SignalLibrary::new(
+            "test".to_string(),
+            "Powertrain 动力".to_string(),
+            super::ChannelType::CAN,
+        );
+        for name in ["v1.0", "v2.0-beta"] {
+            lib.add_version(LibraryVersion::new(
+                name.to_string(),
+                String::new(),
+                "2024-01-01".to_string(),
+            ));
+        }
+
+        assert_eq!(lib.search("", None).unwrap().versions.len(), 2);
+        assert_eq!(lib.search("POWER", None).unwrap().versions.len(), 2);
+        assert_eq!(
+            lib.search("动力", Some(DatabaseType::DBC))
+                .unwrap()
+                .versions
+                .len(),
+            2
+        );
+        let beta = lib.search("beta", None).unwrap();
+        assert_eq!(beta.versions.len(), 1);
+        assert_eq!(beta.versions[0].name, "v2.0-beta");
+        assert!(lib.search("body", None).is_none());
+        assert!(lib.search("", Some(DatabaseType::LDF)).is_none());
+    }
+
+    #[test]
+    fn test_assign_version_to_channels() {
+        let mut lib = SignalLibrary::new(
+            "body".to_string(),
+            "Body".to_string(),
+            super::ChannelType::CAN,
+        );
+        let mut version = LibraryVersion::new(
+            "v1.0".to_string(),
+            "/body.dbc".to_string(),
+            "2024-01-01".to_string(),
+        );
+        version.channel_databases.push(ChannelDatabase {
+            channel_type: super::ChannelType::CAN,
+            channel_id: 2,
+            channel_name: "Body2".to_string(),
+            database_path: "/body_ch2.dbc".to_string(),
+        });
+        lib.add_version(version);
+        lib.add_version(LibraryVersion::new(
+            "v2.0".to_string(),
+            "/body2.dbc".to_string(),
+            "2024-02-01".to_string(),
+        ));
+
+        let mut mappings = Vec::new();
+        lib.assign_version("v1.0", &[1, 2], &mut mappings).unwrap();
+        assert_eq!(lib.version_channels("v1.0", &mappings), vec![1, 2]);
+        assert_eq!(mappings[1].path, "/body_ch2.dbc");
+
+        // Channel 2 moves to v2.0 and channel 1 is dropped from v1.0
+        lib.assign_version("v2.0", &[2], &mut mappings).unwrap();
+        lib.assign_version("v1.0", &[], &mut mappings).unwrap();
+        assert_eq!(mappings.len(), 1);
+        assert_eq!(mappings[0].channel_id, 2);
+        assert_eq!(mappings[0].version_name.as_deref(), Some("v2.0"));
+        assert_eq!(mappings[0].path, "/body2.dbc");
+
+        assert!(lib.assign_version("v9", &[1], &mut mappings).is_err());
+    }
+
+    #[test]
+    fn test_version_checksum() {
+        let mut crc = Crc32::new();
+        crc.update(b"1234");
+        crc.update(b"56789");
+        assert_eq!(crc.finish(), 0xCBF4_3926);
+
+        let path =
+            std::env::temp_dir().join(format!("canview_checksum_{}.dbc", std::process::id()));
+        std::fs::write(&path, "VERSION \"1\"").unwrap();
+        let mut version = LibraryVersion::new(
+            "v1.0".to_string(),
+            path.to_string_lossy().to_string(),
+            "2024-01-01".to_string(),
+        )
+        .with_author("Alice".to_string());
+        assert_eq!(version.checksum_matches(), None);
+
+        version.update_checksum().unwrap();
+        assert_eq!(version.checksum.as_ref().map(String::len), Some(8));
+        assert_eq!(version.checksum_matches(), Some(true));
+
+        let path_str = path.to_string_lossy().to_string();
+        assert_eq!(file_checksum(&path_str).ok(), version.checksum);
+        let mut lib = SignalLibrary::new(
+            "body".to_string(),
+            "Body".to_string(),
+            super::ChannelType::CAN,
+        );
+        lib.add_version(version.clone());
+        let checksum = version.checksum.clone().unwrap();
+        assert_eq!(
+            lib.find_database(&checksum).map(|v| v.name.as_str()),
+            Some("v1.0")
+        );
+        assert!(lib.find_database("00000000").is_none());
+
+        std::fs::write(&path, "VERSION \"2\"").unwrap();
+        assert_eq!(version.checksum_matches(), Some(false));
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(version.checksum_matches(), None);
+        assert!(version.update_checksum().is_err());
+
+        // Versions saved before these fields existed still load
+        let json = r#"{"name":"v1","path":"","date":"2024-01-01"}"#;
+        let old: LibraryVersion = serde_json::from_str(json).unwrap();
+        assert!(old.notes.is_empty() && old.author.is_empty() && old.checksum.is_none());
+    }
+
+    #[test]
+    fn test_mixed_type_channels() {
+        use crate::models::ChannelType;
+
+        let mut version =
+            LibraryVersion::new("v1.0".to_string(), String::new(), "2024-01-01".to_string());
+        let db = |channel_type, path: &str| {
+            ChannelDatabase::new(channel_type, 1, "Gateway".to_string(), path.to_string())
+        };
+        version
+            .add_channel_database(db(ChannelType::CAN, "/gw.dbc"))
+            .unwrap();
+        version
+            .add_channel_database(db(ChannelType::LIN, "/gw_diag.ldf"))
+            .unwrap();
+        assert!(
+            version
+                .add_channel_database(db(ChannelType::CAN, "/gw2.dbc"))
+                .is_err()
+        );
+
+        assert_eq!(
+            version
+                .get_channel_database(ChannelType::LIN, 1)
+                .map(|db| db.database_path.as_str()),
+            Some("/gw_diag.ldf")
+        );
+        assert!(version.is_channel_id_used(ChannelType::CAN, 1));
+        assert!(!version.is_channel_id_used(ChannelType::CAN, 2));
+    }
+
     #[test]
     fn test_database_type() {
         assert_eq!(DatabaseType::from_extension("dbc"), Some(DatabaseType::DBC));
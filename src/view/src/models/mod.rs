@@ -1,16 +1,19 @@
 //! Data models for the CanView application
 
 pub mod library;
+pub mod preferences;
 
+use crate::handlers::{CaptureDevice, ExportPlugin, Highlight, MqttSettings, WatchSettings};
 use gpui::Pixels;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 // Re-export library types
 pub use library::{ChannelDatabase, DatabaseType, LibraryVersion, SignalLibrary, VersionStats};
+pub use preferences::Preferences;
 
 /// Channel type enumeration
-#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Copy)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash, Copy)]
 pub enum ChannelType {
     CAN,
     LIN,
@@ -56,6 +59,9 @@ pub struct ChannelMapping {
     /// 激活的版本名称
     #[serde(default)]
     pub version_name: Option<String>,
+    /// 实时采集该通道所用的硬件接口
+    #[serde(default)]
+    pub capture: Option<CaptureDevice>,
 }
 
 fn default_channel_type() -> ChannelType {
@@ -77,4 +83,19 @@ pub struct AppConfig {
     /// 当前激活的版本名称
     #[serde(default)]
     pub active_version_name: Option<String>,
+    /// 显示偏好设置
+    #[serde(default)]
+    pub preferences: Preferences,
+    /// ID 与通道的高亮颜色
+    #[serde(default)]
+    pub highlights: Vec<Highlight>,
+    /// 解码信号的 MQTT 发布设置
+    #[serde(default)]
+    pub mqtt: MqttSettings,
+    /// 自动导入新日志的监视文件夹
+    #[serde(default)]
+    pub watch: WatchSettings,
+    /// 插件提供的导出格式
+    #[serde(default)]
+    pub export_plugins: Vec<ExportPlugin>,
 }
@@ -0,0 +1,860 @@
+//! User preferences
+//!
+//! Display options edited in the Preferences dialog and stored in the
+//! `preferences` section of the config file. Every field has a serde default
+//! so configs written before this section existed still load.
+
+use serde::{Deserialize, Serialize};
+
+/// Smallest and largest message row height offered, in pixels
+pub const MIN_ROW_HEIGHT: f32 = 18.0;
+pub const MAX_ROW_HEIGHT: f32 = 32.0;
+
+/// Smallest and largest UI scale, and the step of one zoom shortcut
+pub const MIN_UI_SCALE: f32 = 0.8;
+pub const MAX_UI_SCALE: f32 = 2.0;
+pub const UI_SCALE_STEP: f32 = 0.1;
+
+/// Most lines the decoded signals of a log row may wrap onto
+pub const MAX_SIGNAL_LINES: usize = 6;
+
+/// Monospace fonts offered for the log data; "" is the platform default.
+/// Any other installed font can be named in the config file.
+pub const DATA_FONTS: [&str; 5] = ["", "Consolas", "Cascadia Mono", "Menlo", "JetBrains Mono"];
+
+#[cfg(target_os = "windows")]
+const PLATFORM_DATA_FONT: &str = "Consolas";
+#[cfg(target_os = "macos")]
+const PLATFORM_DATA_FONT: &str = "Menlo";
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+const PLATFORM_DATA_FONT: &str = "DejaVu Sans Mono";
+
+/// Fonts tried for glyphs the chosen font lacks (Chinese units and comments
+/// in DBC files), in order. Western fonts such as Consolas have no CJK
+/// glyphs, which otherwise render as boxes.
+#[cfg(target_os = "windows")]
+const PLATFORM_FALLBACK_FONTS: &[&str] =
+    &["Microsoft YaHei", "SimSun", "Yu Gothic", "Malgun Gothic"];
+#[cfg(target_os = "macos")]
+const PLATFORM_FALLBACK_FONTS: &[&str] = &["PingFang SC", "Hiragino Sans", "Apple SD Gothic Neo"];
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+const PLATFORM_FALLBACK_FONTS: &[&str] = &[
+    "Noto Sans CJK SC",
+    "WenQuanYi Micro Hei",
+    "Droid Sans Fallback",
+];
+
+/// How the TIME column is shown
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+pub enum TimestampFormat {
+    /// Wall-clock date and time from the measurement start
+    #[default]
+    Absolute,
+    /// Seconds since the start of the measurement
+    Relative,
+}
+
+impl TimestampFormat {
+    pub const ALL: [TimestampFormat; 2] = [TimestampFormat::Absolute, TimestampFormat::Relative];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            TimestampFormat::Absolute => "Absolute",
+            TimestampFormat::Relative => "Relative",
+        }
+    }
+}
+
+/// Number of fractional second digits shown in the TIME column
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+pub enum TimestampPrecision {
+    Milliseconds,
+    #[default]
+    Microseconds,
+    Nanoseconds,
+}
+
+impl TimestampPrecision {
+    pub const ALL: [TimestampPrecision; 3] = [
+        TimestampPrecision::Milliseconds,
+        TimestampPrecision::Microseconds,
+        TimestampPrecision::Nanoseconds,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            TimestampPrecision::Milliseconds => "ms",
+            TimestampPrecision::Microseconds => "µs",
+            TimestampPrecision::Nanoseconds => "ns",
+        }
+    }
+
+    pub fn digits(&self) -> usize {
+        match self {
+            TimestampPrecision::Milliseconds => 3,
+            TimestampPrecision::Microseconds => 6,
+            TimestampPrecision::Nanoseconds => 9,
+        }
+    }
+}
+
+/// Time zone absolute timestamps are shown in
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+pub enum TimeZoneDisplay {
+    /// The logger's wall clock, as stored in the file
+    #[default]
+    Logger,
+    /// This computer's time zone
+    Local,
+    Utc,
+}
+
+impl TimeZoneDisplay {
+    pub const ALL: [TimeZoneDisplay; 3] = [
+        TimeZoneDisplay::Logger,
+        TimeZoneDisplay::Local,
+        TimeZoneDisplay::Utc,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            TimeZoneDisplay::Logger => "Logger",
+            TimeZoneDisplay::Local => "Local",
+            TimeZoneDisplay::Utc => "UTC",
+        }
+    }
+}
+
+/// Base used for message IDs
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+pub enum IdBase {
+    #[default]
+    Decimal,
+    Hex,
+}
+
+impl IdBase {
+    pub const ALL: [IdBase; 2] = [IdBase::Decimal, IdBase::Hex];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            IdBase::Decimal => "Decimal",
+            IdBase::Hex => "Hex",
+        }
+    }
+}
+
+/// Color theme
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+pub enum Theme {
+    #[default]
+    Dark,
+    Light,
+}
+
+impl Theme {
+    pub const ALL: [Theme; 2] = [Theme::Dark, Theme::Light];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Theme::Dark => "Dark",
+            Theme::Light => "Light",
+        }
+    }
+}
+
+/// UI language
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+pub enum Language {
+    #[default]
+    English,
+    Chinese,
+}
+
+impl Language {
+    pub const ALL: [Language; 2] = [Language::English, Language::Chinese];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Language::English => "English",
+            Language::Chinese => "中文",
+        }
+    }
+}
+
+/// File format used by the export dialog
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+pub enum ExportFormat {
+    Blf,
+    Asc,
+    /// Linux can-utils `candump -l` text log
+    Candump,
+    #[default]
+    Csv,
+    Json,
+    Mf4,
+    /// Only written by builds with the `parquet` feature
+    Parquet,
+    Sqlite,
+}
+
+impl ExportFormat {
+    pub const ALL: [ExportFormat; 8] = [
+        ExportFormat::Blf,
+        ExportFormat::Asc,
+        ExportFormat::Candump,
+        ExportFormat::Csv,
+        ExportFormat::Json,
+        ExportFormat::Mf4,
+        ExportFormat::Parquet,
+        ExportFormat::Sqlite,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ExportFormat::Blf => "BLF",
+            ExportFormat::Asc => "ASC",
+            ExportFormat::Candump => "candump",
+            ExportFormat::Csv => "CSV",
+            ExportFormat::Json => "JSON",
+            ExportFormat::Mf4 => "MDF4",
+            ExportFormat::Parquet => "Parquet",
+            ExportFormat::Sqlite => "SQLite",
+        }
+    }
+
+    /// File extension without the leading dot
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Blf => "blf",
+            ExportFormat::Asc => "asc",
+            ExportFormat::Candump => "log",
+            ExportFormat::Csv => "csv",
+            ExportFormat::Json => "json",
+            ExportFormat::Mf4 => "mf4",
+            ExportFormat::Parquet => "parquet",
+            ExportFormat::Sqlite => "sqlite",
+        }
+    }
+}
+
+/// Field separator of CSV exports
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+pub enum CsvDelimiter {
+    #[default]
+    Comma,
+    /// For spreadsheets in locales with a decimal comma
+    Semicolon,
+    Tab,
+}
+
+impl CsvDelimiter {
+    pub const ALL: [CsvDelimiter; 3] = [
+        CsvDelimiter::Comma,
+        CsvDelimiter::Semicolon,
+        CsvDelimiter::Tab,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            CsvDelimiter::Comma => ",",
+            CsvDelimiter::Semicolon => ";",
+            CsvDelimiter::Tab => "Tab",
+        }
+    }
+
+    pub fn as_char(&self) -> char {
+        match self {
+            CsvDelimiter::Comma => ',',
+            CsvDelimiter::Semicolon => ';',
+            CsvDelimiter::Tab => '\t',
+        }
+    }
+}
+
+/// Character between the whole and the fractional digits of numbers
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+pub enum DecimalSeparator {
+    #[default]
+    Point,
+    /// As written in most of continental Europe
+    Comma,
+}
+
+impl DecimalSeparator {
+    pub const ALL: [DecimalSeparator; 2] = [DecimalSeparator::Point, DecimalSeparator::Comma];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            DecimalSeparator::Point => "1.5",
+            DecimalSeparator::Comma => "1,5",
+        }
+    }
+
+    /// `number` as formatted by Rust, with this separator
+    pub fn localize(&self, number: String) -> String {
+        match self {
+            DecimalSeparator::Point => number,
+            DecimalSeparator::Comma => number.replace('.', ","),
+        }
+    }
+}
+
+/// Fractional digits of signal values
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+pub enum SignalPrecision {
+    /// As many as the signal's scaling resolves, e.g. one for a DBC factor of 0.1
+    #[default]
+    Auto,
+    Fixed(u8),
+}
+
+impl SignalPrecision {
+    pub const ALL: [SignalPrecision; 5] = [
+        SignalPrecision::Auto,
+        SignalPrecision::Fixed(0),
+        SignalPrecision::Fixed(1),
+        SignalPrecision::Fixed(2),
+        SignalPrecision::Fixed(3),
+    ];
+
+    pub fn label(&self) -> String {
+        match self {
+            SignalPrecision::Auto => "Auto".to_string(),
+            SignalPrecision::Fixed(digits) => format!("{} decimals", digits),
+        }
+    }
+}
+
+/// How signal values are written
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+pub struct NumberFormat {
+    #[serde(default)]
+    pub decimal_separator: DecimalSeparator,
+    #[serde(default)]
+    pub precision: SignalPrecision,
+}
+
+impl NumberFormat {
+    /// `value` rounded to the chosen precision, with the chosen separator
+    ///
+    /// `decimals` is the signal's own resolution, used by [`SignalPrecision::Auto`].
+    pub fn format(&self, value: f64, decimals: usize) -> String {
+        if !value.is_finite() {
+            return value.to_string();
+        }
+        let digits = match self.precision {
+            SignalPrecision::Auto => decimals,
+            SignalPrecision::Fixed(digits) => digits as usize,
+        };
+        self.decimal_separator
+            .localize(format!("{:.*}", digits, value))
+    }
+}
+
+/// Layout of CSV exports
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub struct CsvOptions {
+    #[serde(default)]
+    pub delimiter: CsvDelimiter,
+    /// Wall-clock time or seconds since the measurement start
+    #[serde(default = "default_csv_time_format")]
+    pub time_format: TimestampFormat,
+    #[serde(default = "default_csv_id_base")]
+    pub id_base: IdBase,
+    /// Signal values and relative times; a decimal comma wants another delimiter
+    #[serde(default)]
+    pub numbers: NumberFormat,
+}
+
+fn default_csv_time_format() -> TimestampFormat {
+    TimestampFormat::Relative
+}
+
+fn default_csv_id_base() -> IdBase {
+    IdBase::Hex
+}
+
+impl Default for CsvOptions {
+    fn default() -> Self {
+        Self {
+            delimiter: CsvDelimiter::default(),
+            time_format: default_csv_time_format(),
+            id_base: default_csv_id_base(),
+            numbers: NumberFormat::default(),
+        }
+    }
+}
+
+/// How much live traffic is kept for "Save" in live mode
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum CaptureHistory {
+    /// Everything received in the last N seconds
+    Seconds(u32),
+    /// The last N messages
+    Messages(u32),
+}
+
+impl Default for CaptureHistory {
+    fn default() -> Self {
+        CaptureHistory::Seconds(60)
+    }
+}
+
+impl CaptureHistory {
+    pub const ALL: [CaptureHistory; 5] = [
+        CaptureHistory::Seconds(10),
+        CaptureHistory::Seconds(60),
+        CaptureHistory::Seconds(300),
+        CaptureHistory::Messages(100_000),
+        CaptureHistory::Messages(1_000_000),
+    ];
+
+    pub fn label(&self) -> String {
+        match self {
+            CaptureHistory::Seconds(seconds) if seconds % 60 == 0 => {
+                format!("{} min", seconds / 60)
+            }
+            CaptureHistory::Seconds(seconds) => format!("{} s", seconds),
+            CaptureHistory::Messages(count) if count % 1_000_000 == 0 => {
+                format!("{}M msgs", count / 1_000_000)
+            }
+            CaptureHistory::Messages(count) => format!("{}k msgs", count / 1_000),
+        }
+    }
+}
+
+/// What to do with objects that go back in time when a BLF is loaded
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+pub enum TimestampOrder {
+    /// Keep the file order and only report the anomalies
+    #[default]
+    Keep,
+    /// Sort the log by timestamp
+    Sort,
+    /// Shift everything after a backward jump forward
+    Offset,
+}
+
+impl TimestampOrder {
+    pub const ALL: [TimestampOrder; 3] = [
+        TimestampOrder::Keep,
+        TimestampOrder::Sort,
+        TimestampOrder::Offset,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            TimestampOrder::Keep => "Keep",
+            TimestampOrder::Sort => "Sort",
+            TimestampOrder::Offset => "Shift",
+        }
+    }
+
+    pub fn repair(&self) -> blf::TimestampRepair {
+        match self {
+            TimestampOrder::Keep => blf::TimestampRepair::Keep,
+            TimestampOrder::Sort => blf::TimestampRepair::Sort,
+            TimestampOrder::Offset => blf::TimestampRepair::Offset,
+        }
+    }
+}
+
+/// Edge of the log view a panel is docked to
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+pub enum PanelDock {
+    #[default]
+    Left,
+    Right,
+    Bottom,
+}
+
+impl PanelDock {
+    pub const ALL: [PanelDock; 3] = [PanelDock::Left, PanelDock::Right, PanelDock::Bottom];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            PanelDock::Left => "Left",
+            PanelDock::Right => "Right",
+            PanelDock::Bottom => "Bottom",
+        }
+    }
+}
+
+/// Where a panel is shown
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+pub enum PanelPlacement {
+    /// Not next to the log; the statistics and charts keep their own tab
+    #[default]
+    Hidden,
+    Left,
+    Right,
+    Bottom,
+    /// In a window of its own, e.g. on a second monitor
+    Window,
+}
+
+impl PanelPlacement {
+    pub const ALL: [PanelPlacement; 5] = [
+        PanelPlacement::Hidden,
+        PanelPlacement::Left,
+        PanelPlacement::Right,
+        PanelPlacement::Bottom,
+        PanelPlacement::Window,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            PanelPlacement::Hidden => "Hidden",
+            PanelPlacement::Left => "Left",
+            PanelPlacement::Right => "Right",
+            PanelPlacement::Bottom => "Bottom",
+            PanelPlacement::Window => "Window",
+        }
+    }
+
+    /// Edge of the log the panel is docked to
+    pub fn dock(&self) -> Option<PanelDock> {
+        match self {
+            PanelPlacement::Left => Some(PanelDock::Left),
+            PanelPlacement::Right => Some(PanelDock::Right),
+            PanelPlacement::Bottom => Some(PanelDock::Bottom),
+            PanelPlacement::Hidden | PanelPlacement::Window => None,
+        }
+    }
+}
+
+fn default_row_height() -> f32 {
+    22.0
+}
+
+fn default_ui_scale() -> f32 {
+    1.0
+}
+
+fn default_signal_lines() -> usize {
+    1
+}
+
+fn default_show_health_summary() -> bool {
+    true
+}
+
+fn default_health_gap_ms() -> u32 {
+    100
+}
+
+fn default_memory_limit_mb() -> u32 {
+    4096
+}
+
+fn default_detail_panel() -> PanelPlacement {
+    PanelPlacement::Bottom
+}
+
+/// Display preferences
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Preferences {
+    #[serde(default)]
+    pub timestamp_format: TimestampFormat,
+    #[serde(default)]
+    pub id_base: IdBase,
+    #[serde(default)]
+    pub theme: Theme,
+    #[serde(default)]
+    pub language: Language,
+    /// Message list row height in pixels
+    #[serde(default = "default_row_height")]
+    pub row_height: f32,
+    /// Zoom of fonts and row heights, 1.0 being 100%
+    #[serde(default = "default_ui_scale")]
+    pub ui_scale: f32,
+    /// Lines the decoded signals of a log row wrap onto; 1 keeps rows one line high
+    #[serde(default = "default_signal_lines")]
+    pub signal_lines: usize,
+    /// Font of the log table; empty for the platform's monospace font
+    #[serde(default)]
+    pub data_font: String,
+    /// Fonts tried before the platform's CJK fonts when a glyph is missing
+    #[serde(default)]
+    pub fallback_fonts: Vec<String>,
+    #[serde(default)]
+    pub default_export_format: ExportFormat,
+    /// Write a SHA-256 manifest of the exported files and their source log
+    #[serde(default)]
+    pub export_manifest: bool,
+    #[serde(default)]
+    pub csv_export: CsvOptions,
+    /// Signal values shown in the log and the row details
+    #[serde(default)]
+    pub number_format: NumberFormat,
+    #[serde(default)]
+    pub capture_history: CaptureHistory,
+    /// Start a new recording file after this many megabytes; 0 disables
+    #[serde(default)]
+    pub rotate_size_mb: u32,
+    /// Start a new recording file after this many minutes; 0 disables
+    #[serde(default)]
+    pub rotate_minutes: u32,
+    /// Ask before loading a log estimated to need more memory than this; 0 disables
+    #[serde(default = "default_memory_limit_mb")]
+    pub memory_limit_mb: u32,
+    /// Repair applied to out-of-order timestamps when a BLF is loaded
+    #[serde(default)]
+    pub timestamp_order: TimestampOrder,
+    #[serde(default)]
+    pub timestamp_precision: TimestampPrecision,
+    #[serde(default)]
+    pub time_zone: TimeZoneDisplay,
+    /// Offset of the logger's clock from UTC in minutes; `None` if it ran in
+    /// this computer's time zone
+    #[serde(default)]
+    pub logger_utc_offset_min: Option<i32>,
+    /// Show the log health summary after a BLF is loaded
+    #[serde(default = "default_show_health_summary")]
+    pub show_health_summary: bool,
+    /// Silence between two objects reported as a gap by the health summary
+    #[serde(default = "default_health_gap_ms")]
+    pub health_gap_ms: u32,
+    #[serde(default)]
+    pub channel_tree_dock: PanelDock,
+    /// Statistics next to the log or in a window, besides their own tab
+    #[serde(default)]
+    pub stats_panel: PanelPlacement,
+    /// Charts next to the log or in a window, besides their own tab
+    #[serde(default)]
+    pub chart_panel: PanelPlacement,
+    /// Values of the charted signals at the selected row
+    #[serde(default)]
+    pub watch_panel: PanelPlacement,
+    /// Decoded signals of the selected row
+    #[serde(default = "default_detail_panel")]
+    pub detail_panel: PanelPlacement,
+}
+
+impl Default for Preferences {
+    fn default() -> Self {
+        Self {
+            timestamp_format: TimestampFormat::default(),
+            id_base: IdBase::default(),
+            theme: Theme::default(),
+            language: Language::default(),
+            row_height: default_row_height(),
+            ui_scale: default_ui_scale(),
+            signal_lines: default_signal_lines(),
+            data_font: String::new(),
+            fallback_fonts: Vec::new(),
+            default_export_format: ExportFormat::default(),
+            export_manifest: false,
+            csv_export: CsvOptions::default(),
+            number_format: NumberFormat::default(),
+            capture_history: CaptureHistory::default(),
+            rotate_size_mb: 0,
+            rotate_minutes: 0,
+            memory_limit_mb: default_memory_limit_mb(),
+            timestamp_order: TimestampOrder::default(),
+            timestamp_precision: TimestampPrecision::default(),
+            time_zone: TimeZoneDisplay::default(),
+            logger_utc_offset_min: None,
+            show_health_summary: default_show_health_summary(),
+            health_gap_ms: default_health_gap_ms(),
+            channel_tree_dock: PanelDock::default(),
+            stats_panel: PanelPlacement::default(),
+            chart_panel: PanelPlacement::default(),
+            watch_panel: PanelPlacement::default(),
+            detail_panel: default_detail_panel(),
+        }
+    }
+}
+
+impl Preferences {
+    /// Row height limited to the supported range (hand-edited configs may hold anything)
+    pub fn row_height(&self) -> f32 {
+        if self.row_height.is_finite() {
+            self.row_height.clamp(MIN_ROW_HEIGHT, MAX_ROW_HEIGHT)
+        } else {
+            default_row_height()
+        }
+    }
+
+    /// UI scale limited to the supported range
+    pub fn ui_scale(&self) -> f32 {
+        if self.ui_scale.is_finite() {
+            self.ui_scale.clamp(MIN_UI_SCALE, MAX_UI_SCALE)
+        } else {
+            default_ui_scale()
+        }
+    }
+
+    /// Signal lines limited to the supported range
+    pub fn signal_lines(&self) -> usize {
+        self.signal_lines.clamp(1, MAX_SIGNAL_LINES)
+    }
+
+    /// Font family of the log table
+    pub fn data_font(&self) -> &str {
+        match self.data_font.trim() {
+            "" => PLATFORM_DATA_FONT,
+            font => font,
+        }
+    }
+
+    /// Fallback fonts: the configured ones, then the platform's CJK fonts
+    pub fn font_fallbacks(&self) -> Vec<String> {
+        let mut fonts: Vec<String> = Vec::new();
+        let configured = self.fallback_fonts.iter().map(|font| font.trim());
+        for font in configured.chain(PLATFORM_FALLBACK_FONTS.iter().copied()) {
+            if !font.is_empty() && !fonts.iter().any(|known| known.eq_ignore_ascii_case(font)) {
+                fonts.push(font.to_string());
+            }
+        }
+        fonts
+    }
+
+    /// Zoom in by `steps` scale steps, or out if negative
+    pub fn zoom(&mut self, steps: i32) {
+        let scale = self.ui_scale() + steps as f32 * UI_SCALE_STEP;
+        // Round so repeated steps land on whole percentages
+        self.ui_scale = ((scale * 100.0).round() / 100.0).clamp(MIN_UI_SCALE, MAX_UI_SCALE);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_missing_fields_use_defaults() {
+        let prefs: Preferences = serde_json::from_str(r#"{"id_base":"Hex"}"#).unwrap();
+        assert_eq!(prefs.id_base, IdBase::Hex);
+        assert_eq!(prefs.timestamp_format, TimestampFormat::Absolute);
+        assert_eq!(prefs.row_height, 22.0);
+        assert_eq!(prefs.ui_scale, 1.0);
+        assert_eq!(prefs.signal_lines, 1);
+        assert_eq!(prefs.data_font(), PLATFORM_DATA_FONT);
+        assert_eq!(prefs.default_export_format, ExportFormat::Csv);
+        assert_eq!(prefs.csv_export.time_format, TimestampFormat::Relative);
+        assert_eq!(prefs.csv_export.id_base, IdBase::Hex);
+        assert_eq!(prefs.capture_history, CaptureHistory::Seconds(60));
+        assert!(prefs.show_health_summary);
+        assert_eq!(prefs.health_gap_ms, 100);
+        assert_eq!(prefs.memory_limit_mb, 4096);
+        assert_eq!(prefs.channel_tree_dock, PanelDock::Left);
+        assert_eq!(prefs.stats_panel, PanelPlacement::Hidden);
+        assert_eq!(prefs.detail_panel, PanelPlacement::Bottom);
+        assert_eq!(prefs.number_format, NumberFormat::default());
+    }
+
+    #[test]
+    fn test_preferences_roundtrip() {
+        let prefs = Preferences {
+            timestamp_format: TimestampFormat::Relative,
+            id_base: IdBase::Hex,
+            theme: Theme::Light,
+            language: Language::Chinese,
+            row_height: 28.0,
+            ui_scale: 1.5,
+            signal_lines: 3,
+            data_font: "JetBrains Mono".to_string(),
+            fallback_fonts: vec!["Sarasa Mono SC".to_string()],
+            default_export_format: ExportFormat::Asc,
+            export_manifest: true,
+            csv_export: CsvOptions {
+                delimiter: CsvDelimiter::Semicolon,
+                time_format: TimestampFormat::Absolute,
+                id_base: IdBase::Decimal,
+                numbers: NumberFormat {
+                    decimal_separator: DecimalSeparator::Comma,
+                    precision: SignalPrecision::Auto,
+                },
+            },
+            number_format: NumberFormat {
+                decimal_separator: DecimalSeparator::Point,
+                precision: SignalPrecision::Fixed(3),
+            },
+            capture_history: CaptureHistory::Messages(100_000),
+            rotate_size_mb: 500,
+            rotate_minutes: 60,
+            memory_limit_mb: 0,
+            timestamp_order: TimestampOrder::Sort,
+            timestamp_precision: TimestampPrecision::Nanoseconds,
+            time_zone: TimeZoneDisplay::Utc,
+            logger_utc_offset_min: Some(-300),
+            show_health_summary: false,
+            health_gap_ms: 500,
+            channel_tree_dock: PanelDock::Right,
+            stats_panel: PanelPlacement::Bottom,
+            chart_panel: PanelPlacement::Window,
+            watch_panel: PanelPlacement::Right,
+            detail_panel: PanelPlacement::Hidden,
+        };
+        let json = serde_json::to_string(&prefs).unwrap();
+        assert_eq!(serde_json::from_str::<Preferences>(&json).unwrap(), prefs);
+    }
+
+    #[test]
+    fn test_row_height_is_clamped() {
+        let mut prefs = Preferences {
+            row_height: 4.0,
+            ..Preferences::default()
+        };
+        assert_eq!(prefs.row_height(), MIN_ROW_HEIGHT);
+        prefs.row_height = 100.0;
+        assert_eq!(prefs.row_height(), MAX_ROW_HEIGHT);
+        prefs.row_height = f32::NAN;
+        assert_eq!(prefs.row_height(), 22.0);
+        prefs.signal_lines = 0;
+        assert_eq!(prefs.signal_lines(), 1);
+    }
+
+    #[test]
+    fn test_font_fallbacks_put_configured_fonts_first() {
+        let prefs = Preferences {
+            fallback_fonts: vec![" Sarasa Mono SC ".to_string(), String::new()],
+            ..Preferences::default()
+        };
+        let fonts = prefs.font_fallbacks();
+        assert_eq!(fonts[0], "Sarasa Mono SC");
+        assert_eq!(&fonts[1..], PLATFORM_FALLBACK_FONTS);
+
+        let duplicate = Preferences {
+            fallback_fonts: vec![PLATFORM_FALLBACK_FONTS[0].to_lowercase()],
+            ..Preferences::default()
+        };
+        assert_eq!(
+            duplicate.font_fallbacks().len(),
+            PLATFORM_FALLBACK_FONTS.len()
+        );
+    }
+
+    #[test]
+    fn test_zoom_steps_and_limits() {
+        let mut prefs = Preferences::default();
+        prefs.zoom(1);
+        prefs.zoom(1);
+        assert_eq!(prefs.ui_scale(), 1.2);
+        prefs.zoom(-3);
+        assert_eq!(prefs.ui_scale(), 0.9);
+        prefs.zoom(-5);
+        assert_eq!(prefs.ui_scale(), MIN_UI_SCALE);
+        prefs.zoom(100);
+        assert_eq!(prefs.ui_scale(), MAX_UI_SCALE);
+        prefs.ui_scale = f32::NAN;
+        assert_eq!(prefs.ui_scale(), 1.0);
+    }
+
+    #[test]
+    fn test_number_format() {
+        let auto = NumberFormat::default();
+        assert_eq!(auto.format(12.5, 2), "12.50");
+        assert_eq!(auto.format(3.0, 0), "3");
+        assert_eq!(auto.format(f64::NAN, 2), "NaN");
+
+        let comma = NumberFormat {
+            decimal_separator: DecimalSeparator::Comma,
+            precision: SignalPrecision::Fixed(1),
+        };
+        assert_eq!(comma.format(-40.26, 3), "-40,3");
+        assert_eq!(comma.format(1234.0, 0), "1234,0");
+    }
+}
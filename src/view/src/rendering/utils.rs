@@ -57,9 +57,9 @@ pub fn format_hex_data(data: &[u8]) -> String {
 /// * `id` - The CAN ID
 ///
 /// # Returns
-/// A string with the ID formatted as "0xXXX"
+/// A string with the ID formatted as "0xXXX", or "0xXXXXXXXX" if extended
 pub fn format_can_id(id: u32) -> String {
-    format!("0x{:03X}", id)
+    crate::handlers::hex_can_id(id)
 }
 
 #[cfg(test)]
@@ -82,6 +82,7 @@ mod tests {
     #[test]
     fn test_format_can_id() {
         assert_eq!(format_can_id(0x123), "0x123");
-        assert_eq!(format_can_id(0xABC), "0xABC");
+        // Too wide for 11 bits, so extended
+        assert_eq!(format_can_id(0xABC), "0x00000ABC");
     }
 }
@@ -0,0 +1,307 @@
+//! Dropdown / combobox component
+//!
+//! A floating, optionally searchable list of items built on [`VirtualList`].
+//! The search query is owned by the parent: keystrokes are handled by the
+//! parent's key handler and the current text is passed back in through
+//! [`Dropdown::query`].
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! Dropdown::new("channel-filter", &state.channel_filter_list)
+//!     .items(channels.iter().map(|ch| (*ch, format!("CH: {}", ch))))
+//!     .query(state.channel_filter_text.clone())
+//!     .on_select(move |channel, _window, cx| { /* apply */ })
+//!     .on_dismiss(move |_window, cx| { /* close */ })
+//!     .build()
+//! ```
+//!
+//! For multi-select, pass the current set through [`Dropdown::selected_many`];
+//! rows then show a check mark and `on_select` is expected to toggle the value.
+
+use super::virtual_list::{VirtualList, VirtualListHandle};
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+use std::rc::Rc;
+
+type SelectHandler<T> = Rc<dyn Fn(&T, &mut Window, &mut App)>;
+type DismissHandler = Rc<dyn Fn(&mut Window, &mut App)>;
+
+/// Height of the search line shown above the items
+const SEARCH_ROW_HEIGHT: f32 = 26.0;
+
+/// Indices of the labels matching `query` (case-insensitive substring)
+///
+/// An empty or whitespace-only query matches everything.
+pub fn filter_indices<S: AsRef<str>>(labels: &[S], query: &str) -> Vec<usize> {
+    let query = query.trim().to_lowercase();
+    labels
+        .iter()
+        .enumerate()
+        .filter(|(_, label)| query.is_empty() || label.as_ref().to_lowercase().contains(&query))
+        .map(|(index, _)| index)
+        .collect()
+}
+
+/// Builder for a floating dropdown list
+pub struct Dropdown<T: Clone + PartialEq + 'static> {
+    id: SharedString,
+    list: VirtualListHandle,
+    items: Vec<(T, SharedString)>,
+    selected: Vec<T>,
+    multi: bool,
+    query: Option<SharedString>,
+    error: Option<SharedString>,
+    placeholder: SharedString,
+    width: Pixels,
+    max_height: Pixels,
+    on_select: Option<SelectHandler<T>>,
+    on_dismiss: Option<DismissHandler>,
+}
+
+impl<T: Clone + PartialEq + 'static> Dropdown<T> {
+    /// Create a dropdown whose scroll state lives in `list`
+    pub fn new(id: impl Into<SharedString>, list: &VirtualListHandle) -> Self {
+        Self {
+            id: id.into(),
+            list: list.clone(),
+            items: Vec::new(),
+            selected: Vec::new(),
+            multi: false,
+            query: None,
+            error: None,
+            placeholder: "Type to search...".into(),
+            width: px(150.),
+            max_height: px(300.),
+            on_select: None,
+            on_dismiss: None,
+        }
+    }
+
+    /// Set the items as `(value, label)` pairs
+    pub fn items(mut self, items: impl IntoIterator<Item = (T, impl Into<SharedString>)>) -> Self {
+        self.items = items
+            .into_iter()
+            .map(|(value, label)| (value, label.into()))
+            .collect();
+        self
+    }
+
+    /// Highlight the currently selected value
+    pub fn selected(mut self, selected: Option<T>) -> Self {
+        self.selected = selected.into_iter().collect();
+        self
+    }
+
+    /// Switch to multi-select, checking every value in `selected`
+    pub fn selected_many(mut self, selected: impl IntoIterator<Item = T>) -> Self {
+        self.selected = selected.into_iter().collect();
+        self.multi = true;
+        self
+    }
+
+    /// Make the dropdown searchable, showing and filtering by `query`
+    pub fn query(mut self, query: impl Into<SharedString>) -> Self {
+        self.query = Some(query.into());
+        self
+    }
+
+    /// Show `error` under the search line, e.g. when the query is not a valid value
+    pub fn error(mut self, error: Option<impl Into<SharedString>>) -> Self {
+        self.error = error.map(Into::into);
+        self
+    }
+
+    /// Placeholder shown in the search line when the query is empty
+    pub fn placeholder(mut self, placeholder: impl Into<SharedString>) -> Self {
+        self.placeholder = placeholder.into();
+        self
+    }
+
+    pub fn width(mut self, width: Pixels) -> Self {
+        self.width = width;
+        self
+    }
+
+    pub fn max_height(mut self, max_height: Pixels) -> Self {
+        self.max_height = max_height;
+        self
+    }
+
+    /// Called when an item is clicked
+    pub fn on_select(mut self, handler: impl Fn(&T, &mut Window, &mut App) + 'static) -> Self {
+        self.on_select = Some(Rc::new(handler));
+        self
+    }
+
+    /// Called when the user clicks outside the dropdown
+    pub fn on_dismiss(mut self, handler: impl Fn(&mut Window, &mut App) + 'static) -> Self {
+        self.on_dismiss = Some(Rc::new(handler));
+        self
+    }
+
+    fn visible_items(&self) -> Vec<(T, SharedString)> {
+        let query = self.query.as_ref().map(|q| q.as_ref()).unwrap_or("");
+        let labels: Vec<&str> = self.items.iter().map(|(_, label)| label.as_ref()).collect();
+        filter_indices(&labels, query)
+            .into_iter()
+            .map(|index| self.items[index].clone())
+            .collect()
+    }
+
+    /// Build the dropdown element (position it with an absolute wrapper)
+    pub fn build(self) -> impl IntoElement {
+        let visible = Rc::new(self.visible_items());
+        let row_height = self.list.row_height();
+        // The search line and the error line take the same height
+        let header_lines = self.query.is_some() as usize + self.error.is_some() as usize;
+        let search_height = header_lines as f32 * SEARCH_ROW_HEIGHT;
+        let list_height = (visible.len().max(1) as f32 * row_height)
+            .min(f32::from(self.max_height) - search_height);
+
+        let selected = self.selected.clone();
+        let multi = self.multi;
+        let on_select = self.on_select.clone();
+        let item_count = visible.len();
+
+        div()
+            .w(self.width)
+            .bg(rgb(0x1f2937))
+            .border_1()
+            .border_color(rgb(0x3b82f6))
+            .rounded(px(4.))
+            .shadow_lg()
+            .flex()
+            .flex_col()
+            .overflow_hidden() // Important: clip content
+            .occlude() // Keep clicks and wheel events away from what is underneath
+            .when_some(self.on_dismiss, |el, on_dismiss| {
+                el.on_mouse_down_out(move |_event, window, cx| on_dismiss(window, cx))
+            })
+            .when_some(self.query.clone(), |el, query| {
+                let is_empty = query.is_empty();
+                el.child(
+                    div()
+                        .h(px(SEARCH_ROW_HEIGHT))
+                        .px_3()
+                        .flex()
+                        .items_center()
+                        .gap_1()
+                        .border_b_1()
+                        .border_color(rgb(0x374151))
+                        .text_xs()
+                        .child(div().text_color(rgb(0x6b7280)).child("🔍"))
+                        .child(
+                            div()
+                                .text_color(if is_empty {
+                                    rgb(0x6b7280)
+                                } else {
+                                    rgb(0xffffff)
+                                })
+                                .child(if is_empty {
+                                    self.placeholder.clone()
+                                } else {
+                                    query
+                                }),
+                        ),
+                )
+            })
+            .when_some(self.error, |el, error| {
+                el.child(
+                    div()
+                        .h(px(SEARCH_ROW_HEIGHT))
+                        .px_3()
+                        .flex()
+                        .items_center()
+                        .border_b_1()
+                        .border_color(rgb(0x374151))
+                        .text_xs()
+                        .text_color(rgb(0xf87171))
+                        .child(error),
+                )
+            })
+            .child(div().h(px(list_height)).child(if item_count == 0 {
+                div()
+                    .px_3()
+                    .h(px(row_height))
+                    .flex()
+                    .items_center()
+                    .text_sm()
+                    .text_color(rgb(0x6b7280))
+                    .child("No matches")
+                    .into_any_element()
+            } else {
+                VirtualList::new(
+                    self.id,
+                    &self.list,
+                    item_count,
+                    move |range, _window, _cx| {
+                        range
+                            .map(|index| {
+                                let (value, label) = visible[index].clone();
+                                let is_selected = selected.contains(&value);
+                                div()
+                                    .w_full()
+                                    .px_3()
+                                    .h(px(row_height))
+                                    .flex()
+                                    .items_center()
+                                    .text_sm()
+                                    .text_color(rgb(0xffffff))
+                                    .when(is_selected && !multi, |row| row.bg(rgb(0x1e3a8a)))
+                                    .hover(|style| style.bg(rgb(0x374151)))
+                                    .cursor_pointer()
+                                    .when_some(on_select.clone(), |row, on_select| {
+                                        row.on_mouse_down(
+                                            MouseButton::Left,
+                                            move |_event, window, cx| {
+                                                cx.stop_propagation();
+                                                on_select(&value, window, cx);
+                                            },
+                                        )
+                                    })
+                                    .when(multi, |row| {
+                                        row.gap_2().child(
+                                            div()
+                                                .w(px(12.))
+                                                .text_color(rgb(0x60a5fa))
+                                                .child(if is_selected { "✓" } else { "" }),
+                                        )
+                                    })
+                                    .child(label)
+                                    .into_any_element()
+                            })
+                            .collect::<Vec<_>>()
+                    },
+                )
+                .build()
+                .into_any_element()
+            }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_filter_indices_empty_query_matches_all() {
+        let labels = ["CH: 1", "CH: 2", "CH: 10"];
+        assert_eq!(filter_indices(&labels, ""), vec![0, 1, 2]);
+        assert_eq!(filter_indices(&labels, "   "), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn test_filter_indices_substring_case_insensitive() {
+        let labels = ["EngineData", "BrakeStatus", "engine_temp"];
+        assert_eq!(filter_indices(&labels, "ENGINE"), vec![0, 2]);
+        assert_eq!(filter_indices(&labels, "status"), vec![1]);
+        assert!(filter_indices(&labels, "xyz").is_empty());
+    }
+
+    #[test]
+    fn test_filter_indices_numeric_query() {
+        let labels = ["ID: 100", "ID: 256", "ID: 1001"];
+        assert_eq!(filter_indices(&labels, "100"), vec![0, 2]);
+    }
+}
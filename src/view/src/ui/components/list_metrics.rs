@@ -0,0 +1,425 @@
+//! Scroll math of the virtual list
+//!
+//! Plain data without GPUI types: [`VirtualListMetrics`] turns viewport
+//! measurements, wheel distances, scrollbar drags and navigation keys into
+//! row indices. [`super::virtual_list`] feeds it from the rendered element.
+
+use std::collections::HashMap;
+use std::ops::Range;
+
+/// Default row height used by the log table, in pixels
+pub const DEFAULT_ROW_HEIGHT: f32 = 22.0;
+
+/// Width of the scrollbar gutter, in pixels
+pub const SCROLLBAR_WIDTH: f32 = 12.0;
+
+/// Smallest thumb height so the thumb stays grabbable on huge lists
+pub const MIN_THUMB_HEIGHT: f32 = 20.0;
+
+/// Position and size of the scrollbar thumb, relative to the track
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScrollbarGeometry {
+    pub thumb_top: f32,
+    pub thumb_height: f32,
+    pub track_height: f32,
+}
+
+impl ScrollbarGeometry {
+    /// Whether a track-relative y coordinate falls on the thumb
+    pub fn contains(&self, y: f32) -> bool {
+        y >= self.thumb_top && y <= self.thumb_top + self.thumb_height
+    }
+}
+
+/// Keyboard navigation commands understood by the list
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ListKey {
+    LineUp,
+    LineDown,
+    PageUp,
+    PageDown,
+    Home,
+    End,
+}
+
+impl ListKey {
+    /// Map a GPUI keystroke string (e.g. `"pagedown"`) to a navigation command
+    pub fn from_keystroke(keystroke: &str) -> Option<Self> {
+        match keystroke {
+            "up" => Some(ListKey::LineUp),
+            "down" => Some(ListKey::LineDown),
+            "pageup" => Some(ListKey::PageUp),
+            "pagedown" => Some(ListKey::PageDown),
+            "home" | "ctrl-home" | "cmd-up" => Some(ListKey::Home),
+            "end" | "ctrl-end" | "cmd-down" => Some(ListKey::End),
+            _ => None,
+        }
+    }
+}
+
+/// Measured heights of the rows of a variable-height list
+///
+/// Only rows whose height differs from the nominal row height are kept,
+/// together with the sum of the differences, so the content height stays
+/// cheap to compute on huge lists.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RowHeights {
+    measured: HashMap<usize, f32>,
+    extra: f32,
+}
+
+impl RowHeights {
+    /// Height of row `index`, `row_height` until it has been measured
+    pub fn get(&self, index: usize, row_height: f32) -> f32 {
+        self.measured.get(&index).copied().unwrap_or(row_height)
+    }
+
+    /// Record the painted height of a row; returns whether it changed
+    pub fn set(&mut self, index: usize, height: f32, row_height: f32) -> bool {
+        let previous = self.get(index, row_height);
+        if (height - previous).abs() < 0.5 {
+            return false;
+        }
+        self.extra += height - previous;
+        if (height - row_height).abs() < 0.5 {
+            self.measured.remove(&index);
+        } else {
+            self.measured.insert(index, height);
+        }
+        true
+    }
+
+    /// Total height added by the rows taller than `row_height`
+    pub fn extra(&self) -> f32 {
+        self.extra
+    }
+
+    pub fn clear(&mut self) {
+        self.measured.clear();
+        self.extra = 0.0;
+    }
+}
+
+/// Scroll state and geometry of a virtual list
+///
+/// `viewport_top` and `viewport_height` are measured from the rendered
+/// element every frame, so no window-layout constants are needed to convert
+/// mouse positions into list positions.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VirtualListMetrics {
+    pub row_height: f32,
+    pub item_count: usize,
+    pub first_visible: usize,
+    pub viewport_top: f32,
+    pub viewport_height: f32,
+    /// Distance between the grab point and the thumb top while dragging
+    pub drag_grab_offset: Option<f32>,
+    /// Rows may be taller than `row_height`; see [`RowHeights`]
+    pub variable: bool,
+    pub heights: RowHeights,
+    /// Wheel distance not yet turned into whole rows (variable heights)
+    pub pending_scroll: f32,
+}
+
+impl VirtualListMetrics {
+    pub fn new(row_height: f32) -> Self {
+        Self {
+            row_height,
+            item_count: 0,
+            first_visible: 0,
+            viewport_top: 0.0,
+            viewport_height: 0.0,
+            drag_grab_offset: None,
+            variable: false,
+            heights: RowHeights::default(),
+            pending_scroll: 0.0,
+        }
+    }
+
+    /// Height of row `index`, measured or nominal
+    pub fn row_height_at(&self, index: usize) -> f32 {
+        self.heights.get(index, self.row_height)
+    }
+
+    /// Number of rows that fit fully in the viewport (at least 1)
+    pub fn page_rows(&self) -> usize {
+        if !self.variable {
+            return ((self.viewport_height / self.row_height).floor() as usize).max(1);
+        }
+        let mut used = 0.0;
+        let mut rows = 0;
+        for index in self.first_visible..self.item_count {
+            used += self.row_height_at(index);
+            if used > self.viewport_height {
+                break;
+            }
+            rows += 1;
+        }
+        rows.max(1)
+    }
+
+    /// Index of the first visible row when scrolled to the very bottom
+    ///
+    /// A partially visible row counts as visible, matching what
+    /// `uniform_list` reports as the first row of its visible range.
+    /// Variable-height rows are laid out from the top, so there the last row
+    /// must fit fully instead.
+    pub fn max_start_index(&self) -> usize {
+        if !self.variable {
+            let visible = (self.viewport_height / self.row_height).ceil() as usize;
+            return self.item_count.saturating_sub(visible);
+        }
+        let mut used = 0.0;
+        let mut start = self.item_count;
+        while start > 0 {
+            let height = self.row_height_at(start - 1);
+            if used + height > self.viewport_height {
+                break;
+            }
+            used += height;
+            start -= 1;
+        }
+        // A row taller than the viewport is still shown from its top
+        start.min(self.item_count.saturating_sub(1))
+    }
+
+    pub fn content_height(&self) -> f32 {
+        self.item_count as f32 * self.row_height + self.heights.extra()
+    }
+
+    /// Rows laid out by a variable-height list: from the first visible row
+    /// until the viewport is covered
+    pub fn visible_range(&self) -> Range<usize> {
+        let mut used = 0.0;
+        let mut end = self.first_visible;
+        while end < self.item_count && used < self.viewport_height {
+            used += self.row_height_at(end);
+            end += 1;
+        }
+        self.first_visible..end
+    }
+
+    /// Scroll a variable-height list by a wheel distance in pixels (positive
+    /// scrolls down); returns whether the first visible row changed
+    pub fn scroll_by(&mut self, delta: f32) -> bool {
+        let start = self.first_visible;
+        let max_start = self.max_start_index();
+        self.pending_scroll += delta;
+        while self.pending_scroll > 0.0 && self.first_visible < max_start {
+            let height = self.row_height_at(self.first_visible);
+            if self.pending_scroll < height {
+                break;
+            }
+            self.pending_scroll -= height;
+            self.first_visible += 1;
+        }
+        while self.pending_scroll < 0.0 && self.first_visible > 0 {
+            let height = self.row_height_at(self.first_visible - 1);
+            if -self.pending_scroll < height {
+                break;
+            }
+            self.pending_scroll += height;
+            self.first_visible -= 1;
+        }
+        // Nothing left to scroll towards: don't bank the distance
+        if (self.pending_scroll > 0.0 && self.first_visible >= max_start)
+            || (self.pending_scroll < 0.0 && self.first_visible == 0)
+        {
+            self.pending_scroll = 0.0;
+        }
+        self.first_visible != start
+    }
+
+    /// Thumb geometry, or `None` when all rows fit in the viewport
+    pub fn scrollbar(&self) -> Option<ScrollbarGeometry> {
+        let track_height = self.viewport_height;
+        let content_height = self.content_height();
+        if track_height <= 0.0 || content_height <= track_height {
+            return None;
+        }
+
+        let thumb_height = (track_height / content_height * track_height)
+            .max(MIN_THUMB_HEIGHT)
+            .min(track_height);
+        let free_track = track_height - thumb_height;
+        let max_start = self.max_start_index();
+        let progress = if max_start == 0 {
+            0.0
+        } else {
+            (self.first_visible as f32 / max_start as f32).clamp(0.0, 1.0)
+        };
+
+        Some(ScrollbarGeometry {
+            thumb_top: progress * free_track,
+            thumb_height,
+            track_height,
+        })
+    }
+
+    /// Row index to scroll to so the thumb top lands at `thumb_top`
+    pub fn index_for_thumb_top(&self, thumb_top: f32) -> usize {
+        let Some(geometry) = self.scrollbar() else {
+            return 0;
+        };
+        let free_track = geometry.track_height - geometry.thumb_height;
+        if free_track <= 0.0 {
+            return 0;
+        }
+        let progress = (thumb_top / free_track).clamp(0.0, 1.0);
+        (progress * self.max_start_index() as f32).round() as usize
+    }
+
+    /// Row index for a click on the track: centre the thumb on the click
+    pub fn index_for_track_click(&self, track_y: f32) -> usize {
+        let thumb_height = self.scrollbar().map(|g| g.thumb_height).unwrap_or(0.0);
+        self.index_for_thumb_top(track_y - thumb_height / 2.0)
+    }
+
+    /// Row index for a keyboard navigation command
+    pub fn index_for_key(&self, key: ListKey) -> usize {
+        let page = self.page_rows();
+        let target = match key {
+            ListKey::LineUp => self.first_visible.saturating_sub(1),
+            ListKey::LineDown => self.first_visible + 1,
+            ListKey::PageUp => self.first_visible.saturating_sub(page),
+            ListKey::PageDown => self.first_visible + page,
+            ListKey::Home => 0,
+            ListKey::End => self.max_start_index(),
+        };
+        target.min(self.max_start_index())
+    }
+
+    /// Convert a window y coordinate into a track-relative one
+    pub fn track_y(&self, window_y: f32) -> f32 {
+        (window_y - self.viewport_top).clamp(0.0, self.viewport_height)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn metrics(item_count: usize, viewport_height: f32) -> VirtualListMetrics {
+        VirtualListMetrics {
+            item_count,
+            viewport_height,
+            ..VirtualListMetrics::new(DEFAULT_ROW_HEIGHT)
+        }
+    }
+
+    #[test]
+    fn test_no_scrollbar_when_content_fits() {
+        assert_eq!(metrics(10, 500.0).scrollbar(), None);
+        assert_eq!(metrics(0, 500.0).scrollbar(), None);
+    }
+
+    #[test]
+    fn test_max_start_counts_partial_rows() {
+        // 850 / 22 = 38.6 rows -> 39 rows are (partially) visible at the bottom
+        let m = metrics(1000, 850.0);
+        assert_eq!(m.max_start_index(), 961);
+        assert_eq!(m.page_rows(), 38);
+    }
+
+    #[test]
+    fn test_thumb_reaches_track_end_on_last_page() {
+        let mut m = metrics(1000, 850.0);
+        m.first_visible = m.max_start_index();
+        let g = m.scrollbar().unwrap();
+        assert!((g.thumb_top + g.thumb_height - g.track_height).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_thumb_has_minimum_height() {
+        let g = metrics(1_000_000, 500.0).scrollbar().unwrap();
+        assert_eq!(g.thumb_height, MIN_THUMB_HEIGHT);
+    }
+
+    #[test]
+    fn test_thumb_top_round_trips_to_index() {
+        let mut m = metrics(5000, 600.0);
+        for index in [0, 1, 123, 2500, m.max_start_index()] {
+            m.first_visible = index;
+            let top = m.scrollbar().unwrap().thumb_top;
+            let back = m.index_for_thumb_top(top);
+            assert!(back.abs_diff(index) <= 1, "{index} -> {top} -> {back}");
+        }
+    }
+
+    #[test]
+    fn test_track_click_at_ends() {
+        let m = metrics(5000, 600.0);
+        assert_eq!(m.index_for_track_click(0.0), 0);
+        assert_eq!(m.index_for_track_click(600.0), m.max_start_index());
+    }
+
+    #[test]
+    fn test_track_y_uses_measured_viewport() {
+        let m = VirtualListMetrics {
+            viewport_top: 84.0,
+            ..metrics(5000, 600.0)
+        };
+        assert_eq!(m.track_y(84.0), 0.0);
+        assert_eq!(m.track_y(384.0), 300.0);
+        assert_eq!(m.track_y(10.0), 0.0);
+        assert_eq!(m.track_y(2000.0), 600.0);
+    }
+
+    #[test]
+    fn test_keyboard_navigation_is_clamped() {
+        let mut m = metrics(100, 220.0);
+        assert_eq!(m.index_for_key(ListKey::LineUp), 0);
+        assert_eq!(m.index_for_key(ListKey::PageDown), 10);
+        assert_eq!(m.index_for_key(ListKey::End), 90);
+        m.first_visible = 85;
+        assert_eq!(m.index_for_key(ListKey::PageDown), 90);
+        assert_eq!(m.index_for_key(ListKey::PageUp), 75);
+        assert_eq!(m.index_for_key(ListKey::Home), 0);
+    }
+
+    #[test]
+    fn test_variable_heights() {
+        let mut m = VirtualListMetrics {
+            variable: true,
+            ..metrics(100, 110.0)
+        };
+        // Unmeasured rows count as one row high
+        assert_eq!(m.visible_range(), 0..5);
+        assert_eq!(m.max_start_index(), 95);
+
+        // Rows 1 and 98 wrap onto three lines
+        assert!(m.heights.set(1, 66.0, DEFAULT_ROW_HEIGHT));
+        assert!(!m.heights.set(1, 66.0, DEFAULT_ROW_HEIGHT));
+        m.heights.set(98, 66.0, DEFAULT_ROW_HEIGHT);
+        assert_eq!(m.content_height(), 100.0 * 22.0 + 88.0);
+        assert_eq!(m.visible_range(), 0..3);
+        assert_eq!(m.page_rows(), 3);
+        // 22 + 66 + 22 fit exactly; one more row would not
+        assert_eq!(m.max_start_index(), 97);
+
+        // Wheel distance is kept until it covers a whole row
+        assert!(!m.scroll_by(10.0));
+        assert!(m.scroll_by(12.0));
+        assert_eq!(m.first_visible, 1);
+        assert!(!m.scroll_by(60.0));
+        assert!(m.scroll_by(10.0));
+        assert_eq!(m.first_visible, 2);
+        assert!(m.scroll_by(-100.0));
+        assert_eq!(m.first_visible, 0);
+        assert_eq!(m.pending_scroll, 0.0);
+        assert!(m.scroll_by(10_000.0));
+        assert_eq!(m.first_visible, m.max_start_index());
+
+        // Back to one line
+        m.heights.set(1, 22.0, DEFAULT_ROW_HEIGHT);
+        m.heights.set(98, 22.0, DEFAULT_ROW_HEIGHT);
+        assert_eq!(m.heights, RowHeights::default());
+    }
+
+    #[test]
+    fn test_list_key_from_keystroke() {
+        assert_eq!(ListKey::from_keystroke("pagedown"), Some(ListKey::PageDown));
+        assert_eq!(ListKey::from_keystroke("home"), Some(ListKey::Home));
+        assert_eq!(ListKey::from_keystroke("a"), None);
+    }
+}
@@ -6,25 +6,26 @@
 // pub mod button;
 // pub mod card;
 pub mod divider;
+pub mod dropdown;
+pub mod list_metrics;
 // pub mod label;
 // pub mod panel;
-pub mod enhanced_text_input;
-pub mod ime_text_input;
-pub mod simple_text_input; // New simplified version
+pub mod modal;
 pub mod text_input;
-pub mod zed_style_text_input;
+pub mod tooltip;
+pub mod virtual_list;
 
 // Re-export for convenience
 // pub use button::{Button, ButtonColor};
 // pub use card::{Card, CardStyle};
 pub use divider::{Divider, DividerOrientation};
+pub use dropdown::Dropdown;
 // pub use label::{Label, LabelColor, LabelSize};
 // pub use panel::{Panel, PanelStyle};
-pub use enhanced_text_input::{EnhancedTextInputBuilder, EnhancedTextInputState, TextSelection};
-pub use ime_text_input::ImeTextInputState;
-pub use simple_text_input::SimpleTextInputBuilder; // Simple version, no internal event handling
-pub use text_input::{TextInputBuilder, TextInputValidation};
-pub use zed_style_text_input::{ZedStyleTextInputBuilder, ZedStyleTextInputState};
+pub use modal::{Modal, ModalStack};
+pub use text_input::{CHANNEL_ID_VALIDATION, NumberValidation, TextInputValidation, parse_payload};
+pub use tooltip::TextTooltip;
+pub use virtual_list::{VirtualList, VirtualListHandle};
 
 use crate::CanViewApp;
 use crate::app::AppView;
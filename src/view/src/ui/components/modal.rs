@@ -0,0 +1,313 @@
+//! Modal dialog layer
+//!
+//! [`ModalStack`] records which dialogs are open and in what order; the
+//! topmost entry is the only one that receives keyboard input. [`Modal`]
+//! renders a single layer: a dimmed backdrop covering the window and a
+//! centered panel with a title bar, body and optional footer.
+//!
+//! The panel tracks the focus handle it is given and stops propagation of
+//! every key event, so keystrokes never leak to the views underneath
+//! (focus trap). `escape`, a click on the backdrop and the × button all call
+//! the `on_close` handler.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! // In render, for each open modal (bottom to top)
+//! Modal::new("about-modal")
+//!     .title("About")
+//!     .focus(&focus_handle) // only for the topmost modal
+//!     .child(div().child("..."))
+//!     .on_close(move |_window, cx| { /* pop the stack */ })
+//!     .build()
+//! ```
+
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+use std::rc::Rc;
+
+type CloseHandler = Rc<dyn Fn(&mut Window, &mut App)>;
+
+/// Ordered set of open modals, bottom first
+#[derive(Debug, Clone)]
+pub struct ModalStack<K> {
+    layers: Vec<K>,
+}
+
+impl<K: Clone + PartialEq> ModalStack<K> {
+    pub fn new() -> Self {
+        Self { layers: Vec::new() }
+    }
+
+    /// Open `modal` on top; an already open modal is raised instead of duplicated
+    pub fn push(&mut self, modal: K) {
+        self.layers.retain(|open| *open != modal);
+        self.layers.push(modal);
+    }
+
+    /// Close the topmost modal
+    pub fn pop(&mut self) -> Option<K> {
+        self.layers.pop()
+    }
+
+    /// Close `modal` wherever it is in the stack
+    pub fn close(&mut self, modal: &K) -> bool {
+        let before = self.layers.len();
+        self.layers.retain(|open| open != modal);
+        self.layers.len() != before
+    }
+
+    /// The modal currently receiving input
+    pub fn top(&self) -> Option<&K> {
+        self.layers.last()
+    }
+
+    pub fn is_open(&self, modal: &K) -> bool {
+        self.layers.contains(modal)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.layers.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.layers.len()
+    }
+
+    /// Open modals from bottom to top (render order)
+    pub fn iter(&self) -> impl Iterator<Item = &K> {
+        self.layers.iter()
+    }
+}
+
+impl<K: Clone + PartialEq> Default for ModalStack<K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Builder for one modal layer
+pub struct Modal {
+    id: SharedString,
+    title: SharedString,
+    width: Pixels,
+    body: Vec<AnyElement>,
+    footer: Option<AnyElement>,
+    focus: Option<FocusHandle>,
+    on_close: Option<CloseHandler>,
+}
+
+impl FluentBuilder for Modal {}
+
+impl Modal {
+    pub fn new(id: impl Into<SharedString>) -> Self {
+        Self {
+            id: id.into(),
+            title: SharedString::default(),
+            width: px(420.),
+            body: Vec::new(),
+            footer: None,
+            focus: None,
+            on_close: None,
+        }
+    }
+
+    pub fn title(mut self, title: impl Into<SharedString>) -> Self {
+        self.title = title.into();
+        self
+    }
+
+    pub fn width(mut self, width: Pixels) -> Self {
+        self.width = width;
+        self
+    }
+
+    /// Append an element to the body
+    pub fn child(mut self, child: impl IntoElement) -> Self {
+        self.body.push(child.into_any_element());
+        self
+    }
+
+    /// Append elements to the body
+    pub fn children(mut self, children: impl IntoIterator<Item = impl IntoElement>) -> Self {
+        self.body
+            .extend(children.into_iter().map(IntoElement::into_any_element));
+        self
+    }
+
+    /// Row shown under the body, usually the action buttons
+    pub fn footer(mut self, footer: impl IntoElement) -> Self {
+        self.footer = Some(footer.into_any_element());
+        self
+    }
+
+    /// Make this the active layer: it tracks `focus` and traps key events
+    pub fn focus(mut self, focus: &FocusHandle) -> Self {
+        self.focus = Some(focus.clone());
+        self
+    }
+
+    /// Called on escape, backdrop click or the close button
+    pub fn on_close(mut self, handler: impl Fn(&mut Window, &mut App) + 'static) -> Self {
+        self.on_close = Some(Rc::new(handler));
+        self
+    }
+
+    pub fn build(self) -> impl IntoElement {
+        let on_close = self.on_close;
+
+        let panel = div()
+            .id(SharedString::from(format!("{}-panel", self.id)))
+            .w(self.width)
+            .max_h(relative(0.8))
+            .flex()
+            .flex_col()
+            .bg(rgb(0x181818))
+            .border_1()
+            .border_color(rgb(0x2a2a2a))
+            .rounded(px(6.))
+            .shadow_lg()
+            .overflow_hidden()
+            // Clicks inside the panel must not reach the backdrop
+            .on_mouse_down(MouseButton::Left, |_event, _window, cx| {
+                cx.stop_propagation();
+            })
+            .when_some(self.focus, |el, focus| {
+                let on_close = on_close.clone();
+                el.track_focus(&focus)
+                    .on_key_down(move |event, window, cx| {
+                        if format!("{}", event.keystroke) == "escape" {
+                            if let Some(on_close) = &on_close {
+                                on_close(window, cx);
+                            }
+                        }
+                        // Focus trap: nothing typed here reaches the views underneath
+                        cx.stop_propagation();
+                    })
+            })
+            .child(
+                // Title bar
+                div()
+                    .h(px(36.))
+                    .px_4()
+                    .flex()
+                    .items_center()
+                    .justify_between()
+                    .border_b_1()
+                    .border_color(rgb(0x2a2a2a))
+                    .child(
+                        div()
+                            .text_sm()
+                            .font_weight(FontWeight::SEMIBOLD)
+                            .text_color(rgb(0xcdd6f4))
+                            .child(self.title),
+                    )
+                    .when_some(on_close.clone(), |el, on_close| {
+                        el.child(
+                            div()
+                                .id(SharedString::from(format!("{}-close", self.id)))
+                                .w(px(22.))
+                                .h(px(22.))
+                                .flex()
+                                .items_center()
+                                .justify_center()
+                                .rounded(px(3.))
+                                .cursor_pointer()
+                                .text_color(rgb(0x646473))
+                                .hover(|style| style.bg(rgb(0x2a2a2a)).text_color(rgb(0xcdd6f4)))
+                                .on_mouse_down(MouseButton::Left, move |_event, window, cx| {
+                                    cx.stop_propagation();
+                                    on_close(window, cx);
+                                })
+                                .child("×"),
+                        )
+                    }),
+            )
+            .child(
+                div()
+                    .id(SharedString::from(format!("{}-body", self.id)))
+                    .flex_1()
+                    .p_4()
+                    .flex()
+                    .flex_col()
+                    .gap_3()
+                    .overflow_y_scroll()
+                    .text_sm()
+                    .text_color(rgb(0xcdd6f4))
+                    .children(self.body),
+            )
+            .when_some(self.footer, |el, footer| {
+                el.child(
+                    div()
+                        .px_4()
+                        .py_3()
+                        .flex()
+                        .justify_end()
+                        .gap_2()
+                        .border_t_1()
+                        .border_color(rgb(0x2a2a2a))
+                        .child(footer),
+                )
+            });
+
+        div()
+            .id(self.id)
+            .absolute()
+            .inset_0()
+            .flex()
+            .items_center()
+            .justify_center()
+            .bg(rgba(0x00000099))
+            .occlude() // Block hover, clicks and scrolling of everything underneath
+            .when_some(on_close, |el, on_close| {
+                el.on_mouse_down(MouseButton::Left, move |_event, window, cx| {
+                    cx.stop_propagation();
+                    on_close(window, cx);
+                })
+            })
+            .child(panel)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_modal_stack_push_and_pop() {
+        let mut stack = ModalStack::new();
+        assert!(stack.is_empty());
+        stack.push("preferences");
+        stack.push("about");
+        assert_eq!(stack.len(), 2);
+        assert_eq!(stack.top(), Some(&"about"));
+        assert_eq!(stack.pop(), Some("about"));
+        assert_eq!(stack.top(), Some(&"preferences"));
+    }
+
+    #[test]
+    fn test_modal_stack_push_raises_open_modal() {
+        let mut stack = ModalStack::new();
+        stack.push("preferences");
+        stack.push("about");
+        stack.push("preferences");
+        assert_eq!(stack.len(), 2);
+        assert_eq!(
+            stack.iter().copied().collect::<Vec<_>>(),
+            vec!["about", "preferences"]
+        );
+    }
+
+    #[test]
+    fn test_modal_stack_close_from_middle() {
+        let mut stack = ModalStack::new();
+        stack.push("export");
+        stack.push("preferences");
+        stack.push("about");
+        assert!(stack.close(&"preferences"));
+        assert!(!stack.close(&"preferences"));
+        assert!(!stack.is_open(&"preferences"));
+        assert_eq!(stack.top(), Some(&"about"));
+        assert_eq!(stack.len(), 2);
+    }
+}
@@ -0,0 +1,45 @@
+//! Plain text tooltip
+//!
+//! gpui tooltips are views; [`TextTooltip`] is the smallest one, showing a
+//! few lines of text in the same style as the dropdowns.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! div()
+//!     .id("cell")
+//!     .tooltip(move |_window, cx| cx.new(|_| TextTooltip::new(lines.clone())).into())
+//! ```
+
+use gpui::*;
+
+pub struct TextTooltip {
+    lines: Vec<SharedString>,
+}
+
+impl TextTooltip {
+    pub fn new<S: Into<SharedString>>(lines: impl IntoIterator<Item = S>) -> Self {
+        Self {
+            lines: lines.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl Render for TextTooltip {
+    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+        div()
+            .px_2()
+            .py_1()
+            .flex()
+            .flex_col()
+            .gap_0p5()
+            .bg(rgb(0x1f1f1f))
+            .border_1()
+            .border_color(rgb(0x2a2a2a))
+            .rounded(px(4.))
+            .shadow_lg()
+            .text_xs()
+            .text_color(rgb(0xcdd6f4))
+            .children(self.lines.iter().cloned())
+    }
+}
@@ -0,0 +1,396 @@
+//! Virtual list component
+//!
+//! A `uniform_list` wrapper with its own scrollbar, hit-testing and keyboard
+//! navigation. All scroll math lives in [`VirtualListMetrics`]
+//! (`list_metrics.rs`), which is plain data and can be tested without a window.
+//!
+//! Lists built with [`VirtualList::variable_heights`] let rows grow past the
+//! row height (wrapped text). Those rows are laid out by the list itself from
+//! the first visible row down; each row's height is measured when it is
+//! painted and kept in [`RowHeights`] for the scroll math.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! // In state
+//! let handle = VirtualListHandle::new(22.0);
+//!
+//! // In render
+//! VirtualList::new("message-list", &handle, rows.len(), move |range, _window, _cx| {
+//!     range.map(|ix| render_row(&rows[ix])).collect()
+//! })
+//! .build()
+//! ```
+
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+use std::cell::RefCell;
+use std::ops::Range;
+use std::rc::Rc;
+
+pub use super::list_metrics::*;
+
+/// Shared handle to a virtual list's scroll state
+///
+/// Cheap to clone, like [`UniformListScrollHandle`], so it can be stored on
+/// the owning view and captured by event closures.
+#[derive(Clone)]
+pub struct VirtualListHandle {
+    scroll: UniformListScrollHandle,
+    metrics: Rc<RefCell<VirtualListMetrics>>,
+}
+
+impl VirtualListHandle {
+    pub fn new(row_height: f32) -> Self {
+        Self {
+            scroll: UniformListScrollHandle::new(),
+            metrics: Rc::new(RefCell::new(VirtualListMetrics::new(row_height))),
+        }
+    }
+
+    /// Snapshot of the current metrics
+    pub fn metrics(&self) -> VirtualListMetrics {
+        self.metrics.borrow().clone()
+    }
+
+    pub fn row_height(&self) -> f32 {
+        self.metrics.borrow().row_height
+    }
+
+    /// Change the row height; rows rendered by the caller must use the same value
+    /// (or at least it, with variable heights)
+    pub fn set_row_height(&self, row_height: f32) {
+        let mut metrics = self.metrics.borrow_mut();
+        if metrics.row_height != row_height {
+            metrics.row_height = row_height;
+            metrics.heights.clear();
+        }
+    }
+
+    /// Forget the measured row heights, e.g. after the rows were rewrapped
+    pub fn clear_heights(&self) {
+        self.metrics.borrow_mut().heights.clear();
+    }
+
+    pub fn first_visible(&self) -> usize {
+        self.metrics.borrow().first_visible
+    }
+
+    pub fn is_dragging(&self) -> bool {
+        self.metrics.borrow().drag_grab_offset.is_some()
+    }
+
+    /// Scroll so that `index` is the first visible row (clamped to the end)
+    pub fn scroll_to(&self, index: usize) {
+        let (count, max_start, variable) = {
+            let mut metrics = self.metrics.borrow_mut();
+            let max_start = metrics.max_start_index();
+            metrics.first_visible = index.min(max_start);
+            metrics.pending_scroll = 0.0;
+            (metrics.item_count, max_start, metrics.variable)
+        };
+        // Variable-height rows are laid out from `first_visible` directly
+        if count == 0 || variable {
+            return;
+        }
+        if index >= max_start {
+            // Pin the last row to the bottom edge instead of relying on clamping
+            self.scroll
+                .scroll_to_item_strict(count - 1, ScrollStrategy::Bottom);
+        } else {
+            self.scroll
+                .scroll_to_item_strict(index, ScrollStrategy::Top);
+        }
+    }
+
+    /// Make `index` visible, scrolling only if it is outside the viewport
+    pub fn reveal(&self, index: usize) {
+        let (first, page) = {
+            let metrics = self.metrics.borrow();
+            (metrics.first_visible, metrics.page_rows())
+        };
+        if index < first {
+            self.scroll_to(index);
+        } else if index >= first + page {
+            self.scroll_to(index + 1 - page);
+        }
+    }
+
+    /// Handle a navigation keystroke; returns `true` if it was consumed
+    pub fn handle_key(&self, keystroke: &str) -> bool {
+        let Some(key) = ListKey::from_keystroke(keystroke) else {
+            return false;
+        };
+        let target = self.metrics.borrow().index_for_key(key);
+        self.scroll_to(target);
+        true
+    }
+
+    /// Start a thumb drag at a window y coordinate
+    ///
+    /// Clicking the track outside the thumb first jumps the thumb under the
+    /// pointer, then drags from there.
+    pub fn begin_drag(&self, window_y: f32) {
+        let track_y = self.metrics.borrow().track_y(window_y);
+        let geometry = self.metrics.borrow().scrollbar();
+        let Some(geometry) = geometry else {
+            return;
+        };
+
+        let grab_offset = if geometry.contains(track_y) {
+            track_y - geometry.thumb_top
+        } else {
+            let target = self.metrics.borrow().index_for_track_click(track_y);
+            self.scroll_to(target);
+            geometry.thumb_height / 2.0
+        };
+        self.metrics.borrow_mut().drag_grab_offset = Some(grab_offset);
+    }
+
+    /// Continue a thumb drag; returns `true` if the list scrolled
+    pub fn update_drag(&self, window_y: f32) -> bool {
+        let target = {
+            let metrics = self.metrics.borrow();
+            let Some(grab_offset) = metrics.drag_grab_offset else {
+                return false;
+            };
+            metrics.index_for_thumb_top(metrics.track_y(window_y) - grab_offset)
+        };
+        if target == self.first_visible() {
+            return false;
+        }
+        self.scroll_to(target);
+        true
+    }
+
+    pub fn end_drag(&self) {
+        self.metrics.borrow_mut().drag_grab_offset = None;
+    }
+
+    fn set_item_count(&self, item_count: usize) {
+        let mut metrics = self.metrics.borrow_mut();
+        // Rows appended (live capture) keep their heights; anything else renumbers them
+        if item_count < metrics.item_count {
+            metrics.heights.clear();
+        }
+        metrics.item_count = item_count;
+        metrics.first_visible = metrics.first_visible.min(metrics.max_start_index());
+    }
+
+    fn set_variable(&self, variable: bool) {
+        let mut metrics = self.metrics.borrow_mut();
+        if metrics.variable != variable {
+            metrics.variable = variable;
+            metrics.heights.clear();
+        }
+    }
+
+    /// Record the painted height of row `index`; returns whether it changed
+    fn measure(&self, index: usize, height: f32) -> bool {
+        let mut metrics = self.metrics.borrow_mut();
+        let row_height = metrics.row_height;
+        metrics.heights.set(index, height, row_height)
+    }
+
+    /// Returns whether the viewport height changed
+    fn set_viewport(&self, bounds: Bounds<Pixels>) -> bool {
+        let mut metrics = self.metrics.borrow_mut();
+        let height = f32::from(bounds.size.height);
+        let changed = metrics.viewport_height != height;
+        metrics.viewport_top = f32::from(bounds.origin.y);
+        metrics.viewport_height = height;
+        changed
+    }
+
+    fn set_first_visible(&self, first_visible: usize) {
+        self.metrics.borrow_mut().first_visible = first_visible;
+    }
+}
+
+/// Builder for a virtualised, uniformly sized list with a scrollbar
+pub struct VirtualList<F>
+where
+    F: Fn(Range<usize>, &mut Window, &mut App) -> Vec<AnyElement> + 'static,
+{
+    id: SharedString,
+    handle: VirtualListHandle,
+    item_count: usize,
+    variable_heights: bool,
+    render_rows: F,
+}
+
+impl<F> VirtualList<F>
+where
+    F: Fn(Range<usize>, &mut Window, &mut App) -> Vec<AnyElement> + 'static,
+{
+    /// Create a list of `item_count` rows rendered lazily by `render_rows`
+    pub fn new(
+        id: impl Into<SharedString>,
+        handle: &VirtualListHandle,
+        item_count: usize,
+        render_rows: F,
+    ) -> Self {
+        Self {
+            id: id.into(),
+            handle: handle.clone(),
+            item_count,
+            variable_heights: false,
+            render_rows,
+        }
+    }
+
+    /// Let rows be taller than the row height; they are measured as they are painted
+    pub fn variable_heights(mut self, variable: bool) -> Self {
+        self.variable_heights = variable;
+        self
+    }
+
+    /// Build the list element
+    pub fn build(self) -> impl IntoElement {
+        let handle = self.handle;
+        let variable = self.variable_heights;
+        handle.set_variable(variable);
+        handle.set_item_count(self.item_count);
+        let geometry = handle.metrics.borrow().scrollbar();
+        let render_rows = self.render_rows;
+
+        div()
+            .size_full()
+            .relative()
+            .flex()
+            .flex_col()
+            .child(
+                // Measure the viewport every frame for hit-testing
+                canvas(
+                    {
+                        let handle = handle.clone();
+                        move |bounds, window, _cx| {
+                            // Variable rows were laid out for the old height
+                            if handle.set_viewport(bounds) && variable {
+                                window.request_animation_frame();
+                            }
+                        }
+                    },
+                    |_, _, _, _| {},
+                )
+                .absolute()
+                .size_full(),
+            )
+            .child(if variable {
+                VariableRows {
+                    handle: handle.clone(),
+                    render_rows,
+                }
+                .into_any_element()
+            } else {
+                uniform_list(self.id, self.item_count, {
+                    let handle = handle.clone();
+                    move |range: Range<usize>, window: &mut Window, cx: &mut App| {
+                        handle.set_first_visible(range.start);
+                        render_rows(range, window, cx)
+                    }
+                })
+                .track_scroll(&handle.scroll)
+                .flex_1()
+                .pr(px(SCROLLBAR_WIDTH))
+                .into_any_element()
+            })
+            .on_mouse_move({
+                let handle = handle.clone();
+                move |event, window, _cx| {
+                    if event.pressed_button != Some(MouseButton::Left) {
+                        handle.end_drag();
+                    } else if handle.update_drag(f32::from(event.position.y)) {
+                        window.refresh();
+                    }
+                }
+            })
+            .on_mouse_up(MouseButton::Left, {
+                let handle = handle.clone();
+                move |_event, _window, _cx| handle.end_drag()
+            })
+            .child(
+                div()
+                    .absolute()
+                    .right_0()
+                    .top_0()
+                    .bottom_0()
+                    .w(px(SCROLLBAR_WIDTH))
+                    .bg(rgb(0x1a1a1a))
+                    .on_mouse_down(MouseButton::Left, {
+                        let handle = handle.clone();
+                        move |event, window, cx| {
+                            cx.stop_propagation();
+                            handle.begin_drag(f32::from(event.position.y));
+                            window.refresh();
+                        }
+                    })
+                    .when_some(geometry, |track, geometry| {
+                        track.child(
+                            div()
+                                .absolute()
+                                .left(px(2.))
+                                .w(px(8.))
+                                .top(px(geometry.thumb_top))
+                                .h(px(geometry.thumb_height))
+                                .bg(rgb(0x6a6a6a))
+                                .rounded(px(4.))
+                                .hover(|style| style.bg(rgb(0x7a7a7a)))
+                                .cursor_grab(),
+                        )
+                    }),
+            )
+    }
+}
+
+/// Rows of a variable-height list, laid out top-down from the first visible row
+#[derive(IntoElement)]
+struct VariableRows<F>
+where
+    F: Fn(Range<usize>, &mut Window, &mut App) -> Vec<AnyElement> + 'static,
+{
+    handle: VirtualListHandle,
+    render_rows: F,
+}
+
+impl<F> RenderOnce for VariableRows<F>
+where
+    F: Fn(Range<usize>, &mut Window, &mut App) -> Vec<AnyElement> + 'static,
+{
+    fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let handle = self.handle;
+        let range = handle.metrics.borrow().visible_range();
+        let rows = (self.render_rows)(range.clone(), window, cx);
+
+        div()
+            .flex_1()
+            .flex()
+            .flex_col()
+            .overflow_hidden()
+            .pr(px(SCROLLBAR_WIDTH))
+            .on_scroll_wheel({
+                let handle = handle.clone();
+                move |event, window, cx| {
+                    let delta = event.delta.pixel_delta(window.line_height());
+                    if handle.metrics.borrow_mut().scroll_by(-f32::from(delta.y)) {
+                        cx.stop_propagation();
+                        window.refresh();
+                    }
+                }
+            })
+            .children(rows.into_iter().zip(range).map(|(row, index)| {
+                let handle = handle.clone();
+                // A new height moves the rows below and the scrollbar
+                let measure = move |bounds: Bounds<Pixels>, window: &mut Window, _: &mut App| {
+                    if handle.measure(index, f32::from(bounds.size.height)) {
+                        window.request_animation_frame();
+                    }
+                };
+                div()
+                    .relative()
+                    .flex_shrink_0()
+                    .child(row)
+                    .child(canvas(measure, |_, _, _, _| {}).absolute().size_full())
+            }))
+    }
+}
@@ -7,6 +7,7 @@
 
 use crate::app::LibraryDialogType;
 use crate::models::{ChannelDatabase, ChannelMapping, LibraryVersion, SignalLibrary};
+use crate::ui::components::CHANNEL_ID_VALIDATION;
 use gpui::prelude::*;
 use gpui::*;
 use gpui_component::input::{Input, InputState};
@@ -26,7 +27,6 @@ pub fn render_library_management_view(
     show_add_version_input: bool,
     new_library_name: &str,
     new_version_name: &str,
-    focused_input: &Option<String>,
     library_cursor_pos: usize,
     version_cursor_pos: usize,
     library_name_input: Option<&gpui::Entity<gpui_component::input::InputState>>,
@@ -52,7 +52,6 @@ pub fn render_library_management_view(
             mappings,
             show_new_library_input,
             new_library_name,
-            focused_input,
             library_cursor_pos,
             library_name_input,
             cx,
@@ -73,7 +72,6 @@ pub fn render_library_management_view(
             mappings,
             show_add_version_input,
             new_version_name,
-            focused_input,
             version_cursor_pos,
             version_name_input,
             cx,
@@ -109,7 +107,6 @@ fn render_left_column(
     mappings: &[ChannelMapping],
     show_new_library_input: bool,
     new_library_name: &str,
-    focused_input: &Option<String>,
     cursor_pos: usize,
     library_name_input: Option<&gpui::Entity<gpui_component::input::InputState>>,
     cx: &mut Context<crate::CanViewApp>,
@@ -211,9 +208,11 @@ fn render_add_library_input_row(
         .on_key_down(cx.listener(|this, event: &KeyDownEvent, _window, cx| {
             if event.keystroke.key == "escape" {
                 // Close the input without saving
-                this.show_library_dialog = false;
-                this.library_name_input = None;
-                cx.notify();
+                this.library.update(cx, |library, cx| {
+                    library.show_library_dialog = false;
+                    library.library_name_input = None;
+                    cx.notify();
+                });
             }
         }))
         .child(if let Some(input) = library_name_input {
@@ -260,7 +259,9 @@ fn render_library_item(
         .on_mouse_down(
             gpui::MouseButton::Left,
             cx.listener(move |this, _event, _window, cx| {
-                this.selected_library_id = Some(library_id.clone());
+                this.library.update(cx, |library, _| {
+                    library.selected_library_id = Some(library_id.clone());
+                });
                 // Reset add channel input when switching libraries
                 this.hide_add_channel_input(cx);
                 cx.notify();
@@ -315,17 +316,19 @@ fn render_add_library_button(cx: &mut Context<crate::CanViewApp>) -> impl IntoEl
             gpui::MouseButton::Left,
             cx.listener(|this, _event, window, cx| {
                 // Initialize input state when showing input
-                if this.library_name_input.is_none() {
+                if this.library.read(cx).library_name_input.is_none() {
                     let input = cx
                         .new(|cx| InputState::new(window, cx).placeholder("Enter library name..."));
 
-                    this.library_name_input = Some(input.clone());
+                    this.library.update(cx, |library, _| {
+                        library.library_name_input = Some(input.clone())
+                    });
 
-                    // Subscribe to input events - store subscription to keep it alive
-                    let _subscription = cx.subscribe(
+                    // Subscribe to input events; the subscription lives as long as the input
+                    cx.subscribe(
                         &input,
                         |this: &mut crate::CanViewApp,
-                         _input_entity,
+                         input_entity,
                          event: &gpui_component::input::InputEvent,
                          cx| {
                             match event {
@@ -333,42 +336,40 @@ fn render_add_library_button(cx: &mut Context<crate::CanViewApp>) -> impl IntoEl
                                     eprintln!(
                                         "📥 Received PressEnter event from library_name_input"
                                     );
-                                    let name = this
-                                        .library_name_input
-                                        .as_ref()
-                                        .map(|i| i.read(cx).value().to_string())
-                                        .unwrap_or_default();
+                                    let name = input_entity.read(cx).value().to_string();
                                     eprintln!("⏎ Creating library: '{}'", name);
 
-                                    if !name.trim().is_empty() {
-                                        this.new_library_name = name.clone();
-                                        this.create_library(cx);
-                                    }
-
-                                    // Close the dialog
-                                    this.show_library_dialog = false;
-                                    this.library_name_input = None;
-                                    cx.notify();
+                                    // Invalid names keep the dialog open for correction
+                                    this.library.update(cx, |library, _| {
+                                        library.new_library_name = name;
+                                    });
+                                    this.create_library(cx);
+                                    this.library.update(cx, |library, _| {
+                                        if !library.show_library_dialog {
+                                            library.library_name_input = None;
+                                        }
+                                    });
                                 }
                                 gpui_component::input::InputEvent::Change => {
                                     // Sync text to state
-                                    let name = this
-                                        .library_name_input
-                                        .as_ref()
-                                        .map(|i| i.read(cx).value().to_string())
-                                        .unwrap_or_default();
-                                    this.new_library_name = name;
+                                    let name = input_entity.read(cx).value().to_string();
+                                    this.library.update(cx, |library, _| {
+                                        library.new_library_name = name;
+                                    });
                                 }
                                 _ => {}
                             }
                         },
-                    );
+                    )
+                    .detach();
 
                     eprintln!("✅ Created input and subscribed to events");
                 }
-                this.show_library_dialog = true;
-                this.library_dialog_type = LibraryDialogType::Create;
-                cx.notify();
+                this.library.update(cx, |library, cx| {
+                    library.show_library_dialog = true;
+                    library.library_dialog_type = LibraryDialogType::Create;
+                    cx.notify();
+                });
             }),
         )
         .child(
@@ -390,10 +391,9 @@ fn render_middle_column(
     libraries: &[SignalLibrary],
     selected_library_id: &Option<String>,
     selected_version_id: &Option<String>, // Add selected version ID parameter
-    _mappings: &[ChannelMapping],
+    mappings: &[ChannelMapping],
     show_add_version_input: bool,
     new_version_name: &str,
-    focused_input: &Option<String>,
     cursor_pos: usize,
     version_name_input: Option<&gpui::Entity<gpui_component::input::InputState>>,
     cx: &mut Context<crate::CanViewApp>,
@@ -460,8 +460,15 @@ fn render_middle_column(
                     for version in &library.versions {
                         let version_name = version.name.clone();
                         let is_selected = selected_version_id.as_ref() == Some(&version_name);
-                        list =
-                            list.child(render_version_item(version, version_name, is_selected, cx));
+                        let channels = library.version_channels(&version_name, mappings);
+                        list = list.child(render_version_item(
+                            version,
+                            library.id.clone(),
+                            version_name,
+                            channels,
+                            is_selected,
+                            cx,
+                        ));
                     }
                     // 添加内联版本输入行（当show_add_version_input为true时）
                     if show_add_version_input {
@@ -478,18 +485,31 @@ fn render_middle_column(
 }
 
 /// 渲染单个版本项 - Zed IDE 风格
+///
+/// 右键打开通道分配对话框；已激活的通道显示在名称下方。
 fn render_version_item(
     version: &LibraryVersion,
+    library_id: String,
     version_name: String,
+    channels: Vec<u16>,
     is_selected: bool,
     cx: &mut Context<crate::CanViewApp>,
 ) -> impl IntoElement {
     let stats = version.get_stats();
+    let right_click_name = version_name.clone();
+    let notes_library_id = library_id.clone();
+    let notes_version_name = version_name.clone();
+    // Author and checksum on one line, e.g. "alice · crc 1a2b3c4d"
+    let details: Vec<String> = (!version.author.is_empty())
+        .then(|| version.author.clone())
+        .into_iter()
+        .chain(version.checksum.iter().map(|crc| format!("crc {}", crc)))
+        .collect();
 
     div()
         .px_3()
         .py_1p5()
-        .h(px(32.))
+        .min_h(px(32.))
         .cursor_pointer()
         .when(is_selected, |el| {
             el.bg(rgb(0x252525))
@@ -503,26 +523,88 @@ fn render_version_item(
         .on_mouse_down(
             gpui::MouseButton::Left,
             cx.listener(move |this, _event, _window, cx| {
-                this.selected_version_id = Some(version_name.clone());
+                this.library.update(cx, |library, _| {
+                    library.selected_version_id = Some(version_name.clone());
+                });
                 this.status_msg = format!("Selected version: {}", version_name).into();
                 // Ensure add channel input is hidden when determining selection
                 this.hide_add_channel_input(cx);
                 cx.notify();
             }),
         )
+        .on_mouse_down(
+            gpui::MouseButton::Right,
+            cx.listener(move |this, _event, _window, cx| {
+                this.open_assign_version_dialog(library_id.clone(), right_click_name.clone(), cx);
+            }),
+        )
         .child(
-            div().flex().flex_col().gap_0().child(
-                div()
-                    .text_sm()
-                    .text_color(rgb(0xcdd6f4))
-                    .child(version.name.clone()),
-            ),
+            div()
+                .flex()
+                .flex_col()
+                .gap_0()
+                .child(
+                    div()
+                        .text_sm()
+                        .text_color(rgb(0xcdd6f4))
+                        .child(version.name.clone()),
+                )
+                .when(!channels.is_empty(), |el| {
+                    let channels: Vec<String> = channels.iter().map(u16::to_string).collect();
+                    el.child(
+                        div()
+                            .text_xs()
+                            .text_color(rgb(0xa6e3a1)) // Zed green
+                            .child(format!("CH {}", channels.join(", "))),
+                    )
+                })
+                .when(!version.notes.is_empty(), |el| {
+                    let first_line = version.notes.lines().next().unwrap_or_default();
+                    el.child(
+                        div()
+                            .text_xs()
+                            .text_color(rgb(0xa6adc8)) // Zed subtext
+                            .child(first_line.to_string()),
+                    )
+                })
+                .when(!details.is_empty(), |el| {
+                    el.child(
+                        div()
+                            .text_xs()
+                            .text_color(rgb(0x6c7086)) // Zed muted
+                            .child(details.join(" · ")),
+                    )
+                }),
         )
         .child(
             div()
-                .text_xs()
-                .text_color(rgb(0x6c7086)) // Zed muted
-                .child(format!("{}", stats.total_channels)),
+                .flex()
+                .items_center()
+                .gap_2()
+                .child(
+                    div()
+                        .text_xs()
+                        .text_color(rgb(0x6c7086))
+                        .hover(|style| style.text_color(rgb(0xcdd6f4)))
+                        .child("✎")
+                        .on_mouse_down(
+                            gpui::MouseButton::Left,
+                            cx.listener(move |this, _event, _window, cx| {
+                                cx.stop_propagation();
+                                this.open_version_notes_dialog(
+                                    notes_library_id.clone(),
+                                    notes_version_name.clone(),
+                                    cx,
+                                );
+                            }),
+                        ),
+                )
+                .child(
+                    div()
+                        .text_xs()
+                        .text_color(rgb(0x6c7086)) // Zed muted
+                        .child(format!("{}", stats.total_channels)),
+                ),
         )
 }
 
@@ -816,7 +898,7 @@ fn render_channel_item(
                 .on_mouse_down(
                     gpui::MouseButton::Left,
                     cx.listener(move |this, _event, _window, cx| {
-                        this.delete_channel(channel_id, cx);
+                        this.delete_channel(channel_type, channel_id, cx);
                     }),
                 ),
         )
@@ -882,6 +964,7 @@ fn render_channel_item_in_right(
         .to_string();
 
     let channel_id = channel_db.channel_id;
+    let channel_type = channel_db.channel_type;
 
     div()
         .px_3()
@@ -925,7 +1008,7 @@ fn render_channel_item_in_right(
                 .on_mouse_down(
                     gpui::MouseButton::Left,
                     cx.listener(move |this, _event, _window, cx| {
-                        this.delete_channel(channel_id, cx);
+                        this.delete_channel(channel_type, channel_id, cx);
                     }),
                 )
                 .child(
@@ -995,9 +1078,11 @@ fn render_add_channel_button_in_right(cx: &mut Context<crate::CanViewApp>) -> im
                                             .unwrap_or("Unknown");
 
                                         // 自动分配下一个可用的channel ID
+                                        let library = view.library.read(cx);
                                         let next_id =
-                                            if let Some(lib_id) = &view.selected_library_id {
-                                                view.library_manager
+                                            if let Some(lib_id) = &library.selected_library_id {
+                                                library
+                                                    .library_manager
                                                     .find_library(lib_id)
                                                     .and_then(|lib| lib.latest_version())
                                                     .map(|v| v.channel_databases.len() as u16 + 1)
@@ -1069,6 +1154,12 @@ fn render_add_channel_input_row_with_path(
     entity_clone: Option<gpui::Entity<crate::CanViewApp>>, // Pre-cloned entity
     cx: &mut Context<crate::CanViewApp>,
 ) -> impl IntoElement {
+    let channel_id_error = channel_id_input.is_some_and(|input| {
+        CHANNEL_ID_VALIDATION
+            .error(&input.read(cx).value())
+            .is_some()
+    });
+
     div()
         .px_3()
         .py_1()
@@ -1133,15 +1224,15 @@ fn render_add_channel_input_row_with_path(
             ),
         )
         .child(
-            // 通道ID输入
+            // 通道ID输入，无效时红框提示
             div()
                 .w(px(50.0))
                 .flex_shrink_0()
+                .when(channel_id_error, |el| {
+                    el.border_1().border_color(rgb(0xf87171)).rounded(px(3.0))
+                })
                 .child(if let Some(input) = channel_id_input {
-                    div()
-                        .flex_1()
-                        .child(Input::new(input))
-                        .into_any_element()
+                    div().flex_1().child(Input::new(input)).into_any_element()
                 } else {
                     div()
                         .text_color(gpui::rgb(0xffffff))
@@ -1156,10 +1247,7 @@ fn render_add_channel_input_row_with_path(
                 .w(px(120.0))
                 .flex_shrink_0()
                 .child(if let Some(input) = channel_name_input {
-                    div()
-                        .flex_1()
-                        .child(Input::new(input))
-                        .into_any_element()
+                    div().flex_1().child(Input::new(input)).into_any_element()
                 } else {
                     div()
                         .text_color(gpui::rgb(0xffffff))
@@ -1209,16 +1297,17 @@ fn render_add_channel_input_row_with_path(
                                 let this = this.clone();
                                 app.spawn(async move |cx| {
                                     let dialog = rfd::AsyncFileDialog::new();
-                                    
+
                                     let dialog = match channel_type {
-                                        crate::models::ChannelType::CAN => dialog.add_filter("DBC Files", &["dbc"]),
-                                        crate::models::ChannelType::LIN => dialog.add_filter("LDF Files", &["ldf"]),
+                                        crate::models::ChannelType::CAN => {
+                                            dialog.add_filter("DBC Files", &["dbc"])
+                                        }
+                                        crate::models::ChannelType::LIN => {
+                                            dialog.add_filter("LDF Files", &["ldf"])
+                                        }
                                     };
 
-                                    if let Some(file) = dialog
-                                        .pick_file()
-                                        .await
-                                    {
+                                    if let Some(file) = dialog.pick_file().await {
                                         let path_str = file.path().to_string_lossy().to_string();
                                         this.update(cx, |view, cx| {
                                             // 保存文件路径
@@ -1227,8 +1316,11 @@ fn render_add_channel_input_row_with_path(
 
                                             // Auto-fill channel name from filename if empty
                                             if view.new_channel_name.is_empty() {
-                                                if let Some(stem) = std::path::Path::new(&path_str).file_stem() {
-                                                    view.new_channel_name = stem.to_string_lossy().to_string();
+                                                if let Some(stem) =
+                                                    std::path::Path::new(&path_str).file_stem()
+                                                {
+                                                    view.new_channel_name =
+                                                        stem.to_string_lossy().to_string();
                                                 }
                                             }
 
@@ -1324,9 +1416,11 @@ fn render_add_version_input_row(
         .on_key_down(cx.listener(|this, event: &KeyDownEvent, _window, cx| {
             if event.keystroke.key == "escape" {
                 // Close the input without saving
-                this.show_version_input = false;
-                this.version_name_input = None;
-                cx.notify();
+                this.library.update(cx, |library, cx| {
+                    library.show_version_input = false;
+                    library.version_name_input = None;
+                    cx.notify();
+                });
             }
         }))
         .child(
@@ -1369,16 +1463,33 @@ fn render_add_version_button(cx: &mut Context<crate::CanViewApp>) -> impl IntoEl
             gpui::MouseButton::Left,
             cx.listener(|this, _event, window, cx| {
                 // Initialize input state when showing input
-                if this.version_name_input.is_none() {
+                if this.library.read(cx).version_name_input.is_none() {
                     let input = cx.new(|cx| {
                         InputState::new(window, cx)
                             .placeholder("Enter version name (e.g., v1.0)...")
                     });
 
-                    this.version_name_input = Some(input);
+                    // Enter submits once the IME has committed its composition
+                    cx.subscribe(
+                        &input,
+                        |this: &mut crate::CanViewApp,
+                         _input_entity,
+                         event: &gpui_component::input::InputEvent,
+                         cx| {
+                            if let gpui_component::input::InputEvent::PressEnter { .. } = event {
+                                this.add_library_version(cx);
+                            }
+                        },
+                    )
+                    .detach();
+
+                    this.library
+                        .update(cx, |library, _| library.version_name_input = Some(input));
                 }
-                this.show_version_input = true;
-                cx.notify();
+                this.library.update(cx, |library, cx| {
+                    library.show_version_input = true;
+                    cx.notify();
+                });
             }),
         )
         .child(
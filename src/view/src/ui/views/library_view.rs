@@ -103,8 +103,10 @@ pub fn render_library_list(
                 .on_mouse_down(
                     gpui::MouseButton::Left,
                     cx.listener(move |this, _event, _window, cx| {
-                        this.selected_library_id = Some(lib_id.clone());
-                        cx.notify();
+                        this.library.update(cx, |library, cx| {
+                            library.selected_library_id = Some(lib_id.clone());
+                            cx.notify();
+                        });
                     }),
                 ),
         );
@@ -429,8 +431,10 @@ pub fn render_library_dialog(
                             .on_mouse_down(
                                 gpui::MouseButton::Left,
                                 cx.listener(|this, _event, _window, cx| {
-                                    this.show_library_dialog = false;
-                                    cx.notify();
+                                    this.library.update(cx, |library, cx| {
+                                        library.show_library_dialog = false;
+                                        cx.notify();
+                                    });
                                 }),
                             ),
                     ),
@@ -1,8 +1,7 @@
 //! View implementations
 
 pub mod library_management;
-pub mod library_management_enhanced;
-pub mod library_view; // New version with EnhancedTextInput
+pub mod library_view;
 
 // TODO: Implement these views
 // pub mod log_view;
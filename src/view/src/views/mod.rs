@@ -2,8 +2,6 @@
 //!
 //! This module contains view rendering functions for different application views.
 
-pub mod chart_view;
 pub mod config_view;
 
-pub use chart_view::*;
 pub use config_view::*;